@@ -44,6 +44,7 @@
 //! ```
 
 use crate::{CloudClient, Result};
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::collections::HashMap;
@@ -70,6 +71,10 @@ pub struct ModulesData {
 /// RootAccount
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RootAccount {
+    /// The account and the API key making the request
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub account: Option<AccountDetails>,
+
     /// HATEOAS links
     #[serde(skip_serializing_if = "Option::is_none")]
     pub links: Option<Vec<HashMap<String, Value>>>,
@@ -79,6 +84,30 @@ pub struct RootAccount {
     pub extra: Value,
 }
 
+/// Account details returned by `GET /`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountDetails {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<i64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_timestamp: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub updated_timestamp: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub marketplace_status: Option<String>,
+
+    /// Additional fields from the API
+    #[serde(flatten)]
+    pub extra: Value,
+}
+
 /// Account system log entry
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -145,6 +174,9 @@ pub struct PaymentMethods {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub account_id: Option<i32>,
 
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub payment_methods: Option<Vec<PaymentMethod>>,
+
     /// HATEOAS links
     #[serde(skip_serializing_if = "Option::is_none")]
     pub links: Option<Vec<HashMap<String, Value>>>,
@@ -154,6 +186,46 @@ pub struct PaymentMethods {
     pub extra: Value,
 }
 
+impl PaymentMethods {
+    /// The payment method with the given ID, if it belongs to the account
+    pub fn find(&self, id: i32) -> Option<&PaymentMethod> {
+        self.payment_methods
+            .as_deref()
+            .unwrap_or_default()
+            .iter()
+            .find(|method| method.id == Some(id))
+    }
+}
+
+/// A payment method of the account, such as a credit card
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PaymentMethod {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<i32>,
+
+    /// Card brand or payment type, e.g. "Visa"
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub payment_type: Option<String>,
+
+    /// Last four digits of the card number
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub credit_card_ends_with: Option<i32>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name_on_card: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expiration_month: Option<i32>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expiration_year: Option<i32>,
+
+    /// Additional fields from the API
+    #[serde(flatten)]
+    pub extra: Value,
+}
+
 /// RedisLabs database module information
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -348,6 +420,44 @@ impl AccountHandler {
         self.client.get(&format!("/logs{}", query_string)).await
     }
 
+    /// Get all system logs
+    /// Fetches pages of system logs, `page_size` entries at a time, until an empty page is returned.
+    /// With `since`, paging also stops after a newest-first page that reaches back past it.
+    ///
+    /// GET /logs
+    pub async fn get_all_account_system_logs(
+        &self,
+        page_size: i32,
+        since: Option<DateTime<Utc>>,
+    ) -> Result<Vec<AccountSystemLogEntry>> {
+        let page_size = page_size.max(1);
+        let mut all = Vec::new();
+        let mut offset = 0;
+        loop {
+            let page = self
+                .get_account_system_logs(Some(offset), Some(page_size))
+                .await?
+                .entries
+                .unwrap_or_default();
+            if page.is_empty() {
+                return Ok(all);
+            }
+            let count = page.len() as i32;
+            let past_since = since.is_some_and(|since| {
+                reached_since(
+                    page.first().and_then(|e| e.time.as_deref()),
+                    page.last().and_then(|e| e.time.as_deref()),
+                    since,
+                )
+            });
+            all.extend(page);
+            if past_since {
+                return Ok(all);
+            }
+            offset += count;
+        }
+    }
+
     /// Get payment methods
     /// Gets a list of all payment methods for this account.
     ///
@@ -406,4 +516,53 @@ impl AccountHandler {
             .get(&format!("/session-logs{}", query_string))
             .await
     }
+
+    /// Get all session logs
+    /// Fetches pages of session logs, `page_size` entries at a time, until an empty page is returned.
+    /// With `since`, paging also stops after a newest-first page that reaches back past it.
+    ///
+    /// GET /session-logs
+    pub async fn get_all_account_session_logs(
+        &self,
+        page_size: i32,
+        since: Option<DateTime<Utc>>,
+    ) -> Result<Vec<AccountSessionLogEntry>> {
+        let page_size = page_size.max(1);
+        let mut all = Vec::new();
+        let mut offset = 0;
+        loop {
+            let page = self
+                .get_account_session_logs(Some(offset), Some(page_size))
+                .await?
+                .entries
+                .unwrap_or_default();
+            if page.is_empty() {
+                return Ok(all);
+            }
+            let count = page.len() as i32;
+            let past_since = since.is_some_and(|since| {
+                reached_since(
+                    page.first().and_then(|e| e.time.as_deref()),
+                    page.last().and_then(|e| e.time.as_deref()),
+                    since,
+                )
+            });
+            all.extend(page);
+            if past_since {
+                return Ok(all);
+            }
+            offset += count;
+        }
+    }
+}
+
+/// Whether paging can stop: the page is newest first and its last entry is older than `since`
+///
+/// A page in ascending order means the API lists oldest first, so every page must be read.
+fn reached_since(first: Option<&str>, last: Option<&str>, since: DateTime<Utc>) -> bool {
+    let parse = |t: Option<&str>| t.and_then(|t| DateTime::parse_from_rfc3339(t).ok());
+    let (Some(first), Some(last)) = (parse(first), parse(last)) else {
+        return false;
+    };
+    first >= last && last < since
 }
@@ -40,7 +40,7 @@
 //! # }
 //! ```
 
-use crate::{CloudClient, Result};
+use crate::{CloudClient, CloudError, Result};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::collections::HashMap;
@@ -439,6 +439,144 @@ impl AclHandler {
             .await
     }
 
+    /// Attach a database access role to a database
+    /// Adds the database to the role's `rule_name` Redis rule, creating the rule entry if the
+    /// role does not use it yet, and submits the role update.
+    ///
+    /// GET /acl/roles, PUT /acl/roles/{aclRoleId}
+    pub async fn attach_role_to_database(
+        &self,
+        role_name: &str,
+        rule_name: &str,
+        subscription_id: i32,
+        database_id: i32,
+        regions: Option<Vec<String>>,
+    ) -> Result<TaskStateUpdate> {
+        let (role_id, mut rules) = self.find_role_rules(role_name).await?;
+
+        let database = AclRoleDatabaseSpec {
+            subscription_id,
+            database_id,
+            regions,
+            extra: Value::Object(serde_json::Map::new()),
+        };
+        match rules.iter_mut().find(|r| r.rule_name == rule_name) {
+            Some(rule) => {
+                rule.databases.retain(|db| {
+                    !(db.subscription_id == subscription_id && db.database_id == database_id)
+                });
+                rule.databases.push(database);
+            }
+            None => rules.push(AclRoleRedisRuleSpec {
+                rule_name: rule_name.to_string(),
+                databases: vec![database],
+                extra: Value::Object(serde_json::Map::new()),
+            }),
+        }
+
+        self.update_role_rules(role_id, role_name, rules).await
+    }
+
+    /// Detach a database access role from a database
+    /// Removes the database from every Redis rule of the role and submits the role update.
+    /// Rules left without databases are dropped from the role.
+    ///
+    /// GET /acl/roles, PUT /acl/roles/{aclRoleId}
+    pub async fn detach_role_from_database(
+        &self,
+        role_name: &str,
+        subscription_id: i32,
+        database_id: i32,
+    ) -> Result<TaskStateUpdate> {
+        let (role_id, mut rules) = self.find_role_rules(role_name).await?;
+
+        let before: usize = rules.iter().map(|r| r.databases.len()).sum();
+        for rule in &mut rules {
+            rule.databases.retain(|db| {
+                !(db.subscription_id == subscription_id && db.database_id == database_id)
+            });
+        }
+        let after: usize = rules.iter().map(|r| r.databases.len()).sum();
+        if before == after {
+            return Err(CloudError::NotFound {
+                message: format!(
+                    "ACL role '{}' is not attached to database {}:{}",
+                    role_name, subscription_id, database_id
+                ),
+            });
+        }
+        rules.retain(|r| !r.databases.is_empty());
+
+        self.update_role_rules(role_id, role_name, rules).await
+    }
+
+    /// Look up a role by name, returning its ID and Redis rule assignments
+    ///
+    /// Read-only fields returned by the API (rule IDs, database names) are dropped so the
+    /// rules can be sent back in an update request.
+    async fn find_role_rules(&self, role_name: &str) -> Result<(i32, Vec<AclRoleRedisRuleSpec>)> {
+        let roles = self.get_roles().await?;
+        let (index, role) = roles
+            .extra
+            .get("roles")
+            .and_then(|r| r.as_array())
+            .and_then(|roles| {
+                roles
+                    .iter()
+                    .enumerate()
+                    .find(|(_, r)| r.get("name").and_then(|n| n.as_str()) == Some(role_name))
+            })
+            .ok_or_else(|| CloudError::NotFound {
+                message: format!("ACL role '{}' not found", role_name),
+            })?;
+
+        let bad_id = |message: String| CloudError::DeserializationError {
+            path: format!("roles[{}].id", index),
+            message,
+            raw: Box::new(role.clone()),
+        };
+        let role_id = role
+            .get("id")
+            .and_then(|id| id.as_i64())
+            .ok_or_else(|| bad_id(format!("ACL role '{}' has no ID", role_name)))?;
+        let role_id = i32::try_from(role_id).map_err(|_| {
+            bad_id(format!(
+                "ACL role '{}' has ID {}, which is out of range",
+                role_name, role_id
+            ))
+        })?;
+
+        let mut rules: Vec<AclRoleRedisRuleSpec> = serde_json::from_value(
+            role.get("redisRules")
+                .cloned()
+                .unwrap_or(Value::Array(vec![])),
+        )?;
+        for rule in &mut rules {
+            rule.extra = Value::Object(serde_json::Map::new());
+            for db in &mut rule.databases {
+                db.extra = Value::Object(serde_json::Map::new());
+            }
+        }
+
+        Ok((role_id, rules))
+    }
+
+    async fn update_role_rules(
+        &self,
+        role_id: i32,
+        role_name: &str,
+        rules: Vec<AclRoleRedisRuleSpec>,
+    ) -> Result<TaskStateUpdate> {
+        let request = AclRoleUpdateRequest {
+            name: Some(role_name.to_string()),
+            redis_rules: Some(rules),
+            role_id: None,
+            command_type: None,
+            extra: Value::Object(serde_json::Map::new()),
+        };
+        self.update_role(role_id, &request).await
+    }
+
     /// Get access control users
     /// Gets a list of all access control users for this account.
     ///
@@ -7,9 +7,69 @@
 //! both typed and untyped API interactions.
 
 use crate::{CloudError as RestError, Result};
-use reqwest::Client;
+use reqwest::{Client, Response};
 use serde::Serialize;
+use serde::de::DeserializeOwned;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::Semaphore;
+use tracing::warn;
+
+/// Default limit for response bodies read into memory (128 MiB)
+pub const DEFAULT_MAX_BODY_SIZE: usize = 128 * 1024 * 1024;
+
+/// Default limit for requests in flight at once, shared by all clones of a client
+pub const DEFAULT_MAX_CONCURRENT_REQUESTS: usize = 8;
+
+/// One HTTP request made by the client, as reported to a [`RequestObserver`]
+#[derive(Debug, Clone)]
+pub struct RequestEvent {
+    pub method: &'static str,
+    /// Path relative to the base URL, including any query string
+    pub path: String,
+    /// Response status, or `None` when no response arrived
+    pub status: Option<u16>,
+    /// Time until the response headers arrived
+    pub elapsed: Duration,
+    /// Rate-limit state from the response headers, when the API sent any
+    pub rate_limit: Option<RateLimit>,
+}
+
+/// Rate-limit state reported in the `X-RateLimit-*` response headers
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct RateLimit {
+    /// Requests allowed per window
+    pub limit: Option<u64>,
+    /// Requests left in the current window
+    pub remaining: Option<u64>,
+    /// Seconds until the window resets
+    pub reset: Option<u64>,
+}
+
+impl RateLimit {
+    /// Read the rate-limit headers, or `None` when the response carries none of them
+    pub fn from_headers(headers: &reqwest::header::HeaderMap) -> Option<Self> {
+        let header = |name: &str| {
+            headers
+                .get(name)
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.trim().parse().ok())
+        };
+        let rate_limit = Self {
+            limit: header("x-ratelimit-limit"),
+            remaining: header("x-ratelimit-remaining"),
+            reset: header("x-ratelimit-reset"),
+        };
+        (rate_limit != Self::default()).then_some(rate_limit)
+    }
+}
+
+/// Hook notified after every request, for metrics such as request counts and latency
+///
+/// Set it with [`CloudClientBuilder::observer`].
+pub trait RequestObserver: Send + Sync + std::fmt::Debug {
+    fn on_request(&self, event: &RequestEvent);
+}
 
 /// Builder for constructing a CloudClient with custom configuration
 ///
@@ -33,6 +93,7 @@ use std::sync::Arc;
 ///     .api_secret("your-api-secret")
 ///     .base_url("https://api.redislabs.com/v1".to_string())
 ///     .timeout(std::time::Duration::from_secs(120))
+///     .max_body_size(16 * 1024 * 1024)
 ///     .build()?;
 /// # Ok::<(), Box<dyn std::error::Error>>(())
 /// ```
@@ -42,6 +103,11 @@ pub struct CloudClientBuilder {
     api_secret: Option<String>,
     base_url: String,
     timeout: std::time::Duration,
+    max_body_size: usize,
+    observer: Option<Arc<dyn RequestObserver>>,
+    read_only: bool,
+    max_concurrent_requests: usize,
+    limiter: Option<Arc<Semaphore>>,
 }
 
 impl Default for CloudClientBuilder {
@@ -51,6 +117,11 @@ impl Default for CloudClientBuilder {
             api_secret: None,
             base_url: "https://api.redislabs.com/v1".to_string(),
             timeout: std::time::Duration::from_secs(30),
+            max_body_size: DEFAULT_MAX_BODY_SIZE,
+            observer: None,
+            read_only: false,
+            max_concurrent_requests: DEFAULT_MAX_CONCURRENT_REQUESTS,
+            limiter: None,
         }
     }
 }
@@ -85,6 +156,50 @@ impl CloudClientBuilder {
         self
     }
 
+    /// Set the maximum response body size in bytes
+    ///
+    /// Responses larger than this fail with [`CloudError::ResponseTooLarge`](crate::CloudError::ResponseTooLarge)
+    /// instead of being buffered. Defaults to [`DEFAULT_MAX_BODY_SIZE`].
+    pub fn max_body_size(mut self, bytes: usize) -> Self {
+        self.max_body_size = bytes;
+        self
+    }
+
+    /// Report every request to `observer`
+    pub fn observer(mut self, observer: Arc<dyn RequestObserver>) -> Self {
+        self.observer = Some(observer);
+        self
+    }
+
+    /// Refuse POST, PUT, PATCH and DELETE requests
+    ///
+    /// They fail with [`CloudError::ReadOnly`](crate::CloudError::ReadOnly) without being sent.
+    pub fn read_only(mut self, read_only: bool) -> Self {
+        self.read_only = read_only;
+        self
+    }
+
+    /// Limit how many requests are in flight at once
+    ///
+    /// Further requests wait for a slot, so code that fans out over many resources cannot flood
+    /// the API. The limit is shared by all clones of the client and counts a request until its
+    /// response headers arrive, but separately built clients each get their own; use
+    /// [`request_limiter`](Self::request_limiter) to share one limit between them. Defaults to
+    /// [`DEFAULT_MAX_CONCURRENT_REQUESTS`]; 0 is treated as 1.
+    pub fn max_concurrent_requests(mut self, limit: usize) -> Self {
+        self.max_concurrent_requests = limit.max(1);
+        self
+    }
+
+    /// Take request slots from `limiter` instead of a semaphore of the client's own
+    ///
+    /// Clients built with the same limiter count against one limit, whichever API they call.
+    /// Overrides [`max_concurrent_requests`](Self::max_concurrent_requests).
+    pub fn request_limiter(mut self, limiter: Arc<Semaphore>) -> Self {
+        self.limiter = Some(limiter);
+        self
+    }
+
     /// Build the client
     pub fn build(self) -> Result<CloudClient> {
         let api_key = self
@@ -104,6 +219,12 @@ impl CloudClientBuilder {
             api_secret,
             base_url: self.base_url,
             timeout: self.timeout,
+            max_body_size: self.max_body_size,
+            observer: self.observer,
+            read_only: self.read_only,
+            limiter: self
+                .limiter
+                .unwrap_or_else(|| Arc::new(Semaphore::new(self.max_concurrent_requests))),
             client: Arc::new(client),
         })
     }
@@ -117,6 +238,10 @@ pub struct CloudClient {
     pub(crate) base_url: String,
     #[allow(dead_code)]
     pub(crate) timeout: std::time::Duration,
+    pub(crate) max_body_size: usize,
+    pub(crate) observer: Option<Arc<dyn RequestObserver>>,
+    pub(crate) read_only: bool,
+    pub(crate) limiter: Arc<Semaphore>,
     pub(crate) client: Arc<Client>,
 }
 
@@ -126,18 +251,22 @@ impl CloudClient {
         CloudClientBuilder::new()
     }
 
+    /// Base URL requests are sent to
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
     /// Make a GET request with API key authentication
     pub async fn get<T: serde::de::DeserializeOwned>(&self, path: &str) -> Result<T> {
         let url = format!("{}{}", self.base_url, path);
 
         // Redis Cloud API uses these headers for authentication
-        let response = self
+        let request = self
             .client
             .get(&url)
             .header("x-api-key", &self.api_key)
-            .header("x-api-secret-key", &self.api_secret)
-            .send()
-            .await?;
+            .header("x-api-secret-key", &self.api_secret);
+        let response = self.send("GET", path, request).await?;
 
         self.handle_response(response).await
     }
@@ -148,17 +277,17 @@ impl CloudClient {
         path: &str,
         body: &B,
     ) -> Result<T> {
+        self.ensure_writable("POST", path)?;
         let url = format!("{}{}", self.base_url, path);
 
         // Same backwards header naming as GET
-        let response = self
+        let request = self
             .client
             .post(&url)
             .header("x-api-key", &self.api_key)
             .header("x-api-secret-key", &self.api_secret)
-            .json(body)
-            .send()
-            .await?;
+            .json(body);
+        let response = self.send("POST", path, request).await?;
 
         self.handle_response(response).await
     }
@@ -169,33 +298,33 @@ impl CloudClient {
         path: &str,
         body: &B,
     ) -> Result<T> {
+        self.ensure_writable("PUT", path)?;
         let url = format!("{}{}", self.base_url, path);
 
         // Same backwards header naming as GET
-        let response = self
+        let request = self
             .client
             .put(&url)
             .header("x-api-key", &self.api_key)
             .header("x-api-secret-key", &self.api_secret)
-            .json(body)
-            .send()
-            .await?;
+            .json(body);
+        let response = self.send("PUT", path, request).await?;
 
         self.handle_response(response).await
     }
 
     /// Make a DELETE request
     pub async fn delete(&self, path: &str) -> Result<()> {
+        self.ensure_writable("DELETE", path)?;
         let url = format!("{}{}", self.base_url, path);
 
         // Same backwards header naming as GET
-        let response = self
+        let request = self
             .client
             .delete(&url)
             .header("x-api-key", &self.api_key)
-            .header("x-api-secret-key", &self.api_secret)
-            .send()
-            .await?;
+            .header("x-api-secret-key", &self.api_secret);
+        let response = self.send("DELETE", path, request).await?;
 
         if response.status().is_success() {
             Ok(())
@@ -240,39 +369,40 @@ impl CloudClient {
         path: &str,
         body: serde_json::Value,
     ) -> Result<serde_json::Value> {
+        self.ensure_writable("PATCH", path)?;
         let url = format!("{}{}", self.base_url, path);
 
         // Use backwards header names for compatibility
-        let response = self
+        let request = self
             .client
             .patch(&url)
             .header("x-api-key", &self.api_key)
             .header("x-api-secret-key", &self.api_secret)
-            .json(&body)
-            .send()
-            .await?;
+            .json(&body);
+        let response = self.send("PATCH", path, request).await?;
 
         self.handle_response(response).await
     }
 
     /// Execute raw DELETE request returning any response body
     pub async fn delete_raw(&self, path: &str) -> Result<serde_json::Value> {
+        self.ensure_writable("DELETE", path)?;
         let url = format!("{}{}", self.base_url, path);
 
         // Use backwards header names for compatibility
-        let response = self
+        let request = self
             .client
             .delete(&url)
             .header("x-api-key", &self.api_key)
-            .header("x-api-secret-key", &self.api_secret)
-            .send()
-            .await?;
+            .header("x-api-secret-key", &self.api_secret);
+        let response = self.send("DELETE", path, request).await?;
 
         if response.status().is_success() {
             if response.content_length() == Some(0) {
                 Ok(serde_json::json!({"status": "deleted"}))
             } else {
-                response.json().await.map_err(Into::into)
+                let body = self.read_body(response).await?;
+                serde_json::from_slice(&body).map_err(Into::into)
             }
         } else {
             let status = response.status();
@@ -294,6 +424,43 @@ impl CloudClient {
         }
     }
 
+    /// Fail with [`CloudError::ReadOnly`](crate::CloudError::ReadOnly) on a read-only client
+    fn ensure_writable(&self, method: &str, path: &str) -> Result<()> {
+        if self.read_only {
+            return Err(RestError::ReadOnly {
+                method: method.to_string(),
+                path: path.to_string(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Send a request, reporting it to the observer when one is set
+    async fn send(
+        &self,
+        method: &'static str,
+        path: &str,
+        request: reqwest::RequestBuilder,
+    ) -> std::result::Result<Response, reqwest::Error> {
+        // The semaphore is never closed, so acquiring only waits for a free slot
+        let _permit = self.limiter.acquire().await.ok();
+        let start = Instant::now();
+        let response = request.send().await;
+        if let Some(observer) = &self.observer {
+            observer.on_request(&RequestEvent {
+                method,
+                path: path.to_string(),
+                status: response.as_ref().ok().map(|r| r.status().as_u16()),
+                elapsed: start.elapsed(),
+                rate_limit: response
+                    .as_ref()
+                    .ok()
+                    .and_then(|r| RateLimit::from_headers(r.headers())),
+            });
+        }
+        response
+    }
+
     /// Handle HTTP response
     async fn handle_response<T: serde::de::DeserializeOwned>(
         &self,
@@ -302,16 +469,9 @@ impl CloudClient {
         let status = response.status();
 
         if status.is_success() {
-            // Try to get the response text first for debugging
-            let text = response.text().await.map_err(|e| {
-                RestError::ConnectionError(format!("Failed to read response: {}", e))
-            })?;
-
-            // Try to parse as JSON
-            serde_json::from_str::<T>(&text).map_err(|e| {
-                // If parsing fails, include the actual response for debugging
-                RestError::JsonError(e)
-            })
+            let body = self.read_body(response).await?;
+
+            deserialize_response(&body)
         } else {
             let text = response.text().await.unwrap_or_default();
 
@@ -330,4 +490,62 @@ impl CloudClient {
             }
         }
     }
+
+    /// Read a response body chunk by chunk, failing once it exceeds the configured limit
+    async fn read_body(&self, mut response: reqwest::Response) -> Result<Vec<u8>> {
+        let too_large = || RestError::ResponseTooLarge {
+            limit: self.max_body_size,
+        };
+        if response
+            .content_length()
+            .is_some_and(|len| len > self.max_body_size as u64)
+        {
+            return Err(too_large());
+        }
+
+        let mut body = Vec::new();
+        while let Some(chunk) = response
+            .chunk()
+            .await
+            .map_err(|e| RestError::ConnectionError(format!("Failed to read response: {}", e)))?
+        {
+            if body.len() + chunk.len() > self.max_body_size {
+                return Err(too_large());
+            }
+            body.extend_from_slice(&chunk);
+        }
+        Ok(body)
+    }
+}
+
+/// Deserialize a response body, reporting the path of the field that did not match `T`
+///
+/// Bodies that are not JSON at all fail with the plain serde error; bodies that are valid JSON
+/// but do not fit the type keep the raw value so callers can still show it.
+fn deserialize_response<T: DeserializeOwned>(body: &[u8]) -> Result<T> {
+    let mut deserializer = serde_json::Deserializer::from_slice(body);
+    match serde_path_to_error::deserialize::<_, T>(&mut deserializer) {
+        Ok(value) => {
+            deserializer.end()?;
+            Ok(value)
+        }
+        Err(err) => {
+            let Ok(raw) = serde_json::from_slice::<serde_json::Value>(body) else {
+                return Err(err.into_inner().into());
+            };
+            let path = err.path().to_string();
+            let message = err.inner().to_string();
+            warn!(
+                "Response did not match {} at '{}': {}",
+                std::any::type_name::<T>(),
+                path,
+                message
+            );
+            Err(RestError::DeserializationError {
+                path,
+                message,
+                raw: Box::new(raw),
+            })
+        }
+    }
 }
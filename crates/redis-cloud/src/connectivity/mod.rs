@@ -147,6 +147,17 @@ impl ConnectivityHandler {
             .await
     }
 
+    pub async fn create_tgw_attachment_with_cidrs(
+        &self,
+        subscription_id: i32,
+        tgw_id: &str,
+        cidrs: Vec<String>,
+    ) -> crate::Result<crate::types::TaskStateUpdate> {
+        self.transit_gateway
+            .create_attachment_with_cidrs(subscription_id, tgw_id, cidrs)
+            .await
+    }
+
     pub async fn delete_tgw_attachment(
         &self,
         subscription_id: i32,
@@ -149,11 +149,23 @@ impl TransitGatewayHandler {
         subscription_id: i32,
         tgw_id: &str,
     ) -> Result<TaskStateUpdate> {
-        // Create an empty request body as the API might expect one
+        self.create_attachment_with_cidrs(subscription_id, tgw_id, Vec::new())
+            .await
+    }
+
+    /// Create Transit Gateway attachment with tgw_id in path and initial CIDRs
+    ///
+    /// An empty `cidrs` list omits the field so the API applies its defaults.
+    pub async fn create_attachment_with_cidrs(
+        &self,
+        subscription_id: i32,
+        tgw_id: &str,
+        cidrs: Vec<String>,
+    ) -> Result<TaskStateUpdate> {
         let request = TgwAttachmentRequest {
             tgw_id: Some(tgw_id.to_string()),
             aws_account_id: None,
-            cidrs: None,
+            cidrs: if cidrs.is_empty() { None } else { Some(cidrs) },
             extra: serde_json::Value::Object(serde_json::Map::new()),
         };
 
@@ -82,6 +82,18 @@ pub struct FixedSubscriptionsPlans {
     pub extra: Value,
 }
 
+impl FixedSubscriptionsPlans {
+    /// The plans in the response as typed models
+    ///
+    /// The API returns them under `plans`; a response without that key has no plans.
+    pub fn plans(&self) -> serde_json::Result<Vec<FixedSubscriptionsPlan>> {
+        match self.extra.get("plans") {
+            Some(plans) => serde_json::from_value(plans.clone()),
+            None => Ok(Vec::new()),
+        }
+    }
+}
+
 /// ProcessorResponse
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -222,6 +234,27 @@ pub struct FixedSubscriptionsPlan {
     pub extra: Value,
 }
 
+impl FixedSubscriptionsPlan {
+    /// Dataset size in bytes, from `datasetSize` (or `size`) and `sizeMeasurementUnit`
+    pub fn dataset_size_bytes(&self) -> Option<u64> {
+        let size = self.dataset_size.or(self.size)?;
+        let unit: u64 = match self
+            .size_measurement_unit
+            .as_deref()
+            .unwrap_or("MB")
+            .to_ascii_uppercase()
+            .as_str()
+        {
+            "KB" => 1 << 10,
+            "MB" => 1 << 20,
+            "GB" => 1 << 30,
+            "TB" => 1 << 40,
+            _ => return None,
+        };
+        Some((size * unit as f64) as u64)
+    }
+}
+
 /// Essentials subscription create request
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
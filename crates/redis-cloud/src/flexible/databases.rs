@@ -186,7 +186,12 @@ pub struct CrdbFlushRequest {
 #[serde(rename_all = "camelCase")]
 pub struct DatabaseCertificate {
     /// An X.509 PEM (base64) encoded server certificate with new line characters replaced by '\n'.
-    #[serde(skip_serializing_if = "Option::is_none")]
+    /// May hold the whole chain as concatenated PEM blocks.
+    #[serde(
+        rename = "publicCertificatePEMString",
+        alias = "publicCertificatePemString",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub public_certificate_pem_string: Option<String>,
 
     /// Additional fields from the API
@@ -235,6 +240,18 @@ pub struct DatabaseSyncSourceSpec {
     pub extra: Value,
 }
 
+impl DatabaseSyncSourceSpec {
+    /// A sync source at a `redis://` or `rediss://` URI
+    pub fn new(endpoint: impl Into<String>) -> Self {
+        Self {
+            endpoint: endpoint.into(),
+            encryption: None,
+            server_cert: None,
+            extra: Value::Object(Default::default()),
+        }
+    }
+}
+
 /// Optional. A list of client TLS/SSL certificates. If specified, mTLS authentication will be required to authenticate user connections. If set to an empty list, TLS client certificates will be removed and mTLS will not be required. TLS connection may still apply, depending on the value of 'enableTls'.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -430,6 +447,51 @@ pub struct DatabaseThroughputSpec {
     pub extra: Value,
 }
 
+impl DatabaseThroughputSpec {
+    /// Create a throughput spec for the given measurement method
+    pub fn new(by: ThroughputMeasurement, value: i64) -> Self {
+        Self {
+            by: by.as_str().to_string(),
+            value,
+            extra: Value::Object(Default::default()),
+        }
+    }
+
+    /// Throughput in operations per second, the method for all new databases
+    pub fn operations_per_second(value: i64) -> Self {
+        Self::new(ThroughputMeasurement::OperationsPerSecond, value)
+    }
+
+    /// The measurement method, if it is one this crate knows
+    pub fn measurement(&self) -> Option<ThroughputMeasurement> {
+        match self.by.as_str() {
+            "operations-per-second" => Some(ThroughputMeasurement::OperationsPerSecond),
+            "number-of-shards" => Some(ThroughputMeasurement::NumberOfShards),
+            _ => None,
+        }
+    }
+}
+
+/// Throughput measurement method of a Pro database
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ThroughputMeasurement {
+    /// Read and write operations per second
+    OperationsPerSecond,
+    /// Number of shards; only kept by older databases
+    NumberOfShards,
+}
+
+impl ThroughputMeasurement {
+    /// The value the API uses for this method
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ThroughputMeasurement::OperationsPerSecond => "operations-per-second",
+            ThroughputMeasurement::NumberOfShards => "number-of-shards",
+        }
+    }
+}
+
 /// Optional. Changes Remote backup configuration details.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -446,10 +508,18 @@ pub struct DatabaseBackupConfig {
     pub backup_interval: Option<String>,
 
     /// Optional. Hour when the backup starts. Available only for "every-12-hours" and "every-24-hours" backup intervals. Specified as an hour in 24-hour UTC time. Example: "14:00" is 2 PM UTC.
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "timeUTC",
+        alias = "timeUtc",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub time_utc: Option<String>,
 
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "databaseBackupTimeUTC",
+        alias = "databaseBackupTimeUtc",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub database_backup_time_utc: Option<String>,
 
     /// Required when active is 'true'. Type of storage to host backup files. Can be "aws-s3", "google-blob-storage", "azure-blob-storage", or "ftp". See [Set up backup storage locations](https://redis.io/docs/latest/operate/rc/databases/back-up-data/#set-up-backup-storage-locations) to learn how to set up backup storage locations.
@@ -468,6 +538,38 @@ pub struct DatabaseBackupConfig {
     pub extra: Value,
 }
 
+impl DatabaseBackupConfig {
+    /// Periodic backups every `interval` (e.g. "every-12-hours") to a storage location
+    pub fn enabled(
+        interval: impl Into<String>,
+        storage_type: impl Into<String>,
+        storage_path: impl Into<String>,
+    ) -> Self {
+        Self {
+            active: Some(true),
+            interval: Some(interval.into()),
+            storage_type: Some(storage_type.into()),
+            storage_path: Some(storage_path.into()),
+            ..Self::disabled()
+        }
+    }
+
+    /// No periodic backups
+    pub fn disabled() -> Self {
+        Self {
+            active: Some(false),
+            interval: None,
+            backup_interval: None,
+            time_utc: None,
+            database_backup_time_utc: None,
+            storage_type: None,
+            backup_storage_type: None,
+            storage_path: None,
+            extra: Value::Object(Default::default()),
+        }
+    }
+}
+
 /// Optional. Redis advanced capabilities (also known as modules) to be provisioned in the database. Use GET /database-modules to get a list of available advanced capabilities.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DatabaseModuleSpec {
@@ -483,6 +585,17 @@ pub struct DatabaseModuleSpec {
     pub extra: Value,
 }
 
+impl DatabaseModuleSpec {
+    /// A capability by its API name, such as `RedisJSON`, without parameters
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            parameters: None,
+            extra: Value::Object(Default::default()),
+        }
+    }
+}
+
 /// Optional. Changes Replica Of (also known as Active-Passive) configuration details.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -495,6 +608,16 @@ pub struct ReplicaOfSpec {
     pub extra: Value,
 }
 
+impl ReplicaOfSpec {
+    /// Replicate from the given sources; no sources stops replication
+    pub fn new(sync_sources: Vec<DatabaseSyncSourceSpec>) -> Self {
+        Self {
+            sync_sources,
+            extra: Value::Object(Default::default()),
+        }
+    }
+}
+
 /// Database backup request message
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -537,6 +660,86 @@ pub struct Database {
     pub extra: Value,
 }
 
+impl Database {
+    /// The Replica Of (Active-Passive) configuration, if the database replicates from a source
+    ///
+    /// Reads `replica.syncSources`, falling back to the older `replicaOf` list of URIs.
+    pub fn replica_of(&self) -> Option<ReplicaOfSpec> {
+        if let Some(replica) = self
+            .extra
+            .get("replica")
+            .and_then(|r| serde_json::from_value::<ReplicaOfSpec>(r.clone()).ok())
+            .filter(|r| !r.sync_sources.is_empty())
+        {
+            return Some(replica);
+        }
+        let endpoints = self.extra.get("replicaOf").and_then(|r| {
+            r.as_array()
+                .or_else(|| r.get("endpoints").and_then(Value::as_array))
+        })?;
+        let sources: Vec<DatabaseSyncSourceSpec> = endpoints
+            .iter()
+            .filter_map(Value::as_str)
+            .map(DatabaseSyncSourceSpec::new)
+            .collect();
+        (!sources.is_empty()).then(|| ReplicaOfSpec::new(sources))
+    }
+
+    /// The advanced capabilities (modules) the database runs
+    ///
+    /// Database responses list them under `modules` with their ID, version and parameters, which
+    /// are kept in `extra`.
+    pub fn modules(&self) -> Vec<DatabaseModuleSpec> {
+        self.extra
+            .get("modules")
+            .and_then(Value::as_array)
+            .map(|modules| {
+                modules
+                    .iter()
+                    .filter_map(|m| serde_json::from_value(m.clone()).ok())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// The query performance factor of a search database, such as `4x`
+    pub fn query_performance_factor(&self) -> Option<&str> {
+        self.extra
+            .get("queryPerformanceFactor")
+            .and_then(Value::as_str)
+    }
+
+    /// The periodic backup configuration, if the API reports one
+    ///
+    /// Reads `remoteBackup`, falling back to the `backup` object of database responses, whose
+    /// fields are named differently (`enableRemoteBackup`, `destination`).
+    pub fn remote_backup(&self) -> Option<DatabaseBackupConfig> {
+        if let Some(config) = self
+            .extra
+            .get("remoteBackup")
+            .and_then(|b| serde_json::from_value::<DatabaseBackupConfig>(b.clone()).ok())
+        {
+            return Some(config);
+        }
+        let backup = self.extra.get("backup")?.as_object()?;
+        let text = |keys: &[&str]| {
+            keys.iter()
+                .find_map(|key| backup.get(*key).and_then(Value::as_str))
+                .map(str::to_string)
+        };
+        Some(DatabaseBackupConfig {
+            active: ["enableRemoteBackup", "remoteBackupEnabled", "active"]
+                .iter()
+                .find_map(|key| backup.get(*key).and_then(Value::as_bool)),
+            interval: text(&["interval", "backupInterval"]),
+            time_utc: text(&["timeUTC", "timeUtc", "databaseBackupTimeUTC"]),
+            storage_type: text(&["storageType", "backupStorageType"]),
+            storage_path: text(&["destination", "storagePath"]),
+            ..DatabaseBackupConfig::disabled()
+        })
+    }
+}
+
 /// Optional. Changes Redis database alert details.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DatabaseAlertSpec {
@@ -1076,6 +1279,48 @@ impl DatabaseHandler {
             .await
     }
 
+    /// Set the Replica Of (Active-Passive) sources of a Pro database
+    ///
+    /// An empty list of sources stops replication.
+    ///
+    /// PUT /subscriptions/{subscriptionId}/databases/{databaseId}
+    pub async fn update_replica_of(
+        &self,
+        subscription_id: i32,
+        database_id: i32,
+        replica: &ReplicaOfSpec,
+    ) -> Result<TaskStateUpdate> {
+        self.client
+            .put(
+                &format!(
+                    "/subscriptions/{}/databases/{}",
+                    subscription_id, database_id
+                ),
+                &serde_json::json!({ "replica": replica }),
+            )
+            .await
+    }
+
+    /// Set the periodic backup configuration of a Pro database
+    ///
+    /// PUT /subscriptions/{subscriptionId}/databases/{databaseId}
+    pub async fn update_remote_backup(
+        &self,
+        subscription_id: i32,
+        database_id: i32,
+        remote_backup: &DatabaseBackupConfig,
+    ) -> Result<TaskStateUpdate> {
+        self.client
+            .put(
+                &format!(
+                    "/subscriptions/{}/databases/{}",
+                    subscription_id, database_id
+                ),
+                &serde_json::json!({ "remoteBackup": remote_backup }),
+            )
+            .await
+    }
+
     /// Get Pro database backup status
     /// Gets information on the latest backup attempt for this Pro database.
     ///
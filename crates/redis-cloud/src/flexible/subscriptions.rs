@@ -910,6 +910,66 @@ impl SubscriptionHandler {
             .await
     }
 
+    /// Rename Pro subscription
+    /// Sets a new name on the specified Pro subscription, leaving its other settings unchanged.
+    ///
+    /// PUT /subscriptions/{subscriptionId}
+    pub async fn update_subscription_name(
+        &self,
+        subscription_id: i32,
+        name: &str,
+    ) -> Result<TaskStateUpdate> {
+        let request = SubscriptionUpdateRequest {
+            subscription_id: None,
+            name: Some(name.to_string()),
+            payment_method_id: None,
+            payment_method: None,
+            command_type: None,
+            extra: Value::Object(Default::default()),
+        };
+        self.client
+            .put(&format!("/subscriptions/{}", subscription_id), &request)
+            .await
+    }
+
+    /// Update Pro subscription payment method
+    /// Switches the specified Pro subscription to another credit card of the account. Use
+    /// GET /payment-methods to get the account's payment method IDs.
+    ///
+    /// PUT /subscriptions/{subscriptionId}
+    pub async fn update_subscription_payment_method(
+        &self,
+        subscription_id: i32,
+        payment_method_id: i32,
+    ) -> Result<TaskStateUpdate> {
+        let request = SubscriptionUpdateRequest {
+            subscription_id: None,
+            name: None,
+            payment_method_id: Some(payment_method_id),
+            payment_method: Some("credit-card".to_string()),
+            command_type: None,
+            extra: Value::Object(Default::default()),
+        };
+        self.client
+            .put(&format!("/subscriptions/{}", subscription_id), &request)
+            .await
+    }
+
+    /// Update Pro subscription customer managed keys
+    /// Sets the customer managed keys (CMK) that encrypt a Pro subscription's persistent storage.
+    /// Active-Active subscriptions need a key for each region.
+    ///
+    /// PUT /subscriptions/{subscriptionId}/cmks
+    pub async fn update_subscription_cmks(
+        &self,
+        subscription_id: i32,
+        request: &SubscriptionUpdateCMKRequest,
+    ) -> Result<TaskStateUpdate> {
+        self.client
+            .put(&format!("/subscriptions/{}/cmks", subscription_id), request)
+            .await
+    }
+
     /// Get Pro subscription CIDR allowlist
     /// (Self-hosted AWS subscriptions only) Gets a Pro subscription's CIDR allowlist.
     ///
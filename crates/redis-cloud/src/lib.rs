@@ -263,6 +263,29 @@
 //! | [`CloudAccountHandler`] | Cloud providers | AWS, GCP, Azure account integration |
 //! | [`TaskHandler`] | Async operations | track long-running operations |
 //!
+//! ## Feature Flags
+//!
+//! All features are enabled by default. Turn off default features and pick the ones you need
+//! to build a slimmer client:
+//!
+//! | Feature | Enables |
+//! |---------|---------|
+//! | `rustls-tls` | HTTPS through rustls |
+//! | `native-tls` | HTTPS through the platform TLS library (OpenSSL, Secure Transport, SChannel) |
+//! | `acl` | [`acl`] module: ACL users, roles and Redis rules |
+//! | `users` | [`users`] module: account users |
+//! | `connectivity` | [`connectivity`] module: VPC peering, Transit Gateway, PSC |
+//! | `fixed` | [`fixed`] module: Essentials subscriptions and databases |
+//! | `cloud-accounts` | [`cloud_accounts`] module: cloud provider accounts |
+//!
+//! Subscriptions, databases, account and tasks are always available. Enable one TLS feature
+//! unless the client only talks plain HTTP:
+//!
+//! ```toml
+//! [dependencies]
+//! redis-cloud = { version = "0.2", default-features = false, features = ["rustls-tls", "acl"] }
+//! ```
+//!
 //! ## Authentication
 //!
 //! Redis Cloud uses API key authentication with two required headers:
@@ -282,44 +305,61 @@ pub mod client;
 mod lib_tests;
 
 // Re-export client types
-pub use client::{CloudClient, CloudClientBuilder};
+pub use client::{CloudClient, CloudClientBuilder, RateLimit, RequestEvent, RequestObserver};
 
 // Types module for shared models
 pub mod types;
 
 // Handler modules - each handles a specific API domain
 pub mod account;
+#[cfg(feature = "acl")]
 pub mod acl;
+#[cfg(feature = "cloud-accounts")]
 pub mod cloud_accounts;
+#[cfg(feature = "connectivity")]
 pub mod connectivity;
+#[cfg(feature = "fixed")]
 pub mod fixed;
 pub mod flexible;
 pub mod tasks;
+#[cfg(feature = "users")]
 pub mod users;
 
 // Backward compatibility module aliases
+#[cfg(feature = "fixed")]
 pub use fixed::databases as fixed_databases;
+#[cfg(feature = "fixed")]
 pub use fixed::subscriptions as fixed_subscriptions;
 pub use flexible::databases;
 pub use flexible::subscriptions;
 
 // Re-export handlers with standard naming
 pub use account::AccountHandler;
+#[cfg(feature = "acl")]
 pub use acl::AclHandler;
+#[cfg(feature = "cloud-accounts")]
 pub use cloud_accounts::CloudAccountsHandler as CloudAccountHandler;
 
 // Connectivity handlers
+#[cfg(feature = "connectivity")]
 pub use connectivity::psc::PscHandler;
+#[cfg(feature = "connectivity")]
 pub use connectivity::transit_gateway::TransitGatewayHandler;
+#[cfg(feature = "connectivity")]
 pub use connectivity::vpc_peering::VpcPeeringHandler;
 // Legacy connectivity export for backward compatibility
+#[cfg(feature = "connectivity")]
 pub use connectivity::ConnectivityHandler;
 
 // Fixed plan handlers
+#[cfg(feature = "fixed")]
 pub use fixed::databases::FixedDatabaseHandler;
+#[cfg(feature = "fixed")]
 pub use fixed::subscriptions::FixedSubscriptionHandler;
 // Legacy exports for backward compatibility
+#[cfg(feature = "fixed")]
 pub use fixed::databases::FixedDatabaseHandler as FixedDatabasesHandler;
+#[cfg(feature = "fixed")]
 pub use fixed::subscriptions::FixedSubscriptionHandler as FixedSubscriptionsHandler;
 
 // Flexible plan handlers (pay-as-you-go)
@@ -330,6 +370,7 @@ pub use flexible::databases::DatabaseHandler as DatabasesHandler;
 pub use flexible::subscriptions::SubscriptionHandler as SubscriptionsHandler;
 
 pub use tasks::TasksHandler as TaskHandler;
+#[cfg(feature = "users")]
 pub use users::UsersHandler as UserHandler;
 
 // Re-export error types
@@ -367,8 +408,33 @@ pub enum CloudError {
     #[error("Connection error: {0}")]
     ConnectionError(String),
 
+    #[error("Response body exceeds the {limit} byte limit")]
+    ResponseTooLarge { limit: usize },
+
+    #[error("{method} {path} refused: the client is read-only")]
+    ReadOnly { method: String, path: String },
+
     #[error("JSON error: {0}")]
     JsonError(#[from] serde_json::Error),
+
+    #[error("Unexpected response format at '{path}': {message}")]
+    DeserializationError {
+        /// Path of the field that failed, e.g. `subscriptions[0].status`
+        path: String,
+        message: String,
+        /// The response body that could not be deserialized
+        raw: Box<serde_json::Value>,
+    },
+}
+
+impl CloudError {
+    /// Raw response body, if the response did not match the expected type
+    pub fn raw_response(&self) -> Option<&serde_json::Value> {
+        match self {
+            CloudError::DeserializationError { raw, .. } => Some(raw),
+            _ => None,
+        }
+    }
 }
 
 pub type Result<T> = std::result::Result<T, CloudError>;
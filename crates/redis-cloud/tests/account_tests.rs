@@ -1,4 +1,4 @@
-use redis_cloud::{AccountHandler, CloudClient};
+use redis_cloud::{AccountHandler, CloudClient, RateLimit, RequestEvent, RequestObserver};
 use serde_json::json;
 use wiremock::matchers::{header, method, path, query_param};
 use wiremock::{Mock, MockServer, ResponseTemplate};
@@ -36,6 +36,41 @@ async fn test_get_current_account() {
     assert!(result.links.is_some());
 }
 
+#[tokio::test]
+async fn test_get_current_account_details() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "account": {
+                "id": 40131,
+                "name": "Acme",
+                "marketplaceStatus": "active",
+                "key": {"name": "ci-key"}
+            }
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let client = CloudClient::builder()
+        .api_key("test-key".to_string())
+        .api_secret("test-secret".to_string())
+        .base_url(mock_server.uri())
+        .build()
+        .unwrap();
+
+    let result = AccountHandler::new(client)
+        .get_current_account()
+        .await
+        .unwrap();
+
+    let account = result.account.as_ref().unwrap();
+    assert_eq!(account.id, Some(40131));
+    assert_eq!(account.marketplace_status.as_deref(), Some("active"));
+    assert_eq!(account.extra["key"]["name"], "ci-key");
+}
+
 #[tokio::test]
 async fn test_get_data_persistence_options() {
     let mock_server = MockServer::start().await;
@@ -187,7 +222,17 @@ async fn test_get_account_payment_methods() {
         .and(header("x-api-key", "test-key"))
         .and(header("x-api-secret-key", "test-secret"))
         .respond_with(ResponseTemplate::new(200).set_body_json(json!({
-            "accountId": 123
+            "accountId": 123,
+            "paymentMethods": [
+                {
+                    "id": 42,
+                    "type": "Visa",
+                    "creditCardEndsWith": 4242,
+                    "nameOnCard": "Jane Doe",
+                    "expirationMonth": 8,
+                    "expirationYear": 2028
+                }
+            ]
         })))
         .mount(&mock_server)
         .await;
@@ -203,6 +248,10 @@ async fn test_get_account_payment_methods() {
     let result = handler.get_account_payment_methods().await.unwrap();
 
     assert!(result.account_id.is_some());
+    let method = result.find(42).unwrap();
+    assert_eq!(method.payment_type.as_deref(), Some("Visa"));
+    assert_eq!(method.credit_card_ends_with, Some(4242));
+    assert!(result.find(7).is_none());
 }
 
 #[tokio::test]
@@ -247,6 +296,171 @@ async fn test_get_account_system_logs() {
     assert_eq!(entries.len(), 1);
 }
 
+#[tokio::test]
+async fn test_get_all_account_system_logs_paginates() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/logs"))
+        .and(query_param("limit", "2"))
+        .and(query_param("offset", "0"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "entries": [
+                {"id": 3, "time": "2024-01-03T00:00:00Z", "type": "info"},
+                {"id": 2, "time": "2024-01-02T00:00:00Z", "type": "info"}
+            ]
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/logs"))
+        .and(query_param("limit", "2"))
+        .and(query_param("offset", "2"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "entries": [
+                {"id": 1, "time": "2024-01-01T00:00:00Z", "type": "info"}
+            ]
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    // A short page is not the end; only an empty one is
+    Mock::given(method("GET"))
+        .and(path("/logs"))
+        .and(query_param("limit", "2"))
+        .and(query_param("offset", "3"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "entries": [] })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let client = CloudClient::builder()
+        .api_key("test-key".to_string())
+        .api_secret("test-secret".to_string())
+        .base_url(mock_server.uri())
+        .build()
+        .unwrap();
+
+    let handler = AccountHandler::new(client);
+    let entries = handler.get_all_account_system_logs(2, None).await.unwrap();
+
+    assert_eq!(entries.len(), 3);
+    assert_eq!(entries[0].id, Some(3));
+    assert_eq!(entries[2].id, Some(1));
+}
+
+#[tokio::test]
+async fn test_get_all_account_system_logs_stops_before_since() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/logs"))
+        .and(query_param("offset", "0"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "entries": [
+                {"id": 4, "time": "2024-01-04T00:00:00Z", "type": "info"},
+                {"id": 3, "time": "2024-01-03T00:00:00Z", "type": "info"}
+            ]
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/logs"))
+        .and(query_param("offset", "2"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "entries": [
+                {"id": 2, "time": "2024-01-02T00:00:00Z", "type": "info"},
+                {"id": 1, "time": "2024-01-01T00:00:00Z", "type": "info"}
+            ]
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/logs"))
+        .and(query_param("offset", "4"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "entries": [
+                {"id": 0, "time": "2023-12-31T00:00:00Z", "type": "info"}
+            ]
+        })))
+        .expect(0)
+        .mount(&mock_server)
+        .await;
+
+    let client = CloudClient::builder()
+        .api_key("test-key".to_string())
+        .api_secret("test-secret".to_string())
+        .base_url(mock_server.uri())
+        .build()
+        .unwrap();
+
+    let since = "2024-01-02T12:00:00Z".parse().unwrap();
+    let handler = AccountHandler::new(client);
+    let entries = handler
+        .get_all_account_system_logs(2, Some(since))
+        .await
+        .unwrap();
+
+    let ids: Vec<_> = entries.iter().map(|e| e.id).collect();
+    assert_eq!(ids, vec![Some(4), Some(3), Some(2), Some(1)]);
+}
+
+#[tokio::test]
+async fn test_get_all_account_session_logs_single_page() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/session-logs"))
+        .and(query_param("limit", "100"))
+        .and(query_param("offset", "0"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "entries": [
+                {
+                    "id": "abc",
+                    "time": "2024-01-01T00:00:00Z",
+                    "user": "admin@example.com",
+                    "ipAddress": "10.0.0.1",
+                    "action": "login"
+                }
+            ]
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/session-logs"))
+        .and(query_param("offset", "1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "entries": [] })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let client = CloudClient::builder()
+        .api_key("test-key".to_string())
+        .api_secret("test-secret".to_string())
+        .base_url(mock_server.uri())
+        .build()
+        .unwrap();
+
+    let handler = AccountHandler::new(client);
+    let entries = handler
+        .get_all_account_session_logs(100, None)
+        .await
+        .unwrap();
+
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].ip_address.as_deref(), Some("10.0.0.1"));
+    assert_eq!(entries[0].action.as_deref(), Some("login"));
+}
+
 #[tokio::test]
 async fn test_get_supported_search_scaling_factors() {
     let mock_server = MockServer::start().await;
@@ -709,3 +923,215 @@ async fn test_current_account_with_extra_fields() {
         assert_eq!(account_name.as_str().unwrap(), "Test Account");
     }
 }
+
+#[tokio::test]
+async fn test_response_body_size_limit() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "account": {
+                "id": 123,
+                "name": "x".repeat(512)
+            }
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let client = CloudClient::builder()
+        .api_key("test-key".to_string())
+        .api_secret("test-secret".to_string())
+        .base_url(mock_server.uri())
+        .max_body_size(128)
+        .build()
+        .unwrap();
+
+    let result = AccountHandler::new(client).get_current_account().await;
+    assert!(matches!(
+        result,
+        Err(redis_cloud::CloudError::ResponseTooLarge { limit: 128 })
+    ));
+}
+
+#[derive(Debug, Default)]
+struct RecordingObserver(std::sync::Mutex<Vec<RequestEvent>>);
+
+impl RequestObserver for RecordingObserver {
+    fn on_request(&self, event: &RequestEvent) {
+        self.0.lock().unwrap().push(event.clone());
+    }
+}
+
+#[tokio::test]
+async fn test_request_observer() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({"account": {"id": 1}})))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/payment-methods"))
+        .respond_with(ResponseTemplate::new(500))
+        .mount(&mock_server)
+        .await;
+
+    let observer = std::sync::Arc::new(RecordingObserver::default());
+    let client = CloudClient::builder()
+        .api_key("test-key".to_string())
+        .api_secret("test-secret".to_string())
+        .base_url(mock_server.uri())
+        .observer(observer.clone())
+        .build()
+        .unwrap();
+
+    client.get_raw("/").await.unwrap();
+    assert!(client.get_raw("/payment-methods").await.is_err());
+
+    let events = observer.0.lock().unwrap();
+    assert_eq!(events.len(), 2);
+    assert_eq!(events[0].method, "GET");
+    assert_eq!(events[0].path, "/");
+    assert_eq!(events[0].status, Some(200));
+    assert_eq!(events[1].path, "/payment-methods");
+    assert_eq!(events[1].status, Some(500));
+    assert_eq!(events[1].rate_limit, None);
+}
+
+#[tokio::test]
+async fn test_request_observer_rate_limit() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("X-RateLimit-Limit", "400")
+                .insert_header("X-RateLimit-Remaining", "398")
+                .insert_header("X-RateLimit-Reset", "42")
+                .set_body_json(json!({"account": {"id": 1}})),
+        )
+        .mount(&mock_server)
+        .await;
+
+    let observer = std::sync::Arc::new(RecordingObserver::default());
+    let client = CloudClient::builder()
+        .api_key("test-key".to_string())
+        .api_secret("test-secret".to_string())
+        .base_url(mock_server.uri())
+        .observer(observer.clone())
+        .build()
+        .unwrap();
+
+    client.get_raw("/").await.unwrap();
+
+    let events = observer.0.lock().unwrap();
+    assert_eq!(
+        events[0].rate_limit,
+        Some(RateLimit {
+            limit: Some(400),
+            remaining: Some(398),
+            reset: Some(42),
+        })
+    );
+}
+
+#[tokio::test]
+async fn test_read_only_client_refuses_writes() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({"account": {"id": 1}})))
+        .mount(&mock_server)
+        .await;
+
+    let client = CloudClient::builder()
+        .api_key("test-key".to_string())
+        .api_secret("test-secret".to_string())
+        .base_url(mock_server.uri())
+        .read_only(true)
+        .build()
+        .unwrap();
+
+    client.get_raw("/").await.unwrap();
+    let result = client.delete_raw("/subscriptions/1").await;
+    assert!(matches!(
+        result,
+        Err(redis_cloud::CloudError::ReadOnly { ref method, ref path })
+            if method == "DELETE" && path == "/subscriptions/1"
+    ));
+    assert!(client.post_raw("/subscriptions", json!({})).await.is_err());
+
+    // Refused requests never reach the server
+    let requests = mock_server.received_requests().await.unwrap();
+    assert_eq!(requests.len(), 1);
+}
+
+#[tokio::test]
+async fn test_max_concurrent_requests() {
+    let mock_server = MockServer::start().await;
+    let delay = std::time::Duration::from_millis(150);
+
+    Mock::given(method("GET"))
+        .and(path("/"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(json!({"account": {"id": 123}}))
+                .set_delay(delay),
+        )
+        .mount(&mock_server)
+        .await;
+
+    let client = CloudClient::builder()
+        .api_key("test-key".to_string())
+        .api_secret("test-secret".to_string())
+        .base_url(mock_server.uri())
+        .max_concurrent_requests(1)
+        .build()
+        .unwrap();
+
+    // Clones share the limit, so the three requests run one after another
+    let (a, b, c) = (client.clone(), client.clone(), client);
+    let start = std::time::Instant::now();
+    let (ra, rb, rc) = tokio::join!(a.get_raw("/"), b.get_raw("/"), c.get_raw("/"));
+    assert!(ra.is_ok() && rb.is_ok() && rc.is_ok());
+    assert!(start.elapsed() >= delay * 3);
+}
+
+#[tokio::test]
+async fn test_request_limiter_is_shared_between_clients() {
+    let mock_server = MockServer::start().await;
+    let delay = std::time::Duration::from_millis(150);
+
+    Mock::given(method("GET"))
+        .and(path("/"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(json!({"account": {"id": 123}}))
+                .set_delay(delay),
+        )
+        .mount(&mock_server)
+        .await;
+
+    // Two clients built separately, each allowing more than one request, take slots from
+    // the same single-slot limiter
+    let limiter = std::sync::Arc::new(tokio::sync::Semaphore::new(1));
+    let build = || {
+        CloudClient::builder()
+            .api_key("test-key".to_string())
+            .api_secret("test-secret".to_string())
+            .base_url(mock_server.uri())
+            .max_concurrent_requests(4)
+            .request_limiter(limiter.clone())
+            .build()
+            .unwrap()
+    };
+    let (a, b) = (build(), build());
+    let start = std::time::Instant::now();
+    let (ra, rb) = tokio::join!(a.get_raw("/"), b.get_raw("/"));
+    assert!(ra.is_ok() && rb.is_ok());
+    assert!(start.elapsed() >= delay * 2);
+}
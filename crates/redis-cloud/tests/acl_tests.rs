@@ -1,6 +1,8 @@
+#![cfg(feature = "acl")]
+
 use redis_cloud::{AclHandler, CloudClient};
 use serde_json::json;
-use wiremock::matchers::{header, method, path};
+use wiremock::matchers::{body_partial_json, header, method, path};
 use wiremock::{Mock, MockServer, ResponseTemplate};
 
 #[tokio::test]
@@ -876,3 +878,169 @@ async fn test_create_user_with_extra_fields() {
     assert_eq!(response.resource_id, Some(999));
     assert_eq!(response.additional_resource_id, Some(888));
 }
+
+async fn mount_roles_with_rules(mock_server: &MockServer) {
+    Mock::given(method("GET"))
+        .and(path("/acl/roles"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "accountId": 123,
+            "roles": [
+                {
+                    "id": 42,
+                    "name": "app-role",
+                    "redisRules": [
+                        {
+                            "ruleId": 7,
+                            "ruleName": "Read-Only",
+                            "databases": [
+                                {
+                                    "subscriptionId": 100,
+                                    "databaseId": 1,
+                                    "databaseName": "db-one"
+                                }
+                            ]
+                        }
+                    ]
+                }
+            ]
+        })))
+        .mount(mock_server)
+        .await;
+}
+
+fn test_client(mock_server: &MockServer) -> CloudClient {
+    CloudClient::builder()
+        .api_key("test-key".to_string())
+        .api_secret("test-secret".to_string())
+        .base_url(mock_server.uri())
+        .build()
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_attach_role_to_database() {
+    let mock_server = MockServer::start().await;
+    mount_roles_with_rules(&mock_server).await;
+
+    Mock::given(method("PUT"))
+        .and(path("/acl/roles/42"))
+        .and(body_partial_json(json!({
+            "name": "app-role",
+            "redisRules": [
+                {
+                    "ruleName": "Read-Only",
+                    "databases": [
+                        {"subscriptionId": 100, "databaseId": 1}
+                    ]
+                },
+                {
+                    "ruleName": "Full-Access",
+                    "databases": [
+                        {"subscriptionId": 100, "databaseId": 2}
+                    ]
+                }
+            ]
+        })))
+        .respond_with(ResponseTemplate::new(202).set_body_json(json!({
+            "taskId": "task-attach",
+            "commandType": "UPDATE_ROLE",
+            "status": "received"
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let handler = AclHandler::new(test_client(&mock_server));
+    let result = handler
+        .attach_role_to_database("app-role", "Full-Access", 100, 2, None)
+        .await
+        .unwrap();
+
+    assert_eq!(result.task_id, Some("task-attach".to_string()));
+}
+
+#[tokio::test]
+async fn test_attach_role_to_database_unknown_role() {
+    let mock_server = MockServer::start().await;
+    mount_roles_with_rules(&mock_server).await;
+
+    let handler = AclHandler::new(test_client(&mock_server));
+    let result = handler
+        .attach_role_to_database("missing-role", "Full-Access", 100, 2, None)
+        .await;
+
+    assert!(matches!(
+        result,
+        Err(redis_cloud::CloudError::NotFound { .. })
+    ));
+}
+
+#[tokio::test]
+async fn test_attach_role_to_database_role_without_id() {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/acl/roles"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "roles": [
+                {"name": "other-role", "id": 7},
+                {"name": "app-role", "redisRules": []},
+                {"name": "huge-role", "id": 4294967296i64}
+            ]
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let handler = AclHandler::new(test_client(&mock_server));
+    let result = handler
+        .attach_role_to_database("app-role", "Full-Access", 100, 2, None)
+        .await;
+    match result {
+        Err(redis_cloud::CloudError::DeserializationError { path, .. }) => {
+            assert_eq!(path, "roles[1].id")
+        }
+        other => panic!("unexpected result: {:?}", other),
+    }
+
+    let result = handler
+        .attach_role_to_database("huge-role", "Full-Access", 100, 2, None)
+        .await;
+    assert!(matches!(
+        result,
+        Err(redis_cloud::CloudError::DeserializationError { .. })
+    ));
+}
+
+#[tokio::test]
+async fn test_detach_role_from_database() {
+    let mock_server = MockServer::start().await;
+    mount_roles_with_rules(&mock_server).await;
+
+    Mock::given(method("PUT"))
+        .and(path("/acl/roles/42"))
+        .and(body_partial_json(json!({
+            "name": "app-role",
+            "redisRules": []
+        })))
+        .respond_with(ResponseTemplate::new(202).set_body_json(json!({
+            "taskId": "task-detach",
+            "commandType": "UPDATE_ROLE",
+            "status": "received"
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let handler = AclHandler::new(test_client(&mock_server));
+    let result = handler
+        .detach_role_from_database("app-role", 100, 1)
+        .await
+        .unwrap();
+
+    assert_eq!(result.task_id, Some("task-detach".to_string()));
+
+    let not_attached = handler.detach_role_from_database("app-role", 100, 9).await;
+    assert!(matches!(
+        not_attached,
+        Err(redis_cloud::CloudError::NotFound { .. })
+    ));
+}
@@ -1,3 +1,5 @@
+#![cfg(feature = "cloud-accounts")]
+
 use redis_cloud::{CloudAccountHandler, CloudClient};
 use serde_json::json;
 use wiremock::matchers::{header, method, path};
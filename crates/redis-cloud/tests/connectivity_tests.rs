@@ -1,6 +1,8 @@
+#![cfg(feature = "connectivity")]
+
 use redis_cloud::{CloudClient, ConnectivityHandler};
 use serde_json::json;
-use wiremock::matchers::{header, method, path};
+use wiremock::matchers::{body_json, header, method, path};
 use wiremock::{Mock, MockServer, ResponseTemplate};
 
 #[tokio::test]
@@ -444,6 +446,51 @@ async fn test_create_tgw() {
     );
 }
 
+#[tokio::test]
+async fn test_create_tgw_with_cidrs() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path(
+            "/subscriptions/123/transitGateways/tgw-456/attachment",
+        ))
+        .and(header("x-api-key", "test-key"))
+        .and(header("x-api-secret-key", "test-secret"))
+        .and(body_json(json!({
+            "tgwId": "tgw-456",
+            "cidrs": ["10.0.0.0/16", "10.1.0.0/16"]
+        })))
+        .respond_with(ResponseTemplate::new(202).set_body_json(json!({
+            "taskId": "task-create-tgw-cidrs",
+            "commandType": "CREATE_TGW_ATTACHMENT",
+            "status": "received"
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let client = CloudClient::builder()
+        .api_key("test-key".to_string())
+        .api_secret("test-secret".to_string())
+        .base_url(mock_server.uri())
+        .build()
+        .unwrap();
+
+    let handler = ConnectivityHandler::new(client);
+    let result = handler
+        .create_tgw_attachment_with_cidrs(
+            123,
+            "tgw-456",
+            vec!["10.0.0.0/16".to_string(), "10.1.0.0/16".to_string()],
+        )
+        .await
+        .unwrap();
+    assert_eq!(result.task_id, Some("task-create-tgw-cidrs".to_string()));
+    assert_eq!(
+        result.status,
+        Some(redis_cloud::types::TaskStatus::Received)
+    );
+}
+
 #[tokio::test]
 async fn test_update_tgw() {
     let mock_server = MockServer::start().await;
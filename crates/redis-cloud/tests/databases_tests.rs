@@ -1,4 +1,4 @@
-use redis_cloud::{CloudClient, DatabaseHandler};
+use redis_cloud::{CloudClient, CloudError, DatabaseHandler};
 use serde_json::json;
 use wiremock::matchers::{body_json, header, method, path, query_param};
 use wiremock::{Mock, MockServer, ResponseTemplate};
@@ -224,6 +224,181 @@ async fn test_update_database() {
     assert_eq!(result.command_type, Some("UPDATE_DATABASE".to_string()));
 }
 
+#[tokio::test]
+async fn test_update_database_throughput() {
+    use redis_cloud::databases::{
+        DatabaseThroughputSpec, DatabaseUpdateRequest, ThroughputMeasurement,
+    };
+
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("PUT"))
+        .and(path("/subscriptions/123/databases/456"))
+        .and(body_json(json!({
+            "throughputMeasurement": {"by": "operations-per-second", "value": 25000},
+            "memoryLimitInGb": 10.0,
+            "replication": true
+        })))
+        .respond_with(ResponseTemplate::new(202).set_body_json(json!({
+            "taskId": "task-scale-db-456",
+            "commandType": "UPDATE_DATABASE",
+            "status": "received"
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let client = CloudClient::builder()
+        .api_key("test-key".to_string())
+        .api_secret("test-secret".to_string())
+        .base_url(mock_server.uri())
+        .build()
+        .unwrap();
+
+    let mut request: DatabaseUpdateRequest = serde_json::from_value(json!({})).unwrap();
+    let throughput = DatabaseThroughputSpec::operations_per_second(25000);
+    assert_eq!(
+        throughput.measurement(),
+        Some(ThroughputMeasurement::OperationsPerSecond)
+    );
+    request.throughput_measurement = Some(throughput);
+    request.memory_limit_in_gb = Some(10.0);
+    request.replication = Some(true);
+
+    let result = DatabaseHandler::new(client)
+        .update_database(123, 456, &request)
+        .await
+        .unwrap();
+    assert_eq!(result.task_id, Some("task-scale-db-456".to_string()));
+}
+
+#[tokio::test]
+async fn test_replica_of() {
+    use redis_cloud::databases::{DatabaseSyncSourceSpec, ReplicaOfSpec};
+
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/subscriptions/123/databases/456"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "databaseId": 456,
+            "name": "replica",
+            "replicaOf": ["redis://legacy.example.com:12000"]
+        })))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("PUT"))
+        .and(path("/subscriptions/123/databases/456"))
+        .and(body_json(json!({
+            "replica": {
+                "syncSources": [
+                    {"endpoint": "rediss://source.example.com:12000", "encryption": true}
+                ]
+            }
+        })))
+        .respond_with(ResponseTemplate::new(202).set_body_json(json!({
+            "taskId": "task-replica-of-456",
+            "commandType": "UPDATE_DATABASE",
+            "status": "received"
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let client = CloudClient::builder()
+        .api_key("test-key".to_string())
+        .api_secret("test-secret".to_string())
+        .base_url(mock_server.uri())
+        .build()
+        .unwrap();
+    let handler = DatabaseHandler::new(client);
+
+    let database = handler
+        .get_subscription_database_by_id(123, 456)
+        .await
+        .unwrap();
+    let replica = database.replica_of().unwrap();
+    assert_eq!(replica.sync_sources.len(), 1);
+    assert_eq!(
+        replica.sync_sources[0].endpoint,
+        "redis://legacy.example.com:12000"
+    );
+
+    let mut source = DatabaseSyncSourceSpec::new("rediss://source.example.com:12000");
+    source.encryption = Some(true);
+    let result = handler
+        .update_replica_of(123, 456, &ReplicaOfSpec::new(vec![source]))
+        .await
+        .unwrap();
+    assert_eq!(result.task_id, Some("task-replica-of-456".to_string()));
+}
+
+#[tokio::test]
+async fn test_remote_backup() {
+    use redis_cloud::databases::DatabaseBackupConfig;
+
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/subscriptions/123/databases/456"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "databaseId": 456,
+            "name": "cache",
+            "backup": {
+                "enableRemoteBackup": true,
+                "interval": "every-24-hours",
+                "timeUTC": "02:00",
+                "destination": "s3://backups/cache"
+            }
+        })))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("PUT"))
+        .and(path("/subscriptions/123/databases/456"))
+        .and(body_json(json!({
+            "remoteBackup": {
+                "active": true,
+                "interval": "every-12-hours",
+                "storageType": "google-blob-storage",
+                "storagePath": "gs://backups/cache"
+            }
+        })))
+        .respond_with(ResponseTemplate::new(202).set_body_json(json!({
+            "taskId": "task-backup-456",
+            "commandType": "UPDATE_DATABASE",
+            "status": "received"
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let client = CloudClient::builder()
+        .api_key("test-key".to_string())
+        .api_secret("test-secret".to_string())
+        .base_url(mock_server.uri())
+        .build()
+        .unwrap();
+    let handler = DatabaseHandler::new(client);
+
+    let database = handler
+        .get_subscription_database_by_id(123, 456)
+        .await
+        .unwrap();
+    let backup = database.remote_backup().unwrap();
+    assert_eq!(backup.active, Some(true));
+    assert_eq!(backup.interval.as_deref(), Some("every-24-hours"));
+    assert_eq!(backup.time_utc.as_deref(), Some("02:00"));
+    assert_eq!(backup.storage_path.as_deref(), Some("s3://backups/cache"));
+
+    let config = DatabaseBackupConfig::enabled(
+        "every-12-hours",
+        "google-blob-storage",
+        "gs://backups/cache",
+    );
+    let result = handler
+        .update_remote_backup(123, 456, &config)
+        .await
+        .unwrap();
+    assert_eq!(result.task_id, Some("task-backup-456".to_string()));
+}
+
 #[tokio::test]
 async fn test_delete_database_by_id() {
     let mock_server = MockServer::start().await;
@@ -418,6 +593,39 @@ async fn test_upgrade_database_module() {
     // assert_eq!(result.command_type, Some("UPGRADE_DATABASE_MODULE".to_string()));
 }
 
+#[tokio::test]
+async fn test_get_subscription_database_certificate() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/subscriptions/123/databases/456/certificate"))
+        .and(header("x-api-key", "test-key"))
+        .and(header("x-api-secret-key", "test-secret"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "publicCertificatePEMString": "-----BEGIN CERTIFICATE-----\nMIIB\n-----END CERTIFICATE-----\n"
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let client = CloudClient::builder()
+        .api_key("test-key".to_string())
+        .api_secret("test-secret".to_string())
+        .base_url(mock_server.uri())
+        .build()
+        .unwrap();
+
+    let handler = DatabaseHandler::new(client);
+    let result = handler
+        .get_subscription_database_certificate(123, 456)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        result.public_certificate_pem_string.as_deref(),
+        Some("-----BEGIN CERTIFICATE-----\nMIIB\n-----END CERTIFICATE-----\n")
+    );
+}
+
 #[tokio::test]
 async fn test_get_database_pricing() {
     let mock_server = MockServer::start().await;
@@ -452,6 +660,40 @@ async fn test_get_database_pricing() {
     // assert!(result.pricing.is_some());
 }
 
+#[tokio::test]
+async fn test_type_mismatch_reports_field() {
+    let mock_server = MockServer::start().await;
+
+    let body = json!({
+        "entries": [
+            {"id": 1, "startTime": "2024-01-01T00:00:00Z", "duration": "slow"}
+        ]
+    });
+    Mock::given(method("GET"))
+        .and(path("/subscriptions/123/databases/456/slow-log"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(body.clone()))
+        .mount(&mock_server)
+        .await;
+
+    let client = CloudClient::builder()
+        .api_key("test-key".to_string())
+        .api_secret("test-secret".to_string())
+        .base_url(mock_server.uri())
+        .build()
+        .unwrap();
+
+    let handler = DatabaseHandler::new(client);
+    let err = handler.get_slow_log(123, 456, None).await.unwrap_err();
+
+    match &err {
+        CloudError::DeserializationError { path, .. } => {
+            assert_eq!(path, "entries[0].duration");
+        }
+        other => panic!("unexpected error: {:?}", other),
+    }
+    assert_eq!(err.raw_response(), Some(&body));
+}
+
 #[tokio::test]
 async fn test_create_database_with_modules() {
     let mock_server = MockServer::start().await;
@@ -770,6 +1012,89 @@ async fn test_error_handling_401() {
     }
 }
 
+#[tokio::test]
+async fn test_flush_crdb() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("PUT"))
+        .and(path("/subscriptions/123/databases/456/flush"))
+        .and(body_json(json!({})))
+        .respond_with(ResponseTemplate::new(202).set_body_json(json!({
+            "taskId": "task-flush",
+            "commandType": "activeActiveDatabaseFlushRequest",
+            "status": "received"
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let client = CloudClient::builder()
+        .api_key("test-key".to_string())
+        .api_secret("test-secret".to_string())
+        .base_url(mock_server.uri())
+        .build()
+        .unwrap();
+
+    let handler = DatabaseHandler::new(client);
+    let request = redis_cloud::databases::CrdbFlushRequest {
+        subscription_id: None,
+        database_id: None,
+        command_type: None,
+        extra: json!({}),
+    };
+    let task = handler.flush_crdb(123, 456, &request).await.unwrap();
+    assert_eq!(task.task_id.as_deref(), Some("task-flush"));
+}
+
+#[tokio::test]
+async fn test_update_crdb_local_properties() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("PUT"))
+        .and(path("/subscriptions/123/databases/456/regions"))
+        .and(body_json(json!({
+            "regions": [{
+                "region": "us-east-1",
+                "localThroughputMeasurement": {
+                    "region": "us-east-1",
+                    "readOperationsPerSecond": 2000,
+                    "writeOperationsPerSecond": 500
+                }
+            }]
+        })))
+        .respond_with(ResponseTemplate::new(202).set_body_json(json!({
+            "taskId": "task-regions",
+            "status": "received"
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let client = CloudClient::builder()
+        .api_key("test-key".to_string())
+        .api_secret("test-secret".to_string())
+        .base_url(mock_server.uri())
+        .build()
+        .unwrap();
+
+    let handler = DatabaseHandler::new(client);
+    let request: redis_cloud::databases::CrdbUpdatePropertiesRequest =
+        serde_json::from_value(json!({
+            "regions": [{
+                "region": "us-east-1",
+                "localThroughputMeasurement": {
+                    "region": "us-east-1",
+                    "readOperationsPerSecond": 2000,
+                    "writeOperationsPerSecond": 500
+                }
+            }]
+        }))
+        .unwrap();
+    let task = handler
+        .update_crdb_local_properties(123, 456, &request)
+        .await
+        .unwrap();
+    assert_eq!(task.task_id.as_deref(), Some("task-regions"));
+}
+
 #[tokio::test]
 async fn test_error_handling_403() {
     let mock_server = MockServer::start().await;
@@ -899,3 +1224,39 @@ async fn test_error_handling_500() {
         _ => panic!("Expected InternalServerError error"),
     }
 }
+
+#[tokio::test]
+async fn test_database_modules_read() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/subscriptions/123/databases/456"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "databaseId": 456,
+            "name": "catalog",
+            "queryPerformanceFactor": "2x",
+            "modules": [
+                {"id": 1, "name": "RediSearch", "capabilityName": "Search and query", "version": "2.8.4", "parameters": {}}
+            ]
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let client = CloudClient::builder()
+        .api_key("test-key".to_string())
+        .api_secret("test-secret".to_string())
+        .base_url(mock_server.uri())
+        .build()
+        .unwrap();
+    let handler = DatabaseHandler::new(client);
+
+    let database = handler
+        .get_subscription_database_by_id(123, 456)
+        .await
+        .unwrap();
+    assert_eq!(database.query_performance_factor(), Some("2x"));
+    let modules = database.modules();
+    assert_eq!(modules.len(), 1);
+    assert_eq!(modules[0].name, "RediSearch");
+    assert_eq!(modules[0].extra["version"], "2.8.4");
+}
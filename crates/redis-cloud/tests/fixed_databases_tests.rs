@@ -1,3 +1,5 @@
+#![cfg(feature = "fixed")]
+
 use redis_cloud::{CloudClient, FixedDatabaseHandler};
 use serde_json::json;
 use wiremock::matchers::{header, method, path};
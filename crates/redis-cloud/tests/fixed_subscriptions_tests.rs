@@ -1,3 +1,5 @@
+#![cfg(feature = "fixed")]
+
 use redis_cloud::{CloudClient, FixedSubscriptionsHandler};
 use serde_json::json;
 use wiremock::matchers::{header, method, path, query_param};
@@ -50,6 +52,59 @@ async fn test_get_all_fixed_subscriptions_plans() {
     assert_eq!(plans.len(), 2);
 }
 
+#[tokio::test]
+async fn test_list_plans_typed() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/fixed/plans"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "plans": [
+                {
+                    "id": 1,
+                    "name": "Cache 250MB",
+                    "size": 250,
+                    "sizeMeasurementUnit": "MB",
+                    "provider": "AWS",
+                    "region": "us-east-1",
+                    "price": 5
+                },
+                {
+                    "id": 2,
+                    "name": "Cache 1GB",
+                    "size": 1.0,
+                    "datasetSize": 0.5,
+                    "sizeMeasurementUnit": "GB",
+                    "provider": "GCP",
+                    "region": "us-central1",
+                    "price": 22
+                }
+            ]
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let client = CloudClient::builder()
+        .api_key("test-key".to_string())
+        .api_secret("test-secret".to_string())
+        .base_url(mock_server.uri())
+        .build()
+        .unwrap();
+
+    let handler = FixedSubscriptionsHandler::new(client);
+    let plans = handler
+        .list_plans(None, None)
+        .await
+        .unwrap()
+        .plans()
+        .unwrap();
+
+    assert_eq!(plans.len(), 2);
+    assert_eq!(plans[0].provider.as_deref(), Some("AWS"));
+    assert_eq!(plans[0].dataset_size_bytes(), Some(250 * 1024 * 1024));
+    assert_eq!(plans[1].dataset_size_bytes(), Some(512 * 1024 * 1024));
+}
+
 #[tokio::test]
 async fn test_get_fixed_subscriptions_plans_by_subscription_id() {
     let mock_server = MockServer::start().await;
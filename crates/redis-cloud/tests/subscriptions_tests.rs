@@ -1,6 +1,6 @@
 use redis_cloud::{CloudClient, SubscriptionsHandler};
 use serde_json::json;
-use wiremock::matchers::{header, method, path, query_param};
+use wiremock::matchers::{body_json, body_partial_json, header, method, path, query_param};
 use wiremock::{Mock, MockServer, ResponseTemplate};
 
 #[tokio::test]
@@ -107,6 +107,45 @@ async fn test_create_subscription() {
     assert_eq!(result.task_id, Some("task-create-sub".to_string()));
 }
 
+#[tokio::test]
+async fn test_create_subscription_dry_run() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/subscriptions"))
+        .and(body_partial_json(
+            json!({"dryRun": true, "name": "planned"}),
+        ))
+        .respond_with(ResponseTemplate::new(202).set_body_json(json!({
+            "taskId": "task-dry-run",
+            "commandType": "subscriptionCreateRequest",
+            "status": "received"
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let client = CloudClient::builder()
+        .api_key("test-key".to_string())
+        .api_secret("test-secret".to_string())
+        .base_url(mock_server.uri())
+        .build()
+        .unwrap();
+
+    let handler = SubscriptionsHandler::new(client);
+    let mut request: redis_cloud::subscriptions::SubscriptionCreateRequest =
+        serde_json::from_value(json!({
+            "name": "planned",
+            "cloudProviders": [{"regions": [{"region": "us-east-1"}]}],
+            "databases": [{"name": "db1", "protocol": "redis"}]
+        }))
+        .unwrap();
+    request.dry_run = Some(true);
+
+    let result = handler.create_subscription(&request).await.unwrap();
+    assert_eq!(result.task_id, Some("task-dry-run".to_string()));
+}
+
 #[tokio::test]
 async fn test_get_redis_versions() {
     let mock_server = MockServer::start().await;
@@ -238,6 +277,119 @@ async fn test_update_subscription() {
     assert_eq!(result.task_id, Some("task-update-sub".to_string()));
 }
 
+#[tokio::test]
+async fn test_update_subscription_cmks() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("PUT"))
+        .and(path("/subscriptions/123/cmks"))
+        .and(body_partial_json(json!({
+            "deletionGracePeriod": "alerts-only",
+            "customerManagedKeys": [{
+                "resourceName": "arn:aws:kms:us-east-1:123456789012:key/1234abcd-12ab-34cd-56ef-1234567890ab",
+                "region": "us-east-1"
+            }]
+        })))
+        .respond_with(ResponseTemplate::new(202).set_body_json(json!({
+            "taskId": "task-cmk",
+            "commandType": "UPDATE_SUBSCRIPTION_CMKS",
+            "status": "received"
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let client = CloudClient::builder()
+        .api_key("test-key".to_string())
+        .api_secret("test-secret".to_string())
+        .base_url(mock_server.uri())
+        .build()
+        .unwrap();
+
+    let handler = SubscriptionsHandler::new(client);
+    let request = redis_cloud::subscriptions::SubscriptionUpdateCMKRequest {
+        subscription_id: None,
+        command_type: None,
+        deletion_grace_period: Some("alerts-only".to_string()),
+        customer_managed_keys: vec![redis_cloud::subscriptions::CustomerManagedKey {
+            resource_name:
+                "arn:aws:kms:us-east-1:123456789012:key/1234abcd-12ab-34cd-56ef-1234567890ab"
+                    .to_string(),
+            region: Some("us-east-1".to_string()),
+            extra: serde_json::Value::Null,
+        }],
+        extra: serde_json::Value::Null,
+    };
+
+    let result = handler
+        .update_subscription_cmks(123, &request)
+        .await
+        .unwrap();
+    assert_eq!(result.task_id, Some("task-cmk".to_string()));
+}
+
+#[tokio::test]
+async fn test_update_subscription_payment_method() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("PUT"))
+        .and(path("/subscriptions/123"))
+        .and(body_partial_json(json!({
+            "paymentMethodId": 42,
+            "paymentMethod": "credit-card"
+        })))
+        .respond_with(ResponseTemplate::new(202).set_body_json(json!({
+            "taskId": "task-payment",
+            "commandType": "UPDATE_SUBSCRIPTION",
+            "status": "received"
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let client = CloudClient::builder()
+        .api_key("test-key".to_string())
+        .api_secret("test-secret".to_string())
+        .base_url(mock_server.uri())
+        .build()
+        .unwrap();
+
+    let handler = SubscriptionsHandler::new(client);
+    let result = handler
+        .update_subscription_payment_method(123, 42)
+        .await
+        .unwrap();
+    assert_eq!(result.task_id, Some("task-payment".to_string()));
+}
+
+#[tokio::test]
+async fn test_update_subscription_name() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("PUT"))
+        .and(path("/subscriptions/123"))
+        .and(body_json(json!({"name": "production"})))
+        .respond_with(ResponseTemplate::new(202).set_body_json(json!({
+            "taskId": "task-rename",
+            "commandType": "UPDATE_SUBSCRIPTION",
+            "status": "received"
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let client = CloudClient::builder()
+        .api_key("test-key".to_string())
+        .api_secret("test-secret".to_string())
+        .base_url(mock_server.uri())
+        .build()
+        .unwrap();
+
+    let handler = SubscriptionsHandler::new(client);
+    let result = handler
+        .update_subscription_name(123, "production")
+        .await
+        .unwrap();
+    assert_eq!(result.task_id, Some("task-rename".to_string()));
+}
+
 #[tokio::test]
 async fn test_get_cidr_allowlist() {
     let mock_server = MockServer::start().await;
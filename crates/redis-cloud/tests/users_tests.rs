@@ -1,3 +1,5 @@
+#![cfg(feature = "users")]
+
 use redis_cloud::{CloudClient, UserHandler};
 use serde_json::json;
 use wiremock::matchers::{header, method, path};
@@ -4,6 +4,7 @@
 //! - Configure alert settings
 //! - Query alert history
 //! - Manage alert thresholds
+//! - Configure where alerts are delivered: the cluster's SMTP server and per-alert webhooks
 
 use crate::client::RestClient;
 use crate::error::Result;
@@ -192,6 +193,60 @@ pub struct ClusterAlertsSettings {
     pub extra: Value,
 }
 
+/// SMTP server the cluster sends alert emails through
+///
+/// These are fields of the cluster object, so reading and updating them goes through
+/// `/v1/cluster`. Fields left as `None` are not changed by an update.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SmtpSettings {
+    /// Whether the cluster sends alert emails at all
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email_alerts: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub smtp_host: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub smtp_port: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub smtp_username: Option<String>,
+    /// Write-only; the cluster does not return it
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub smtp_password: Option<String>,
+    /// `none`, `starttls` or `tls`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub smtp_tls_mode: Option<String>,
+    /// Deprecated in favour of `smtp_tls_mode`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub smtp_use_tls: Option<bool>,
+}
+
+impl ClusterAlertsSettings {
+    /// Every alert's settings by name, including alerts without a field of their own
+    pub fn entries(&self) -> Result<Vec<(String, ClusterAlertSettingsWithThreshold)>> {
+        let Value::Object(map) = serde_json::to_value(self)? else {
+            return Ok(Vec::new());
+        };
+        let mut entries = Vec::new();
+        for (name, value) in map {
+            // Entries that are not alert settings, such as flags, are left out
+            if let Ok(settings) = serde_json::from_value(value) {
+                entries.push((name, settings));
+            }
+        }
+        Ok(entries)
+    }
+
+    /// Settings built from alert entries, the inverse of [`ClusterAlertsSettings::entries`]
+    pub fn from_entries(
+        entries: impl IntoIterator<Item = (String, ClusterAlertSettingsWithThreshold)>,
+    ) -> Result<Self> {
+        let mut map = serde_json::Map::new();
+        for (name, settings) in entries {
+            map.insert(name, serde_json::to_value(settings)?);
+        }
+        Ok(serde_json::from_value(Value::Object(map))?)
+    }
+}
+
 /// Alert handler for managing alerts
 pub struct AlertHandler {
     client: RestClient,
@@ -288,6 +343,16 @@ impl AlertHandler {
             .await
     }
 
+    /// Get the SMTP settings alert emails are sent with
+    pub async fn get_smtp_settings(&self) -> Result<SmtpSettings> {
+        self.client.get("/v1/cluster").await
+    }
+
+    /// Update the SMTP settings, changing only the fields that are set
+    pub async fn update_smtp_settings(&self, settings: &SmtpSettings) -> Result<SmtpSettings> {
+        self.client.put("/v1/cluster", settings).await
+    }
+
     /// Clear/acknowledge an alert
     pub async fn clear(&self, uid: &str) -> Result<()> {
         self.client.delete(&format!("/v1/alerts/{}", uid)).await
@@ -359,6 +359,158 @@ pub struct DatabaseInfo {
     pub extra: Value,
 }
 
+impl DatabaseInfo {
+    /// Whether clients must connect with TLS, from `tls_mode` or the older `ssl` flag
+    pub fn tls_enabled(&self) -> bool {
+        self.tls_mode.as_deref() == Some("enabled") || self.ssl == Some(true)
+    }
+
+    /// The endpoints of the database, empty when the cluster reported none
+    pub fn endpoint_list(&self) -> &[EndpointInfo] {
+        self.endpoints.as_deref().unwrap_or_default()
+    }
+
+    /// The port clients connect to, preferring the first endpoint's port
+    pub fn connection_port(&self) -> Option<u16> {
+        self.endpoint_list()
+            .iter()
+            .find_map(|endpoint| endpoint.port)
+            .or(self.port)
+    }
+
+    /// The sync this database runs with its sources, if any
+    ///
+    /// Active-Active instances sync with their peers; a database with Replica Of sources syncs
+    /// from them.
+    pub fn sync_kind(&self) -> Option<SyncKind> {
+        if self.crdt == Some(true) || self.crdt_guid.is_some() {
+            Some(SyncKind::Crdt)
+        } else if self.replica_sources.as_ref().is_some_and(|s| !s.is_empty())
+            || self.sync_sources.as_ref().is_some_and(|s| !s.is_empty())
+        {
+            Some(SyncKind::Replica)
+        } else {
+            None
+        }
+    }
+
+    /// State of a sync, from `crdt_sync`, or `replica_sync` with the older `sync` as fallback
+    pub fn sync_state(&self, kind: SyncKind) -> Option<SyncState> {
+        let state = match kind {
+            SyncKind::Crdt => self.crdt_sync.as_deref(),
+            SyncKind::Replica => self.replica_sync.as_deref().or(self.sync.as_deref()),
+        }?;
+        serde_json::from_value(Value::String(state.to_string())).ok()
+    }
+
+    /// The database's access control settings
+    ///
+    /// `roles_permissions` entries that are not plain role to ACL bindings are kept as
+    /// [`RolePermissionEntry::Other`].
+    pub fn acl(&self) -> DatabaseAcl {
+        DatabaseAcl {
+            default_user: self.default_user,
+            roles_permissions: self.roles_permissions.as_ref().map(|entries| {
+                entries
+                    .iter()
+                    .map(|entry| {
+                        serde_json::from_value(entry.clone())
+                            .unwrap_or_else(|_| RolePermissionEntry::Other(entry.clone()))
+                    })
+                    .collect()
+            }),
+        }
+    }
+}
+
+/// Which sync of a database to control
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SyncKind {
+    /// Replica Of: the database syncs from one or more source databases
+    Replica,
+    /// Active-Active: the instance syncs with its peers
+    Crdt,
+}
+
+impl SyncKind {
+    /// The bdb field holding this sync's state
+    pub fn field(self) -> &'static str {
+        match self {
+            SyncKind::Replica => "replica_sync",
+            SyncKind::Crdt => "crdt_sync",
+        }
+    }
+}
+
+/// State of a database's sync (`replica_sync`, `crdt_sync`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SyncState {
+    Enabled,
+    Disabled,
+    Paused,
+    Stopped,
+    /// A state this version of the crate does not know
+    #[serde(other)]
+    Unknown,
+}
+
+/// Access control settings of a database
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DatabaseAcl {
+    /// Whether the default user (password-only `AUTH`) may connect
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default_user: Option<bool>,
+    /// Redis ACL applied to each role's users on this database; an update leaves the bindings
+    /// unchanged when `None`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub roles_permissions: Option<Vec<RolePermissionEntry>>,
+}
+
+impl DatabaseAcl {
+    /// The plain role to ACL bindings
+    pub fn bindings(&self) -> impl Iterator<Item = &RolePermission> {
+        self.roles_permissions
+            .iter()
+            .flatten()
+            .filter_map(|entry| match entry {
+                RolePermissionEntry::Binding(binding) => Some(binding),
+                RolePermissionEntry::Other(_) => None,
+            })
+    }
+}
+
+/// One entry of a database's `roles_permissions`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum RolePermissionEntry {
+    Binding(RolePermission),
+    /// An entry in a form this crate does not know, kept as is so an update sends it back
+    Other(Value),
+}
+
+impl RolePermissionEntry {
+    /// The role the entry applies to, if it names one
+    pub fn role_uid(&self) -> Option<u32> {
+        match self {
+            RolePermissionEntry::Binding(binding) => Some(binding.role_uid),
+            RolePermissionEntry::Other(entry) => entry
+                .get("role_uid")
+                .and_then(Value::as_u64)
+                .and_then(|uid| u32::try_from(uid).ok()),
+        }
+    }
+}
+
+/// Binding of a role to the Redis ACL its users get on a database
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RolePermission {
+    pub role_uid: u32,
+    pub redis_acl_uid: u32,
+}
+
 /// Database endpoint information
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EndpointInfo {
@@ -385,6 +537,15 @@ pub struct EndpointInfo {
     pub extra: Value,
 }
 
+impl EndpointInfo {
+    /// Host clients should use: the DNS name, or the first address when there is none
+    pub fn host(&self) -> Option<&str> {
+        self.dns_name
+            .as_deref()
+            .or_else(|| self.addr.as_ref()?.first().map(String::as_str))
+    }
+}
+
 /// Module configuration for database creation
 #[derive(Debug, Clone, Serialize, Deserialize, TypedBuilder)]
 pub struct ModuleConfig {
@@ -473,6 +634,16 @@ impl DatabaseHandler {
         self.client.get("/v1/bdbs").await
     }
 
+    /// List all databases, returning only the given fields of each
+    ///
+    /// The cluster leaves out every other field, which keeps responses small on clusters with
+    /// many databases. The objects are partial, so they are returned untyped.
+    pub async fn list_with_fields(&self, fields: &[&str]) -> Result<Vec<Value>> {
+        self.client
+            .get(&format!("/v1/bdbs?fields={}", fields.join(",")))
+            .await
+    }
+
     /// Get specific database info (BDB.INFO)
     pub async fn info(&self, uid: u32) -> Result<DatabaseInfo> {
         self.client.get(&format!("/v1/bdbs/{}", uid)).await
@@ -483,11 +654,53 @@ impl DatabaseHandler {
         self.info(uid).await
     }
 
+    /// Get the access control settings of a database
+    pub async fn get_acl(&self, uid: u32) -> Result<DatabaseAcl> {
+        Ok(self.info(uid).await?.acl())
+    }
+
+    /// Replace the access control settings of a database
+    ///
+    /// `roles_permissions` is replaced as a whole when given; each field is left unchanged when
+    /// `None`.
+    pub async fn update_acl(&self, uid: u32, acl: &DatabaseAcl) -> Result<DatabaseInfo> {
+        self.client.put(&format!("/v1/bdbs/{}", uid), acl).await
+    }
+
+    /// Set the state of a database's sync (BDB.UPDATE of `replica_sync` or `crdt_sync`)
+    pub async fn set_sync(
+        &self,
+        uid: u32,
+        kind: SyncKind,
+        state: SyncState,
+    ) -> Result<DatabaseInfo> {
+        let mut body = serde_json::Map::new();
+        body.insert(kind.field().to_string(), serde_json::to_value(state)?);
+        self.update(uid, Value::Object(body)).await
+    }
+
+    /// Pause a database's sync, keeping its sources configured
+    pub async fn pause_sync(&self, uid: u32, kind: SyncKind) -> Result<DatabaseInfo> {
+        self.set_sync(uid, kind, SyncState::Paused).await
+    }
+
+    /// Resume a paused database sync
+    pub async fn resume_sync(&self, uid: u32, kind: SyncKind) -> Result<DatabaseInfo> {
+        self.set_sync(uid, kind, SyncState::Enabled).await
+    }
+
     /// Create a new database (BDB.CREATE)
     pub async fn create(&self, request: CreateDatabaseRequest) -> Result<DatabaseInfo> {
         self.client.post("/v1/bdbs", &request).await
     }
 
+    /// Validate a database create request without creating it - POST /v1/bdbs/dry-run
+    ///
+    /// Takes the raw request so fields not modeled by [`CreateDatabaseRequest`] are validated too.
+    pub async fn create_dry_run(&self, request: Value) -> Result<Value> {
+        self.client.post("/v1/bdbs/dry-run", &request).await
+    }
+
     /// Update database configuration (BDB.UPDATE)
     pub async fn update(&self, uid: u32, updates: Value) -> Result<DatabaseInfo> {
         self.client
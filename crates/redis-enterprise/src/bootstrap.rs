@@ -36,14 +36,43 @@ pub struct ClusterBootstrap {
 }
 
 /// Node bootstrap configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct NodeBootstrap {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub paths: Option<NodePaths>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub identity: Option<NodeIdentity>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limits: Option<NodeLimits>,
+}
+
+/// How the node identifies itself to the cluster
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct NodeIdentity {
+    /// Internal address the node uses for cluster traffic
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub addr: Option<String>,
+    /// External addresses clients use to reach the node
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub external_addr: Option<Vec<String>>,
+    /// Rack or availability zone, for rack-aware clusters
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rack_id: Option<String>,
+}
+
+/// Resource limits of the node
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct NodeLimits {
+    /// Maximum number of database endpoints (listeners) on the node
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_listeners: Option<u32>,
+    /// Maximum number of shards on the node
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_redis_servers: Option<u32>,
 }
 
 /// Node paths configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct NodePaths {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub persistent_path: Option<String>,
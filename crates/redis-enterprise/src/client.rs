@@ -1,11 +1,39 @@
 //! REST API client implementation
 
 use crate::error::{RestError, Result};
+use crate::json_stream::ArrayElements;
 use reqwest::{Client, Response};
 use serde::{Serialize, de::DeserializeOwned};
 use std::sync::Arc;
-use std::time::Duration;
-use tracing::{debug, trace};
+use std::time::{Duration, Instant};
+use tokio::io::AsyncWriteExt;
+use tokio::sync::Semaphore;
+use tracing::{debug, trace, warn};
+
+/// Default limit for response bodies read into memory (128 MiB)
+pub const DEFAULT_MAX_BODY_SIZE: usize = 128 * 1024 * 1024;
+
+/// Default limit for requests in flight at once, shared by all clones of a client
+pub const DEFAULT_MAX_CONCURRENT_REQUESTS: usize = 8;
+
+/// One HTTP request made by the client, as reported to a [`RequestObserver`]
+#[derive(Debug, Clone)]
+pub struct RequestEvent {
+    pub method: &'static str,
+    /// Path relative to the base URL, including any query string
+    pub path: String,
+    /// Response status, or `None` when no response arrived
+    pub status: Option<u16>,
+    /// Time until the response headers arrived
+    pub elapsed: Duration,
+}
+
+/// Hook notified after every request, for metrics such as request counts and latency
+///
+/// Set it with [`EnterpriseClientBuilder::observer`].
+pub trait RequestObserver: Send + Sync + std::fmt::Debug {
+    fn on_request(&self, event: &RequestEvent);
+}
 
 // Legacy alias for backwards compatibility during migration
 pub type RestConfig = EnterpriseClientBuilder;
@@ -18,6 +46,12 @@ pub struct EnterpriseClientBuilder {
     password: Option<String>,
     timeout: Duration,
     insecure: bool,
+    tls_fingerprint_sha256: Option<String>,
+    max_body_size: usize,
+    observer: Option<Arc<dyn RequestObserver>>,
+    read_only: bool,
+    max_concurrent_requests: usize,
+    limiter: Option<Arc<Semaphore>>,
 }
 
 impl Default for EnterpriseClientBuilder {
@@ -28,6 +62,12 @@ impl Default for EnterpriseClientBuilder {
             password: None,
             timeout: Duration::from_secs(30),
             insecure: false,
+            tls_fingerprint_sha256: None,
+            max_body_size: DEFAULT_MAX_BODY_SIZE,
+            observer: None,
+            read_only: false,
+            max_concurrent_requests: DEFAULT_MAX_CONCURRENT_REQUESTS,
+            limiter: None,
         }
     }
 }
@@ -68,14 +108,83 @@ impl EnterpriseClientBuilder {
         self
     }
 
+    /// Trust only the server certificate with this SHA-256 fingerprint
+    ///
+    /// The fingerprint is hex, with or without `:` separators, as printed by `openssl x509
+    /// -noout -fingerprint -sha256`. The certificate's issuer and host name are not checked, so a
+    /// self-signed certificate works without [`insecure`](Self::insecure), but any other
+    /// certificate fails the connection. Takes precedence over `insecure`, and needs the
+    /// `rustls-tls` feature; [`build`](Self::build) fails without it.
+    pub fn tls_fingerprint_sha256(mut self, fingerprint: impl Into<String>) -> Self {
+        self.tls_fingerprint_sha256 = Some(fingerprint.into());
+        self
+    }
+
+    /// Set the maximum response body size in bytes
+    ///
+    /// Responses larger than this fail with [`RestError::ResponseTooLarge`] instead of
+    /// being buffered. For streamed requests the limit applies to each array element.
+    pub fn max_body_size(mut self, bytes: usize) -> Self {
+        self.max_body_size = bytes;
+        self
+    }
+
+    /// Report every request to `observer`
+    pub fn observer(mut self, observer: Arc<dyn RequestObserver>) -> Self {
+        self.observer = Some(observer);
+        self
+    }
+
+    /// Refuse POST, PUT, PATCH and DELETE requests
+    ///
+    /// They fail with [`RestError::ReadOnly`] without being sent.
+    pub fn read_only(mut self, read_only: bool) -> Self {
+        self.read_only = read_only;
+        self
+    }
+
+    /// Limit how many requests are in flight at once
+    ///
+    /// Further requests wait for a slot, so code that fans out over many resources cannot flood
+    /// the API. The limit is shared by all clones of the client and counts a request until its
+    /// response headers arrive, but separately built clients each get their own; use
+    /// [`request_limiter`](Self::request_limiter) to share one limit between them. Defaults to
+    /// [`DEFAULT_MAX_CONCURRENT_REQUESTS`]; 0 is treated as 1.
+    pub fn max_concurrent_requests(mut self, limit: usize) -> Self {
+        self.max_concurrent_requests = limit.max(1);
+        self
+    }
+
+    /// Take request slots from `limiter` instead of a semaphore of the client's own
+    ///
+    /// Clients built with the same limiter count against one limit, whichever API they call.
+    /// Overrides [`max_concurrent_requests`](Self::max_concurrent_requests).
+    pub fn request_limiter(mut self, limiter: Arc<Semaphore>) -> Self {
+        self.limiter = Some(limiter);
+        self
+    }
+
     /// Build the client
     pub fn build(self) -> Result<EnterpriseClient> {
         let username = self.username.unwrap_or_default();
         let password = self.password.unwrap_or_default();
 
-        let client_builder = Client::builder()
-            .timeout(self.timeout)
-            .danger_accept_invalid_certs(self.insecure);
+        let client_builder = Client::builder().timeout(self.timeout);
+        #[cfg(any(feature = "rustls-tls", feature = "native-tls"))]
+        let client_builder = client_builder.danger_accept_invalid_certs(self.insecure);
+        let client_builder = match self.tls_fingerprint_sha256.as_deref() {
+            #[cfg(feature = "rustls-tls")]
+            Some(fingerprint) => client_builder.use_preconfigured_tls(crate::tls::pinned_config(
+                crate::tls::parse_fingerprint(fingerprint)?,
+            )?),
+            #[cfg(not(feature = "rustls-tls"))]
+            Some(_) => {
+                return Err(RestError::ValidationError(
+                    "Certificate pinning needs the rustls-tls feature".to_string(),
+                ));
+            }
+            None => client_builder,
+        };
 
         let client = client_builder
             .build()
@@ -86,6 +195,12 @@ impl EnterpriseClientBuilder {
             username,
             password,
             timeout: self.timeout,
+            max_body_size: self.max_body_size,
+            observer: self.observer,
+            read_only: self.read_only,
+            limiter: self
+                .limiter
+                .unwrap_or_else(|| Arc::new(Semaphore::new(self.max_concurrent_requests))),
             client: Arc::new(client),
         })
     }
@@ -98,6 +213,10 @@ pub struct EnterpriseClient {
     username: String,
     password: String,
     timeout: Duration,
+    max_body_size: usize,
+    observer: Option<Arc<dyn RequestObserver>>,
+    read_only: bool,
+    limiter: Arc<Semaphore>,
     client: Arc<Client>,
 }
 
@@ -144,11 +263,35 @@ impl EnterpriseClient {
         let url = format!("{}{}", self.base_url, path);
         debug!("GET {}", url);
 
+        let request = self
+            .client
+            .get(&url)
+            .basic_auth(&self.username, Some(&self.password));
         let response = self
+            .send("GET", path, request)
+            .await
+            .map_err(|e| self.map_reqwest_error(e, &url))?;
+
+        trace!("Response status: {}", response.status());
+        self.handle_response(response).await
+    }
+
+    /// Make a GET request with URL-encoded query parameters
+    pub async fn get_with_query<T: DeserializeOwned, Q: Serialize + ?Sized>(
+        &self,
+        path: &str,
+        query: &Q,
+    ) -> Result<T> {
+        let url = format!("{}{}", self.base_url, path);
+        debug!("GET {} (with query)", url);
+
+        let request = self
             .client
             .get(&url)
-            .basic_auth(&self.username, Some(&self.password))
-            .send()
+            .query(query)
+            .basic_auth(&self.username, Some(&self.password));
+        let response = self
+            .send("GET", path, request)
             .await
             .map_err(|e| self.map_reqwest_error(e, &url))?;
 
@@ -161,22 +304,20 @@ impl EnterpriseClient {
         let url = format!("{}{}", self.base_url, path);
         debug!("GET {} (text)", url);
 
-        let response = self
+        let request = self
             .client
             .get(&url)
-            .basic_auth(&self.username, Some(&self.password))
-            .send()
+            .basic_auth(&self.username, Some(&self.password));
+        let response = self
+            .send("GET", path, request)
             .await
             .map_err(|e| self.map_reqwest_error(e, &url))?;
 
         trace!("Response status: {}", response.status());
 
         if response.status().is_success() {
-            let text = response
-                .text()
-                .await
-                .map_err(crate::error::RestError::RequestFailed)?;
-            Ok(text)
+            let body = self.read_body(response).await?;
+            String::from_utf8(body).map_err(|e| RestError::ParseError(e.to_string()))
         } else {
             let status = response.status();
             let error_text = response
@@ -190,18 +331,103 @@ impl EnterpriseClient {
         }
     }
 
+    /// Make a GET request for a binary body, writing it to `writer` as it arrives
+    ///
+    /// Returns the number of bytes written. Unlike the other requests this is not bound by the
+    /// response size limit, since the body never has to fit in memory; use it for archives such
+    /// as debuginfo packages.
+    pub async fn get_to_writer<W: tokio::io::AsyncWrite + Unpin>(
+        &self,
+        path: &str,
+        writer: &mut W,
+    ) -> Result<u64> {
+        let url = format!("{}{}", self.base_url, path);
+        debug!("GET {} (download)", url);
+
+        let request = self
+            .client
+            .get(&url)
+            .basic_auth(&self.username, Some(&self.password));
+        let mut response = self
+            .send("GET", path, request)
+            .await
+            .map_err(|e| self.map_reqwest_error(e, &url))?;
+
+        trace!("Response status: {}", response.status());
+        if !response.status().is_success() {
+            return Err(Self::error_from_response(response).await);
+        }
+
+        let mut written = 0;
+        while let Some(chunk) = response
+            .chunk()
+            .await
+            .map_err(|e| self.map_reqwest_error(e, &url))?
+        {
+            writer.write_all(&chunk).await?;
+            written += chunk.len() as u64;
+        }
+        Ok(written)
+    }
+
+    /// Make a GET request for an endpoint returning a JSON array, handing each element
+    /// to `on_item` as it arrives instead of buffering the whole response
+    ///
+    /// Returns the number of elements processed. Use this for endpoints that can return
+    /// very large lists, such as event logs over long time ranges.
+    pub async fn get_stream<T, F>(&self, path: &str, mut on_item: F) -> Result<usize>
+    where
+        T: DeserializeOwned,
+        F: FnMut(T) -> Result<()>,
+    {
+        let url = format!("{}{}", self.base_url, path);
+        debug!("GET {} (stream)", url);
+
+        let request = self
+            .client
+            .get(&url)
+            .basic_auth(&self.username, Some(&self.password));
+        let mut response = self
+            .send("GET", path, request)
+            .await
+            .map_err(|e| self.map_reqwest_error(e, &url))?;
+
+        trace!("Response status: {}", response.status());
+        if !response.status().is_success() {
+            return Err(Self::error_from_response(response).await);
+        }
+
+        let mut elements = ArrayElements::new(self.max_body_size);
+        let mut count = 0;
+        while let Some(chunk) = response
+            .chunk()
+            .await
+            .map_err(|e| self.map_reqwest_error(e, &url))?
+        {
+            for element in elements.push(&chunk)? {
+                on_item(deserialize_response(&element)?)?;
+                count += 1;
+            }
+        }
+        elements.finish()?;
+
+        Ok(count)
+    }
+
     /// Make a POST request
     pub async fn post<B: Serialize, T: DeserializeOwned>(&self, path: &str, body: &B) -> Result<T> {
+        self.ensure_writable("POST", path)?;
         let url = format!("{}{}", self.base_url, path);
         debug!("POST {}", url);
         trace!("Request body: {:?}", serde_json::to_value(body).ok());
 
-        let response = self
+        let request = self
             .client
             .post(&url)
             .basic_auth(&self.username, Some(&self.password))
-            .json(body)
-            .send()
+            .json(body);
+        let response = self
+            .send("POST", path, request)
             .await
             .map_err(|e| self.map_reqwest_error(e, &url))?;
 
@@ -211,16 +437,18 @@ impl EnterpriseClient {
 
     /// Make a PUT request
     pub async fn put<B: Serialize, T: DeserializeOwned>(&self, path: &str, body: &B) -> Result<T> {
+        self.ensure_writable("PUT", path)?;
         let url = format!("{}{}", self.base_url, path);
         debug!("PUT {}", url);
         trace!("Request body: {:?}", serde_json::to_value(body).ok());
 
-        let response = self
+        let request = self
             .client
             .put(&url)
             .basic_auth(&self.username, Some(&self.password))
-            .json(body)
-            .send()
+            .json(body);
+        let response = self
+            .send("PUT", path, request)
             .await
             .map_err(|e| self.map_reqwest_error(e, &url))?;
 
@@ -230,14 +458,16 @@ impl EnterpriseClient {
 
     /// Make a DELETE request
     pub async fn delete(&self, path: &str) -> Result<()> {
+        self.ensure_writable("DELETE", path)?;
         let url = format!("{}{}", self.base_url, path);
         debug!("DELETE {}", url);
 
-        let response = self
+        let request = self
             .client
             .delete(&url)
-            .basic_auth(&self.username, Some(&self.password))
-            .send()
+            .basic_auth(&self.username, Some(&self.password));
+        let response = self
+            .send("DELETE", path, request)
             .await
             .map_err(|e| self.map_reqwest_error(e, &url))?;
 
@@ -271,16 +501,18 @@ impl EnterpriseClient {
 
     /// POST request for actions that return no content
     pub async fn post_action<B: Serialize>(&self, path: &str, body: &B) -> Result<()> {
+        self.ensure_writable("POST", path)?;
         let url = format!("{}{}", self.base_url, path);
         debug!("POST {}", url);
         trace!("Request body: {:?}", serde_json::to_value(body).ok());
 
-        let response = self
+        let request = self
             .client
             .post(&url)
             .basic_auth(&self.username, Some(&self.password))
-            .json(body)
-            .send()
+            .json(body);
+        let response = self
+            .send("POST", path, request)
             .await
             .map_err(|e| self.map_reqwest_error(e, &url))?;
 
@@ -302,27 +534,44 @@ impl EnterpriseClient {
         self.clone()
     }
 
+    /// Base URL requests are sent to
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// Copy of this client with the same credentials and settings but a different base URL,
+    /// e.g. to reach the `/v1/local` endpoints of a specific node
+    pub fn with_base_url(&self, base_url: impl Into<String>) -> Self {
+        Self {
+            base_url: base_url.into(),
+            ..self.clone()
+        }
+    }
+
     /// POST request for bootstrap - handles empty response
     pub async fn post_bootstrap<B: Serialize>(
         &self,
         path: &str,
         body: &B,
     ) -> Result<serde_json::Value> {
+        self.ensure_writable("POST", path)?;
         let url = format!("{}{}", self.base_url, path);
 
-        let response = self
+        let request = self
             .client
             .post(&url)
             .basic_auth(&self.username, Some(&self.password))
-            .json(body)
-            .send()
+            .json(body);
+        let response = self
+            .send("POST", path, request)
             .await
             .map_err(|e| self.map_reqwest_error(e, &url))?;
 
         let status = response.status();
         if status.is_success() {
             // Try to parse JSON, but if empty/invalid, return success
-            let text = response.text().await.unwrap_or_default();
+            let body = self.read_body(response).await?;
+            let text = String::from_utf8_lossy(&body);
             if text.is_empty() || text.trim().is_empty() {
                 Ok(serde_json::json!({"status": "success"}))
             } else {
@@ -344,21 +593,21 @@ impl EnterpriseClient {
         path: &str,
         body: serde_json::Value,
     ) -> Result<serde_json::Value> {
+        self.ensure_writable("PATCH", path)?;
         let url = format!("{}{}", self.base_url, path);
-        let response = self
+        let request = self
             .client
             .patch(&url)
             .basic_auth(&self.username, Some(&self.password))
-            .json(&body)
-            .send()
+            .json(&body);
+        let response = self
+            .send("PATCH", path, request)
             .await
             .map_err(|e| self.map_reqwest_error(e, &url))?;
 
         if response.status().is_success() {
-            response
-                .json()
-                .await
-                .map_err(|e| RestError::ParseError(e.to_string()))
+            let body = self.read_body(response).await?;
+            serde_json::from_slice(&body).map_err(|e| RestError::ParseError(e.to_string()))
         } else {
             let status = response.status();
             let text = response.text().await.unwrap_or_default();
@@ -371,12 +620,14 @@ impl EnterpriseClient {
 
     /// Execute raw DELETE request returning any response body
     pub async fn delete_raw(&self, path: &str) -> Result<serde_json::Value> {
+        self.ensure_writable("DELETE", path)?;
         let url = format!("{}{}", self.base_url, path);
-        let response = self
+        let request = self
             .client
             .delete(&url)
-            .basic_auth(&self.username, Some(&self.password))
-            .send()
+            .basic_auth(&self.username, Some(&self.password));
+        let response = self
+            .send("DELETE", path, request)
             .await
             .map_err(|e| self.map_reqwest_error(e, &url))?;
 
@@ -384,10 +635,8 @@ impl EnterpriseClient {
             if response.content_length() == Some(0) {
                 Ok(serde_json::json!({"status": "deleted"}))
             } else {
-                response
-                    .json()
-                    .await
-                    .map_err(|e| RestError::ParseError(e.to_string()))
+                let body = self.read_body(response).await?;
+                serde_json::from_slice(&body).map_err(|e| RestError::ParseError(e.to_string()))
             }
         } else {
             let status = response.status();
@@ -401,6 +650,13 @@ impl EnterpriseClient {
 
     /// Map reqwest errors to more specific error messages
     fn map_reqwest_error(&self, error: reqwest::Error, url: &str) -> RestError {
+        #[cfg(feature = "rustls-tls")]
+        if let Some(tls_error) = crate::tls::handshake_error(&error) {
+            return RestError::ConnectionError(format!(
+                "TLS handshake with {} failed: {}",
+                url, tls_error
+            ));
+        }
         if error.is_connect() {
             RestError::ConnectionError(format!(
                 "Failed to connect to {}: Connection refused or host unreachable. Check if the Redis Enterprise server is running and accessible.",
@@ -431,23 +687,116 @@ impl EnterpriseClient {
         }
     }
 
+    /// Fail with [`RestError::ReadOnly`] on a read-only client
+    fn ensure_writable(&self, method: &str, path: &str) -> Result<()> {
+        if self.read_only {
+            return Err(RestError::ReadOnly {
+                method: method.to_string(),
+                path: path.to_string(),
+            });
+        }
+        Ok(())
+    }
+
     /// Handle HTTP response
+    /// Send a request, reporting it to the observer when one is set
+    async fn send(
+        &self,
+        method: &'static str,
+        path: &str,
+        request: reqwest::RequestBuilder,
+    ) -> std::result::Result<Response, reqwest::Error> {
+        // The semaphore is never closed, so acquiring only waits for a free slot
+        let _permit = self.limiter.acquire().await.ok();
+        let start = Instant::now();
+        let response = request.send().await;
+        if let Some(observer) = &self.observer {
+            observer.on_request(&RequestEvent {
+                method,
+                path: path.to_string(),
+                status: response.as_ref().ok().map(|r| r.status().as_u16()),
+                elapsed: start.elapsed(),
+            });
+        }
+        response
+    }
+
     async fn handle_response<T: DeserializeOwned>(&self, response: Response) -> Result<T> {
         if response.status().is_success() {
-            response.json::<T>().await.map_err(Into::into)
+            let body = self.read_body(response).await?;
+            deserialize_response(&body)
         } else {
-            let status = response.status();
-            let text = response.text().await.unwrap_or_default();
+            Err(Self::error_from_response(response).await)
+        }
+    }
+
+    /// Build the error for a non-success response
+    async fn error_from_response(response: Response) -> RestError {
+        let status = response.status();
+        let text = response.text().await.unwrap_or_default();
+
+        match status.as_u16() {
+            401 => RestError::Unauthorized,
+            404 => RestError::NotFound,
+            500..=599 => RestError::ServerError(text),
+            _ => RestError::ApiError {
+                code: status.as_u16(),
+                message: text,
+            },
+        }
+    }
 
-            match status.as_u16() {
-                401 => Err(RestError::Unauthorized),
-                404 => Err(RestError::NotFound),
-                500..=599 => Err(RestError::ServerError(text)),
-                _ => Err(RestError::ApiError {
-                    code: status.as_u16(),
-                    message: text,
-                }),
+    /// Read a response body chunk by chunk, failing once it exceeds the configured limit
+    async fn read_body(&self, mut response: Response) -> Result<Vec<u8>> {
+        let too_large = || RestError::ResponseTooLarge {
+            limit: self.max_body_size,
+        };
+        if response
+            .content_length()
+            .is_some_and(|len| len > self.max_body_size as u64)
+        {
+            return Err(too_large());
+        }
+
+        let mut body = Vec::new();
+        while let Some(chunk) = response.chunk().await? {
+            if body.len() + chunk.len() > self.max_body_size {
+                return Err(too_large());
             }
+            body.extend_from_slice(&chunk);
+        }
+        Ok(body)
+    }
+}
+
+/// Deserialize a response body, reporting the path of the field that did not match `T`
+///
+/// Bodies that are not JSON at all fail with the plain serde error; bodies that are valid JSON
+/// but do not fit the type keep the raw value so callers can still show it.
+fn deserialize_response<T: DeserializeOwned>(body: &[u8]) -> Result<T> {
+    let mut deserializer = serde_json::Deserializer::from_slice(body);
+    match serde_path_to_error::deserialize::<_, T>(&mut deserializer) {
+        Ok(value) => {
+            deserializer.end()?;
+            Ok(value)
+        }
+        Err(err) => {
+            let Ok(raw) = serde_json::from_slice::<serde_json::Value>(body) else {
+                return Err(err.into_inner().into());
+            };
+            let path = err.path().to_string();
+            let message = err.inner().to_string();
+            warn!(
+                "Response did not match {} at '{}': {}",
+                std::any::type_name::<T>(),
+                path,
+                message
+            );
+            Err(RestError::DeserializationError {
+                path,
+                message,
+                raw: Box::new(raw),
+            })
         }
     }
 }
@@ -2,7 +2,7 @@
 //!
 //! ## Overview
 //! - Query cluster info, settings, topology, and license
-//! - Manage nodes (join, remove, maintenance mode)
+//! - Manage nodes (join, remove, maintenance mode with quorum checks)
 //! - Configure cluster policies and services
 //! - Handle certificates and LDAP configuration
 //!
@@ -43,12 +43,20 @@
 //! // Remove a node
 //! let action = cluster.remove_node(3).await?;
 //! println!("Removal started: {:?}", action);
+//!
+//! // Nodes in maintenance mode, and whether taking node 2 out of service keeps a majority
+//! let status = cluster.maintenance_status().await?;
+//! for node in status.in_maintenance() {
+//!     println!("Node {} is in maintenance", node.uid);
+//! }
+//! println!("Quorum kept: {}", status.quorum_after(&[2]).is_met());
 //! # Ok(())
 //! # }
 //! ```
 
 use crate::client::RestClient;
 use crate::error::Result;
+use crate::nodes::{Node, NodeActionResponse};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use typed_builder::TypedBuilder;
@@ -65,6 +73,135 @@ pub struct ClusterActionResponse {
     pub extra: Value,
 }
 
+/// Cluster recovery plan, describing what a recover action would restore
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClusterRecoveryPlan {
+    /// Overall recovery state of the cluster
+    pub status: Option<String>,
+    /// Databases the recovery would bring back
+    #[serde(default)]
+    pub bdbs: Vec<RecoveryPlanDatabase>,
+    /// Additional fields from the response
+    #[serde(flatten)]
+    pub extra: Value,
+}
+
+/// One database in a cluster recovery plan
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecoveryPlanDatabase {
+    pub uid: Option<u32>,
+    pub name: Option<String>,
+    /// Where the data is recovered from, such as persistence files or a replica
+    pub source: Option<String>,
+    pub status: Option<String>,
+    /// Additional fields from the response
+    #[serde(flatten)]
+    pub extra: Value,
+}
+
+/// Options for putting a node into maintenance mode
+///
+/// By default the node's shards, primaries and replicas alike, are migrated to other nodes before
+/// it is taken out of service.
+///
+/// # Example
+///
+/// ```
+/// use redis_enterprise::MaintenanceOnRequest;
+///
+/// let request = MaintenanceOnRequest::builder().keep_slave_shards(true).build();
+/// ```
+#[derive(Debug, Clone, Default, Serialize, TypedBuilder)]
+pub struct MaintenanceOnRequest {
+    /// Leave replica shards on the node and only migrate primaries
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[builder(default, setter(strip_option))]
+    pub keep_slave_shards: Option<bool>,
+    /// Drop replicas that cannot be placed on another node instead of failing
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[builder(default, setter(strip_option))]
+    pub evict_ha_replica: Option<bool>,
+    /// Drop Active-Active replicas that cannot be placed on another node instead of failing
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[builder(default, setter(strip_option))]
+    pub evict_active_active_replica: Option<bool>,
+}
+
+/// Options for taking a node out of maintenance mode
+#[derive(Debug, Clone, Default, Serialize, TypedBuilder)]
+pub struct MaintenanceOffRequest {
+    /// Leave the shards where they are instead of moving them back to the node
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[builder(default, setter(strip_option))]
+    pub skip_shards_restore: Option<bool>,
+    /// Snapshot to restore the shards from, instead of the one taken when maintenance started
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[builder(default, setter(into, strip_option))]
+    pub snapshot_name: Option<String>,
+}
+
+/// Maintenance state of one node
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NodeMaintenanceStatus {
+    pub uid: u32,
+    pub addr: Option<String>,
+    /// Node status, such as `active` or `down`
+    pub status: String,
+    pub in_maintenance: bool,
+    pub accept_servers: Option<bool>,
+    pub shard_count: Option<u32>,
+}
+
+impl NodeMaintenanceStatus {
+    /// Whether the node is up and serving, so it counts towards the quorum
+    pub fn is_available(&self) -> bool {
+        self.status == "active" && !self.in_maintenance
+    }
+}
+
+/// Maintenance state of every node in the cluster
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ClusterMaintenanceStatus {
+    pub nodes: Vec<NodeMaintenanceStatus>,
+}
+
+impl ClusterMaintenanceStatus {
+    /// Nodes that are currently in maintenance mode
+    pub fn in_maintenance(&self) -> impl Iterator<Item = &NodeMaintenanceStatus> {
+        self.nodes.iter().filter(|node| node.in_maintenance)
+    }
+
+    /// How putting `node_uids` into maintenance would leave the cluster's quorum
+    pub fn quorum_after(&self, node_uids: &[u32]) -> MaintenanceQuorum {
+        MaintenanceQuorum {
+            total: self.nodes.len(),
+            required: self.nodes.len() / 2 + 1,
+            available: self
+                .nodes
+                .iter()
+                .filter(|node| node.is_available() && !node_uids.contains(&node.uid))
+                .count(),
+        }
+    }
+}
+
+/// Node counts deciding whether the cluster keeps a majority of serving nodes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MaintenanceQuorum {
+    /// Nodes in the cluster
+    pub total: usize,
+    /// Serving nodes needed for a majority
+    pub required: usize,
+    /// Nodes that would still be serving
+    pub available: usize,
+}
+
+impl MaintenanceQuorum {
+    pub fn is_met(&self) -> bool {
+        self.available >= self.required
+    }
+}
+
 /// Node information
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ClusterNode {
@@ -275,6 +412,43 @@ impl ClusterHandler {
         Ok(serde_json::json!({"message": format!("Node {} removed", node_uid)}))
     }
 
+    /// Put a node into maintenance mode, migrating its shards away
+    /// - POST /v1/nodes/{uid}/actions/maintenance_on
+    pub async fn enable_node_maintenance(
+        &self,
+        node_uid: u32,
+        request: &MaintenanceOnRequest,
+    ) -> Result<NodeActionResponse> {
+        self.client
+            .post(
+                &format!("/v1/nodes/{}/actions/maintenance_on", node_uid),
+                request,
+            )
+            .await
+    }
+
+    /// Take a node out of maintenance mode, restoring its shards
+    /// - POST /v1/nodes/{uid}/actions/maintenance_off
+    pub async fn disable_node_maintenance(
+        &self,
+        node_uid: u32,
+        request: &MaintenanceOffRequest,
+    ) -> Result<NodeActionResponse> {
+        self.client
+            .post(
+                &format!("/v1/nodes/{}/actions/maintenance_off", node_uid),
+                request,
+            )
+            .await
+    }
+
+    /// Which nodes are in maintenance mode, from the node list and `/v1/nodes/status`
+    pub async fn maintenance_status(&self) -> Result<ClusterMaintenanceStatus> {
+        let nodes: Vec<Node> = self.client.get("/v1/nodes").await?;
+        let statuses: Value = self.client.get("/v1/nodes/status").await?;
+        Ok(maintenance_status_of(&nodes, &statuses))
+    }
+
     /// Reset cluster to factory defaults (CLUSTER.RESET) - DANGEROUS
     pub async fn reset(&self) -> Result<ClusterActionResponse> {
         self.client
@@ -293,6 +467,11 @@ impl ClusterHandler {
 
     // raw variant removed: use recover()
 
+    /// Get the recovery plan for a cluster that needs recovering
+    pub async fn recovery_plan(&self) -> Result<ClusterRecoveryPlan> {
+        self.client.get("/v1/cluster/recovery").await
+    }
+
     /// Get cluster settings (CLUSTER.SETTINGS)
     pub async fn settings(&self) -> Result<Value> {
         self.client.get("/v1/cluster/settings").await
@@ -420,6 +599,36 @@ impl ClusterHandler {
     }
 }
 
+/// Combine the node list with the per-node status map keyed by node UID
+///
+/// A node is in maintenance when either its status or its `node_status` says so, or when it
+/// carries a `maintenance_mode` flag; clusters differ in which of these they report.
+fn maintenance_status_of(nodes: &[Node], statuses: &Value) -> ClusterMaintenanceStatus {
+    let nodes = nodes
+        .iter()
+        .map(|node| {
+            let node_status = statuses
+                .get(node.uid.to_string())
+                .and_then(|status| status.get("node_status"))
+                .and_then(Value::as_str);
+            let in_maintenance = [Some(node.status.as_str()), node_status]
+                .into_iter()
+                .flatten()
+                .any(|status| status.contains("maintenance"))
+                || node.extra.get("maintenance_mode").and_then(Value::as_bool) == Some(true);
+            NodeMaintenanceStatus {
+                uid: node.uid,
+                addr: node.addr.clone(),
+                status: node.status.clone(),
+                in_maintenance,
+                accept_servers: node.accept_servers,
+                shard_count: node.shard_count,
+            }
+        })
+        .collect();
+    ClusterMaintenanceStatus { nodes }
+}
+
 /// Node information
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NodeInfo {
@@ -27,6 +27,16 @@ pub struct CmSettings {
     pub slave_ha_grace_period: Option<u32>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub max_simultaneous_backups: Option<u32>,
+    /// Maximum number of shards upgraded in parallel during cluster upgrades
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parallel_shards_upgrade: Option<u32>,
+    /// Cluster manager UI timezone
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timezone: Option<String>,
+    /// Named cluster manager timeouts, kept as returned since values are not
+    /// all plain integers
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timeouts: Option<Value>,
 
     #[serde(flatten)]
     pub extra: Value,
@@ -4,18 +4,34 @@
 //! - Track CRDB async operations
 //! - Query task status
 //! - Manage replication tasks
+//! - Wait for a task to finish
 
 use crate::client::RestClient;
-use crate::error::Result;
+use crate::error::{RestError, Result};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use std::time::{Duration, Instant};
 use typed_builder::TypedBuilder;
 
+/// Statuses after which a CRDB task no longer changes
+pub const TERMINAL_TASK_STATUSES: &[&str] = &[
+    "finished",
+    "completed",
+    "failed",
+    "error",
+    "canceled",
+    "cancelled",
+    "aborted",
+];
+
 /// CRDB task information
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CrdbTask {
+    #[serde(alias = "id")]
     pub task_id: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
     pub crdb_guid: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
     pub task_type: String,
     pub status: String,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -26,6 +42,40 @@ pub struct CrdbTask {
     pub end_time: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub error: Option<String>,
+    /// Errors reported by the participating clusters
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub errors: Vec<CrdbTaskError>,
+
+    #[serde(flatten)]
+    pub extra: Value,
+}
+
+impl CrdbTask {
+    /// Whether the task has reached a final status
+    pub fn is_terminal(&self) -> bool {
+        TERMINAL_TASK_STATUSES
+            .iter()
+            .any(|status| self.status.eq_ignore_ascii_case(status))
+    }
+
+    /// Whether the task ended without completing
+    pub fn is_failed(&self) -> bool {
+        self.is_terminal()
+            && !["finished", "completed"]
+                .iter()
+                .any(|status| self.status.eq_ignore_ascii_case(status))
+    }
+}
+
+/// Error reported by one participating cluster of a CRDB task
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CrdbTaskError {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cluster_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error_code: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
 
     #[serde(flatten)]
     pub extra: Value,
@@ -77,6 +127,35 @@ impl CrdbTasksHandler {
             .await
     }
 
+    /// Poll a task until it reaches a terminal status
+    ///
+    /// Returns the task in its final state, whether it finished or failed; check
+    /// [`CrdbTask::is_failed`]. Fails with [`RestError::Timeout`] if the task is still running
+    /// after `timeout`.
+    pub async fn wait(
+        &self,
+        task_id: &str,
+        poll_interval: Duration,
+        timeout: Duration,
+    ) -> Result<CrdbTask> {
+        let started = Instant::now();
+        loop {
+            let task = self.get(task_id).await?;
+            if task.is_terminal() {
+                return Ok(task);
+            }
+            if started.elapsed() >= timeout {
+                return Err(RestError::Timeout(format!(
+                    "CRDB task {} (still {} after {}s)",
+                    task_id,
+                    task.status,
+                    timeout.as_secs()
+                )));
+            }
+            tokio::time::sleep(poll_interval).await;
+        }
+    }
+
     /// Get tasks for a specific CRDB
     pub async fn list_by_crdb(&self, crdb_guid: &str) -> Result<Vec<CrdbTask>> {
         self.client
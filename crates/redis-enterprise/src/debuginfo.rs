@@ -108,6 +108,21 @@ impl DebugInfoHandler {
             .await
     }
 
+    /// Stream the cluster-wide debuginfo package (a tar.gz) into `writer`, returning its size
+    ///
+    /// GET /v1/debuginfo/all, or /v1/debuginfo/all/bdb/{uid} for a single database
+    pub async fn download_all_to<W: tokio::io::AsyncWrite + Unpin>(
+        &self,
+        bdb_uid: Option<u32>,
+        writer: &mut W,
+    ) -> Result<u64> {
+        let path = match bdb_uid {
+            Some(uid) => format!("/v1/debuginfo/all/bdb/{}", uid),
+            None => "/v1/debuginfo/all".to_string(),
+        };
+        self.client.get_to_writer(&path, writer).await
+    }
+
     /// Get node debug info - GET /v1/debuginfo/node
     pub async fn node(&self) -> Result<Value> {
         self.client.get("/v1/debuginfo/node").await
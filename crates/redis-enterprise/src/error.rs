@@ -2,7 +2,11 @@
 
 use thiserror::Error;
 
+/// Errors returned by the REST client
+///
+/// New variants can be added in minor releases, so matches need a wildcard arm.
 #[derive(Error, Debug)]
+#[non_exhaustive]
 pub enum RestError {
     #[error("Invalid URL: {0}")]
     InvalidUrl(String),
@@ -22,6 +26,15 @@ pub enum RestError {
     #[error("Parse error: {0}")]
     ParseError(String),
 
+    #[error("Unexpected response format at '{path}': {message}")]
+    DeserializationError {
+        /// Path of the field that failed, e.g. `shards[0].status`
+        path: String,
+        message: String,
+        /// The response body that could not be deserialized
+        raw: Box<serde_json::Value>,
+    },
+
     #[error("Connection error: {0}")]
     ConnectionError(String),
 
@@ -39,6 +52,18 @@ pub enum RestError {
 
     #[error("Server error: {0}")]
     ServerError(String),
+
+    #[error("Response body exceeds the {limit} byte limit")]
+    ResponseTooLarge { limit: usize },
+
+    #[error("{method} {path} refused: the client is read-only")]
+    ReadOnly { method: String, path: String },
+
+    #[error("Timed out waiting for {0}")]
+    Timeout(String),
+
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
 }
 
 impl RestError {
@@ -48,6 +73,14 @@ impl RestError {
             || matches!(self, RestError::ApiError { code, .. } if *code == 404)
     }
 
+    /// Raw response body, if the response did not match the expected type
+    pub fn raw_response(&self) -> Option<&serde_json::Value> {
+        match self {
+            RestError::DeserializationError { raw, .. } => Some(raw),
+            _ => None,
+        }
+    }
+
     /// Check if this is an authentication error
     pub fn is_unauthorized(&self) -> bool {
         matches!(self, RestError::Unauthorized)
@@ -0,0 +1,240 @@
+//! Incremental parsing of top-level JSON arrays
+//!
+//! Splits a JSON array arriving in arbitrary chunks into the raw bytes of its elements,
+//! so each element can be deserialized and handed off without buffering the whole body.
+
+use crate::error::{RestError, Result};
+
+/// Splits a streamed JSON array into its elements
+pub(crate) struct ArrayElements {
+    buf: Vec<u8>,
+    scanned: usize,
+    element_start: Option<usize>,
+    /// Closing bracket expected for each open array or object, innermost last
+    closers: Vec<u8>,
+    started: bool,
+    /// A top-level comma was seen and the next element has not started yet
+    awaiting_element: bool,
+    finished: bool,
+    in_string: bool,
+    escaped: bool,
+    max_element_size: usize,
+}
+
+impl ArrayElements {
+    /// Create a splitter that rejects any single element larger than `max_element_size`
+    pub(crate) fn new(max_element_size: usize) -> Self {
+        Self {
+            buf: Vec::new(),
+            scanned: 0,
+            element_start: None,
+            closers: Vec::new(),
+            started: false,
+            awaiting_element: false,
+            finished: false,
+            in_string: false,
+            escaped: false,
+            max_element_size,
+        }
+    }
+
+    /// Feed the next chunk, returning the raw bytes of every element it completes
+    pub(crate) fn push(&mut self, chunk: &[u8]) -> Result<Vec<Vec<u8>>> {
+        self.buf.extend_from_slice(chunk);
+        let mut elements = Vec::new();
+
+        for i in self.scanned..self.buf.len() {
+            let b = self.buf[i];
+
+            if self.finished {
+                if !b.is_ascii_whitespace() {
+                    return Err(parse_error("unexpected data after JSON array"));
+                }
+                continue;
+            }
+            if !self.started {
+                match b {
+                    b'[' => {
+                        self.started = true;
+                        self.closers.push(b']');
+                    }
+                    b if b.is_ascii_whitespace() => {}
+                    _ => return Err(parse_error("expected a JSON array")),
+                }
+                continue;
+            }
+            if self.in_string {
+                if self.escaped {
+                    self.escaped = false;
+                } else if b == b'\\' {
+                    self.escaped = true;
+                } else if b == b'"' {
+                    self.in_string = false;
+                }
+                continue;
+            }
+
+            let depth = self.closers.len();
+            match b {
+                b',' if depth == 1 => {
+                    elements.push(self.take_element(i)?);
+                    self.awaiting_element = true;
+                }
+                b']' | b'}' => {
+                    if self.closers.pop() != Some(b) {
+                        return Err(parse_error(&format!(
+                            "unexpected '{}' in JSON array",
+                            b as char
+                        )));
+                    }
+                    if self.closers.is_empty() {
+                        if self.awaiting_element && self.element_start.is_none() {
+                            return Err(parse_error("trailing comma in JSON array"));
+                        }
+                        if self.element_start.is_some() {
+                            elements.push(self.take_element(i)?);
+                        }
+                        self.finished = true;
+                    }
+                }
+                b if b.is_ascii_whitespace() => {}
+                _ => {
+                    if depth == 1 && self.element_start.is_none() {
+                        self.element_start = Some(i);
+                        self.awaiting_element = false;
+                    }
+                    match b {
+                        b'"' => self.in_string = true,
+                        b'[' => self.closers.push(b']'),
+                        b'{' => self.closers.push(b'}'),
+                        _ => {}
+                    }
+                }
+            }
+        }
+
+        // Drop everything before the element in progress
+        let keep_from = self.element_start.unwrap_or(self.buf.len());
+        self.buf.drain(..keep_from);
+        self.element_start = self.element_start.map(|_| 0);
+        self.scanned = self.buf.len();
+
+        if self.buf.len() > self.max_element_size {
+            return Err(RestError::ResponseTooLarge {
+                limit: self.max_element_size,
+            });
+        }
+
+        Ok(elements)
+    }
+
+    /// Check that the array was closed once the body has been fully read
+    pub(crate) fn finish(&self) -> Result<()> {
+        if self.finished {
+            Ok(())
+        } else {
+            Err(parse_error("unexpected end of JSON array"))
+        }
+    }
+
+    fn take_element(&mut self, end: usize) -> Result<Vec<u8>> {
+        let start = self
+            .element_start
+            .take()
+            .ok_or_else(|| parse_error("empty element in JSON array"))?;
+        Ok(self.buf[start..end].trim_ascii_end().to_vec())
+    }
+}
+
+fn parse_error(message: &str) -> RestError {
+    RestError::ParseError(format!("Invalid streamed response: {}", message))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{Value, json};
+
+    fn split(chunks: &[&str]) -> Result<Vec<Value>> {
+        let mut splitter = ArrayElements::new(1024);
+        let mut values = Vec::new();
+        for chunk in chunks {
+            for element in splitter.push(chunk.as_bytes())? {
+                values.push(serde_json::from_slice(&element).unwrap());
+            }
+        }
+        splitter.finish()?;
+        Ok(values)
+    }
+
+    #[test]
+    fn test_splits_elements_across_chunk_boundaries() {
+        let values = split(&[
+            " [{\"id\": 1, \"msg\": \"a,b]\"}",
+            ", {\"id\": 2, \"nested\": [1, {\"x\": \"}\"}]}, ",
+            "3, \"q\\\"uo",
+            "te\", null]\n",
+        ])
+        .unwrap();
+
+        assert_eq!(
+            values,
+            vec![
+                json!({"id": 1, "msg": "a,b]"}),
+                json!({"id": 2, "nested": [1, {"x": "}"}]}),
+                json!(3),
+                json!("q\"uote"),
+                json!(null),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_empty_array() {
+        assert!(split(&["[", " ]"]).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_rejects_non_array_and_truncated_bodies() {
+        assert!(split(&["{\"a\": 1}"]).is_err());
+        assert!(split(&["[1, 2"]).is_err());
+        assert!(split(&["[1,,2]"]).is_err());
+        assert!(split(&["[1] x"]).is_err());
+    }
+
+    #[test]
+    fn test_rejects_trailing_comma() {
+        for chunks in [&["[{\"id\": 1},]"][..], &["[1, 2,", " ", "]"][..]] {
+            let error = split(chunks).unwrap_err();
+            assert!(
+                matches!(&error, RestError::ParseError(message) if message.contains("trailing comma")),
+                "{:?}: {:?}",
+                chunks,
+                error
+            );
+        }
+    }
+
+    #[test]
+    fn test_rejects_mismatched_brackets() {
+        for body in ["[1}", "[{\"a\": 1]]", "[[1}]", "[1, 2}, 3]"] {
+            let error = split(&[body]).unwrap_err();
+            assert!(
+                matches!(&error, RestError::ParseError(message) if message.contains("unexpected")),
+                "{}: {:?}",
+                body,
+                error
+            );
+        }
+    }
+
+    #[test]
+    fn test_rejects_oversized_element() {
+        let mut splitter = ArrayElements::new(8);
+        assert!(splitter.push(b"[1, 2, ").unwrap().len() == 2);
+        assert!(matches!(
+            splitter.push(b"\"0123456789\""),
+            Err(RestError::ResponseTooLarge { limit: 8 })
+        ));
+    }
+}
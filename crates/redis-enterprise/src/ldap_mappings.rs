@@ -44,6 +44,48 @@ pub struct CreateLdapMappingRequest {
     pub role_uids: Option<Vec<u32>>,
 }
 
+/// Update LDAP mapping request; only the fields that are set are changed
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use redis_enterprise::UpdateLdapMappingRequest;
+///
+/// let request = UpdateLdapMappingRequest::builder()
+///     .role_uids(vec![3])
+///     .build();
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TypedBuilder)]
+pub struct UpdateLdapMappingRequest {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[builder(default, setter(into, strip_option))]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[builder(default, setter(into, strip_option))]
+    pub dn: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[builder(default, setter(into, strip_option))]
+    pub role: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[builder(default, setter(into, strip_option))]
+    pub email: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[builder(default, setter(strip_option))]
+    pub role_uids: Option<Vec<u32>>,
+}
+
+impl From<CreateLdapMappingRequest> for UpdateLdapMappingRequest {
+    fn from(request: CreateLdapMappingRequest) -> Self {
+        UpdateLdapMappingRequest {
+            name: Some(request.name),
+            dn: Some(request.dn),
+            role: Some(request.role),
+            email: request.email,
+            role_uids: request.role_uids,
+        }
+    }
+}
+
 /// LDAP configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LdapConfig {
@@ -102,9 +144,16 @@ impl LdapMappingHandler {
     }
 
     /// Update an existing LDAP mapping
-    pub async fn update(&self, uid: u32, request: CreateLdapMappingRequest) -> Result<LdapMapping> {
+    ///
+    /// Accepts a full [`CreateLdapMappingRequest`] or an [`UpdateLdapMappingRequest`] with only
+    /// the fields to change.
+    pub async fn update(
+        &self,
+        uid: u32,
+        request: impl Into<UpdateLdapMappingRequest>,
+    ) -> Result<LdapMapping> {
         self.client
-            .put(&format!("/v1/ldap_mappings/{}", uid), &request)
+            .put(&format!("/v1/ldap_mappings/{}", uid), &request.into())
             .await
     }
 
@@ -305,6 +305,32 @@
 //! | `LogsHandler` | Logs | cluster and database logs |
 //! | `ActionHandler` | Actions | v1/v2 workflows |
 //!
+//! # Feature Flags
+//!
+//! All features are enabled by default. Turn off default features and pick the ones you need
+//! to build a slimmer client:
+//!
+//! | Feature | Enables |
+//! |---------|---------|
+//! | `rustls-tls` | HTTPS through rustls |
+//! | `native-tls` | HTTPS through the platform TLS library (OpenSSL, Secure Transport, SChannel) |
+//! | `crdb` | Active-Active databases and CRDB tasks |
+//! | `ldap` | LDAP mappings |
+//! | `ocsp` | OCSP configuration and status |
+//! | `diagnostics` | Debug info collection and diagnostics |
+//! | `migrations` | Database migrations |
+//! | `job-scheduler` | Scheduled jobs |
+//! | `usage-report` | Usage reports |
+//!
+//! Databases, cluster, nodes, users, roles, ACLs, stats, alerts and the other core endpoints are
+//! always available. The `insecure` builder option needs one of the TLS features, and
+//! `tls_fingerprint_sha256` needs `rustls-tls`.
+//!
+//! ```toml
+//! [dependencies]
+//! redis-enterprise = { version = "0.2", default-features = false, features = ["rustls-tls"] }
+//! ```
+//!
 //! # Production Best Practices
 //!
 //! - **Connection Pooling**: The client reuses HTTP connections automatically
@@ -333,21 +359,30 @@ pub mod bootstrap;
 pub mod client;
 pub mod cluster;
 pub mod cm_settings;
+#[cfg(feature = "crdb")]
 pub mod crdb;
+#[cfg(feature = "crdb")]
 pub mod crdb_tasks;
+#[cfg(feature = "diagnostics")]
 pub mod debuginfo;
+#[cfg(feature = "diagnostics")]
 pub mod diagnostics;
 pub mod endpoints;
 pub mod error;
+#[cfg(feature = "job-scheduler")]
 pub mod job_scheduler;
+mod json_stream;
 pub mod jsonschema;
+#[cfg(feature = "ldap")]
 pub mod ldap_mappings;
 pub mod license;
 pub mod local;
 pub mod logs;
+#[cfg(feature = "migrations")]
 pub mod migrations;
 pub mod modules;
 pub mod nodes;
+#[cfg(feature = "ocsp")]
 pub mod ocsp;
 pub mod proxies;
 pub mod redis_acls;
@@ -356,7 +391,10 @@ pub mod services;
 pub mod shards;
 pub mod stats;
 pub mod suffixes;
+#[cfg(feature = "rustls-tls")]
+mod tls;
 pub mod types;
+#[cfg(feature = "usage-report")]
 pub mod usage_report;
 pub mod users;
 
@@ -364,12 +402,13 @@ pub mod users;
 mod lib_tests;
 
 // Core client and error types
-pub use client::{EnterpriseClient, EnterpriseClientBuilder};
+pub use client::{EnterpriseClient, EnterpriseClientBuilder, RequestEvent, RequestObserver};
 pub use error::{RestError, Result};
 
 // Database management
 pub use bdb::{
-    BdbHandler, CreateDatabaseRequest, CreateDatabaseRequestBuilder, Database, ModuleConfig,
+    BdbHandler, CreateDatabaseRequest, CreateDatabaseRequestBuilder, Database, DatabaseAcl,
+    ModuleConfig, RolePermission, RolePermissionEntry, SyncKind, SyncState,
 };
 
 // Database groups
@@ -377,11 +416,15 @@ pub use bdb_groups::{BdbGroup, BdbGroupsHandler};
 
 // Cluster management
 pub use cluster::{
-    BootstrapRequest, ClusterHandler, ClusterInfo, ClusterNode, LicenseInfo, NodeInfo,
+    BootstrapRequest, ClusterActionResponse, ClusterHandler, ClusterInfo, ClusterMaintenanceStatus,
+    ClusterNode, ClusterRecoveryPlan, LicenseInfo, MaintenanceOffRequest, MaintenanceOnRequest,
+    MaintenanceQuorum, NodeInfo, NodeMaintenanceStatus, RecoveryPlanDatabase,
 };
 
 // Node management
-pub use nodes::{Node, NodeActionRequest, NodeHandler, NodeStats};
+pub use nodes::{
+    Node, NodeActionRequest, NodeActionResponse, NodeHandler, NodeStats, NodeUpdateRequest,
+};
 
 // User management
 pub use users::{CreateUserRequest, Role, RoleHandler, UpdateUserRequest, User, UserHandler};
@@ -393,16 +436,17 @@ pub use modules::{Module, ModuleHandler, UploadModuleRequest};
 pub use actions::{Action, ActionHandler};
 
 // Logs
-pub use logs::{LogEntry, LogsHandler, LogsQuery};
+pub use logs::{LogEntry, LogOrder, LogsHandler, LogsQuery};
 
 // Active-Active databases
+#[cfg(feature = "crdb")]
 pub use crdb::{Crdb, CrdbHandler, CrdbInstance, CreateCrdbInstance, CreateCrdbRequest};
 
 // Statistics
 pub use stats::{StatsHandler, StatsInterval, StatsQuery, StatsResponse};
 
 // Alerts
-pub use alerts::{Alert, AlertHandler, AlertSettings};
+pub use alerts::{Alert, AlertHandler, AlertSettings, SmtpSettings};
 
 // Redis ACLs
 pub use redis_acls::{CreateRedisAclRequest, RedisAcl, RedisAclHandler};
@@ -414,11 +458,14 @@ pub use shards::{Shard, ShardHandler, ShardStats};
 pub use proxies::{Proxy, ProxyHandler, ProxyStats};
 
 // LDAP mappings
+#[cfg(feature = "ldap")]
 pub use ldap_mappings::{
     CreateLdapMappingRequest, LdapConfig, LdapMapping, LdapMappingHandler, LdapServer,
+    UpdateLdapMappingRequest,
 };
 
 // OCSP
+#[cfg(feature = "ocsp")]
 pub use ocsp::{OcspConfig, OcspHandler, OcspStatus, OcspTestResult};
 
 // Local endpoints
@@ -427,19 +474,22 @@ pub use local::LocalHandler;
 // Bootstrap
 pub use bootstrap::{
     BootstrapConfig, BootstrapHandler, BootstrapStatus, ClusterBootstrap, CredentialsBootstrap,
-    NodeBootstrap, NodePaths,
+    NodeBootstrap, NodeIdentity, NodeLimits, NodePaths,
 };
 
 // Cluster Manager settings
 pub use cm_settings::{CmSettings, CmSettingsHandler};
 
 // CRDB tasks
-pub use crdb_tasks::{CrdbTask, CrdbTasksHandler, CreateCrdbTaskRequest};
+#[cfg(feature = "crdb")]
+pub use crdb_tasks::{CrdbTask, CrdbTaskError, CrdbTasksHandler, CreateCrdbTaskRequest};
 
 // Debug info
+#[cfg(feature = "diagnostics")]
 pub use debuginfo::{DebugInfoHandler, DebugInfoRequest, DebugInfoStatus, TimeRange};
 
 // Diagnostics
+#[cfg(feature = "diagnostics")]
 pub use diagnostics::{
     DiagnosticReport, DiagnosticRequest, DiagnosticResult, DiagnosticSummary, DiagnosticsHandler,
 };
@@ -448,6 +498,7 @@ pub use diagnostics::{
 pub use endpoints::{Endpoint, EndpointStats, EndpointsHandler};
 
 // Job scheduler
+#[cfg(feature = "job-scheduler")]
 pub use job_scheduler::{
     CreateScheduledJobRequest, JobExecution, JobSchedulerHandler, ScheduledJob,
 };
@@ -459,6 +510,7 @@ pub use jsonschema::JsonSchemaHandler;
 pub use license::{License, LicenseHandler, LicenseUpdateRequest, LicenseUsage};
 
 // Migrations
+#[cfg(feature = "migrations")]
 pub use migrations::{CreateMigrationRequest, Migration, MigrationEndpoint, MigrationsHandler};
 
 // Roles
@@ -473,6 +525,7 @@ pub use services::{
 pub use suffixes::{CreateSuffixRequest, Suffix, SuffixesHandler};
 
 // Usage report
+#[cfg(feature = "usage-report")]
 pub use usage_report::{
     DatabaseUsage, NodeUsage, UsageReport, UsageReportConfig, UsageReportHandler, UsageSummary,
 };
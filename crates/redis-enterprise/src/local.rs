@@ -7,7 +7,7 @@
 
 use crate::client::RestClient;
 use crate::error::Result;
-use serde_json::Value;
+use serde_json::{Value, json};
 
 pub struct LocalHandler {
     client: RestClient,
@@ -32,4 +32,30 @@ impl LocalHandler {
     pub async fn services_update(&self, body: Value) -> Result<Value> {
         self.client.post("/v1/local/services", &body).await
     }
+
+    /// Restart services on this node - POST /v1/local/services
+    ///
+    /// Local endpoints act on the node the client is connected to; use
+    /// [`RestClient::with_base_url`] to target a specific node.
+    pub async fn restart_services(&self, services: &[&str]) -> Result<Value> {
+        self.services_command("restart", services).await
+    }
+
+    /// Stop services on this node - POST /v1/local/services
+    pub async fn stop_services(&self, services: &[&str]) -> Result<Value> {
+        self.services_command("stop", services).await
+    }
+
+    /// Start services on this node - POST /v1/local/services
+    pub async fn start_services(&self, services: &[&str]) -> Result<Value> {
+        self.services_command("start", services).await
+    }
+
+    async fn services_command(&self, command: &str, services: &[&str]) -> Result<Value> {
+        self.services_update(json!({
+            "command": command,
+            "services": services,
+        }))
+        .await
+    }
 }
@@ -11,24 +11,83 @@ use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
 /// Log entry
+///
+/// Cluster event logs report the event type and severity rather than a level and message, and
+/// give UIDs as strings; both shapes are accepted.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LogEntry {
+    #[serde(default)]
     pub id: u64,
     pub time: String,
+    #[serde(default, alias = "severity")]
     pub level: String,
     pub component: Option<String>,
+    #[serde(default)]
     pub message: String,
+    #[serde(default, deserialize_with = "uid_from_string_or_number")]
     pub node_uid: Option<u32>,
+    #[serde(default, deserialize_with = "uid_from_string_or_number")]
     pub bdb_uid: Option<u32>,
     pub user: Option<String>,
+    /// Event type, e.g. `bdb_name_updated` or `node_status_changed`
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub event_type: Option<String>,
+    /// UID of the user that caused the event
+    #[serde(
+        default,
+        deserialize_with = "uid_from_string_or_number",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub originator_uid: Option<u32>,
+    /// Name of the user that caused the event
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub originator_username: Option<String>,
 
     #[serde(flatten)]
     pub extra: Value,
 }
 
+fn uid_from_string_or_number<'de, D>(deserializer: D) -> std::result::Result<Option<u32>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    use serde::de::Error;
+    match Option::<Value>::deserialize(deserializer)? {
+        None | Some(Value::Null) => Ok(None),
+        Some(Value::Number(n)) => n
+            .as_u64()
+            .and_then(|n| u32::try_from(n).ok())
+            .map(Some)
+            .ok_or_else(|| D::Error::custom(format!("invalid uid {}", n))),
+        Some(Value::String(s)) => s
+            .parse()
+            .map(Some)
+            .map_err(|_| D::Error::custom(format!("invalid uid '{}'", s))),
+        Some(other) => Err(D::Error::custom(format!("invalid uid {}", other))),
+    }
+}
+
+/// Order of returned log entries by time
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogOrder {
+    /// Oldest first
+    Asc,
+    /// Newest first
+    Desc,
+}
+
 /// Logs query parameters
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Default, Serialize)]
 pub struct LogsQuery {
+    /// Only entries at or after this ISO 8601 time
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stime: Option<String>,
+    /// Only entries at or before this ISO 8601 time
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub etime: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub order: Option<LogOrder>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub limit: Option<u32>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -41,6 +100,10 @@ pub struct LogsQuery {
     pub node_uid: Option<u32>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub bdb_uid: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub originator_uid: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub originator_username: Option<String>,
 }
 
 /// Logs handler for querying event logs
@@ -64,6 +127,23 @@ impl LogsHandler {
         }
     }
 
+    /// Stream event logs, calling `on_entry` for each entry as it is received
+    ///
+    /// Returns the number of entries processed.
+    pub async fn stream<F>(&self, query: Option<LogsQuery>, on_entry: F) -> Result<usize>
+    where
+        F: FnMut(LogEntry) -> Result<()>,
+    {
+        let path = match query {
+            Some(q) => format!(
+                "/v1/logs?{}",
+                serde_urlencoded::to_string(&q).unwrap_or_default()
+            ),
+            None => "/v1/logs".to_string(),
+        };
+        self.client.get_stream(&path, on_entry).await
+    }
+
     /// Get specific log entry
     pub async fn get(&self, id: u64) -> Result<LogEntry> {
         self.client.get(&format!("/v1/logs/{}", id)).await
@@ -14,22 +14,103 @@ use serde_json::Value;
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Module {
     pub uid: String,
+    /// Module name; the API calls it `module_name`
+    #[serde(alias = "module_name")]
     pub name: String,
+    /// Module version; the API reports it as a number such as `20612`
+    #[serde(deserialize_with = "string_or_number")]
     pub version: String,
+    /// Version as `major.minor.patch`
     pub semantic_version: Option<String>,
+    pub display_name: Option<String>,
     pub author: Option<String>,
     pub description: Option<String>,
     pub homepage: Option<String>,
     pub license: Option<String>,
     pub command_line_args: Option<String>,
+    /// Features the module supports, such as `replica_of` or `crdb`
     pub capabilities: Option<Vec<String>>,
+    /// Lowest Redis version the module runs on
     pub min_redis_version: Option<String>,
     pub min_redis_pack_version: Option<String>,
+    /// Redis version the module was built for
+    pub compatible_redis_version: Option<String>,
+    /// Whether the module ships with Redis Enterprise
+    pub is_bundled: Option<bool>,
 
     #[serde(flatten)]
     pub extra: Value,
 }
 
+impl Module {
+    /// The semantic version, falling back to the plain version
+    pub fn semver(&self) -> &str {
+        self.semantic_version.as_deref().unwrap_or(&self.version)
+    }
+
+    /// Whether the module can be loaded into a database running Redis `redis_version`
+    ///
+    /// Modules without a minimum Redis version are assumed compatible.
+    pub fn supports_redis(&self, redis_version: &str) -> bool {
+        match &self.min_redis_version {
+            Some(min) => compare_versions(min, redis_version) != std::cmp::Ordering::Greater,
+            None => true,
+        }
+    }
+
+    /// Whether the module lists `capability`, ignoring case
+    pub fn has_capability(&self, capability: &str) -> bool {
+        self.capabilities
+            .iter()
+            .flatten()
+            .any(|c| c.eq_ignore_ascii_case(capability))
+    }
+}
+
+/// Compare dotted versions component by component; missing components count as 0
+///
+/// Non-numeric suffixes such as `-rc1` are ignored, so `7.2` equals `7.2.0`.
+pub fn compare_versions(a: &str, b: &str) -> std::cmp::Ordering {
+    let parse = |version: &str| -> Vec<u64> {
+        version
+            .trim()
+            .trim_start_matches('v')
+            .split('.')
+            .map(|part| {
+                part.chars()
+                    .take_while(char::is_ascii_digit)
+                    .collect::<String>()
+                    .parse()
+                    .unwrap_or(0)
+            })
+            .collect()
+    };
+    let (a, b) = (parse(a), parse(b));
+    (0..a.len().max(b.len()))
+        .map(|i| {
+            a.get(i)
+                .copied()
+                .unwrap_or(0)
+                .cmp(&b.get(i).copied().unwrap_or(0))
+        })
+        .find(|ordering| ordering.is_ne())
+        .unwrap_or(std::cmp::Ordering::Equal)
+}
+
+fn string_or_number<'de, D>(deserializer: D) -> std::result::Result<String, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    match Value::deserialize(deserializer)? {
+        Value::String(s) => Ok(s),
+        Value::Number(n) => Ok(n.to_string()),
+        other => Err(serde::de::Error::custom(format!(
+            "expected a string or number, got {}",
+            other
+        ))),
+    }
+}
+
 /// Module upload request
 #[derive(Debug, Serialize)]
 pub struct UploadModuleRequest {
@@ -69,6 +69,9 @@ pub struct Node {
     pub rack_id: Option<String>,
     pub second_rack_id: Option<String>,
 
+    /// Path of the node's recovery files (`ccs-redis.rdb` and database configuration)
+    pub recovery_path: Option<String>,
+
     /// Shard information
     pub shard_count: Option<u32>,
     pub shard_list: Option<Vec<u32>>,
@@ -125,6 +128,42 @@ pub struct NodeActionRequest {
     pub node_uid: Option<u32>,
 }
 
+/// Settings of a node that can be changed; unset fields are left as they are
+///
+/// # Example
+///
+/// ```
+/// use redis_enterprise::NodeUpdateRequest;
+///
+/// let request = NodeUpdateRequest::builder()
+///     .external_addr(vec!["203.0.113.5".to_string()])
+///     .rack_id("us-east-1a")
+///     .build();
+/// ```
+#[derive(Debug, Clone, Default, Serialize, TypedBuilder)]
+pub struct NodeUpdateRequest {
+    /// Whether the node accepts new shards
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[builder(default, setter(strip_option))]
+    pub accept_servers: Option<bool>,
+    /// External IP addresses of the node; an empty list removes them
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[builder(default, setter(strip_option))]
+    pub external_addr: Option<Vec<String>>,
+    /// Rack or zone of the node, for rack-aware clusters
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[builder(default, setter(into, strip_option))]
+    pub rack_id: Option<String>,
+    /// Second rack, for clusters spread over two racks per zone
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[builder(default, setter(into, strip_option))]
+    pub second_rack_id: Option<String>,
+    /// Path of the node's recovery files
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[builder(default, setter(into, strip_option))]
+    pub recovery_path: Option<String>,
+}
+
 /// Node handler for executing node commands
 pub struct NodeHandler {
     client: RestClient,
@@ -155,6 +194,13 @@ impl NodeHandler {
             .await
     }
 
+    /// Update node settings
+    pub async fn update_settings(&self, uid: u32, request: &NodeUpdateRequest) -> Result<Node> {
+        self.client
+            .put(&format!("/v1/nodes/{}", uid), request)
+            .await
+    }
+
     /// Remove node from cluster
     pub async fn remove(&self, uid: u32) -> Result<()> {
         self.client.delete(&format!("/v1/nodes/{}", uid)).await
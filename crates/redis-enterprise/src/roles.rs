@@ -6,7 +6,7 @@
 //! - Query role assignments
 
 use crate::client::RestClient;
-use crate::error::Result;
+use crate::error::{RestError, Result};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use typed_builder::TypedBuilder;
@@ -113,6 +113,42 @@ impl RolesHandler {
         self.client.delete(&format!("/v1/roles/{}", uid)).await
     }
 
+    /// Resolve role names or numeric UIDs to UIDs, in the given order
+    ///
+    /// Names match exactly, or case-insensitively if no role has the exact name. The role list
+    /// is only fetched when at least one entry is not a number.
+    pub async fn resolve_uids(&self, roles: &[impl AsRef<str>]) -> Result<Vec<u32>> {
+        let mut known: Option<Vec<RoleInfo>> = None;
+        let mut uids = Vec::with_capacity(roles.len());
+        for role in roles {
+            let role = role.as_ref().trim();
+            if let Ok(uid) = role.parse::<u32>() {
+                uids.push(uid);
+                continue;
+            }
+            if known.is_none() {
+                known = Some(self.list().await?);
+            }
+            let listed = known.as_deref().unwrap_or_default();
+            let found = listed
+                .iter()
+                .find(|r| r.name == role)
+                .or_else(|| listed.iter().find(|r| r.name.eq_ignore_ascii_case(role)));
+            match found {
+                Some(found) => uids.push(found.uid),
+                None => {
+                    let names: Vec<&str> = listed.iter().map(|r| r.name.as_str()).collect();
+                    return Err(RestError::ValidationError(format!(
+                        "Unknown role '{}'; available roles: {}",
+                        role,
+                        names.join(", ")
+                    )));
+                }
+            }
+        }
+        Ok(uids)
+    }
+
     /// Get built-in roles
     pub async fn built_in(&self) -> Result<Vec<RoleInfo>> {
         self.client.get("/v1/roles/builtin").await
@@ -100,27 +100,33 @@ impl ServicesHandler {
             .await
     }
 
-    /// Restart service
-    pub async fn restart(&self, service_id: &str) -> Result<ServiceStatus> {
-        self.client
-            .post(
-                &format!("/v1/services/{}/restart", service_id),
-                &Value::Null,
-            )
-            .await
+    /// Restart a service on every node - POST /v1/services
+    ///
+    /// Returns the per-service results the cluster reports. To act on a single node, use the
+    /// node-local service control API ([`crate::local::LocalHandler::restart_services`]).
+    pub async fn restart(&self, service_id: &str) -> Result<Value> {
+        self.services_command("restart", &[service_id]).await
     }
 
-    /// Stop service
-    pub async fn stop(&self, service_id: &str) -> Result<ServiceStatus> {
-        self.client
-            .post(&format!("/v1/services/{}/stop", service_id), &Value::Null)
-            .await
+    /// Stop a service on every node - POST /v1/services
+    pub async fn stop(&self, service_id: &str) -> Result<Value> {
+        self.services_command("stop", &[service_id]).await
+    }
+
+    /// Start a service on every node - POST /v1/services
+    pub async fn start(&self, service_id: &str) -> Result<Value> {
+        self.services_command("start", &[service_id]).await
     }
 
-    /// Start service
-    pub async fn start(&self, service_id: &str) -> Result<ServiceStatus> {
+    async fn services_command(&self, command: &str, services: &[&str]) -> Result<Value> {
         self.client
-            .post(&format!("/v1/services/{}/start", service_id), &Value::Null)
+            .post(
+                "/v1/services",
+                &serde_json::json!({
+                    "command": command,
+                    "services": services,
+                }),
+            )
             .await
     }
 
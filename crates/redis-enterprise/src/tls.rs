@@ -0,0 +1,203 @@
+//! Certificate pinning by SHA-256 fingerprint
+//!
+//! A pinned client trusts exactly one certificate: the one whose DER encoding hashes to the
+//! configured fingerprint. The chain, the issuer and the host name are not checked, which is what
+//! makes pinning useful for self-signed cluster certificates, but the handshake signature still
+//! is, so the server must hold the certificate's private key. Any other certificate fails the
+//! handshake.
+
+use crate::error::{RestError, Result};
+use rustls::client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier};
+use rustls::crypto::{self, CryptoProvider};
+use rustls::pki_types::{CertificateDer, ServerName, UnixTime};
+use rustls::{CertificateError, ClientConfig, DigitallySignedStruct, OtherError, SignatureScheme};
+use sha2::{Digest, Sha256};
+use std::fmt;
+use std::sync::Arc;
+
+/// Parse a SHA-256 fingerprint written as hex, with or without `:` separators
+///
+/// Accepts the `openssl x509 -fingerprint -sha256` form, optionally with its `SHA256
+/// Fingerprint=` prefix, in either case.
+pub(crate) fn parse_fingerprint(fingerprint: &str) -> Result<[u8; 32]> {
+    let invalid = || {
+        RestError::ValidationError(format!(
+            "Invalid SHA-256 certificate fingerprint '{}': expected 64 hex digits",
+            fingerprint
+        ))
+    };
+    let trimmed = fingerprint.trim();
+    let hex: String = trimmed
+        .rsplit_once('=')
+        .map_or(trimmed, |(_, hex)| hex)
+        .chars()
+        .filter(|c| *c != ':' && !c.is_whitespace())
+        .collect();
+    if hex.len() != 64 {
+        return Err(invalid());
+    }
+    let mut bytes = [0u8; 32];
+    for (byte, pair) in bytes.iter_mut().zip(hex.as_bytes().chunks(2)) {
+        let pair = std::str::from_utf8(pair).map_err(|_| invalid())?;
+        *byte = u8::from_str_radix(pair, 16).map_err(|_| invalid())?;
+    }
+    Ok(bytes)
+}
+
+/// A fingerprint in the colon-separated upper-case form
+pub(crate) fn format_fingerprint(bytes: &[u8]) -> String {
+    bytes
+        .iter()
+        .map(|b| format!("{:02X}", b))
+        .collect::<Vec<_>>()
+        .join(":")
+}
+
+/// TLS configuration that accepts only the certificate with `fingerprint`
+pub(crate) fn pinned_config(fingerprint: [u8; 32]) -> Result<ClientConfig> {
+    let provider = Arc::new(crypto::ring::default_provider());
+    let config = ClientConfig::builder_with_provider(provider.clone())
+        .with_safe_default_protocol_versions()
+        .map_err(|e| RestError::ConnectionError(format!("Failed to configure TLS: {}", e)))?
+        .dangerous()
+        .with_custom_certificate_verifier(Arc::new(PinnedCertificate {
+            fingerprint,
+            provider,
+        }))
+        .with_no_client_auth();
+    Ok(config)
+}
+
+/// The certificate a pinned client was given did not match the pin
+#[derive(Debug)]
+struct FingerprintMismatch {
+    expected: [u8; 32],
+    presented: [u8; 32],
+}
+
+impl fmt::Display for FingerprintMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "certificate fingerprint mismatch: expected SHA-256 {}, server presented {}",
+            format_fingerprint(&self.expected),
+            format_fingerprint(&self.presented)
+        )
+    }
+}
+
+impl std::error::Error for FingerprintMismatch {}
+
+#[derive(Debug)]
+struct PinnedCertificate {
+    fingerprint: [u8; 32],
+    provider: Arc<CryptoProvider>,
+}
+
+impl ServerCertVerifier for PinnedCertificate {
+    fn verify_server_cert(
+        &self,
+        end_entity: &CertificateDer<'_>,
+        _intermediates: &[CertificateDer<'_>],
+        _server_name: &ServerName<'_>,
+        _ocsp_response: &[u8],
+        _now: UnixTime,
+    ) -> std::result::Result<ServerCertVerified, rustls::Error> {
+        let presented: [u8; 32] = Sha256::digest(end_entity.as_ref()).into();
+        if presented == self.fingerprint {
+            return Ok(ServerCertVerified::assertion());
+        }
+        Err(rustls::Error::InvalidCertificate(CertificateError::Other(
+            OtherError(Arc::new(FingerprintMismatch {
+                expected: self.fingerprint,
+                presented,
+            })),
+        )))
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> std::result::Result<HandshakeSignatureValid, rustls::Error> {
+        crypto::verify_tls12_signature(
+            message,
+            cert,
+            dss,
+            &self.provider.signature_verification_algorithms,
+        )
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> std::result::Result<HandshakeSignatureValid, rustls::Error> {
+        crypto::verify_tls13_signature(
+            message,
+            cert,
+            dss,
+            &self.provider.signature_verification_algorithms,
+        )
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.provider
+            .signature_verification_algorithms
+            .supported_schemes()
+    }
+}
+
+/// The TLS error behind a failed connection, if the handshake is what failed
+pub(crate) fn handshake_error(error: &reqwest::Error) -> Option<String> {
+    let mut source = std::error::Error::source(error);
+    while let Some(err) = source {
+        // I/O errors wrap the TLS error, possibly more than once, without exposing it as a source
+        let mut inner: &(dyn std::error::Error + 'static) = err;
+        while let Some(wrapped) = inner
+            .downcast_ref::<std::io::Error>()
+            .and_then(|io| io.get_ref())
+        {
+            inner = wrapped;
+        }
+        if let Some(tls) = inner.downcast_ref::<rustls::Error>() {
+            return Some(match tls {
+                rustls::Error::InvalidCertificate(CertificateError::Other(other)) => {
+                    other.to_string()
+                }
+                other => other.to_string(),
+            });
+        }
+        source = err.source();
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FINGERPRINT: &str = "3A:0F:5C:91:7B:22:E4:10:9D:6A:C8:F3:01:4B:7E:55:AA:12:34:56:78:9A:BC:DE:F0:11:22:33:44:55:66:77";
+
+    #[test]
+    fn test_parse_fingerprint_forms() {
+        let bytes = parse_fingerprint(FINGERPRINT).unwrap();
+        assert_eq!(bytes[0], 0x3A);
+        assert_eq!(bytes[31], 0x77);
+        assert_eq!(format_fingerprint(&bytes), FINGERPRINT);
+
+        let plain = FINGERPRINT.replace(':', "").to_lowercase();
+        assert_eq!(parse_fingerprint(&plain).unwrap(), bytes);
+        let openssl = format!("sha256 Fingerprint={}", FINGERPRINT);
+        assert_eq!(parse_fingerprint(&openssl).unwrap(), bytes);
+    }
+
+    #[test]
+    fn test_parse_fingerprint_rejects_bad_input() {
+        assert!(parse_fingerprint("3A:0F").is_err());
+        assert!(parse_fingerprint(&FINGERPRINT.replace("3A", "ZZ")).is_err());
+        assert!(parse_fingerprint("").is_err());
+    }
+}
@@ -179,6 +179,15 @@ impl UserHandler {
         self.client.delete(&format!("/v1/users/{}", uid)).await
     }
 
+    /// Revoke all of a user's sessions - DELETE /v1/users/{uid}/sessions
+    ///
+    /// Tokens issued to the user stop working, so they have to log in again.
+    pub async fn revoke_sessions(&self, uid: u32) -> Result<()> {
+        self.client
+            .delete(&format!("/v1/users/{}/sessions", uid))
+            .await
+    }
+
     /// Get permissions - GET /v1/users/permissions (raw)
     pub async fn permissions(&self) -> Result<Value> {
         self.client.get("/v1/users/permissions").await
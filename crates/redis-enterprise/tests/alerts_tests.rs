@@ -1,6 +1,7 @@
 //! Alerts endpoint tests for Redis Enterprise
 
-use redis_enterprise::{AlertHandler, AlertSettings, EnterpriseClient};
+use redis_enterprise::alerts::ClusterAlertsSettings;
+use redis_enterprise::{AlertHandler, AlertSettings, EnterpriseClient, SmtpSettings};
 use serde_json::json;
 use wiremock::matchers::{basic_auth, body_json, method, path};
 use wiremock::{Mock, MockServer, ResponseTemplate};
@@ -516,3 +517,68 @@ async fn test_alerts_get_settings_nonexistent() {
 
     assert!(result.is_err());
 }
+
+#[tokio::test]
+async fn test_alerts_update_smtp_settings() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("PUT"))
+        .and(path("/v1/cluster"))
+        .and(basic_auth("admin", "password"))
+        .and(body_json(json!({
+            "email_alerts": true,
+            "smtp_host": "smtp.example.com",
+            "smtp_port": 587,
+            "smtp_tls_mode": "starttls"
+        })))
+        .respond_with(success_response(json!({
+            "name": "cluster.local",
+            "email_alerts": true,
+            "smtp_host": "smtp.example.com",
+            "smtp_port": 587,
+            "smtp_tls_mode": "starttls"
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let client = EnterpriseClient::builder()
+        .base_url(mock_server.uri())
+        .username("admin")
+        .password("password")
+        .build()
+        .unwrap();
+
+    let handler = AlertHandler::new(client);
+    let settings = SmtpSettings {
+        email_alerts: Some(true),
+        smtp_host: Some("smtp.example.com".to_string()),
+        smtp_port: Some(587),
+        smtp_tls_mode: Some("starttls".to_string()),
+        ..Default::default()
+    };
+    let updated = handler.update_smtp_settings(&settings).await.unwrap();
+
+    assert_eq!(updated, settings);
+}
+
+#[test]
+fn test_cluster_alerts_settings_entries() {
+    let settings: ClusterAlertsSettings = serde_json::from_value(json!({
+        "node_memory": {"enabled": true, "threshold": "80"},
+        "node_failed": {"enabled": true, "threshold": "0", "webhook_url": "https://hooks.example.com"}
+    }))
+    .unwrap();
+
+    let mut entries = settings.entries().unwrap();
+    entries.sort_by(|a, b| a.0.cmp(&b.0));
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[0].0, "node_failed");
+    assert_eq!(
+        entries[0].1.webhook_url.as_deref(),
+        Some("https://hooks.example.com")
+    );
+
+    let rebuilt = ClusterAlertsSettings::from_entries(entries).unwrap();
+    assert_eq!(rebuilt.node_memory.unwrap().threshold, "80");
+    assert_eq!(rebuilt.extra["node_failed"]["enabled"], true);
+}
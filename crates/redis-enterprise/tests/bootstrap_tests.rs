@@ -2,10 +2,10 @@
 
 use redis_enterprise::{
     BootstrapConfig, BootstrapHandler, ClusterBootstrap, CredentialsBootstrap, EnterpriseClient,
-    NodeBootstrap, NodePaths,
+    NodeBootstrap, NodeIdentity, NodeLimits, NodePaths,
 };
 use serde_json::json;
-use wiremock::matchers::{basic_auth, method, path};
+use wiremock::matchers::{basic_auth, body_partial_json, method, path};
 use wiremock::{Mock, MockServer, ResponseTemplate};
 
 // Test helper functions
@@ -46,6 +46,8 @@ fn cluster_bootstrap_config() -> BootstrapConfig {
                 persistent_path: Some("/opt/redislabs/persist".to_string()),
                 ephemeral_path: Some("/opt/redislabs/tmp".to_string()),
             }),
+            identity: None,
+            limits: None,
         }),
         credentials: Some(CredentialsBootstrap {
             username: "admin".to_string(),
@@ -64,6 +66,8 @@ fn join_node_config() -> BootstrapConfig {
                 persistent_path: Some("/opt/redislabs/persist".to_string()),
                 ephemeral_path: Some("/opt/redislabs/tmp".to_string()),
             }),
+            identity: None,
+            limits: None,
         }),
         credentials: Some(CredentialsBootstrap {
             username: "admin".to_string(),
@@ -109,6 +113,63 @@ async fn test_bootstrap_create_cluster() {
     assert_eq!(status.message, Some("Initializing cluster".to_string()));
 }
 
+#[tokio::test]
+async fn test_bootstrap_create_with_node_identity() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/v1/bootstrap"))
+        .and(body_partial_json(json!({
+            "node": {
+                "paths": {"persistent_path": "/var/opt/redislabs/persist"},
+                "identity": {
+                    "addr": "10.0.0.5",
+                    "external_addr": ["203.0.113.5"],
+                    "rack_id": "us-east-1a"
+                },
+                "limits": {"max_listeners": 200}
+            }
+        })))
+        .respond_with(created_response(bootstrap_status_response(
+            "in_progress",
+            None,
+            None,
+        )))
+        .mount(&mock_server)
+        .await;
+
+    let client = EnterpriseClient::builder()
+        .base_url(mock_server.uri())
+        .username("admin")
+        .password("password")
+        .build()
+        .unwrap();
+
+    let mut config = cluster_bootstrap_config();
+    config.node = Some(NodeBootstrap {
+        paths: Some(NodePaths {
+            persistent_path: Some("/var/opt/redislabs/persist".to_string()),
+            ephemeral_path: None,
+        }),
+        identity: Some(NodeIdentity {
+            addr: Some("10.0.0.5".to_string()),
+            external_addr: Some(vec!["203.0.113.5".to_string()]),
+            rack_id: Some("us-east-1a".to_string()),
+        }),
+        limits: Some(NodeLimits {
+            max_listeners: Some(200),
+            max_redis_servers: None,
+        }),
+    });
+
+    let value = serde_json::to_value(config.node.as_ref().unwrap()).unwrap();
+    assert!(value["paths"].get("ephemeral_path").is_none());
+    assert!(value["limits"].get("max_redis_servers").is_none());
+
+    let status = BootstrapHandler::new(client).create(config).await.unwrap();
+    assert_eq!(status.status, "in_progress");
+}
+
 #[tokio::test]
 async fn test_bootstrap_status_in_progress() {
     let mock_server = MockServer::start().await;
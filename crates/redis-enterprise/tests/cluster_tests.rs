@@ -1,8 +1,11 @@
 //! Cluster endpoint tests for Redis Enterprise
 
-use redis_enterprise::{ClusterHandler, EnterpriseClient};
+use redis_enterprise::{
+    ClusterHandler, EnterpriseClient, MaintenanceOffRequest, MaintenanceOnRequest, RequestEvent,
+    RequestObserver,
+};
 use serde_json::json;
-use wiremock::matchers::{basic_auth, method, path};
+use wiremock::matchers::{basic_auth, body_json, method, path};
 use wiremock::{Mock, MockServer, ResponseTemplate};
 
 // Test helper functions
@@ -257,3 +260,267 @@ async fn test_cluster_recover() {
     let result = handler.recover().await;
     assert!(result.is_ok());
 }
+
+#[tokio::test]
+async fn test_cluster_recovery_plan() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/v1/cluster/recovery"))
+        .and(basic_auth("admin", "password"))
+        .respond_with(success_response(json!({
+            "status": "recovery_needed",
+            "bdbs": [
+                {"uid": 1, "name": "cache", "source": "persistence", "status": "pending"},
+                {"uid": 2, "name": "sessions"}
+            ],
+            "nodes": 3
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let client = EnterpriseClient::builder()
+        .base_url(mock_server.uri())
+        .username("admin")
+        .password("password")
+        .build()
+        .unwrap();
+
+    let handler = ClusterHandler::new(client);
+    let plan = handler.recovery_plan().await.unwrap();
+    assert_eq!(plan.status.as_deref(), Some("recovery_needed"));
+    assert_eq!(plan.bdbs.len(), 2);
+    assert_eq!(plan.bdbs[0].source.as_deref(), Some("persistence"));
+    assert_eq!(plan.bdbs[1].status, None);
+    assert_eq!(plan.extra["nodes"], 3);
+}
+
+#[tokio::test]
+async fn test_cluster_node_maintenance_actions() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/v1/nodes/2/actions/maintenance_on"))
+        .and(body_json(json!({"keep_slave_shards": true})))
+        .respond_with(success_response(
+            json!({"action_uid": "act-maint-on", "description": "maintenance_on"}),
+        ))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v1/nodes/2/actions/maintenance_off"))
+        .and(body_json(json!({})))
+        .respond_with(success_response(json!({"action_uid": "act-maint-off"})))
+        .mount(&mock_server)
+        .await;
+
+    let client = EnterpriseClient::builder()
+        .base_url(mock_server.uri())
+        .username("admin")
+        .password("password")
+        .build()
+        .unwrap();
+
+    let handler = ClusterHandler::new(client);
+    let on = MaintenanceOnRequest::builder()
+        .keep_slave_shards(true)
+        .build();
+    let response = handler.enable_node_maintenance(2, &on).await.unwrap();
+    assert_eq!(response.action_uid, "act-maint-on");
+    let response = handler
+        .disable_node_maintenance(2, &MaintenanceOffRequest::default())
+        .await
+        .unwrap();
+    assert_eq!(response.action_uid, "act-maint-off");
+}
+
+#[tokio::test]
+async fn test_cluster_maintenance_status() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/v1/nodes"))
+        .respond_with(success_response(json!([
+            {"uid": 1, "addr": "10.0.0.1", "status": "active", "shard_count": 4},
+            {"uid": 2, "addr": "10.0.0.2", "status": "active", "accept_servers": false, "shard_count": 0},
+            {"uid": 3, "addr": "10.0.0.3", "status": "down"},
+            {"uid": 4, "addr": "10.0.0.4", "status": "active", "maintenance_mode": true}
+        ])))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/nodes/status"))
+        .respond_with(success_response(json!({
+            "1": {"node_status": "active"},
+            "2": {"node_status": "maintenance"},
+            "3": {"node_status": "down"}
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let client = EnterpriseClient::builder()
+        .base_url(mock_server.uri())
+        .username("admin")
+        .password("password")
+        .build()
+        .unwrap();
+
+    let status = ClusterHandler::new(client)
+        .maintenance_status()
+        .await
+        .unwrap();
+    let in_maintenance: Vec<u32> = status.in_maintenance().map(|node| node.uid).collect();
+    assert_eq!(in_maintenance, vec![2, 4]);
+    assert_eq!(status.nodes[1].accept_servers, Some(false));
+
+    // Only node 1 still serves: 1 of the 3 needed out of 4
+    let quorum = status.quorum_after(&[]);
+    assert_eq!((quorum.total, quorum.required, quorum.available), (4, 3, 1));
+    assert!(!quorum.is_met());
+    assert_eq!(status.quorum_after(&[1]).available, 0);
+}
+
+#[derive(Debug, Default)]
+struct RecordingObserver(std::sync::Mutex<Vec<RequestEvent>>);
+
+impl RequestObserver for RecordingObserver {
+    fn on_request(&self, event: &RequestEvent) {
+        self.0.lock().unwrap().push(event.clone());
+    }
+}
+
+#[tokio::test]
+async fn test_request_observer() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/v1/cluster"))
+        .respond_with(success_response(test_cluster()))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v1/cluster/actions/recover"))
+        .respond_with(success_response(json!({"action_uid": "act-1"})))
+        .mount(&mock_server)
+        .await;
+
+    let observer = std::sync::Arc::new(RecordingObserver::default());
+    let client = EnterpriseClient::builder()
+        .base_url(mock_server.uri())
+        .username("admin")
+        .password("password")
+        .observer(observer.clone())
+        .build()
+        .unwrap();
+
+    let handler = ClusterHandler::new(client);
+    handler.info().await.unwrap();
+    handler.recover().await.unwrap();
+
+    let events = observer.0.lock().unwrap();
+    let seen: Vec<(&str, &str, Option<u16>)> = events
+        .iter()
+        .map(|e| (e.method, e.path.as_str(), e.status))
+        .collect();
+    assert_eq!(
+        seen,
+        vec![
+            ("GET", "/v1/cluster", Some(200)),
+            ("POST", "/v1/cluster/actions/recover", Some(200))
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_read_only_client_refuses_writes() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/v1/cluster"))
+        .respond_with(success_response(test_cluster()))
+        .mount(&mock_server)
+        .await;
+
+    let client = EnterpriseClient::builder()
+        .base_url(mock_server.uri())
+        .username("admin")
+        .password("password")
+        .read_only(true)
+        .build()
+        .unwrap();
+
+    let handler = ClusterHandler::new(client);
+    handler.info().await.unwrap();
+    let result = handler.recover().await;
+    assert!(matches!(
+        result,
+        Err(redis_enterprise::RestError::ReadOnly { ref method, ref path })
+            if method == "POST" && path == "/v1/cluster/actions/recover"
+    ));
+
+    // Refused requests never reach the server
+    let requests = mock_server.received_requests().await.unwrap();
+    assert_eq!(requests.len(), 1);
+}
+
+#[tokio::test]
+async fn test_max_concurrent_requests() {
+    let mock_server = MockServer::start().await;
+    let delay = std::time::Duration::from_millis(150);
+
+    Mock::given(method("GET"))
+        .and(path("/v1/cluster"))
+        .respond_with(success_response(test_cluster()).set_delay(delay))
+        .mount(&mock_server)
+        .await;
+
+    let client = EnterpriseClient::builder()
+        .base_url(mock_server.uri())
+        .username("admin")
+        .password("password")
+        .max_concurrent_requests(2)
+        .build()
+        .unwrap();
+
+    // Clones share the limit: two requests at a time, so three take two rounds
+    let (a, b, c) = (client.clone(), client.clone(), client);
+    let start = std::time::Instant::now();
+    let (ra, rb, rc) = tokio::join!(
+        a.get_raw("/v1/cluster"),
+        b.get_raw("/v1/cluster"),
+        c.get_raw("/v1/cluster")
+    );
+    assert!(ra.is_ok() && rb.is_ok() && rc.is_ok());
+    assert!(start.elapsed() >= delay * 2);
+}
+
+#[tokio::test]
+async fn test_request_limiter_is_shared_between_clients() {
+    let mock_server = MockServer::start().await;
+    let delay = std::time::Duration::from_millis(150);
+
+    Mock::given(method("GET"))
+        .and(path("/v1/cluster"))
+        .respond_with(success_response(test_cluster()).set_delay(delay))
+        .mount(&mock_server)
+        .await;
+
+    // Two clients built separately, each allowing more than one request, take slots from
+    // the same single-slot limiter
+    let limiter = std::sync::Arc::new(tokio::sync::Semaphore::new(1));
+    let build = || {
+        EnterpriseClient::builder()
+            .base_url(mock_server.uri())
+            .username("admin")
+            .password("password")
+            .max_concurrent_requests(4)
+            .request_limiter(limiter.clone())
+            .build()
+            .unwrap()
+    };
+    let (a, b) = (build(), build());
+    let start = std::time::Instant::now();
+    let (ra, rb) = tokio::join!(a.get_raw("/v1/cluster"), b.get_raw("/v1/cluster"));
+    assert!(ra.is_ok() && rb.is_ok());
+    assert!(start.elapsed() >= delay * 2);
+}
@@ -151,6 +151,9 @@ async fn test_cm_settings_update_full() {
         slave_ha: Some(true),
         slave_ha_grace_period: Some(600),
         max_simultaneous_backups: Some(5),
+        parallel_shards_upgrade: None,
+        timezone: None,
+        timeouts: None,
         extra: json!({}),
     };
 
@@ -203,6 +206,9 @@ async fn test_cm_settings_update_partial() {
         slave_ha: None,
         slave_ha_grace_period: None,
         max_simultaneous_backups: Some(2),
+        parallel_shards_upgrade: None,
+        timezone: None,
+        timeouts: None,
         extra: json!({}),
     };
 
@@ -251,6 +257,9 @@ async fn test_cm_settings_update_minimal() {
         slave_ha: None,
         slave_ha_grace_period: None,
         max_simultaneous_backups: None,
+        parallel_shards_upgrade: None,
+        timezone: None,
+        timeouts: None,
         extra: json!({}),
     };
 
@@ -295,6 +304,9 @@ async fn test_cm_settings_update_invalid() {
         slave_ha: Some(true),
         slave_ha_grace_period: Some(0),    // Invalid grace period
         max_simultaneous_backups: Some(0), // Invalid backup count
+        parallel_shards_upgrade: None,
+        timezone: None,
+        timeouts: None,
         extra: json!({}),
     };
 
@@ -388,6 +400,9 @@ async fn test_cm_settings_workflow() {
         slave_ha: Some(true),
         slave_ha_grace_period: Some(300),
         max_simultaneous_backups: Some(3),
+        parallel_shards_upgrade: None,
+        timezone: None,
+        timeouts: None,
         extra: json!({}),
     };
 
@@ -423,3 +438,92 @@ async fn test_cm_settings_workflow() {
     assert_eq!(updated_settings.auto_recovery, Some(true));
     assert_eq!(updated_settings.max_simultaneous_backups, Some(3));
 }
+
+#[tokio::test]
+async fn test_cm_settings_update_upgrade_and_timeouts() {
+    let mock_server = MockServer::start().await;
+
+    let settings = CmSettings {
+        cm_port: None,
+        cm_session_timeout: None,
+        auto_recovery: None,
+        auto_failover: None,
+        slave_ha: None,
+        slave_ha_grace_period: None,
+        max_simultaneous_backups: None,
+        parallel_shards_upgrade: Some(4),
+        timezone: Some("UTC".to_string()),
+        timeouts: Some(json!({"edit_db": 120})),
+        extra: json!({}),
+    };
+
+    Mock::given(method("PUT"))
+        .and(path("/v1/cm_settings"))
+        .and(basic_auth("admin", "password"))
+        .and(body_json(json!({
+            "parallel_shards_upgrade": 4,
+            "timezone": "UTC",
+            "timeouts": {"edit_db": 120}
+        })))
+        .respond_with(success_response(json!({
+            "cm_port": 8443,
+            "parallel_shards_upgrade": 4,
+            "timezone": "UTC",
+            "timeouts": {"edit_db": 120}
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let client = EnterpriseClient::builder()
+        .base_url(mock_server.uri())
+        .username("admin")
+        .password("password")
+        .build()
+        .unwrap();
+
+    let handler = CmSettingsHandler::new(client);
+    let updated_settings = handler.update(settings).await.unwrap();
+
+    assert_eq!(updated_settings.cm_port, Some(8443));
+    assert_eq!(updated_settings.parallel_shards_upgrade, Some(4));
+    assert_eq!(updated_settings.timezone.as_deref(), Some("UTC"));
+    assert_eq!(
+        updated_settings
+            .timeouts
+            .as_ref()
+            .and_then(|t| t.get("edit_db")),
+        Some(&json!(120))
+    );
+}
+
+#[tokio::test]
+async fn test_cm_settings_get_non_integer_timeouts() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/v1/cm_settings"))
+        .and(basic_auth("admin", "password"))
+        .respond_with(success_response(json!({
+            "cm_port": 8443,
+            "timeouts": {
+                "edit_db": 120.5,
+                "upgrade": {"per_shard": 30, "max": 600}
+            }
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let client = EnterpriseClient::builder()
+        .base_url(mock_server.uri())
+        .username("admin")
+        .password("password")
+        .build()
+        .unwrap();
+
+    let handler = CmSettingsHandler::new(client);
+    let settings = handler.get().await.unwrap();
+
+    let timeouts = settings.timeouts.unwrap();
+    assert_eq!(timeouts["edit_db"], json!(120.5));
+    assert_eq!(timeouts["upgrade"]["max"], json!(600));
+}
@@ -1,5 +1,7 @@
 //! CRDB tasks endpoint tests for Redis Enterprise
 
+#![cfg(feature = "crdb")]
+
 use redis_enterprise::{CrdbTasksHandler, CreateCrdbTaskRequest, EnterpriseClient};
 use serde_json::json;
 use wiremock::matchers::{basic_auth, body_json, method, path};
@@ -508,3 +510,96 @@ async fn test_crdb_tasks_list_by_crdb_nonexistent() {
 
     assert!(result.is_err());
 }
+
+#[tokio::test]
+async fn test_crdb_tasks_wait_until_failed() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/v1/crdb_tasks/task-42"))
+        .and(basic_auth("admin", "password"))
+        .respond_with(success_response(json!({
+            "id": "task-42",
+            "status": "started"
+        })))
+        .up_to_n_times(1)
+        .mount(&mock_server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/v1/crdb_tasks/task-42"))
+        .and(basic_auth("admin", "password"))
+        .respond_with(success_response(json!({
+            "id": "task-42",
+            "status": "failed",
+            "errors": [{
+                "cluster_name": "cluster2.example.com",
+                "error_code": "connection_error",
+                "description": "Failed to connect to cluster"
+            }]
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let client = EnterpriseClient::builder()
+        .base_url(mock_server.uri())
+        .username("admin")
+        .password("password")
+        .build()
+        .unwrap();
+
+    let handler = CrdbTasksHandler::new(client);
+    let task = handler
+        .wait(
+            "task-42",
+            std::time::Duration::from_millis(10),
+            std::time::Duration::from_secs(5),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(task.task_id, "task-42");
+    assert!(task.is_failed());
+    assert_eq!(task.errors.len(), 1);
+    assert_eq!(
+        task.errors[0].cluster_name.as_deref(),
+        Some("cluster2.example.com")
+    );
+    assert_eq!(
+        task.errors[0].error_code.as_deref(),
+        Some("connection_error")
+    );
+}
+
+#[tokio::test]
+async fn test_crdb_tasks_wait_timeout() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/v1/crdb_tasks/task-123"))
+        .and(basic_auth("admin", "password"))
+        .respond_with(success_response(test_crdb_task()))
+        .mount(&mock_server)
+        .await;
+
+    let client = EnterpriseClient::builder()
+        .base_url(mock_server.uri())
+        .username("admin")
+        .password("password")
+        .build()
+        .unwrap();
+
+    let handler = CrdbTasksHandler::new(client);
+    let result = handler
+        .wait(
+            "task-123",
+            std::time::Duration::from_millis(10),
+            std::time::Duration::from_millis(50),
+        )
+        .await;
+
+    assert!(matches!(
+        result,
+        Err(redis_enterprise::RestError::Timeout(_))
+    ));
+}
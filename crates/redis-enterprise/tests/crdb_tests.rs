@@ -1,5 +1,7 @@
 //! Active-Active (CRDB) endpoint tests for Redis Enterprise
 
+#![cfg(feature = "crdb")]
+
 use redis_enterprise::{CrdbHandler, CreateCrdbInstance, CreateCrdbRequest, EnterpriseClient};
 use serde_json::json;
 use wiremock::matchers::{basic_auth, body_json, method, path};
@@ -1,9 +1,12 @@
 //! Database (BDB) endpoint tests for Redis Enterprise
 
 use redis_enterprise::bdb::CreateDatabaseRequest;
-use redis_enterprise::{BdbHandler, EnterpriseClient};
+use redis_enterprise::{
+    BdbHandler, DatabaseAcl, EnterpriseClient, RolePermission, RolePermissionEntry, SyncKind,
+    SyncState,
+};
 use serde_json::json;
-use wiremock::matchers::{basic_auth, method, path};
+use wiremock::matchers::{basic_auth, body_json, method, path, query_param};
 use wiremock::{Mock, MockServer, ResponseTemplate};
 
 // Test helper functions
@@ -66,6 +69,38 @@ async fn test_database_list() {
     assert_eq!(databases.len(), 2);
 }
 
+#[tokio::test]
+async fn test_database_list_with_fields() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/v1/bdbs"))
+        .and(query_param("fields", "uid,name,status"))
+        .and(basic_auth("admin", "password"))
+        .respond_with(success_response(json!([
+            {"uid": 1, "name": "cache", "status": "active"},
+            {"uid": 2, "name": "sessions", "status": "pending"}
+        ])))
+        .mount(&mock_server)
+        .await;
+
+    let client = EnterpriseClient::builder()
+        .base_url(mock_server.uri())
+        .username("admin")
+        .password("password")
+        .build()
+        .unwrap();
+
+    let handler = BdbHandler::new(client);
+    let databases = handler
+        .list_with_fields(&["uid", "name", "status"])
+        .await
+        .unwrap();
+    assert_eq!(databases.len(), 2);
+    assert_eq!(databases[1]["name"], "sessions");
+    assert!(databases[0].get("memory_size").is_none());
+}
+
 #[tokio::test]
 async fn test_database_get() {
     let mock_server = MockServer::start().await;
@@ -93,6 +128,206 @@ async fn test_database_get() {
     assert_eq!(db.name, "test-db");
 }
 
+#[tokio::test]
+async fn test_database_get_connection_info() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/v1/bdbs/1"))
+        .and(basic_auth("admin", "password"))
+        .respond_with(success_response(json!({
+            "uid": 1,
+            "name": "test-db",
+            "port": 12000,
+            "tls_mode": "enabled",
+            "oss_cluster": true,
+            "shards_placement": "sparse",
+            "endpoints": [{
+                "uid": "1:1",
+                "dns_name": "redis-12000.cluster.local",
+                "addr": ["10.0.0.1"],
+                "addr_type": "external",
+                "port": 12001,
+                "proxy_policy": "single"
+            }],
+            "future_field": "kept"
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let client = EnterpriseClient::builder()
+        .base_url(mock_server.uri())
+        .username("admin")
+        .password("password")
+        .build()
+        .unwrap();
+
+    let db = BdbHandler::new(client).info(1).await.unwrap();
+    assert!(db.tls_enabled());
+    assert_eq!(db.oss_cluster, Some(true));
+    assert_eq!(db.shards_placement.as_deref(), Some("sparse"));
+    assert_eq!(db.connection_port(), Some(12001));
+    assert_eq!(db.endpoint_list().len(), 1);
+    assert_eq!(
+        db.endpoint_list()[0].host(),
+        Some("redis-12000.cluster.local")
+    );
+    assert_eq!(db.extra["future_field"], "kept");
+}
+
+#[tokio::test]
+async fn test_database_acl() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/v1/bdbs/1"))
+        .and(basic_auth("admin", "password"))
+        .respond_with(success_response(json!({
+            "uid": 1,
+            "name": "test-db",
+            "default_user": true,
+            "roles_permissions": [
+                {"role_uid": 3, "redis_acl_uid": 2},
+                {"role_uid": 4}
+            ]
+        })))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("PUT"))
+        .and(path("/v1/bdbs/1"))
+        .and(basic_auth("admin", "password"))
+        .and(body_json(json!({
+            "default_user": false,
+            "roles_permissions": [
+                {"role_uid": 3, "redis_acl_uid": 5},
+                {"role_uid": 4}
+            ]
+        })))
+        .respond_with(success_response(json!({"uid": 1, "name": "test-db"})))
+        .mount(&mock_server)
+        .await;
+
+    let client = EnterpriseClient::builder()
+        .base_url(mock_server.uri())
+        .username("admin")
+        .password("password")
+        .build()
+        .unwrap();
+    let handler = BdbHandler::new(client);
+
+    let acl = handler.get_acl(1).await.unwrap();
+    assert_eq!(acl.default_user, Some(true));
+    assert_eq!(
+        acl.bindings().collect::<Vec<_>>(),
+        vec![&RolePermission {
+            role_uid: 3,
+            redis_acl_uid: 2
+        }]
+    );
+
+    // The entry that is not a plain binding is sent back unchanged
+    let mut entries = acl.roles_permissions.unwrap();
+    assert_eq!(
+        entries[1],
+        RolePermissionEntry::Other(json!({"role_uid": 4}))
+    );
+    assert_eq!(entries[1].role_uid(), Some(4));
+    entries[0] = RolePermissionEntry::Binding(RolePermission {
+        role_uid: 3,
+        redis_acl_uid: 5,
+    });
+    let updated = DatabaseAcl {
+        default_user: Some(false),
+        roles_permissions: Some(entries),
+    };
+    handler.update_acl(1, &updated).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_database_acl_update_without_bindings() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("PUT"))
+        .and(path("/v1/bdbs/1"))
+        .and(body_json(json!({"default_user": false})))
+        .respond_with(success_response(json!({"uid": 1, "name": "test-db"})))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let client = EnterpriseClient::builder()
+        .base_url(mock_server.uri())
+        .username("admin")
+        .password("password")
+        .build()
+        .unwrap();
+    let updated = DatabaseAcl {
+        default_user: Some(false),
+        roles_permissions: None,
+    };
+    BdbHandler::new(client)
+        .update_acl(1, &updated)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_database_sync_pause_and_resume() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/v1/bdbs/2"))
+        .and(basic_auth("admin", "password"))
+        .respond_with(success_response(json!({
+            "uid": 2,
+            "name": "replica-db",
+            "replica_sources": [{"uri": "redis://src:12000", "status": "in-sync"}],
+            "sync": "enabled",
+            "crdt_sync": "disabled"
+        })))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("PUT"))
+        .and(path("/v1/bdbs/2"))
+        .and(body_json(json!({"replica_sync": "paused"})))
+        .respond_with(success_response(
+            json!({"uid": 2, "name": "replica-db", "replica_sync": "paused"}),
+        ))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("PUT"))
+        .and(path("/v1/bdbs/2"))
+        .and(body_json(json!({"crdt_sync": "enabled"})))
+        .respond_with(success_response(
+            json!({"uid": 2, "name": "replica-db", "crdt_sync": "enabled"}),
+        ))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let client = EnterpriseClient::builder()
+        .base_url(mock_server.uri())
+        .username("admin")
+        .password("password")
+        .build()
+        .unwrap();
+    let handler = BdbHandler::new(client);
+
+    let db = handler.get(2).await.unwrap();
+    assert_eq!(db.sync_kind(), Some(SyncKind::Replica));
+    // `replica_sync` is missing, so the older `sync` field is used
+    assert_eq!(db.sync_state(SyncKind::Replica), Some(SyncState::Enabled));
+
+    let paused = handler.pause_sync(2, SyncKind::Replica).await.unwrap();
+    assert_eq!(
+        paused.sync_state(SyncKind::Replica),
+        Some(SyncState::Paused)
+    );
+    let resumed = handler.resume_sync(2, SyncKind::Crdt).await.unwrap();
+    assert_eq!(resumed.sync_state(SyncKind::Crdt), Some(SyncState::Enabled));
+}
+
 #[tokio::test]
 async fn test_database_create() {
     let mock_server = MockServer::start().await;
@@ -125,6 +360,37 @@ async fn test_database_create() {
     assert_eq!(db.name, "test-db");
 }
 
+#[tokio::test]
+async fn test_database_create_dry_run() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/v1/bdbs/dry-run"))
+        .and(basic_auth("admin", "password"))
+        .respond_with(success_response(json!({
+            "name": "test-db",
+            "memory_size": 1073741824,
+            "shards_count": 2
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let client = EnterpriseClient::builder()
+        .base_url(mock_server.uri())
+        .username("admin")
+        .password("password")
+        .build()
+        .unwrap();
+
+    let handler = BdbHandler::new(client);
+    let result = handler
+        .create_dry_run(json!({"name": "test-db", "memory_size": 1073741824, "shards_count": 2}))
+        .await
+        .unwrap();
+    assert_eq!(result["shards_count"], 2);
+}
+
 #[tokio::test]
 async fn test_database_delete() {
     let mock_server = MockServer::start().await;
@@ -1,3 +1,5 @@
+#![cfg(feature = "diagnostics")]
+
 #[cfg(test)]
 mod tests {
     use redis_enterprise::EnterpriseClient;
@@ -166,6 +168,32 @@ mod tests {
         assert!(result.get("nodes").is_some());
     }
 
+    #[tokio::test]
+    async fn test_download_all_debug_info_to_writer() {
+        let mock_server = MockServer::start().await;
+        let handler = setup_mock_client(&mock_server).await;
+
+        let package: Vec<u8> = (0..=255u8).cycle().take(100_000).collect();
+        Mock::given(method("GET"))
+            .and(path("/v1/debuginfo/all/bdb/3"))
+            .and(basic_auth("test_user", "test_pass"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("content-type", "application/x-gzip")
+                    .set_body_bytes(package.clone()),
+            )
+            .mount(&mock_server)
+            .await;
+
+        let mut written = Vec::new();
+        let size = handler
+            .download_all_to(Some(3), &mut written)
+            .await
+            .unwrap();
+        assert_eq!(size, package.len() as u64);
+        assert_eq!(written, package);
+    }
+
     #[tokio::test]
     async fn test_get_all_bdb_debug_info() {
         let mock_server = MockServer::start().await;
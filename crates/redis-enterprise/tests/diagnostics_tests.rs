@@ -1,5 +1,7 @@
 //! Diagnostics endpoint tests for Redis Enterprise
 
+#![cfg(feature = "diagnostics")]
+
 use redis_enterprise::{DiagnosticRequest, DiagnosticsHandler, EnterpriseClient};
 use serde_json::json;
 use wiremock::matchers::{basic_auth, body_json, method, path};
@@ -1,5 +1,7 @@
 //! Job scheduler tests for Redis Enterprise
 
+#![cfg(feature = "job-scheduler")]
+
 use redis_enterprise::{CreateScheduledJobRequest, EnterpriseClient, JobSchedulerHandler};
 use serde_json::json;
 use wiremock::matchers::{basic_auth, body_json, method, path};
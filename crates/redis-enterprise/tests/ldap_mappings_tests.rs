@@ -1,7 +1,10 @@
 //! LDAP mappings endpoint tests for Redis Enterprise
 
+#![cfg(feature = "ldap")]
+
 use redis_enterprise::{
     CreateLdapMappingRequest, EnterpriseClient, LdapConfig, LdapMappingHandler, LdapServer,
+    UpdateLdapMappingRequest,
 };
 use serde_json::json;
 use wiremock::matchers::{basic_auth, body_json, method, path};
@@ -314,6 +317,34 @@ async fn test_ldap_mappings_update() {
     assert_eq!(mapping.name, "test_mapping");
 }
 
+#[tokio::test]
+async fn test_ldap_mappings_update_partial() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("PUT"))
+        .and(path("/v1/ldap_mappings/1"))
+        .and(basic_auth("admin", "password"))
+        .and(body_json(json!({"role_uids": [3]})))
+        .respond_with(success_response(test_ldap_mapping()))
+        .mount(&mock_server)
+        .await;
+
+    let client = EnterpriseClient::builder()
+        .base_url(mock_server.uri())
+        .username("admin")
+        .password("password")
+        .build()
+        .unwrap();
+
+    let handler = LdapMappingHandler::new(client);
+    let request = UpdateLdapMappingRequest::builder()
+        .role_uids(vec![3])
+        .build();
+    let result = handler.update(1, request).await;
+
+    assert!(result.is_ok());
+}
+
 #[tokio::test]
 async fn test_ldap_mappings_update_nonexistent() {
     let mock_server = MockServer::start().await;
@@ -3,7 +3,7 @@ mod tests {
     use redis_enterprise::EnterpriseClient;
     use redis_enterprise::local::LocalHandler;
     use serde_json::json;
-    use wiremock::matchers::{basic_auth, method, path};
+    use wiremock::matchers::{basic_auth, body_json, method, path};
     use wiremock::{Mock, MockServer, ResponseTemplate};
 
     async fn setup_mock_client(mock_server: &MockServer) -> LocalHandler {
@@ -275,4 +275,62 @@ mod tests {
         let services = result["services"].as_array().unwrap();
         assert_eq!(services.len(), 0);
     }
+
+    #[tokio::test]
+    async fn test_restart_services() {
+        let mock_server = MockServer::start().await;
+        let handler = setup_mock_client(&mock_server).await;
+
+        Mock::given(method("POST"))
+            .and(path("/v1/local/services"))
+            .and(basic_auth("test_user", "test_pass"))
+            .and(body_json(json!({
+                "command": "restart",
+                "services": ["cm_server", "stats_archiver"]
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "cm_server": {"status": "RUNNING"},
+                "stats_archiver": {"status": "RUNNING"}
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let result = handler
+            .restart_services(&["cm_server", "stats_archiver"])
+            .await
+            .unwrap();
+        assert_eq!(result["cm_server"]["status"], "RUNNING");
+    }
+
+    #[tokio::test]
+    async fn test_local_services_on_another_node() {
+        let cluster = MockServer::start().await;
+        let node = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/v1/local/services"))
+            .and(basic_auth("test_user", "test_pass"))
+            .and(body_json(
+                json!({"command": "stop", "services": ["mdns_server"]}),
+            ))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({})))
+            .expect(1)
+            .mount(&node)
+            .await;
+
+        let client = EnterpriseClient::builder()
+            .base_url(cluster.uri())
+            .username("test_user")
+            .password("test_pass")
+            .build()
+            .unwrap();
+        let node_client = client.with_base_url(node.uri());
+        assert_eq!(node_client.base_url(), node.uri());
+
+        LocalHandler::new(node_client)
+            .stop_services(&["mdns_server"])
+            .await
+            .unwrap();
+    }
 }
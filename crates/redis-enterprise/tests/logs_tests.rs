@@ -1,6 +1,6 @@
 //! Logs endpoint tests for Redis Enterprise
 
-use redis_enterprise::{EnterpriseClient, LogsHandler, LogsQuery};
+use redis_enterprise::{EnterpriseClient, LogOrder, LogsHandler, LogsQuery};
 use serde_json::json;
 use wiremock::matchers::{basic_auth, method, path, query_param};
 use wiremock::{Mock, MockServer, ResponseTemplate};
@@ -141,6 +141,7 @@ async fn test_logs_list_with_limit() {
         component: None,
         node_uid: None,
         bdb_uid: None,
+        ..Default::default()
     };
     let result = handler.list(Some(query)).await;
 
@@ -176,6 +177,7 @@ async fn test_logs_list_with_offset() {
         component: None,
         node_uid: None,
         bdb_uid: None,
+        ..Default::default()
     };
     let result = handler.list(Some(query)).await;
 
@@ -211,6 +213,7 @@ async fn test_logs_list_filter_by_level() {
         component: None,
         node_uid: None,
         bdb_uid: None,
+        ..Default::default()
     };
     let result = handler.list(Some(query)).await;
 
@@ -247,6 +250,7 @@ async fn test_logs_list_filter_by_component() {
         component: Some("database".to_string()),
         node_uid: None,
         bdb_uid: None,
+        ..Default::default()
     };
     let result = handler.list(Some(query)).await;
 
@@ -283,6 +287,7 @@ async fn test_logs_list_filter_by_node() {
         component: None,
         node_uid: Some(1),
         bdb_uid: None,
+        ..Default::default()
     };
     let result = handler.list(Some(query)).await;
 
@@ -319,6 +324,7 @@ async fn test_logs_list_filter_by_database() {
         component: None,
         node_uid: None,
         bdb_uid: Some(1),
+        ..Default::default()
     };
     let result = handler.list(Some(query)).await;
 
@@ -358,6 +364,7 @@ async fn test_logs_list_complex_query() {
         component: None,
         node_uid: Some(2),
         bdb_uid: None,
+        ..Default::default()
     };
     let result = handler.list(Some(query)).await;
 
@@ -447,3 +454,137 @@ async fn test_logs_list_error() {
 
     assert!(result.is_err());
 }
+
+#[tokio::test]
+async fn test_logs_stream() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/v1/logs"))
+        .and(query_param("limit", "3"))
+        .and(basic_auth("admin", "password"))
+        .respond_with(success_response(json!([
+            test_log_entry(),
+            test_warning_log(),
+            test_error_log()
+        ])))
+        .mount(&mock_server)
+        .await;
+
+    let client = EnterpriseClient::builder()
+        .base_url(mock_server.uri())
+        .username("admin")
+        .password("password")
+        .build()
+        .unwrap();
+
+    let handler = LogsHandler::new(client);
+    let query = LogsQuery {
+        limit: Some(3),
+        offset: None,
+        level: None,
+        component: None,
+        node_uid: None,
+        bdb_uid: None,
+        ..Default::default()
+    };
+    let mut levels = Vec::new();
+    let count = handler
+        .stream(Some(query), |entry| {
+            levels.push(entry.level);
+            Ok(())
+        })
+        .await
+        .unwrap();
+
+    assert_eq!(count, 3);
+    assert_eq!(levels, vec!["INFO", "WARNING", "ERROR"]);
+}
+
+#[tokio::test]
+async fn test_logs_response_too_large() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/v1/logs"))
+        .and(basic_auth("admin", "password"))
+        .respond_with(success_response(json!([
+            test_log_entry(),
+            test_warning_log(),
+            test_error_log()
+        ])))
+        .mount(&mock_server)
+        .await;
+
+    let client = EnterpriseClient::builder()
+        .base_url(mock_server.uri())
+        .username("admin")
+        .password("password")
+        .max_body_size(256)
+        .build()
+        .unwrap();
+
+    let handler = LogsHandler::new(client);
+    let result = handler.list(None).await;
+    assert!(matches!(
+        result,
+        Err(redis_enterprise::RestError::ResponseTooLarge { limit: 256 })
+    ));
+
+    // Streaming only holds one entry at a time, so the same limit is enough
+    let count = handler.stream(None, |_| Ok(())).await.unwrap();
+    assert_eq!(count, 3);
+}
+
+#[tokio::test]
+async fn test_logs_list_time_range_and_order() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/v1/logs"))
+        .and(query_param("stime", "2023-01-01T00:00:00Z"))
+        .and(query_param("etime", "2023-01-02T00:00:00Z"))
+        .and(query_param("order", "desc"))
+        .and(query_param("limit", "2"))
+        .and(query_param("originator_username", "admin"))
+        .and(basic_auth("admin", "password"))
+        .respond_with(success_response(json!([
+            {
+                "time": "2023-01-01T12:05:00Z",
+                "type": "bdb_name_updated",
+                "severity": "INFO",
+                "bdb_uid": "4",
+                "originator_uid": "1",
+                "originator_username": "admin"
+            },
+            test_log_entry()
+        ])))
+        .mount(&mock_server)
+        .await;
+
+    let client = EnterpriseClient::builder()
+        .base_url(mock_server.uri())
+        .username("admin")
+        .password("password")
+        .build()
+        .unwrap();
+
+    let handler = LogsHandler::new(client);
+    let query = LogsQuery {
+        stime: Some("2023-01-01T00:00:00Z".to_string()),
+        etime: Some("2023-01-02T00:00:00Z".to_string()),
+        order: Some(LogOrder::Desc),
+        limit: Some(2),
+        originator_username: Some("admin".to_string()),
+        ..Default::default()
+    };
+    let logs = handler.list(Some(query)).await.unwrap();
+
+    assert_eq!(logs.len(), 2);
+    assert_eq!(logs[0].event_type.as_deref(), Some("bdb_name_updated"));
+    assert_eq!(logs[0].level, "INFO");
+    assert_eq!(logs[0].bdb_uid, Some(4));
+    assert_eq!(logs[0].originator_uid, Some(1));
+    assert_eq!(logs[0].originator_username.as_deref(), Some("admin"));
+    assert_eq!(logs[1].id, 123456);
+}
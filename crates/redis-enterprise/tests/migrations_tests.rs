@@ -1,5 +1,7 @@
 //! Migrations endpoint tests for Redis Enterprise
 
+#![cfg(feature = "migrations")]
+
 use redis_enterprise::{
     CreateMigrationRequest, EnterpriseClient, MigrationEndpoint, MigrationsHandler,
 };
@@ -63,6 +63,44 @@ async fn test_module_list() {
     assert_eq!(modules.len(), 2);
 }
 
+#[tokio::test]
+async fn test_module_list_api_field_names() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/v1/modules"))
+        .respond_with(success_response(json!([{
+            "uid": "a1b2",
+            "module_name": "search",
+            "display_name": "RediSearch 2",
+            "version": 20612,
+            "semantic_version": "2.6.12",
+            "min_redis_version": "6.0",
+            "compatible_redis_version": "7.2",
+            "is_bundled": true,
+            "capabilities": ["replica_of", "crdb", "Backup_Restore"]
+        }])))
+        .mount(&mock_server)
+        .await;
+
+    let client = EnterpriseClient::builder()
+        .base_url(mock_server.uri())
+        .username("admin")
+        .password("password")
+        .build()
+        .unwrap();
+
+    let modules = ModuleHandler::new(client).list().await.unwrap();
+    let module = &modules[0];
+    assert_eq!(module.name, "search");
+    assert_eq!(module.version, "20612");
+    assert_eq!(module.semver(), "2.6.12");
+    assert!(module.supports_redis("7.2"));
+    assert!(module.supports_redis("6.0.20"));
+    assert!(!module.supports_redis("5.0"));
+    assert!(module.has_capability("backup_restore"));
+}
+
 #[tokio::test]
 async fn test_module_get() {
     let mock_server = MockServer::start().await;
@@ -1,6 +1,6 @@
 //! Node endpoint tests for Redis Enterprise
 
-use redis_enterprise::{EnterpriseClient, NodeHandler};
+use redis_enterprise::{EnterpriseClient, NodeHandler, NodeUpdateRequest, RestError};
 use serde_json::json;
 use wiremock::matchers::{basic_auth, body_json, method, path};
 use wiremock::{Mock, MockServer, ResponseTemplate};
@@ -304,6 +304,39 @@ async fn test_node_get_nonexistent() {
     assert!(result.is_err());
 }
 
+#[tokio::test]
+async fn test_node_get_type_mismatch_reports_field() {
+    let mock_server = MockServer::start().await;
+
+    let mut node = test_node();
+    node["cores"] = json!("eight");
+    Mock::given(method("GET"))
+        .and(path("/v1/nodes/1"))
+        .and(basic_auth("admin", "password"))
+        .respond_with(success_response(node.clone()))
+        .mount(&mock_server)
+        .await;
+
+    let client = EnterpriseClient::builder()
+        .base_url(mock_server.uri())
+        .username("admin")
+        .password("password")
+        .build()
+        .unwrap();
+
+    let handler = NodeHandler::new(client);
+    let err = handler.get(1).await.unwrap_err();
+
+    match &err {
+        RestError::DeserializationError { path, message, .. } => {
+            assert_eq!(path, "cores");
+            assert!(message.contains("invalid type"));
+        }
+        other => panic!("unexpected error: {:?}", other),
+    }
+    assert_eq!(err.raw_response(), Some(&node));
+}
+
 #[tokio::test]
 async fn test_node_update() {
     let mock_server = MockServer::start().await;
@@ -344,6 +377,52 @@ async fn test_node_update() {
     assert_eq!(node.rack_id.unwrap(), "rack-3");
 }
 
+#[tokio::test]
+async fn test_node_update_settings() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("PUT"))
+        .and(path("/v1/nodes/1"))
+        .and(basic_auth("admin", "password"))
+        .and(body_json(json!({
+            "external_addr": ["203.0.113.5"],
+            "rack_id": "us-east-1a",
+            "recovery_path": "/var/opt/redislabs/recovery"
+        })))
+        .respond_with(success_response(json!({
+            "uid": 1,
+            "addr": "10.0.0.1",
+            "status": "active",
+            "external_addr": ["203.0.113.5"],
+            "rack_id": "us-east-1a",
+            "recovery_path": "/var/opt/redislabs/recovery"
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let client = EnterpriseClient::builder()
+        .base_url(mock_server.uri())
+        .username("admin")
+        .password("password")
+        .build()
+        .unwrap();
+
+    let request = NodeUpdateRequest::builder()
+        .external_addr(vec!["203.0.113.5".to_string()])
+        .rack_id("us-east-1a")
+        .recovery_path("/var/opt/redislabs/recovery")
+        .build();
+    let node = NodeHandler::new(client)
+        .update_settings(1, &request)
+        .await
+        .unwrap();
+    assert_eq!(node.external_addr, Some(vec!["203.0.113.5".to_string()]));
+    assert_eq!(
+        node.recovery_path.as_deref(),
+        Some("/var/opt/redislabs/recovery")
+    );
+}
+
 #[tokio::test]
 async fn test_node_update_nonexistent() {
     let mock_server = MockServer::start().await;
@@ -1,5 +1,7 @@
 //! OCSP endpoint tests for Redis Enterprise
 
+#![cfg(feature = "ocsp")]
+
 use redis_enterprise::{EnterpriseClient, OcspConfig, OcspHandler};
 use serde_json::json;
 use wiremock::matchers::{basic_auth, body_json, method, path};
@@ -547,3 +547,37 @@ async fn test_role_users_nonexistent() {
 
     assert!(result.is_err());
 }
+
+#[tokio::test]
+async fn test_roles_resolve_uids() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/v1/roles"))
+        .and(basic_auth("admin", "password"))
+        .respond_with(success_response(json!([
+            {"uid": 3, "name": "db_admin"},
+            {"uid": 4, "name": "DB Viewer"}
+        ])))
+        // Once per call that needs a name lookup
+        .expect(2)
+        .mount(&mock_server)
+        .await;
+
+    let client = EnterpriseClient::builder()
+        .base_url(mock_server.uri())
+        .username("admin")
+        .password("password")
+        .build()
+        .unwrap();
+
+    let handler = RolesHandler::new(client);
+    let uids = handler
+        .resolve_uids(&["db_admin", "7", "db viewer"])
+        .await
+        .unwrap();
+    assert_eq!(uids, vec![3, 7, 4]);
+
+    let err = handler.resolve_uids(&["auditor"]).await.unwrap_err();
+    assert!(err.to_string().contains("Unknown role 'auditor'"));
+}
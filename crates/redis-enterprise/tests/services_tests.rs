@@ -429,13 +429,14 @@ async fn test_service_restart() {
     let mock_server = MockServer::start().await;
 
     Mock::given(method("POST"))
-        .and(path("/v1/services/redis-db/restart"))
+        .and(path("/v1/services"))
         .and(basic_auth("admin", "password"))
-        .and(body_json(json!(null)))
+        .and(body_json(json!({
+            "command": "restart",
+            "services": ["cm_server"]
+        })))
         .respond_with(success_response(json!({
-            "service_id": "redis-db",
-            "status": "restarting",
-            "message": "Service restart initiated"
+            "cm_server": {"status": "RUNNING"}
         })))
         .mount(&mock_server)
         .await;
@@ -448,16 +449,9 @@ async fn test_service_restart() {
         .unwrap();
 
     let handler = ServicesHandler::new(client);
-    let result = handler.restart("redis-db").await;
+    let result = handler.restart("cm_server").await.unwrap();
 
-    assert!(result.is_ok());
-    let status = result.unwrap();
-    assert_eq!(status.service_id, "redis-db");
-    assert_eq!(status.status, "restarting");
-    assert_eq!(
-        status.message,
-        Some("Service restart initiated".to_string())
-    );
+    assert_eq!(result["cm_server"]["status"], "RUNNING");
 }
 
 #[tokio::test]
@@ -465,9 +459,12 @@ async fn test_service_restart_nonexistent() {
     let mock_server = MockServer::start().await;
 
     Mock::given(method("POST"))
-        .and(path("/v1/services/nonexistent/restart"))
+        .and(path("/v1/services"))
         .and(basic_auth("admin", "password"))
-        .and(body_json(json!(null)))
+        .and(body_json(json!({
+            "command": "restart",
+            "services": ["nonexistent"]
+        })))
         .respond_with(error_response(404, "Service not found"))
         .mount(&mock_server)
         .await;
@@ -490,13 +487,14 @@ async fn test_service_stop() {
     let mock_server = MockServer::start().await;
 
     Mock::given(method("POST"))
-        .and(path("/v1/services/redis-db/stop"))
+        .and(path("/v1/services"))
         .and(basic_auth("admin", "password"))
-        .and(body_json(json!(null)))
+        .and(body_json(json!({
+            "command": "stop",
+            "services": ["mdns_server"]
+        })))
         .respond_with(success_response(json!({
-            "service_id": "redis-db",
-            "status": "stopping",
-            "message": "Service stop initiated"
+            "mdns_server": {"status": "STOPPED"}
         })))
         .mount(&mock_server)
         .await;
@@ -509,13 +507,9 @@ async fn test_service_stop() {
         .unwrap();
 
     let handler = ServicesHandler::new(client);
-    let result = handler.stop("redis-db").await;
+    let result = handler.stop("mdns_server").await.unwrap();
 
-    assert!(result.is_ok());
-    let status = result.unwrap();
-    assert_eq!(status.service_id, "redis-db");
-    assert_eq!(status.status, "stopping");
-    assert_eq!(status.message, Some("Service stop initiated".to_string()));
+    assert_eq!(result["mdns_server"]["status"], "STOPPED");
 }
 
 #[tokio::test]
@@ -523,9 +517,12 @@ async fn test_service_stop_nonexistent() {
     let mock_server = MockServer::start().await;
 
     Mock::given(method("POST"))
-        .and(path("/v1/services/nonexistent/stop"))
+        .and(path("/v1/services"))
         .and(basic_auth("admin", "password"))
-        .and(body_json(json!(null)))
+        .and(body_json(json!({
+            "command": "stop",
+            "services": ["nonexistent"]
+        })))
         .respond_with(error_response(404, "Service not found"))
         .mount(&mock_server)
         .await;
@@ -548,13 +545,14 @@ async fn test_service_start() {
     let mock_server = MockServer::start().await;
 
     Mock::given(method("POST"))
-        .and(path("/v1/services/monitoring/start"))
+        .and(path("/v1/services"))
         .and(basic_auth("admin", "password"))
-        .and(body_json(json!(null)))
+        .and(body_json(json!({
+            "command": "start",
+            "services": ["mdns_server"]
+        })))
         .respond_with(success_response(json!({
-            "service_id": "monitoring",
-            "status": "starting",
-            "message": "Service start initiated"
+            "mdns_server": {"status": "RUNNING"}
         })))
         .mount(&mock_server)
         .await;
@@ -567,13 +565,9 @@ async fn test_service_start() {
         .unwrap();
 
     let handler = ServicesHandler::new(client);
-    let result = handler.start("monitoring").await;
+    let result = handler.start("mdns_server").await.unwrap();
 
-    assert!(result.is_ok());
-    let status = result.unwrap();
-    assert_eq!(status.service_id, "monitoring");
-    assert_eq!(status.status, "starting");
-    assert_eq!(status.message, Some("Service start initiated".to_string()));
+    assert_eq!(result["mdns_server"]["status"], "RUNNING");
 }
 
 #[tokio::test]
@@ -581,9 +575,12 @@ async fn test_service_start_nonexistent() {
     let mock_server = MockServer::start().await;
 
     Mock::given(method("POST"))
-        .and(path("/v1/services/nonexistent/start"))
+        .and(path("/v1/services"))
         .and(basic_auth("admin", "password"))
-        .and(body_json(json!(null)))
+        .and(body_json(json!({
+            "command": "start",
+            "services": ["nonexistent"]
+        })))
         .respond_with(error_response(404, "Service not found"))
         .mount(&mock_server)
         .await;
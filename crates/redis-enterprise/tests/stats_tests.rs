@@ -610,3 +610,38 @@ async fn test_stats_shards() {
     assert_eq!(stats.stats[0].uid, 1);
     assert_eq!(stats.stats[1].uid, 2);
 }
+
+#[tokio::test]
+async fn test_get_with_query_encodes_parameters() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/v1/bdbs/1/stats"))
+        .and(query_param("interval", "1hour&stime=x"))
+        .and(basic_auth("admin", "password"))
+        .respond_with(success_response(json!({"intervals": []})))
+        .mount(&mock_server)
+        .await;
+
+    let client = EnterpriseClient::builder()
+        .base_url(mock_server.uri())
+        .username("admin")
+        .password("password")
+        .build()
+        .unwrap();
+
+    let result: serde_json::Value = client
+        .get_with_query("/v1/bdbs/1/stats", &[("interval", Some("1hour&stime=x"))])
+        .await
+        .unwrap();
+    assert_eq!(result, json!({"intervals": []}));
+
+    // A parameter without a value is left out of the query string, so no mock matches
+    let missing = client
+        .get_with_query::<serde_json::Value, _>("/v1/bdbs/1/stats", &[("interval", None::<&str>)])
+        .await;
+    assert!(missing.is_err());
+    let requests = mock_server.received_requests().await.unwrap();
+    assert_eq!(requests[0].url.query(), Some("interval=1hour%26stime%3Dx"));
+    assert_eq!(requests[1].url.query(), None);
+}
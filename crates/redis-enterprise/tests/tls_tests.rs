@@ -0,0 +1,163 @@
+//! HTTPS against a cluster with a self-signed certificate
+//!
+//! Runs against whichever TLS backend the crate is built with:
+//!
+//! ```text
+//! cargo test -p redis-enterprise --test tls_tests
+//! cargo test -p redis-enterprise --no-default-features --features native-tls --test tls_tests
+//! ```
+
+#![cfg(any(feature = "rustls-tls", feature = "native-tls"))]
+
+use redis_enterprise::{EnterpriseClient, RestError};
+use serde_json::json;
+use std::sync::Arc;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+use tokio_rustls::TlsAcceptor;
+use tokio_rustls::rustls::ServerConfig;
+use tokio_rustls::rustls::pki_types::pem::PemObject;
+use tokio_rustls::rustls::pki_types::{CertificateDer, PrivateKeyDer};
+
+const CERT: &[u8] = include_bytes!("fixtures/self-signed.crt");
+const KEY: &[u8] = include_bytes!("fixtures/self-signed.key");
+
+/// Serve `body` as JSON over HTTPS with the self-signed certificate, returning the base URL
+async fn start_https_server(body: serde_json::Value) -> String {
+    let cert = CertificateDer::from_pem_slice(CERT).unwrap();
+    let key = PrivateKeyDer::from_pem_slice(KEY).unwrap();
+    let config = ServerConfig::builder_with_provider(Arc::new(
+        tokio_rustls::rustls::crypto::ring::default_provider(),
+    ))
+    .with_safe_default_protocol_versions()
+    .unwrap()
+    .with_no_client_auth()
+    .with_single_cert(vec![cert], key)
+    .unwrap();
+    let acceptor = TlsAcceptor::from(Arc::new(config));
+
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let body = body.to_string();
+    tokio::spawn(async move {
+        loop {
+            let Ok((stream, _)) = listener.accept().await else {
+                return;
+            };
+            let acceptor = acceptor.clone();
+            let body = body.clone();
+            tokio::spawn(async move {
+                // A client that rejects the certificate aborts the handshake
+                let Ok(mut stream) = acceptor.accept(stream).await else {
+                    return;
+                };
+                let mut request = Vec::new();
+                let mut buf = [0u8; 1024];
+                while !request.windows(4).any(|w| w == b"\r\n\r\n") {
+                    match stream.read(&mut buf).await {
+                        Ok(0) | Err(_) => return,
+                        Ok(n) => request.extend_from_slice(&buf[..n]),
+                    }
+                }
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    body.len(),
+                    body
+                );
+                let _ = stream.write_all(response.as_bytes()).await;
+                let _ = stream.shutdown().await;
+            });
+        }
+    });
+    format!("https://localhost:{}", port)
+}
+
+fn client(base_url: &str, insecure: bool) -> EnterpriseClient {
+    EnterpriseClient::builder()
+        .base_url(base_url)
+        .username("admin")
+        .password("password")
+        .insecure(insecure)
+        .build()
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_self_signed_cluster_with_insecure() {
+    let base_url = start_https_server(json!({"name": "self-signed-cluster"})).await;
+
+    let cluster = client(&base_url, true)
+        .get_raw("/v1/cluster")
+        .await
+        .unwrap();
+    assert_eq!(cluster["name"], "self-signed-cluster");
+}
+
+#[tokio::test]
+async fn test_self_signed_cluster_rejected_by_default() {
+    let base_url = start_https_server(json!({"name": "self-signed-cluster"})).await;
+
+    let result = client(&base_url, false).get_raw("/v1/cluster").await;
+    assert!(
+        matches!(result, Err(RestError::ConnectionError(_))),
+        "expected a certificate error, got {:?}",
+        result
+    );
+}
+
+/// SHA-256 fingerprint of `fixtures/self-signed.crt`
+#[cfg(feature = "rustls-tls")]
+const CERT_FINGERPRINT: &str = "44:CF:47:25:EB:DC:6E:1C:4E:ED:D3:0E:5D:12:32:16:05:B1:71:5E:F5:72:74:F7:BF:5C:A0:F6:71:03:2E:DC";
+
+#[cfg(feature = "rustls-tls")]
+fn pinned_client(base_url: &str, fingerprint: &str) -> EnterpriseClient {
+    EnterpriseClient::builder()
+        .base_url(base_url)
+        .username("admin")
+        .password("password")
+        .tls_fingerprint_sha256(fingerprint)
+        .build()
+        .unwrap()
+}
+
+#[cfg(feature = "rustls-tls")]
+#[tokio::test]
+async fn test_self_signed_cluster_with_pinned_fingerprint() {
+    let base_url = start_https_server(json!({"name": "self-signed-cluster"})).await;
+
+    let cluster = pinned_client(&base_url, &CERT_FINGERPRINT.to_lowercase())
+        .get_raw("/v1/cluster")
+        .await
+        .unwrap();
+    assert_eq!(cluster["name"], "self-signed-cluster");
+}
+
+#[cfg(feature = "rustls-tls")]
+#[tokio::test]
+async fn test_pinned_fingerprint_mismatch_fails_closed() {
+    let base_url = start_https_server(json!({"name": "self-signed-cluster"})).await;
+    let other = CERT_FINGERPRINT.replace("44:CF", "00:00");
+
+    let result = pinned_client(&base_url, &other)
+        .get_raw("/v1/cluster")
+        .await;
+    match result {
+        Err(RestError::ConnectionError(message)) => {
+            assert!(
+                message.contains("fingerprint mismatch") && message.contains(CERT_FINGERPRINT),
+                "unexpected message: {}",
+                message
+            );
+        }
+        other => panic!("expected a fingerprint mismatch, got {:?}", other),
+    }
+}
+
+#[cfg(feature = "rustls-tls")]
+#[test]
+fn test_invalid_fingerprint_rejected_at_build() {
+    let result = EnterpriseClient::builder()
+        .tls_fingerprint_sha256("not-a-fingerprint")
+        .build();
+    assert!(matches!(result, Err(RestError::ValidationError(_))));
+}
@@ -1,5 +1,7 @@
 //! Usage report endpoint tests for Redis Enterprise
 
+#![cfg(feature = "usage-report")]
+
 use redis_enterprise::{EnterpriseClient, UsageReportConfig, UsageReportHandler};
 use serde_json::json;
 use wiremock::matchers::{basic_auth, body_json, method, path};
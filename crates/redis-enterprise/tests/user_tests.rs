@@ -174,3 +174,28 @@ async fn test_user_delete() {
 
     assert!(result.is_ok());
 }
+
+#[tokio::test]
+async fn test_user_revoke_sessions() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("DELETE"))
+        .and(path("/v1/users/1/sessions"))
+        .and(basic_auth("admin", "password"))
+        .respond_with(no_content_response())
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let client = EnterpriseClient::builder()
+        .base_url(mock_server.uri())
+        .username("admin")
+        .password("password")
+        .build()
+        .unwrap();
+
+    let handler = UserHandler::new(client);
+    let result = handler.revoke_sessions(1).await;
+
+    assert!(result.is_ok());
+}
@@ -0,0 +1,191 @@
+//! Per-command API request statistics
+//!
+//! Every client created by the connection manager reports its requests to one process-wide
+//! collector. With `-v`, a one-line footer after the command shows how many requests were made,
+//! the total time spent waiting on the API and the slowest request. Only these running totals are
+//! kept, so long-running commands use constant memory.
+
+#![allow(dead_code)]
+
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::Duration;
+
+static STATS: OnceLock<Arc<ApiStats>> = OnceLock::new();
+
+/// The collector shared by all clients of this process
+pub fn global() -> Arc<ApiStats> {
+    STATS.get_or_init(|| Arc::new(ApiStats::default())).clone()
+}
+
+/// One request as recorded by the collector
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecordedRequest {
+    pub method: &'static str,
+    pub path: String,
+    pub status: Option<u16>,
+    pub elapsed: Duration,
+}
+
+/// Totals over every request made so far
+#[derive(Debug, Clone, PartialEq)]
+pub struct Summary {
+    pub requests: usize,
+    pub failed: usize,
+    /// Requests with a method other than GET or HEAD, which may have changed something
+    pub changes: usize,
+    pub total: Duration,
+    pub slowest: RecordedRequest,
+}
+
+#[derive(Debug, Default)]
+pub struct ApiStats {
+    summary: Mutex<Option<Summary>>,
+    cloud_rate_limit: Mutex<Option<redis_cloud::RateLimit>>,
+}
+
+impl ApiStats {
+    fn record(&self, request: RecordedRequest) {
+        let Ok(mut summary) = self.summary.lock() else {
+            return;
+        };
+        let failed = request.status.is_none_or(|status| status >= 400);
+        let change = !matches!(request.method, "GET" | "HEAD");
+        match summary.as_mut() {
+            Some(summary) => {
+                summary.requests += 1;
+                summary.failed += usize::from(failed);
+                summary.changes += usize::from(change);
+                summary.total = summary.total.saturating_add(request.elapsed);
+                if request.elapsed > summary.slowest.elapsed {
+                    summary.slowest = request;
+                }
+            }
+            None => {
+                *summary = Some(Summary {
+                    requests: 1,
+                    failed: usize::from(failed),
+                    changes: usize::from(change),
+                    total: request.elapsed,
+                    slowest: request,
+                })
+            }
+        }
+    }
+
+    /// The rate-limit state from the most recent Cloud response that reported one
+    pub fn cloud_rate_limit(&self) -> Option<redis_cloud::RateLimit> {
+        *self.cloud_rate_limit.lock().ok()?
+    }
+
+    /// Whether any request that may have changed something was sent
+    pub fn sent_changes(&self) -> bool {
+        self.summary().is_some_and(|summary| summary.changes > 0)
+    }
+
+    /// Totals, or `None` when no request was made
+    pub fn summary(&self) -> Option<Summary> {
+        self.summary.lock().ok()?.clone()
+    }
+}
+
+impl redis_cloud::RequestObserver for ApiStats {
+    fn on_request(&self, event: &redis_cloud::RequestEvent) {
+        if let (Some(rate_limit), Ok(mut last)) = (event.rate_limit, self.cloud_rate_limit.lock()) {
+            *last = Some(rate_limit);
+        }
+        self.record(RecordedRequest {
+            method: event.method,
+            path: event.path.clone(),
+            status: event.status,
+            elapsed: event.elapsed,
+        });
+    }
+}
+
+impl redis_enterprise::RequestObserver for ApiStats {
+    fn on_request(&self, event: &redis_enterprise::RequestEvent) {
+        self.record(RecordedRequest {
+            method: event.method,
+            path: event.path.clone(),
+            status: event.status,
+            elapsed: event.elapsed,
+        });
+    }
+}
+
+/// The footer line, e.g. `API: 3 requests in 412 ms; slowest GET /v1/bdbs (250 ms)`
+pub fn format_summary(summary: &Summary) -> String {
+    let failed = if summary.failed > 0 {
+        format!(", {} failed", summary.failed)
+    } else {
+        String::new()
+    };
+    format!(
+        "API: {} request{}{} in {}; slowest {} {} ({})",
+        summary.requests,
+        if summary.requests == 1 { "" } else { "s" },
+        failed,
+        format_duration(summary.total),
+        summary.slowest.method,
+        summary.slowest.path,
+        format_duration(summary.slowest.elapsed)
+    )
+}
+
+fn format_duration(duration: Duration) -> String {
+    if duration < Duration::from_secs(1) {
+        format!("{} ms", duration.as_millis())
+    } else {
+        format!("{:.2} s", duration.as_secs_f64())
+    }
+}
+
+/// Print the footer to stderr if any request was made
+pub fn print_summary() {
+    if let Some(summary) = global().summary() {
+        eprintln!("{}", format_summary(&summary));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(path: &str, status: Option<u16>, millis: u64) -> RecordedRequest {
+        RecordedRequest {
+            method: "GET",
+            path: path.to_string(),
+            status,
+            elapsed: Duration::from_millis(millis),
+        }
+    }
+
+    #[test]
+    fn test_summary() {
+        let stats = ApiStats::default();
+        assert!(stats.summary().is_none());
+
+        stats.record(request("/v1/cluster", Some(200), 120));
+        stats.record(request("/v1/bdbs", Some(200), 950));
+        stats.record(request("/v1/nodes", Some(404), 40));
+        stats.record(request("/v1/shards", None, 30));
+        let summary = stats.summary().unwrap();
+        assert_eq!(summary.requests, 4);
+        assert_eq!(summary.failed, 2);
+        assert_eq!(summary.changes, 0);
+        assert!(!stats.sent_changes());
+        assert_eq!(summary.total, Duration::from_millis(1140));
+        assert_eq!(summary.slowest.path, "/v1/bdbs");
+        assert_eq!(
+            format_summary(&summary),
+            "API: 4 requests, 2 failed in 1.14 s; slowest GET /v1/bdbs (950 ms)"
+        );
+
+        stats.record(RecordedRequest {
+            method: "DELETE",
+            ..request("/v1/bdbs/1", Some(200), 10)
+        });
+        assert_eq!(stats.summary().unwrap().changes, 1);
+        assert!(stats.sent_changes());
+    }
+}
@@ -0,0 +1,354 @@
+//! TLS certificate chain handling shared by Cloud and Enterprise commands
+//!
+//! Cloud's database certificate endpoint and Enterprise's OCSP settings both return PEM, possibly
+//! a whole chain. These helpers split the bundle, describe each certificate and check that the
+//! chain links up and is within its validity period.
+
+#![allow(dead_code)]
+
+use chrono::DateTime;
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tabled::{Table, Tabled, settings::Style};
+use x509_parser::pem::Pem;
+
+/// Certificates expiring within this many days are reported as a warning
+pub const EXPIRY_WARNING_DAYS: i64 = 30;
+
+/// One certificate of the chain
+#[derive(Debug, Clone, Serialize)]
+pub struct CertificateInfo {
+    pub subject: String,
+    pub issuer: String,
+    pub serial: String,
+    pub not_before: String,
+    pub not_after: String,
+    pub days_remaining: i64,
+    pub is_ca: bool,
+    pub self_signed: bool,
+    /// `ok`, `expiring`, `expired` or `not-yet-valid`
+    pub status: String,
+    /// PEM of this certificate alone
+    #[serde(skip)]
+    pub pem: String,
+}
+
+/// Result of checking a chain
+#[derive(Debug, Clone, Serialize)]
+pub struct ChainVerification {
+    pub valid: bool,
+    pub problems: Vec<String>,
+    pub warnings: Vec<String>,
+}
+
+#[derive(Tabled)]
+struct CertificateRow {
+    #[tabled(rename = "#")]
+    index: usize,
+    #[tabled(rename = "SUBJECT")]
+    subject: String,
+    #[tabled(rename = "ISSUER")]
+    issuer: String,
+    #[tabled(rename = "NOT AFTER")]
+    not_after: String,
+    #[tabled(rename = "DAYS LEFT")]
+    days_remaining: i64,
+    #[tabled(rename = "STATUS")]
+    status: String,
+}
+
+/// Turn an API certificate string into PEM text
+///
+/// The API documents newlines as escaped `\n`; undo that if the escapes survived decoding.
+pub fn normalize_pem(raw: &str) -> String {
+    let pem = if !raw.contains('\n') && raw.contains("\\n") {
+        raw.replace("\\n", "\n")
+    } else {
+        raw.to_string()
+    };
+    let mut pem = pem.trim().to_string();
+    pem.push('\n');
+    pem
+}
+
+/// Split a PEM bundle into its certificates, in order, as seen at `now` (Unix seconds)
+pub fn parse_chain(pem: &str, now: i64) -> Result<Vec<CertificateInfo>, String> {
+    let mut certificates = Vec::new();
+    for (index, block) in Pem::iter_from_buffer(pem.as_bytes()).enumerate() {
+        let block = block.map_err(|e| format!("invalid PEM block {}: {}", index + 1, e))?;
+        if block.label != "CERTIFICATE" {
+            continue;
+        }
+        let cert = block
+            .parse_x509()
+            .map_err(|e| format!("invalid certificate {}: {}", index + 1, e))?;
+
+        let not_before = cert.validity().not_before.timestamp();
+        let not_after = cert.validity().not_after.timestamp();
+        let days_remaining = (not_after - now).div_euclid(86_400);
+        let status = if now < not_before {
+            "not-yet-valid"
+        } else if now > not_after {
+            "expired"
+        } else if days_remaining < EXPIRY_WARNING_DAYS {
+            "expiring"
+        } else {
+            "ok"
+        };
+
+        certificates.push(CertificateInfo {
+            subject: cert.subject().to_string(),
+            issuer: cert.issuer().to_string(),
+            serial: cert.raw_serial_as_string(),
+            not_before: format_time(not_before),
+            not_after: format_time(not_after),
+            days_remaining,
+            is_ca: cert.is_ca(),
+            self_signed: cert.subject() == cert.issuer(),
+            status: status.to_string(),
+            pem: pem_block(&block.contents),
+        });
+    }
+
+    if certificates.is_empty() {
+        return Err("no certificates found in PEM data".to_string());
+    }
+    Ok(certificates)
+}
+
+/// Check validity periods and that each certificate is signed by the next one
+///
+/// A chain that stops before its root is fine; clients supply the root from their trust store.
+pub fn verify_chain(pem: &str, now: i64) -> Result<ChainVerification, String> {
+    let infos = parse_chain(pem, now)?;
+    let blocks: Vec<Pem> = Pem::iter_from_buffer(pem.as_bytes())
+        .filter_map(Result::ok)
+        .filter(|block| block.label == "CERTIFICATE")
+        .collect();
+    let certs = blocks
+        .iter()
+        .map(|block| block.parse_x509().map_err(|e| e.to_string()))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut problems = Vec::new();
+    let mut warnings = Vec::new();
+
+    for (index, info) in infos.iter().enumerate() {
+        let position = index + 1;
+        match info.status.as_str() {
+            "expired" => problems.push(format!(
+                "certificate {} ({}) expired on {}",
+                position, info.subject, info.not_after
+            )),
+            "not-yet-valid" => problems.push(format!(
+                "certificate {} ({}) is not valid before {}",
+                position, info.subject, info.not_before
+            )),
+            "expiring" => warnings.push(format!(
+                "certificate {} ({}) expires in {} day(s)",
+                position, info.subject, info.days_remaining
+            )),
+            _ => {}
+        }
+    }
+
+    for (index, pair) in certs.windows(2).enumerate() {
+        let (cert, issuer) = (&pair[0], &pair[1]);
+        if cert.issuer() != issuer.subject() {
+            problems.push(format!(
+                "certificate {} is issued by {}, but certificate {} is {}",
+                index + 1,
+                cert.issuer(),
+                index + 2,
+                issuer.subject()
+            ));
+        } else if cert.verify_signature(Some(issuer.public_key())).is_err() {
+            problems.push(format!(
+                "certificate {} is not signed by certificate {}",
+                index + 1,
+                index + 2
+            ));
+        }
+    }
+
+    if let Some(last) = certs.last() {
+        if last.subject() == last.issuer() {
+            if last.verify_signature(None).is_err() {
+                problems.push(format!(
+                    "self-signed certificate {} has an invalid signature",
+                    certs.len()
+                ));
+            }
+        } else {
+            warnings.push(format!(
+                "chain ends at {}; its issuer {} must be in the client's trust store",
+                last.subject(),
+                last.issuer()
+            ));
+        }
+    }
+
+    Ok(ChainVerification {
+        valid: problems.is_empty(),
+        problems,
+        warnings,
+    })
+}
+
+/// Write the chain to `dir` as `<prefix>-chain.pem`, `<prefix>-cert.pem` and, when the chain
+/// has more than one certificate, `<prefix>-ca.pem`
+pub fn write_pem_files(
+    dir: &Path,
+    prefix: &str,
+    pem: &str,
+    certificates: &[CertificateInfo],
+) -> std::io::Result<Vec<PathBuf>> {
+    fs::create_dir_all(dir)?;
+
+    let mut files = vec![(format!("{}-chain.pem", prefix), pem.to_string())];
+    if let Some((leaf, rest)) = certificates.split_first() {
+        files.push((format!("{}-cert.pem", prefix), leaf.pem.clone()));
+        if !rest.is_empty() {
+            let ca: String = rest.iter().map(|c| c.pem.as_str()).collect();
+            files.push((format!("{}-ca.pem", prefix), ca));
+        }
+    }
+
+    files
+        .into_iter()
+        .map(|(name, content)| {
+            let path = dir.join(name);
+            fs::write(&path, content)?;
+            Ok(path)
+        })
+        .collect()
+}
+
+/// Render the chain as a table
+pub fn chain_table(certificates: &[CertificateInfo]) -> String {
+    let rows: Vec<CertificateRow> = certificates
+        .iter()
+        .enumerate()
+        .map(|(index, cert)| CertificateRow {
+            index: index + 1,
+            subject: cert.subject.clone(),
+            issuer: cert.issuer.clone(),
+            not_after: cert.not_after.clone(),
+            days_remaining: cert.days_remaining,
+            status: cert.status.clone(),
+        })
+        .collect();
+    let mut table = Table::new(rows);
+    table.with(Style::modern());
+    table.to_string()
+}
+
+fn format_time(timestamp: i64) -> String {
+    DateTime::from_timestamp(timestamp, 0)
+        .map(|t| t.format("%Y-%m-%d %H:%M:%S UTC").to_string())
+        .unwrap_or_else(|| timestamp.to_string())
+}
+
+/// Re-encode DER contents as a PEM certificate block
+fn pem_block(der: &[u8]) -> String {
+    use base64::Engine;
+    let encoded = base64::engine::general_purpose::STANDARD.encode(der);
+    let mut pem = String::from("-----BEGIN CERTIFICATE-----\n");
+    for line in encoded.as_bytes().chunks(64) {
+        pem.push_str(std::str::from_utf8(line).unwrap_or_default());
+        pem.push('\n');
+    }
+    pem.push_str("-----END CERTIFICATE-----\n");
+    pem
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LEAF: &str = "-----BEGIN CERTIFICATE-----
+MIIBfjCCASWgAwIBAgIUa2J00l10OZY4pB3DzJEVk+9/N4UwCgYIKoZIzj0EAwIw
+FzEVMBMGA1UEAwwMVGVzdCBSb290IENBMCAXDTI2MTAxNjE1MzUzM1oYDzIxMjYw
+OTIyMTUzNTMzWjAiMSAwHgYDVQQDDBdyZWRpcy0xMjM0NS5leGFtcGxlLmNvbTBZ
+MBMGByqGSM49AgEGCCqGSM49AwEHA0IABHlkEKL3R0wgj0LUqQjAbrXn+d3bzKMk
+YsBVjxht0bQDkip4IFTRUyX6TPIUrbatK6ejO9PB3/zV29Ddg4QV06SjQjBAMB0G
+A1UdDgQWBBQKn1GPDm7iG1XRUgUhAtwC++PUjzAfBgNVHSMEGDAWgBQXRnWJUIL8
+i7yJktPr8+cvf+6x/jAKBggqhkjOPQQDAgNHADBEAiASkZR0FbgZzLBKcxzKKmOe
+OFWqfqelPf/Arwy4pf4x2AIgdX8mpJ2AGONxDiyYi8INFzFNzXtZr9IoZi2FMx3v
+ZRs=
+-----END CERTIFICATE-----
+";
+
+    const ROOT: &str = "-----BEGIN CERTIFICATE-----
+MIIBhTCCASugAwIBAgIUUJdjE+biBDrKY2pyMy15Ke/ear8wCgYIKoZIzj0EAwIw
+FzEVMBMGA1UEAwwMVGVzdCBSb290IENBMCAXDTI2MTAxNjE1MzUzM1oYDzIxMjYw
+OTIyMTUzNTMzWjAXMRUwEwYDVQQDDAxUZXN0IFJvb3QgQ0EwWTATBgcqhkjOPQIB
+BggqhkjOPQMBBwNCAATP5CeXG1UvQdhFRRQ/MeQlW4p02gUCOlsA4Ukj7+xBQIQf
+dy2PRVXEGI2G7eqNN+pjQTyaNsDMddfuCIDCazjJo1MwUTAdBgNVHQ4EFgQUF0Z1
+iVCC/Iu8iZLT6/PnL3/usf4wHwYDVR0jBBgwFoAUF0Z1iVCC/Iu8iZLT6/PnL3/u
+sf4wDwYDVR0TAQH/BAUwAwEB/zAKBggqhkjOPQQDAgNIADBFAiA2aRWzY1jT0R09
+R5xvBaJlC9mdLpsD2tHwTOzf8SG6bAIhANRr6D/F12FjwtBtUOjTcnuY1rBwpUUq
+sv8F6AJwUFc+
+-----END CERTIFICATE-----
+";
+
+    /// 2030-01-01T00:00:00Z, inside the test certificates' validity
+    const NOW: i64 = 1_893_456_000;
+
+    #[test]
+    fn test_parse_chain() {
+        let chain = format!("{}{}", LEAF, ROOT);
+        let certs = parse_chain(&chain, NOW).unwrap();
+
+        assert_eq!(certs.len(), 2);
+        assert_eq!(certs[0].subject, "CN=redis-12345.example.com");
+        assert_eq!(certs[0].issuer, "CN=Test Root CA");
+        assert!(!certs[0].self_signed);
+        assert!(certs[1].self_signed);
+        assert!(certs[1].is_ca);
+        assert_eq!(certs[0].status, "ok");
+        assert_eq!(certs[0].pem, LEAF);
+    }
+
+    #[test]
+    fn test_verify_chain() {
+        let valid = verify_chain(&format!("{}{}", LEAF, ROOT), NOW).unwrap();
+        assert!(valid.valid, "{:?}", valid.problems);
+        assert!(valid.warnings.is_empty());
+
+        let partial = verify_chain(LEAF, NOW).unwrap();
+        assert!(partial.valid);
+        assert_eq!(partial.warnings.len(), 1);
+
+        let reversed = verify_chain(&format!("{}{}", ROOT, LEAF), NOW).unwrap();
+        assert!(!reversed.valid);
+
+        // Far past the certificates' expiry in 2126
+        let expired = verify_chain(LEAF, 7_000_000_000).unwrap();
+        assert!(!expired.valid);
+        assert!(expired.problems[0].contains("expired"));
+    }
+
+    #[test]
+    fn test_normalize_pem_and_write_files() {
+        let escaped = format!("{}{}", LEAF, ROOT).replace('\n', "\\n");
+        let pem = normalize_pem(&escaped);
+        assert_eq!(pem, format!("{}{}", LEAF, ROOT));
+
+        let dir = tempfile::tempdir().unwrap();
+        let certs = parse_chain(&pem, NOW).unwrap();
+        let files = write_pem_files(&dir.path().join("certs"), "123-456", &pem, &certs).unwrap();
+        let names: Vec<_> = files
+            .iter()
+            .map(|f| f.file_name().unwrap().to_string_lossy().into_owned())
+            .collect();
+        assert_eq!(
+            names,
+            ["123-456-chain.pem", "123-456-cert.pem", "123-456-ca.pem"]
+        );
+        assert_eq!(fs::read_to_string(&files[2]).unwrap(), ROOT);
+
+        assert!(parse_chain("not a certificate", NOW).is_err());
+    }
+}
@@ -12,6 +12,8 @@ use serde_json::Value;
 #[allow(dead_code)] // Used by binary target
 pub struct ApiCommandParams {
     pub config: Config,
+    pub cloud_api_url: Option<String>,
+    pub unlock: bool,
     pub profile_name: Option<String>,
     pub deployment: DeploymentType,
     pub method: HttpMethod,
@@ -24,7 +26,9 @@ pub struct ApiCommandParams {
 /// Handle raw API commands
 #[allow(dead_code)] // Used by binary target
 pub async fn handle_api_command(params: ApiCommandParams) -> CliResult<()> {
-    let connection_manager = ConnectionManager::new(params.config);
+    let connection_manager = ConnectionManager::new(params.config)
+        .with_cloud_api_url(params.cloud_api_url)
+        .with_unlock(params.unlock);
 
     match params.deployment {
         DeploymentType::Cloud => {
@@ -122,6 +126,17 @@ async fn handle_cloud_api(
                 }
                 crate::cli::OutputFormat::Yaml => crate::output::OutputFormat::Yaml,
                 crate::cli::OutputFormat::Table => crate::output::OutputFormat::Table,
+                crate::cli::OutputFormat::Id => {
+                    let data = match query.as_deref() {
+                        Some(q) => crate::commands::cloud::utils::apply_jmespath(&response, q)?,
+                        None => response,
+                    };
+                    return crate::output::print_ids(&data).map_err(|e| {
+                        crate::error::RedisCtlError::OutputError {
+                            message: e.to_string(),
+                        }
+                    });
+                }
             };
 
             print_output(response, format, query.as_deref()).map_err(|e| {
@@ -131,6 +146,7 @@ async fn handle_cloud_api(
             })?;
             Ok(())
         }
+        Err(e @ redis_cloud::CloudError::ReadOnly { .. }) => Err(e.into()),
         Err(e) => {
             // Format error nicely
             eprintln!("API Error: {}", e);
@@ -211,6 +227,19 @@ async fn handle_enterprise_api(
         None
     };
 
+    // Large list endpoints are printed element by element instead of being buffered
+    if matches!(method, HttpMethod::Get)
+        && query.is_none()
+        && matches!(output_format, OutputFormat::Auto | OutputFormat::Json)
+        && is_streamed_enterprise_path(&normalized_path)
+    {
+        if let Err(e) = stream_json_array(&client, &normalized_path).await {
+            eprintln!("API Error: {}", e);
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
     // Execute the API call based on HTTP method
     let result: std::result::Result<Value, _> = match method {
         HttpMethod::Get => client.get_raw(&normalized_path).await,
@@ -238,6 +267,17 @@ async fn handle_enterprise_api(
                 }
                 crate::cli::OutputFormat::Yaml => crate::output::OutputFormat::Yaml,
                 crate::cli::OutputFormat::Table => crate::output::OutputFormat::Table,
+                crate::cli::OutputFormat::Id => {
+                    let data = match query.as_deref() {
+                        Some(q) => crate::commands::cloud::utils::apply_jmespath(&response, q)?,
+                        None => response,
+                    };
+                    return crate::output::print_ids(&data).map_err(|e| {
+                        crate::error::RedisCtlError::OutputError {
+                            message: e.to_string(),
+                        }
+                    });
+                }
             };
 
             print_output(response, format, query.as_deref()).map_err(|e| {
@@ -247,6 +287,7 @@ async fn handle_enterprise_api(
             })?;
             Ok(())
         }
+        Err(e @ redis_enterprise::RestError::ReadOnly { .. }) => Err(e.into()),
         Err(e) => {
             // Format error nicely
             eprintln!("API Error: {}", e);
@@ -254,3 +295,54 @@ async fn handle_enterprise_api(
         }
     }
 }
+
+/// Enterprise endpoints known to return very large JSON arrays
+const STREAMED_ENTERPRISE_PATHS: &[&str] = &["/v1/logs"];
+
+/// Check whether a GET on this path should be streamed
+fn is_streamed_enterprise_path(path: &str) -> bool {
+    let path = path.split('?').next().unwrap_or(path);
+    STREAMED_ENTERPRISE_PATHS.contains(&path.trim_end_matches('/'))
+}
+
+/// Print a JSON array response as it arrives, formatted like the buffered pretty output
+async fn stream_json_array(
+    client: &redis_enterprise::EnterpriseClient,
+    path: &str,
+) -> redis_enterprise::Result<()> {
+    use std::io::Write;
+
+    let write_error = |e: std::io::Error| redis_enterprise::RestError::ParseError(e.to_string());
+    let mut stdout = std::io::stdout().lock();
+    let mut first = true;
+
+    client
+        .get_stream(path, |element: Value| {
+            let pretty = serde_json::to_string_pretty(&element)?;
+            let indented = pretty
+                .lines()
+                .map(|line| format!("  {}", line))
+                .collect::<Vec<_>>()
+                .join("\n");
+            let separator = if first { "[\n" } else { ",\n" };
+            first = false;
+            write!(stdout, "{}{}", separator, indented).map_err(write_error)
+        })
+        .await?;
+
+    let closing = if first { "[]" } else { "\n]" };
+    writeln!(stdout, "{}", closing).map_err(write_error)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_streamed_enterprise_path() {
+        assert!(is_streamed_enterprise_path("/v1/logs"));
+        assert!(is_streamed_enterprise_path("/v1/logs?limit=1000"));
+        assert!(!is_streamed_enterprise_path("/v1/logs/123"));
+        assert!(!is_streamed_enterprise_path("/v1/bdbs"));
+    }
+}
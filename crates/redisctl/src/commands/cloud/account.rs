@@ -3,13 +3,16 @@
 #![allow(dead_code)] // Used by binary target
 
 use anyhow::Context;
+use chrono::{DateTime, Utc};
 use redis_cloud::AccountHandler;
+use redis_cloud::account::{AccountSessionLogEntry, AccountSystemLogEntry};
 use serde_json::Value;
 use tabled::{Table, settings::Style};
 
-use crate::cli::{CloudAccountCommands, OutputFormat};
+use crate::cli::{CloudAccountCommands, LogExportFormat, OutputFormat};
 use crate::connection::ConnectionManager;
-use crate::error::Result as CliResult;
+use crate::error::{RedisCtlError, Result as CliResult};
+use crate::output::notice;
 
 use super::utils::*;
 
@@ -44,27 +47,41 @@ pub async fn handle_account_command(
         CloudAccountCommands::GetPersistenceOptions => {
             get_persistence_options(conn_mgr, profile_name, output_format, query).await
         }
-        CloudAccountCommands::GetSystemLogs { limit, offset } => {
-            get_system_logs(
-                conn_mgr,
-                profile_name,
+        CloudAccountCommands::GetSystemLogs {
+            limit,
+            offset,
+            since,
+            until,
+            all,
+            export,
+        } => {
+            let options = LogOptions::new(
                 *limit,
                 *offset,
-                output_format,
-                query,
-            )
-            .await
-        }
-        CloudAccountCommands::GetSessionLogs { limit, offset } => {
-            get_session_logs(
-                conn_mgr,
-                profile_name,
+                since.as_deref(),
+                until.as_deref(),
+                *all,
+                *export,
+            )?;
+            get_system_logs(conn_mgr, profile_name, &options, output_format, query).await
+        }
+        CloudAccountCommands::GetSessionLogs {
+            limit,
+            offset,
+            since,
+            until,
+            all,
+            export,
+        } => {
+            let options = LogOptions::new(
                 *limit,
                 *offset,
-                output_format,
-                query,
-            )
-            .await
+                since.as_deref(),
+                until.as_deref(),
+                *all,
+                *export,
+            )?;
+            get_session_logs(conn_mgr, profile_name, &options, output_format, query).await
         }
         CloudAccountCommands::GetSearchScaling => {
             get_search_scaling(conn_mgr, profile_name, output_format, query).await
@@ -104,7 +121,7 @@ fn print_payment_methods_table(data: &Value) -> CliResult<()> {
 
     if let Some(methods) = methods {
         if methods.is_empty() {
-            println!("No payment methods configured");
+            notice!("No payment methods configured");
             return Ok(());
         }
 
@@ -136,7 +153,7 @@ fn print_payment_methods_table(data: &Value) -> CliResult<()> {
         table.with(Style::blank());
         output_with_pager(&table.to_string());
     } else {
-        println!("No payment methods data available");
+        notice!("No payment methods data available");
     }
     Ok(())
 }
@@ -147,7 +164,7 @@ fn print_regions_table(data: &Value) -> CliResult<()> {
 
     if let Some(regions) = regions {
         if regions.is_empty() {
-            println!("No regions available");
+            notice!("No regions available");
             return Ok(());
         }
 
@@ -174,7 +191,7 @@ fn print_regions_table(data: &Value) -> CliResult<()> {
         table.with(Style::blank());
         output_with_pager(&table.to_string());
     } else {
-        println!("No regions data available");
+        notice!("No regions data available");
     }
     Ok(())
 }
@@ -185,7 +202,7 @@ fn print_modules_table(data: &Value) -> CliResult<()> {
 
     if let Some(modules) = modules {
         if modules.is_empty() {
-            println!("No modules available");
+            notice!("No modules available");
             return Ok(());
         }
 
@@ -209,7 +226,7 @@ fn print_modules_table(data: &Value) -> CliResult<()> {
         table.with(Style::blank());
         output_with_pager(&table.to_string());
     } else {
-        println!("No modules data available");
+        notice!("No modules data available");
     }
     Ok(())
 }
@@ -220,7 +237,7 @@ fn print_persistence_table(data: &Value) -> CliResult<()> {
 
     if let Some(options) = options {
         if options.is_empty() {
-            println!("No persistence options available");
+            notice!("No persistence options available");
             return Ok(());
         }
 
@@ -242,7 +259,7 @@ fn print_persistence_table(data: &Value) -> CliResult<()> {
         table.with(Style::blank());
         output_with_pager(&table.to_string());
     } else {
-        println!("No persistence options data available");
+        notice!("No persistence options data available");
     }
     Ok(())
 }
@@ -253,7 +270,7 @@ fn print_system_logs_table(data: &Value) -> CliResult<()> {
 
     if let Some(entries) = entries {
         if entries.is_empty() {
-            println!("No system log entries");
+            notice!("No system log entries");
             return Ok(());
         }
 
@@ -279,7 +296,7 @@ fn print_system_logs_table(data: &Value) -> CliResult<()> {
         table.with(Style::blank());
         output_with_pager(&table.to_string());
     } else {
-        println!("No system log data available");
+        notice!("No system log data available");
     }
     Ok(())
 }
@@ -290,7 +307,7 @@ fn print_session_logs_table(data: &Value) -> CliResult<()> {
 
     if let Some(entries) = entries {
         if entries.is_empty() {
-            println!("No session log entries");
+            notice!("No session log entries");
             return Ok(());
         }
 
@@ -316,7 +333,7 @@ fn print_session_logs_table(data: &Value) -> CliResult<()> {
         table.with(Style::blank());
         output_with_pager(&table.to_string());
     } else {
-        println!("No session log data available");
+        notice!("No session log data available");
     }
     Ok(())
 }
@@ -327,7 +344,7 @@ fn print_search_scaling_table(data: &Value) -> CliResult<()> {
 
     if let Some(factors) = factors {
         if factors.is_empty() {
-            println!("No search scaling factors available");
+            notice!("No search scaling factors available");
             return Ok(());
         }
 
@@ -349,7 +366,7 @@ fn print_search_scaling_table(data: &Value) -> CliResult<()> {
         table.with(Style::blank());
         output_with_pager(&table.to_string());
     } else {
-        println!("No search scaling data available");
+        notice!("No search scaling data available");
     }
     Ok(())
 }
@@ -507,7 +524,7 @@ fn print_account_table(data: &Value) -> CliResult<()> {
     }
 
     if rows.is_empty() {
-        println!("No account information available");
+        notice!("No account information available");
         return Ok(());
     }
 
@@ -631,24 +648,135 @@ async fn get_persistence_options(
     Ok(())
 }
 
+/// Paging, time filter and export options shared by the account log commands
+struct LogOptions {
+    limit: Option<u32>,
+    offset: Option<u32>,
+    since: Option<DateTime<Utc>>,
+    until: Option<DateTime<Utc>>,
+    all: bool,
+    export: Option<LogExportFormat>,
+}
+
+impl LogOptions {
+    fn new(
+        limit: Option<u32>,
+        offset: Option<u32>,
+        since: Option<&str>,
+        until: Option<&str>,
+        all: bool,
+        export: Option<LogExportFormat>,
+    ) -> CliResult<Self> {
+        let now = Utc::now();
+        let since = since.map(|s| parse_time_bound(s, now, false)).transpose()?;
+        let until = until.map(|s| parse_time_bound(s, now, true)).transpose()?;
+        if let (Some(since), Some(until)) = (since, until)
+            && since > until
+        {
+            return Err(RedisCtlError::Validation {
+                message: "--since must not be later than --until".to_string(),
+            });
+        }
+
+        Ok(Self {
+            limit,
+            offset,
+            since,
+            until,
+            all,
+            export,
+        })
+    }
+
+    fn page_size(&self) -> i32 {
+        self.limit.unwrap_or(100) as i32
+    }
+
+    /// Keep entries whose `time` falls within the --since/--until window
+    fn filter(&self, entries: Vec<Value>) -> Vec<Value> {
+        if self.since.is_none() && self.until.is_none() {
+            return entries;
+        }
+
+        entries
+            .into_iter()
+            .filter(|entry| {
+                let Some(time) = entry
+                    .get("time")
+                    .and_then(|t| t.as_str())
+                    .and_then(|t| DateTime::parse_from_rfc3339(t).ok())
+                    .map(|t| t.with_timezone(&Utc))
+                else {
+                    return false;
+                };
+                self.since.is_none_or(|since| time >= since)
+                    && self.until.is_none_or(|until| time <= until)
+            })
+            .collect()
+    }
+}
+
+/// Parse a --since/--until value; see [`crate::units::parse_time_bound`]
+pub(crate) fn parse_time_bound(
+    value: &str,
+    now: DateTime<Utc>,
+    end_of_day: bool,
+) -> CliResult<DateTime<Utc>> {
+    crate::units::parse_time_bound(value, now, end_of_day)
+        .map_err(|message| RedisCtlError::Validation { message })
+}
+
 /// Get system logs
 async fn get_system_logs(
     conn_mgr: &ConnectionManager,
     profile_name: Option<&str>,
-    limit: Option<u32>,
-    offset: Option<u32>,
+    options: &LogOptions,
     output_format: OutputFormat,
     query: Option<&str>,
 ) -> CliResult<()> {
     let client = conn_mgr.create_cloud_client(profile_name).await?;
     let handler = AccountHandler::new(client);
 
-    let response = handler
-        .get_account_system_logs(offset.map(|v| v as i32), limit.map(|v| v as i32))
-        .await
-        .context("Failed to fetch system logs")?;
+    let entries: Vec<AccountSystemLogEntry> = if options.all {
+        handler
+            .get_all_account_system_logs(options.page_size(), options.since)
+            .await
+            .context("Failed to fetch system logs")?
+    } else {
+        handler
+            .get_account_system_logs(
+                options.offset.map(|v| v as i32),
+                options.limit.map(|v| v as i32),
+            )
+            .await
+            .context("Failed to fetch system logs")?
+            .entries
+            .unwrap_or_default()
+    };
+
+    let entries = entries
+        .into_iter()
+        .map(serde_json::to_value)
+        .collect::<Result<Vec<_>, _>>()?;
+    let entries = options.filter(entries);
+
+    if let Some(format) = options.export {
+        return export_entries(
+            &entries,
+            &[
+                "id",
+                "time",
+                "originator",
+                "apiKeyName",
+                "resource",
+                "type",
+                "description",
+            ],
+            format,
+        );
+    }
 
-    let json_value = serde_json::to_value(response)?;
+    let json_value = serde_json::json!({ "entries": entries });
     let data = handle_output(json_value, output_format, query)?;
 
     match output_format {
@@ -665,20 +793,54 @@ async fn get_system_logs(
 async fn get_session_logs(
     conn_mgr: &ConnectionManager,
     profile_name: Option<&str>,
-    limit: Option<u32>,
-    offset: Option<u32>,
+    options: &LogOptions,
     output_format: OutputFormat,
     query: Option<&str>,
 ) -> CliResult<()> {
     let client = conn_mgr.create_cloud_client(profile_name).await?;
     let handler = AccountHandler::new(client);
 
-    let response = handler
-        .get_account_session_logs(offset.map(|v| v as i32), limit.map(|v| v as i32))
-        .await
-        .context("Failed to fetch session logs")?;
+    let entries: Vec<AccountSessionLogEntry> = if options.all {
+        handler
+            .get_all_account_session_logs(options.page_size(), options.since)
+            .await
+            .context("Failed to fetch session logs")?
+    } else {
+        handler
+            .get_account_session_logs(
+                options.offset.map(|v| v as i32),
+                options.limit.map(|v| v as i32),
+            )
+            .await
+            .context("Failed to fetch session logs")?
+            .entries
+            .unwrap_or_default()
+    };
+
+    let entries = entries
+        .into_iter()
+        .map(serde_json::to_value)
+        .collect::<Result<Vec<_>, _>>()?;
+    let entries = options.filter(entries);
+
+    if let Some(format) = options.export {
+        return export_entries(
+            &entries,
+            &[
+                "id",
+                "time",
+                "user",
+                "userAgent",
+                "ipAddress",
+                "userRole",
+                "type",
+                "action",
+            ],
+            format,
+        );
+    }
 
-    let json_value = serde_json::to_value(response)?;
+    let json_value = serde_json::json!({ "entries": entries });
     let data = handle_output(json_value, output_format, query)?;
 
     match output_format {
@@ -718,3 +880,52 @@ async fn get_search_scaling(
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_log_options_filter() {
+        let options = LogOptions::new(
+            None,
+            None,
+            Some("2024-01-02"),
+            Some("2024-01-02"),
+            false,
+            None,
+        )
+        .unwrap();
+        let entries = vec![
+            json!({"id": 1, "time": "2024-01-01T23:59:59Z"}),
+            json!({"id": 2, "time": "2024-01-02T08:00:00Z"}),
+            json!({"id": 3, "time": "2024-01-03T00:00:00Z"}),
+            json!({"id": 4}),
+        ];
+        let filtered = options.filter(entries);
+        assert_eq!(
+            filtered,
+            vec![json!({"id": 2, "time": "2024-01-02T08:00:00Z"})]
+        );
+
+        assert!(
+            LogOptions::new(
+                None,
+                None,
+                Some("2024-02-01"),
+                Some("2024-01-01"),
+                false,
+                None
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn test_csv_field_quoting() {
+        assert_eq!(csv_field("plain"), "plain");
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+    }
+}
@@ -3,6 +3,7 @@
 use crate::cli::{CloudAclCommands, OutputFormat};
 use crate::connection::ConnectionManager;
 use crate::error::Result as CliResult;
+use crate::password::{DEFAULT_GENERATED_LENGTH, check_password_strength, resolve_password};
 
 use super::acl_impl::{self, AclOperationParams};
 
@@ -121,8 +122,18 @@ pub async fn handle_acl_command(
             name,
             role,
             password,
+            password_stdin,
+            generate_password,
+            allow_weak_password,
             async_ops,
         } => {
+            let password = if *generate_password {
+                crate::password::generate_password(DEFAULT_GENERATED_LENGTH)
+            } else {
+                let password = resolve_password(password.as_deref(), *password_stdin)?;
+                check_password_strength(&password, &[name, role], *allow_weak_password)?;
+                password
+            };
             let params = AclOperationParams {
                 conn_mgr,
                 profile_name,
@@ -130,15 +141,26 @@ pub async fn handle_acl_command(
                 output_format,
                 query,
             };
-            acl_impl::create_acl_user(&params, name, role, password).await
+            acl_impl::create_acl_user(&params, name, role, &password, *generate_password).await
         }
         CloudAclCommands::UpdateAclUser {
             id,
             name,
             role,
             password,
+            password_stdin,
+            allow_weak_password,
             async_ops,
         } => {
+            let password = if *password_stdin {
+                Some(resolve_password(None, true)?)
+            } else {
+                password.clone()
+            };
+            if let Some(password) = &password {
+                let inputs: Vec<&str> = name.iter().chain(role).map(String::as_str).collect();
+                check_password_strength(password, &inputs, *allow_weak_password)?;
+            }
             let params = AclOperationParams {
                 conn_mgr,
                 profile_name,
@@ -155,6 +177,20 @@ pub async fn handle_acl_command(
             )
             .await
         }
+        CloudAclCommands::RotatePassword {
+            id,
+            length,
+            async_ops,
+        } => {
+            let params = AclOperationParams {
+                conn_mgr,
+                profile_name,
+                async_ops,
+                output_format,
+                query,
+            };
+            acl_impl::rotate_acl_user_password(&params, *id, *length).await
+        }
         CloudAclCommands::DeleteAclUser {
             id,
             force,
@@ -169,5 +205,8 @@ pub async fn handle_acl_command(
             };
             acl_impl::delete_acl_user(&params, *id, *force).await
         }
+        CloudAclCommands::Graph { format } => {
+            super::acl_graph::acl_graph(conn_mgr, profile_name, *format).await
+        }
     }
 }
@@ -0,0 +1,296 @@
+//! Graph of database access paths for visualization
+//!
+//! Draws ACL users, the roles they hold, the Redis rules those roles apply and the databases
+//! each rule is applied to, as Graphviz DOT or a Mermaid flowchart. A role applies a rule to its
+//! own set of databases, so a rule shared by several roles is drawn once per role; every path
+//! from a user to a database in the graph is then an access path that really exists.
+
+#![allow(dead_code)]
+
+use crate::cli::AclGraphFormat;
+use crate::connection::ConnectionManager;
+use crate::error::Result as CliResult;
+use anyhow::Context;
+use redis_cloud::acl::AclHandler;
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+
+use super::access_report::{list, string};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum NodeKind {
+    User,
+    Role,
+    Rule,
+    Database,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GraphNode {
+    pub kind: NodeKind,
+    pub label: String,
+}
+
+/// Nodes keyed by an identifier that is valid in both DOT and Mermaid, and the edges between them
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AccessGraph {
+    pub nodes: BTreeMap<String, GraphNode>,
+    pub edges: Vec<(String, String)>,
+}
+
+impl AccessGraph {
+    fn node(&mut self, id: String, kind: NodeKind, label: String) -> String {
+        self.nodes
+            .entry(id.clone())
+            .or_insert(GraphNode { kind, label });
+        id
+    }
+
+    fn edge(&mut self, from: &str, to: &str) {
+        let edge = (from.to_string(), to.to_string());
+        if !self.edges.contains(&edge) {
+            self.edges.push(edge);
+        }
+    }
+}
+
+pub async fn acl_graph(
+    conn_mgr: &ConnectionManager,
+    profile_name: Option<&str>,
+    format: AclGraphFormat,
+) -> CliResult<()> {
+    let client = conn_mgr.create_cloud_client(profile_name).await?;
+    let acl = AclHandler::new(client);
+    let (users, roles, rules) = tokio::try_join!(
+        acl.get_all_acl_users(),
+        acl.get_roles(),
+        acl.get_all_redis_rules(),
+    )
+    .context("Failed to fetch ACL users, roles and rules")?;
+
+    let graph = build_graph(
+        &serde_json::to_value(users).context("Failed to serialize ACL users")?,
+        &serde_json::to_value(roles).context("Failed to serialize ACL roles")?,
+        &serde_json::to_value(rules).context("Failed to serialize Redis rules")?,
+    );
+    match format {
+        AclGraphFormat::Dot => print!("{}", to_dot(&graph)),
+        AclGraphFormat::Mermaid => print!("{}", to_mermaid(&graph)),
+    }
+    Ok(())
+}
+
+/// Build the graph from the JSON bodies of `/acl/users`, `/acl/roles` and `/acl/redisRules`
+///
+/// Roles no user holds are included, since they still show what would be granted.
+pub fn build_graph(acl_users: &Value, roles: &Value, rules: &Value) -> AccessGraph {
+    let mut graph = AccessGraph::default();
+    let rule_text: HashMap<String, String> = list(rules, "redisRules")
+        .into_iter()
+        .filter_map(|rule| {
+            let name = string(rule, "name")?;
+            let acl = string(rule, "acl").or_else(|| string(rule, "redisRule"))?;
+            Some((name, acl))
+        })
+        .collect();
+
+    let mut role_ids: HashMap<String, String> = HashMap::new();
+    for (index, role) in list(roles, "roles").into_iter().enumerate() {
+        let Some(name) = string(role, "name") else {
+            continue;
+        };
+        let role_key = role
+            .get("id")
+            .and_then(Value::as_i64)
+            .map_or_else(|| format!("x{}", index), |id| id.to_string());
+        let role_id = graph.node(format!("role_{}", role_key), NodeKind::Role, name.clone());
+        role_ids.insert(name, role_id.clone());
+
+        for (rule_index, rule) in list(role, "redisRules").into_iter().enumerate() {
+            let rule_name = string(rule, "ruleName").unwrap_or_else(|| "?".to_string());
+            let rule_key = rule
+                .get("ruleId")
+                .and_then(Value::as_i64)
+                .map_or_else(|| format!("x{}", rule_index), |id| id.to_string());
+            let label = match rule_text.get(&rule_name) {
+                Some(acl) => format!("{}\n{}", rule_name, acl),
+                None => rule_name,
+            };
+            let rule_id = graph.node(
+                format!("rule_{}_{}", role_key, rule_key),
+                NodeKind::Rule,
+                label,
+            );
+            graph.edge(&role_id, &rule_id);
+
+            for database in list(rule, "databases") {
+                let subscription = database.get("subscriptionId").and_then(Value::as_i64);
+                let Some(database_id) = database.get("databaseId").and_then(Value::as_i64) else {
+                    continue;
+                };
+                let label = format!(
+                    "{}:{}{}",
+                    subscription.map_or("?".to_string(), |id| id.to_string()),
+                    database_id,
+                    string(database, "databaseName")
+                        .map(|name| format!(" ({})", name))
+                        .unwrap_or_default()
+                );
+                let db_id = graph.node(
+                    format!("db_{}_{}", subscription.unwrap_or_default(), database_id),
+                    NodeKind::Database,
+                    label,
+                );
+                graph.edge(&rule_id, &db_id);
+            }
+        }
+    }
+
+    for (index, user) in list(acl_users, "users").into_iter().enumerate() {
+        let user_key = user
+            .get("id")
+            .and_then(Value::as_i64)
+            .map_or_else(|| format!("x{}", index), |id| id.to_string());
+        let user_id = graph.node(
+            format!("user_{}", user_key),
+            NodeKind::User,
+            string(user, "name").unwrap_or_default(),
+        );
+        let Some(role) = string(user, "role") else {
+            continue;
+        };
+        let role_id = match role_ids.get(&role) {
+            Some(id) => id.clone(),
+            // A role the roles list does not know, e.g. a built-in one
+            None => graph.node(
+                format!("role_{}", sanitize(&role)),
+                NodeKind::Role,
+                role.clone(),
+            ),
+        };
+        graph.edge(&user_id, &role_id);
+    }
+
+    graph
+}
+
+/// Graphviz DOT, laid out left to right
+pub fn to_dot(graph: &AccessGraph) -> String {
+    let mut out = String::from("digraph acl {\n  rankdir=LR;\n  node [fontname=\"Helvetica\"];\n");
+    for (id, node) in &graph.nodes {
+        let shape = match node.kind {
+            NodeKind::User => "ellipse",
+            NodeKind::Role => "box",
+            NodeKind::Rule => "note",
+            NodeKind::Database => "cylinder",
+        };
+        out.push_str(&format!(
+            "  {} [label=\"{}\", shape={}];\n",
+            id,
+            node.label
+                .replace('\\', "\\\\")
+                .replace('"', "\\\"")
+                .replace('\n', "\\n"),
+            shape
+        ));
+    }
+    for (from, to) in &graph.edges {
+        out.push_str(&format!("  {} -> {};\n", from, to));
+    }
+    out.push_str("}\n");
+    out
+}
+
+/// Mermaid flowchart, for Markdown renderers such as GitHub's
+pub fn to_mermaid(graph: &AccessGraph) -> String {
+    let mut out = String::from("flowchart LR\n");
+    for (id, node) in &graph.nodes {
+        let label = node.label.replace('"', "#quot;").replace('\n', "<br/>");
+        let shape = match node.kind {
+            NodeKind::User => format!("([\"{}\"])", label),
+            NodeKind::Role => format!("[\"{}\"]", label),
+            NodeKind::Rule => format!("[/\"{}\"/]", label),
+            NodeKind::Database => format!("[(\"{}\")]", label),
+        };
+        out.push_str(&format!("  {}{}\n", id, shape));
+    }
+    for (from, to) in &graph.edges {
+        out.push_str(&format!("  {} --> {}\n", from, to));
+    }
+    out
+}
+
+/// Keep only characters valid in a DOT or Mermaid identifier
+fn sanitize(name: &str) -> String {
+    name.chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn graph() -> AccessGraph {
+        let users = json!({"users": [
+            {"id": 20, "name": "app", "role": "app-role"},
+            {"id": 21, "name": "admin", "role": "Full Access"}
+        ]});
+        let roles = json!({"roles": [
+            {"id": 1, "name": "app-role", "redisRules": [{
+                "ruleId": 5, "ruleName": "Read-Only",
+                "databases": [
+                    {"subscriptionId": 100, "databaseId": 1, "databaseName": "cache"},
+                    {"subscriptionId": 100, "databaseId": 2}
+                ]
+            }]},
+            {"id": 2, "name": "ops-role", "redisRules": [{
+                "ruleId": 5, "ruleName": "Read-Only",
+                "databases": [{"subscriptionId": 100, "databaseId": 3}]
+            }]}
+        ]});
+        let rules = json!({"redisRules": [{"id": 5, "name": "Read-Only", "acl": "+@read ~*"}]});
+        build_graph(&users, &roles, &rules)
+    }
+
+    #[test]
+    fn test_build_graph_keeps_rules_per_role() {
+        let graph = graph();
+        assert_eq!(graph.nodes["rule_1_5"].label, "Read-Only\n+@read ~*");
+        assert!(graph.nodes.contains_key("rule_2_5"));
+        assert!(
+            graph
+                .edges
+                .contains(&("rule_1_5".to_string(), "db_100_1".to_string()))
+        );
+        // The ops role's use of the rule does not reach the app role's databases
+        assert!(
+            !graph
+                .edges
+                .contains(&("rule_2_5".to_string(), "db_100_1".to_string()))
+        );
+        assert_eq!(graph.nodes["db_100_1"].label, "100:1 (cache)");
+        // Roles missing from the roles list still get a node
+        assert!(
+            graph
+                .edges
+                .contains(&("user_21".to_string(), "role_Full_Access".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_render_formats() {
+        let graph = graph();
+        let dot = to_dot(&graph);
+        assert!(dot.starts_with("digraph acl {"));
+        assert!(dot.contains("  rule_1_5 [label=\"Read-Only\\n+@read ~*\", shape=note];"));
+        assert!(dot.contains("  user_20 -> role_1;"));
+
+        let mermaid = to_mermaid(&graph);
+        assert!(mermaid.starts_with("flowchart LR\n"));
+        assert!(mermaid.contains("  db_100_1[(\"100:1 (cache)\")]"));
+        assert!(mermaid.contains("  rule_1_5[/\"Read-Only<br/>+@read ~*\"/]"));
+        assert!(mermaid.contains("  role_1 --> rule_1_5"));
+    }
+}
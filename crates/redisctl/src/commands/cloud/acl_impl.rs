@@ -1,9 +1,13 @@
 #![allow(dead_code)]
 
 use crate::cli::OutputFormat;
-use crate::commands::cloud::async_utils::{AsyncOperationArgs, handle_async_response};
+use crate::commands::cloud::async_utils::{
+    AsyncOperationArgs, get_task_state, handle_async_response, is_failed_state, poll_task,
+};
 use crate::connection::ConnectionManager;
-use crate::error::Result as CliResult;
+use crate::error::{RedisCtlError, Result as CliResult};
+use crate::output::notice;
+use crate::password::generate_password;
 use anyhow::Context;
 use redis_cloud::acl::AclHandler;
 
@@ -122,7 +126,7 @@ pub async fn delete_redis_rule(
     if !force {
         let confirm = confirm_action(&format!("delete Redis rule {}", id))?;
         if !confirm {
-            println!("Operation cancelled");
+            notice!("Operation cancelled");
             return Ok(());
         }
     }
@@ -258,7 +262,7 @@ pub async fn delete_role(params: &AclOperationParams<'_>, id: i32, force: bool)
     if !force {
         let confirm = confirm_action(&format!("delete ACL role {}", id))?;
         if !confirm {
-            println!("Operation cancelled");
+            notice!("Operation cancelled");
             return Ok(());
         }
     }
@@ -322,11 +326,13 @@ pub async fn get_acl_user(
     Ok(())
 }
 
+/// Create an ACL user; a `generated` password is printed once along with the result
 pub async fn create_acl_user(
     params: &AclOperationParams<'_>,
     name: &str,
     role: &str,
     password: &str,
+    generated: bool,
 ) -> CliResult<()> {
     let client = params
         .conn_mgr
@@ -344,6 +350,17 @@ pub async fn create_acl_user(
         .await
         .context("Failed to create ACL user")?;
 
+    if generated {
+        return print_with_password(
+            params,
+            response,
+            serde_json::json!({ "name": name, "role": role, "password": password }),
+            &format!("Password for ACL user '{}': {}", name, password),
+            "ACL user creation",
+            "ACL user creation task {} failed",
+        )
+        .await;
+    }
     handle_async_response(
         params.conn_mgr,
         params.profile_name,
@@ -408,6 +425,110 @@ pub async fn update_acl_user(
     .await
 }
 
+/// Set a generated password on an ACL user and print it once
+pub async fn rotate_acl_user_password(
+    params: &AclOperationParams<'_>,
+    id: i32,
+    length: usize,
+) -> CliResult<()> {
+    let client = params
+        .conn_mgr
+        .create_cloud_client(params.profile_name)
+        .await?;
+
+    let user = client
+        .get_raw(&format!("/acl/users/{}", id))
+        .await
+        .context(format!("Failed to get ACL user {}", id))?;
+    let name = user
+        .get("name")
+        .and_then(|n| n.as_str())
+        .unwrap_or_default()
+        .to_string();
+
+    let password = generate_password(length);
+    let response = client
+        .put_raw(
+            &format!("/acl/users/{}", id),
+            serde_json::json!({ "password": password }),
+        )
+        .await
+        .context("Failed to update ACL user password")?;
+
+    print_with_password(
+        params,
+        response,
+        serde_json::json!({ "id": id, "name": name, "password": password }),
+        &format!(
+            "New password for ACL user '{}' ({}): {}",
+            name, id, password
+        ),
+        "ACL user password rotation",
+        "Password rotation task {} failed; the old password is still valid",
+    )
+    .await
+}
+
+/// Print the outcome of a request that set a generated password, with the password shown once
+///
+/// Table output prints `password_line` after the task; structured output adds the task to
+/// `result`, which carries the password. `failure` is the error for a failed task, with `{}`
+/// for its ID.
+async fn print_with_password(
+    params: &AclOperationParams<'_>,
+    response: serde_json::Value,
+    mut result: serde_json::Value,
+    password_line: &str,
+    description: &str,
+    failure: &str,
+) -> CliResult<()> {
+    match params.output_format {
+        OutputFormat::Auto | OutputFormat::Table if params.query.is_none() => {
+            handle_async_response(
+                params.conn_mgr,
+                params.profile_name,
+                response,
+                params.async_ops,
+                params.output_format,
+                None,
+                description,
+            )
+            .await?;
+            println!("{}", password_line);
+        }
+        _ => {
+            // The password has to be part of the structured output, so the task is polled
+            // quietly instead of being printed
+            let task_id = response
+                .get("taskId")
+                .and_then(|t| t.as_str())
+                .map(str::to_string);
+            result["taskId"] = serde_json::json!(task_id);
+            if params.async_ops.wait
+                && let Some(task_id) = &task_id
+            {
+                let task = poll_task(
+                    params.conn_mgr,
+                    params.profile_name,
+                    task_id,
+                    params.async_ops.wait_timeout,
+                    params.async_ops.wait_interval,
+                )
+                .await?;
+                let state = get_task_state(&task);
+                if is_failed_state(&state) {
+                    return Err(RedisCtlError::api(failure.replace("{}", task_id)));
+                }
+                result["status"] = serde_json::Value::String(state);
+            }
+            let data = handle_output(result, params.output_format, params.query)?;
+            print_formatted_output(data, params.output_format)?;
+        }
+    }
+    notice!("The password is shown only once; store it now.");
+    Ok(())
+}
+
 pub async fn delete_acl_user(
     params: &AclOperationParams<'_>,
     id: i32,
@@ -416,7 +537,7 @@ pub async fn delete_acl_user(
     if !force {
         let confirm = confirm_action(&format!("delete ACL user {}", id))?;
         if !confirm {
-            println!("Operation cancelled");
+            notice!("Operation cancelled");
             return Ok(());
         }
     }
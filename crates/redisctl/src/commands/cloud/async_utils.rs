@@ -3,9 +3,11 @@
 use crate::cli::OutputFormat;
 use crate::connection::ConnectionManager;
 use crate::error::{RedisCtlError, Result as CliResult};
+use crate::output::notice;
 use crate::output::print_output;
+use crate::progress::TaskProgress;
+use crate::units::parse_duration_secs;
 use clap::Args;
-use indicatif::{ProgressBar, ProgressStyle};
 use serde_json::Value;
 use std::time::{Duration, Instant};
 use tokio::time::sleep;
@@ -15,6 +17,7 @@ fn print_json_or_yaml(data: Value, output_format: OutputFormat) -> CliResult<()>
     match output_format {
         OutputFormat::Json => print_output(data, crate::output::OutputFormat::Json, None)?,
         OutputFormat::Yaml => print_output(data, crate::output::OutputFormat::Yaml, None)?,
+        OutputFormat::Id => crate::output::print_ids(&data)?,
         OutputFormat::Auto | OutputFormat::Table => {
             print_output(data, crate::output::OutputFormat::Json, None)?
         }
@@ -29,12 +32,12 @@ pub struct AsyncOperationArgs {
     #[arg(long)]
     pub wait: bool,
 
-    /// Maximum time to wait in seconds
-    #[arg(long, default_value = "300", requires = "wait")]
+    /// Maximum time to wait (seconds, or a duration such as 20m)
+    #[arg(long, default_value = "300", requires = "wait", value_parser = parse_duration_secs)]
     pub wait_timeout: u64,
 
-    /// Polling interval in seconds
-    #[arg(long, default_value = "5", requires = "wait")]
+    /// Polling interval (seconds, or a duration such as 30s)
+    #[arg(long, default_value = "5", requires = "wait", value_parser = parse_duration_secs)]
     pub wait_interval: u64,
 }
 
@@ -79,7 +82,7 @@ pub async fn handle_async_response(
 
         // Print success message for table format
         if matches!(output_format, OutputFormat::Table) {
-            println!("{}", success_message);
+            notice!("{}", success_message);
         }
         return Ok(());
     }
@@ -87,16 +90,21 @@ pub async fn handle_async_response(
     // Normal output without waiting
     match output_format {
         OutputFormat::Auto | OutputFormat::Table => {
-            println!("{}", success_message);
+            notice!("{}", success_message);
             if let Some(task_id) = task_id {
                 println!("Task ID: {}", task_id);
-                println!(
+                notice!(
                     "To wait for completion, run: redisctl cloud task wait {}",
                     task_id
                 );
             }
         }
         OutputFormat::Json | OutputFormat::Yaml => print_json_or_yaml(result, output_format)?,
+        // Without waiting, the task is the only thing that exists yet
+        OutputFormat::Id => match task_id {
+            Some(task_id) if query.is_none() => println!("{}", task_id),
+            _ => print_json_or_yaml(result, output_format)?,
+        },
     }
 
     Ok(())
@@ -111,54 +119,72 @@ pub async fn wait_for_task(
     interval_secs: u64,
     output_format: OutputFormat,
 ) -> CliResult<()> {
+    let task = poll_task(conn_mgr, profile_name, task_id, timeout_secs, interval_secs).await?;
+    let state = get_task_state(&task);
+
+    match output_format {
+        OutputFormat::Auto | OutputFormat::Table => {
+            print_task_details(&task)?;
+        }
+        OutputFormat::Json => {
+            print_output(task, crate::output::OutputFormat::Json, None)?;
+        }
+        OutputFormat::Yaml => {
+            print_output(task, crate::output::OutputFormat::Yaml, None)?;
+        }
+        OutputFormat::Id => match crate::output::task_resource_id(&task) {
+            Some(id) => println!("{}", id),
+            None if is_failed_state(&state) => {}
+            None => {
+                return Err(RedisCtlError::OutputError {
+                    message: format!(
+                        "Task {} completed without reporting a resource ID; use -o json to see the task",
+                        task_id
+                    ),
+                });
+            }
+        },
+    }
+
+    // Check if task failed
+    if is_failed_state(&state) {
+        return Err(RedisCtlError::Validation {
+            message: format!("Task {} failed", task_id),
+        });
+    }
+
+    Ok(())
+}
+
+/// Poll a task until it reaches a terminal state, returning the final task without printing it
+pub async fn poll_task(
+    conn_mgr: &ConnectionManager,
+    profile_name: Option<&str>,
+    task_id: &str,
+    timeout_secs: u64,
+    interval_secs: u64,
+) -> CliResult<Value> {
     let client = conn_mgr.create_cloud_client(profile_name).await?;
     let start = Instant::now();
     let timeout = Duration::from_secs(timeout_secs);
     let interval = Duration::from_secs(interval_secs);
 
-    // Create progress bar
-    let pb = ProgressBar::new_spinner();
-    pb.set_style(
-        ProgressStyle::default_spinner()
-            .template("{spinner:.green} {msg} [{elapsed_precise}]")
-            .unwrap(),
-    );
-    pb.set_message(format!("Waiting for task {}", task_id));
+    let progress = TaskProgress::start(task_id);
 
     loop {
         let task = fetch_task(&client, task_id).await?;
         let state = get_task_state(&task);
 
-        pb.set_message(format!("Task {}: {}", task_id, format_task_state(&state)));
+        progress.update(&state, &format_task_state(&state));
 
         if is_terminal_state(&state) {
-            pb.finish_with_message(format!("Task {}: {}", task_id, format_task_state(&state)));
-
-            match output_format {
-                OutputFormat::Auto | OutputFormat::Table => {
-                    print_task_details(&task)?;
-                }
-                OutputFormat::Json => {
-                    print_output(task, crate::output::OutputFormat::Json, None)?;
-                }
-                OutputFormat::Yaml => {
-                    print_output(task, crate::output::OutputFormat::Yaml, None)?;
-                }
-            }
-
-            // Check if task failed
-            if state == "failed" || state == "error" {
-                return Err(RedisCtlError::InvalidInput {
-                    message: format!("Task {} failed", task_id),
-                });
-            }
-
-            return Ok(());
+            progress.finish(&state, &format_task_state(&state));
+            return Ok(task);
         }
 
         // Check timeout
         if start.elapsed() > timeout {
-            pb.finish_with_message(format!("Task {} timed out", task_id));
+            progress.timeout();
             return Err(RedisCtlError::Timeout {
                 message: format!(
                     "Task {} did not complete within {} seconds",
@@ -177,13 +203,11 @@ async fn fetch_task(client: &redis_cloud::CloudClient, task_id: &str) -> CliResu
     client
         .get_raw(&format!("/tasks/{}", task_id))
         .await
-        .map_err(|e| RedisCtlError::ApiError {
-            message: format!("Failed to fetch task {}: {}", task_id, e),
-        })
+        .map_err(|e| RedisCtlError::api(format!("Failed to fetch task {}: {}", task_id, e)))
 }
 
 /// Get task state from task response
-fn get_task_state(task: &Value) -> String {
+pub fn get_task_state(task: &Value) -> String {
     task.get("status")
         .or_else(|| task.get("state"))
         .and_then(|v| v.as_str())
@@ -195,15 +219,30 @@ fn get_task_state(task: &Value) -> String {
 fn is_terminal_state(state: &str) -> bool {
     matches!(
         state.to_lowercase().as_str(),
-        "completed" | "complete" | "succeeded" | "success" | "failed" | "error" | "cancelled"
+        "completed"
+            | "complete"
+            | "succeeded"
+            | "success"
+            | "failed"
+            | "error"
+            | "cancelled"
+            | "processing-completed"
+            | "processing-error"
     )
 }
 
+/// Whether a task state means the operation failed
+pub fn is_failed_state(state: &str) -> bool {
+    state == "failed" || state == "error" || state == "processing-error"
+}
+
 /// Format task state for display
 fn format_task_state(state: &str) -> String {
     match state.to_lowercase().as_str() {
-        "completed" | "complete" | "succeeded" | "success" => format!("✓ {}", state),
-        "failed" | "error" => format!("✗ {}", state),
+        "completed" | "complete" | "succeeded" | "success" | "processing-completed" => {
+            format!("✓ {}", state)
+        }
+        "failed" | "error" | "processing-error" => format!("✗ {}", state),
         "cancelled" => format!("⊘ {}", state),
         "processing" | "running" | "in_progress" => format!("⟳ {}", state),
         _ => state.to_string(),
@@ -227,6 +266,10 @@ fn print_task_details(task: &Value) -> CliResult<()> {
         println!("Description: {}", description);
     }
 
+    if let Some(resource_id) = crate::output::task_resource_id(task) {
+        println!("Resource ID: {}", resource_id);
+    }
+
     if let Some(progress) = task.get("progress") {
         println!("Progress: {}", progress);
     }
@@ -7,6 +7,7 @@ use crate::commands::cloud::utils::{
 };
 use crate::connection::ConnectionManager;
 use crate::error::Result as CliResult;
+use crate::output::notice;
 
 use anyhow::Context;
 use comfy_table::{Cell, Color, Table};
@@ -235,7 +236,7 @@ pub async fn handle_delete(
     if !force {
         let confirmed = confirm_action(&format!("delete cloud account {}", account_id))?;
         if !confirmed {
-            println!("Operation cancelled");
+            notice!("Operation cancelled");
             return Ok(());
         }
     }
@@ -2,6 +2,7 @@
 
 #![allow(dead_code)]
 
+pub mod peering_verify;
 pub mod psc;
 pub mod tgw;
 pub mod vpc_peering;
@@ -0,0 +1,547 @@
+//! End-to-end checks of a VPC peering
+//!
+//! A peering can show `active` while traffic still does not flow: a route table that does not
+//! send the deployment CIDR to the peering connection, a VPC CIDR that overlaps the deployment
+//! CIDR, or clients that connect to the public endpoint. `vpc-peering verify` reads the peering
+//! and the subscription, checks the status and the CIDR rules, and with `--probe` opens a TCP
+//! connection from this machine to a database endpoint. Every problem comes with the likely fix.
+
+#![allow(dead_code)]
+
+use crate::cli::OutputFormat;
+use crate::commands::cloud::async_utils::{get_task_state, is_failed_state, poll_task};
+use crate::commands::cloud::subscription_cidr::Ipv4Cidr;
+use crate::commands::cloud::utils::{handle_output, print_formatted_output};
+use crate::commands::doctor::{Finding, Status, count, format_findings};
+use crate::connection::ConnectionManager;
+use crate::error::{RedisCtlError, Result as CliResult};
+use anyhow::Context;
+use redis_cloud::CloudClient;
+use serde::Serialize;
+use serde_json::Value;
+use std::net::{IpAddr, SocketAddr};
+use std::time::Duration;
+
+/// How long to wait for the task that lists peerings
+const LIST_TIMEOUT_SECS: u64 = 120;
+const LIST_INTERVAL_SECS: u64 = 2;
+
+/// Peering listings: standard subscriptions, then Active-Active ones
+const PEERING_PATHS: [&str; 2] = [
+    "/subscriptions/{}/peerings",
+    "/subscriptions/{}/regions/peerings",
+];
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VerifyReport {
+    pub subscription_id: i32,
+    pub peering_id: i32,
+    pub status: Option<String>,
+    pub deployment_cidrs: Vec<String>,
+    pub vpc_cidrs: Vec<String>,
+    pub findings: Vec<Finding>,
+    pub warnings: usize,
+    pub failures: usize,
+}
+
+pub struct VerifyOptions<'a> {
+    pub subscription_id: i32,
+    pub peering_id: i32,
+    /// Database endpoint to connect to, as `host:port`
+    pub probe: Option<&'a str>,
+    pub probe_timeout: Duration,
+}
+
+pub async fn verify_peering(
+    conn_mgr: &ConnectionManager,
+    profile_name: Option<&str>,
+    client: &CloudClient,
+    options: &VerifyOptions<'_>,
+    output_format: OutputFormat,
+    query: Option<&str>,
+) -> CliResult<()> {
+    let subscription = client
+        .get_raw(&format!("/subscriptions/{}", options.subscription_id))
+        .await
+        .context(format!(
+            "Failed to get subscription {}",
+            options.subscription_id
+        ))?;
+    let peering = find_peering(conn_mgr, profile_name, client, options).await?;
+
+    let deployment = deployment_cidrs(&subscription);
+    let vpc = vpc_cidrs(&peering);
+    let mut findings = vec![status_finding(&peering)];
+    findings.extend(cidr_findings(&deployment, &vpc));
+    match options.probe {
+        Some(target) => {
+            findings.extend(probe_findings(target, &deployment, options.probe_timeout).await)
+        }
+        None => findings.push(untested_routes_finding(&deployment)),
+    }
+
+    let report = VerifyReport {
+        subscription_id: options.subscription_id,
+        peering_id: options.peering_id,
+        status: peering
+            .get("status")
+            .and_then(Value::as_str)
+            .map(str::to_string),
+        deployment_cidrs: deployment.iter().map(ToString::to_string).collect(),
+        vpc_cidrs: vpc.iter().map(ToString::to_string).collect(),
+        warnings: count(&findings, Status::Warn),
+        failures: count(&findings, Status::Fail),
+        findings,
+    };
+
+    match output_format {
+        OutputFormat::Auto | OutputFormat::Table if query.is_none() => {
+            println!(
+                "VPC peering {} of subscription {}\n",
+                report.peering_id, report.subscription_id
+            );
+            print!("{}", format_findings(&report.findings));
+        }
+        _ => {
+            let json = serde_json::to_value(&report).context("Failed to serialize report")?;
+            let data = handle_output(json, output_format, query)?;
+            print_formatted_output(data, output_format)?;
+        }
+    }
+
+    if report.failures > 0 {
+        return Err(RedisCtlError::Validation {
+            message: format!(
+                "VPC peering {} has {} problem{}",
+                report.peering_id,
+                report.failures,
+                if report.failures == 1 { "" } else { "s" }
+            ),
+        });
+    }
+    Ok(())
+}
+
+/// The peering with the requested ID, from the standard or the Active-Active listing
+async fn find_peering(
+    conn_mgr: &ConnectionManager,
+    profile_name: Option<&str>,
+    client: &CloudClient,
+    options: &VerifyOptions<'_>,
+) -> CliResult<Value> {
+    for path in PEERING_PATHS {
+        let path = path.replace("{}", &options.subscription_id.to_string());
+        let Ok(response) = client.get_raw(&path).await else {
+            continue;
+        };
+        let listing = match response.get("taskId").and_then(Value::as_str) {
+            Some(task_id) => {
+                let task = poll_task(
+                    conn_mgr,
+                    profile_name,
+                    task_id,
+                    LIST_TIMEOUT_SECS,
+                    LIST_INTERVAL_SECS,
+                )
+                .await?;
+                if is_failed_state(&get_task_state(&task)) {
+                    continue;
+                }
+                task.pointer("/response/resource")
+                    .cloned()
+                    .unwrap_or(Value::Null)
+            }
+            None => response,
+        };
+        if let Some(peering) = peering_in(&listing, options.peering_id) {
+            return Ok(peering.clone());
+        }
+    }
+    Err(RedisCtlError::Validation {
+        message: format!(
+            "Subscription {} has no VPC peering {}",
+            options.subscription_id, options.peering_id
+        ),
+    })
+}
+
+/// Find a peering in a listing, which may be a bare list, `{peerings: [...]}`, or nest the
+/// peerings per region (`{regions: [{peerings: [...]}]}`) for Active-Active subscriptions
+fn peering_in(listing: &Value, peering_id: i32) -> Option<&Value> {
+    let matches = |p: &&Value| {
+        ["vpcPeeringId", "peeringId", "id"]
+            .iter()
+            .find_map(|key| p.get(*key)?.as_i64())
+            == Some(i64::from(peering_id))
+    };
+    let direct = listing
+        .as_array()
+        .or_else(|| listing.get("peerings").and_then(Value::as_array));
+    if let Some(found) = direct.into_iter().flatten().find(matches) {
+        return Some(found);
+    }
+    listing
+        .get("regions")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|region| region.get("peerings").and_then(Value::as_array))
+        .flatten()
+        .find(matches)
+}
+
+/// Every deployment CIDR the subscription reports, in any region
+pub fn deployment_cidrs(subscription: &Value) -> Vec<Ipv4Cidr> {
+    let mut found = Vec::new();
+    collect_strings(
+        subscription,
+        &["deploymentCIDR", "deploymentCidr", "deploymentCIDRs"],
+        &mut found,
+    );
+    parse_all(found)
+}
+
+/// The peered VPC's CIDRs
+pub fn vpc_cidrs(peering: &Value) -> Vec<Ipv4Cidr> {
+    let mut found = Vec::new();
+    collect_strings(peering, &["vpcCidr", "vpcCidrs", "cidrs"], &mut found);
+    parse_all(found)
+}
+
+fn collect_strings(value: &Value, keys: &[&str], out: &mut Vec<String>) {
+    match value {
+        Value::Object(map) => {
+            for (key, child) in map {
+                if keys.contains(&key.as_str()) {
+                    match child {
+                        Value::String(s) => out.push(s.clone()),
+                        Value::Array(items) => out.extend(
+                            items
+                                .iter()
+                                .filter_map(|item| item.as_str().map(str::to_string)),
+                        ),
+                        _ => {}
+                    }
+                } else {
+                    collect_strings(child, keys, out);
+                }
+            }
+        }
+        Value::Array(items) => items
+            .iter()
+            .for_each(|item| collect_strings(item, keys, out)),
+        _ => {}
+    }
+}
+
+fn parse_all(cidrs: Vec<String>) -> Vec<Ipv4Cidr> {
+    let mut parsed: Vec<Ipv4Cidr> = Vec::new();
+    for cidr in cidrs {
+        if let Ok(cidr) = Ipv4Cidr::parse_lenient(&cidr)
+            && !parsed.contains(&cidr)
+        {
+            parsed.push(cidr);
+        }
+    }
+    parsed
+}
+
+fn status_finding(peering: &Value) -> Finding {
+    let status = peering
+        .get("status")
+        .and_then(Value::as_str)
+        .unwrap_or("unknown");
+    let aws_id = peering
+        .get("awsPeeringUid")
+        .or_else(|| peering.get("awsPeeringId"))
+        .and_then(Value::as_str);
+    match status.to_ascii_lowercase().as_str() {
+        "active" => Finding::ok("status", "active"),
+        "pending-acceptance" => Finding::fail(
+            "status",
+            "waiting for acceptance in the peer VPC's account",
+            match aws_id {
+                Some(id) => format!(
+                    "Accept it: aws ec2 accept-vpc-peering-connection --vpc-peering-connection-id {}",
+                    id
+                ),
+                None => "Accept the pending peering request in the peer VPC's account".to_string(),
+            },
+        ),
+        "initiating" | "pending" | "provisioning" => Finding::warn(
+            "status",
+            status,
+            "Wait for the peering to become active, then run verify again",
+        ),
+        "failed" | "rejected" | "expired" | "deleted" | "error" => Finding::fail(
+            "status",
+            status,
+            "Delete the peering and create it again; an expired request must be re-created",
+        ),
+        other => Finding::warn(
+            "status",
+            other,
+            "Check the peering in the Redis Cloud console",
+        ),
+    }
+}
+
+/// The VPC's CIDRs must not overlap the deployment CIDRs, or routes cannot tell them apart
+fn cidr_findings(deployment: &[Ipv4Cidr], vpc: &[Ipv4Cidr]) -> Vec<Finding> {
+    let mut findings = Vec::new();
+    if vpc.is_empty() {
+        findings.push(Finding::warn(
+            "cidr",
+            "the peering does not report the VPC's CIDRs",
+            "Check that the peering lists every CIDR of the VPC clients connect from",
+        ));
+        return findings;
+    }
+    if deployment.is_empty() {
+        findings.push(Finding::warn(
+            "cidr",
+            "the subscription does not report its deployment CIDR",
+            "Compare the VPC CIDRs with the deployment CIDR shown in the console",
+        ));
+        return findings;
+    }
+
+    let overlaps: Vec<String> = vpc
+        .iter()
+        .flat_map(|v| {
+            deployment
+                .iter()
+                .filter(move |d| d.overlaps(v))
+                .map(move |d| format!("{} overlaps deployment CIDR {}", v, d))
+        })
+        .collect();
+    if overlaps.is_empty() {
+        findings.push(Finding::ok(
+            "cidr",
+            format!(
+                "VPC {} and deployment {} do not overlap",
+                join(vpc),
+                join(deployment)
+            ),
+        ));
+    } else {
+        findings.push(Finding::fail(
+            "cidr",
+            overlaps.join("; "),
+            "Overlapping ranges cannot be routed; peer a VPC with a different CIDR, or recreate \
+             the subscription with a free deployment CIDR (see `subscription plan-cidr`)",
+        ));
+    }
+    for (i, a) in vpc.iter().enumerate() {
+        for b in &vpc[i + 1..] {
+            if a.overlaps(b) {
+                findings.push(Finding::warn(
+                    "cidr",
+                    format!("VPC CIDRs {} and {} overlap", a, b),
+                    "List each VPC CIDR once; overlapping entries create conflicting routes",
+                ));
+            }
+        }
+    }
+    findings
+}
+
+/// Routing can only be tested with a probe; say what to check when there is none
+fn untested_routes_finding(deployment: &[Ipv4Cidr]) -> Finding {
+    let target = if deployment.is_empty() {
+        "the deployment CIDR".to_string()
+    } else {
+        join(deployment)
+    };
+    Finding::warn(
+        "routes",
+        "not tested; pass --probe with a database endpoint to test them",
+        format!(
+            "The client subnets' route tables need a route for {} with the peering connection \
+             (pcx-...) as target",
+            target
+        ),
+    )
+}
+
+/// Resolve and connect to `target` from this machine
+async fn probe_findings(target: &str, deployment: &[Ipv4Cidr], timeout: Duration) -> Vec<Finding> {
+    let mut findings = Vec::new();
+    let addresses = match tokio::time::timeout(timeout, tokio::net::lookup_host(target)).await {
+        Ok(Ok(addresses)) => addresses.collect::<Vec<SocketAddr>>(),
+        Ok(Err(e)) => {
+            findings.push(Finding::fail(
+                "dns",
+                format!("cannot resolve {}: {}", target, e),
+                "Use the database's private endpoint as host:port, and check that this \
+                 machine uses the VPC's DNS resolver",
+            ));
+            return findings;
+        }
+        Err(_) => {
+            findings.push(Finding::fail(
+                "dns",
+                format!("resolving {} timed out", target),
+                "Check this machine's DNS resolver",
+            ));
+            return findings;
+        }
+    };
+    let Some(address) = addresses.first().copied() else {
+        findings.push(Finding::fail(
+            "dns",
+            format!("{} has no addresses", target),
+            "Use the database's private endpoint as host:port",
+        ));
+        return findings;
+    };
+    findings.push(address_finding(address, deployment));
+
+    let result = tokio::time::timeout(timeout, tokio::net::TcpStream::connect(address)).await;
+    findings.push(match result {
+        Ok(Ok(_)) => Finding::ok("tcp", format!("connected to {}", address)),
+        Ok(Err(e)) if e.kind() == std::io::ErrorKind::ConnectionRefused => Finding::fail(
+            "tcp",
+            format!("{} refused the connection", address),
+            "The network path works but nothing listens there; check the database port",
+        ),
+        Ok(Err(e)) => Finding::fail(
+            "tcp",
+            format!("cannot connect to {}: {}", address, e),
+            "Check the route to the deployment CIDR and that this machine is in the peered VPC",
+        ),
+        Err(_) => Finding::fail(
+            "tcp",
+            format!(
+                "connecting to {} timed out after {}s",
+                address,
+                timeout.as_secs()
+            ),
+            "Packets are dropped: check the route tables for the deployment CIDR, and that \
+             security groups and network ACLs allow the database port",
+        ),
+    });
+    findings
+}
+
+/// Whether the probed address is inside a deployment CIDR, i.e. reached over the peering
+fn address_finding(address: SocketAddr, deployment: &[Ipv4Cidr]) -> Finding {
+    let inside = match address.ip() {
+        IpAddr::V4(ip) => {
+            let host = Ipv4Cidr::parse_lenient(&format!("{}/32", ip)).ok();
+            host.is_some_and(|host| deployment.iter().any(|d| d.contains(&host)))
+        }
+        IpAddr::V6(_) => false,
+    };
+    if inside || deployment.is_empty() {
+        Finding::ok("dns", format!("resolves to {}", address.ip()))
+    } else {
+        Finding::warn(
+            "dns",
+            format!(
+                "resolves to {}, outside the deployment CIDR {}",
+                address.ip(),
+                join(deployment)
+            ),
+            "This is not the peered path; connect to the database's private endpoint",
+        )
+    }
+}
+
+fn join(cidrs: &[Ipv4Cidr]) -> String {
+    cidrs
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn cidrs(list: &[&str]) -> Vec<Ipv4Cidr> {
+        list.iter().map(|c| Ipv4Cidr::parse(c).unwrap()).collect()
+    }
+
+    #[test]
+    fn test_cidrs_from_responses() {
+        let subscription = json!({
+            "cloudDetails": [{"regions": [
+                {"region": "us-east-1", "networking": [{"deploymentCIDR": "10.1.0.0/24"}]},
+                {"region": "eu-west-1", "networking": [{"deploymentCIDR": "10.1.1.0/24"}]}
+            ]}]
+        });
+        assert_eq!(
+            deployment_cidrs(&subscription),
+            cidrs(&["10.1.0.0/24", "10.1.1.0/24"])
+        );
+        let peering =
+            json!({"vpcCidr": "172.31.0.0/16", "vpcCidrs": ["172.31.0.0/16", "10.9.0.0/16"]});
+        assert_eq!(
+            vpc_cidrs(&peering),
+            cidrs(&["172.31.0.0/16", "10.9.0.0/16"])
+        );
+    }
+
+    #[test]
+    fn test_peering_in_listing() {
+        let listing =
+            json!({"peerings": [{"vpcPeeringId": 3}, {"vpcPeeringId": 4, "status": "active"}]});
+        assert_eq!(peering_in(&listing, 4).unwrap()["status"], "active");
+        let aa = json!({"regions": [{"peerings": [{"peeringId": 9}]}]});
+        assert!(peering_in(&aa, 9).is_some());
+        assert!(peering_in(&aa, 4).is_none());
+    }
+
+    #[test]
+    fn test_cidr_findings() {
+        let deployment = cidrs(&["10.0.1.0/24"]);
+        let overlapping = cidr_findings(&deployment, &cidrs(&["10.0.0.0/16"]));
+        assert_eq!(overlapping[0].status, Status::Fail);
+        assert!(
+            overlapping[0]
+                .detail
+                .contains("overlaps deployment CIDR 10.0.1.0/24")
+        );
+
+        let clear = cidr_findings(&deployment, &cidrs(&["172.31.0.0/16"]));
+        assert_eq!(clear.len(), 1);
+        assert_eq!(clear[0].status, Status::Ok);
+    }
+
+    #[test]
+    fn test_status_and_address_findings() {
+        let pending =
+            status_finding(&json!({"status": "pending-acceptance", "awsPeeringUid": "pcx-1"}));
+        assert_eq!(pending.status, Status::Fail);
+        assert!(pending.fix.unwrap().contains("pcx-1"));
+        assert_eq!(
+            status_finding(&json!({"status": "active"})).status,
+            Status::Ok
+        );
+
+        let deployment = cidrs(&["10.0.1.0/24"]);
+        let private: SocketAddr = "10.0.1.17:12000".parse().unwrap();
+        let public: SocketAddr = "54.1.2.3:12000".parse().unwrap();
+        assert_eq!(address_finding(private, &deployment).status, Status::Ok);
+        assert_eq!(address_finding(public, &deployment).status, Status::Warn);
+    }
+
+    #[tokio::test]
+    async fn test_probe_reports_refused_connection() {
+        // Bind and drop a listener to get a local port nothing listens on
+        let port = std::net::TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+        let findings =
+            probe_findings(&format!("127.0.0.1:{}", port), &[], Duration::from_secs(2)).await;
+        assert_eq!(findings[0].status, Status::Ok);
+        assert_eq!(findings[1].status, Status::Fail);
+        assert!(findings[1].detail.contains("refused"));
+    }
+}
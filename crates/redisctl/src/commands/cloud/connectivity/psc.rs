@@ -10,6 +10,7 @@ use crate::commands::cloud::utils::{
 };
 use crate::connection::ConnectionManager;
 use crate::error::Result as CliResult;
+use crate::output::notice;
 use anyhow::Context;
 use redis_cloud::CloudClient;
 use redis_cloud::connectivity::psc::{PscEndpointUpdateRequest, PscHandler};
@@ -254,7 +255,7 @@ async fn delete_service(params: &ConnectivityOperationParams<'_>, yes: bool) ->
             params.subscription_id
         );
         if !confirm_action(&prompt)? {
-            eprintln!("Operation cancelled");
+            notice!("Operation cancelled");
             return Ok(());
         }
     }
@@ -373,7 +374,7 @@ async fn delete_endpoint(
             endpoint_id, params.subscription_id
         );
         if !confirm_action(&prompt)? {
-            eprintln!("Operation cancelled");
+            notice!("Operation cancelled");
             return Ok(());
         }
     }
@@ -480,7 +481,7 @@ async fn delete_service_aa(params: &ConnectivityOperationParams<'_>, yes: bool)
             params.subscription_id
         );
         if !confirm_action(&prompt)? {
-            eprintln!("Operation cancelled");
+            notice!("Operation cancelled");
             return Ok(());
         }
     }
@@ -567,7 +568,7 @@ async fn delete_endpoint_aa(
             endpoint_id, region_id, params.subscription_id
         );
         if !confirm_action(&prompt)? {
-            eprintln!("Operation cancelled");
+            notice!("Operation cancelled");
             return Ok(());
         }
     }
@@ -10,6 +10,7 @@ use crate::commands::cloud::utils::{
 };
 use crate::connection::ConnectionManager;
 use crate::error::Result as CliResult;
+use crate::output::notice;
 use anyhow::Context;
 use redis_cloud::CloudClient;
 use redis_cloud::connectivity::transit_gateway::{TgwAttachmentRequest, TransitGatewayHandler};
@@ -51,6 +52,7 @@ pub async fn handle_tgw_command(
         TgwCommands::AttachmentCreateWithId {
             subscription_id,
             tgw_id,
+            cidrs,
             async_ops,
         } => {
             let params = ConnectivityOperationParams {
@@ -62,7 +64,7 @@ pub async fn handle_tgw_command(
                 output_format,
                 query,
             };
-            create_attachment_with_id(&params, tgw_id).await
+            create_attachment_with_id(&params, tgw_id, cidrs).await
         }
         TgwCommands::AttachmentUpdate {
             subscription_id,
@@ -104,28 +106,34 @@ pub async fn handle_tgw_command(
         TgwCommands::InvitationAccept {
             subscription_id,
             invitation_id,
+            async_ops,
         } => {
-            accept_invitation(
-                &client,
-                *subscription_id,
-                invitation_id,
+            let params = ConnectivityOperationParams {
+                conn_mgr,
+                profile_name,
+                client: &client,
+                subscription_id: *subscription_id,
+                async_ops,
                 output_format,
                 query,
-            )
-            .await
+            };
+            accept_invitation(&params, invitation_id).await
         }
         TgwCommands::InvitationReject {
             subscription_id,
             invitation_id,
+            async_ops,
         } => {
-            reject_invitation(
-                &client,
-                *subscription_id,
-                invitation_id,
+            let params = ConnectivityOperationParams {
+                conn_mgr,
+                profile_name,
+                client: &client,
+                subscription_id: *subscription_id,
+                async_ops,
                 output_format,
                 query,
-            )
-            .await
+            };
+            reject_invitation(&params, invitation_id).await
         }
 
         // Active-Active TGW operations
@@ -192,31 +200,35 @@ pub async fn handle_tgw_command(
             subscription_id,
             region_id,
             invitation_id,
+            async_ops,
         } => {
-            accept_invitation_aa(
-                &client,
-                *subscription_id,
-                *region_id,
-                invitation_id,
+            let params = ConnectivityOperationParams {
+                conn_mgr,
+                profile_name,
+                client: &client,
+                subscription_id: *subscription_id,
+                async_ops,
                 output_format,
                 query,
-            )
-            .await
+            };
+            accept_invitation_aa(&params, *region_id, invitation_id).await
         }
         TgwCommands::AaInvitationReject {
             subscription_id,
             region_id,
             invitation_id,
+            async_ops,
         } => {
-            reject_invitation_aa(
-                &client,
-                *subscription_id,
-                *region_id,
-                invitation_id,
+            let params = ConnectivityOperationParams {
+                conn_mgr,
+                profile_name,
+                client: &client,
+                subscription_id: *subscription_id,
+                async_ops,
                 output_format,
                 query,
-            )
-            .await
+            };
+            reject_invitation_aa(&params, *region_id, invitation_id).await
         }
     }
 }
@@ -271,10 +283,11 @@ async fn create_attachment(params: &ConnectivityOperationParams<'_>, file: &str)
 async fn create_attachment_with_id(
     params: &ConnectivityOperationParams<'_>,
     tgw_id: &str,
+    cidrs: &[String],
 ) -> CliResult<()> {
     let handler = TransitGatewayHandler::new(params.client.clone());
     let response = handler
-        .create_attachment_with_id(params.subscription_id, tgw_id)
+        .create_attachment_with_cidrs(params.subscription_id, tgw_id, cidrs.to_vec())
         .await
         .context("Failed to create TGW attachment")?;
 
@@ -332,7 +345,7 @@ async fn delete_attachment(
             attachment_id, params.subscription_id
         );
         if !confirm_action(&prompt)? {
-            eprintln!("Operation cancelled");
+            notice!("Operation cancelled");
             return Ok(());
         }
     }
@@ -343,7 +356,7 @@ async fn delete_attachment(
         .await
         .context("Failed to delete TGW attachment")?;
 
-    eprintln!("TGW attachment deleted successfully");
+    notice!("TGW attachment deleted successfully");
     Ok(())
 }
 
@@ -366,50 +379,51 @@ async fn list_invitations(
 }
 
 async fn accept_invitation(
-    client: &CloudClient,
-    subscription_id: i32,
+    params: &ConnectivityOperationParams<'_>,
     invitation_id: &str,
-    output_format: OutputFormat,
-    query: Option<&str>,
 ) -> CliResult<()> {
-    let handler = TransitGatewayHandler::new(client.clone());
+    let handler = TransitGatewayHandler::new(params.client.clone());
     let response = handler
-        .accept_resource_share(subscription_id, invitation_id.to_string())
+        .accept_resource_share(params.subscription_id, invitation_id.to_string())
         .await
         .context("Failed to accept TGW invitation")?;
 
-    // Convert response to JSON and check for task ID
     let json_response = serde_json::to_value(&response).context("Failed to serialize response")?;
-    if let Some(task_id) = json_response.get("taskId").and_then(|v| v.as_str()) {
-        eprintln!("TGW invitation acceptance initiated. Task ID: {}", task_id);
-        eprintln!(
-            "Use 'redisctl cloud task wait {}' to monitor progress",
-            task_id
-        );
-    }
 
-    let data = handle_output(json_response, output_format, query)?;
-    print_formatted_output(data, output_format)?;
-    Ok(())
+    handle_async_response(
+        params.conn_mgr,
+        params.profile_name,
+        json_response,
+        params.async_ops,
+        params.output_format,
+        params.query,
+        "TGW invitation acceptance initiated",
+    )
+    .await
 }
 
 async fn reject_invitation(
-    client: &CloudClient,
-    subscription_id: i32,
+    params: &ConnectivityOperationParams<'_>,
     invitation_id: &str,
-    output_format: OutputFormat,
-    query: Option<&str>,
 ) -> CliResult<()> {
-    let handler = TransitGatewayHandler::new(client.clone());
+    let handler = TransitGatewayHandler::new(params.client.clone());
     let response = handler
-        .reject_resource_share(subscription_id, invitation_id.to_string())
+        .reject_resource_share(params.subscription_id, invitation_id.to_string())
         .await
         .context("Failed to reject TGW invitation")?;
 
     let json_response = serde_json::to_value(&response).context("Failed to serialize response")?;
-    let data = handle_output(json_response, output_format, query)?;
-    print_formatted_output(data, output_format)?;
-    Ok(())
+
+    handle_async_response(
+        params.conn_mgr,
+        params.profile_name,
+        json_response,
+        params.async_ops,
+        params.output_format,
+        params.query,
+        "TGW invitation rejection initiated",
+    )
+    .await
 }
 
 // ============================================================================
@@ -510,7 +524,7 @@ async fn delete_attachment_aa(
             attachment_id, region_id, params.subscription_id
         );
         if !confirm_action(&prompt)? {
-            eprintln!("Operation cancelled");
+            notice!("Operation cancelled");
             return Ok(());
         }
     }
@@ -525,7 +539,7 @@ async fn delete_attachment_aa(
         .await
         .context("Failed to delete Active-Active TGW attachment")?;
 
-    eprintln!("Active-Active TGW attachment deleted successfully");
+    notice!("Active-Active TGW attachment deleted successfully");
     Ok(())
 }
 
@@ -548,53 +562,59 @@ async fn list_invitations_aa(
 }
 
 async fn accept_invitation_aa(
-    client: &CloudClient,
-    subscription_id: i32,
+    params: &ConnectivityOperationParams<'_>,
     region_id: i32,
     invitation_id: &str,
-    output_format: OutputFormat,
-    query: Option<&str>,
 ) -> CliResult<()> {
-    let handler = TransitGatewayHandler::new(client.clone());
+    let handler = TransitGatewayHandler::new(params.client.clone());
     let response = handler
-        .accept_resource_share_active_active(subscription_id, region_id, invitation_id.to_string())
+        .accept_resource_share_active_active(
+            params.subscription_id,
+            region_id,
+            invitation_id.to_string(),
+        )
         .await
         .context("Failed to accept Active-Active TGW invitation")?;
 
-    // Convert response to JSON and check for task ID
     let json_response = serde_json::to_value(&response).context("Failed to serialize response")?;
-    if let Some(task_id) = json_response.get("taskId").and_then(|v| v.as_str()) {
-        eprintln!(
-            "Active-Active TGW invitation acceptance initiated. Task ID: {}",
-            task_id
-        );
-        eprintln!(
-            "Use 'redisctl cloud task wait {}' to monitor progress",
-            task_id
-        );
-    }
 
-    let data = handle_output(json_response, output_format, query)?;
-    print_formatted_output(data, output_format)?;
-    Ok(())
+    handle_async_response(
+        params.conn_mgr,
+        params.profile_name,
+        json_response,
+        params.async_ops,
+        params.output_format,
+        params.query,
+        "Active-Active TGW invitation acceptance initiated",
+    )
+    .await
 }
 
 async fn reject_invitation_aa(
-    client: &CloudClient,
-    subscription_id: i32,
+    params: &ConnectivityOperationParams<'_>,
     region_id: i32,
     invitation_id: &str,
-    output_format: OutputFormat,
-    query: Option<&str>,
 ) -> CliResult<()> {
-    let handler = TransitGatewayHandler::new(client.clone());
+    let handler = TransitGatewayHandler::new(params.client.clone());
     let response = handler
-        .reject_resource_share_active_active(subscription_id, region_id, invitation_id.to_string())
+        .reject_resource_share_active_active(
+            params.subscription_id,
+            region_id,
+            invitation_id.to_string(),
+        )
         .await
         .context("Failed to reject Active-Active TGW invitation")?;
 
     let json_response = serde_json::to_value(&response).context("Failed to serialize response")?;
-    let data = handle_output(json_response, output_format, query)?;
-    print_formatted_output(data, output_format)?;
-    Ok(())
+
+    handle_async_response(
+        params.conn_mgr,
+        params.profile_name,
+        json_response,
+        params.async_ops,
+        params.output_format,
+        params.query,
+        "Active-Active TGW invitation rejection initiated",
+    )
+    .await
 }
@@ -4,14 +4,18 @@
 
 use super::ConnectivityOperationParams;
 use crate::cli::{OutputFormat, VpcPeeringCommands};
-use crate::commands::cloud::async_utils::handle_async_response;
+use crate::commands::cloud::async_utils::{
+    get_task_state, handle_async_response, is_failed_state, poll_task,
+};
 use crate::commands::cloud::utils::{
     confirm_action, handle_output, print_formatted_output, read_file_input,
 };
 use crate::connection::ConnectionManager;
-use crate::error::Result as CliResult;
+use crate::error::{RedisCtlError, Result as CliResult};
+use crate::output::notice;
 use anyhow::Context;
 use redis_cloud::CloudClient;
+use serde::Serialize;
 use serde_json::Value;
 
 /// Handle VPC peering commands
@@ -32,6 +36,7 @@ pub async fn handle_vpc_peering_command(
         VpcPeeringCommands::Create {
             subscription,
             data,
+            print_acceptance,
             async_ops,
         } => {
             let params = ConnectivityOperationParams {
@@ -43,7 +48,7 @@ pub async fn handle_vpc_peering_command(
                 output_format,
                 query,
             };
-            handle_create(&params, data).await
+            handle_create(&params, data, *print_acceptance).await
         }
         VpcPeeringCommands::Update {
             subscription,
@@ -79,12 +84,35 @@ pub async fn handle_vpc_peering_command(
             };
             handle_delete(&params, *peering_id, *force).await
         }
+        VpcPeeringCommands::Verify {
+            subscription,
+            peering_id,
+            probe,
+            probe_timeout,
+        } => {
+            let options = super::peering_verify::VerifyOptions {
+                subscription_id: *subscription,
+                peering_id: *peering_id,
+                probe: probe.as_deref(),
+                probe_timeout: std::time::Duration::from_secs(*probe_timeout),
+            };
+            super::peering_verify::verify_peering(
+                conn_mgr,
+                profile_name,
+                &client,
+                &options,
+                output_format,
+                query,
+            )
+            .await
+        }
         VpcPeeringCommands::ListActiveActive { subscription } => {
             handle_list_active_active(&client, *subscription, output_format, query).await
         }
         VpcPeeringCommands::CreateActiveActive {
             subscription,
             data,
+            print_acceptance,
             async_ops,
         } => {
             let params = ConnectivityOperationParams {
@@ -96,7 +124,7 @@ pub async fn handle_vpc_peering_command(
                 output_format,
                 query,
             };
-            handle_create_active_active(&params, data).await
+            handle_create_active_active(&params, data, *print_acceptance).await
         }
         VpcPeeringCommands::UpdateActiveActive {
             subscription,
@@ -159,7 +187,11 @@ async fn handle_get(
 }
 
 /// Create VPC peering
-async fn handle_create(params: &ConnectivityOperationParams<'_>, data: &str) -> CliResult<()> {
+async fn handle_create(
+    params: &ConnectivityOperationParams<'_>,
+    data: &str,
+    print_acceptance: bool,
+) -> CliResult<()> {
     let content = read_file_input(data)?;
     let payload: Value = serde_json::from_str(&content).context("Failed to parse JSON input")?;
 
@@ -167,11 +199,15 @@ async fn handle_create(params: &ConnectivityOperationParams<'_>, data: &str) ->
         .client
         .post_raw(
             &format!("/subscriptions/{}/peerings/vpc", params.subscription_id),
-            payload,
+            payload.clone(),
         )
         .await
         .context("Failed to create VPC peering")?;
 
+    if print_acceptance {
+        return print_acceptance_instructions(params, &payload, result).await;
+    }
+
     handle_async_response(
         params.conn_mgr,
         params.profile_name,
@@ -229,7 +265,7 @@ async fn handle_delete(
             peering_id, params.subscription_id
         ))?;
         if !confirmed {
-            println!("Operation cancelled");
+            notice!("Operation cancelled");
             return Ok(());
         }
     }
@@ -285,6 +321,7 @@ async fn handle_list_active_active(
 async fn handle_create_active_active(
     params: &ConnectivityOperationParams<'_>,
     data: &str,
+    print_acceptance: bool,
 ) -> CliResult<()> {
     let content = read_file_input(data)?;
     let payload: Value = serde_json::from_str(&content).context("Failed to parse JSON input")?;
@@ -296,11 +333,15 @@ async fn handle_create_active_active(
                 "/subscriptions/{}/peerings/vpc/active-active",
                 params.subscription_id
             ),
-            payload,
+            payload.clone(),
         )
         .await
         .context("Failed to create Active-Active VPC peering")?;
 
+    if print_acceptance {
+        return print_acceptance_instructions(params, &payload, result).await;
+    }
+
     handle_async_response(
         params.conn_mgr,
         params.profile_name,
@@ -358,7 +399,7 @@ async fn handle_delete_active_active(
             peering_id, params.subscription_id
         ))?;
         if !confirmed {
-            println!("Operation cancelled");
+            notice!("Operation cancelled");
             return Ok(());
         }
     }
@@ -384,6 +425,172 @@ async fn handle_delete_active_active(
     .await
 }
 
+/// What the owner of the AWS account needs to accept a peering request
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PeeringAcceptance {
+    /// Redis Cloud peering ID
+    resource_id: Option<Value>,
+    /// AWS peering connection ID (`pcx-...`), once Redis Cloud has requested the peering
+    aws_peering_id: Option<String>,
+    aws_account_id: Option<String>,
+    region: Option<String>,
+    vpc_id: Option<String>,
+    accept_command: String,
+    console_url: String,
+}
+
+/// Wait for a peering create task, then print how to accept the peering on the AWS side
+async fn print_acceptance_instructions(
+    params: &ConnectivityOperationParams<'_>,
+    payload: &Value,
+    response: Value,
+) -> CliResult<()> {
+    let task_id = response
+        .get("taskId")
+        .and_then(Value::as_str)
+        .ok_or_else(|| {
+            RedisCtlError::api(
+                "The create response did not include a task ID to wait for".to_string(),
+            )
+        })?;
+    let task = poll_task(
+        params.conn_mgr,
+        params.profile_name,
+        task_id,
+        params.async_ops.wait_timeout,
+        params.async_ops.wait_interval,
+    )
+    .await?;
+    if is_failed_state(&get_task_state(&task)) {
+        let reason = task
+            .pointer("/response/error/description")
+            .and_then(Value::as_str)
+            .unwrap_or("no reason given");
+        return Err(RedisCtlError::api(format!(
+            "Task {} failed: {}",
+            task_id, reason
+        )));
+    }
+
+    let acceptance = peering_acceptance(payload, &task);
+    match params.output_format {
+        OutputFormat::Auto | OutputFormat::Table if params.query.is_none() => {
+            print!("{}", format_acceptance(&acceptance));
+        }
+        _ => {
+            let json = serde_json::to_value(&acceptance).context("Failed to serialize output")?;
+            let data = handle_output(json, params.output_format, params.query)?;
+            print_formatted_output(data, params.output_format)?;
+        }
+    }
+    Ok(())
+}
+
+/// Acceptance details from the create request and the finished task
+///
+/// The account, region and VPC are the ones the peering was requested for; the AWS peering
+/// connection ID only exists in the task result, either as the resource itself or as an entry
+/// of its `peerings` list.
+fn peering_acceptance(payload: &Value, task: &Value) -> PeeringAcceptance {
+    let resource_id = task.pointer("/response/resourceId").cloned();
+    let resource = task.pointer("/response/resource");
+    let peering = match resource
+        .and_then(|r| r.get("peerings"))
+        .and_then(Value::as_array)
+    {
+        Some(peerings) => peerings
+            .iter()
+            .find(|p| {
+                resource_id.is_some()
+                    && p.get("vpcPeeringId").or_else(|| p.get("peeringId")) == resource_id.as_ref()
+            })
+            .or_else(|| peerings.first()),
+        None => resource,
+    };
+    let from_peering = |keys: &[&str]| {
+        keys.iter()
+            .find_map(|key| peering?.get(*key)?.as_str())
+            .map(str::to_string)
+    };
+    let from_payload = |keys: &[&str]| {
+        keys.iter()
+            .find_map(|key| payload.get(*key)?.as_str())
+            .map(str::to_string)
+    };
+
+    let aws_peering_id = from_peering(&["awsPeeringUid", "awsPeeringId", "vpcPeeringConnectionId"]);
+    let aws_account_id =
+        from_payload(&["awsAccountId"]).or_else(|| from_peering(&["awsAccountId"]));
+    // Active-Active peerings are accepted in the destination region
+    let region = from_payload(&["region", "destinationRegion"])
+        .or_else(|| from_peering(&["region", "regionName"]));
+    let vpc_id = from_payload(&["vpcId"]).or_else(|| from_peering(&["vpcUid", "vpcId"]));
+
+    let region_arg = region
+        .as_deref()
+        .map(|region| format!(" --region {}", region))
+        .unwrap_or_default();
+    let accept_command = match &aws_peering_id {
+        Some(id) => format!(
+            "aws ec2 accept-vpc-peering-connection --vpc-peering-connection-id {}{}",
+            id, region_arg
+        ),
+        None => format!(
+            "aws ec2 describe-vpc-peering-connections --filters Name=status-code,Values=pending-acceptance{}{}",
+            vpc_id
+                .as_deref()
+                .map(|vpc| format!(" Name=accepter-vpc-info.vpc-id,Values={}", vpc))
+                .unwrap_or_default(),
+            region_arg
+        ),
+    };
+    let console_url = match &region {
+        Some(region) => format!(
+            "https://console.aws.amazon.com/vpc/home?region={}#PeeringConnections:",
+            region
+        ),
+        None => "https://console.aws.amazon.com/vpc/home#PeeringConnections:".to_string(),
+    };
+
+    PeeringAcceptance {
+        resource_id,
+        aws_peering_id,
+        aws_account_id,
+        region,
+        vpc_id,
+        accept_command,
+        console_url,
+    }
+}
+
+fn format_acceptance(acceptance: &PeeringAcceptance) -> String {
+    let mut out = match &acceptance.resource_id {
+        Some(id) => format!("VPC peering created (peering ID {})\n\n", id),
+        None => "VPC peering created\n\n".to_string(),
+    };
+    let target = match (&acceptance.aws_account_id, &acceptance.region) {
+        (Some(account), Some(region)) => format!(" in AWS account {} ({})", account, region),
+        (Some(account), None) => format!(" in AWS account {}", account),
+        (None, Some(region)) => format!(" in {}", region),
+        (None, None) => String::new(),
+    };
+    if acceptance.aws_peering_id.is_some() {
+        out.push_str(&format!("Accept the peering request{}:\n", target));
+    } else {
+        out.push_str(&format!(
+            "The task did not report the AWS peering connection ID. Find the pending request{} with:\n",
+            target
+        ));
+    }
+    out.push_str(&format!("  {}\n\n", acceptance.accept_command));
+    out.push_str(&format!(
+        "Or in the AWS console: {}\n",
+        acceptance.console_url
+    ));
+    out
+}
+
 /// Print VPC peering details in table format
 fn print_vpc_peering_table(data: &Value) -> CliResult<()> {
     use super::super::utils::DetailRow;
@@ -451,7 +658,7 @@ fn print_vpc_peering_table(data: &Value) -> CliResult<()> {
     }
 
     if rows.is_empty() {
-        println!("No VPC peering information available");
+        notice!("No VPC peering information available");
         return Ok(());
     }
 
@@ -471,12 +678,12 @@ fn print_vpc_peering_list_table(data: &Value) -> CliResult<()> {
     } else if let Some(peerings) = data.get("peerings").and_then(|p| p.as_array()) {
         peerings.clone()
     } else {
-        println!("No VPC peerings found");
+        notice!("No VPC peerings found");
         return Ok(());
     };
 
     if peerings.is_empty() {
-        println!("No VPC peerings found");
+        notice!("No VPC peerings found");
         return Ok(());
     }
 
@@ -543,3 +750,60 @@ fn print_vpc_peering_list_table(data: &Value) -> CliResult<()> {
     println!("{}", table);
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_peering_acceptance() {
+        let payload = json!({
+            "region": "us-east-1",
+            "awsAccountId": "123456789012",
+            "vpcId": "vpc-0abc",
+            "vpcCidr": "10.0.0.0/16"
+        });
+        let task = json!({
+            "taskId": "t-1",
+            "status": "processing-completed",
+            "response": {
+                "resourceId": 42,
+                "resource": {"peerings": [
+                    {"vpcPeeringId": 41, "awsPeeringUid": "pcx-other"},
+                    {"vpcPeeringId": 42, "awsPeeringUid": "pcx-0123", "status": "pending-acceptance"}
+                ]}
+            }
+        });
+        let acceptance = peering_acceptance(&payload, &task);
+        assert_eq!(acceptance.aws_peering_id.as_deref(), Some("pcx-0123"));
+        assert_eq!(
+            acceptance.accept_command,
+            "aws ec2 accept-vpc-peering-connection --vpc-peering-connection-id pcx-0123 --region us-east-1"
+        );
+        assert_eq!(
+            acceptance.console_url,
+            "https://console.aws.amazon.com/vpc/home?region=us-east-1#PeeringConnections:"
+        );
+        let text = format_acceptance(&acceptance);
+        assert!(text.starts_with("VPC peering created (peering ID 42)\n"));
+        assert!(
+            text.contains("Accept the peering request in AWS account 123456789012 (us-east-1):\n")
+        );
+    }
+
+    #[test]
+    fn test_peering_acceptance_without_peering_id() {
+        let payload = json!({"destinationRegion": "eu-west-1", "vpcId": "vpc-0abc"});
+        let task = json!({"response": {"resourceId": 7, "resource": {"status": "initiating"}}});
+        let acceptance = peering_acceptance(&payload, &task);
+        assert_eq!(acceptance.aws_peering_id, None);
+        assert_eq!(
+            acceptance.accept_command,
+            "aws ec2 describe-vpc-peering-connections --filters Name=status-code,Values=pending-acceptance Name=accepter-vpc-info.vpc-id,Values=vpc-0abc --region eu-west-1"
+        );
+        assert!(
+            format_acceptance(&acceptance).contains("did not report the AWS peering connection ID")
+        );
+    }
+}
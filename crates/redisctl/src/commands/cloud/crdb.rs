@@ -0,0 +1,56 @@
+//! Active-Active (CRDB) database command router for Cloud
+
+#![allow(dead_code)]
+
+use crate::cli::{CloudCrdbCommands, OutputFormat};
+use crate::connection::ConnectionManager;
+use crate::error::Result as CliResult;
+
+use super::database_impl;
+
+pub async fn handle_crdb_command(
+    conn_mgr: &ConnectionManager,
+    profile_name: Option<&str>,
+    command: &CloudCrdbCommands,
+    output_format: OutputFormat,
+    query: Option<&str>,
+) -> CliResult<()> {
+    match command {
+        CloudCrdbCommands::Flush {
+            id,
+            force,
+            async_ops,
+        } => {
+            database_impl::flush_crdb(
+                conn_mgr,
+                profile_name,
+                id,
+                *force,
+                async_ops,
+                output_format,
+                query,
+            )
+            .await
+        }
+        CloudCrdbCommands::SetRegionThroughput {
+            id,
+            region,
+            read_ops,
+            write_ops,
+            async_ops,
+        } => {
+            database_impl::set_region_throughput(
+                conn_mgr,
+                profile_name,
+                id,
+                region,
+                *read_ops,
+                *write_ops,
+                async_ops,
+                output_format,
+                query,
+            )
+            .await
+        }
+    }
+}
@@ -4,9 +4,14 @@
 
 use super::utils::DetailRow;
 use super::utils::*;
-use crate::cli::{CloudDatabaseCommands, OutputFormat};
+use crate::cli::{
+    CloudDatabaseAclCommands, CloudDatabaseBackupConfigCommands, CloudDatabaseCommands,
+    CloudDatabaseModuleCommands, CloudDatabaseReplicaOfCommands, OutputFormat,
+};
+use crate::commands::examples::{ExampleKind, print_example};
 use crate::connection::ConnectionManager;
 use crate::error::{RedisCtlError, Result as CliResult};
+use crate::output::notice;
 use crate::output::print_output;
 use anyhow::Context;
 use serde_json::Value;
@@ -42,8 +47,19 @@ pub async fn handle_database_command(
     query: Option<&str>,
 ) -> CliResult<()> {
     match command {
-        CloudDatabaseCommands::List { subscription } => {
-            list_databases(conn_mgr, profile_name, *subscription, output_format, query).await
+        CloudDatabaseCommands::List {
+            subscription,
+            stream,
+        } => {
+            list_databases(
+                conn_mgr,
+                profile_name,
+                *subscription,
+                *stream,
+                output_format,
+                query,
+            )
+            .await
         }
         CloudDatabaseCommands::Get { id } => {
             get_database(conn_mgr, profile_name, id, output_format, query).await
@@ -51,13 +67,35 @@ pub async fn handle_database_command(
         CloudDatabaseCommands::Create {
             subscription,
             data,
+            memory,
+            backup_every,
+            backup_path,
+            modules,
+            query_performance_factor,
+            example,
+            if_absent,
             async_ops,
         } => {
+            let (Some(subscription), Some(data), false) = (subscription, data.as_deref(), *example)
+            else {
+                return print_example(ExampleKind::CloudDatabase, output_format);
+            };
+            let backup = super::database_backup::backup_from_flags(
+                backup_every.as_deref(),
+                backup_path.as_ref(),
+            )?;
             super::database_impl::create_database(
                 conn_mgr,
                 profile_name,
                 *subscription,
                 data,
+                *memory,
+                backup.as_ref(),
+                &super::database_module::ModuleChanges {
+                    modules: modules.clone(),
+                    query_performance_factor: query_performance_factor.clone(),
+                },
+                if_absent.as_deref(),
                 async_ops,
                 output_format,
                 query,
@@ -67,13 +105,71 @@ pub async fn handle_database_command(
         CloudDatabaseCommands::Update {
             id,
             data,
+            patch,
+            memory,
+            backup_every,
+            backup_path,
+            query_performance_factor,
             async_ops,
         } => {
+            let backup = super::database_backup::backup_from_flags(
+                backup_every.as_deref(),
+                backup_path.as_ref(),
+            )?;
             super::database_impl::update_database(
                 conn_mgr,
                 profile_name,
                 id,
-                data,
+                data.as_deref(),
+                patch.as_deref(),
+                *memory,
+                backup.as_ref(),
+                query_performance_factor.as_deref(),
+                async_ops,
+                output_format,
+                query,
+            )
+            .await
+        }
+        CloudDatabaseCommands::Scale {
+            id,
+            ops,
+            memory,
+            replication,
+            no_wait,
+            wait_timeout,
+            wait_interval,
+        } => {
+            let async_ops = super::async_utils::AsyncOperationArgs {
+                wait: !no_wait,
+                wait_timeout: *wait_timeout,
+                wait_interval: *wait_interval,
+            };
+            super::database_scale::scale_database(
+                conn_mgr,
+                profile_name,
+                id,
+                super::database_scale::ScaleChange {
+                    ops: *ops,
+                    memory: *memory,
+                    replication: *replication,
+                },
+                &async_ops,
+                output_format,
+                query,
+            )
+            .await
+        }
+        CloudDatabaseCommands::Rename {
+            id,
+            name,
+            async_ops,
+        } => {
+            super::database_impl::rename_database(
+                conn_mgr,
+                profile_name,
+                id,
+                name,
                 async_ops,
                 output_format,
                 query,
@@ -143,9 +239,33 @@ pub async fn handle_database_command(
             )
             .await
         }
-        CloudDatabaseCommands::GetCertificate { id } => {
-            super::database_impl::get_certificate(conn_mgr, profile_name, id, output_format, query)
-                .await
+        CloudDatabaseCommands::GetCertificate { id, out, verify } => {
+            super::database_impl::get_certificate(
+                conn_mgr,
+                profile_name,
+                id,
+                out.as_deref(),
+                *verify,
+                output_format,
+                query,
+            )
+            .await
+        }
+        CloudDatabaseCommands::ConnectionString {
+            id,
+            private,
+            with_password,
+        } => {
+            super::database_connection::connection_string(
+                conn_mgr,
+                profile_name,
+                id,
+                *private,
+                *with_password,
+                output_format,
+                query,
+            )
+            .await
         }
         CloudDatabaseCommands::SlowLog { id, limit, offset } => {
             super::database_impl::get_slow_log(
@@ -189,12 +309,17 @@ pub async fn handle_database_command(
             super::database_impl::delete_tag(conn_mgr, profile_name, id, key, output_format, query)
                 .await
         }
-        CloudDatabaseCommands::FlushCrdb { id, force } => {
+        CloudDatabaseCommands::FlushCrdb {
+            id,
+            force,
+            async_ops,
+        } => {
             super::database_impl::flush_crdb(
                 conn_mgr,
                 profile_name,
                 id,
                 *force,
+                async_ops,
                 output_format,
                 query,
             )
@@ -221,6 +346,183 @@ pub async fn handle_database_command(
             )
             .await
         }
+        CloudDatabaseCommands::Acl(acl_command) => match acl_command {
+            CloudDatabaseAclCommands::Attach {
+                id,
+                role,
+                rule,
+                regions,
+                async_ops,
+            } => {
+                super::database_impl::attach_acl_role(
+                    conn_mgr,
+                    profile_name,
+                    id,
+                    role,
+                    rule,
+                    regions,
+                    async_ops,
+                    output_format,
+                    query,
+                )
+                .await
+            }
+            CloudDatabaseAclCommands::Detach {
+                id,
+                role,
+                async_ops,
+            } => {
+                super::database_impl::detach_acl_role(
+                    conn_mgr,
+                    profile_name,
+                    id,
+                    role,
+                    async_ops,
+                    output_format,
+                    query,
+                )
+                .await
+            }
+        },
+        CloudDatabaseCommands::SetExpiry {
+            id,
+            expires_in,
+            at,
+            clear,
+        } => {
+            super::database_expiry::set_expiry(
+                conn_mgr,
+                profile_name,
+                id,
+                *expires_in,
+                at.as_deref(),
+                *clear,
+                output_format,
+                query,
+            )
+            .await
+        }
+        CloudDatabaseCommands::Reap {
+            subscription,
+            dry_run,
+            force,
+            async_ops,
+        } => {
+            super::database_expiry::reap(
+                conn_mgr,
+                profile_name,
+                *subscription,
+                *dry_run,
+                *force,
+                async_ops,
+                output_format,
+                query,
+            )
+            .await
+        }
+        CloudDatabaseCommands::ReplicaOf(replica_command) => match replica_command {
+            CloudDatabaseReplicaOfCommands::Set {
+                id,
+                sources,
+                encryption,
+                server_cert,
+                async_ops,
+            } => {
+                let options = super::database_replica::SourceOptions {
+                    encryption: *encryption,
+                    server_cert: server_cert.as_deref(),
+                };
+                super::database_replica::set_replica_of(
+                    conn_mgr,
+                    profile_name,
+                    id,
+                    sources,
+                    &options,
+                    async_ops,
+                    output_format,
+                    query,
+                )
+                .await
+            }
+            CloudDatabaseReplicaOfCommands::Stop { id, async_ops } => {
+                super::database_replica::stop_replica_of(
+                    conn_mgr,
+                    profile_name,
+                    id,
+                    async_ops,
+                    output_format,
+                    query,
+                )
+                .await
+            }
+            CloudDatabaseReplicaOfCommands::Status { id } => {
+                super::database_replica::replica_of_status(
+                    conn_mgr,
+                    profile_name,
+                    id,
+                    output_format,
+                    query,
+                )
+                .await
+            }
+        },
+        CloudDatabaseCommands::BackupConfig(backup_command) => match backup_command {
+            CloudDatabaseBackupConfigCommands::Set {
+                id,
+                every,
+                s3_path,
+                gcs_path,
+                abs_path,
+                ftp_path,
+                at,
+                disable,
+                async_ops,
+            } => {
+                let changes = super::database_backup::BackupChanges {
+                    interval: every.clone(),
+                    destination: super::database_backup::destination_from_flags(
+                        s3_path.as_ref(),
+                        gcs_path.as_ref(),
+                        abs_path.as_ref(),
+                        ftp_path.as_ref(),
+                    ),
+                    time_utc: at.clone(),
+                    disable: *disable,
+                };
+                super::database_backup::set_backup_config(
+                    conn_mgr,
+                    profile_name,
+                    id,
+                    &changes,
+                    async_ops,
+                    output_format,
+                    query,
+                )
+                .await
+            }
+            CloudDatabaseBackupConfigCommands::Get { id } => {
+                super::database_backup::get_backup_config(
+                    conn_mgr,
+                    profile_name,
+                    id,
+                    output_format,
+                    query,
+                )
+                .await
+            }
+        },
+        CloudDatabaseCommands::Module(module_command) => match module_command {
+            CloudDatabaseModuleCommands::List { id } => {
+                super::database_module::list_modules(
+                    conn_mgr,
+                    profile_name,
+                    id,
+                    output_format,
+                    query,
+                )
+                .await
+            }
+        },
     }
 }
 
@@ -229,9 +531,13 @@ async fn list_databases(
     conn_mgr: &ConnectionManager,
     profile_name: Option<&str>,
     subscription_id: Option<u32>,
+    stream: bool,
     output_format: OutputFormat,
     query: Option<&str>,
 ) -> CliResult<()> {
+    if stream {
+        crate::output::check_stream_output(output_format, query)?;
+    }
     let client = conn_mgr.create_cloud_client(profile_name).await?;
 
     // Fetch both flexible and fixed subscriptions
@@ -245,89 +551,78 @@ async fn list_databases(
         .await
         .context("Failed to fetch fixed subscriptions")?;
 
-    let mut all_databases = Vec::new();
-
-    // Process flexible subscriptions
-    if let Some(Value::Array(flex_subs)) = flex_response.get("subscriptions") {
-        for sub in flex_subs {
-            let sub_id = match sub.get("id").and_then(|i| i.as_u64()) {
-                Some(id) => id as u32,
-                None => continue,
-            };
-
-            // Skip if filtering by subscription and this isn't it
-            if let Some(filter_id) = subscription_id
-                && sub_id != filter_id
-            {
-                continue;
-            }
-
-            let sub_name = extract_field(sub, "name", "Unknown");
-
-            // Fetch databases for this flexible subscription
-            let db_response = client
-                .get_raw(&format!("/subscriptions/{}/databases", sub_id))
-                .await
-                .ok();
-
-            if let Some(Value::Array(databases)) = db_response {
-                for db in databases {
-                    let mut db_with_sub = db.clone();
-                    if let Value::Object(ref mut map) = db_with_sub {
-                        map.insert("subscriptionId".to_string(), Value::Number(sub_id.into()));
-                        map.insert(
-                            "subscriptionName".to_string(),
-                            Value::String(sub_name.clone()),
-                        );
-                    }
-                    all_databases.push(db_with_sub);
+    // (subscription, name, fixed) in listing order
+    let mut subscriptions = Vec::new();
+    for (response, fixed) in [(&flex_response, false), (&fixed_response, true)] {
+        if let Some(Value::Array(subs)) = response.get("subscriptions") {
+            for sub in subs {
+                let sub_id = match sub.get("id").and_then(|i| i.as_u64()) {
+                    Some(id) => id as u32,
+                    None => continue,
+                };
+
+                // Skip if filtering by subscription and this isn't it
+                if let Some(filter_id) = subscription_id
+                    && sub_id != filter_id
+                {
+                    continue;
                 }
+
+                subscriptions.push((sub_id, extract_field(sub, "name", "Unknown"), fixed));
             }
         }
     }
 
-    // Process fixed subscriptions
-    if let Some(Value::Array(fixed_subs)) = fixed_response.get("subscriptions") {
-        for sub in fixed_subs {
-            let sub_id = match sub.get("id").and_then(|i| i.as_u64()) {
-                Some(id) => id as u32,
-                None => continue,
-            };
-
-            // Skip if filtering by subscription and this isn't it
-            if let Some(filter_id) = subscription_id
-                && sub_id != filter_id
-            {
-                continue;
-            }
-
-            let sub_name = extract_field(sub, "name", "Unknown");
+    let mut all_databases = Vec::new();
+    let mut rows = crate::output::RowStream::new::<DatabaseRow>();
 
-            // Fetch databases for this fixed subscription
-            let db_response = client
+    for (sub_id, sub_name, fixed) in subscriptions {
+        let databases = if fixed {
+            // Fixed subscriptions have a different response structure
+            client
                 .get_raw(&format!("/fixed/subscriptions/{}/databases", sub_id))
                 .await
-                .ok();
+                .ok()
+                .and_then(|r| r.get("subscription").cloned())
+                .and_then(|sub_data| sub_data.get("databases").cloned())
+        } else {
+            client
+                .get_raw(&format!("/subscriptions/{}/databases", sub_id))
+                .await
+                .ok()
+        };
 
-            // Fixed subscriptions have a different response structure
-            if let Some(sub_data) = db_response.and_then(|r| r.get("subscription").cloned())
-                && let Some(Value::Array(databases)) = sub_data.get("databases")
-            {
-                for db in databases {
-                    let mut db_with_sub = db.clone();
-                    if let Value::Object(ref mut map) = db_with_sub {
-                        map.insert("subscriptionId".to_string(), Value::Number(sub_id.into()));
-                        map.insert(
-                            "subscriptionName".to_string(),
-                            Value::String(sub_name.clone()),
-                        );
-                    }
-                    all_databases.push(db_with_sub);
+        let Some(Value::Array(databases)) = databases else {
+            continue;
+        };
+        let page: Vec<Value> = databases
+            .into_iter()
+            .map(|mut db| {
+                if let Value::Object(ref mut map) = db {
+                    map.insert("subscriptionId".to_string(), Value::Number(sub_id.into()));
+                    map.insert(
+                        "subscriptionName".to_string(),
+                        Value::String(sub_name.clone()),
+                    );
                 }
-            }
+                db
+            })
+            .collect();
+
+        if stream {
+            print_stream_page(&mut rows, &page, output_format)?;
+        } else {
+            all_databases.extend(page);
         }
     }
 
+    if stream {
+        if rows.rows() == 0 && matches!(output_format, OutputFormat::Auto | OutputFormat::Table) {
+            notice!("No databases found");
+        }
+        return Ok(());
+    }
+
     let data = if let Some(q) = query {
         apply_jmespath(&Value::Array(all_databases), q)?
     } else {
@@ -352,55 +647,56 @@ async fn list_databases(
                 }
             })?;
         }
+        OutputFormat::Id => {
+            crate::output::print_ids(&data).map_err(|e| RedisCtlError::OutputError {
+                message: e.to_string(),
+            })?;
+        }
     }
 
     Ok(())
 }
 
+/// Print one subscription's databases as soon as they are fetched
+fn print_stream_page(
+    rows: &mut crate::output::RowStream,
+    page: &[Value],
+    output_format: OutputFormat,
+) -> CliResult<()> {
+    let result = match output_format {
+        OutputFormat::Json => crate::output::print_ndjson(page),
+        OutputFormat::Id => {
+            for id in crate::output::resource_ids(&Value::Array(page.to_vec())) {
+                println!("{}", id);
+            }
+            Ok(())
+        }
+        _ => {
+            rows.push(&page.iter().map(database_row).collect::<Vec<_>>());
+            Ok(())
+        }
+    };
+    result.map_err(|e| RedisCtlError::OutputError {
+        message: e.to_string(),
+    })
+}
+
 /// Print databases in clean table format
 fn print_databases_table(data: &Value) -> CliResult<()> {
     let databases = match data {
         Value::Array(arr) => arr.clone(),
         _ => {
-            println!("No databases found");
+            notice!("No databases found");
             return Ok(());
         }
     };
 
     if databases.is_empty() {
-        println!("No databases found");
+        notice!("No databases found");
         return Ok(());
     }
 
-    let mut rows = Vec::new();
-    for db in databases {
-        let sub_id = extract_field(&db, "subscriptionId", "");
-        let db_id = extract_field(&db, "databaseId", &extract_field(&db, "uid", "—"));
-        let full_id = if !sub_id.is_empty() && db_id != "—" {
-            format!("{}:{}", sub_id, db_id)
-        } else {
-            db_id.clone()
-        };
-
-        rows.push(DatabaseRow {
-            id: full_id,
-            name: truncate_string(&extract_field(&db, "name", "—"), 20),
-            status: format_status(extract_field(&db, "status", "unknown")),
-            subscription: truncate_string(
-                &extract_field(
-                    &db,
-                    "subscriptionName",
-                    &extract_field(&db, "subscriptionId", "—"),
-                ),
-                15,
-            ),
-            memory: format_database_memory(&db),
-            region: extract_database_region(&db),
-            endpoint: extract_database_endpoint(&db),
-            created: format_date(extract_field(&db, "created", "")),
-        });
-    }
-
+    let rows: Vec<DatabaseRow> = databases.iter().map(database_row).collect();
     let mut table = Table::new(&rows);
     table.with(Style::blank());
 
@@ -408,11 +704,39 @@ fn print_databases_table(data: &Value) -> CliResult<()> {
     Ok(())
 }
 
+fn database_row(db: &Value) -> DatabaseRow {
+    let sub_id = extract_field(db, "subscriptionId", "");
+    let db_id = extract_field(db, "databaseId", &extract_field(db, "uid", "—"));
+    let full_id = if !sub_id.is_empty() && db_id != "—" {
+        format!("{}:{}", sub_id, db_id)
+    } else {
+        db_id.clone()
+    };
+
+    DatabaseRow {
+        id: full_id,
+        name: truncate_string(&extract_field(db, "name", "—"), 20),
+        status: format_status(extract_field(db, "status", "unknown")),
+        subscription: truncate_string(
+            &extract_field(
+                db,
+                "subscriptionName",
+                &extract_field(db, "subscriptionId", "—"),
+            ),
+            15,
+        ),
+        memory: format_database_memory(db),
+        region: extract_database_region(db),
+        endpoint: extract_database_endpoint(db),
+        created: format_date(extract_field(db, "created", "")),
+    }
+}
+
 /// Parse database ID into subscription and database IDs
 fn parse_database_id(id: &str) -> CliResult<(u32, u32)> {
     let parts: Vec<&str> = id.split(':').collect();
     if parts.len() != 2 {
-        return Err(RedisCtlError::InvalidInput {
+        return Err(RedisCtlError::Validation {
             message: format!(
                 "Invalid database ID format: {}. Expected format: subscription_id:database_id",
                 id
@@ -422,13 +746,13 @@ fn parse_database_id(id: &str) -> CliResult<(u32, u32)> {
 
     let subscription_id = parts[0]
         .parse::<u32>()
-        .map_err(|_| RedisCtlError::InvalidInput {
+        .map_err(|_| RedisCtlError::Validation {
             message: format!("Invalid subscription ID: {}", parts[0]),
         })?;
 
     let database_id = parts[1]
         .parse::<u32>()
-        .map_err(|_| RedisCtlError::InvalidInput {
+        .map_err(|_| RedisCtlError::Validation {
             message: format!("Invalid database ID: {}", parts[1]),
         })?;
 
@@ -567,6 +891,11 @@ async fn get_database(
                 }
             })?;
         }
+        OutputFormat::Id => {
+            crate::output::print_ids(&data).map_err(|e| RedisCtlError::OutputError {
+                message: e.to_string(),
+            })?;
+        }
     }
 
     Ok(())
@@ -727,7 +1056,7 @@ fn print_database_detail(data: &Value) -> CliResult<()> {
     }
 
     if rows.is_empty() {
-        println!("No database information available");
+        notice!("No database information available");
         return Ok(());
     }
 
@@ -0,0 +1,393 @@
+//! Expiry tags for ephemeral databases
+//!
+//! `set-expiry` stores the time a database may be removed in its `expires-at` tag. `reap` finds
+//! databases whose expiry has passed and deletes them, so preview and test environments do not
+//! outlive their purpose.
+
+#![allow(dead_code)]
+
+use super::async_utils::{AsyncOperationArgs, get_task_state, is_failed_state, poll_task};
+use super::database_impl::parse_database_id;
+use super::utils::*;
+use crate::cli::OutputFormat;
+use crate::connection::ConnectionManager;
+use crate::error::{RedisCtlError, Result as CliResult};
+use crate::output::notice;
+use anyhow::Context;
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde_json::{Value, json};
+use tabled::{Table, Tabled, settings::Style};
+
+/// Tag holding the RFC 3339 time after which a database may be reaped
+pub const EXPIRY_TAG: &str = "expires-at";
+
+/// The value of a tag, from either `{"tags": [{"key", "value"}]}` or `{"tags": {key: value}}`
+fn tag_value(response: &Value, key: &str) -> Option<String> {
+    match response.get("tags")? {
+        Value::Array(tags) => tags
+            .iter()
+            .find(|tag| tag.get("key").and_then(Value::as_str) == Some(key))
+            .and_then(|tag| tag.get("value"))
+            .and_then(Value::as_str)
+            .map(str::to_string),
+        Value::Object(tags) => tags.get(key).and_then(Value::as_str).map(str::to_string),
+        _ => None,
+    }
+}
+
+/// Set, or with `clear` remove, a database's expiry
+#[allow(clippy::too_many_arguments)]
+pub async fn set_expiry(
+    conn_mgr: &ConnectionManager,
+    profile_name: Option<&str>,
+    id: &str,
+    expires_in: Option<std::time::Duration>,
+    at: Option<&str>,
+    clear: bool,
+    output_format: OutputFormat,
+    query: Option<&str>,
+) -> CliResult<()> {
+    let (subscription_id, database_id) = parse_database_id(id)?;
+    let client = conn_mgr.create_cloud_client(profile_name).await?;
+    let tags_path = format!(
+        "/subscriptions/{}/databases/{}/tags",
+        subscription_id, database_id
+    );
+    let current = client
+        .get_raw(&tags_path)
+        .await
+        .context("Failed to get tags")?;
+    let existing = tag_value(&current, EXPIRY_TAG);
+
+    if clear {
+        if existing.is_some() {
+            client
+                .delete_raw(&format!("{}/{}", tags_path, EXPIRY_TAG))
+                .await
+                .context("Failed to remove expiry tag")?;
+        }
+        return print_expiry(id, None, existing.is_some(), output_format, query);
+    }
+
+    let expires_at = match (expires_in, at) {
+        (Some(duration), _) => {
+            Utc::now()
+                + chrono::Duration::from_std(duration).map_err(|_| RedisCtlError::Validation {
+                    message: "Expiry is too far in the future".to_string(),
+                })?
+        }
+        (None, Some(at)) => DateTime::parse_from_rfc3339(at)
+            .map_err(|_| RedisCtlError::Validation {
+                message: format!("Invalid --at '{}': expected RFC 3339", at),
+            })?
+            .with_timezone(&Utc),
+        (None, None) => {
+            return Err(RedisCtlError::Validation {
+                message: "Give --in, --at or --clear".to_string(),
+            });
+        }
+    };
+    let value = expires_at.to_rfc3339_opts(SecondsFormat::Secs, true);
+
+    if existing.is_some() {
+        client
+            .put_raw(
+                &format!("{}/{}", tags_path, EXPIRY_TAG),
+                json!({ "value": value }),
+            )
+            .await
+            .context("Failed to update expiry tag")?;
+    } else {
+        client
+            .post_raw(&tags_path, json!({ "key": EXPIRY_TAG, "value": value }))
+            .await
+            .context("Failed to add expiry tag")?;
+    }
+    print_expiry(id, Some(&value), true, output_format, query)
+}
+
+fn print_expiry(
+    id: &str,
+    expires_at: Option<&str>,
+    changed: bool,
+    output_format: OutputFormat,
+    query: Option<&str>,
+) -> CliResult<()> {
+    match output_format {
+        OutputFormat::Auto | OutputFormat::Table if query.is_none() => match expires_at {
+            Some(expires_at) => notice!("Database {} expires at {}", id, expires_at),
+            None if changed => notice!("Expiry removed from database {}", id),
+            None => notice!("Database {} has no expiry", id),
+        },
+        _ => {
+            let result = json!({ "database": id, "expiresAt": expires_at });
+            let data = handle_output(result, output_format, query)?;
+            print_formatted_output(data, output_format)?;
+        }
+    }
+    Ok(())
+}
+
+/// A database carrying an expiry tag
+#[derive(Debug, Clone)]
+struct Candidate {
+    id: String,
+    name: String,
+    expires_at: String,
+    /// `None` when the tag is not a valid time
+    expired: Option<bool>,
+}
+
+/// Reap outcome for table display
+#[derive(Tabled)]
+struct ReapRow {
+    #[tabled(rename = "DATABASE")]
+    id: String,
+    #[tabled(rename = "NAME")]
+    name: String,
+    #[tabled(rename = "EXPIRES AT")]
+    expires_at: String,
+    #[tabled(rename = "RESULT")]
+    result: String,
+}
+
+/// Whether an expiry tag value has passed; `None` if it is not an RFC 3339 time
+fn is_expired(value: &str, now: DateTime<Utc>) -> Option<bool> {
+    DateTime::parse_from_rfc3339(value)
+        .ok()
+        .map(|time| time.with_timezone(&Utc) <= now)
+}
+
+/// Delete every database whose expiry tag has passed
+#[allow(clippy::too_many_arguments)]
+pub async fn reap(
+    conn_mgr: &ConnectionManager,
+    profile_name: Option<&str>,
+    subscription: Option<u32>,
+    dry_run: bool,
+    force: bool,
+    async_ops: &AsyncOperationArgs,
+    output_format: OutputFormat,
+    query: Option<&str>,
+) -> CliResult<()> {
+    let client = conn_mgr.create_cloud_client(profile_name).await?;
+    let now = Utc::now();
+
+    let subscription_ids: Vec<u32> = match subscription {
+        Some(id) => vec![id],
+        None => client
+            .get_raw("/subscriptions")
+            .await
+            .context("Failed to list subscriptions")?
+            .get("subscriptions")
+            .and_then(Value::as_array)
+            .map(|subs| {
+                subs.iter()
+                    .filter_map(|sub| sub.get("id").and_then(Value::as_u64))
+                    .map(|id| id as u32)
+                    .collect()
+            })
+            .unwrap_or_default(),
+    };
+
+    let mut candidates = Vec::new();
+    for subscription_id in subscription_ids {
+        let databases =
+            super::subscription_impl::fetch_subscription_databases(&client, subscription_id)
+                .await?;
+        for database in databases {
+            let Some(database_id) = database.get("databaseId").and_then(Value::as_u64) else {
+                continue;
+            };
+            let tags = client
+                .get_raw(&format!(
+                    "/subscriptions/{}/databases/{}/tags",
+                    subscription_id, database_id
+                ))
+                .await
+                .context("Failed to get tags")?;
+            if let Some(expires_at) = tag_value(&tags, EXPIRY_TAG) {
+                candidates.push(Candidate {
+                    id: format!("{}:{}", subscription_id, database_id),
+                    name: extract_field(&database, "name", ""),
+                    expired: is_expired(&expires_at, now),
+                    expires_at,
+                });
+            }
+        }
+    }
+
+    let expired: Vec<&Candidate> = candidates
+        .iter()
+        .filter(|c| c.expired == Some(true))
+        .collect();
+    let confirmed = !dry_run && !expired.is_empty() && (force || confirm_reap(&expired)?);
+
+    let mut results = Vec::new();
+    let mut failures = 0;
+    for candidate in &candidates {
+        let result = match candidate.expired {
+            None => "skipped: invalid expiry".to_string(),
+            Some(false) => "not expired".to_string(),
+            Some(true) if dry_run => "would delete".to_string(),
+            Some(true) if !confirmed => "cancelled".to_string(),
+            Some(true) => match delete(conn_mgr, profile_name, &client, candidate, async_ops).await
+            {
+                Ok(result) => result,
+                Err(e) => {
+                    failures += 1;
+                    format!("failed: {}", e)
+                }
+            },
+        };
+        results.push((candidate, result));
+    }
+
+    match output_format {
+        OutputFormat::Auto | OutputFormat::Table if query.is_none() => {
+            if results.is_empty() {
+                notice!("No databases have an {} tag", EXPIRY_TAG);
+            } else {
+                let rows: Vec<ReapRow> = results
+                    .iter()
+                    .map(|(c, result)| ReapRow {
+                        id: c.id.clone(),
+                        name: c.name.clone(),
+                        expires_at: c.expires_at.clone(),
+                        result: result.clone(),
+                    })
+                    .collect();
+                let mut table = Table::new(rows);
+                table.with(Style::blank());
+                println!("{}", table);
+                if dry_run {
+                    println!(
+                        "\nDry run: {} expired database(s) would be deleted",
+                        expired.len()
+                    );
+                }
+            }
+        }
+        _ => {
+            let result = json!({
+                "dryRun": dry_run,
+                "databases": results
+                    .iter()
+                    .map(|(c, result)| json!({
+                        "id": c.id,
+                        "name": c.name,
+                        "expiresAt": c.expires_at,
+                        "result": result,
+                    }))
+                    .collect::<Vec<_>>(),
+            });
+            let data = handle_output(result, output_format, query)?;
+            print_formatted_output(data, output_format)?;
+        }
+    }
+
+    if failures > 0 {
+        return Err(RedisCtlError::api(format!(
+            "{} expired database(s) could not be deleted",
+            failures
+        )));
+    }
+    Ok(())
+}
+
+fn confirm_reap(expired: &[&Candidate]) -> CliResult<bool> {
+    use dialoguer::Confirm;
+    crate::interactive::ensure_prompt_allowed(
+        "Deleting expired databases needs confirmation",
+        "pass --force to delete them without it",
+    )?;
+    eprintln!("Expired databases:");
+    for candidate in expired {
+        eprintln!(
+            "  {} {} (expired {})",
+            candidate.id, candidate.name, candidate.expires_at
+        );
+    }
+    let confirm = Confirm::new()
+        .with_prompt(format!("Delete {} expired database(s)?", expired.len()))
+        .default(false)
+        .interact()
+        .map_err(|e| RedisCtlError::Validation {
+            message: format!("Failed to read confirmation: {}", e),
+        })?;
+    Ok(confirm)
+}
+
+/// Delete one database, waiting for its task with `--wait`
+async fn delete(
+    conn_mgr: &ConnectionManager,
+    profile_name: Option<&str>,
+    client: &redis_cloud::CloudClient,
+    candidate: &Candidate,
+    async_ops: &AsyncOperationArgs,
+) -> CliResult<String> {
+    let (subscription_id, database_id) = parse_database_id(&candidate.id)?;
+    let response = client
+        .delete_raw(&format!(
+            "/subscriptions/{}/databases/{}",
+            subscription_id, database_id
+        ))
+        .await
+        .context("Failed to delete database")?;
+    let task_id = response
+        .get("taskId")
+        .and_then(Value::as_str)
+        .map(str::to_string);
+
+    match task_id {
+        Some(task_id) if async_ops.wait => {
+            let task = poll_task(
+                conn_mgr,
+                profile_name,
+                &task_id,
+                async_ops.wait_timeout,
+                async_ops.wait_interval,
+            )
+            .await?;
+            let state = get_task_state(&task);
+            if is_failed_state(&state) {
+                Err(RedisCtlError::api(format!("task {} {}", task_id, state)))
+            } else {
+                Ok("deleted".to_string())
+            }
+        }
+        Some(task_id) => Ok(format!("deletion started (task {})", task_id)),
+        None => Ok("deletion started".to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tag_value() {
+        let listed = json!({"tags": [
+            {"key": "env", "value": "preview"},
+            {"key": "expires-at", "value": "2024-03-10T12:00:00Z"}
+        ]});
+        assert_eq!(
+            tag_value(&listed, EXPIRY_TAG).as_deref(),
+            Some("2024-03-10T12:00:00Z")
+        );
+        let keyed = json!({"tags": {"expires-at": "2024-03-10T12:00:00Z"}});
+        assert!(tag_value(&keyed, EXPIRY_TAG).is_some());
+        assert_eq!(tag_value(&json!({"tags": []}), EXPIRY_TAG), None);
+    }
+
+    #[test]
+    fn test_is_expired() {
+        let now = DateTime::parse_from_rfc3339("2024-03-10T12:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        assert_eq!(is_expired("2024-03-10T11:59:59Z", now), Some(true));
+        assert_eq!(is_expired("2024-03-10T12:00:00Z", now), Some(true));
+        assert_eq!(is_expired("2024-03-11T00:00:00+02:00", now), Some(false));
+        assert_eq!(is_expired("next week", now), None);
+    }
+}
@@ -1,13 +1,25 @@
 //! Implementation of additional database commands
 
 use super::async_utils::{AsyncOperationArgs, handle_async_response};
+use super::database_module::{ModuleChanges, with_modules, with_query_performance_factor};
+use super::if_absent;
 use super::utils::*;
+use crate::certificate;
 use crate::cli::OutputFormat;
+use crate::commands::patch::patch_update;
 use crate::connection::ConnectionManager;
 use crate::error::{RedisCtlError, Result as CliResult};
+use crate::output::notice;
 use crate::output::print_output;
 use anyhow::Context;
+use chrono::Utc;
+use redis_cloud::DatabaseHandler;
+use redis_cloud::acl::AclHandler;
+use redis_cloud::databases::{
+    CrdbFlushRequest, CrdbUpdatePropertiesRequest, DatabaseBackupConfig, DatabaseUpdateRequest,
+};
 use serde_json::{Value, json};
+use std::path::Path;
 use tabled::{Table, Tabled, settings::Style};
 
 /// Helper to print non-table output
@@ -15,16 +27,17 @@ fn print_json_or_yaml(data: Value, output_format: OutputFormat) -> CliResult<()>
     match output_format {
         OutputFormat::Json => print_output(data, crate::output::OutputFormat::Json, None)?,
         OutputFormat::Yaml => print_output(data, crate::output::OutputFormat::Yaml, None)?,
+        OutputFormat::Id => crate::output::print_ids(&data)?,
         _ => print_output(data, crate::output::OutputFormat::Json, None)?,
     }
     Ok(())
 }
 
 /// Parse database ID into subscription and database IDs
-fn parse_database_id(id: &str) -> CliResult<(u32, u32)> {
+pub(super) fn parse_database_id(id: &str) -> CliResult<(u32, u32)> {
     let parts: Vec<&str> = id.split(':').collect();
     if parts.len() != 2 {
-        return Err(RedisCtlError::InvalidInput {
+        return Err(RedisCtlError::Validation {
             message: format!(
                 "Invalid database ID format: {}. Expected format: subscription_id:database_id",
                 id
@@ -34,13 +47,13 @@ fn parse_database_id(id: &str) -> CliResult<(u32, u32)> {
 
     let subscription_id = parts[0]
         .parse::<u32>()
-        .map_err(|_| RedisCtlError::InvalidInput {
+        .map_err(|_| RedisCtlError::Validation {
             message: format!("Invalid subscription ID: {}", parts[0]),
         })?;
 
     let database_id = parts[1]
         .parse::<u32>()
-        .map_err(|_| RedisCtlError::InvalidInput {
+        .map_err(|_| RedisCtlError::Validation {
             message: format!("Invalid database ID: {}", parts[1]),
         })?;
 
@@ -51,7 +64,7 @@ fn parse_database_id(id: &str) -> CliResult<(u32, u32)> {
 fn read_json_data(data: &str) -> CliResult<Value> {
     let json_str = if let Some(file_path) = data.strip_prefix('@') {
         // Read from file
-        std::fs::read_to_string(file_path).map_err(|e| RedisCtlError::InvalidInput {
+        std::fs::read_to_string(file_path).map_err(|e| RedisCtlError::Validation {
             message: format!("Failed to read file {}: {}", file_path, e),
         })?
     } else {
@@ -59,23 +72,46 @@ fn read_json_data(data: &str) -> CliResult<Value> {
         data.to_string()
     };
 
-    serde_json::from_str(&json_str).map_err(|e| RedisCtlError::InvalidInput {
+    serde_json::from_str(&json_str).map_err(|e| RedisCtlError::Validation {
         message: format!("Invalid JSON: {}", e),
     })
 }
 
 /// Create a new database
+#[allow(clippy::too_many_arguments)]
 pub async fn create_database(
     conn_mgr: &ConnectionManager,
     profile_name: Option<&str>,
     subscription_id: u32,
     data: &str,
+    memory: Option<u64>,
+    backup: Option<&DatabaseBackupConfig>,
+    modules: &ModuleChanges,
+    if_absent: Option<&str>,
     async_ops: &AsyncOperationArgs,
     output_format: OutputFormat,
     query: Option<&str>,
 ) -> CliResult<()> {
     let client = conn_mgr.create_cloud_client(profile_name).await?;
-    let request = read_json_data(data)?;
+    let request = with_dataset_size(read_json_data(data)?, memory)?;
+    let request = with_remote_backup(request, backup)?;
+    let mut request = with_modules(&client, request, modules).await?;
+
+    if let Some(spec) = if_absent {
+        let name = if_absent::resolve_name(spec, &mut request, "database")?;
+        let databases =
+            super::subscription_impl::fetch_subscription_databases(&client, subscription_id)
+                .await?;
+        if let Some(existing) = if_absent::find_by_name(&databases, &name, "database")? {
+            let id = format!(
+                "{}:{}",
+                subscription_id,
+                extract_field(existing, "databaseId", "")
+            );
+            return if_absent::print_found("database", json!(id), existing, output_format, query);
+        }
+        if_absent::note_creating("database", &name);
+    }
 
     let response = client
         .post_raw(
@@ -97,28 +133,99 @@ pub async fn create_database(
     .await
 }
 
+/// Set `datasetSizeInGb` from a `--memory` size in bytes, overriding the payload's value
+fn with_dataset_size(mut request: Value, memory: Option<u64>) -> CliResult<Value> {
+    let Some(bytes) = memory else {
+        return Ok(request);
+    };
+    let object = request
+        .as_object_mut()
+        .ok_or_else(|| RedisCtlError::Validation {
+            message: "--memory requires --data to be a JSON object".to_string(),
+        })?;
+    object.insert(
+        "datasetSizeInGb".to_string(),
+        json!(bytes as f64 / (1u64 << 30) as f64),
+    );
+    Ok(request)
+}
+
+/// Set `remoteBackup` from `--backup-every` and `--backup-path`, overriding the payload's value
+fn with_remote_backup(
+    mut request: Value,
+    backup: Option<&DatabaseBackupConfig>,
+) -> CliResult<Value> {
+    let Some(backup) = backup else {
+        return Ok(request);
+    };
+    let object = request
+        .as_object_mut()
+        .ok_or_else(|| RedisCtlError::Validation {
+            message: "--backup-every requires --data to be a JSON object".to_string(),
+        })?;
+    object.insert(
+        "remoteBackup".to_string(),
+        serde_json::to_value(backup).context("Failed to serialize backup configuration")?,
+    );
+    Ok(request)
+}
+
 /// Update database configuration
+#[allow(clippy::too_many_arguments)]
 pub async fn update_database(
     conn_mgr: &ConnectionManager,
     profile_name: Option<&str>,
     id: &str,
-    data: &str,
+    data: Option<&str>,
+    patch: Option<&str>,
+    memory: Option<u64>,
+    backup: Option<&DatabaseBackupConfig>,
+    query_performance_factor: Option<&str>,
     async_ops: &AsyncOperationArgs,
     output_format: OutputFormat,
     query: Option<&str>,
 ) -> CliResult<()> {
     let (subscription_id, database_id) = parse_database_id(id)?;
     let client = conn_mgr.create_cloud_client(profile_name).await?;
-    let request = read_json_data(data)?;
+    let path = format!(
+        "/subscriptions/{}/databases/{}",
+        subscription_id, database_id
+    );
+    let request = match patch {
+        Some(patch) => {
+            let current = client
+                .get_raw(&path)
+                .await
+                .context("Failed to get database")?;
+            patch_update(&current, patch)?
+        }
+        None => {
+            let request = match data {
+                Some(data) => read_json_data(data)?,
+                None => json!({}),
+            };
+            let request = with_remote_backup(with_dataset_size(request, memory)?, backup)?;
+            let request = match query_performance_factor {
+                Some(factor) => {
+                    let database = DatabaseHandler::new(client.clone())
+                        .get_subscription_database_by_id(subscription_id as i32, database_id as i32)
+                        .await
+                        .context("Failed to get database")?;
+                    with_query_performance_factor(&client, request, factor, &database.modules())
+                        .await?
+                }
+                None => request,
+            };
+            Some(request)
+        }
+    };
+    let Some(request) = request else {
+        notice!("No changes to apply");
+        return Ok(());
+    };
 
     let response = client
-        .put_raw(
-            &format!(
-                "/subscriptions/{}/databases/{}",
-                subscription_id, database_id
-            ),
-            request,
-        )
+        .put_raw(&path, request)
         .await
         .context("Failed to update database")?;
 
@@ -134,6 +241,77 @@ pub async fn update_database(
     .await
 }
 
+/// Rename a database with an update that carries only the new name
+pub async fn rename_database(
+    conn_mgr: &ConnectionManager,
+    profile_name: Option<&str>,
+    id: &str,
+    name: &str,
+    async_ops: &AsyncOperationArgs,
+    output_format: OutputFormat,
+    query: Option<&str>,
+) -> CliResult<()> {
+    let (subscription_id, database_id) = parse_database_id(id)?;
+    validate_database_name(name)?;
+
+    let client = conn_mgr.create_cloud_client(profile_name).await?;
+    let handler = DatabaseHandler::new(client);
+    let current = handler
+        .get_subscription_database_by_id(subscription_id as i32, database_id as i32)
+        .await
+        .context("Failed to get database")?;
+    let old_name = current
+        .extra
+        .get("name")
+        .and_then(Value::as_str)
+        .unwrap_or_default()
+        .to_string();
+    if old_name == name {
+        notice!("Database {} is already named '{}'", id, name);
+        return Ok(());
+    }
+
+    let mut request: DatabaseUpdateRequest =
+        serde_json::from_value(json!({})).context("Failed to build rename")?;
+    request.name = Some(name.to_string());
+    let task = handler
+        .update_database(subscription_id as i32, database_id as i32, &request)
+        .await
+        .context("Failed to rename database")?;
+    let response = serde_json::to_value(task).context("Failed to serialize response")?;
+
+    handle_async_response(
+        conn_mgr,
+        profile_name,
+        response,
+        async_ops,
+        output_format,
+        query,
+        &format!("Database renamed from '{}' to '{}'", old_name, name),
+    )
+    .await
+}
+
+/// Check a name against Redis Cloud's database naming rules
+fn validate_database_name(name: &str) -> CliResult<()> {
+    let valid = !name.is_empty()
+        && name.len() <= 40
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+        && name.starts_with(|c: char| c.is_ascii_alphabetic())
+        && !name.ends_with('-');
+    if valid {
+        Ok(())
+    } else {
+        Err(RedisCtlError::Validation {
+            message: format!(
+                "Invalid database name '{}': use up to 40 letters, digits and hyphens, \
+                 starting with a letter and not ending with a hyphen",
+                name
+            ),
+        })
+    }
+}
+
 /// Delete a database
 pub async fn delete_database(
     conn_mgr: &ConnectionManager,
@@ -149,16 +327,20 @@ pub async fn delete_database(
     // Confirmation prompt unless --force is used
     if !force {
         use dialoguer::Confirm;
+        crate::interactive::ensure_prompt_allowed(
+            &format!("Deleting database {} needs confirmation", id),
+            "pass --force to delete without it",
+        )?;
         let confirm = Confirm::new()
             .with_prompt(format!("Are you sure you want to delete database {}?", id))
             .default(false)
             .interact()
-            .map_err(|e| RedisCtlError::InvalidInput {
+            .map_err(|e| RedisCtlError::Validation {
                 message: format!("Failed to read confirmation: {}", e),
             })?;
 
         if !confirm {
-            println!("Database deletion cancelled");
+            notice!("Database deletion cancelled");
             return Ok(());
         }
     }
@@ -346,43 +528,97 @@ pub async fn import_database(
     .await
 }
 
-/// Get database certificate
+/// Get the database TLS certificate chain, optionally writing PEM files and verifying it
 pub async fn get_certificate(
     conn_mgr: &ConnectionManager,
     profile_name: Option<&str>,
     id: &str,
+    out: Option<&Path>,
+    verify: bool,
     output_format: OutputFormat,
     query: Option<&str>,
 ) -> CliResult<()> {
     let (subscription_id, database_id) = parse_database_id(id)?;
     let client = conn_mgr.create_cloud_client(profile_name).await?;
 
-    let response = client
-        .get_raw(&format!(
-            "/subscriptions/{}/databases/{}/certificate",
-            subscription_id, database_id
-        ))
+    let response = DatabaseHandler::new(client)
+        .get_subscription_database_certificate(subscription_id as i32, database_id as i32)
         .await
         .context("Failed to get certificate")?;
+    let pem = response
+        .public_certificate_pem_string
+        .filter(|pem| !pem.trim().is_empty())
+        .map(|pem| certificate::normalize_pem(&pem))
+        .ok_or_else(|| {
+            RedisCtlError::api(format!(
+                "Database {} returned no certificate; is TLS enabled for it?",
+                id
+            ))
+        })?;
 
-    let result = if let Some(q) = query {
-        apply_jmespath(&response, q)?
+    let now = Utc::now().timestamp();
+    let certificates = certificate::parse_chain(&pem, now).map_err(|message| {
+        RedisCtlError::api(format!(
+            "Could not parse the database certificate: {}",
+            message
+        ))
+    })?;
+    let verification = if verify {
+        Some(certificate::verify_chain(&pem, now).map_err(RedisCtlError::api)?)
     } else {
-        response
+        None
+    };
+    let files = match out {
+        Some(dir) => {
+            let prefix = format!("{}-{}", subscription_id, database_id);
+            certificate::write_pem_files(dir, &prefix, &pem, &certificates)
+                .with_context(|| format!("Failed to write certificates to {}", dir.display()))?
+        }
+        None => Vec::new(),
     };
 
     match output_format {
-        OutputFormat::Table => {
-            if let Some(cert) = result.get("certificate") {
-                println!("{}", cert.as_str().unwrap_or(""));
+        OutputFormat::Auto | OutputFormat::Table if query.is_none() => {
+            if out.is_none() && !verify {
+                print!("{}", pem);
             } else {
-                println!("No certificate available");
+                println!("{}", certificate::chain_table(&certificates));
+            }
+            for file in &files {
+                notice!("Wrote {}", file.display());
+            }
+            if let Some(verification) = &verification {
+                for warning in &verification.warnings {
+                    println!("Warning: {}", warning);
+                }
+                for problem in &verification.problems {
+                    println!("Problem: {}", problem);
+                }
+                if verification.valid {
+                    println!("Chain is valid");
+                }
             }
         }
-        _ => print_json_or_yaml(result, output_format)?,
+        _ => {
+            let json = json!({
+                "certificate": pem,
+                "chain": certificates,
+                "files": files,
+                "verification": verification,
+            });
+            let data = handle_output(json, output_format, query)?;
+            print_formatted_output(data, output_format)?;
+        }
     }
 
-    Ok(())
+    match verification {
+        Some(verification) if !verification.valid => Err(RedisCtlError::api(format!(
+            "Certificate chain for database {} is invalid: {}",
+            id,
+            verification.problems.join("; ")
+        ))),
+        _ => Ok(()),
+    }
 }
 
 /// Slow log entry for table display
@@ -441,7 +677,7 @@ pub async fn get_slow_log(
             }
 
             if entries.is_empty() {
-                println!("No slow log entries found");
+                notice!("No slow log entries found");
             } else {
                 let mut table = Table::new(entries);
                 table.with(Style::modern());
@@ -502,7 +738,7 @@ pub async fn list_tags(
             }
 
             if entries.is_empty() {
-                println!("No tags found");
+                notice!("No tags found");
             } else {
                 let mut table = Table::new(entries);
                 table.with(Style::modern());
@@ -552,7 +788,7 @@ pub async fn add_tag(
 
     match output_format {
         OutputFormat::Table => {
-            println!("Tag added successfully: {} = {}", key, value);
+            notice!("Tag added successfully: {} = {}", key, value);
         }
         _ => print_json_or_yaml(result, output_format)?,
     }
@@ -592,7 +828,7 @@ pub async fn update_tags(
 
     match output_format {
         OutputFormat::Table => {
-            println!("Tags updated successfully");
+            notice!("Tags updated successfully");
         }
         _ => print_json_or_yaml(result, output_format)?,
     }
@@ -622,7 +858,7 @@ pub async fn delete_tag(
 
     match output_format {
         OutputFormat::Table => {
-            println!("Tag '{}' deleted successfully", key);
+            notice!("Tag '{}' deleted successfully", key);
         }
         _ => {
             let result = json!({"message": format!("Tag '{}' deleted", key)});
@@ -633,12 +869,13 @@ pub async fn delete_tag(
     Ok(())
 }
 
-/// Flush Active-Active database
+/// Flush an Active-Active database in every region
 pub async fn flush_crdb(
     conn_mgr: &ConnectionManager,
     profile_name: Option<&str>,
     id: &str,
     force: bool,
+    async_ops: &AsyncOperationArgs,
     output_format: OutputFormat,
     query: Option<&str>,
 ) -> CliResult<()> {
@@ -647,50 +884,93 @@ pub async fn flush_crdb(
     // Confirmation prompt unless --force is used
     if !force {
         use dialoguer::Confirm;
+        crate::interactive::ensure_prompt_allowed(
+            &format!("Flushing database {} needs confirmation", id),
+            "pass --force to flush without it",
+        )?;
         let confirm = Confirm::new()
-            .with_prompt(format!("Are you sure you want to flush Active-Active database {}? This will delete all data!", id))
+            .with_prompt(format!("Are you sure you want to flush Active-Active database {}? This will delete all data in every region!", id))
             .default(false)
             .interact()
-            .map_err(|e| RedisCtlError::InvalidInput {
+            .map_err(|e| RedisCtlError::Validation {
                 message: format!("Failed to read confirmation: {}", e),
             })?;
 
         if !confirm {
-            println!("Flush operation cancelled");
+            notice!("Flush operation cancelled");
             return Ok(());
         }
     }
 
     let client = conn_mgr.create_cloud_client(profile_name).await?;
-
-    let response = client
-        .post_raw(
-            &format!(
-                "/subscriptions/{}/databases/{}/flush",
-                subscription_id, database_id
-            ),
-            json!({}),
-        )
+    let request = CrdbFlushRequest {
+        subscription_id: None,
+        database_id: None,
+        command_type: None,
+        extra: json!({}),
+    };
+    let task = DatabaseHandler::new(client)
+        .flush_crdb(subscription_id as i32, database_id as i32, &request)
         .await
         .context("Failed to flush database")?;
+    let response = serde_json::to_value(task).context("Failed to serialize response")?;
 
-    let result = if let Some(q) = query {
-        apply_jmespath(&response, q)?
-    } else {
-        response
-    };
+    handle_async_response(
+        conn_mgr,
+        profile_name,
+        response,
+        async_ops,
+        output_format,
+        query,
+        "Active-Active database flush initiated",
+    )
+    .await
+}
 
-    match output_format {
-        OutputFormat::Table => {
-            println!("Active-Active database flush initiated");
-            if let Some(task_id) = result.get("taskId") {
-                println!("Task ID: {}", task_id);
+/// Set the local read and write throughput of one region of an Active-Active database
+#[allow(clippy::too_many_arguments)]
+pub async fn set_region_throughput(
+    conn_mgr: &ConnectionManager,
+    profile_name: Option<&str>,
+    id: &str,
+    region: &str,
+    read_ops: i64,
+    write_ops: i64,
+    async_ops: &AsyncOperationArgs,
+    output_format: OutputFormat,
+    query: Option<&str>,
+) -> CliResult<()> {
+    let (subscription_id, database_id) = parse_database_id(id)?;
+
+    let request: CrdbUpdatePropertiesRequest = serde_json::from_value(json!({
+        "regions": [{
+            "region": region,
+            "localThroughputMeasurement": {
+                "region": region,
+                "readOperationsPerSecond": read_ops,
+                "writeOperationsPerSecond": write_ops
             }
-        }
-        _ => print_json_or_yaml(result, output_format)?,
-    }
+        }]
+    }))
+    .context("Failed to build region update")?;
 
-    Ok(())
+    let client = conn_mgr.create_cloud_client(profile_name).await?;
+    let task = DatabaseHandler::new(client)
+        .update_crdb_local_properties(subscription_id as i32, database_id as i32, &request)
+        .await
+        .context("Failed to update region throughput")?;
+    let response = serde_json::to_value(task).context("Failed to serialize response")?;
+
+    handle_async_response(
+        conn_mgr,
+        profile_name,
+        response,
+        async_ops,
+        output_format,
+        query,
+        &format!("Throughput update for region {} initiated", region),
+    )
+    .await
 }
 
 /// Get Redis version upgrade status
@@ -774,7 +1054,7 @@ pub async fn upgrade_redis(
 
     match output_format {
         OutputFormat::Table => {
-            println!("Redis version upgrade initiated to {}", version);
+            notice!("Redis version upgrade initiated to {}", version);
             if let Some(task_id) = result.get("taskId") {
                 println!("Task ID: {}", task_id);
             }
@@ -784,3 +1064,111 @@ pub async fn upgrade_redis(
 
     Ok(())
 }
+
+/// Attach an ACL role to a database
+#[allow(clippy::too_many_arguments)]
+pub async fn attach_acl_role(
+    conn_mgr: &ConnectionManager,
+    profile_name: Option<&str>,
+    id: &str,
+    role: &str,
+    rule: &str,
+    regions: &[String],
+    async_ops: &AsyncOperationArgs,
+    output_format: OutputFormat,
+    query: Option<&str>,
+) -> CliResult<()> {
+    let (subscription_id, database_id) = parse_database_id(id)?;
+    let client = conn_mgr.create_cloud_client(profile_name).await?;
+    let handler = AclHandler::new(client);
+
+    let regions = (!regions.is_empty()).then(|| regions.to_vec());
+    let task = handler
+        .attach_role_to_database(
+            role,
+            rule,
+            subscription_id as i32,
+            database_id as i32,
+            regions,
+        )
+        .await?;
+    let response = serde_json::to_value(task).context("Failed to serialize task")?;
+
+    handle_async_response(
+        conn_mgr,
+        profile_name,
+        response,
+        async_ops,
+        output_format,
+        query,
+        &format!("ACL role '{}' attached to database {}", role, id),
+    )
+    .await
+}
+
+/// Detach an ACL role from a database
+pub async fn detach_acl_role(
+    conn_mgr: &ConnectionManager,
+    profile_name: Option<&str>,
+    id: &str,
+    role: &str,
+    async_ops: &AsyncOperationArgs,
+    output_format: OutputFormat,
+    query: Option<&str>,
+) -> CliResult<()> {
+    let (subscription_id, database_id) = parse_database_id(id)?;
+    let client = conn_mgr.create_cloud_client(profile_name).await?;
+    let handler = AclHandler::new(client);
+
+    let task = handler
+        .detach_role_from_database(role, subscription_id as i32, database_id as i32)
+        .await?;
+    let response = serde_json::to_value(task).context("Failed to serialize task")?;
+
+    handle_async_response(
+        conn_mgr,
+        profile_name,
+        response,
+        async_ops,
+        output_format,
+        query,
+        &format!("ACL role '{}' detached from database {}", role, id),
+    )
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_database_name() {
+        assert!(validate_database_name("cache-01").is_ok());
+        assert!(validate_database_name("").is_err());
+        assert!(validate_database_name("1cache").is_err());
+        assert!(validate_database_name("cache-").is_err());
+        assert!(validate_database_name("cache_01").is_err());
+        assert!(validate_database_name(&"a".repeat(41)).is_err());
+    }
+
+    #[test]
+    fn test_with_dataset_size() {
+        let request = json!({"name": "cache", "datasetSizeInGb": 1});
+        let request = with_dataset_size(request, Some(5 << 29)).unwrap();
+        assert_eq!(request["datasetSizeInGb"], json!(2.5));
+        assert_eq!(request["name"], "cache");
+        assert_eq!(with_dataset_size(json!([]), None).unwrap(), json!([]));
+        assert!(with_dataset_size(json!([]), Some(1)).is_err());
+    }
+
+    #[test]
+    fn test_with_remote_backup() {
+        let backup = DatabaseBackupConfig::enabled("every-6-hours", "aws-s3", "s3://bucket/db");
+        let request = with_remote_backup(json!({"name": "cache"}), Some(&backup)).unwrap();
+        assert_eq!(request["remoteBackup"]["active"], true);
+        assert_eq!(request["remoteBackup"]["interval"], "every-6-hours");
+        assert_eq!(request["remoteBackup"]["storagePath"], "s3://bucket/db");
+        assert_eq!(with_remote_backup(json!([]), None).unwrap(), json!([]));
+        assert!(with_remote_backup(json!([]), Some(&backup)).is_err());
+    }
+}
@@ -0,0 +1,322 @@
+//! Advanced capabilities (modules) of Pro databases
+//!
+//! Modules are chosen when a database is created; the update request has no `modules` field,
+//! so they can be listed afterwards but not changed. The query performance factor, which adds
+//! compute for search queries, is only accepted for databases running search, and only with a
+//! factor the account offers.
+
+#![allow(dead_code)]
+
+use super::database_impl::parse_database_id;
+use super::utils::*;
+use crate::cli::{CloudModuleArg, OutputFormat};
+use crate::connection::ConnectionManager;
+use crate::error::{RedisCtlError, Result as CliResult};
+use crate::output::notice;
+use anyhow::Context;
+use redis_cloud::databases::DatabaseModuleSpec;
+use redis_cloud::{AccountHandler, CloudClient, DatabaseHandler};
+use serde::Serialize;
+use serde_json::Value;
+use tabled::{Table, Tabled, settings::Style};
+
+/// Query performance factors are even multiples up to this one
+const MAX_QUERY_PERFORMANCE_FACTOR: u32 = 16;
+
+/// Modules and search scaling requested on the command line
+#[derive(Debug, Clone, Default)]
+pub struct ModuleChanges {
+    pub modules: Vec<CloudModuleArg>,
+    /// Normalized factor, e.g. `4x`
+    pub query_performance_factor: Option<String>,
+}
+
+impl ModuleChanges {
+    pub fn is_empty(&self) -> bool {
+        self.modules.is_empty() && self.query_performance_factor.is_none()
+    }
+}
+
+/// The API name of a module
+pub fn module_name(module: CloudModuleArg) -> &'static str {
+    match module {
+        CloudModuleArg::Search => "RediSearch",
+        CloudModuleArg::Json => "RedisJSON",
+        CloudModuleArg::Timeseries => "RedisTimeSeries",
+        CloudModuleArg::Bloom => "RedisBloom",
+    }
+}
+
+/// Parse a query performance factor such as `4` or `4x` into the API's `4x`
+pub fn parse_query_performance_factor(s: &str) -> Result<String, String> {
+    let trimmed = s.trim().to_ascii_lowercase();
+    let number = trimmed.strip_suffix('x').unwrap_or(&trimmed);
+    match number.parse::<u32>() {
+        Ok(n) if (2..=MAX_QUERY_PERFORMANCE_FACTOR).contains(&n) && n.is_multiple_of(2) => {
+            Ok(format!("{}x", n))
+        }
+        _ => Err(format!(
+            "invalid query performance factor: {} (expected an even factor from 2x to {}x)",
+            s.trim(),
+            MAX_QUERY_PERFORMANCE_FACTOR
+        )),
+    }
+}
+
+/// `current` with the requested modules added, and the names of those that were added
+///
+/// Only the name and parameters of the current modules are kept, since responses also carry
+/// read-only fields such as the version.
+pub fn merge_modules(
+    current: &[DatabaseModuleSpec],
+    add: &[CloudModuleArg],
+) -> (Vec<DatabaseModuleSpec>, Vec<&'static str>) {
+    let mut modules: Vec<DatabaseModuleSpec> = current
+        .iter()
+        .map(|m| DatabaseModuleSpec {
+            parameters: m.parameters.clone(),
+            ..DatabaseModuleSpec::new(m.name.clone())
+        })
+        .collect();
+    let mut added = Vec::new();
+    for name in add.iter().map(|m| module_name(*m)) {
+        if !has_module(&modules, name) && !added.contains(&name) {
+            modules.push(DatabaseModuleSpec::new(name));
+            added.push(name);
+        }
+    }
+    (modules, added)
+}
+
+fn has_module(modules: &[DatabaseModuleSpec], name: &str) -> bool {
+    modules.iter().any(|m| m.name.eq_ignore_ascii_case(name))
+}
+
+/// A query performance factor needs search among the database's modules
+pub fn check_factor_applies(modules: &[DatabaseModuleSpec], factor: Option<&str>) -> CliResult<()> {
+    match factor {
+        Some(factor) if !has_module(modules, module_name(CloudModuleArg::Search)) => {
+            Err(RedisCtlError::Validation {
+                message: format!(
+                    "Query performance factor {} applies only to databases with search",
+                    factor
+                ),
+            })
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Refuse a factor the account does not offer; an empty or unavailable list is not checked
+async fn check_factor_offered(client: &CloudClient, factor: &str) -> CliResult<()> {
+    let offered = match AccountHandler::new(client.clone())
+        .get_supported_search_scaling_factors()
+        .await
+    {
+        Ok(data) => data.query_performance_factors.unwrap_or_default(),
+        Err(e) => {
+            tracing::debug!("Could not list query performance factors: {}", e);
+            return Ok(());
+        }
+    };
+    if offered.is_empty() || offered.iter().any(|f| f.eq_ignore_ascii_case(factor)) {
+        return Ok(());
+    }
+    Err(RedisCtlError::Validation {
+        message: format!(
+            "Query performance factor {} is not available for this account (available: {})",
+            factor,
+            offered.join(", ")
+        ),
+    })
+}
+
+/// Apply `--module` and `--query-performance-factor` to a create payload
+///
+/// The modules already in the payload are kept.
+pub async fn with_modules(
+    client: &CloudClient,
+    mut request: Value,
+    changes: &ModuleChanges,
+) -> CliResult<Value> {
+    if changes.is_empty() {
+        return Ok(request);
+    }
+    let object = request
+        .as_object_mut()
+        .ok_or_else(|| RedisCtlError::Validation {
+            message: "--module requires --data to be a JSON object".to_string(),
+        })?;
+    let from_payload: Option<Vec<DatabaseModuleSpec>> = object
+        .get("modules")
+        .map(|m| serde_json::from_value(m.clone()))
+        .transpose()
+        .map_err(|e| RedisCtlError::Validation {
+            message: format!("Invalid modules in --data: {}", e),
+        })?;
+    let (modules, _) = merge_modules(
+        from_payload.as_deref().unwrap_or_default(),
+        &changes.modules,
+    );
+    let factor = changes
+        .query_performance_factor
+        .as_deref()
+        .or_else(|| object.get("queryPerformanceFactor").and_then(Value::as_str));
+    check_factor_applies(&modules, factor)?;
+    if let Some(factor) = &changes.query_performance_factor {
+        check_factor_offered(client, factor).await?;
+        object.insert(
+            "queryPerformanceFactor".to_string(),
+            Value::String(factor.clone()),
+        );
+    }
+    if !changes.modules.is_empty() {
+        object.insert(
+            "modules".to_string(),
+            serde_json::to_value(&modules).context("Failed to serialize modules")?,
+        );
+    }
+    Ok(request)
+}
+
+/// Set the query performance factor in an update request for a database running `current`
+pub async fn with_query_performance_factor(
+    client: &CloudClient,
+    mut request: Value,
+    factor: &str,
+    current: &[DatabaseModuleSpec],
+) -> CliResult<Value> {
+    let object = request
+        .as_object_mut()
+        .ok_or_else(|| RedisCtlError::Validation {
+            message: "--query-performance-factor requires --data to be a JSON object".to_string(),
+        })?;
+    check_factor_applies(current, Some(factor))?;
+    check_factor_offered(client, factor).await?;
+    object.insert(
+        "queryPerformanceFactor".to_string(),
+        Value::String(factor.to_string()),
+    );
+    Ok(request)
+}
+
+/// A module of a database, for display
+#[derive(Debug, Clone, Serialize, Tabled)]
+pub struct ModuleRow {
+    #[tabled(rename = "NAME")]
+    pub name: String,
+    #[tabled(rename = "CAPABILITY")]
+    pub capability: String,
+    #[tabled(rename = "VERSION")]
+    pub version: String,
+}
+
+impl From<&DatabaseModuleSpec> for ModuleRow {
+    fn from(module: &DatabaseModuleSpec) -> Self {
+        let text = |key: &str| {
+            module
+                .extra
+                .get(key)
+                .and_then(Value::as_str)
+                .unwrap_or("-")
+                .to_string()
+        };
+        ModuleRow {
+            name: module.name.clone(),
+            capability: text("capabilityName"),
+            version: text("version"),
+        }
+    }
+}
+
+/// List the modules a database runs
+pub async fn list_modules(
+    conn_mgr: &ConnectionManager,
+    profile_name: Option<&str>,
+    id: &str,
+    output_format: OutputFormat,
+    query: Option<&str>,
+) -> CliResult<()> {
+    let (subscription_id, database_id) = parse_database_id(id)?;
+    let client = conn_mgr.create_cloud_client(profile_name).await?;
+    let database = DatabaseHandler::new(client)
+        .get_subscription_database_by_id(subscription_id as i32, database_id as i32)
+        .await
+        .context("Failed to get database")?;
+    let modules = database.modules();
+
+    match output_format {
+        OutputFormat::Auto | OutputFormat::Table if query.is_none() => {
+            if modules.is_empty() {
+                notice!("Database {} runs no modules", id);
+            } else {
+                let rows: Vec<ModuleRow> = modules.iter().map(ModuleRow::from).collect();
+                let mut table = Table::new(rows);
+                table.with(Style::blank());
+                println!("{}", table);
+            }
+            if let Some(factor) = database.query_performance_factor() {
+                println!("\nQuery performance factor: {}", factor);
+            }
+            Ok(())
+        }
+        _ => {
+            let json = serde_json::json!({
+                "modules": modules,
+                "queryPerformanceFactor": database.query_performance_factor(),
+            });
+            let data = handle_output(json, output_format, query)?;
+            print_formatted_output(data, output_format)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn spec(value: Value) -> DatabaseModuleSpec {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn test_parse_query_performance_factor() {
+        assert_eq!(parse_query_performance_factor("4").unwrap(), "4x");
+        assert_eq!(parse_query_performance_factor(" 16X ").unwrap(), "16x");
+        assert!(parse_query_performance_factor("3x").is_err());
+        assert!(parse_query_performance_factor("18x").is_err());
+        assert!(parse_query_performance_factor("fast").is_err());
+    }
+
+    #[test]
+    fn test_merge_modules_keeps_current_and_skips_duplicates() {
+        let current = vec![spec(json!({
+            "name": "RediSearch", "version": "2.8.4", "parameters": {}
+        }))];
+        let (modules, added) = merge_modules(
+            &current,
+            &[
+                CloudModuleArg::Json,
+                CloudModuleArg::Search,
+                CloudModuleArg::Json,
+            ],
+        );
+        let names: Vec<&str> = modules.iter().map(|m| m.name.as_str()).collect();
+        assert_eq!(names, vec!["RediSearch", "RedisJSON"]);
+        assert_eq!(added, vec!["RedisJSON"]);
+        assert_eq!(
+            serde_json::to_value(&modules[0]).unwrap(),
+            json!({"name": "RediSearch", "parameters": {}})
+        );
+    }
+
+    #[test]
+    fn test_check_factor_applies() {
+        let json_only = vec![DatabaseModuleSpec::new("RedisJSON")];
+        assert!(check_factor_applies(&json_only, Some("4x")).is_err());
+        assert!(check_factor_applies(&json_only, None).is_ok());
+        let search = vec![DatabaseModuleSpec::new("redisearch")];
+        assert!(check_factor_applies(&search, Some("4x")).is_ok());
+    }
+}
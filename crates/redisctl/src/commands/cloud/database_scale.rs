@@ -0,0 +1,207 @@
+//! Typed scaling of Pro databases
+//!
+//! `scale` builds the `throughputMeasurement`, `datasetSizeInGb` and `replication` fields of a
+//! database update from flags, shows how they differ from the current values and waits for the
+//! update task.
+
+#![allow(dead_code)]
+
+use super::async_utils::{AsyncOperationArgs, handle_async_response};
+use super::database_impl::parse_database_id;
+use crate::cli::OutputFormat;
+use crate::connection::ConnectionManager;
+use crate::error::Result as CliResult;
+use crate::output::notice;
+use anyhow::Context;
+use redis_cloud::DatabaseHandler;
+use redis_cloud::databases::{
+    DatabaseThroughputSpec, DatabaseUpdateRequest, ThroughputMeasurement,
+};
+use serde_json::{Value, json};
+use tabled::{Table, Tabled, settings::Style};
+
+/// The settings `scale` changes; `None` leaves a setting as it is
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ScaleChange {
+    /// Throughput in operations per second
+    pub ops: Option<u32>,
+    /// Dataset size in bytes
+    pub memory: Option<u64>,
+    pub replication: Option<bool>,
+}
+
+#[derive(Tabled, Debug, PartialEq)]
+struct ScaleRow {
+    #[tabled(rename = "SETTING")]
+    setting: &'static str,
+    #[tabled(rename = "CURRENT")]
+    current: String,
+    #[tabled(rename = "NEW")]
+    new: String,
+}
+
+/// Scale a database and, unless told not to, wait for the update task
+pub async fn scale_database(
+    conn_mgr: &ConnectionManager,
+    profile_name: Option<&str>,
+    id: &str,
+    change: ScaleChange,
+    async_ops: &AsyncOperationArgs,
+    output_format: OutputFormat,
+    query: Option<&str>,
+) -> CliResult<()> {
+    let (subscription_id, database_id) = parse_database_id(id)?;
+
+    let client = conn_mgr.create_cloud_client(profile_name).await?;
+    let handler = DatabaseHandler::new(client);
+    let current = handler
+        .get_subscription_database_by_id(subscription_id as i32, database_id as i32)
+        .await
+        .context("Failed to get database")?;
+
+    let rows = scale_rows(&current.extra, &change);
+    if rows.iter().all(|row| row.current == row.new) {
+        notice!("Database {} already has the requested settings", id);
+        return Ok(());
+    }
+    // The planned change goes to stderr, so stdout carries only the result in any format
+    let mut table = Table::new(&rows);
+    table.with(Style::blank());
+    notice!("{}", table);
+
+    let task = handler
+        .update_database(
+            subscription_id as i32,
+            database_id as i32,
+            &scale_request(&change)?,
+        )
+        .await
+        .context("Failed to scale database")?;
+    let response = serde_json::to_value(task).context("Failed to serialize response")?;
+
+    handle_async_response(
+        conn_mgr,
+        profile_name,
+        response,
+        async_ops,
+        output_format,
+        query,
+        &format!("Database {} scaled", id),
+    )
+    .await
+}
+
+/// The update payload, carrying only the settings being changed
+fn scale_request(change: &ScaleChange) -> CliResult<DatabaseUpdateRequest> {
+    let mut request: DatabaseUpdateRequest =
+        serde_json::from_value(json!({})).context("Failed to build update")?;
+    request.throughput_measurement = change
+        .ops
+        .map(|ops| DatabaseThroughputSpec::operations_per_second(ops.into()));
+    request.dataset_size_in_gb = change.memory.map(bytes_to_gb);
+    request.replication = change.replication;
+    Ok(request)
+}
+
+/// Current and new value of each setting being changed
+fn scale_rows(current: &Value, change: &ScaleChange) -> Vec<ScaleRow> {
+    let mut rows = Vec::new();
+    if let Some(ops) = change.ops {
+        let throughput = current
+            .get("throughputMeasurement")
+            .cloned()
+            .and_then(|t| serde_json::from_value::<DatabaseThroughputSpec>(t).ok());
+        rows.push(ScaleRow {
+            setting: "throughput",
+            current: throughput.map_or_else(|| "-".to_string(), |t| format_throughput(&t)),
+            new: format_throughput(&DatabaseThroughputSpec::operations_per_second(ops.into())),
+        });
+    }
+    if let Some(memory) = change.memory {
+        let size = current
+            .get("datasetSizeInGb")
+            .or_else(|| current.get("memoryLimitInGb"))
+            .and_then(Value::as_f64);
+        rows.push(ScaleRow {
+            setting: "dataset size",
+            current: size.map_or_else(|| "-".to_string(), |gb| format!("{} GB", gb)),
+            new: format!("{} GB", bytes_to_gb(memory)),
+        });
+    }
+    if let Some(replication) = change.replication {
+        rows.push(ScaleRow {
+            setting: "replication",
+            current: current
+                .get("replication")
+                .and_then(Value::as_bool)
+                .map_or_else(|| "-".to_string(), |r| r.to_string()),
+            new: replication.to_string(),
+        });
+    }
+    rows
+}
+
+fn format_throughput(throughput: &DatabaseThroughputSpec) -> String {
+    match throughput.measurement() {
+        Some(ThroughputMeasurement::OperationsPerSecond) => {
+            format!("{} ops/sec", throughput.value)
+        }
+        Some(ThroughputMeasurement::NumberOfShards) => format!("{} shards", throughput.value),
+        None => format!("{} {}", throughput.value, throughput.by),
+    }
+}
+
+fn bytes_to_gb(bytes: u64) -> f64 {
+    bytes as f64 / (1u64 << 30) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scale_request() {
+        let change = ScaleChange {
+            ops: Some(25000),
+            memory: Some(10 << 30),
+            replication: Some(true),
+        };
+        assert_eq!(
+            serde_json::to_value(scale_request(&change).unwrap()).unwrap(),
+            json!({
+                "throughputMeasurement": {"by": "operations-per-second", "value": 25000},
+                "datasetSizeInGb": 10.0,
+                "replication": true
+            })
+        );
+
+        let ops_only = ScaleChange {
+            ops: Some(1000),
+            ..Default::default()
+        };
+        assert_eq!(
+            serde_json::to_value(scale_request(&ops_only).unwrap()).unwrap(),
+            json!({"throughputMeasurement": {"by": "operations-per-second", "value": 1000}})
+        );
+    }
+
+    #[test]
+    fn test_scale_rows() {
+        let current = json!({
+            "throughputMeasurement": {"by": "number-of-shards", "value": 2},
+            "datasetSizeInGb": 2.5,
+            "replication": true
+        });
+        let change = ScaleChange {
+            ops: Some(25000),
+            memory: Some(5 << 30),
+            replication: Some(true),
+        };
+        let rows = scale_rows(&current, &change);
+        assert_eq!(rows[0].current, "2 shards");
+        assert_eq!(rows[0].new, "25000 ops/sec");
+        assert_eq!(rows[1].current, "2.5 GB");
+        assert_eq!(rows[1].new, "5 GB");
+        assert_eq!(rows[2].current, rows[2].new);
+    }
+}
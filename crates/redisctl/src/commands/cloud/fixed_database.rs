@@ -9,6 +9,7 @@ use crate::commands::cloud::utils::{
 };
 use crate::connection::ConnectionManager;
 use crate::error::{RedisCtlError, Result as CliResult};
+use crate::output::notice;
 use anyhow::Context;
 use redis_cloud::fixed::databases::{
     DatabaseTagCreateRequest, DatabaseTagUpdateRequest, FixedDatabaseBackupRequest,
@@ -20,7 +21,7 @@ use redis_cloud::fixed::databases::{
 fn parse_fixed_database_id(id: &str) -> CliResult<(i32, i32)> {
     let parts: Vec<&str> = id.split(':').collect();
     if parts.len() != 2 {
-        return Err(RedisCtlError::InvalidInput {
+        return Err(RedisCtlError::Validation {
             message: format!(
                 "Invalid database ID format: {}. Expected format: subscription_id:database_id",
                 id
@@ -146,7 +147,7 @@ pub async fn handle_fixed_database_command(
             if !yes {
                 let prompt = format!("Delete fixed database {}:{}?", subscription_id, database_id);
                 if !confirm_action(&prompt)? {
-                    eprintln!("Operation cancelled");
+                    notice!("Operation cancelled");
                     return Ok(());
                 }
             }
@@ -397,7 +398,7 @@ pub async fn handle_fixed_database_command(
                 .await
                 .context("Failed to delete tag")?;
 
-            eprintln!("Tag '{}' deleted successfully", key);
+            notice!("Tag '{}' deleted successfully", key);
             Ok(())
         }
     }
@@ -0,0 +1,210 @@
+//! Searching Essentials (fixed) plans by requirements
+//!
+//! The plans endpoint returns every plan for every provider and region, so filtering by
+//! provider, region, dataset size and price happens client-side on the typed plan models.
+
+#![allow(dead_code)]
+
+use clap::Args;
+use redis_cloud::fixed::subscriptions::FixedSubscriptionsPlan;
+use tabled::{Table, Tabled, settings::Style};
+
+use super::utils::output_with_pager;
+use crate::output::notice;
+use crate::units::parse_byte_size;
+
+/// Filters for `list-plans` and `recommend-plans`
+#[derive(Args, Debug, Clone, Default)]
+pub struct PlanFilterArgs {
+    /// Filter by cloud provider (AWS, GCP, Azure)
+    #[arg(long)]
+    pub provider: Option<String>,
+
+    /// Filter by region, e.g. us-east-1
+    #[arg(long)]
+    pub region: Option<String>,
+
+    /// Minimum dataset size, e.g. 250mb or 1gb
+    #[arg(long, value_name = "SIZE", value_parser = parse_byte_size)]
+    pub min_memory: Option<u64>,
+
+    /// Maximum price per billing period
+    #[arg(long, value_name = "PRICE")]
+    pub max_price: Option<f64>,
+}
+
+impl PlanFilterArgs {
+    /// Whether any filter was given
+    pub fn is_empty(&self) -> bool {
+        self.provider.is_none()
+            && self.region.is_none()
+            && self.min_memory.is_none()
+            && self.max_price.is_none()
+    }
+
+    /// Whether a plan satisfies every given filter
+    ///
+    /// A plan that does not report a field a filter needs does not match.
+    pub fn matches(&self, plan: &FixedSubscriptionsPlan) -> bool {
+        let text_matches = |wanted: &Option<String>, actual: &Option<String>| match wanted {
+            Some(wanted) => actual
+                .as_deref()
+                .is_some_and(|actual| actual.eq_ignore_ascii_case(wanted)),
+            None => true,
+        };
+
+        text_matches(&self.provider, &plan.provider)
+            && text_matches(&self.region, &plan.region)
+            && self
+                .min_memory
+                .is_none_or(|min| plan.dataset_size_bytes().is_some_and(|size| size >= min))
+            && self
+                .max_price
+                .is_none_or(|max| plan.price.is_some_and(|price| f64::from(price) <= max))
+    }
+}
+
+/// Plans matching the filters, in the order the API returned them
+pub fn filter_plans(
+    plans: Vec<FixedSubscriptionsPlan>,
+    filter: &PlanFilterArgs,
+) -> Vec<FixedSubscriptionsPlan> {
+    plans
+        .into_iter()
+        .filter(|plan| filter.matches(plan))
+        .collect()
+}
+
+/// Matching plans, cheapest first, at most `limit` of them
+///
+/// Ties on price go to the larger dataset; plans without a price come last.
+pub fn recommend_plans(
+    plans: Vec<FixedSubscriptionsPlan>,
+    filter: &PlanFilterArgs,
+    limit: usize,
+) -> Vec<FixedSubscriptionsPlan> {
+    let mut plans = filter_plans(plans, filter);
+    plans.sort_by(|a, b| {
+        let price = |plan: &FixedSubscriptionsPlan| plan.price.unwrap_or(i32::MAX);
+        price(a)
+            .cmp(&price(b))
+            .then_with(|| b.dataset_size_bytes().cmp(&a.dataset_size_bytes()))
+    });
+    plans.truncate(limit);
+    plans
+}
+
+#[derive(Tabled)]
+struct PlanRow {
+    #[tabled(rename = "ID")]
+    id: String,
+    #[tabled(rename = "NAME")]
+    name: String,
+    #[tabled(rename = "PROVIDER")]
+    provider: String,
+    #[tabled(rename = "REGION")]
+    region: String,
+    #[tabled(rename = "DATASET")]
+    dataset: String,
+    #[tabled(rename = "PRICE")]
+    price: String,
+    #[tabled(rename = "DATABASES")]
+    databases: String,
+}
+
+/// Print plans as a table
+pub fn print_plans_table(plans: &[FixedSubscriptionsPlan]) {
+    if plans.is_empty() {
+        notice!("No plans match the given requirements");
+        return;
+    }
+
+    let dash = || "-".to_string();
+    let rows: Vec<PlanRow> = plans
+        .iter()
+        .map(|plan| PlanRow {
+            id: plan.id.map_or_else(dash, |id| id.to_string()),
+            name: plan.name.clone().unwrap_or_else(dash),
+            provider: plan.provider.clone().unwrap_or_else(dash),
+            region: plan.region.clone().unwrap_or_else(dash),
+            dataset: match (plan.dataset_size.or(plan.size), &plan.size_measurement_unit) {
+                (Some(size), Some(unit)) => format!("{} {}", size, unit),
+                (Some(size), None) => size.to_string(),
+                _ => dash(),
+            },
+            price: plan.price.map_or_else(dash, |price| {
+                let mut text = price.to_string();
+                if let Some(currency) = &plan.price_currency {
+                    text.push(' ');
+                    text.push_str(currency);
+                }
+                if let Some(period) = &plan.price_period {
+                    text.push('/');
+                    text.push_str(period);
+                }
+                text
+            }),
+            databases: plan.maximum_databases.map_or_else(dash, |n| n.to_string()),
+        })
+        .collect();
+
+    let mut table = Table::new(rows);
+    table.with(Style::modern());
+    output_with_pager(&table.to_string());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn plans() -> Vec<FixedSubscriptionsPlan> {
+        serde_json::from_value(json!([
+            {"id": 1, "name": "30MB", "size": 30, "sizeMeasurementUnit": "MB",
+             "provider": "AWS", "region": "us-east-1", "price": 0},
+            {"id": 2, "name": "1GB", "size": 1, "sizeMeasurementUnit": "GB",
+             "provider": "AWS", "region": "us-east-1", "price": 22},
+            {"id": 3, "name": "2.5GB", "size": 2.5, "sizeMeasurementUnit": "GB",
+             "provider": "AWS", "region": "us-east-1", "price": 22},
+            {"id": 4, "name": "5GB", "size": 5, "sizeMeasurementUnit": "GB",
+             "provider": "AWS", "region": "us-east-1", "price": 77},
+            {"id": 5, "name": "1GB GCP", "size": 1, "sizeMeasurementUnit": "GB",
+             "provider": "GCP", "region": "us-central1", "price": 20},
+            {"id": 6, "name": "1GB unpriced", "size": 1, "sizeMeasurementUnit": "GB",
+             "provider": "AWS", "region": "us-east-1"}
+        ]))
+        .unwrap()
+    }
+
+    fn ids(plans: &[FixedSubscriptionsPlan]) -> Vec<i32> {
+        plans.iter().filter_map(|plan| plan.id).collect()
+    }
+
+    #[test]
+    fn test_filter_plans() {
+        let filter = PlanFilterArgs {
+            provider: Some("aws".to_string()),
+            region: Some("us-east-1".to_string()),
+            min_memory: Some(1 << 30),
+            max_price: Some(50.0),
+        };
+        assert_eq!(ids(&filter_plans(plans(), &filter)), vec![2, 3]);
+
+        let unfiltered = PlanFilterArgs::default();
+        assert!(unfiltered.is_empty());
+        assert_eq!(filter_plans(plans(), &unfiltered).len(), 6);
+    }
+
+    #[test]
+    fn test_recommend_plans() {
+        let filter = PlanFilterArgs {
+            min_memory: Some(1 << 30),
+            ..Default::default()
+        };
+        assert_eq!(
+            ids(&recommend_plans(plans(), &filter, 10)),
+            vec![5, 3, 2, 4, 6]
+        );
+        assert_eq!(ids(&recommend_plans(plans(), &filter, 2)), vec![5, 3]);
+    }
+}
@@ -4,11 +4,13 @@
 
 use crate::cli::{CloudFixedSubscriptionCommands, OutputFormat};
 use crate::commands::cloud::async_utils::handle_async_response;
+use crate::commands::cloud::fixed_plans::{filter_plans, print_plans_table, recommend_plans};
 use crate::commands::cloud::utils::{
     confirm_action, handle_output, print_formatted_output, read_file_input,
 };
 use crate::connection::ConnectionManager;
 use crate::error::Result as CliResult;
+use crate::output::notice;
 use anyhow::Context;
 use redis_cloud::fixed::subscriptions::{
     FixedSubscriptionCreateRequest, FixedSubscriptionHandler, FixedSubscriptionUpdateRequest,
@@ -30,38 +32,48 @@ pub async fn handle_fixed_subscription_command(
     let handler = FixedSubscriptionHandler::new(client);
 
     match command {
-        CloudFixedSubscriptionCommands::ListPlans { provider } => {
-            let plans = if let Some(provider_filter) = provider {
-                // If provider specified, fetch all plans and filter
-                let all_plans = handler
-                    .list_plans(None, None)
-                    .await
-                    .context("Failed to list fixed subscription plans")?;
-
-                // Convert to JSON for filtering
-                let mut json_plans = serde_json::to_value(all_plans)?;
-
-                // Filter by provider if the structure supports it
-                if let Some(plans_array) = json_plans.as_array_mut() {
-                    plans_array.retain(|plan| {
-                        plan.get("cloudProvider")
-                            .and_then(|p| p.as_str())
-                            .map(|p| p.eq_ignore_ascii_case(provider_filter))
-                            .unwrap_or(false)
-                    });
-                }
+        CloudFixedSubscriptionCommands::ListPlans { filter } => {
+            let response = handler
+                .list_plans(None, None)
+                .await
+                .context("Failed to list fixed subscription plans")?;
+            let plans = filter_plans(response.plans().context("Failed to parse plans")?, filter);
 
-                json_plans
+            if matches!(output_format, OutputFormat::Auto | OutputFormat::Table) && query.is_none()
+            {
+                print_plans_table(&plans);
+                return Ok(());
+            }
+            let json_response = if filter.is_empty() {
+                serde_json::to_value(response)
             } else {
-                // No filter, get all plans
-                let plans = handler
-                    .list_plans(None, None)
-                    .await
-                    .context("Failed to list fixed subscription plans")?;
-                serde_json::to_value(plans)?
-            };
-
-            let data = handle_output(plans, output_format, query)?;
+                serde_json::to_value(plans)
+            }
+            .context("Failed to serialize response")?;
+            let data = handle_output(json_response, output_format, query)?;
+            print_formatted_output(data, output_format)?;
+            Ok(())
+        }
+
+        CloudFixedSubscriptionCommands::RecommendPlans { filter, limit } => {
+            let response = handler
+                .list_plans(None, None)
+                .await
+                .context("Failed to list fixed subscription plans")?;
+            let plans = recommend_plans(
+                response.plans().context("Failed to parse plans")?,
+                filter,
+                *limit,
+            );
+
+            if matches!(output_format, OutputFormat::Auto | OutputFormat::Table) && query.is_none()
+            {
+                print_plans_table(&plans);
+                return Ok(());
+            }
+            let json_response =
+                serde_json::to_value(plans).context("Failed to serialize response")?;
+            let data = handle_output(json_response, output_format, query)?;
             print_formatted_output(data, output_format)?;
             Ok(())
         }
@@ -176,7 +188,7 @@ pub async fn handle_fixed_subscription_command(
             if !yes {
                 let prompt = format!("Delete fixed subscription {}?", id);
                 if !confirm_action(&prompt)? {
-                    eprintln!("Operation cancelled");
+                    notice!("Operation cancelled");
                     return Ok(());
                 }
             }
@@ -0,0 +1,186 @@
+//! Create-if-absent support for Cloud create commands
+//!
+//! Re-running automation after a timeout must not create a second subscription or database.
+//! With `--if-absent name=<value>`, a create command first looks for an existing resource with
+//! that name and reports it instead of creating a new one.
+
+#![allow(dead_code)]
+
+use super::utils::*;
+use crate::cli::OutputFormat;
+use crate::error::{RedisCtlError, Result as CliResult};
+use crate::output::notice;
+use serde_json::{Value, json};
+
+/// The name from an `--if-absent name=<value>` spec, reconciled with the request payload
+///
+/// A payload without a name gets the value; a payload with a different name is rejected, since
+/// the lookup and the create would then disagree about which resource is meant.
+pub fn resolve_name(spec: &str, request: &mut Value, kind: &str) -> CliResult<String> {
+    let name = match spec.split_once('=') {
+        Some(("name", value)) if !value.trim().is_empty() => value.trim().to_string(),
+        _ => {
+            return Err(RedisCtlError::Validation {
+                message: format!(
+                    "Invalid --if-absent '{}': expected name=<{} name>",
+                    spec, kind
+                ),
+            });
+        }
+    };
+
+    let object = request
+        .as_object_mut()
+        .ok_or_else(|| RedisCtlError::Validation {
+            message: "--if-absent requires --data to be a JSON object".to_string(),
+        })?;
+    match object.get("name").and_then(Value::as_str) {
+        Some(existing) if existing != name => Err(RedisCtlError::Validation {
+            message: format!(
+                "--if-absent name '{}' does not match the {} name '{}' in --data",
+                name, kind, existing
+            ),
+        }),
+        Some(_) => Ok(name),
+        None => {
+            object.insert("name".to_string(), json!(name));
+            Ok(name)
+        }
+    }
+}
+
+/// The single resource with this name, ignoring resources that are being deleted
+///
+/// More than one match is an error: returning either could hand automation the wrong resource.
+pub fn find_by_name<'a>(
+    resources: &'a [Value],
+    name: &str,
+    kind: &str,
+) -> CliResult<Option<&'a Value>> {
+    let matches: Vec<&Value> = resources
+        .iter()
+        .filter(|r| r.get("name").and_then(Value::as_str) == Some(name))
+        .filter(|r| r.get("status").and_then(Value::as_str) != Some("deleting"))
+        .collect();
+    match matches.as_slice() {
+        [] => Ok(None),
+        [resource] => Ok(Some(resource)),
+        _ => Err(RedisCtlError::Validation {
+            message: format!(
+                "{} {}s are named '{}' (ids {}); refusing to pick one",
+                matches.len(),
+                kind,
+                name,
+                matches
+                    .iter()
+                    .map(|r| resource_id(r))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        }),
+    }
+}
+
+fn resource_id(resource: &Value) -> String {
+    resource
+        .get("databaseId")
+        .or_else(|| resource.get("id"))
+        .map(id_string)
+        .unwrap_or_else(|| "?".to_string())
+}
+
+fn id_string(id: &Value) -> String {
+    match id {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// Report an existing resource in place of a create
+///
+/// Structured output is `{"outcome": "found", "id": .., "resource": ..}` so scripts can tell it
+/// apart from the task a create returns.
+pub fn print_found(
+    kind: &str,
+    id: Value,
+    resource: &Value,
+    output_format: OutputFormat,
+    query: Option<&str>,
+) -> CliResult<()> {
+    let name = extract_field(resource, "name", "");
+    match output_format {
+        OutputFormat::Auto | OutputFormat::Table if query.is_none() => {
+            notice!(
+                "Found existing {} '{}' (id {}); nothing created",
+                kind,
+                name,
+                id_string(&id)
+            );
+            if let Some(status) = resource.get("status").and_then(Value::as_str) {
+                notice!("Status: {}", status);
+            }
+        }
+        _ => {
+            let result = json!({
+                "outcome": "found",
+                "id": id,
+                "resource": resource,
+            });
+            let data = handle_output(result, output_format, query)?;
+            print_formatted_output(data, output_format)?;
+        }
+    }
+    Ok(())
+}
+
+/// Note on stderr that the lookup found nothing, before the create output
+pub fn note_creating(kind: &str, name: &str) {
+    notice!("No {} named '{}' found; creating it", kind, name);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_name() {
+        let mut request = json!({"memoryLimitInGb": 1});
+        assert_eq!(
+            resolve_name("name=cache", &mut request, "database").unwrap(),
+            "cache"
+        );
+        assert_eq!(request["name"], "cache");
+
+        let mut request = json!({"name": "cache"});
+        assert!(resolve_name("name=cache", &mut request, "database").is_ok());
+        assert!(resolve_name("name=other", &mut request, "database").is_err());
+        assert!(resolve_name("cache", &mut request, "database").is_err());
+        assert!(resolve_name("id=5", &mut request, "database").is_err());
+        assert!(resolve_name("name=", &mut request, "database").is_err());
+        assert!(resolve_name("name=cache", &mut json!([]), "database").is_err());
+    }
+
+    #[test]
+    fn test_find_by_name() {
+        let resources = vec![
+            json!({"id": 1, "name": "prod", "status": "active"}),
+            json!({"id": 2, "name": "old", "status": "deleting"}),
+            json!({"id": 3, "name": "dup"}),
+            json!({"id": 4, "name": "dup"}),
+        ];
+        assert_eq!(
+            find_by_name(&resources, "prod", "subscription").unwrap(),
+            Some(&resources[0])
+        );
+        assert_eq!(
+            find_by_name(&resources, "old", "subscription").unwrap(),
+            None
+        );
+        assert_eq!(
+            find_by_name(&resources, "new", "subscription").unwrap(),
+            None
+        );
+        let err = find_by_name(&resources, "dup", "subscription").unwrap_err();
+        assert!(err.to_string().contains("ids 3, 4"));
+    }
+}
@@ -0,0 +1,657 @@
+//! Forwarding account system and session logs to syslog
+//!
+//! Redis Cloud offers no log delivery settings, so `cloud logs forward` reads the logs through
+//! the API and sends each entry as an RFC 5424 message whose MSG is the entry as JSON. Pages are
+//! read newest first until entries older than the cursor appear. When following, each poll
+//! looks back [`LOOKBACK_SECS`] behind the newest forwarded entry, so entries that show up late
+//! are still sent, and a set of forwarded entries keeps them from being sent twice. Entries
+//! whose delivery fails are retried on the next poll.
+
+#![allow(dead_code)]
+
+use super::account::parse_time_bound;
+use crate::cli::{CloudLogSource, CloudLogsCommands};
+use crate::connection::ConnectionManager;
+use crate::error::{RedisCtlError, Result as CliResult};
+use crate::output::notice;
+use anyhow::Context;
+use chrono::{DateTime, Duration as ChronoDuration, SecondsFormat, Utc};
+use redis_cloud::AccountHandler;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::net::{Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::time::Duration;
+use tokio::io::AsyncWriteExt;
+use tokio::net::{TcpStream, UdpSocket};
+
+/// Entries requested per page
+const PAGE_SIZE: i32 = 100;
+
+/// How far behind the newest forwarded entry each poll looks for late entries
+const LOOKBACK_SECS: i64 = 300;
+
+/// Port used when the syslog URL has none
+const DEFAULT_SYSLOG_PORT: u16 = 514;
+
+/// `log audit` facility
+const FACILITY: u8 = 13;
+
+/// `informational` severity
+const SEVERITY: u8 = 6;
+
+pub async fn handle_logs_command(
+    conn_mgr: &ConnectionManager,
+    profile_name: Option<&str>,
+    command: &CloudLogsCommands,
+) -> CliResult<()> {
+    match command {
+        CloudLogsCommands::Forward {
+            syslog,
+            stdout: _,
+            sources,
+            follow,
+            interval,
+            since,
+            hostname,
+            app_name,
+        } => {
+            let sink = match syslog {
+                Some(url) => Sink::Syslog(SyslogTarget::parse(url)?),
+                None => Sink::Stdout,
+            };
+            let header = Header::new(hostname, app_name)?;
+            let start = match since {
+                Some(since) => Some(parse_time_bound(since, Utc::now(), false)?),
+                None => follow.then(Utc::now),
+            };
+            forward_logs(
+                conn_mgr,
+                profile_name,
+                sink,
+                &header,
+                sources,
+                start,
+                follow.then_some(*interval),
+            )
+            .await
+        }
+    }
+}
+
+/// Transport and address of a syslog collector
+#[derive(Debug, Clone, PartialEq)]
+pub enum SyslogTarget {
+    Tcp { host: String, port: u16 },
+    Udp { host: String, port: u16 },
+}
+
+impl SyslogTarget {
+    /// Parse `tcp://HOST[:PORT]` or `udp://HOST[:PORT]`
+    pub fn parse(value: &str) -> CliResult<Self> {
+        let invalid = |reason: &str| RedisCtlError::Validation {
+            message: format!(
+                "Invalid syslog URL '{}': {} (expected tcp://HOST:PORT or udp://HOST:PORT)",
+                value, reason
+            ),
+        };
+        let url = reqwest::Url::parse(value).map_err(|e| invalid(&e.to_string()))?;
+        let host = url
+            .host_str()
+            .filter(|host| !host.is_empty())
+            .ok_or_else(|| invalid("no host"))?
+            .trim_start_matches('[')
+            .trim_end_matches(']')
+            .to_string();
+        let port = url.port().unwrap_or(DEFAULT_SYSLOG_PORT);
+        match url.scheme() {
+            "tcp" => Ok(SyslogTarget::Tcp { host, port }),
+            "udp" => Ok(SyslogTarget::Udp { host, port }),
+            scheme => Err(invalid(&format!("unsupported scheme '{}'", scheme))),
+        }
+    }
+}
+
+impl std::fmt::Display for SyslogTarget {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (scheme, host, port) = match self {
+            SyslogTarget::Tcp { host, port } => ("tcp", host, port),
+            SyslogTarget::Udp { host, port } => ("udp", host, port),
+        };
+        if host.contains(':') {
+            write!(f, "{}://[{}]:{}", scheme, host, port)
+        } else {
+            write!(f, "{}://{}:{}", scheme, host, port)
+        }
+    }
+}
+
+/// Where messages are sent
+#[derive(Debug, Clone, PartialEq)]
+pub enum Sink {
+    Syslog(SyslogTarget),
+    /// Print the messages instead of sending them
+    Stdout,
+}
+
+/// HOSTNAME and APP-NAME fields, checked against RFC 5424's limits
+#[derive(Debug, Clone, PartialEq)]
+pub struct Header {
+    hostname: String,
+    app_name: String,
+}
+
+impl Header {
+    pub fn new(hostname: &str, app_name: &str) -> CliResult<Self> {
+        let check = |flag: &str, value: &str, max: usize| {
+            let printable = value.bytes().all(|b| (33..=126).contains(&b));
+            if value.is_empty() || value.len() > max || !printable {
+                return Err(RedisCtlError::Validation {
+                    message: format!(
+                        "--{} must be 1 to {} printable ASCII characters without spaces",
+                        flag, max
+                    ),
+                });
+            }
+            Ok(value.to_string())
+        };
+        Ok(Self {
+            hostname: check("hostname", hostname, 255)?,
+            app_name: check("app-name", app_name, 48)?,
+        })
+    }
+}
+
+/// One log entry with what forwarding needs to know about it
+#[derive(Debug, Clone, PartialEq)]
+pub struct LogRecord {
+    pub source: CloudLogSource,
+    /// Identity of the entry, for not sending it twice
+    pub key: String,
+    pub time: Option<DateTime<Utc>>,
+    pub entry: Value,
+}
+
+impl LogRecord {
+    pub fn new(source: CloudLogSource, entry: Value) -> Self {
+        let key = match entry.get("id") {
+            Some(Value::String(id)) => format!("{}:{}", source_name(source), id),
+            Some(id) if !id.is_null() => format!("{}:{}", source_name(source), id),
+            _ => format!("{}:{}", source_name(source), entry),
+        };
+        let time = entry
+            .get("time")
+            .and_then(Value::as_str)
+            .and_then(|t| DateTime::parse_from_rfc3339(t).ok())
+            .map(|t| t.with_timezone(&Utc));
+        Self {
+            source,
+            key,
+            time,
+            entry,
+        }
+    }
+}
+
+fn source_name(source: CloudLogSource) -> &'static str {
+    match source {
+        CloudLogSource::System => "system",
+        CloudLogSource::Session => "session",
+    }
+}
+
+/// Which entries have been forwarded, and from when on entries are wanted
+#[derive(Debug, Clone, Default)]
+pub struct Cursor {
+    /// Lower bound from --since, or the start of following
+    start: Option<DateTime<Utc>>,
+    /// Time of the newest forwarded entry
+    watermark: Option<DateTime<Utc>>,
+    /// Forwarded entries within the lookback window, by key, with the time they are pruned by
+    forwarded: HashMap<String, Option<DateTime<Utc>>>,
+}
+
+impl Cursor {
+    pub fn new(start: Option<DateTime<Utc>>) -> Self {
+        Self {
+            start,
+            ..Default::default()
+        }
+    }
+
+    /// Oldest entry time still of interest
+    pub fn floor(&self) -> Option<DateTime<Utc>> {
+        let lookback = self
+            .watermark
+            .map(|w| w - ChronoDuration::seconds(LOOKBACK_SECS));
+        self.start.max(lookback)
+    }
+
+    /// Entries not forwarded yet and not older than the floor, oldest first
+    pub fn select(&self, records: Vec<LogRecord>) -> Vec<LogRecord> {
+        let floor = self.floor();
+        let mut selected: Vec<LogRecord> = records
+            .into_iter()
+            .filter(|record| !self.forwarded.contains_key(&record.key))
+            .filter(|record| match (record.time, floor) {
+                (Some(time), Some(floor)) => time >= floor,
+                _ => true,
+            })
+            .collect();
+        selected.sort_by_key(|record| record.time);
+        selected.dedup_by(|a, b| a.key == b.key);
+        selected
+    }
+
+    /// Record that `record` was delivered
+    ///
+    /// An entry without a time is pruned as if it had the newest time forwarded before it, or the
+    /// start time, so it leaves the lookback window once later entries arrive.
+    pub fn commit(&mut self, record: &LogRecord) {
+        let prune_time = record.time.or(self.watermark).or(self.start);
+        self.forwarded.insert(record.key.clone(), prune_time);
+        self.watermark = self.watermark.max(record.time);
+    }
+
+    /// Forget forwarded entries that fell out of the lookback window
+    pub fn prune(&mut self) {
+        let floor = self.floor();
+        self.forwarded
+            .retain(|_, time| time.is_none_or(|time| floor.is_none_or(|floor| time >= floor)));
+    }
+}
+
+/// The RFC 5424 message for `record`
+pub fn format_message(header: &Header, record: &LogRecord) -> String {
+    let timestamp = record
+        .time
+        .map(|t| t.to_rfc3339_opts(SecondsFormat::Millis, true))
+        .unwrap_or_else(|| "-".to_string());
+    format!(
+        "<{}>1 {} {} {} - {} [origin software=\"redisctl\" swVersion=\"{}\"] {}",
+        FACILITY * 8 + SEVERITY,
+        timestamp,
+        header.hostname,
+        header.app_name,
+        source_name(record.source),
+        escape_param(env!("CARGO_PKG_VERSION")),
+        record.entry
+    )
+}
+
+/// Escape `"`, `\` and `]` in a structured data parameter value
+fn escape_param(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        if matches!(c, '"' | '\\' | ']') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Whether paging can stop: the page is newest first and reaches past `floor`
+///
+/// A page in ascending order means the API lists oldest first, so every page must be read.
+fn reached_floor(page: &[LogRecord], floor: Option<DateTime<Utc>>) -> bool {
+    let (Some(floor), Some(first), Some(last)) = (
+        floor,
+        page.first().and_then(|r| r.time),
+        page.last().and_then(|r| r.time),
+    ) else {
+        return false;
+    };
+    first >= last && last < floor
+}
+
+/// Entries of `source` back to `floor`, or all of them without one
+async fn fetch_records(
+    handler: &AccountHandler,
+    source: CloudLogSource,
+    floor: Option<DateTime<Utc>>,
+) -> CliResult<Vec<LogRecord>> {
+    let mut records = Vec::new();
+    let mut offset = 0;
+    loop {
+        let page: Vec<Value> = match source {
+            CloudLogSource::System => handler
+                .get_account_system_logs(Some(offset), Some(PAGE_SIZE))
+                .await
+                .context("Failed to fetch system logs")?
+                .entries
+                .unwrap_or_default()
+                .into_iter()
+                .map(serde_json::to_value)
+                .collect::<Result<_, _>>()?,
+            CloudLogSource::Session => handler
+                .get_account_session_logs(Some(offset), Some(PAGE_SIZE))
+                .await
+                .context("Failed to fetch session logs")?
+                .entries
+                .unwrap_or_default()
+                .into_iter()
+                .map(serde_json::to_value)
+                .collect::<Result<_, _>>()?,
+        };
+        let count = page.len() as i32;
+        let page: Vec<LogRecord> = page
+            .into_iter()
+            .map(|entry| LogRecord::new(source, entry))
+            .collect();
+        let done = count < PAGE_SIZE || reached_floor(&page, floor);
+        records.extend(page);
+        if done {
+            return Ok(records);
+        }
+        offset += count;
+    }
+}
+
+/// Open connection or socket to the collector
+enum Connection {
+    Tcp(TcpStream),
+    Udp(UdpSocket),
+}
+
+impl Connection {
+    async fn open(target: &SyslogTarget) -> std::io::Result<Self> {
+        match target {
+            SyslogTarget::Tcp { host, port } => Ok(Connection::Tcp(
+                TcpStream::connect((host.as_str(), *port)).await?,
+            )),
+            SyslogTarget::Udp { host, port } => {
+                // Resolve first and bind in the target's family; an IPv4 socket cannot send to
+                // an IPv6 address and vice versa
+                let address = tokio::net::lookup_host((host.as_str(), *port))
+                    .await?
+                    .next()
+                    .ok_or_else(|| {
+                        std::io::Error::new(
+                            std::io::ErrorKind::NotFound,
+                            format!("no addresses found for {}", host),
+                        )
+                    })?;
+                let bind: SocketAddr = match address {
+                    SocketAddr::V4(_) => (Ipv4Addr::UNSPECIFIED, 0).into(),
+                    SocketAddr::V6(_) => (Ipv6Addr::UNSPECIFIED, 0).into(),
+                };
+                let socket = UdpSocket::bind(bind).await?;
+                socket.connect(address).await?;
+                Ok(Connection::Udp(socket))
+            }
+        }
+    }
+
+    /// Send one message: octet-counted over TCP (RFC 6587), one datagram over UDP (RFC 5426)
+    async fn send(&mut self, message: &str) -> std::io::Result<()> {
+        match self {
+            Connection::Tcp(stream) => {
+                let framed = format!("{} {}", message.len(), message);
+                stream.write_all(framed.as_bytes()).await
+            }
+            Connection::Udp(socket) => socket.send(message.as_bytes()).await.map(|_| ()),
+        }
+    }
+}
+
+/// Delivers messages, reconnecting once when the connection has dropped
+struct Sender {
+    sink: Sink,
+    connection: Option<Connection>,
+}
+
+impl Sender {
+    async fn send(&mut self, message: &str) -> CliResult<()> {
+        let Sink::Syslog(target) = &self.sink else {
+            println!("{}", message);
+            return Ok(());
+        };
+        let mut last_error = None;
+        for _ in 0..2 {
+            let connection = match self.connection.as_mut() {
+                Some(connection) => connection,
+                None => match Connection::open(target).await {
+                    Ok(connection) => self.connection.insert(connection),
+                    Err(e) => {
+                        last_error = Some(e);
+                        continue;
+                    }
+                },
+            };
+            match connection.send(message).await {
+                Ok(()) => return Ok(()),
+                Err(e) => {
+                    self.connection = None;
+                    last_error = Some(e);
+                }
+            }
+        }
+        Err(RedisCtlError::network(format!(
+            "Failed to send to syslog collector {}: {}",
+            target,
+            last_error.map(|e| e.to_string()).unwrap_or_default()
+        )))
+    }
+}
+
+async fn forward_logs(
+    conn_mgr: &ConnectionManager,
+    profile_name: Option<&str>,
+    sink: Sink,
+    header: &Header,
+    sources: &[CloudLogSource],
+    start: Option<DateTime<Utc>>,
+    follow_interval: Option<u64>,
+) -> CliResult<()> {
+    if follow_interval == Some(0) {
+        return Err(RedisCtlError::Validation {
+            message: "--interval must be at least 1s".to_string(),
+        });
+    }
+    let client = conn_mgr.create_cloud_client(profile_name).await?;
+    let handler = AccountHandler::new(client);
+    let mut sender = Sender {
+        sink: sink.clone(),
+        connection: None,
+    };
+    let mut cursor = Cursor::new(start);
+    let mut sources = sources.to_vec();
+    sources.dedup();
+
+    let mut ticker = tokio::time::interval(Duration::from_secs(follow_interval.unwrap_or(1)));
+    ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    let mut total = 0;
+    loop {
+        tokio::select! {
+            _ = ticker.tick() => {}
+            _ = tokio::signal::ctrl_c() => break,
+        }
+
+        let mut records = Vec::new();
+        for source in &sources {
+            match fetch_records(&handler, *source, cursor.floor()).await {
+                Ok(fetched) => records.extend(fetched),
+                Err(e) if follow_interval.is_some() => {
+                    notice!("Warning: {}", e);
+                }
+                Err(e) => return Err(e),
+            }
+        }
+
+        let mut forwarded = 0;
+        for record in cursor.select(records) {
+            match sender.send(&format_message(header, &record)).await {
+                Ok(()) => {
+                    cursor.commit(&record);
+                    forwarded += 1;
+                }
+                Err(e) if follow_interval.is_some() => {
+                    notice!("Warning: {}; retrying on the next poll", e);
+                    break;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+        cursor.prune();
+        total += forwarded;
+
+        if follow_interval.is_none() {
+            break;
+        }
+        if forwarded > 0 && sink != Sink::Stdout {
+            notice!("Forwarded {} entries", forwarded);
+        }
+    }
+
+    if let Sink::Syslog(target) = &sink {
+        notice!("Forwarded {} entries to {}", total, target);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn record(source: CloudLogSource, id: u32, time: &str) -> LogRecord {
+        LogRecord::new(source, json!({"id": id, "time": time, "type": "Database"}))
+    }
+
+    fn at(time: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(time).unwrap().to_utc()
+    }
+
+    #[test]
+    fn test_parse_syslog_target() {
+        assert_eq!(
+            SyslogTarget::parse("tcp://collector:6514").unwrap(),
+            SyslogTarget::Tcp {
+                host: "collector".to_string(),
+                port: 6514
+            }
+        );
+        let udp = SyslogTarget::parse("udp://[::1]").unwrap();
+        assert_eq!(
+            udp,
+            SyslogTarget::Udp {
+                host: "::1".to_string(),
+                port: 514
+            }
+        );
+        assert_eq!(udp.to_string(), "udp://[::1]:514");
+        assert!(SyslogTarget::parse("http://collector:514").is_err());
+        assert!(SyslogTarget::parse("collector:514").is_err());
+    }
+
+    #[test]
+    fn test_header_limits() {
+        assert!(Header::new("redis-cloud", "redisctl").is_ok());
+        assert!(Header::new("redis cloud", "redisctl").is_err());
+        assert!(Header::new("redis-cloud", &"a".repeat(49)).is_err());
+    }
+
+    #[test]
+    fn test_format_message() {
+        let header = Header::new("redis-cloud", "redisctl").unwrap();
+        let record = LogRecord::new(
+            CloudLogSource::Session,
+            json!({"id": "abc", "time": "2024-03-10T11:00:00Z", "action": "login"}),
+        );
+        assert_eq!(record.key, "session:abc");
+        let message = format_message(&header, &record);
+        assert!(
+            message.starts_with(
+                "<110>1 2024-03-10T11:00:00.000Z redis-cloud redisctl - session [origin \
+                 software=\"redisctl\" swVersion=\""
+            ),
+            "{}",
+            message
+        );
+        assert!(
+            message.ends_with(r#"] {"action":"login","id":"abc","time":"2024-03-10T11:00:00Z"}"#)
+        );
+        assert_eq!(escape_param(r#"a"b]\"#), r#"a\"b\]\\"#);
+    }
+
+    #[test]
+    fn test_cursor_forwards_each_entry_once() {
+        use CloudLogSource::System;
+        let mut cursor = Cursor::new(Some(at("2024-03-10T10:00:00Z")));
+        let poll = vec![
+            record(System, 3, "2024-03-10T10:02:00Z"),
+            record(System, 2, "2024-03-10T10:01:00Z"),
+            record(System, 1, "2024-03-10T09:59:00Z"),
+        ];
+        let selected = cursor.select(poll.clone());
+        let ids: Vec<&str> = selected.iter().map(|r| r.key.as_str()).collect();
+        assert_eq!(ids, vec!["system:2", "system:3"]);
+        for record in &selected {
+            cursor.commit(record);
+        }
+        cursor.prune();
+
+        // A late entry inside the lookback window is sent; earlier ones are not repeated
+        let mut next = poll;
+        next.insert(0, record(System, 4, "2024-03-10T10:01:30Z"));
+        let selected = cursor.select(next);
+        let ids: Vec<&str> = selected.iter().map(|r| r.key.as_str()).collect();
+        assert_eq!(ids, vec!["system:4"]);
+    }
+
+    #[test]
+    fn test_cursor_floor_and_prune() {
+        let mut cursor = Cursor::new(None);
+        assert_eq!(cursor.floor(), None);
+        cursor.commit(&record(CloudLogSource::Session, 1, "2024-03-10T10:00:00Z"));
+        cursor.commit(&record(CloudLogSource::Session, 2, "2024-03-10T11:00:00Z"));
+        assert_eq!(cursor.floor(), Some(at("2024-03-10T10:55:00Z")));
+        cursor.prune();
+        assert_eq!(cursor.forwarded.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_udp_connection_resolves_the_target() {
+        let collector = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let target = SyslogTarget::Udp {
+            host: "127.0.0.1".to_string(),
+            port: collector.local_addr().unwrap().port(),
+        };
+        let mut connection = Connection::open(&target).await.unwrap();
+        connection.send("<134>1 - - - - - hello").await.unwrap();
+        let mut buf = [0u8; 64];
+        let len = collector.recv(&mut buf).await.unwrap();
+        assert_eq!(&buf[..len], b"<134>1 - - - - - hello");
+    }
+
+    #[test]
+    fn test_cursor_prunes_entries_without_time() {
+        use CloudLogSource::System;
+        let mut cursor = Cursor::new(Some(at("2024-03-10T10:00:00Z")));
+        let untimed = LogRecord::new(System, json!({"id": 9, "action": "login"}));
+        assert_eq!(untimed.time, None);
+        cursor.commit(&untimed);
+        cursor.commit(&record(System, 1, "2024-03-10T10:01:00Z"));
+        cursor.prune();
+        assert!(cursor.forwarded.contains_key(&untimed.key));
+
+        cursor.commit(&record(System, 2, "2024-03-10T11:00:00Z"));
+        cursor.prune();
+        assert!(!cursor.forwarded.contains_key(&untimed.key));
+        assert_eq!(cursor.forwarded.len(), 1);
+    }
+
+    #[test]
+    fn test_reached_floor() {
+        use CloudLogSource::System;
+        let floor = Some(at("2024-03-10T10:00:00Z"));
+        let newest_first = vec![
+            record(System, 2, "2024-03-10T10:05:00Z"),
+            record(System, 1, "2024-03-10T09:55:00Z"),
+        ];
+        assert!(reached_floor(&newest_first, floor));
+        assert!(!reached_floor(&newest_first, None));
+        let oldest_first: Vec<LogRecord> = newest_first.into_iter().rev().collect();
+        assert!(!reached_floor(&oldest_first, floor));
+    }
+}
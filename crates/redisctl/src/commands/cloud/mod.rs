@@ -1,28 +1,47 @@
 //! Cloud command implementations
 //!
 //! This module contains all cloud-specific command handlers organized into submodules:
+//! - `access_report`: Consolidated user access report
 //! - `account`: Account management commands
+//! - `logs`: Forwarding account logs to syslog
 //! - `subscription`: Subscription management commands
 //! - `user`: User management commands
 //! - `database`: Database management commands
 //! - `utils`: Shared utilities and helper functions
+//! - `whoami`: Account and API key summary
 
+pub mod access_report;
 pub mod account;
 pub mod acl;
+pub mod acl_graph;
 pub mod acl_impl;
 pub mod async_utils;
 pub mod cloud_account;
 pub mod cloud_account_impl;
 pub mod connectivity;
+pub mod crdb;
 pub mod database;
+pub mod database_backup;
+pub mod database_connection;
+pub mod database_expiry;
 pub mod database_impl;
+pub mod database_module;
+pub mod database_replica;
+pub mod database_scale;
 pub mod fixed_database;
+pub mod fixed_plans;
 pub mod fixed_subscription;
+pub mod if_absent;
+pub mod logs;
 pub mod subscription;
+pub mod subscription_cidr;
+pub mod subscription_cmk;
 pub mod subscription_impl;
 pub mod task;
+pub mod task_notify;
 pub mod user;
 pub mod utils;
+pub mod whoami;
 
 // Re-export all handler functions for backward compatibility
 #[allow(unused_imports)]
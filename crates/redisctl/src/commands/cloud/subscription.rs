@@ -7,8 +7,10 @@ use serde_json::Value;
 use tabled::{Table, Tabled, settings::Style};
 
 use crate::cli::{CloudSubscriptionCommands, OutputFormat};
+use crate::commands::examples::{ExampleKind, print_example};
 use crate::connection::ConnectionManager;
 use crate::error::Result as CliResult;
+use crate::output::notice;
 
 use super::subscription_impl;
 use super::utils::*;
@@ -49,27 +51,83 @@ pub async fn handle_subscription_command(
         CloudSubscriptionCommands::Get { id } => {
             get_subscription(conn_mgr, profile_name, *id, output_format, query).await
         }
-        CloudSubscriptionCommands::Create { data, async_ops } => {
+        CloudSubscriptionCommands::Create {
+            data,
+            example,
+            dry_run,
+            if_absent,
+            async_ops,
+        } => {
+            let Some(data) = data.as_deref().filter(|_| !*example) else {
+                return print_example(ExampleKind::CloudSubscription, output_format);
+            };
             subscription_impl::create_subscription(
                 conn_mgr,
                 profile_name,
                 data,
+                *dry_run,
+                if_absent.as_deref(),
                 async_ops,
                 output_format,
                 query,
             )
             .await
         }
+        CloudSubscriptionCommands::PlanCidr {
+            existing,
+            regions,
+            validate,
+        } => super::subscription_cidr::plan_cidr(
+            existing,
+            usize::from(*regions),
+            validate.as_deref(),
+            output_format,
+            query,
+        ),
         CloudSubscriptionCommands::Update {
             id,
             data,
+            patch,
             async_ops,
         } => {
             subscription_impl::update_subscription(
                 conn_mgr,
                 profile_name,
                 *id,
-                data,
+                data.as_deref(),
+                patch.as_deref(),
+                async_ops,
+                output_format,
+                query,
+            )
+            .await
+        }
+        CloudSubscriptionCommands::Rename {
+            id,
+            name,
+            async_ops,
+        } => {
+            subscription_impl::rename_subscription(
+                conn_mgr,
+                profile_name,
+                *id,
+                name,
+                async_ops,
+                output_format,
+                query,
+            )
+            .await
+        }
+        CloudSubscriptionCommands::SetPayment {
+            id,
+            method_id,
+            async_ops,
+        } => {
+            subscription_impl::set_payment_method(
+                conn_mgr,
+                profile_name,
+                *id,
+                *method_id,
                 async_ops,
                 output_format,
                 query,
@@ -79,6 +137,7 @@ pub async fn handle_subscription_command(
         CloudSubscriptionCommands::Delete {
             id,
             force,
+            cascade,
             async_ops,
         } => {
             subscription_impl::delete_subscription(
@@ -86,6 +145,7 @@ pub async fn handle_subscription_command(
                 profile_name,
                 *id,
                 *force,
+                *cascade,
                 async_ops,
                 output_format,
                 query,
@@ -141,6 +201,16 @@ pub async fn handle_subscription_command(
             )
             .await
         }
+        CloudSubscriptionCommands::Cmk(command) => {
+            super::subscription_cmk::handle_cmk_command(
+                conn_mgr,
+                profile_name,
+                command,
+                output_format,
+                query,
+            )
+            .await
+        }
         CloudSubscriptionCommands::ListAaRegions { id } => {
             subscription_impl::list_aa_regions(conn_mgr, profile_name, *id, output_format, query)
                 .await
@@ -226,13 +296,13 @@ fn print_subscriptions_table(data: &Value) -> CliResult<()> {
         Value::Array(arr) => arr.clone(),
         Value::Object(_) => vec![data.clone()],
         _ => {
-            println!("No subscriptions found");
+            notice!("No subscriptions found");
             return Ok(());
         }
     };
 
     if subscriptions.is_empty() {
-        println!("No subscriptions found");
+        notice!("No subscriptions found");
         return Ok(());
     }
 
@@ -434,7 +504,7 @@ fn print_subscription_detail(data: &Value) -> CliResult<()> {
     }
 
     if rows.is_empty() {
-        println!("No subscription information available");
+        notice!("No subscription information available");
         return Ok(());
     }
 
@@ -0,0 +1,365 @@
+//! Deployment CIDR planning for Pro subscriptions
+//!
+//! Every subscription region is deployed into a /24 "deployment CIDR". It must not overlap the
+//! VPCs it will be peered with, nor the other regions of an Active-Active subscription.
+//! `plan-cidr` suggests free blocks from the private ranges, and `create` checks the CIDRs in
+//! a request before sending it.
+
+#![allow(dead_code)]
+
+use crate::cli::OutputFormat;
+use crate::error::{RedisCtlError, Result as CliResult};
+use crate::output::notice;
+use anyhow::Context;
+use serde::Serialize;
+use serde_json::Value;
+use std::fmt;
+use std::net::Ipv4Addr;
+use tabled::{Table, Tabled, settings::Style};
+
+use super::utils::*;
+
+/// Prefix length of a deployment CIDR; the API requires a range of 256 addresses
+pub const DEPLOYMENT_PREFIX: u8 = 24;
+
+/// RFC 1918 private ranges, searched in this order
+const PRIVATE_RANGES: [Ipv4Cidr; 3] = [
+    Ipv4Cidr::new_unchecked(0x0A00_0000, 8),  // 10.0.0.0/8
+    Ipv4Cidr::new_unchecked(0xAC10_0000, 12), // 172.16.0.0/12
+    Ipv4Cidr::new_unchecked(0xC0A8_0000, 16), // 192.168.0.0/16
+];
+
+/// An IPv4 block such as `10.0.0.0/16`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Ipv4Cidr {
+    network: u32,
+    prefix: u8,
+}
+
+impl Ipv4Cidr {
+    const fn new_unchecked(network: u32, prefix: u8) -> Self {
+        Self { network, prefix }
+    }
+
+    /// Parse a block, rejecting addresses with bits set past the prefix
+    pub fn parse(s: &str) -> Result<Self, String> {
+        let (addr, prefix) = parse_parts(s)?;
+        let cidr = Self {
+            network: u32::from(addr) & mask(prefix),
+            prefix,
+        };
+        if u32::from(addr) != cidr.network {
+            return Err(format!(
+                "{} is not a network address; did you mean {}?",
+                s, cidr
+            ));
+        }
+        Ok(cidr)
+    }
+
+    /// Parse a block, clearing any bits past the prefix (`10.1.2.3/16` is `10.1.0.0/16`)
+    pub fn parse_lenient(s: &str) -> Result<Self, String> {
+        let (addr, prefix) = parse_parts(s)?;
+        Ok(Self {
+            network: u32::from(addr) & mask(prefix),
+            prefix,
+        })
+    }
+
+    pub fn first(&self) -> Ipv4Addr {
+        Ipv4Addr::from(self.network)
+    }
+
+    pub fn last(&self) -> Ipv4Addr {
+        Ipv4Addr::from(self.network | !mask(self.prefix))
+    }
+
+    pub fn prefix(&self) -> u8 {
+        self.prefix
+    }
+
+    pub fn overlaps(&self, other: &Ipv4Cidr) -> bool {
+        let shorter = self.prefix.min(other.prefix);
+        self.network & mask(shorter) == other.network & mask(shorter)
+    }
+
+    pub fn contains(&self, other: &Ipv4Cidr) -> bool {
+        self.prefix <= other.prefix && self.overlaps(other)
+    }
+
+    fn is_private(&self) -> bool {
+        PRIVATE_RANGES.iter().any(|range| range.contains(self))
+    }
+}
+
+impl fmt::Display for Ipv4Cidr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.first(), self.prefix)
+    }
+}
+
+fn parse_parts(s: &str) -> Result<(Ipv4Addr, u8), String> {
+    let (addr, prefix) = s
+        .trim()
+        .split_once('/')
+        .ok_or_else(|| format!("invalid CIDR: {} (expected format: 10.0.0.0/16)", s))?;
+    let addr = addr
+        .parse()
+        .map_err(|_| format!("invalid CIDR: {} (bad IPv4 address '{}')", s, addr))?;
+    match prefix.parse::<u8>() {
+        Ok(p) if p <= 32 => Ok((addr, p)),
+        _ => Err(format!("invalid CIDR: {} (prefix length must be 0-32)", s)),
+    }
+}
+
+fn mask(prefix: u8) -> u32 {
+    match prefix {
+        0 => 0,
+        p => u32::MAX << (32 - p),
+    }
+}
+
+/// Up to `count` free /24 blocks from the private ranges, avoiding `existing` and each other
+pub fn plan_cidrs(existing: &[Ipv4Cidr], count: usize) -> Vec<Ipv4Cidr> {
+    let step = 1u32 << (32 - DEPLOYMENT_PREFIX);
+    let mut planned: Vec<Ipv4Cidr> = Vec::with_capacity(count);
+    for range in PRIVATE_RANGES {
+        let mut network = range.network;
+        while planned.len() < count
+            && range.contains(&Ipv4Cidr::new_unchecked(network, DEPLOYMENT_PREFIX))
+        {
+            let candidate = Ipv4Cidr::new_unchecked(network, DEPLOYMENT_PREFIX);
+            if let Some(taken) = existing.iter().find(|e| e.overlaps(&candidate)) {
+                // Skip past the whole existing block rather than one /24 at a time; a block
+                // narrower than a /24 rounds down to the candidate, so always move at least one
+                let past_taken = u32::from(taken.last())
+                    .checked_add(1)
+                    .map(|next| next & mask(DEPLOYMENT_PREFIX));
+                match (past_taken, network.checked_add(step)) {
+                    (Some(past_taken), Some(next)) => network = past_taken.max(next),
+                    _ => break,
+                }
+                continue;
+            }
+            planned.push(candidate);
+            match network.checked_add(step) {
+                Some(next) => network = next,
+                None => break,
+            }
+        }
+    }
+    planned
+}
+
+/// Problems that would make the API reject `cidr` as a deployment CIDR or break peering
+pub fn deployment_cidr_problems(cidr: &str, existing: &[Ipv4Cidr]) -> Vec<String> {
+    let cidr = match Ipv4Cidr::parse(cidr) {
+        Ok(cidr) => cidr,
+        Err(e) => return vec![e],
+    };
+    let mut problems = Vec::new();
+    if cidr.prefix != DEPLOYMENT_PREFIX {
+        problems.push(format!(
+            "{} is a /{}; a deployment CIDR must be a /{} (256 addresses)",
+            cidr, cidr.prefix, DEPLOYMENT_PREFIX
+        ));
+    }
+    if !cidr.is_private() {
+        problems.push(format!(
+            "{} is not in a private range (10.0.0.0/8, 172.16.0.0/12 or 192.168.0.0/16)",
+            cidr
+        ));
+    }
+    for other in existing.iter().filter(|e| e.overlaps(&cidr)) {
+        problems.push(format!("{} overlaps {}", cidr, other));
+    }
+    problems
+}
+
+/// Check the deployment CIDRs of a subscription create request before it is sent
+///
+/// Each region's CIDR must be valid on its own and must not overlap another region's.
+pub fn validate_request_cidrs(request: &Value) -> CliResult<()> {
+    let mut seen: Vec<Ipv4Cidr> = Vec::new();
+    let mut problems = Vec::new();
+    let providers = request.get("cloudProviders").and_then(Value::as_array);
+    let regions = providers
+        .into_iter()
+        .flatten()
+        .filter_map(|p| p.get("regions").and_then(Value::as_array))
+        .flatten();
+    for region in regions {
+        let networking = region.get("networking");
+        let Some(cidr) = networking
+            .and_then(|n| n.get("deploymentCIDR").or_else(|| n.get("deploymentCidr")))
+            .and_then(Value::as_str)
+        else {
+            continue;
+        };
+        let name = region
+            .get("region")
+            .and_then(Value::as_str)
+            .unwrap_or("region");
+        let found = deployment_cidr_problems(cidr, &seen);
+        problems.extend(found.iter().map(|p| format!("{}: {}", name, p)));
+        if let Ok(parsed) = Ipv4Cidr::parse(cidr) {
+            seen.push(parsed);
+        }
+    }
+    if problems.is_empty() {
+        Ok(())
+    } else {
+        Err(RedisCtlError::Validation {
+            message: format!("Invalid deployment CIDR: {}", problems.join("; ")),
+        })
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct PlannedCidr {
+    cidr: String,
+    first: String,
+    last: String,
+}
+
+#[derive(Tabled)]
+struct PlannedRow {
+    #[tabled(rename = "#")]
+    index: usize,
+    #[tabled(rename = "CIDR")]
+    cidr: String,
+    #[tabled(rename = "RANGE")]
+    range: String,
+}
+
+/// Suggest deployment CIDRs, or check one with `validate`
+pub fn plan_cidr(
+    existing: &[String],
+    regions: usize,
+    validate: Option<&str>,
+    output_format: OutputFormat,
+    query: Option<&str>,
+) -> CliResult<()> {
+    let existing = existing
+        .iter()
+        .map(|e| Ipv4Cidr::parse_lenient(e))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|message| RedisCtlError::Validation { message })?;
+
+    if let Some(cidr) = validate {
+        let problems = deployment_cidr_problems(cidr, &existing);
+        if !problems.is_empty() {
+            return Err(RedisCtlError::Validation {
+                message: format!("Invalid deployment CIDR: {}", problems.join("; ")),
+            });
+        }
+        notice!("{} is a valid deployment CIDR", cidr);
+        return Ok(());
+    }
+
+    let planned = plan_cidrs(&existing, regions);
+    if planned.len() < regions {
+        return Err(RedisCtlError::Validation {
+            message: format!(
+                "Only {} free /{} blocks left outside the existing ranges",
+                planned.len(),
+                DEPLOYMENT_PREFIX
+            ),
+        });
+    }
+
+    match output_format {
+        OutputFormat::Auto | OutputFormat::Table if query.is_none() => {
+            let rows = planned.iter().enumerate().map(|(i, cidr)| PlannedRow {
+                index: i + 1,
+                cidr: cidr.to_string(),
+                range: format!("{} - {}", cidr.first(), cidr.last()),
+            });
+            let mut table = Table::new(rows);
+            table.with(Style::blank());
+            println!("{}", table);
+            Ok(())
+        }
+        _ => {
+            let planned: Vec<PlannedCidr> = planned
+                .iter()
+                .map(|cidr| PlannedCidr {
+                    cidr: cidr.to_string(),
+                    first: cidr.first().to_string(),
+                    last: cidr.last().to_string(),
+                })
+                .collect();
+            let json = serde_json::to_value(planned).context("Failed to serialize CIDRs")?;
+            let data = handle_output(json, output_format, query)?;
+            print_formatted_output(data, output_format)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn cidrs(list: &[&str]) -> Vec<Ipv4Cidr> {
+        list.iter()
+            .map(|c| Ipv4Cidr::parse_lenient(c).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_plan_cidrs() {
+        let planned = plan_cidrs(&cidrs(&["10.0.0.0/16"]), 3);
+        let planned: Vec<String> = planned.iter().map(ToString::to_string).collect();
+        assert_eq!(planned, ["10.1.0.0/24", "10.1.1.0/24", "10.1.2.0/24"]);
+
+        // Gaps between existing ranges are used, and a full 10/8 moves on to 172.16/12
+        let planned = plan_cidrs(&cidrs(&["10.0.0.0/24", "10.0.2.0/23"]), 2);
+        assert_eq!(planned, cidrs(&["10.0.1.0/24", "10.0.4.0/24"]));
+        let planned = plan_cidrs(&cidrs(&["10.0.0.0/8"]), 1);
+        assert_eq!(planned, cidrs(&["172.16.0.0/24"]));
+    }
+
+    #[test]
+    fn test_plan_cidrs_skips_blocks_narrower_than_a_slash_24() {
+        let planned = plan_cidrs(&cidrs(&["10.0.0.0/28"]), 1);
+        assert_eq!(planned, cidrs(&["10.0.1.0/24"]));
+        let planned = plan_cidrs(&cidrs(&["10.0.0.16/28", "10.0.1.128/25"]), 2);
+        assert_eq!(planned, cidrs(&["10.0.2.0/24", "10.0.3.0/24"]));
+    }
+
+    #[test]
+    fn test_deployment_cidr_problems() {
+        let existing = cidrs(&["10.0.0.0/16"]);
+        assert!(deployment_cidr_problems("10.1.0.0/24", &existing).is_empty());
+
+        let problems = deployment_cidr_problems("10.0.5.0/24", &existing);
+        assert_eq!(problems, ["10.0.5.0/24 overlaps 10.0.0.0/16"]);
+        assert!(deployment_cidr_problems("10.1.0.0/16", &[])[0].contains("must be a /24"));
+        assert!(deployment_cidr_problems("8.8.8.0/24", &[])[0].contains("private range"));
+        assert!(
+            deployment_cidr_problems("10.1.0.5/24", &[])[0].contains("did you mean 10.1.0.0/24")
+        );
+    }
+
+    #[test]
+    fn test_validate_request_cidrs() {
+        let request = |a: &str, b: &str| {
+            json!({
+                "cloudProviders": [{
+                    "regions": [
+                        {"region": "us-east-1", "networking": {"deploymentCIDR": a}},
+                        {"region": "us-west-2", "networking": {"deploymentCIDR": b}}
+                    ]
+                }]
+            })
+        };
+        assert!(validate_request_cidrs(&request("10.0.0.0/24", "10.0.1.0/24")).is_ok());
+        let err = validate_request_cidrs(&request("10.0.0.0/24", "10.0.0.0/24")).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("us-west-2: 10.0.0.0/24 overlaps 10.0.0.0/24")
+        );
+        assert!(validate_request_cidrs(&json!({"name": "no networking"})).is_ok());
+    }
+}
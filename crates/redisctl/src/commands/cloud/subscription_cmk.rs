@@ -0,0 +1,697 @@
+//! Customer-managed keys (CMK) for Pro subscriptions
+//!
+//! A subscription created with `persistentStorageEncryptionType: customer-managed-key` stays
+//! pending until it is given the keys to encrypt its persistent storage with: an AWS KMS key
+//! ARN or a GCP Cloud KMS crypto key name, one per region for Active-Active subscriptions.
+//! `status` shows what Redis needs access to (the IAM role or service account to grant), and
+//! `set` checks the keys against the subscription's provider and regions before sending them.
+
+#![allow(dead_code)]
+
+use super::async_utils::{AsyncOperationArgs, handle_async_response};
+use super::utils::*;
+use crate::cli::{CloudSubscriptionCmkCommands, OutputFormat};
+use crate::connection::ConnectionManager;
+use crate::error::{RedisCtlError, Result as CliResult};
+use anyhow::Context;
+use redis_cloud::SubscriptionHandler;
+use redis_cloud::subscriptions::{CustomerManagedKey, Subscription, SubscriptionUpdateCMKRequest};
+use serde::Serialize;
+use serde_json::Value;
+use std::fmt;
+
+/// `persistentStorageEncryptionType` of subscriptions that use customer-managed keys
+const CMK_ENCRYPTION: &str = "customer-managed-key";
+
+pub async fn handle_cmk_command(
+    conn_mgr: &ConnectionManager,
+    profile_name: Option<&str>,
+    command: &CloudSubscriptionCmkCommands,
+    output_format: OutputFormat,
+    query: Option<&str>,
+) -> CliResult<()> {
+    match command {
+        CloudSubscriptionCmkCommands::Status { id } => {
+            cmk_status(conn_mgr, profile_name, *id, output_format, query).await
+        }
+        CloudSubscriptionCmkCommands::Set {
+            id,
+            keys,
+            deletion_grace_period,
+            async_ops,
+        } => {
+            set_cmk(
+                conn_mgr,
+                profile_name,
+                *id,
+                keys,
+                deletion_grace_period.as_deref(),
+                async_ops,
+                output_format,
+                query,
+            )
+            .await
+        }
+    }
+}
+
+/// Key management service a key identifier belongs to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum KmsProvider {
+    Aws,
+    Gcp,
+}
+
+impl KmsProvider {
+    /// The provider of a subscription's `provider` field, such as `AWS`
+    fn of_subscription(provider: &str) -> Option<Self> {
+        match provider.to_ascii_uppercase().as_str() {
+            "AWS" => Some(KmsProvider::Aws),
+            "GCP" => Some(KmsProvider::Gcp),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for KmsProvider {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            KmsProvider::Aws => "AWS KMS",
+            KmsProvider::Gcp => "GCP Cloud KMS",
+        })
+    }
+}
+
+/// A validated key identifier
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KmsKey {
+    pub provider: KmsProvider,
+    pub resource_name: String,
+    /// Region the key lives in, when the identifier names one
+    pub region: Option<String>,
+}
+
+/// Parse an AWS KMS key ARN or a GCP Cloud KMS crypto key resource name
+pub fn parse_kms_key(key: &str) -> Result<KmsKey, String> {
+    let key = key.trim();
+    if key.starts_with("arn:") {
+        parse_aws_key(key)
+    } else if key.starts_with("projects/") {
+        parse_gcp_key(key)
+    } else if is_uuid(key) || key.starts_with("mrk-") || key.starts_with("alias/") {
+        Err(format!(
+            "'{}' is a bare AWS key ID or alias; give the full key ARN \
+             (arn:aws:kms:<region>:<account>:key/<key-id>)",
+            key
+        ))
+    } else {
+        Err(format!(
+            "'{}' is neither an AWS KMS key ARN (arn:aws:kms:<region>:<account>:key/<key-id>) \
+             nor a GCP key name (projects/<project>/locations/<location>/keyRings/<ring>/\
+             cryptoKeys/<key>)",
+            key
+        ))
+    }
+}
+
+/// `arn:<partition>:kms:<region>:<account>:key/<key-id>`
+fn parse_aws_key(arn: &str) -> Result<KmsKey, String> {
+    let invalid = |reason: &str| format!("Invalid AWS KMS key ARN '{}': {}", arn, reason);
+    let parts: Vec<&str> = arn.splitn(6, ':').collect();
+    let [_, partition, service, region, account, resource] = parts.as_slice() else {
+        return Err(invalid(
+            "expected arn:aws:kms:<region>:<account>:key/<key-id>",
+        ));
+    };
+    if !matches!(*partition, "aws" | "aws-cn" | "aws-us-gov") {
+        return Err(invalid(&format!("unknown partition '{}'", partition)));
+    }
+    if *service != "kms" {
+        return Err(invalid(&format!(
+            "service is '{}', not 'kms'; give the ARN of a KMS key",
+            service
+        )));
+    }
+    if region.is_empty()
+        || !region
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+    {
+        return Err(invalid("the region is missing or malformed"));
+    }
+    if account.len() != 12 || !account.chars().all(|c| c.is_ascii_digit()) {
+        return Err(invalid("the account ID must be 12 digits"));
+    }
+    let key_id = match resource.split_once('/') {
+        Some(("key", key_id)) => key_id,
+        Some(("alias", _)) => {
+            return Err(invalid(
+                "aliases are not accepted; give the ARN of the key the alias points to",
+            ));
+        }
+        _ => return Err(invalid("expected key/<key-id> after the account ID")),
+    };
+    let multi_region = key_id
+        .strip_prefix("mrk-")
+        .is_some_and(|id| id.len() == 32 && id.chars().all(|c| c.is_ascii_hexdigit()));
+    if !is_uuid(key_id) && !multi_region {
+        return Err(invalid(
+            "the key ID must be a UUID or a multi-Region key ID (mrk-...)",
+        ));
+    }
+    Ok(KmsKey {
+        provider: KmsProvider::Aws,
+        resource_name: arn.to_string(),
+        region: Some(region.to_string()),
+    })
+}
+
+/// `projects/<project>/locations/<location>/keyRings/<ring>/cryptoKeys/<key>`
+fn parse_gcp_key(name: &str) -> Result<KmsKey, String> {
+    let invalid = |reason: &str| format!("Invalid GCP key name '{}': {}", name, reason);
+    if name.contains("/cryptoKeyVersions/") {
+        return Err(invalid(
+            "this names a key version; give the crypto key without /cryptoKeyVersions/...",
+        ));
+    }
+    let parts: Vec<&str> = name.split('/').collect();
+    let labels = ["projects", "locations", "keyRings", "cryptoKeys"];
+    if parts.len() != 8 || !parts.iter().step_by(2).eq(labels.iter()) {
+        return Err(invalid(
+            "expected projects/<project>/locations/<location>/keyRings/<ring>/cryptoKeys/<key>",
+        ));
+    }
+    let (project, location, ring, key) = (parts[1], parts[3], parts[5], parts[7]);
+    let project_ok = (6..=30).contains(&project.len())
+        && project.starts_with(|c: char| c.is_ascii_lowercase())
+        && project
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-');
+    if !project_ok {
+        return Err(invalid(&format!(
+            "'{}' is not a project ID (6-30 lowercase letters, digits or hyphens)",
+            project
+        )));
+    }
+    if location.is_empty()
+        || !location
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+    {
+        return Err(invalid(&format!("'{}' is not a location", location)));
+    }
+    for (label, id) in [("key ring", ring), ("key", key)] {
+        let id_ok = (1..=63).contains(&id.len())
+            && id
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+        if !id_ok {
+            return Err(invalid(&format!(
+                "'{}' is not a valid {} name (1-63 letters, digits, '-' or '_')",
+                id, label
+            )));
+        }
+    }
+    // Multi-regions such as `us` or `europe` and `global` are not subscription regions
+    let region = location
+        .chars()
+        .any(|c| c.is_ascii_digit())
+        .then(|| location.to_string());
+    Ok(KmsKey {
+        provider: KmsProvider::Gcp,
+        resource_name: name.to_string(),
+        region,
+    })
+}
+
+fn is_uuid(s: &str) -> bool {
+    let groups: Vec<&str> = s.split('-').collect();
+    groups.len() == 5
+        && groups
+            .iter()
+            .zip([8, 4, 4, 4, 12])
+            .all(|(group, len)| group.len() == len && group.chars().all(|c| c.is_ascii_hexdigit()))
+}
+
+/// Provider, regions and deployment of a subscription, as far as the key checks need them
+#[derive(Debug, Clone, Default, PartialEq)]
+struct Deployment {
+    provider: Option<String>,
+    regions: Vec<String>,
+    active_active: bool,
+}
+
+impl Deployment {
+    fn of(subscription: &Subscription) -> Self {
+        let extra = &subscription.extra;
+        let providers = ["cloudDetails", "cloudProviders"]
+            .iter()
+            .find_map(|field| extra.get(field).and_then(Value::as_array))
+            .cloned()
+            .unwrap_or_default();
+        let provider = providers
+            .iter()
+            .find_map(|p| p.get("provider").and_then(Value::as_str))
+            .map(str::to_string);
+        let regions: Vec<String> = providers
+            .iter()
+            .filter_map(|p| p.get("regions").and_then(Value::as_array))
+            .flatten()
+            .filter_map(|r| r.get("region").and_then(Value::as_str))
+            .map(str::to_string)
+            .collect();
+        let active_active = extra
+            .get("deploymentType")
+            .and_then(Value::as_str)
+            .is_some_and(|t| t.eq_ignore_ascii_case("active-active"))
+            || regions.len() > 1;
+        Deployment {
+            provider,
+            regions,
+            active_active,
+        }
+    }
+}
+
+/// Parse `[REGION=]KEY` arguments and check them against the subscription's deployment
+fn build_keys(args: &[String], deployment: &Deployment) -> CliResult<Vec<CustomerManagedKey>> {
+    let invalid = |message: String| RedisCtlError::Validation { message };
+    let provider = match deployment.provider.as_deref() {
+        Some(name) => Some(KmsProvider::of_subscription(name).ok_or_else(|| {
+            invalid(format!(
+                "Customer-managed keys are supported on AWS and GCP subscriptions, not {}",
+                name
+            ))
+        })?),
+        None => None,
+    };
+
+    let mut keys: Vec<(Option<String>, KmsKey)> = Vec::new();
+    for arg in args {
+        let (region, key) = match arg.split_once('=') {
+            Some((region, key)) => (Some(region.trim().to_string()), key),
+            None => (None, arg.as_str()),
+        };
+        let key = parse_kms_key(key).map_err(invalid)?;
+        if let Some(provider) = provider
+            && key.provider != provider
+        {
+            return Err(invalid(format!(
+                "The subscription runs on {}, but {} is a {} key",
+                deployment.provider.as_deref().unwrap_or_default(),
+                key.resource_name,
+                key.provider
+            )));
+        }
+        // AWS KMS keys are regional, so an explicit region must agree with the ARN
+        if let (Some(region), KmsProvider::Aws, Some(key_region)) =
+            (&region, key.provider, &key.region)
+            && region != key_region
+        {
+            return Err(invalid(format!(
+                "{} is in {}, not {}",
+                key.resource_name, key_region, region
+            )));
+        }
+        let region = region.or_else(|| key.region.clone());
+        keys.push((region, key));
+    }
+
+    if deployment.active_active {
+        let mut seen = Vec::new();
+        for (region, key) in &keys {
+            let region = region.as_deref().ok_or_else(|| {
+                invalid(format!(
+                    "Active-Active subscriptions need a key per region; write --key \
+                     <REGION>={}",
+                    key.resource_name
+                ))
+            })?;
+            if seen.contains(&region) {
+                return Err(invalid(format!("More than one key for region {}", region)));
+            }
+            if !deployment.regions.is_empty() && !deployment.regions.iter().any(|r| r == region) {
+                return Err(invalid(format!(
+                    "The subscription has no region {} (regions: {})",
+                    region,
+                    deployment.regions.join(", ")
+                )));
+            }
+            seen.push(region);
+        }
+        let missing: Vec<&str> = deployment
+            .regions
+            .iter()
+            .map(String::as_str)
+            .filter(|r| !seen.contains(r))
+            .collect();
+        if !missing.is_empty() {
+            return Err(invalid(format!(
+                "No key given for region(s) {}; Active-Active subscriptions need a key per region",
+                missing.join(", ")
+            )));
+        }
+    } else {
+        if keys.len() > 1 {
+            return Err(invalid(
+                "A single-region subscription takes exactly one key".to_string(),
+            ));
+        }
+        if let ([(Some(region), key)], [subscription_region]) =
+            (keys.as_slice(), deployment.regions.as_slice())
+            && key.provider == KmsProvider::Aws
+            && region != subscription_region
+        {
+            return Err(invalid(format!(
+                "{} is in {}, but the subscription is deployed in {}",
+                key.resource_name, region, subscription_region
+            )));
+        }
+    }
+
+    Ok(keys
+        .into_iter()
+        .map(|(region, key)| CustomerManagedKey {
+            resource_name: key.resource_name,
+            region,
+            extra: Value::Object(Default::default()),
+        })
+        .collect())
+}
+
+/// What a subscription's encryption looks like and what Redis needs access to
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CmkStatus {
+    pub subscription_id: u32,
+    pub name: Option<String>,
+    pub status: Option<String>,
+    pub provider: Option<String>,
+    pub encryption: Option<String>,
+    pub deletion_grace_period: Option<String>,
+    /// IAM role Redis uses to reach an AWS key
+    pub redis_iam_role: Option<String>,
+    /// Service account Redis uses to reach a GCP key
+    pub redis_service_account: Option<String>,
+    pub google_predefined_roles: Vec<String>,
+    pub google_custom_permissions: Vec<String>,
+    /// Statements to add to an AWS key policy, by name
+    pub required_key_policy_statements: Option<Value>,
+    pub deletion_grace_period_options: Vec<String>,
+}
+
+impl CmkStatus {
+    fn of(id: u32, subscription: &Subscription) -> Self {
+        let access = subscription.customer_managed_key_access_details.as_ref();
+        CmkStatus {
+            subscription_id: id,
+            name: subscription.name.clone(),
+            status: subscription.status.clone(),
+            provider: Deployment::of(subscription).provider,
+            encryption: subscription.persistent_storage_encryption_type.clone(),
+            deletion_grace_period: subscription.deletion_grace_period.clone(),
+            redis_iam_role: access.and_then(|a| a.redis_iam_role.clone()),
+            redis_service_account: access.and_then(|a| a.redis_service_account.clone()),
+            google_predefined_roles: access
+                .and_then(|a| a.google_predefined_roles.clone())
+                .unwrap_or_default(),
+            google_custom_permissions: access
+                .and_then(|a| a.google_custom_permissions.clone())
+                .unwrap_or_default(),
+            required_key_policy_statements: access
+                .and_then(|a| a.required_key_policy_statements.as_ref())
+                .and_then(|s| serde_json::to_value(s).ok()),
+            deletion_grace_period_options: access
+                .and_then(|a| a.deletion_grace_period_options.clone())
+                .unwrap_or_default(),
+        }
+    }
+
+    fn uses_cmk(&self) -> bool {
+        self.encryption.as_deref() == Some(CMK_ENCRYPTION)
+    }
+}
+
+async fn cmk_status(
+    conn_mgr: &ConnectionManager,
+    profile_name: Option<&str>,
+    id: u32,
+    output_format: OutputFormat,
+    query: Option<&str>,
+) -> CliResult<()> {
+    let client = conn_mgr.create_cloud_client(profile_name).await?;
+    let subscription = SubscriptionHandler::new(client)
+        .get_subscription_by_id(id as i32)
+        .await
+        .context(format!("Failed to get subscription {}", id))?;
+    let status = CmkStatus::of(id, &subscription);
+
+    match output_format {
+        OutputFormat::Auto | OutputFormat::Table if query.is_none() => {
+            print!("{}", format_status(&status));
+            Ok(())
+        }
+        _ => {
+            let json = serde_json::to_value(&status).context("Failed to serialize CMK status")?;
+            let data = handle_output(json, output_format, query)?;
+            print_formatted_output(data, output_format)
+        }
+    }
+}
+
+fn format_status(status: &CmkStatus) -> String {
+    let or_dash = |value: &Option<String>| value.clone().unwrap_or_else(|| "-".to_string());
+    let mut out = format!(
+        "Subscription:  {} ({})\n",
+        status.subscription_id,
+        or_dash(&status.name)
+    );
+    out.push_str(&format!("Status:        {}\n", or_dash(&status.status)));
+    out.push_str(&format!("Provider:      {}\n", or_dash(&status.provider)));
+    out.push_str(&format!("Encryption:    {}\n", or_dash(&status.encryption)));
+    if !status.uses_cmk() {
+        out.push_str(
+            "\nThis subscription does not use customer-managed keys; they can only be chosen \
+             when it is created.\n",
+        );
+        return out;
+    }
+    out.push_str(&format!(
+        "Grace period:  {}\n",
+        or_dash(&status.deletion_grace_period)
+    ));
+    if let Some(role) = &status.redis_iam_role {
+        out.push_str(&format!("Redis role:    {}\n", role));
+    }
+    if let Some(account) = &status.redis_service_account {
+        out.push_str(&format!("Redis account: {}\n", account));
+    }
+    if !status.google_predefined_roles.is_empty() {
+        out.push_str(&format!(
+            "Grant roles:   {}\n",
+            status.google_predefined_roles.join(", ")
+        ));
+    }
+    if !status.google_custom_permissions.is_empty() {
+        out.push_str(&format!(
+            "Or permissions: {}\n",
+            status.google_custom_permissions.join(", ")
+        ));
+    }
+    if let Some(statements) = &status.required_key_policy_statements {
+        out.push_str("\nAdd to the key policy:\n");
+        out.push_str(&serde_json::to_string_pretty(statements).unwrap_or_default());
+        out.push('\n');
+    }
+    out
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn set_cmk(
+    conn_mgr: &ConnectionManager,
+    profile_name: Option<&str>,
+    id: u32,
+    key_args: &[String],
+    deletion_grace_period: Option<&str>,
+    async_ops: &AsyncOperationArgs,
+    output_format: OutputFormat,
+    query: Option<&str>,
+) -> CliResult<()> {
+    let client = conn_mgr.create_cloud_client(profile_name).await?;
+    let handler = SubscriptionHandler::new(client);
+    let subscription = handler
+        .get_subscription_by_id(id as i32)
+        .await
+        .context(format!("Failed to get subscription {}", id))?;
+
+    let status = CmkStatus::of(id, &subscription);
+    if let Some(encryption) = &status.encryption
+        && !status.uses_cmk()
+    {
+        return Err(RedisCtlError::Validation {
+            message: format!(
+                "Subscription {} encrypts with {}; customer-managed keys can only be set on \
+                 subscriptions created with {}",
+                id, encryption, CMK_ENCRYPTION
+            ),
+        });
+    }
+    if let Some(period) = deletion_grace_period
+        && !status.deletion_grace_period_options.is_empty()
+        && !status
+            .deletion_grace_period_options
+            .iter()
+            .any(|o| o == period)
+    {
+        return Err(RedisCtlError::Validation {
+            message: format!(
+                "Unknown deletion grace period '{}'; choose one of: {}",
+                period,
+                status.deletion_grace_period_options.join(", ")
+            ),
+        });
+    }
+    let keys = build_keys(key_args, &Deployment::of(&subscription))?;
+    let count = keys.len();
+
+    let request = SubscriptionUpdateCMKRequest {
+        subscription_id: None,
+        command_type: None,
+        deletion_grace_period: deletion_grace_period.map(str::to_string),
+        customer_managed_keys: keys,
+        extra: Value::Object(Default::default()),
+    };
+    let task = handler
+        .update_subscription_cmks(id as i32, &request)
+        .await
+        .context("Failed to set customer-managed keys")?;
+    let response = serde_json::to_value(task).context("Failed to serialize response")?;
+
+    handle_async_response(
+        conn_mgr,
+        profile_name,
+        response,
+        async_ops,
+        output_format,
+        query,
+        &format!(
+            "Subscription {} set to encrypt with {} customer-managed key(s)",
+            id, count
+        ),
+    )
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const AWS_KEY: &str =
+        "arn:aws:kms:us-east-1:123456789012:key/1234abcd-12ab-34cd-56ef-1234567890ab";
+    const GCP_KEY: &str =
+        "projects/my-project-1/locations/us-east1/keyRings/redis/cryptoKeys/storage";
+
+    fn deployment(provider: &str, regions: &[&str]) -> Deployment {
+        Deployment {
+            provider: Some(provider.to_string()),
+            regions: regions.iter().map(|r| r.to_string()).collect(),
+            active_active: regions.len() > 1,
+        }
+    }
+
+    #[test]
+    fn test_parse_aws_keys() {
+        let key = parse_kms_key(AWS_KEY).unwrap();
+        assert_eq!(key.provider, KmsProvider::Aws);
+        assert_eq!(key.region.as_deref(), Some("us-east-1"));
+
+        let mrk = "arn:aws-us-gov:kms:us-gov-west-1:123456789012:key/mrk-1234abcd12ab34cd56ef1234567890ab";
+        assert!(parse_kms_key(mrk).is_ok());
+
+        for bad in [
+            "arn:aws:kms:us-east-1:123456789012:alias/redis",
+            "arn:aws:s3:us-east-1:123456789012:key/1234abcd-12ab-34cd-56ef-1234567890ab",
+            "arn:aws:kms:us-east-1:1234:key/1234abcd-12ab-34cd-56ef-1234567890ab",
+            "arn:aws:kms:us-east-1:123456789012:key/not-a-key",
+            "1234abcd-12ab-34cd-56ef-1234567890ab",
+        ] {
+            assert!(parse_kms_key(bad).is_err(), "{} should be rejected", bad);
+        }
+    }
+
+    #[test]
+    fn test_parse_gcp_keys() {
+        let key = parse_kms_key(GCP_KEY).unwrap();
+        assert_eq!(key.provider, KmsProvider::Gcp);
+        assert_eq!(key.region.as_deref(), Some("us-east1"));
+
+        let global = "projects/my-project-1/locations/global/keyRings/redis/cryptoKeys/storage";
+        assert_eq!(parse_kms_key(global).unwrap().region, None);
+
+        let version = format!("{}/cryptoKeyVersions/1", GCP_KEY);
+        assert!(parse_kms_key(&version).unwrap_err().contains("key version"));
+        assert!(parse_kms_key("projects/ab/locations/us-east1/keyRings/r/cryptoKeys/k").is_err());
+        assert!(parse_kms_key("keyRings/redis/cryptoKeys/storage").is_err());
+    }
+
+    #[test]
+    fn test_build_keys_single_region() {
+        let keys = build_keys(&[AWS_KEY.to_string()], &deployment("AWS", &["us-east-1"])).unwrap();
+        assert_eq!(keys.len(), 1);
+        assert_eq!(keys[0].region.as_deref(), Some("us-east-1"));
+
+        // Key in another region, wrong provider, more than one key
+        assert!(build_keys(&[AWS_KEY.to_string()], &deployment("AWS", &["eu-west-1"])).is_err());
+        assert!(build_keys(&[GCP_KEY.to_string()], &deployment("AWS", &["us-east-1"])).is_err());
+        assert!(
+            build_keys(
+                &[AWS_KEY.to_string(), AWS_KEY.to_string()],
+                &deployment("AWS", &["us-east-1"])
+            )
+            .is_err()
+        );
+        assert!(build_keys(&[AWS_KEY.to_string()], &deployment("Azure", &[])).is_err());
+    }
+
+    #[test]
+    fn test_build_keys_active_active() {
+        let regions = deployment("GCP", &["us-east1", "europe-west1"]);
+        let eu_key =
+            "europe-west1=projects/my-project-1/locations/global/keyRings/redis/cryptoKeys/storage";
+        let keys = build_keys(&[GCP_KEY.to_string(), eu_key.to_string()], &regions).unwrap();
+        assert_eq!(keys[1].region.as_deref(), Some("europe-west1"));
+
+        // A region without a key, and a global key without a region
+        let err = build_keys(&[GCP_KEY.to_string()], &regions).unwrap_err();
+        assert!(err.to_string().contains("europe-west1"));
+        let global = eu_key.trim_start_matches("europe-west1=").to_string();
+        assert!(build_keys(&[GCP_KEY.to_string(), global], &regions).is_err());
+    }
+
+    #[test]
+    fn test_deployment_from_subscription() {
+        let subscription: Subscription = serde_json::from_value(json!({
+            "id": 7,
+            "deploymentType": "active-active",
+            "persistentStorageEncryptionType": "customer-managed-key",
+            "cloudDetails": [{"provider": "AWS", "regions": [
+                {"region": "us-east-1"}, {"region": "eu-west-1"}
+            ]}],
+            "customerManagedKeyAccessDetails": {
+                "redisIamRole": "arn:aws:iam::999999999999:role/redis-cmk",
+                "deletionGracePeriodOptions": ["immediate", "alerts-only"]
+            }
+        }))
+        .unwrap();
+        let deployment = Deployment::of(&subscription);
+        assert_eq!(deployment.provider.as_deref(), Some("AWS"));
+        assert_eq!(deployment.regions, ["us-east-1", "eu-west-1"]);
+        assert!(deployment.active_active);
+
+        let status = CmkStatus::of(7, &subscription);
+        assert!(status.uses_cmk());
+        assert!(format_status(&status).contains("role/redis-cmk"));
+        assert_eq!(status.deletion_grace_period_options.len(), 2);
+    }
+}
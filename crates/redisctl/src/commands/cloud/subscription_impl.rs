@@ -1,13 +1,19 @@
 //! Implementation of additional subscription commands
 
-use super::async_utils::{AsyncOperationArgs, handle_async_response};
+use super::async_utils::{AsyncOperationArgs, get_task_state, handle_async_response, poll_task};
+use super::if_absent;
 use super::utils::*;
 use crate::cli::OutputFormat;
+use crate::commands::patch::patch_update;
 use crate::connection::ConnectionManager;
 use crate::error::{RedisCtlError, Result as CliResult};
+use crate::output::notice;
 use crate::output::print_output;
 use anyhow::Context;
-use serde_json::Value;
+use redis_cloud::account::{PaymentMethod, PaymentMethods};
+use redis_cloud::subscriptions::SubscriptionCreateRequest;
+use redis_cloud::{AccountHandler, SubscriptionHandler};
+use serde_json::{Value, json};
 use tabled::{Table, Tabled, settings::Style};
 
 /// Helper to print non-table output
@@ -15,6 +21,7 @@ fn print_json_or_yaml(data: Value, output_format: OutputFormat) -> CliResult<()>
     match output_format {
         OutputFormat::Json => print_output(data, crate::output::OutputFormat::Json, None)?,
         OutputFormat::Yaml => print_output(data, crate::output::OutputFormat::Yaml, None)?,
+        OutputFormat::Id => crate::output::print_ids(&data)?,
         _ => print_output(data, crate::output::OutputFormat::Json, None)?,
     }
     Ok(())
@@ -24,7 +31,7 @@ fn print_json_or_yaml(data: Value, output_format: OutputFormat) -> CliResult<()>
 fn read_json_data(data: &str) -> CliResult<Value> {
     let json_str = if let Some(file_path) = data.strip_prefix('@') {
         // Read from file
-        std::fs::read_to_string(file_path).map_err(|e| RedisCtlError::InvalidInput {
+        std::fs::read_to_string(file_path).map_err(|e| RedisCtlError::Validation {
             message: format!("Failed to read file {}: {}", file_path, e),
         })?
     } else {
@@ -32,22 +39,57 @@ fn read_json_data(data: &str) -> CliResult<Value> {
         data.to_string()
     };
 
-    serde_json::from_str(&json_str).map_err(|e| RedisCtlError::InvalidInput {
+    serde_json::from_str(&json_str).map_err(|e| RedisCtlError::Validation {
         message: format!("Invalid JSON: {}", e),
     })
 }
 
 /// Create a new subscription
+#[allow(clippy::too_many_arguments)]
 pub async fn create_subscription(
     conn_mgr: &ConnectionManager,
     profile_name: Option<&str>,
     data: &str,
+    dry_run: bool,
+    if_absent: Option<&str>,
     async_ops: &AsyncOperationArgs,
     output_format: OutputFormat,
     query: Option<&str>,
 ) -> CliResult<()> {
     let client = conn_mgr.create_cloud_client(profile_name).await?;
-    let request = read_json_data(data)?;
+    let mut request = read_json_data(data)?;
+    super::subscription_cidr::validate_request_cidrs(&request)?;
+
+    if let Some(spec) = if_absent {
+        let name = if_absent::resolve_name(spec, &mut request, "subscription")?;
+        let response = client
+            .get_raw("/subscriptions")
+            .await
+            .context("Failed to list subscriptions")?;
+        let subscriptions = response
+            .get("subscriptions")
+            .and_then(Value::as_array)
+            .cloned()
+            .unwrap_or_default();
+        if let Some(existing) = if_absent::find_by_name(&subscriptions, &name, "subscription")? {
+            let id = existing.get("id").cloned().unwrap_or(Value::Null);
+            return if_absent::print_found("subscription", id, existing, output_format, query);
+        }
+        if_absent::note_creating("subscription", &name);
+    }
+
+    if dry_run {
+        return dry_run_subscription(
+            conn_mgr,
+            profile_name,
+            client,
+            request,
+            async_ops,
+            output_format,
+            query,
+        )
+        .await;
+    }
 
     let response = client
         .post_raw("/subscriptions", request)
@@ -66,18 +108,101 @@ pub async fn create_subscription(
     .await
 }
 
+/// Validate a subscription create request without creating any resources
+///
+/// The API answers a dry run with a task whose response describes the planned deployment,
+/// so the task is always polled to completion.
+async fn dry_run_subscription(
+    conn_mgr: &ConnectionManager,
+    profile_name: Option<&str>,
+    client: redis_cloud::CloudClient,
+    request: Value,
+    async_ops: &AsyncOperationArgs,
+    output_format: OutputFormat,
+    query: Option<&str>,
+) -> CliResult<()> {
+    let mut request: SubscriptionCreateRequest =
+        serde_json::from_value(request).map_err(|e| RedisCtlError::Validation {
+            message: format!("Invalid subscription request: {}", e),
+        })?;
+    request.dry_run = Some(true);
+
+    let handler = SubscriptionHandler::new(client);
+    let task = handler
+        .create_subscription(&request)
+        .await
+        .context("Failed to validate subscription")?;
+    let task_id = task.task_id.ok_or_else(|| {
+        RedisCtlError::api("Dry run response did not include a task ID".to_string())
+    })?;
+
+    let task = poll_task(
+        conn_mgr,
+        profile_name,
+        &task_id,
+        async_ops.wait_timeout,
+        async_ops.wait_interval,
+    )
+    .await?;
+
+    let response = task.get("response").cloned().unwrap_or(Value::Null);
+    let state = get_task_state(&task);
+    if state == "processing-error" || state == "failed" || state == "error" {
+        let reason = response
+            .get("error")
+            .map(|e| {
+                e.get("description")
+                    .and_then(|d| d.as_str())
+                    .map(String::from)
+                    .unwrap_or_else(|| e.to_string())
+            })
+            .unwrap_or_else(|| "no details returned".to_string());
+        return Err(RedisCtlError::Validation {
+            message: format!("Subscription dry run failed: {}", reason),
+        });
+    }
+
+    let plan = response.get("resource").cloned().unwrap_or(response);
+    let data = handle_output(plan, output_format, query)?;
+
+    match output_format {
+        OutputFormat::Auto | OutputFormat::Table => {
+            notice!("Dry run succeeded; no resources were created. Planned deployment:");
+            print_json_or_yaml(data, OutputFormat::Json)?;
+        }
+        _ => print_json_or_yaml(data, output_format)?,
+    }
+
+    Ok(())
+}
+
 /// Update subscription configuration
+#[allow(clippy::too_many_arguments)]
 pub async fn update_subscription(
     conn_mgr: &ConnectionManager,
     profile_name: Option<&str>,
     id: u32,
-    data: &str,
+    data: Option<&str>,
+    patch: Option<&str>,
     async_ops: &AsyncOperationArgs,
     output_format: OutputFormat,
     query: Option<&str>,
 ) -> CliResult<()> {
     let client = conn_mgr.create_cloud_client(profile_name).await?;
-    let request = read_json_data(data)?;
+    let request = match patch {
+        Some(patch) => {
+            let current = client
+                .get_raw(&format!("/subscriptions/{}", id))
+                .await
+                .context("Failed to get subscription")?;
+            patch_update(&current, patch)?
+        }
+        None => Some(read_json_data(data.unwrap_or_default())?),
+    };
+    let Some(request) = request else {
+        notice!("No changes to apply");
+        return Ok(());
+    };
 
     let response = client
         .put_raw(&format!("/subscriptions/{}", id), request)
@@ -96,34 +221,347 @@ pub async fn update_subscription(
     .await
 }
 
+/// Rename a subscription with an update that carries only the new name
+pub async fn rename_subscription(
+    conn_mgr: &ConnectionManager,
+    profile_name: Option<&str>,
+    id: u32,
+    name: &str,
+    async_ops: &AsyncOperationArgs,
+    output_format: OutputFormat,
+    query: Option<&str>,
+) -> CliResult<()> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(RedisCtlError::Validation {
+            message: "Subscription name cannot be empty".to_string(),
+        });
+    }
+
+    let client = conn_mgr.create_cloud_client(profile_name).await?;
+    let handler = SubscriptionHandler::new(client);
+    let current = handler
+        .get_subscription_by_id(id as i32)
+        .await
+        .context(format!("Failed to get subscription {}", id))?;
+    let old_name = current.name.unwrap_or_default();
+    if old_name == name {
+        notice!("Subscription {} is already named '{}'", id, name);
+        return Ok(());
+    }
+
+    let task = handler
+        .update_subscription_name(id as i32, name)
+        .await
+        .context("Failed to rename subscription")?;
+    let response = serde_json::to_value(task).context("Failed to serialize response")?;
+
+    handle_async_response(
+        conn_mgr,
+        profile_name,
+        response,
+        async_ops,
+        output_format,
+        query,
+        &format!("Subscription renamed from '{}' to '{}'", old_name, name),
+    )
+    .await
+}
+
+/// Payment method choices for table display
+#[derive(Tabled)]
+struct PaymentMethodRow {
+    #[tabled(rename = "ID")]
+    id: String,
+    #[tabled(rename = "TYPE")]
+    payment_type: String,
+    #[tabled(rename = "ENDS WITH")]
+    ends_with: String,
+    #[tabled(rename = "EXPIRES")]
+    expires: String,
+    #[tabled(rename = "CURRENT")]
+    current: String,
+}
+
+/// Switch a subscription to another payment method of the account
+///
+/// Without `method_id`, lists the account's payment methods and marks the one the subscription
+/// uses. The method is checked against `GET /payment-methods` before the update is sent.
+#[allow(clippy::too_many_arguments)]
+pub async fn set_payment_method(
+    conn_mgr: &ConnectionManager,
+    profile_name: Option<&str>,
+    id: u32,
+    method_id: Option<i32>,
+    async_ops: &AsyncOperationArgs,
+    output_format: OutputFormat,
+    query: Option<&str>,
+) -> CliResult<()> {
+    let client = conn_mgr.create_cloud_client(profile_name).await?;
+    let handler = SubscriptionHandler::new(client.clone());
+    let (subscription, methods) = tokio::try_join!(
+        async {
+            handler
+                .get_subscription_by_id(id as i32)
+                .await
+                .context(format!("Failed to get subscription {}", id))
+        },
+        async {
+            AccountHandler::new(client.clone())
+                .get_account_payment_methods()
+                .await
+                .context("Failed to fetch payment methods")
+        },
+    )?;
+    let current = subscription.payment_method_id;
+
+    let Some(method_id) = method_id else {
+        return print_payment_methods(&methods, current, output_format, query);
+    };
+
+    let method = methods
+        .find(method_id)
+        .ok_or_else(|| RedisCtlError::Validation {
+            message: unknown_payment_method(method_id, &methods),
+        })?;
+    if current == Some(method_id) {
+        notice!(
+            "Subscription {} already uses payment method {}",
+            id,
+            describe_payment_method(method)
+        );
+        return Ok(());
+    }
+
+    let task = handler
+        .update_subscription_payment_method(id as i32, method_id)
+        .await
+        .context("Failed to update payment method")?;
+    let response = serde_json::to_value(task).context("Failed to serialize response")?;
+
+    handle_async_response(
+        conn_mgr,
+        profile_name,
+        response,
+        async_ops,
+        output_format,
+        query,
+        &format!(
+            "Subscription {} now uses payment method {}",
+            id,
+            describe_payment_method(method)
+        ),
+    )
+    .await
+}
+
+fn print_payment_methods(
+    methods: &PaymentMethods,
+    current: Option<i32>,
+    output_format: OutputFormat,
+    query: Option<&str>,
+) -> CliResult<()> {
+    let methods = methods.payment_methods.as_deref().unwrap_or_default();
+    match output_format {
+        OutputFormat::Auto | OutputFormat::Table if query.is_none() => {
+            if methods.is_empty() {
+                notice!("No payment methods configured");
+                return Ok(());
+            }
+            let rows: Vec<PaymentMethodRow> = methods
+                .iter()
+                .map(|method| PaymentMethodRow {
+                    id: method
+                        .id
+                        .map_or_else(|| "-".to_string(), |id| id.to_string()),
+                    payment_type: method.payment_type.clone().unwrap_or_default(),
+                    ends_with: method
+                        .credit_card_ends_with
+                        .map(|digits| format!("{:04}", digits))
+                        .unwrap_or_default(),
+                    expires: match (method.expiration_month, method.expiration_year) {
+                        (Some(month), Some(year)) => format!("{:02}/{}", month, year),
+                        _ => String::new(),
+                    },
+                    current: if method.id.is_some() && method.id == current {
+                        "*".to_string()
+                    } else {
+                        String::new()
+                    },
+                })
+                .collect();
+            let mut table = Table::new(rows);
+            table.with(Style::blank());
+            println!("{}", table);
+            Ok(())
+        }
+        _ => {
+            let json: Vec<Value> = methods
+                .iter()
+                .map(|method| {
+                    let mut value = serde_json::to_value(method).unwrap_or_default();
+                    if let Value::Object(map) = &mut value {
+                        map.insert(
+                            "current".to_string(),
+                            json!(method.id.is_some() && method.id == current),
+                        );
+                    }
+                    value
+                })
+                .collect();
+            let data = handle_output(Value::Array(json), output_format, query)?;
+            print_formatted_output(data, output_format)
+        }
+    }
+}
+
+/// A payment method as `42 (Visa ending 4242)`
+fn describe_payment_method(method: &PaymentMethod) -> String {
+    let id = method
+        .id
+        .map_or_else(|| "?".to_string(), |id| id.to_string());
+    match (&method.payment_type, method.credit_card_ends_with) {
+        (Some(kind), Some(digits)) => format!("{} ({} ending {:04})", id, kind, digits),
+        (Some(kind), None) => format!("{} ({})", id, kind),
+        (None, Some(digits)) => format!("{} (ending {:04})", id, digits),
+        (None, None) => id,
+    }
+}
+
+fn unknown_payment_method(method_id: i32, methods: &PaymentMethods) -> String {
+    let available: Vec<String> = methods
+        .payment_methods
+        .as_deref()
+        .unwrap_or_default()
+        .iter()
+        .map(describe_payment_method)
+        .collect();
+    if available.is_empty() {
+        format!(
+            "Payment method {} does not belong to this account, which has no payment methods",
+            method_id
+        )
+    } else {
+        format!(
+            "Payment method {} does not belong to this account; available: {}",
+            method_id,
+            available.join(", ")
+        )
+    }
+}
+
 /// Delete a subscription
+#[allow(clippy::too_many_arguments)]
 pub async fn delete_subscription(
     conn_mgr: &ConnectionManager,
     profile_name: Option<&str>,
     id: u32,
     force: bool,
+    cascade: bool,
     async_ops: &AsyncOperationArgs,
     output_format: OutputFormat,
     query: Option<&str>,
 ) -> CliResult<()> {
+    let client = conn_mgr.create_cloud_client(profile_name).await?;
+
+    let subscription = client
+        .get_raw(&format!("/subscriptions/{}", id))
+        .await
+        .context("Failed to get subscription")?;
+    let name = extract_field(&subscription, "name", "");
+
+    // Pre-flight: the API refuses to delete a subscription that still has databases
+    let databases = fetch_subscription_databases(&client, id).await?;
+    if !databases.is_empty() {
+        eprintln!(
+            "Subscription {} contains {} database(s):",
+            id,
+            databases.len()
+        );
+        for db in &databases {
+            eprintln!(
+                "  - {} ({})",
+                extract_field(db, "databaseId", "?"),
+                extract_field(db, "name", "unnamed")
+            );
+        }
+
+        if !cascade {
+            return Err(RedisCtlError::Validation {
+                message: format!(
+                    "Subscription {} still contains {} database(s). Delete them first or re-run with --cascade",
+                    id,
+                    databases.len()
+                ),
+            });
+        }
+    }
+
     // Confirmation prompt unless --force is used
     if !force {
-        use dialoguer::Confirm;
-        let confirm = Confirm::new()
-            .with_prompt(format!("Are you sure you want to delete subscription {}? This will delete all databases in the subscription!", id))
-            .default(false)
-            .interact()
-            .map_err(|e| RedisCtlError::InvalidInput {
+        use dialoguer::Input;
+        crate::interactive::ensure_prompt_allowed(
+            &format!("Deleting subscription {} needs confirmation", id),
+            "pass --force to delete without it",
+        )?;
+        let expected = if name.is_empty() {
+            id.to_string()
+        } else {
+            name.clone()
+        };
+        let prompt = if databases.is_empty() {
+            format!("Type '{}' to delete subscription {}", expected, id)
+        } else {
+            format!(
+                "Type '{}' to delete subscription {} and its {} database(s)",
+                expected,
+                id,
+                databases.len()
+            )
+        };
+        let answer: String = Input::new()
+            .with_prompt(prompt)
+            .allow_empty(true)
+            .interact_text()
+            .map_err(|e| RedisCtlError::Validation {
                 message: format!("Failed to read confirmation: {}", e),
             })?;
 
-        if !confirm {
-            println!("Subscription deletion cancelled");
+        if answer.trim() != expected {
+            notice!("Subscription deletion cancelled");
             return Ok(());
         }
     }
 
-    let client = conn_mgr.create_cloud_client(profile_name).await?;
+    // Delete child databases one at a time, waiting for each task before moving on
+    for db in &databases {
+        let db_id = database_id(db, id)?;
+
+        crate::progress::step(&format!("Deleting database {}...", db_id));
+        let response = client
+            .delete_raw(&format!("/subscriptions/{}/databases/{}", id, db_id))
+            .await
+            .with_context(|| format!("Failed to delete database {}", db_id))?;
+
+        if let Some(task_id) = response.get("taskId").and_then(|v| v.as_str()) {
+            let task = poll_task(
+                conn_mgr,
+                profile_name,
+                task_id,
+                async_ops.wait_timeout,
+                async_ops.wait_interval,
+            )
+            .await?;
+
+            let state = get_task_state(&task);
+            if state == "failed" || state == "error" || state == "processing-error" {
+                return Err(RedisCtlError::api(format!(
+                    "Deletion of database {} failed (task {}); subscription {} was not deleted",
+                    db_id, task_id, id
+                )));
+            }
+        }
+    }
 
     let response = client
         .delete_raw(&format!("/subscriptions/{}", id))
@@ -142,6 +580,73 @@ pub async fn delete_subscription(
     .await
 }
 
+/// Databases requested per page when listing a subscription's databases
+const DATABASE_PAGE_SIZE: usize = 100;
+
+/// Fetch every database belonging to a flexible subscription
+///
+/// Pages are read until one comes back empty or repeats databases already seen. An entry
+/// without a numeric `databaseId` is an error, since callers act on the IDs.
+pub(crate) async fn fetch_subscription_databases(
+    client: &redis_cloud::CloudClient,
+    id: u32,
+) -> CliResult<Vec<Value>> {
+    let mut databases: Vec<Value> = Vec::new();
+    let mut seen = std::collections::HashSet::new();
+    loop {
+        let response = client
+            .get_raw(&format!(
+                "/subscriptions/{}/databases?offset={}&limit={}",
+                id,
+                databases.len(),
+                DATABASE_PAGE_SIZE
+            ))
+            .await
+            .context("Failed to list subscription databases")?;
+        let mut added = false;
+        for db in databases_in(response) {
+            if seen.insert(database_id(&db, id)?) {
+                databases.push(db);
+                added = true;
+            }
+        }
+        if !added {
+            return Ok(databases);
+        }
+    }
+}
+
+/// The databases in one page of `/subscriptions/{id}/databases`
+///
+/// The API nests databases under `subscription[].databases`; a bare array is accepted too.
+fn databases_in(response: Value) -> Vec<Value> {
+    match response {
+        Value::Array(databases) => databases,
+        other => other
+            .get("subscription")
+            .and_then(|s| s.as_array())
+            .map(|subs| {
+                subs.iter()
+                    .filter_map(|sub| sub.get("databases").and_then(|d| d.as_array()))
+                    .flatten()
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_default(),
+    }
+}
+
+/// The `databaseId` of a database listed in `subscription`
+pub(crate) fn database_id(db: &Value, subscription: u32) -> CliResult<u64> {
+    db.get("databaseId").and_then(Value::as_u64).ok_or_else(|| {
+        RedisCtlError::api(format!(
+            "Subscription {} lists a database without a numeric databaseId: {}",
+            subscription,
+            db.get("databaseId").unwrap_or(&Value::Null)
+        ))
+    })
+}
+
 /// Redis version info for table display
 #[derive(Tabled)]
 struct RedisVersionRow {
@@ -195,7 +700,7 @@ pub async fn get_redis_versions(
             }
 
             if rows.is_empty() {
-                println!("No Redis versions found");
+                notice!("No Redis versions found");
             } else {
                 let mut table = Table::new(rows);
                 table.with(Style::modern());
@@ -294,7 +799,7 @@ pub async fn get_cidr_allowlist(
             }
 
             if entries.is_empty() {
-                println!("No CIDR blocks configured");
+                notice!("No CIDR blocks configured");
             } else {
                 let mut table = Table::new(entries);
                 table.with(Style::modern());
@@ -332,7 +837,7 @@ pub async fn update_cidr_allowlist(
 
     match output_format {
         OutputFormat::Table => {
-            println!("CIDR allowlist updated successfully");
+            notice!("CIDR allowlist updated successfully");
             if let Some(task_id) = result.get("taskId") {
                 println!("Task ID: {}", task_id);
             }
@@ -391,7 +896,7 @@ pub async fn get_maintenance_windows(
             }
 
             if rows.is_empty() {
-                println!("No maintenance windows configured");
+                notice!("No maintenance windows configured");
             } else {
                 let mut table = Table::new(rows);
                 table.with(Style::modern());
@@ -432,7 +937,7 @@ pub async fn update_maintenance_windows(
 
     match output_format {
         OutputFormat::Table => {
-            println!("Maintenance windows updated successfully");
+            notice!("Maintenance windows updated successfully");
             if let Some(task_id) = result.get("taskId") {
                 println!("Task ID: {}", task_id);
             }
@@ -490,7 +995,7 @@ pub async fn list_aa_regions(
             }
 
             if rows.is_empty() {
-                println!("No Active-Active regions found");
+                notice!("No Active-Active regions found");
             } else {
                 let mut table = Table::new(rows);
                 table.with(Style::modern());
@@ -528,7 +1033,7 @@ pub async fn add_aa_region(
 
     match output_format {
         OutputFormat::Table => {
-            println!("Active-Active region added successfully");
+            notice!("Active-Active region added successfully");
             if let Some(task_id) = result.get("taskId") {
                 println!("Task ID: {}", task_id);
             }
@@ -552,6 +1057,10 @@ pub async fn delete_aa_regions(
     // Confirmation prompt unless --force is used
     if !force {
         use dialoguer::Confirm;
+        crate::interactive::ensure_prompt_allowed(
+            &format!("Deleting regions of subscription {} needs confirmation", id),
+            "pass --force to delete without it",
+        )?;
         let confirm = Confirm::new()
             .with_prompt(format!(
                 "Are you sure you want to delete regions from Active-Active subscription {}?",
@@ -559,12 +1068,12 @@ pub async fn delete_aa_regions(
             ))
             .default(false)
             .interact()
-            .map_err(|e| RedisCtlError::InvalidInput {
+            .map_err(|e| RedisCtlError::Validation {
                 message: format!("Failed to read confirmation: {}", e),
             })?;
 
         if !confirm {
-            println!("Region deletion cancelled");
+            notice!("Region deletion cancelled");
             return Ok(());
         }
     }
@@ -585,7 +1094,7 @@ pub async fn delete_aa_regions(
 
     match output_format {
         OutputFormat::Table => {
-            println!("Active-Active regions deletion initiated");
+            notice!("Active-Active regions deletion initiated");
             if let Some(task_id) = result.get("taskId") {
                 println!("Task ID: {}", task_id);
             }
@@ -595,3 +1104,92 @@ pub async fn delete_aa_regions(
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn methods() -> PaymentMethods {
+        serde_json::from_value(json!({
+            "accountId": 1,
+            "paymentMethods": [
+                {"id": 42, "type": "Visa", "creditCardEndsWith": 4242},
+                {"id": 7, "type": "Mastercard", "creditCardEndsWith": 81}
+            ]
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_databases_in_nested_and_bare() {
+        let nested = json!({
+            "subscription": [{"databases": [{"databaseId": 1}, {"databaseId": 2}]}]
+        });
+        assert_eq!(databases_in(nested).len(), 2);
+        assert_eq!(databases_in(json!([{"databaseId": 3}])).len(), 1);
+        assert!(databases_in(json!({"subscription": []})).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_fetch_subscription_databases_reads_every_page() {
+        // 150 databases served 100 at a time, in the API's nested shape
+        async fn page(uri: axum::http::Uri) -> axum::Json<Value> {
+            let param = |name: &str| -> usize {
+                uri.query()
+                    .unwrap_or_default()
+                    .split('&')
+                    .find_map(|pair| pair.strip_prefix(name)?.strip_prefix('='))
+                    .unwrap()
+                    .parse()
+                    .unwrap()
+            };
+            let (offset, limit) = (param("offset"), param("limit"));
+            let databases: Vec<Value> = (offset..150.min(offset + limit))
+                .map(|id| json!({"databaseId": id + 1}))
+                .collect();
+            axum::Json(json!({"subscription": [{"databases": databases}]}))
+        }
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        let router =
+            axum::Router::new().route("/subscriptions/1/databases", axum::routing::get(page));
+        tokio::spawn(async move { axum::serve(listener, router).await });
+
+        let client = redis_cloud::CloudClient::builder()
+            .api_key("key".to_string())
+            .api_secret("secret".to_string())
+            .base_url(format!("http://{}", address))
+            .build()
+            .unwrap();
+        let databases = fetch_subscription_databases(&client, 1).await.unwrap();
+        assert_eq!(databases.len(), 150);
+        assert_eq!(databases[149]["databaseId"], 150);
+    }
+
+    #[test]
+    fn test_database_id_rejects_non_numeric() {
+        assert_eq!(database_id(&json!({"databaseId": 7}), 1).unwrap(), 7);
+        let err = database_id(&json!({"databaseId": "7a"}), 1).unwrap_err();
+        assert!(err.to_string().contains("\"7a\""));
+        assert!(database_id(&json!({"name": "cache"}), 1).is_err());
+    }
+
+    #[test]
+    fn test_describe_payment_method() {
+        let methods = methods();
+        assert_eq!(
+            describe_payment_method(methods.find(7).unwrap()),
+            "7 (Mastercard ending 0081)"
+        );
+    }
+
+    #[test]
+    fn test_unknown_payment_method_lists_available() {
+        let message = unknown_payment_method(9, &methods());
+        assert!(message.contains("Payment method 9 does not belong"));
+        assert!(message.contains("42 (Visa ending 4242), 7 (Mastercard ending 0081)"));
+
+        let empty: PaymentMethods = serde_json::from_value(json!({"accountId": 1})).unwrap();
+        assert!(unknown_payment_method(9, &empty).contains("no payment methods"));
+    }
+}
@@ -5,10 +5,11 @@
 use crate::cli::{CloudTaskCommands, OutputFormat};
 use crate::connection::ConnectionManager;
 use crate::error::{RedisCtlError, Result as CliResult};
+use crate::output::notice;
 use crate::output::print_output;
+use crate::progress::{self, TaskProgress};
 use anyhow::Context;
 use colored::Colorize;
-use indicatif::{ProgressBar, ProgressStyle};
 use redis_cloud::CloudClient;
 use serde_json::Value;
 use std::time::Duration;
@@ -56,6 +57,30 @@ pub async fn handle_task_command(
             )
             .await
         }
+        CloudTaskCommands::Notify {
+            id,
+            webhook,
+            on,
+            format,
+            timeout,
+            interval,
+        } => {
+            super::task_notify::notify_task(
+                conn_mgr,
+                profile_name,
+                id,
+                &super::task_notify::NotifyOptions {
+                    webhook,
+                    on,
+                    format: *format,
+                    timeout_secs: *timeout,
+                    interval_secs: *interval,
+                },
+                output_format,
+                query,
+            )
+            .await
+        }
     }
 }
 
@@ -95,6 +120,11 @@ async fn get_task(
                 }
             })?;
         }
+        OutputFormat::Id => {
+            crate::output::print_ids(&data).map_err(|e| RedisCtlError::OutputError {
+                message: e.to_string(),
+            })?;
+        }
     }
 
     Ok(())
@@ -114,23 +144,16 @@ async fn wait_for_task(
     let timeout = Duration::from_secs(timeout_secs);
     let interval = Duration::from_secs(interval_secs);
 
-    // Create progress bar
-    let pb = ProgressBar::new_spinner();
-    pb.set_style(
-        ProgressStyle::default_spinner()
-            .template("{spinner:.green} {msg} [{elapsed_precise}]")
-            .unwrap(),
-    );
-    pb.set_message(format!("Waiting for task {}", task_id));
+    let progress = TaskProgress::start(task_id);
 
     loop {
         let task = fetch_task(&client, task_id).await?;
         let state = get_task_state(&task);
 
-        pb.set_message(format!("Task {}: {}", task_id, format_task_state(&state)));
+        progress.update(&state, &format_task_state(&state));
 
         if is_terminal_state(&state) {
-            pb.finish_with_message(format!("Task {}: {}", task_id, format_task_state(&state)));
+            progress.finish(&state, &format_task_state(&state));
 
             match output_format {
                 OutputFormat::Auto | OutputFormat::Table => {
@@ -142,13 +165,24 @@ async fn wait_for_task(
                 OutputFormat::Yaml => {
                     print_output(task, crate::output::OutputFormat::Yaml, None)?;
                 }
+                OutputFormat::Id => {
+                    let id = crate::output::task_resource_id(&task).ok_or_else(|| {
+                        RedisCtlError::OutputError {
+                            message: format!(
+                                "Task {} finished without reporting a resource ID",
+                                task_id
+                            ),
+                        }
+                    })?;
+                    println!("{}", id);
+                }
             }
 
             return Ok(());
         }
 
         if start.elapsed() > timeout {
-            pb.finish_with_message(format!("Timeout waiting for task {}", task_id));
+            progress.timeout();
             return Err(RedisCtlError::Timeout {
                 message: format!(
                     "Task {} did not complete within {} seconds",
@@ -174,17 +208,25 @@ async fn poll_task(
     let interval = Duration::from_secs(interval_secs);
     let mut poll_count = 0u64;
 
-    println!(
+    notice!(
         "Polling task {} every {} seconds...",
-        task_id, interval_secs
+        task_id,
+        interval_secs
     );
-    println!("Press Ctrl+C to stop\n");
+    notice!("Press Ctrl+C to stop\n");
 
     loop {
         let task = fetch_task(&client, task_id).await?;
         let state = get_task_state(&task);
         let timestamp = chrono::Local::now().format("%H:%M:%S");
 
+        if progress::is_json() {
+            progress::emit(
+                "task_status",
+                serde_json::json!({ "task_id": task_id, "state": state, "poll": poll_count + 1 }),
+            );
+        }
+
         // Clear screen for table output in auto mode
         if matches!(output_format, OutputFormat::Auto | OutputFormat::Table) {
             // Move cursor up to overwrite previous output
@@ -236,18 +278,16 @@ async fn poll_task(
 }
 
 /// Fetch task details from API
-async fn fetch_task(client: &CloudClient, task_id: &str) -> CliResult<Value> {
+pub(super) async fn fetch_task(client: &CloudClient, task_id: &str) -> CliResult<Value> {
     client
         .get_raw(&format!("/tasks/{}", task_id))
         .await
         .with_context(|| format!("Failed to fetch task {}", task_id))
-        .map_err(|e| RedisCtlError::ApiError {
-            message: e.to_string(),
-        })
+        .map_err(|e| RedisCtlError::api(e.to_string()))
 }
 
 /// Extract task state from response
-fn get_task_state(task: &Value) -> String {
+pub(super) fn get_task_state(task: &Value) -> String {
     task.get("state")
         .or_else(|| task.get("status"))
         .and_then(|s| s.as_str())
@@ -256,19 +296,26 @@ fn get_task_state(task: &Value) -> String {
 }
 
 /// Check if task is in terminal state
-fn is_terminal_state(state: &str) -> bool {
+pub(super) fn is_terminal_state(state: &str) -> bool {
     matches!(
         state.to_lowercase().as_str(),
-        "completed" | "failed" | "error" | "success" | "cancelled" | "aborted"
+        "completed"
+            | "failed"
+            | "error"
+            | "success"
+            | "cancelled"
+            | "aborted"
+            | "processing-completed"
+            | "processing-error"
     )
 }
 
 /// Format task state with color
-fn format_task_state(state: &str) -> String {
+pub(super) fn format_task_state(state: &str) -> String {
     match state.to_lowercase().as_str() {
         "processing" | "running" | "in_progress" => state.yellow().to_string(),
-        "completed" | "success" => state.green().to_string(),
-        "failed" | "error" => state.red().to_string(),
+        "completed" | "success" | "processing-completed" => state.green().to_string(),
+        "failed" | "error" | "processing-error" => state.red().to_string(),
         "cancelled" | "aborted" => state.dimmed().to_string(),
         _ => state.to_string(),
     }
@@ -313,10 +360,15 @@ fn print_task_details(task: &Value) -> CliResult<()> {
     }
 
     // Resource info
-    if let Some(resource) = task.get("resourceId").and_then(|r| r.as_str()) {
+    let resource = task
+        .get("resourceId")
+        .and_then(|r| r.as_str())
+        .map(str::to_string)
+        .or_else(|| crate::output::task_resource_id(task));
+    if let Some(resource) = resource {
         rows.push(DetailRow {
             field: "Resource ID".to_string(),
-            value: resource.to_string(),
+            value: resource,
         });
     }
 
@@ -359,7 +411,7 @@ fn print_task_details(task: &Value) -> CliResult<()> {
     }
 
     if rows.is_empty() {
-        println!("No task information available");
+        notice!("No task information available");
         return Ok(());
     }
 
@@ -8,6 +8,7 @@ use super::utils::*;
 use crate::cli::{CloudUserCommands, OutputFormat};
 use crate::connection::ConnectionManager;
 use crate::error::{RedisCtlError, Result as CliResult};
+use crate::output::notice;
 use crate::output::print_output;
 use anyhow::Context;
 use colored::Colorize;
@@ -107,6 +108,11 @@ async fn list_users(
                 }
             })?;
         }
+        OutputFormat::Id => {
+            crate::output::print_ids(&data).map_err(|e| RedisCtlError::OutputError {
+                message: e.to_string(),
+            })?;
+        }
     }
 
     Ok(())
@@ -142,12 +148,12 @@ fn print_users_table(data: &Value) -> CliResult<()> {
     } else if data.is_object() {
         vec![data.clone()]
     } else {
-        println!("No users found");
+        notice!("No users found");
         return Ok(());
     };
 
     if users.is_empty() {
-        println!("No users found");
+        notice!("No users found");
         return Ok(());
     }
 
@@ -303,6 +309,11 @@ async fn get_user(
                 }
             })?;
         }
+        OutputFormat::Id => {
+            crate::output::print_ids(&data).map_err(|e| RedisCtlError::OutputError {
+                message: e.to_string(),
+            })?;
+        }
     }
 
     Ok(())
@@ -419,7 +430,7 @@ fn print_user_detail(data: &Value) -> CliResult<()> {
     }
 
     if rows.is_empty() {
-        println!("No user information available");
+        notice!("No user information available");
         return Ok(());
     }
 
@@ -480,7 +491,7 @@ async fn update_user(
         // Validate role
         let valid_roles = ["owner", "manager", "viewer", "billing_admin"];
         if !valid_roles.contains(&role.to_lowercase().as_str()) {
-            return Err(RedisCtlError::InvalidInput {
+            return Err(RedisCtlError::Validation {
                 message: format!(
                     "Invalid role '{}'. Valid roles are: owner, manager, viewer, billing_admin",
                     role
@@ -503,7 +514,7 @@ async fn update_user(
     }
 
     if payload.as_object().unwrap().is_empty() {
-        return Err(RedisCtlError::InvalidInput {
+        return Err(RedisCtlError::Validation {
             message: "No fields to update. Please specify at least one field to update."
                 .to_string(),
         });
@@ -523,7 +534,7 @@ async fn update_user(
 
     match output_format {
         OutputFormat::Auto | OutputFormat::Table => {
-            println!("User {} updated successfully", user_id);
+            notice!("User {} updated successfully", user_id);
             print_user_detail(&data)?;
         }
         OutputFormat::Json => {
@@ -540,6 +551,11 @@ async fn update_user(
                 }
             })?;
         }
+        OutputFormat::Id => {
+            crate::output::print_ids(&data).map_err(|e| RedisCtlError::OutputError {
+                message: e.to_string(),
+            })?;
+        }
     }
 
     Ok(())
@@ -557,6 +573,10 @@ async fn delete_user(
 ) -> CliResult<()> {
     // Confirm deletion unless forced
     if !force {
+        crate::interactive::ensure_prompt_allowed(
+            &format!("Deleting user {} needs confirmation", user_id),
+            "pass --force to delete without it",
+        )?;
         print!("Are you sure you want to delete user {}? [y/N]: ", user_id);
         std::io::Write::flush(&mut std::io::stdout())?;
 
@@ -564,7 +584,7 @@ async fn delete_user(
         std::io::stdin().read_line(&mut input)?;
 
         if !input.trim().eq_ignore_ascii_case("y") && !input.trim().eq_ignore_ascii_case("yes") {
-            println!("Operation cancelled");
+            notice!("Operation cancelled");
             return Ok(());
         }
     }
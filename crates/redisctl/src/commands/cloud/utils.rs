@@ -1,7 +1,7 @@
 //! Shared utilities for cloud command implementations
 
 use anyhow::Context;
-use chrono::{DateTime, Utc};
+use chrono::Utc;
 use colored::Colorize;
 use redis_cloud::CloudClient;
 use serde_json::Value;
@@ -12,7 +12,7 @@ use tabled::Tabled;
 #[cfg(unix)]
 use std::io::IsTerminal;
 
-use crate::cli::OutputFormat;
+use crate::cli::{LogExportFormat, OutputFormat};
 use crate::config::Profile;
 use crate::error::{RedisCtlError, Result as CliResult};
 use crate::output::print_output;
@@ -142,14 +142,8 @@ pub fn format_date(date_str: String) -> String {
         return "—".to_string();
     }
 
-    // If it's already formatted (e.g., "2024-04-09 02:22:05"), keep it
-    if date_str.contains(' ') && !date_str.contains('T') {
-        return date_str;
-    }
-
-    // Try to parse as ISO8601/RFC3339
-    if let Ok(dt) = DateTime::parse_from_rfc3339(&date_str) {
-        let utc: DateTime<Utc> = dt.into();
+    // RFC3339, "2024-04-09 02:22:05" (UTC) or epoch seconds/milliseconds
+    if let Some(utc) = crate::timestamps::parse_timestamp_str(&date_str) {
         let now = Utc::now();
         let duration = now.signed_duration_since(utc);
 
@@ -163,8 +157,14 @@ pub fn format_date(date_str: String) -> String {
             return format!("{} days ago", duration.num_days());
         }
 
-        // Show date for older items
-        return utc.format("%Y-%m-%d").to_string();
+        // Show the date for older items, in the zone picked with --utc/--local
+        return match crate::timestamps::mode() {
+            crate::timestamps::TimeZoneMode::Utc => utc.format("%Y-%m-%d").to_string(),
+            crate::timestamps::TimeZoneMode::Local => utc
+                .with_timezone(&chrono::Local)
+                .format("%Y-%m-%d")
+                .to_string(),
+        };
     }
 
     // Fallback to original string
@@ -192,7 +192,7 @@ pub fn provider_short_name(provider: &str) -> &str {
 
 /// Apply JMESPath query to JSON data
 pub fn apply_jmespath(data: &Value, query: &str) -> CliResult<Value> {
-    let expr = jmespath::compile(query)
+    let expr = crate::query::compile(query)
         .with_context(|| format!("Invalid JMESPath expression: {}", query))?;
 
     let result = expr
@@ -237,6 +237,11 @@ pub fn print_formatted_output(data: Value, output_format: OutputFormat) -> CliRe
                 }
             })?;
         }
+        OutputFormat::Id => {
+            crate::output::print_ids(&data).map_err(|e| RedisCtlError::OutputError {
+                message: e.to_string(),
+            })?;
+        }
         _ => {} // Table format handled by individual commands
     }
     Ok(())
@@ -244,6 +249,10 @@ pub fn print_formatted_output(data: Value, output_format: OutputFormat) -> CliRe
 
 /// Prompts the user for confirmation
 pub fn confirm_action(message: &str) -> CliResult<bool> {
+    crate::interactive::ensure_prompt_allowed(
+        &format!("Confirmation required to {}", message),
+        "pass --force to proceed without it",
+    )?;
     print!("Are you sure you want to {}? [y/N]: ", message);
     io::stdout().flush()?;
 
@@ -290,3 +299,42 @@ pub fn read_file_input(input: &str) -> CliResult<String> {
         Ok(input.to_string())
     }
 }
+
+/// Write entries as CSV (selected columns) or NDJSON to stdout
+pub fn export_entries(
+    entries: &[Value],
+    columns: &[&str],
+    format: LogExportFormat,
+) -> CliResult<()> {
+    match format {
+        LogExportFormat::Ndjson => {
+            for entry in entries {
+                println!("{}", serde_json::to_string(entry)?);
+            }
+        }
+        LogExportFormat::Csv => {
+            println!("{}", columns.join(","));
+            for entry in entries {
+                let record: Vec<String> = columns
+                    .iter()
+                    .map(|column| match entry.get(*column) {
+                        None | Some(Value::Null) => String::new(),
+                        Some(Value::String(s)) => csv_field(s),
+                        Some(other) => csv_field(&other.to_string()),
+                    })
+                    .collect();
+                println!("{}", record.join(","));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Quote a CSV field when it contains separators, quotes or newlines (RFC 4180)
+pub fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
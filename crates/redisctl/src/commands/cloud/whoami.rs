@@ -0,0 +1,215 @@
+//! Summary of the account and API key a Cloud profile acts as
+//!
+//! `whoami` reads the account and key details from `GET /`, resolves the key owner's account role
+//! from `GET /users` and reports the rate-limit headers of those responses.
+
+#![allow(dead_code)]
+
+use crate::cli::OutputFormat;
+use crate::connection::ConnectionManager;
+use crate::error::Result as CliResult;
+use anyhow::Context;
+use redis_cloud::users::UsersHandler;
+use redis_cloud::{AccountHandler, RateLimit};
+use serde::Serialize;
+use serde_json::Value;
+
+use super::utils::*;
+
+/// Who the current credentials belong to and what they may do
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Whoami {
+    pub account_id: Option<i64>,
+    pub account_name: Option<String>,
+    pub api_key_name: Option<String>,
+    pub owner_name: Option<String>,
+    pub owner_email: Option<String>,
+    /// Account role of the key owner, which bounds what the key may do
+    pub role: Option<String>,
+    /// Source IPs the key is allowed to connect from
+    pub allowed_source_ips: Vec<String>,
+    /// Address the API saw this request come from
+    pub source_ip: Option<String>,
+    pub rate_limit: Option<RateLimit>,
+}
+
+pub async fn whoami(
+    conn_mgr: &ConnectionManager,
+    profile_name: Option<&str>,
+    output_format: OutputFormat,
+    query: Option<&str>,
+) -> CliResult<()> {
+    let client = conn_mgr.create_cloud_client(profile_name).await?;
+    let accounts = AccountHandler::new(client.clone());
+    let users = UsersHandler::new(client);
+    let (account, users) = tokio::join!(accounts.get_current_account(), users.get_all_users());
+    let account = account.context("Failed to get account")?;
+
+    // Keys that may not list users still get the rest of the summary
+    let users = users
+        .ok()
+        .and_then(|users| serde_json::to_value(users).ok());
+    let mut info = build_whoami(
+        &serde_json::to_value(account).context("Failed to serialize account")?,
+        users.as_ref(),
+    );
+    info.rate_limit = crate::api_stats::global().cloud_rate_limit();
+
+    match output_format {
+        OutputFormat::Auto | OutputFormat::Table if query.is_none() => {
+            print_whoami(&info);
+            Ok(())
+        }
+        _ => {
+            let json = serde_json::to_value(&info).context("Failed to serialize account")?;
+            let data = handle_output(json, output_format, query)?;
+            print_formatted_output(data, output_format)
+        }
+    }
+}
+
+/// Combine the bodies of `GET /` and `GET /users` into a summary
+pub fn build_whoami(account: &Value, users: Option<&Value>) -> Whoami {
+    let account = account.get("account").unwrap_or(account);
+    let key = account.get("key").unwrap_or(&Value::Null);
+    let owner = key.get("owner").unwrap_or(&Value::Null);
+    let owner_email = string(owner, "email");
+    let owner_id = key.get("userAccountId").and_then(Value::as_i64);
+
+    let role = users
+        .and_then(|users| users.get("users"))
+        .and_then(Value::as_array)
+        .and_then(|users| {
+            users.iter().find(|user| {
+                let id = user.get("id").and_then(Value::as_i64);
+                (owner_id.is_some() && id == owner_id)
+                    || (owner_email.is_some() && string(user, "email") == owner_email)
+            })
+        })
+        .and_then(|user| string(user, "role"));
+
+    Whoami {
+        account_id: account.get("id").and_then(Value::as_i64),
+        account_name: string(account, "name").or_else(|| string(key, "accountName")),
+        api_key_name: string(key, "name"),
+        owner_name: string(owner, "name"),
+        owner_email,
+        role,
+        allowed_source_ips: key
+            .get("allowedSourceIps")
+            .and_then(Value::as_array)
+            .map(|ips| {
+                ips.iter()
+                    .filter_map(|ip| ip.as_str().map(str::to_string))
+                    .collect()
+            })
+            .unwrap_or_default(),
+        source_ip: string(key, "httpSourceIp"),
+        rate_limit: None,
+    }
+}
+
+fn string(value: &Value, key: &str) -> Option<String> {
+    value.get(key).and_then(Value::as_str).map(str::to_string)
+}
+
+fn print_whoami(info: &Whoami) {
+    let or_dash = |value: &Option<String>| value.clone().unwrap_or_else(|| "-".to_string());
+    let account = match (&info.account_name, info.account_id) {
+        (Some(name), Some(id)) => format!("{} ({})", name, id),
+        (Some(name), None) => name.clone(),
+        (None, Some(id)) => id.to_string(),
+        (None, None) => "-".to_string(),
+    };
+    let owner = match (&info.owner_name, &info.owner_email) {
+        (Some(name), Some(email)) => format!("{} <{}>", name, email),
+        (name, email) => or_dash(&name.clone().or_else(|| email.clone())),
+    };
+    let source_ips = if info.allowed_source_ips.is_empty() {
+        "-".to_string()
+    } else {
+        info.allowed_source_ips.join(", ")
+    };
+
+    println!("Account:     {}", account);
+    println!("API key:     {}", or_dash(&info.api_key_name));
+    println!("Owner:       {}", owner);
+    println!("Role:        {}", or_dash(&info.role));
+    println!("Allowed IPs: {}", source_ips);
+    println!("Source IP:   {}", or_dash(&info.source_ip));
+    println!("Rate limit:  {}", format_rate_limit(info.rate_limit));
+}
+
+/// E.g. `398 of 400 requests left, resets in 42s`
+fn format_rate_limit(rate_limit: Option<RateLimit>) -> String {
+    let Some(rate_limit) = rate_limit else {
+        return "not reported".to_string();
+    };
+    let mut text = match (rate_limit.remaining, rate_limit.limit) {
+        (Some(remaining), Some(limit)) => format!("{} of {} requests left", remaining, limit),
+        (Some(remaining), None) => format!("{} requests left", remaining),
+        (None, Some(limit)) => format!("{} requests per window", limit),
+        (None, None) => String::new(),
+    };
+    if let Some(reset) = rate_limit.reset {
+        if !text.is_empty() {
+            text.push_str(", ");
+        }
+        text.push_str(&format!("resets in {}s", reset));
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_build_whoami() {
+        let account = json!({
+            "account": {
+                "id": 40131,
+                "name": "Acme",
+                "key": {
+                    "name": "ci-key",
+                    "accountName": "Acme",
+                    "allowedSourceIps": ["10.0.0.0/8"],
+                    "owner": {"name": "Jo Ops", "email": "jo@acme.io"},
+                    "httpSourceIp": "203.0.113.7"
+                }
+            }
+        });
+        let users = json!({
+            "account": 40131,
+            "users": [
+                {"id": 1, "email": "sam@acme.io", "role": "Owner"},
+                {"id": 2, "email": "jo@acme.io", "role": "Viewer"}
+            ]
+        });
+
+        let info = build_whoami(&account, Some(&users));
+        assert_eq!(info.account_id, Some(40131));
+        assert_eq!(info.api_key_name.as_deref(), Some("ci-key"));
+        assert_eq!(info.owner_email.as_deref(), Some("jo@acme.io"));
+        assert_eq!(info.role.as_deref(), Some("Viewer"));
+        assert_eq!(info.allowed_source_ips, vec!["10.0.0.0/8"]);
+        assert_eq!(info.source_ip.as_deref(), Some("203.0.113.7"));
+
+        assert_eq!(build_whoami(&account, None).role, None);
+    }
+
+    #[test]
+    fn test_format_rate_limit() {
+        assert_eq!(format_rate_limit(None), "not reported");
+        assert_eq!(
+            format_rate_limit(Some(RateLimit {
+                limit: Some(400),
+                remaining: Some(398),
+                reset: Some(42),
+            })),
+            "398 of 400 requests left, resets in 42s"
+        );
+    }
+}
@@ -0,0 +1,494 @@
+//! Structural diff of a resource between two profiles
+//!
+//! `diff` runs the same GET against the selected profile and the `--against` profile and
+//! compares the two responses field by field, to spot drift between environments that are meant
+//! to mirror each other. Lists of objects are matched by `name` when every entry has one, so a
+//! database list compares database by database rather than by position. Fields that always
+//! differ between clusters or accounts (uids, timestamps) are left out unless `--all-fields` is
+//! given.
+
+#![allow(dead_code)]
+
+use crate::cli::OutputFormat;
+use crate::config::DeploymentType;
+use crate::connection::ConnectionManager;
+use crate::error::{RedisCtlError, Result as CliResult};
+use crate::output::notice;
+use anyhow::Context;
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+use super::cloud::utils::{handle_output, print_formatted_output};
+
+/// Fields skipped unless `--all-fields` is given, at any depth
+pub const VOLATILE_FIELDS: &[&str] = &[
+    "uid",
+    "created_time",
+    "last_changed_time",
+    "last_backup_time",
+    "epoch",
+    "id",
+    "databaseId",
+    "subscriptionId",
+    "activatedOn",
+    "lastModified",
+    "links",
+];
+
+/// Resources by name: (deployment, name, path without id, path with `{id}`)
+const RESOURCES: &[(DeploymentType, &str, &str, &str)] = &[
+    (DeploymentType::Enterprise, "cluster", "/v1/cluster", ""),
+    (
+        DeploymentType::Enterprise,
+        "database",
+        "/v1/bdbs",
+        "/v1/bdbs/{id}",
+    ),
+    (
+        DeploymentType::Enterprise,
+        "node",
+        "/v1/nodes",
+        "/v1/nodes/{id}",
+    ),
+    (
+        DeploymentType::Enterprise,
+        "user",
+        "/v1/users",
+        "/v1/users/{id}",
+    ),
+    (
+        DeploymentType::Enterprise,
+        "role",
+        "/v1/roles",
+        "/v1/roles/{id}",
+    ),
+    (
+        DeploymentType::Enterprise,
+        "redis-acl",
+        "/v1/redis_acls",
+        "/v1/redis_acls/{id}",
+    ),
+    (
+        DeploymentType::Enterprise,
+        "crdb",
+        "/v1/crdbs",
+        "/v1/crdbs/{id}",
+    ),
+    (
+        DeploymentType::Enterprise,
+        "module",
+        "/v1/modules",
+        "/v1/modules/{id}",
+    ),
+    (
+        DeploymentType::Enterprise,
+        "policy",
+        "/v1/cluster/policy",
+        "",
+    ),
+    (DeploymentType::Enterprise, "license", "/v1/license", ""),
+    (DeploymentType::Cloud, "account", "/", ""),
+    (
+        DeploymentType::Cloud,
+        "subscription",
+        "/subscriptions",
+        "/subscriptions/{id}",
+    ),
+    (
+        DeploymentType::Cloud,
+        "database",
+        "",
+        "/subscriptions/{subscription}/databases/{database}",
+    ),
+    (DeploymentType::Cloud, "user", "/users", "/users/{id}"),
+    (
+        DeploymentType::Cloud,
+        "acl-user",
+        "/acl/users",
+        "/acl/users/{id}",
+    ),
+    (DeploymentType::Cloud, "acl-role", "/acl/roles", ""),
+    (DeploymentType::Cloud, "acl-rule", "/acl/redisRules", ""),
+];
+
+/// One difference between the two responses
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Difference {
+    /// Dotted path of the field, with `[name]` or `[index]` for list entries
+    pub path: String,
+    /// Value in the selected profile, absent if only the other has it
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub left: Option<Value>,
+    /// Value in the `--against` profile, absent if only the selected one has it
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub right: Option<Value>,
+}
+
+#[derive(Debug, Serialize)]
+struct DiffReport {
+    path: String,
+    profile: String,
+    against: String,
+    differences: Vec<Difference>,
+}
+
+/// Options of the `diff` command
+#[derive(Debug)]
+pub struct DiffOptions<'a> {
+    pub deployment: DeploymentType,
+    pub resource: &'a str,
+    pub id: Option<&'a str>,
+    pub against: &'a str,
+    pub ignore: &'a [String],
+    pub all_fields: bool,
+    pub exit_code: bool,
+}
+
+pub async fn run_diff(
+    conn_mgr: &ConnectionManager,
+    profile_name: Option<&str>,
+    options: &DiffOptions<'_>,
+    output_format: OutputFormat,
+    query: Option<&str>,
+) -> CliResult<()> {
+    let path = resource_path(options.deployment, options.resource, options.id)?;
+    let (left, right) = match options.deployment {
+        DeploymentType::Cloud => {
+            let left = conn_mgr.create_cloud_client(profile_name).await?;
+            let right = conn_mgr.create_cloud_client(Some(options.against)).await?;
+            tokio::try_join!(left.get_raw(&path), right.get_raw(&path))
+                .with_context(|| format!("Failed to read {}", path))?
+        }
+        DeploymentType::Enterprise => {
+            let left = conn_mgr.create_enterprise_client(profile_name).await?;
+            let right = conn_mgr
+                .create_enterprise_client(Some(options.against))
+                .await?;
+            tokio::try_join!(left.get_raw(&path), right.get_raw(&path))
+                .with_context(|| format!("Failed to read {}", path))?
+        }
+    };
+
+    let mut ignored: Vec<&str> = options.ignore.iter().map(String::as_str).collect();
+    if !options.all_fields {
+        ignored.extend(VOLATILE_FIELDS);
+    }
+    let differences = diff_values(&left, &right, &ignored);
+
+    let report = DiffReport {
+        path,
+        profile: profile_name
+            .map(str::to_string)
+            .or_else(|| conn_mgr.config.default_profile.clone())
+            .unwrap_or_else(|| "default".to_string()),
+        against: options.against.to_string(),
+        differences,
+    };
+
+    match output_format {
+        OutputFormat::Auto | OutputFormat::Table if query.is_none() => {
+            if report.differences.is_empty() {
+                notice!(
+                    "No differences in {} between '{}' and '{}'",
+                    report.path,
+                    report.profile,
+                    report.against
+                );
+            } else {
+                println!("--- {} ({})", report.profile, report.path);
+                println!("+++ {} ({})", report.against, report.path);
+                for difference in &report.differences {
+                    println!("{}", format_difference(difference));
+                }
+            }
+        }
+        _ => {
+            let json = serde_json::to_value(&report).context("Failed to serialize diff")?;
+            let data = handle_output(json, output_format, query)?;
+            print_formatted_output(data, output_format)?;
+        }
+    }
+
+    if options.exit_code && !report.differences.is_empty() {
+        let count = report.differences.len();
+        return Err(RedisCtlError::Validation {
+            message: format!(
+                "{} difference{} between '{}' and '{}'",
+                count,
+                if count == 1 { "" } else { "s" },
+                report.profile,
+                report.against
+            ),
+        });
+    }
+    Ok(())
+}
+
+/// API path of a resource, or the resource itself when it is a path
+pub fn resource_path(
+    deployment: DeploymentType,
+    resource: &str,
+    id: Option<&str>,
+) -> CliResult<String> {
+    if resource.starts_with('/') {
+        return Ok(resource.to_string());
+    }
+    let known: Vec<&str> = RESOURCES
+        .iter()
+        .filter(|(d, ..)| *d == deployment)
+        .map(|(_, name, ..)| *name)
+        .collect();
+    let (_, _, list_path, item_path) = RESOURCES
+        .iter()
+        .find(|(d, name, ..)| *d == deployment && *name == resource)
+        .ok_or_else(|| RedisCtlError::Validation {
+            message: format!(
+                "Unknown resource '{}'; expected an API path or one of: {}",
+                resource,
+                known.join(", ")
+            ),
+        })?;
+
+    match id {
+        None if list_path.is_empty() => Err(RedisCtlError::Validation {
+            message: format!("'{}' needs an id", resource),
+        }),
+        None => Ok(list_path.to_string()),
+        Some(_) if item_path.is_empty() => Err(RedisCtlError::Validation {
+            message: format!("'{}' does not take an id", resource),
+        }),
+        Some(id) if item_path.contains("{subscription}") => {
+            let (subscription, database) =
+                id.split_once(':')
+                    .ok_or_else(|| RedisCtlError::Validation {
+                        message: format!(
+                            "Invalid database id '{}': expected subscription_id:database_id",
+                            id
+                        ),
+                    })?;
+            Ok(item_path
+                .replace("{subscription}", subscription)
+                .replace("{database}", database))
+        }
+        Some(id) => Ok(item_path.replace("{id}", id)),
+    }
+}
+
+/// Every difference between two values, skipping fields named in `ignored`
+pub fn diff_values(left: &Value, right: &Value, ignored: &[&str]) -> Vec<Difference> {
+    let mut differences = Vec::new();
+    diff_into(String::new(), left, right, ignored, &mut differences);
+    differences
+}
+
+fn diff_into(
+    path: String,
+    left: &Value,
+    right: &Value,
+    ignored: &[&str],
+    out: &mut Vec<Difference>,
+) {
+    match (left, right) {
+        (Value::Object(l), Value::Object(r)) => {
+            let mut keys: Vec<&String> = l.keys().chain(r.keys()).collect();
+            keys.sort();
+            keys.dedup();
+            for key in keys {
+                if ignored.contains(&key.as_str()) {
+                    continue;
+                }
+                let child = if path.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{}.{}", path, key)
+                };
+                compare_entries(child, l.get(key), r.get(key), ignored, out);
+            }
+        }
+        (Value::Array(l), Value::Array(r)) => match (by_name(l), by_name(r)) {
+            (Some(l), Some(r)) => {
+                let mut names: Vec<&String> = l.keys().chain(r.keys()).collect();
+                names.sort();
+                names.dedup();
+                for name in names {
+                    let child = format!("{}[{}]", path, name);
+                    compare_entries(child, l.get(name), r.get(name), ignored, out);
+                }
+            }
+            _ => {
+                for index in 0..l.len().max(r.len()) {
+                    let child = format!("{}[{}]", path, index);
+                    compare_entries(child, l.get(index), r.get(index), ignored, out);
+                }
+            }
+        },
+        _ if left != right => out.push(Difference {
+            path,
+            left: Some(left.clone()),
+            right: Some(right.clone()),
+        }),
+        _ => {}
+    }
+}
+
+fn compare_entries(
+    path: String,
+    left: Option<&Value>,
+    right: Option<&Value>,
+    ignored: &[&str],
+    out: &mut Vec<Difference>,
+) {
+    match (left, right) {
+        (Some(l), Some(r)) => diff_into(path, l, r, ignored, out),
+        (l, r) => out.push(Difference {
+            path,
+            left: l.map(|v| strip_ignored(v, ignored)),
+            right: r.map(|v| strip_ignored(v, ignored)),
+        }),
+    }
+}
+
+/// Objects of a list keyed by their `name`, if every entry has a distinct one
+fn by_name(values: &[Value]) -> Option<Map<String, Value>> {
+    let mut map = Map::new();
+    for value in values {
+        let name = value.get("name")?.as_str()?;
+        if map.insert(name.to_string(), value.clone()).is_some() {
+            return None;
+        }
+    }
+    (!map.is_empty()).then_some(map)
+}
+
+/// A value without its ignored fields, for entries present on one side only
+fn strip_ignored(value: &Value, ignored: &[&str]) -> Value {
+    match value {
+        Value::Object(map) => Value::Object(
+            map.iter()
+                .filter(|(key, _)| !ignored.contains(&key.as_str()))
+                .map(|(key, value)| (key.clone(), strip_ignored(value, ignored)))
+                .collect(),
+        ),
+        Value::Array(values) => Value::Array(
+            values
+                .iter()
+                .map(|value| strip_ignored(value, ignored))
+                .collect(),
+        ),
+        other => other.clone(),
+    }
+}
+
+/// One line per difference: `~` changed, `-` only in the selected profile, `+` only in the other
+pub fn format_difference(difference: &Difference) -> String {
+    let path = if difference.path.is_empty() {
+        "(root)"
+    } else {
+        &difference.path
+    };
+    match (&difference.left, &difference.right) {
+        (Some(l), Some(r)) => format!("~ {}: {} -> {}", path, l, r),
+        (Some(l), None) => format!("- {}: {}", path, l),
+        (None, Some(r)) => format!("+ {}: {}", path, r),
+        (None, None) => format!("  {}", path),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_resource_path() {
+        let path = |deployment, resource, id| resource_path(deployment, resource, id);
+        assert_eq!(
+            path(DeploymentType::Enterprise, "database", Some("1")).unwrap(),
+            "/v1/bdbs/1"
+        );
+        assert_eq!(
+            path(DeploymentType::Enterprise, "database", None).unwrap(),
+            "/v1/bdbs"
+        );
+        assert_eq!(
+            path(DeploymentType::Cloud, "database", Some("12:34")).unwrap(),
+            "/subscriptions/12/databases/34"
+        );
+        assert_eq!(
+            path(DeploymentType::Cloud, "/acl/users", None).unwrap(),
+            "/acl/users"
+        );
+        assert!(path(DeploymentType::Cloud, "database", None).is_err());
+        assert!(
+            path(DeploymentType::Enterprise, "cluster", Some("1"))
+                .unwrap_err()
+                .to_string()
+                .contains("does not take an id")
+        );
+        assert!(
+            path(DeploymentType::Cloud, "node", None)
+                .unwrap_err()
+                .to_string()
+                .contains("account, subscription")
+        );
+    }
+
+    #[test]
+    fn test_diff_values() {
+        let left = json!({
+            "uid": 1,
+            "name": "cache",
+            "memory_size": 1073741824u64,
+            "module_list": [{"module_name": "search"}],
+            "tags": ["a"]
+        });
+        let right = json!({
+            "uid": 7,
+            "name": "cache",
+            "memory_size": 2147483648u64,
+            "module_list": [{"module_name": "search"}, {"module_name": "json"}],
+            "eviction_policy": "noeviction"
+        });
+        let differences = diff_values(&left, &right, VOLATILE_FIELDS);
+        let lines: Vec<String> = differences.iter().map(format_difference).collect();
+        assert_eq!(
+            lines,
+            vec![
+                "+ eviction_policy: \"noeviction\"",
+                "~ memory_size: 1073741824 -> 2147483648",
+                "+ module_list[1]: {\"module_name\":\"json\"}",
+                "- tags: [\"a\"]",
+            ]
+        );
+
+        let with_uid = diff_values(&left, &right, &[]);
+        assert!(with_uid.iter().any(|d| d.path == "uid"));
+    }
+
+    #[test]
+    fn test_diff_values_matches_lists_by_name() {
+        let left = json!([
+            {"uid": 1, "name": "cache", "port": 12000},
+            {"uid": 2, "name": "sessions", "port": 12001}
+        ]);
+        let right = json!([
+            {"uid": 5, "name": "sessions", "port": 12001},
+            {"uid": 6, "name": "queue", "port": 12002}
+        ]);
+        let differences = diff_values(&left, &right, VOLATILE_FIELDS);
+        let lines: Vec<String> = differences.iter().map(format_difference).collect();
+        assert_eq!(
+            lines,
+            vec![
+                "- [cache]: {\"name\":\"cache\",\"port\":12000}",
+                "+ [queue]: {\"name\":\"queue\",\"port\":12002}",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_diff_values_identical() {
+        let value = json!({"name": "cache", "nested": {"a": [1, 2]}});
+        assert!(diff_values(&value, &value, &[]).is_empty());
+    }
+}
@@ -0,0 +1,985 @@
+//! Diagnosis of common setup problems
+//!
+//! Unlike `selftest`, which assumes a working setup and exercises the API, `doctor` looks for the
+//! reasons a setup does not work: a missing or unreadable config file, environment variables that
+//! silently override or half-override a profile, rejected credentials, a skewed clock and
+//! endpoints that fail at DNS, TCP or TLS level. Every finding that is not OK comes with a fix.
+
+#![allow(dead_code)]
+
+use crate::cli::OutputFormat;
+use crate::config::{Config, DeploymentType, Profile};
+use crate::connection::ConnectionManager;
+use crate::error::{RedisCtlError, Result as CliResult};
+use serde::Serialize;
+use std::path::Path;
+use std::time::Duration;
+
+/// Time allowed for each of the DNS, TCP and TLS stages
+const STAGE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Clock difference to the server above which a warning is reported
+const MAX_CLOCK_SKEW_SECS: i64 = 60;
+
+/// Environment variables that redisctl reads, with the deployment they belong to
+const CLOUD_ENV: &[&str] = &[
+    "REDIS_CLOUD_API_KEY",
+    "REDIS_CLOUD_SECRET_KEY",
+    "REDIS_CLOUD_API_URL",
+];
+const ENTERPRISE_ENV: &[&str] = &[
+    "REDIS_ENTERPRISE_URL",
+    "REDIS_ENTERPRISE_USER",
+    "REDIS_ENTERPRISE_PASSWORD",
+    "REDIS_ENTERPRISE_INSECURE",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Status {
+    Ok,
+    Warn,
+    Fail,
+    /// Not run because an earlier stage failed
+    Skip,
+}
+
+impl Status {
+    pub(crate) fn label(self) -> &'static str {
+        match self {
+            Status::Ok => "OK",
+            Status::Warn => "WARN",
+            Status::Fail => "FAIL",
+            Status::Skip => "SKIP",
+        }
+    }
+}
+
+/// Result of one check, with a suggested fix when it is not OK
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Finding {
+    pub check: String,
+    pub status: Status,
+    pub detail: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fix: Option<String>,
+}
+
+impl Finding {
+    pub(crate) fn ok(check: &str, detail: impl Into<String>) -> Self {
+        Self::new(check, Status::Ok, detail, None::<String>)
+    }
+
+    pub(crate) fn warn(check: &str, detail: impl Into<String>, fix: impl Into<String>) -> Self {
+        Self::new(check, Status::Warn, detail, Some(fix))
+    }
+
+    pub(crate) fn fail(check: &str, detail: impl Into<String>, fix: impl Into<String>) -> Self {
+        Self::new(check, Status::Fail, detail, Some(fix))
+    }
+
+    pub(crate) fn skip(check: &str) -> Self {
+        Self::new(
+            check,
+            Status::Skip,
+            "not run after an earlier failure",
+            None::<String>,
+        )
+    }
+
+    fn new(
+        check: &str,
+        status: Status,
+        detail: impl Into<String>,
+        fix: Option<impl Into<String>>,
+    ) -> Self {
+        Self {
+            check: check.to_string(),
+            status,
+            detail: detail.into(),
+            fix: fix.map(Into::into),
+        }
+    }
+}
+
+/// The full doctor report
+#[derive(Debug, Clone, Serialize)]
+pub struct DoctorReport {
+    pub redisctl_version: String,
+    pub config_path: Option<String>,
+    pub profile: Option<String>,
+    pub deployment: Option<String>,
+    pub endpoint: Option<String>,
+    pub findings: Vec<Finding>,
+    pub warnings: usize,
+    pub failures: usize,
+}
+
+/// Where the endpoint checks connect, and how
+struct Endpoint {
+    deployment: DeploymentType,
+    url: String,
+    insecure: bool,
+    /// Pinned server certificate, which replaces CA verification
+    tls_fingerprint_sha256: Option<String>,
+}
+
+pub async fn run_doctor(
+    conn_mgr: &ConnectionManager,
+    profile_name: Option<&str>,
+    deployment: Option<DeploymentType>,
+    output_format: OutputFormat,
+    query: Option<&str>,
+) -> CliResult<()> {
+    let env = |name: &str| std::env::var(name).ok().filter(|value| !value.is_empty());
+    let mut findings = Vec::new();
+
+    let config_path = Config::config_path().ok();
+    match &config_path {
+        Some(path) => findings.extend(config_file_findings(
+            path,
+            env_has_credentials(&env) || !conn_mgr.config.provided_profiles.is_empty(),
+        )),
+        None => findings.push(Finding::fail(
+            "config file",
+            "could not determine the config directory (no home directory)",
+            "Set HOME (or XDG_CONFIG_HOME) for the user running redisctl",
+        )),
+    }
+
+    let resolved_name = profile_name
+        .map(str::to_string)
+        .or_else(|| env("REDISCTL_PROFILE"))
+        .or_else(|| conn_mgr.config.default_profile.clone());
+    let profile = resolved_name
+        .as_deref()
+        .and_then(|name| conn_mgr.config.find_profile(name));
+    findings.push(profile_finding(
+        &conn_mgr.config,
+        profile_name,
+        resolved_name.as_deref(),
+        env_has_credentials(&env),
+    ));
+    findings.extend(environment_findings(
+        &env,
+        resolved_name.as_deref().zip(profile),
+    ));
+
+    let endpoint = resolve_endpoint(conn_mgr, &env, profile, deployment);
+    let mut endpoint_url = None;
+    match &endpoint {
+        Some(endpoint) => {
+            endpoint_url = Some(endpoint.url.clone());
+            let stages = endpoint_findings(endpoint).await;
+            let reachable = stages.iter().all(|finding| finding.status != Status::Fail);
+            findings.extend(stages);
+            if reachable {
+                findings.push(credentials_finding(conn_mgr, profile_name, endpoint).await);
+            } else {
+                findings.push(Finding::skip("credentials"));
+            }
+        }
+        None => findings.push(Finding::skip("endpoint")),
+    }
+
+    let warnings = count(&findings, Status::Warn);
+    let failures = count(&findings, Status::Fail);
+    let report = DoctorReport {
+        redisctl_version: env!("CARGO_PKG_VERSION").to_string(),
+        config_path: config_path.map(|path| path.display().to_string()),
+        profile: resolved_name,
+        deployment: endpoint
+            .as_ref()
+            .map(|endpoint| endpoint.deployment.to_string()),
+        endpoint: endpoint_url,
+        findings,
+        warnings,
+        failures,
+    };
+
+    match output_format {
+        OutputFormat::Auto | OutputFormat::Table if query.is_none() => {
+            print!("{}", format_report(&report));
+        }
+        _ => {
+            let json = serde_json::to_value(&report).map_err(|e| RedisCtlError::OutputError {
+                message: e.to_string(),
+            })?;
+            let data = super::cloud::utils::handle_output(json, output_format, query)?;
+            super::cloud::utils::print_formatted_output(data, output_format)?;
+        }
+    }
+
+    if report.failures > 0 {
+        return Err(RedisCtlError::Configuration(format!(
+            "doctor found {} problem{}",
+            report.failures,
+            if report.failures == 1 { "" } else { "s" }
+        )));
+    }
+    Ok(())
+}
+
+pub(crate) fn count(findings: &[Finding], status: Status) -> usize {
+    findings.iter().filter(|f| f.status == status).count()
+}
+
+/// Whether the environment alone provides complete credentials for either deployment
+fn env_has_credentials(env: &impl Fn(&str) -> Option<String>) -> bool {
+    (env("REDIS_CLOUD_API_KEY").is_some() && env("REDIS_CLOUD_SECRET_KEY").is_some())
+        || (env("REDIS_ENTERPRISE_URL").is_some() && env("REDIS_ENTERPRISE_USER").is_some())
+}
+
+/// Existence, permissions and syntax of the config file
+/// `elsewhere` tells whether profiles or credentials come from outside the file
+fn config_file_findings(path: &Path, elsewhere: bool) -> Vec<Finding> {
+    const CHECK: &str = "config file";
+    if !path.exists() {
+        return vec![if elsewhere {
+            Finding::ok(
+                CHECK,
+                format!(
+                    "none at {}; using the environment or profile directories",
+                    path.display()
+                ),
+            )
+        } else {
+            Finding::warn(
+                CHECK,
+                format!("none at {}", path.display()),
+                "Create a profile with `redisctl profile set <name> --deployment cloud|enterprise ...`",
+            )
+        }];
+    }
+
+    let mut findings = Vec::new();
+    let content = match std::fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) => {
+            return vec![Finding::fail(
+                CHECK,
+                format!("cannot read {}: {}", path.display(), e),
+                format!(
+                    "Make the file readable by your user, e.g. `chmod 600 {}`",
+                    path.display()
+                ),
+            )];
+        }
+    };
+
+    #[cfg(unix)]
+    if let Ok(metadata) = std::fs::metadata(path) {
+        use std::os::unix::fs::PermissionsExt;
+        if let Some(finding) = permission_finding(path, metadata.permissions().mode()) {
+            findings.push(finding);
+        }
+    }
+
+    let parsed = Config::expand_env_vars(&content)
+        .map_err(|e| {
+            (
+                format!("{:#}", e),
+                "Export the referenced variable or give it a default, e.g. `${VAR:-value}`"
+                    .to_string(),
+            )
+        })
+        .and_then(|expanded| {
+            toml::from_str::<Config>(&expanded).map_err(|e| {
+                (
+                    e.message().to_string(),
+                    format!(
+                        "Fix the TOML syntax in {} (see `redisctl profile show` for the expected fields)",
+                        path.display()
+                    ),
+                )
+            })
+        });
+    match parsed {
+        Ok(config) => findings.push(Finding::ok(
+            CHECK,
+            format!(
+                "{} ({} profile{})",
+                path.display(),
+                config.profiles.len(),
+                if config.profiles.len() == 1 { "" } else { "s" }
+            ),
+        )),
+        Err((detail, fix)) => findings.push(Finding::fail(
+            CHECK,
+            format!("{} is invalid: {}", path.display(), detail),
+            fix,
+        )),
+    }
+    findings
+}
+
+/// A warning when the config file, which may hold secrets, is accessible to other users
+fn permission_finding(path: &Path, mode: u32) -> Option<Finding> {
+    if mode & 0o077 == 0 {
+        return None;
+    }
+    Some(Finding::warn(
+        "config permissions",
+        format!(
+            "{} is accessible to other users (mode {:o})",
+            path.display(),
+            mode & 0o777
+        ),
+        format!("Restrict it to your user: `chmod 600 {}`", path.display()),
+    ))
+}
+
+/// Whether the selected profile exists
+fn profile_finding(
+    config: &Config,
+    explicit: Option<&str>,
+    resolved: Option<&str>,
+    env_credentials: bool,
+) -> Finding {
+    const CHECK: &str = "profile";
+    match resolved {
+        Some(name) if config.find_profile(name).is_some() => Finding::ok(
+            CHECK,
+            format!(
+                "'{}' [{}]",
+                name,
+                config.profile_source(name).unwrap_or("config")
+            ),
+        ),
+        Some(name) => {
+            let origin = if explicit.is_some() {
+                "--profile"
+            } else if std::env::var("REDISCTL_PROFILE").is_ok() {
+                "REDISCTL_PROFILE"
+            } else {
+                "default_profile in the config file"
+            };
+            Finding::fail(
+                CHECK,
+                format!("'{}' (from {}) does not exist", name, origin),
+                format!(
+                    "Create it with `redisctl profile set {} ...`, or pick one from `redisctl profile list`",
+                    name
+                ),
+            )
+        }
+        None if env_credentials => Finding::ok(CHECK, "none; using environment variables"),
+        None if config.list_profiles().is_empty() => Finding::fail(
+            CHECK,
+            "no profiles configured",
+            "Create one with `redisctl profile set <name> --deployment cloud|enterprise ...`",
+        ),
+        None => Finding::warn(
+            CHECK,
+            "no default profile",
+            "Set one with `redisctl profile default <name>`, or pass --profile",
+        ),
+    }
+}
+
+/// Environment variables that are misnamed, incomplete, invalid, or override the profile
+fn environment_findings(
+    env: &impl Fn(&str) -> Option<String>,
+    profile: Option<(&str, &Profile)>,
+) -> Vec<Finding> {
+    const CHECK: &str = "environment";
+    let mut findings = Vec::new();
+
+    if env("REDIS_CLOUD_API_SECRET").is_some() && env("REDIS_CLOUD_SECRET_KEY").is_none() {
+        findings.push(Finding::warn(
+            CHECK,
+            "REDIS_CLOUD_API_SECRET is set, but redisctl reads the secret from REDIS_CLOUD_SECRET_KEY",
+            "export REDIS_CLOUD_SECRET_KEY=\"$REDIS_CLOUD_API_SECRET\"",
+        ));
+    }
+
+    for (set, missing, deployment) in [
+        (
+            "REDIS_CLOUD_API_KEY",
+            "REDIS_CLOUD_SECRET_KEY",
+            DeploymentType::Cloud,
+        ),
+        (
+            "REDIS_CLOUD_SECRET_KEY",
+            "REDIS_CLOUD_API_KEY",
+            DeploymentType::Cloud,
+        ),
+        (
+            "REDIS_ENTERPRISE_URL",
+            "REDIS_ENTERPRISE_USER",
+            DeploymentType::Enterprise,
+        ),
+        (
+            "REDIS_ENTERPRISE_USER",
+            "REDIS_ENTERPRISE_URL",
+            DeploymentType::Enterprise,
+        ),
+    ] {
+        if env(set).is_some() && env(missing).is_none() {
+            let rest = match profile {
+                Some((name, p)) if p.deployment_type == deployment => {
+                    format!("the rest comes from profile '{}'", name)
+                }
+                _ => "there is no matching profile to complete it".to_string(),
+            };
+            findings.push(Finding::warn(
+                CHECK,
+                format!("{} is set without {}; {}", set, missing, rest),
+                format!("Set both variables, or unset {} to use the profile", set),
+            ));
+        }
+    }
+
+    if let Some(value) = env("REDIS_ENTERPRISE_INSECURE")
+        && !["true", "1", "false", "0"].contains(&value.to_lowercase().as_str())
+    {
+        findings.push(Finding::warn(
+            CHECK,
+            format!("REDIS_ENTERPRISE_INSECURE='{}' is treated as false", value),
+            "Use `true` or `1` to disable certificate verification, `false` or `0` to keep it",
+        ));
+    }
+
+    if let Some((name, p)) = profile {
+        let overriding: &[&str] = match p.deployment_type {
+            DeploymentType::Cloud => CLOUD_ENV,
+            DeploymentType::Enterprise => ENTERPRISE_ENV,
+        };
+        let set: Vec<&str> = overriding
+            .iter()
+            .copied()
+            .filter(|var| env(var).is_some())
+            .collect();
+        if !set.is_empty() {
+            findings.push(Finding::warn(
+                CHECK,
+                format!(
+                    "{} override{} profile '{}'",
+                    set.join(", "),
+                    if set.len() == 1 { "s" } else { "" },
+                    name
+                ),
+                format!("Unset {} to use the profile's settings", set.join(" and ")),
+            ));
+        }
+    }
+
+    if findings.is_empty() {
+        findings.push(Finding::ok(CHECK, "no conflicting variables"));
+    }
+    findings
+}
+
+/// The URL to check, from the environment or the profile, the same way clients are created
+fn resolve_endpoint(
+    conn_mgr: &ConnectionManager,
+    env: &impl Fn(&str) -> Option<String>,
+    profile: Option<&Profile>,
+    deployment: Option<DeploymentType>,
+) -> Option<Endpoint> {
+    let deployment = deployment
+        .or(profile.map(|p| p.deployment_type))
+        .or_else(|| {
+            if env("REDIS_CLOUD_API_KEY").is_some() {
+                Some(DeploymentType::Cloud)
+            } else if env("REDIS_ENTERPRISE_URL").is_some() {
+                Some(DeploymentType::Enterprise)
+            } else {
+                None
+            }
+        })?;
+    let profile = profile.filter(|p| p.deployment_type == deployment);
+
+    match deployment {
+        DeploymentType::Cloud => {
+            let url = conn_mgr
+                .cloud_api_url
+                .clone()
+                .or_else(|| env("REDIS_CLOUD_API_URL"))
+                .or_else(|| {
+                    profile
+                        .and_then(Profile::cloud_credentials)
+                        .map(|(_, _, url)| url.to_string())
+                })
+                .unwrap_or_else(|| "https://api.redislabs.com/v1".to_string());
+            Some(Endpoint {
+                deployment,
+                url,
+                insecure: false,
+                tls_fingerprint_sha256: None,
+            })
+        }
+        DeploymentType::Enterprise => {
+            let credentials = profile.and_then(Profile::enterprise_credentials);
+            let url = env("REDIS_ENTERPRISE_URL")
+                .or_else(|| credentials.map(|(url, _, _, _)| url.to_string()))?;
+            let insecure = env("REDIS_ENTERPRISE_INSECURE")
+                .map(|value| value.to_lowercase() == "true" || value == "1")
+                .or_else(|| credentials.map(|(_, _, _, insecure)| insecure))
+                .unwrap_or(false);
+            let tls_fingerprint_sha256 = env("REDIS_ENTERPRISE_TLS_FINGERPRINT_SHA256")
+                .or_else(|| profile.and_then(|p| p.tls_fingerprint_sha256().map(str::to_string)));
+            Some(Endpoint {
+                deployment,
+                url,
+                insecure,
+                tls_fingerprint_sha256,
+            })
+        }
+    }
+}
+
+/// DNS, TCP, TLS and clock checks, each stage skipped once an earlier one fails
+async fn endpoint_findings(endpoint: &Endpoint) -> Vec<Finding> {
+    let mut findings = Vec::new();
+    let url = match reqwest::Url::parse(&endpoint.url) {
+        Ok(url) if url.host_str().is_some() => url,
+        Ok(_) | Err(_) => {
+            findings.push(Finding::fail(
+                "endpoint",
+                format!("'{}' is not a valid URL", endpoint.url),
+                "Use a full URL such as https://cluster.example.com:9443",
+            ));
+            return findings;
+        }
+    };
+    let host = url.host_str().unwrap_or_default().to_string();
+    let port = url.port_or_known_default().unwrap_or(443);
+
+    let addresses = match tokio::time::timeout(
+        STAGE_TIMEOUT,
+        tokio::net::lookup_host((host.as_str(), port)),
+    )
+    .await
+    {
+        Ok(Ok(addresses)) => addresses.collect::<Vec<_>>(),
+        Ok(Err(e)) => {
+            findings.push(dns_failure(&host, &e.to_string()));
+            findings.extend(["tcp", "tls", "clock"].map(Finding::skip));
+            return findings;
+        }
+        Err(_) => {
+            findings.push(dns_failure(&host, "timed out"));
+            findings.extend(["tcp", "tls", "clock"].map(Finding::skip));
+            return findings;
+        }
+    };
+    let Some(address) = addresses.first().copied() else {
+        findings.push(dns_failure(&host, "no addresses"));
+        findings.extend(["tcp", "tls", "clock"].map(Finding::skip));
+        return findings;
+    };
+    findings.push(Finding::ok(
+        "dns",
+        format!("{} resolves to {}", host, address.ip()),
+    ));
+
+    // The stream is dropped right away so single-threaded servers can answer the TLS stage
+    let tcp_error =
+        match tokio::time::timeout(STAGE_TIMEOUT, tokio::net::TcpStream::connect(address)).await {
+            Ok(Ok(_)) => None,
+            Ok(Err(e)) => Some(e.to_string()),
+            Err(_) => Some(format!("timed out after {}s", STAGE_TIMEOUT.as_secs())),
+        };
+    if let Some(error) = tcp_error {
+        findings.push(Finding::fail(
+            "tcp",
+            format!("cannot connect to {}: {}", address, error),
+            format!(
+                "Check that port {} is open from this machine (firewall, security group, VPN or proxy){}",
+                port,
+                match endpoint.deployment {
+                    DeploymentType::Enterprise => "; the REST API listens on 9443 by default",
+                    DeploymentType::Cloud => "",
+                }
+            ),
+        ));
+        findings.extend(["tls", "clock"].map(Finding::skip));
+        return findings;
+    }
+    findings.push(Finding::ok("tcp", format!("connected to {}", address)));
+
+    let pinned = url.scheme() == "https" && endpoint.tls_fingerprint_sha256.is_some();
+    if let Some(fingerprint) = endpoint
+        .tls_fingerprint_sha256
+        .as_deref()
+        .filter(|_| pinned)
+        && let Some(failure) = pinned_tls_failure(&url, fingerprint).await
+    {
+        findings.push(failure);
+        findings.push(Finding::skip("clock"));
+        return findings;
+    }
+
+    // With a pin, the certificate was checked above; this request only reads the server's clock
+    let client = reqwest::Client::builder()
+        .danger_accept_invalid_certs(endpoint.insecure || pinned)
+        .timeout(STAGE_TIMEOUT)
+        .build();
+    let response = match client {
+        Ok(client) => client.get(url.clone()).send().await,
+        Err(e) => {
+            findings.push(Finding::fail(
+                "tls",
+                format!("cannot create an HTTP client: {}", e),
+                "This build of redisctl may lack TLS support; reinstall an official release",
+            ));
+            findings.push(Finding::skip("clock"));
+            return findings;
+        }
+    };
+    let response = match response {
+        Ok(response) => response,
+        Err(e) => {
+            findings.push(tls_failure(&error_chain(&e), endpoint.insecure));
+            findings.push(Finding::skip("clock"));
+            return findings;
+        }
+    };
+    findings.push(if url.scheme() == "https" {
+        Finding::ok(
+            "tls",
+            if pinned {
+                "handshake succeeded and the certificate matches the pinned fingerprint"
+            } else if endpoint.insecure {
+                "handshake succeeded (certificate not verified: insecure mode)"
+            } else {
+                "handshake and certificate verification succeeded"
+            },
+        )
+    } else {
+        Finding::warn(
+            "tls",
+            "the endpoint uses plain HTTP; credentials are sent unencrypted",
+            "Use an https:// URL",
+        )
+    });
+
+    let server_time = response
+        .headers()
+        .get(reqwest::header::DATE)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| chrono::DateTime::parse_from_rfc2822(value).ok());
+    findings.push(match server_time {
+        Some(server_time) => clock_finding(
+            chrono::Utc::now()
+                .signed_duration_since(server_time)
+                .num_seconds(),
+        ),
+        None => Finding::ok("clock", "not checked: the server sent no Date header"),
+    });
+    findings
+}
+
+/// Connect with the certificate pinned as the API client does; any response means it matched
+async fn pinned_tls_failure(url: &reqwest::Url, fingerprint: &str) -> Option<Finding> {
+    let client = match redis_enterprise::EnterpriseClient::builder()
+        .base_url(url.origin().ascii_serialization())
+        .timeout(STAGE_TIMEOUT)
+        .tls_fingerprint_sha256(fingerprint)
+        .build()
+    {
+        Ok(client) => client,
+        Err(e) => {
+            return Some(Finding::fail(
+                "tls",
+                e.to_string(),
+                "Set tls_fingerprint_sha256 to the 64 hex digits printed by `openssl x509 -noout \
+                 -fingerprint -sha256`",
+            ));
+        }
+    };
+    match client.get_raw("/").await {
+        Err(redis_enterprise::RestError::ConnectionError(message)) => Some(Finding::fail(
+            "tls",
+            message,
+            "If the cluster certificate was replaced, pin the new one in tls_fingerprint_sha256 \
+             (REDIS_ENTERPRISE_TLS_FINGERPRINT_SHA256) after checking it with the cluster admin",
+        )),
+        _ => None,
+    }
+}
+
+fn dns_failure(host: &str, error: &str) -> Finding {
+    Finding::fail(
+        "dns",
+        format!("cannot resolve {}: {}", host, error),
+        "Check the hostname in the profile URL; private endpoints may need a VPN or internal DNS",
+    )
+}
+
+/// An error with all its sources, since reqwest hides the TLS cause behind "error sending request"
+fn error_chain(error: &dyn std::error::Error) -> String {
+    let mut message = error.to_string();
+    let mut source = error.source();
+    while let Some(cause) = source {
+        message.push_str(": ");
+        message.push_str(&cause.to_string());
+        source = cause.source();
+    }
+    message
+}
+
+fn tls_failure(error: &str, insecure: bool) -> Finding {
+    let lower = error.to_lowercase();
+    let fix = if lower.contains("certificate") || lower.contains("unknownissuer") {
+        if insecure {
+            "Check the certificate chain presented by the server".to_string()
+        } else {
+            "Install the CA that signed the server certificate, or for self-signed clusters set \
+             `insecure = true` in the profile (REDIS_ENTERPRISE_INSECURE=true); if the certificate \
+             looks valid, check the system clock, which must be within its validity period"
+                .to_string()
+        }
+    } else {
+        "Check that the URL scheme and port match the server (https on the REST API port) and \
+         that no proxy intercepts the connection"
+            .to_string()
+    };
+    Finding::fail("tls", error.to_string(), fix)
+}
+
+/// Warning when the local clock is `skew_secs` away from the server's
+fn clock_finding(skew_secs: i64) -> Finding {
+    let direction = if skew_secs > 0 { "ahead of" } else { "behind" };
+    if skew_secs.abs() > MAX_CLOCK_SKEW_SECS {
+        Finding::warn(
+            "clock",
+            format!(
+                "local clock is {}s {} the server; certificate checks and token expiry may fail",
+                skew_secs.abs(),
+                direction
+            ),
+            "Synchronize the system clock, e.g. `sudo timedatectl set-ntp true`",
+        )
+    } else {
+        Finding::ok(
+            "clock",
+            format!("within {}s of the server", skew_secs.abs()),
+        )
+    }
+}
+
+/// An authenticated request, to tell rejected credentials from other failures
+async fn credentials_finding(
+    conn_mgr: &ConnectionManager,
+    profile_name: Option<&str>,
+    endpoint: &Endpoint,
+) -> Finding {
+    const CHECK: &str = "credentials";
+    let result = match endpoint.deployment {
+        DeploymentType::Cloud => match conn_mgr.create_cloud_client(profile_name).await {
+            Ok(client) => client.get_raw("/").await.map(|_| ()).map_err(|e| {
+                let rejected = matches!(
+                    e,
+                    redis_cloud::CloudError::AuthenticationFailed { .. }
+                        | redis_cloud::CloudError::Forbidden { .. }
+                );
+                (e.to_string(), rejected)
+            }),
+            Err(e) => Err((e.to_string(), false)),
+        },
+        DeploymentType::Enterprise => match conn_mgr.create_enterprise_client(profile_name).await {
+            Ok(client) => client
+                .get_raw("/v1/cluster")
+                .await
+                .map(|_| ())
+                .map_err(|e| (e.to_string(), e.is_unauthorized())),
+            Err(e) => Err((e.to_string(), false)),
+        },
+    };
+
+    match result {
+        Ok(()) => Finding::ok(CHECK, "accepted"),
+        Err((error, true)) => Finding::fail(
+            CHECK,
+            format!("rejected: {}", error),
+            match endpoint.deployment {
+                DeploymentType::Cloud => {
+                    "The API key or secret is wrong, revoked or lacks API access; create a new key \
+                     under Access Management > API Keys in the console and update the profile"
+                }
+                DeploymentType::Enterprise => {
+                    "The username or password is wrong or has expired; check them in the cluster \
+                     manager UI and update the profile (`redisctl profile set`)"
+                }
+            },
+        ),
+        Err((error, false)) => Finding::fail(
+            CHECK,
+            error,
+            "Run `redisctl selftest -v` for details on the failing request",
+        ),
+    }
+}
+
+/// Plain-text report: one line per finding, with the fix indented below it
+pub fn format_report(report: &DoctorReport) -> String {
+    let mut out = String::new();
+    out.push_str(&format!("redisctl doctor ({})\n", report.redisctl_version));
+    if let Some(path) = &report.config_path {
+        out.push_str(&format!("  config:     {}\n", path));
+    }
+    out.push_str(&format!(
+        "  profile:    {}\n",
+        report.profile.as_deref().unwrap_or("(none)")
+    ));
+    if let Some(deployment) = &report.deployment {
+        out.push_str(&format!("  deployment: {}\n", deployment));
+    }
+    if let Some(endpoint) = &report.endpoint {
+        out.push_str(&format!("  endpoint:   {}\n", endpoint));
+    }
+
+    out.push('\n');
+    out.push_str(&format_findings(&report.findings));
+    out
+}
+
+/// One line per finding, with the fix indented below it, then the problem and warning counts
+pub(crate) fn format_findings(findings: &[Finding]) -> String {
+    let mut out = String::new();
+    let width = findings
+        .iter()
+        .map(|finding| finding.check.len())
+        .max()
+        .unwrap_or(0);
+    for finding in findings {
+        out.push_str(&format!(
+            "  {:<4}  {:<width$}  {}\n",
+            finding.status.label(),
+            finding.check,
+            finding.detail,
+            width = width
+        ));
+        if let Some(fix) = &finding.fix {
+            out.push_str(&format!(
+                "        {:<width$}  fix: {}\n",
+                "",
+                fix,
+                width = width
+            ));
+        }
+    }
+
+    let failures = count(findings, Status::Fail);
+    let warnings = count(findings, Status::Warn);
+    out.push_str(&format!(
+        "\n{} problem{}, {} warning{}\n",
+        failures,
+        if failures == 1 { "" } else { "s" },
+        warnings,
+        if warnings == 1 { "" } else { "s" }
+    ));
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn env_of(vars: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+        let vars: HashMap<String, String> = vars
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        move |name| vars.get(name).cloned()
+    }
+
+    fn cloud_profile() -> Profile {
+        Profile {
+            deployment_type: DeploymentType::Cloud,
+            credentials: crate::config::ProfileCredentials::Cloud {
+                api_key: "key".to_string(),
+                api_secret: "secret".to_string(),
+                api_url: "https://api.redislabs.com/v1".to_string(),
+            },
+            read_only: false,
+        }
+    }
+
+    #[test]
+    fn test_environment_findings() {
+        let profile = cloud_profile();
+        let findings = environment_findings(&env_of(&[]), Some(("prod", &profile)));
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].status, Status::Ok);
+
+        let findings = environment_findings(
+            &env_of(&[
+                ("REDIS_CLOUD_API_KEY", "k"),
+                ("REDIS_CLOUD_API_SECRET", "s"),
+                ("REDIS_ENTERPRISE_INSECURE", "yes"),
+            ]),
+            Some(("prod", &profile)),
+        );
+        let details: Vec<&str> = findings.iter().map(|f| f.detail.as_str()).collect();
+        assert_eq!(findings.len(), 4);
+        assert!(findings.iter().all(|f| f.status == Status::Warn));
+        assert!(details[0].contains("reads the secret from REDIS_CLOUD_SECRET_KEY"));
+        assert_eq!(
+            details[1],
+            "REDIS_CLOUD_API_KEY is set without REDIS_CLOUD_SECRET_KEY; the rest comes from profile 'prod'"
+        );
+        assert!(details[2].contains("treated as false"));
+        assert_eq!(details[3], "REDIS_CLOUD_API_KEY overrides profile 'prod'");
+    }
+
+    #[test]
+    fn test_permission_finding() {
+        let path = Path::new("/home/me/.config/redisctl/config.toml");
+        assert!(permission_finding(path, 0o100600).is_none());
+        let finding = permission_finding(path, 0o100644).unwrap();
+        assert_eq!(finding.status, Status::Warn);
+        assert!(finding.detail.ends_with("(mode 644)"));
+        assert_eq!(
+            finding.fix.as_deref(),
+            Some("Restrict it to your user: `chmod 600 /home/me/.config/redisctl/config.toml`")
+        );
+    }
+
+    #[test]
+    fn test_clock_and_tls_findings() {
+        assert_eq!(clock_finding(-12).status, Status::Ok);
+        let skewed = clock_finding(-600);
+        assert_eq!(skewed.status, Status::Warn);
+        assert!(
+            skewed
+                .detail
+                .starts_with("local clock is 600s behind the server")
+        );
+
+        let tls = tls_failure(
+            "error sending request: invalid peer certificate: UnknownIssuer",
+            false,
+        );
+        assert_eq!(tls.status, Status::Fail);
+        assert!(tls.fix.unwrap().contains("insecure = true"));
+        let tls = tls_failure("error sending request: connection reset", false);
+        assert!(tls.fix.unwrap().contains("URL scheme and port"));
+    }
+
+    #[test]
+    fn test_format_report() {
+        let report = DoctorReport {
+            redisctl_version: "1.0.0".to_string(),
+            config_path: Some("/tmp/config.toml".to_string()),
+            profile: Some("prod".to_string()),
+            deployment: Some("enterprise".to_string()),
+            endpoint: Some("https://cluster:9443".to_string()),
+            findings: vec![
+                Finding::ok("dns", "cluster resolves to 10.0.0.1"),
+                Finding::fail("tcp", "cannot connect", "Open port 9443"),
+                Finding::skip("tls"),
+            ],
+            warnings: 0,
+            failures: 1,
+        };
+        let text = format_report(&report);
+        assert!(text.contains("  OK    dns  cluster resolves to 10.0.0.1\n"));
+        assert!(text.contains("  FAIL  tcp  cannot connect\n             fix: Open port 9443\n"));
+        assert!(text.contains("  SKIP  tls  not run after an earlier failure\n"));
+        assert!(text.ends_with("\n1 problem, 0 warnings\n"));
+    }
+}
@@ -0,0 +1,364 @@
+//! Offline linter for Redis ACL rule strings
+//!
+//! Validates the syntax of the rule stored in a Redis Enterprise `redis_acl` (for example
+//! `+@read ~cache:*`) and warns about rules that are valid but probably not what was meant,
+//! such as granting commands without any key patterns.
+
+#![allow(dead_code)]
+
+use serde::Serialize;
+
+/// ACL categories known to Redis and Redis Stack modules
+const CATEGORIES: &[&str] = &[
+    "all",
+    "admin",
+    "bitmap",
+    "blocking",
+    "bloom",
+    "cms",
+    "connection",
+    "cuckoo",
+    "dangerous",
+    "fast",
+    "geo",
+    "hash",
+    "hyperloglog",
+    "json",
+    "keyspace",
+    "list",
+    "pubsub",
+    "read",
+    "scripting",
+    "search",
+    "set",
+    "slow",
+    "sortedset",
+    "stream",
+    "string",
+    "tdigest",
+    "timeseries",
+    "topk",
+    "transaction",
+    "write",
+];
+
+/// Rules that belong to a user definition rather than a Redis ACL
+const USER_ONLY_RULES: &[&str] = &["on", "off", "nopass", "resetpass", "reset"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+/// A problem found in an ACL rule
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct LintIssue {
+    pub severity: Severity,
+    pub token: String,
+    pub message: String,
+}
+
+impl LintIssue {
+    fn error(token: &str, message: impl Into<String>) -> Self {
+        Self {
+            severity: Severity::Error,
+            token: token.to_string(),
+            message: message.into(),
+        }
+    }
+
+    fn warning(token: &str, message: impl Into<String>) -> Self {
+        Self {
+            severity: Severity::Warning,
+            token: token.to_string(),
+            message: message.into(),
+        }
+    }
+}
+
+/// Lint an ACL rule string, returning errors and warnings in rule order
+pub fn lint_acl(acl: &str) -> Vec<LintIssue> {
+    let tokens = match split_tokens(acl) {
+        Ok(tokens) => tokens,
+        Err(issue) => return vec![issue],
+    };
+    if tokens.is_empty() {
+        return vec![LintIssue::error("", "ACL rule is empty")];
+    }
+
+    let mut issues = Vec::new();
+    let mut seen = Vec::new();
+    let mut grants_commands = false;
+    let mut has_key_access = false;
+    let mut all_commands_at: Option<usize> = None;
+    let mut all_keys = false;
+
+    for (index, token) in tokens.iter().enumerate() {
+        let token = token.as_str();
+        if seen.contains(&token) {
+            issues.push(LintIssue::warning(token, "duplicate rule"));
+            continue;
+        }
+        seen.push(token);
+
+        if token.starts_with('(') {
+            // Selector: lint its rules on their own
+            let inner = &token[1..token.len() - 1];
+            for issue in lint_acl(inner) {
+                issues.push(LintIssue {
+                    message: format!("in selector {}: {}", token, issue.message),
+                    ..issue
+                });
+            }
+            continue;
+        }
+
+        match token {
+            "allcommands" | "+@all" => {
+                grants_commands = true;
+                all_commands_at = Some(index);
+                for earlier in &tokens[..index] {
+                    if earlier.starts_with('-') {
+                        issues.push(LintIssue::warning(
+                            earlier,
+                            format!("overridden by the later '{}'", token),
+                        ));
+                    }
+                }
+            }
+            "nocommands" | "-@all" => all_commands_at = None,
+            "allkeys" | "~*" => {
+                has_key_access = true;
+                all_keys = true;
+            }
+            "resetkeys" => {
+                has_key_access = false;
+                all_keys = false;
+            }
+            "allchannels" | "resetchannels" => {}
+            _ if USER_ONLY_RULES.contains(&token)
+                || token.starts_with('>')
+                || token.starts_with('<')
+                || token.starts_with('#')
+                || token.starts_with('!') =>
+            {
+                issues.push(LintIssue::error(
+                    token,
+                    "user rule (state or password) is not allowed in a Redis ACL; set it on the user",
+                ));
+            }
+            _ if token.starts_with('~') || token.starts_with('%') => match key_pattern(token) {
+                Ok(pattern) => {
+                    has_key_access = true;
+                    if all_keys && pattern != "*" {
+                        issues.push(LintIssue::warning(
+                            token,
+                            "redundant: all keys are already allowed",
+                        ));
+                    }
+                }
+                Err(message) => issues.push(LintIssue::error(token, message)),
+            },
+            _ if token.starts_with('&') => {
+                if token.len() == 1 {
+                    issues.push(LintIssue::error(token, "empty channel pattern"));
+                }
+            }
+            _ if token.starts_with('+') || token.starts_with('-') => {
+                let grant = token.starts_with('+');
+                let name = &token[1..];
+                if let Some(category) = name.strip_prefix('@') {
+                    if !CATEGORIES.contains(&category.to_lowercase().as_str()) {
+                        issues.push(LintIssue::error(
+                            token,
+                            format!("unknown ACL category '@{}'", category),
+                        ));
+                        continue;
+                    }
+                    let opposite = format!("{}@{}", if grant { '-' } else { '+' }, category);
+                    if tokens[..index].contains(&opposite) {
+                        issues.push(LintIssue::warning(
+                            token,
+                            format!("conflicts with the earlier '{}'", opposite),
+                        ));
+                    }
+                } else if let Err(message) = command_name(name) {
+                    issues.push(LintIssue::error(token, message));
+                    continue;
+                }
+
+                if grant {
+                    grants_commands = true;
+                    if all_commands_at.is_some() {
+                        issues.push(LintIssue::warning(
+                            token,
+                            "redundant: all commands are already allowed",
+                        ));
+                    }
+                }
+            }
+            _ => issues.push(LintIssue::error(token, "unrecognized ACL rule")),
+        }
+    }
+
+    if grants_commands && !has_key_access {
+        issues.push(LintIssue::warning(
+            "",
+            "no key patterns (~pattern or allkeys); commands that access keys will be denied",
+        ));
+    }
+
+    issues
+}
+
+/// Whether any issue is an error
+pub fn has_errors(issues: &[LintIssue]) -> bool {
+    issues.iter().any(|i| i.severity == Severity::Error)
+}
+
+/// Format an issue for human-readable output
+pub fn format_issue(issue: &LintIssue) -> String {
+    let level = match issue.severity {
+        Severity::Error => "error",
+        Severity::Warning => "warning",
+    };
+    if issue.token.is_empty() {
+        format!("{}: {}", level, issue.message)
+    } else {
+        format!("{}: '{}': {}", level, issue.token, issue.message)
+    }
+}
+
+/// Split a rule string into tokens, keeping selectors `( ... )` together
+pub fn split_tokens(acl: &str) -> Result<Vec<String>, LintIssue> {
+    let mut tokens = Vec::new();
+    let mut chars = acl.trim().chars().peekable();
+
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+            continue;
+        }
+        let mut token = String::new();
+        if c == '(' {
+            for c in chars.by_ref() {
+                token.push(c);
+                if c == ')' {
+                    break;
+                }
+            }
+            if !token.ends_with(')') {
+                return Err(LintIssue::error(&token, "unterminated selector"));
+            }
+        } else {
+            while let Some(&c) = chars.peek() {
+                if c.is_whitespace() {
+                    break;
+                }
+                token.push(c);
+                chars.next();
+            }
+        }
+        tokens.push(token);
+    }
+
+    Ok(tokens)
+}
+
+/// Validate a key pattern rule (`~pattern`, `%R~pattern`, `%W~pattern`, `%RW~pattern`)
+fn key_pattern(token: &str) -> Result<&str, String> {
+    let pattern = match token.strip_prefix('%') {
+        Some(rest) => {
+            let (perms, pattern) = rest
+                .split_once('~')
+                .ok_or_else(|| "key permission rule must look like %R~pattern".to_string())?;
+            if perms.is_empty() || !perms.chars().all(|c| matches!(c, 'R' | 'W')) {
+                return Err(format!(
+                    "invalid key permissions '%{}' (expected R, W or RW)",
+                    perms
+                ));
+            }
+            pattern
+        }
+        None => &token[1..],
+    };
+
+    if pattern.is_empty() {
+        Err("empty key pattern".to_string())
+    } else {
+        Ok(pattern)
+    }
+}
+
+/// Validate a command name, optionally with a subcommand (`config|get`)
+fn command_name(name: &str) -> Result<(), String> {
+    let mut parts = name.split('|');
+    let command = parts.next().unwrap_or_default();
+    let subcommand = parts.next();
+
+    let valid = |s: &str| {
+        !s.is_empty()
+            && s.chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-'))
+    };
+
+    if !valid(command) || parts.next().is_some() || subcommand.is_some_and(|s| !valid(s)) {
+        Err(format!("invalid command name '{}'", name))
+    } else {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn messages(acl: &str) -> Vec<String> {
+        lint_acl(acl).iter().map(format_issue).collect()
+    }
+
+    #[test]
+    fn test_valid_rules() {
+        assert!(lint_acl("+@read ~cache:*").is_empty());
+        assert!(lint_acl("+@all -@dangerous allkeys &notifications:*").is_empty());
+        assert!(lint_acl("+get +config|get %R~reports:* %RW~tmp:*").is_empty());
+        assert!(lint_acl("-@all").is_empty());
+    }
+
+    #[test]
+    fn test_syntax_errors() {
+        assert!(has_errors(&lint_acl("")));
+        assert!(has_errors(&lint_acl("+@raed ~*")));
+        assert!(has_errors(&lint_acl("+get ~")));
+        assert!(has_errors(&lint_acl("+get %X~key")));
+        assert!(has_errors(&lint_acl("+get|a|b ~*")));
+        assert!(has_errors(&lint_acl("read ~*")));
+        assert!(has_errors(&lint_acl("+get ~* >secret")));
+        assert!(has_errors(&lint_acl("+get (~foo")));
+    }
+
+    #[test]
+    fn test_missing_key_pattern_warning() {
+        let issues = lint_acl("+@read");
+        assert!(!has_errors(&issues));
+        assert!(messages("+@read")[0].contains("no key patterns"));
+    }
+
+    #[test]
+    fn test_conflicting_and_overridden_rules() {
+        assert!(messages("+@write -@write ~*")[0].contains("conflicts with the earlier '+@write'"));
+        assert!(messages("-@dangerous +@all ~*")[0].contains("overridden by the later '+@all'"));
+        assert!(messages("+@all +@read ~*")[0].contains("redundant"));
+        assert!(messages("+get allkeys ~user:*")[0].contains("redundant"));
+        assert!(messages("+get +get ~*")[0].contains("duplicate"));
+    }
+
+    #[test]
+    fn test_selectors_are_linted() {
+        assert!(lint_acl("+get ~a:* (+set ~b:*)").is_empty());
+        let messages = messages("+get ~a:* (+@nope ~b:*)");
+        assert!(messages[0].contains("in selector"));
+    }
+}
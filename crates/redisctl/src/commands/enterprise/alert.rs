@@ -0,0 +1,409 @@
+//! Alert notification destinations for Enterprise
+//!
+//! Alert emails go through the SMTP server set on the cluster object; webhooks are set per alert
+//! in the cluster alert settings. The REST API has no request that makes the cluster send a test
+//! alert, so delivery cannot be verified from here; a real alert is the only end-to-end check.
+
+#![allow(dead_code)]
+
+use super::utils::*;
+use crate::cli::{
+    EnterpriseAlertCommands, EnterpriseAlertNotifyCommands, OutputFormat, SmtpTlsMode,
+};
+use crate::connection::ConnectionManager;
+use crate::error::{RedisCtlError, Result as CliResult};
+use crate::output::notice;
+use crate::password::resolve_password;
+use anyhow::Context;
+use redis_enterprise::alerts::ClusterAlertsSettings;
+use redis_enterprise::{AlertHandler, SmtpSettings};
+use serde::Serialize;
+use serde_json::json;
+use std::collections::BTreeSet;
+use tabled::{Table, Tabled, settings::Style};
+
+pub async fn handle_alert_command(
+    conn_mgr: &ConnectionManager,
+    profile_name: Option<&str>,
+    command: &EnterpriseAlertCommands,
+    output_format: OutputFormat,
+    query: Option<&str>,
+) -> CliResult<()> {
+    match command {
+        EnterpriseAlertCommands::Notify(notify) => match notify {
+            EnterpriseAlertNotifyCommands::Show => {
+                show_notifications(conn_mgr, profile_name, output_format, query).await
+            }
+            EnterpriseAlertNotifyCommands::SetSmtp {
+                host,
+                port,
+                username,
+                password,
+                password_stdin,
+                tls_mode,
+                disable,
+            } => {
+                let settings = if *disable {
+                    SmtpSettings {
+                        email_alerts: Some(false),
+                        ..Default::default()
+                    }
+                } else {
+                    let password = if password.is_some() || *password_stdin {
+                        Some(resolve_password(password.as_deref(), *password_stdin)?)
+                    } else {
+                        None
+                    };
+                    smtp_update(host.clone(), *port, username.clone(), password, *tls_mode)?
+                };
+                set_smtp(conn_mgr, profile_name, &settings, output_format, query).await
+            }
+            EnterpriseAlertNotifyCommands::SetWebhook { url, alerts, clear } => {
+                let url = if *clear { None } else { url.as_deref() };
+                set_webhook(conn_mgr, profile_name, url, alerts, output_format, query).await
+            }
+        },
+    }
+}
+
+/// Delivery settings of one cluster alert
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AlertDestination {
+    pub alert: String,
+    pub enabled: bool,
+    pub threshold: String,
+    pub email: Vec<String>,
+    pub webhook_url: Option<String>,
+}
+
+#[derive(Tabled)]
+struct DestinationRow {
+    #[tabled(rename = "ALERT")]
+    alert: String,
+    #[tabled(rename = "ENABLED")]
+    enabled: String,
+    #[tabled(rename = "THRESHOLD")]
+    threshold: String,
+    #[tabled(rename = "EMAIL")]
+    email: String,
+    #[tabled(rename = "WEBHOOK")]
+    webhook: String,
+}
+
+async fn show_notifications(
+    conn_mgr: &ConnectionManager,
+    profile_name: Option<&str>,
+    output_format: OutputFormat,
+    query: Option<&str>,
+) -> CliResult<()> {
+    let client = conn_mgr.create_enterprise_client(profile_name).await?;
+    let handler = AlertHandler::new(client);
+    let smtp = handler
+        .get_smtp_settings()
+        .await
+        .context("Failed to get SMTP settings")?;
+    let settings = handler
+        .get_cluster_alert_settings()
+        .await
+        .context("Failed to get cluster alert settings")?;
+    let destinations = alert_destinations(&settings)?;
+
+    if matches!(output_format, OutputFormat::Auto | OutputFormat::Table) && query.is_none() {
+        print_smtp(&smtp);
+        if destinations.is_empty() {
+            println!("\nNo cluster alert settings found");
+            return Ok(());
+        }
+        let rows: Vec<DestinationRow> = destinations
+            .iter()
+            .map(|d| DestinationRow {
+                alert: d.alert.clone(),
+                enabled: if d.enabled { "yes" } else { "no" }.to_string(),
+                threshold: d.threshold.clone(),
+                email: if d.email.is_empty() {
+                    "-".to_string()
+                } else {
+                    d.email.join(", ")
+                },
+                webhook: d.webhook_url.clone().unwrap_or_else(|| "-".to_string()),
+            })
+            .collect();
+        let mut table = Table::new(rows);
+        table.with(Style::blank());
+        println!("\n{}", table);
+        return Ok(());
+    }
+
+    let json = json!({ "smtp": smtp, "alerts": destinations });
+    let data = handle_output(json, output_format, query)?;
+    print_formatted_output(data, output_format)
+}
+
+/// Delivery settings of every cluster alert, sorted by name
+pub fn alert_destinations(settings: &ClusterAlertsSettings) -> CliResult<Vec<AlertDestination>> {
+    let mut destinations: Vec<AlertDestination> = settings
+        .entries()
+        .context("Failed to read cluster alert settings")?
+        .into_iter()
+        .map(|(alert, s)| AlertDestination {
+            alert,
+            enabled: s.enabled,
+            threshold: s.threshold,
+            email: s.email.unwrap_or_default(),
+            webhook_url: s.webhook_url,
+        })
+        .collect();
+    destinations.sort_by(|a, b| a.alert.cmp(&b.alert));
+    Ok(destinations)
+}
+
+/// The SMTP fields to change; `None` leaves a field as it is
+pub fn smtp_update(
+    host: Option<String>,
+    port: Option<u16>,
+    username: Option<String>,
+    password: Option<String>,
+    tls_mode: Option<SmtpTlsMode>,
+) -> CliResult<SmtpSettings> {
+    if host.is_none()
+        && port.is_none()
+        && username.is_none()
+        && password.is_none()
+        && tls_mode.is_none()
+    {
+        return Err(RedisCtlError::Validation {
+            message: "Nothing to change; give --host, --port, --username, --password, --tls-mode \
+                      or --disable"
+                .to_string(),
+        });
+    }
+    Ok(SmtpSettings {
+        email_alerts: Some(true),
+        smtp_host: host,
+        smtp_port: port,
+        smtp_username: username,
+        smtp_password: password,
+        smtp_tls_mode: tls_mode.map(|mode| {
+            match mode {
+                SmtpTlsMode::None => "none",
+                SmtpTlsMode::Starttls => "starttls",
+                SmtpTlsMode::Tls => "tls",
+            }
+            .to_string()
+        }),
+        smtp_use_tls: None,
+    })
+}
+
+async fn set_smtp(
+    conn_mgr: &ConnectionManager,
+    profile_name: Option<&str>,
+    settings: &SmtpSettings,
+    output_format: OutputFormat,
+    query: Option<&str>,
+) -> CliResult<()> {
+    let client = conn_mgr.create_enterprise_client(profile_name).await?;
+    let mut updated = AlertHandler::new(client)
+        .update_smtp_settings(settings)
+        .await
+        .context("Failed to update SMTP settings")?;
+    updated.smtp_password = None;
+
+    if matches!(output_format, OutputFormat::Auto | OutputFormat::Table) && query.is_none() {
+        if settings.email_alerts == Some(false) {
+            notice!("Alert emails disabled");
+        } else {
+            print_smtp(&updated);
+        }
+        return Ok(());
+    }
+
+    let json = serde_json::to_value(&updated).context("Failed to serialize SMTP settings")?;
+    let data = handle_output(json, output_format, query)?;
+    print_formatted_output(data, output_format)
+}
+
+fn print_smtp(smtp: &SmtpSettings) {
+    let text = |value: &Option<String>| value.clone().unwrap_or_else(|| "-".to_string());
+    println!(
+        "Alert emails: {}",
+        match smtp.email_alerts {
+            Some(true) => "enabled",
+            Some(false) => "disabled",
+            None => "-",
+        }
+    );
+    println!(
+        "SMTP server:  {}",
+        match (&smtp.smtp_host, smtp.smtp_port) {
+            (Some(host), Some(port)) => format!("{}:{}", host, port),
+            (Some(host), None) => host.clone(),
+            _ => "-".to_string(),
+        }
+    );
+    println!("SMTP user:    {}", text(&smtp.smtp_username));
+    println!("SMTP TLS:     {}", text(&smtp.smtp_tls_mode));
+}
+
+async fn set_webhook(
+    conn_mgr: &ConnectionManager,
+    profile_name: Option<&str>,
+    url: Option<&str>,
+    alerts: &[String],
+    output_format: OutputFormat,
+    query: Option<&str>,
+) -> CliResult<()> {
+    if let Some(url) = url {
+        validate_webhook_url(url)?;
+    }
+    let client = conn_mgr.create_enterprise_client(profile_name).await?;
+    let handler = AlertHandler::new(client);
+    let current = handler
+        .get_cluster_alert_settings()
+        .await
+        .context("Failed to get cluster alert settings")?;
+
+    let (settings, changed) = with_webhook(&current, url, alerts)?;
+    if changed.is_empty() {
+        notice!("Every selected alert already has this webhook");
+        return Ok(());
+    }
+    handler
+        .update_cluster_alert_settings(&settings)
+        .await
+        .context("Failed to update cluster alert settings")?;
+
+    if matches!(output_format, OutputFormat::Auto | OutputFormat::Table) && query.is_none() {
+        match url {
+            Some(url) => notice!("Webhook {} set on {} alerts", url, changed.len()),
+            None => notice!("Webhook removed from {} alerts", changed.len()),
+        }
+        return Ok(());
+    }
+
+    let json = json!({ "webhook_url": url, "alerts": changed });
+    let data = handle_output(json, output_format, query)?;
+    print_formatted_output(data, output_format)
+}
+
+/// Only http and https URLs can receive alerts
+fn validate_webhook_url(url: &str) -> CliResult<()> {
+    let parsed = reqwest::Url::parse(url).map_err(|e| RedisCtlError::Validation {
+        message: format!("Invalid webhook URL '{}': {}", url, e),
+    })?;
+    if !matches!(parsed.scheme(), "http" | "https") {
+        return Err(RedisCtlError::Validation {
+            message: format!("Webhook URL '{}' must use http or https", url),
+        });
+    }
+    Ok(())
+}
+
+/// Settings with the webhook of the selected alerts (all when `alerts` is empty) set to `url`,
+/// and the names of the alerts that changed
+pub fn with_webhook(
+    current: &ClusterAlertsSettings,
+    url: Option<&str>,
+    alerts: &[String],
+) -> CliResult<(ClusterAlertsSettings, Vec<String>)> {
+    let mut entries = current
+        .entries()
+        .context("Failed to read cluster alert settings")?;
+
+    let known: BTreeSet<&str> = entries.iter().map(|(name, _)| name.as_str()).collect();
+    let unknown: Vec<&str> = alerts
+        .iter()
+        .map(String::as_str)
+        .filter(|name| !known.contains(name))
+        .collect();
+    if !unknown.is_empty() {
+        return Err(RedisCtlError::Validation {
+            message: format!(
+                "Unknown cluster alert: {}; run `enterprise alert notify show` for the names",
+                unknown.join(", ")
+            ),
+        });
+    }
+
+    let mut changed = Vec::new();
+    for (name, settings) in entries.iter_mut() {
+        if (alerts.is_empty() || alerts.contains(name)) && settings.webhook_url.as_deref() != url {
+            settings.webhook_url = url.map(str::to_string);
+            changed.push(name.clone());
+        }
+    }
+    changed.sort();
+
+    let settings = ClusterAlertsSettings::from_entries(entries)
+        .context("Failed to build cluster alert settings")?;
+    Ok((settings, changed))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settings() -> ClusterAlertsSettings {
+        serde_json::from_value(json!({
+            "node_memory": {"enabled": true, "threshold": "80"},
+            "node_cpu_utilization": {
+                "enabled": true,
+                "threshold": "90",
+                "webhook_url": "https://hooks.example.com/a"
+            },
+            "cluster_license_about_to_expire": {"enabled": false, "threshold": "7"}
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_with_webhook_all_alerts() {
+        let (updated, changed) =
+            with_webhook(&settings(), Some("https://hooks.example.com/a"), &[]).unwrap();
+        assert_eq!(
+            changed,
+            vec!["cluster_license_about_to_expire", "node_memory"]
+        );
+        let destinations = alert_destinations(&updated).unwrap();
+        assert!(
+            destinations
+                .iter()
+                .all(|d| d.webhook_url.as_deref() == Some("https://hooks.example.com/a"))
+        );
+    }
+
+    #[test]
+    fn test_with_webhook_selected_and_clear() {
+        let (updated, changed) =
+            with_webhook(&settings(), None, &["node_cpu_utilization".to_string()]).unwrap();
+        assert_eq!(changed, vec!["node_cpu_utilization"]);
+        assert_eq!(updated.node_cpu_utilization.unwrap().webhook_url, None);
+        assert_eq!(updated.node_memory.unwrap().threshold, "80");
+
+        let err = with_webhook(&settings(), None, &["no_such_alert".to_string()]).unwrap_err();
+        assert!(err.to_string().contains("no_such_alert"));
+    }
+
+    #[test]
+    fn test_smtp_update() {
+        let update = smtp_update(
+            Some("smtp.example.com".to_string()),
+            Some(587),
+            None,
+            None,
+            Some(SmtpTlsMode::Starttls),
+        )
+        .unwrap();
+        assert_eq!(
+            serde_json::to_value(&update).unwrap(),
+            json!({
+                "email_alerts": true,
+                "smtp_host": "smtp.example.com",
+                "smtp_port": 587,
+                "smtp_tls_mode": "starttls"
+            })
+        );
+        assert!(smtp_update(None, None, None, None, None).is_err());
+        assert!(validate_webhook_url("ftp://example.com").is_err());
+    }
+}
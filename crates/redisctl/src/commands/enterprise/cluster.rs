@@ -6,7 +6,10 @@ use crate::cli::{EnterpriseClusterCommands, OutputFormat};
 use crate::connection::ConnectionManager;
 use crate::error::Result as CliResult;
 
+use super::cluster_events;
 use super::cluster_impl;
+use super::cluster_maintenance;
+use super::service_impl;
 
 pub async fn handle_cluster_command(
     conn_mgr: &ConnectionManager,
@@ -20,8 +23,16 @@ pub async fn handle_cluster_command(
         EnterpriseClusterCommands::Get => {
             cluster_impl::get_cluster(conn_mgr, profile_name, output_format, query).await
         }
-        EnterpriseClusterCommands::Update { data } => {
-            cluster_impl::update_cluster(conn_mgr, profile_name, data, output_format, query).await
+        EnterpriseClusterCommands::Update { data, patch } => {
+            cluster_impl::update_cluster(
+                conn_mgr,
+                profile_name,
+                data.as_deref(),
+                patch.as_deref(),
+                output_format,
+                query,
+            )
+            .await
         }
         EnterpriseClusterCommands::GetPolicy => {
             cluster_impl::get_cluster_policy(conn_mgr, profile_name, output_format, query).await
@@ -30,6 +41,20 @@ pub async fn handle_cluster_command(
             cluster_impl::update_cluster_policy(conn_mgr, profile_name, data, output_format, query)
                 .await
         }
+        EnterpriseClusterCommands::GetCmSettings => {
+            cluster_impl::get_cm_settings(conn_mgr, profile_name, output_format, query).await
+        }
+        EnterpriseClusterCommands::UpdateCmSettings { set, data } => {
+            cluster_impl::update_cm_settings(
+                conn_mgr,
+                profile_name,
+                set,
+                data.as_deref(),
+                output_format,
+                query,
+            )
+            .await
+        }
         EnterpriseClusterCommands::GetLicense => {
             cluster_impl::get_cluster_license(conn_mgr, profile_name, output_format, query).await
         }
@@ -45,23 +70,85 @@ pub async fn handle_cluster_command(
         }
 
         // Cluster Operations
-        EnterpriseClusterCommands::Bootstrap { data } => {
-            cluster_impl::bootstrap_cluster(conn_mgr, profile_name, data, output_format, query)
-                .await
+        EnterpriseClusterCommands::Bootstrap {
+            data,
+            cluster_name,
+            username,
+            password,
+            password_stdin,
+            rack_aware,
+            persistent_path,
+            ephemeral_path,
+            rack_id,
+            addr,
+            external_addr,
+            max_listeners,
+        } => {
+            let flags = cluster_impl::BootstrapFlags {
+                cluster_name: cluster_name.clone(),
+                username: username.clone(),
+                password: password.clone(),
+                password_stdin: *password_stdin,
+                rack_aware: *rack_aware,
+                persistent_path: persistent_path.clone(),
+                ephemeral_path: ephemeral_path.clone(),
+                rack_id: rack_id.clone(),
+                addr: addr.clone(),
+                external_addr: external_addr.clone(),
+                max_listeners: *max_listeners,
+            };
+            cluster_impl::bootstrap_cluster(
+                conn_mgr,
+                profile_name,
+                data.as_deref(),
+                &flags,
+                output_format,
+                query,
+            )
+            .await
         }
         EnterpriseClusterCommands::Join { data } => {
             cluster_impl::join_cluster(conn_mgr, profile_name, data, output_format, query).await
         }
-        EnterpriseClusterCommands::Recover { data } => {
-            cluster_impl::recover_cluster(conn_mgr, profile_name, data, output_format, query).await
+        EnterpriseClusterCommands::Recover {
+            data,
+            force: _,
+            dry_run,
+            timeout,
+            interval,
+        } => {
+            cluster_impl::recover_cluster(
+                conn_mgr,
+                profile_name,
+                data.as_deref(),
+                *dry_run,
+                *timeout,
+                *interval,
+                output_format,
+                query,
+            )
+            .await
         }
-        EnterpriseClusterCommands::Reset { force } => {
-            cluster_impl::reset_cluster(conn_mgr, profile_name, *force, output_format, query).await
+        EnterpriseClusterCommands::Reset {
+            force: _,
+            timeout,
+            interval,
+        } => {
+            cluster_impl::reset_cluster(
+                conn_mgr,
+                profile_name,
+                *timeout,
+                *interval,
+                output_format,
+                query,
+            )
+            .await
         }
 
         // Cluster Monitoring
-        EnterpriseClusterCommands::Stats => {
-            cluster_impl::get_cluster_stats(conn_mgr, profile_name, output_format, query).await
+        EnterpriseClusterCommands::Stats { format } => {
+            cluster_impl::get_cluster_stats(conn_mgr, profile_name, *format, output_format, query)
+                .await
         }
         EnterpriseClusterCommands::Metrics { interval } => {
             cluster_impl::get_cluster_metrics(
@@ -76,9 +163,25 @@ pub async fn handle_cluster_command(
         EnterpriseClusterCommands::Alerts => {
             cluster_impl::get_cluster_alerts(conn_mgr, profile_name, output_format, query).await
         }
-        EnterpriseClusterCommands::Events { limit } => {
-            cluster_impl::get_cluster_events(conn_mgr, profile_name, *limit, output_format, query)
-                .await
+        EnterpriseClusterCommands::Events {
+            limit,
+            since,
+            event_types,
+            export,
+        } => {
+            cluster_events::list_cluster_events(
+                conn_mgr,
+                profile_name,
+                cluster_events::EventsFilter {
+                    limit: *limit,
+                    since: since.as_deref(),
+                    event_types,
+                },
+                *export,
+                output_format,
+                query,
+            )
+            .await
         }
         EnterpriseClusterCommands::AuditLog { from } => {
             cluster_impl::get_audit_log(
@@ -92,20 +195,61 @@ pub async fn handle_cluster_command(
         }
 
         // Cluster Maintenance
-        EnterpriseClusterCommands::MaintenanceModeEnable => {
-            cluster_impl::enable_maintenance_mode(conn_mgr, profile_name, output_format, query)
-                .await
+        EnterpriseClusterCommands::MaintenanceMode(command) => {
+            cluster_maintenance::handle_maintenance_command(
+                conn_mgr,
+                profile_name,
+                command,
+                output_format,
+                query,
+            )
+            .await
         }
-        EnterpriseClusterCommands::MaintenanceModeDisable => {
-            cluster_impl::disable_maintenance_mode(conn_mgr, profile_name, output_format, query)
+        EnterpriseClusterCommands::DebugInfo {
+            database,
+            file,
+            from_file,
+            upload,
+            ticket,
+            upload_url,
+            attempts,
+        } => {
+            let options = super::debuginfo::DebugInfoOptions {
+                database: *database,
+                file: file.clone(),
+                from_file: from_file.clone(),
+                upload: *upload,
+                ticket: ticket.clone(),
+                upload_url: upload_url.clone(),
+                attempts: *attempts,
+            };
+            super::debuginfo::debug_info(conn_mgr, profile_name, &options, output_format, query)
                 .await
         }
-        EnterpriseClusterCommands::DebugInfo => {
-            cluster_impl::collect_debug_info(conn_mgr, profile_name, output_format, query).await
-        }
         EnterpriseClusterCommands::CheckStatus => {
             cluster_impl::check_cluster_status(conn_mgr, profile_name, output_format, query).await
         }
+        EnterpriseClusterCommands::RollingRestart {
+            service,
+            health_timeout,
+            sync_timeout,
+            force,
+        } => {
+            let options = service_impl::RollingRestartOptions {
+                health_timeout: *health_timeout,
+                sync_timeout: *sync_timeout,
+                force: *force,
+            };
+            service_impl::rolling_restart(
+                conn_mgr,
+                profile_name,
+                service,
+                &options,
+                output_format,
+                query,
+            )
+            .await
+        }
 
         // Certificates & Security
         EnterpriseClusterCommands::GetCertificates => {
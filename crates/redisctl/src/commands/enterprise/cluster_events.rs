@@ -0,0 +1,231 @@
+//! `enterprise cluster events`: the cluster's event history
+//!
+//! Events come from the cluster event log (`/v1/logs`). The cluster filters by time itself, but
+//! not by event type, so pages are fetched newest first and filtered here until `--limit`
+//! matching events are found. A cluster that ignores the order returns events oldest first; then
+//! every page is read, since the newest events can be on any of them.
+
+#![allow(dead_code)]
+
+use super::logs::{LogRow, parse_log_time};
+use super::utils::*;
+use crate::cli::{LogExportFormat, OutputFormat};
+use crate::commands::cloud::utils::export_entries;
+use crate::connection::ConnectionManager;
+use crate::error::Result as CliResult;
+use crate::output::notice;
+use anyhow::Context;
+use chrono::Utc;
+use redis_enterprise::{LogEntry, LogOrder, LogsHandler, LogsQuery};
+use tabled::{Table, settings::Style};
+
+/// Events requested per page
+const PAGE_SIZE: u32 = 100;
+
+/// Columns written by `--export csv`
+const EXPORT_COLUMNS: &[&str] = &[
+    "time",
+    "level",
+    "type",
+    "bdb_uid",
+    "node_uid",
+    "originator_username",
+    "message",
+];
+
+/// Which events to list
+#[derive(Debug, Clone, Copy)]
+pub struct EventsFilter<'a> {
+    /// Maximum number of events; 0 for all
+    pub limit: u32,
+    pub since: Option<&'a str>,
+    /// Event types to keep; empty keeps every type
+    pub event_types: &'a [String],
+}
+
+impl EventsFilter<'_> {
+    fn matches(&self, event: &LogEntry) -> bool {
+        self.event_types.is_empty()
+            || event.event_type.as_deref().is_some_and(|event_type| {
+                self.event_types
+                    .iter()
+                    .any(|t| t.eq_ignore_ascii_case(event_type))
+            })
+    }
+}
+
+pub async fn list_cluster_events(
+    conn_mgr: &ConnectionManager,
+    profile_name: Option<&str>,
+    filter: EventsFilter<'_>,
+    export: Option<LogExportFormat>,
+    output_format: OutputFormat,
+    query: Option<&str>,
+) -> CliResult<()> {
+    let stime = filter
+        .since
+        .map(|since| parse_log_time(since, Utc::now(), false))
+        .transpose()?;
+    let client = conn_mgr.create_enterprise_client(profile_name).await?;
+    let handler = LogsHandler::new(client);
+
+    let mut events: Vec<LogEntry> = Vec::new();
+    let mut offset = 0;
+    // Stopping at the limit is only safe while the pages come newest first
+    let mut ordered = true;
+    let mut last_time: Option<String> = None;
+    loop {
+        let page = handler
+            .list(Some(LogsQuery {
+                stime: stime.clone(),
+                order: Some(LogOrder::Desc),
+                limit: Some(PAGE_SIZE),
+                offset: Some(offset),
+                ..Default::default()
+            }))
+            .await
+            .context("Failed to get cluster events")?;
+        let fetched = page.len() as u32;
+        ordered &= is_newest_first(last_time.as_deref(), &page);
+        last_time = page.last().map(|event| event.time.clone()).or(last_time);
+        events.extend(page.into_iter().filter(|event| {
+            filter.matches(event)
+                && stime
+                    .as_deref()
+                    .is_none_or(|stime| newest_first(&event.time, stime).is_le())
+        }));
+
+        offset += fetched;
+        let enough = ordered && filter.limit > 0 && events.len() >= filter.limit as usize;
+        if fetched < PAGE_SIZE || enough {
+            break;
+        }
+    }
+
+    // Sort everything that was read, then keep the newest `--limit` events
+    events.sort_by(|a, b| newest_first(&a.time, &b.time));
+    if filter.limit > 0 {
+        events.truncate(filter.limit as usize);
+    }
+
+    if let Some(format) = export {
+        let json: Vec<serde_json::Value> = events
+            .iter()
+            .map(serde_json::to_value)
+            .collect::<Result<_, _>>()
+            .context("Failed to serialize cluster events")?;
+        return export_entries(&json, EXPORT_COLUMNS, format);
+    }
+
+    match output_format {
+        OutputFormat::Auto | OutputFormat::Table if query.is_none() => {
+            if events.is_empty() {
+                notice!("No events found");
+                return Ok(());
+            }
+            let rows: Vec<LogRow> = events.iter().map(LogRow::from).collect();
+            let mut table = Table::new(rows);
+            table.with(Style::blank());
+            println!("{}", table);
+            Ok(())
+        }
+        _ => {
+            let json =
+                serde_json::to_value(&events).context("Failed to serialize cluster events")?;
+            let data = handle_output(json, output_format, query)?;
+            print_formatted_output(data, output_format)
+        }
+    }
+}
+
+/// Whether `page` continues a newest-first listing whose last event was at `after`
+fn is_newest_first(after: Option<&str>, page: &[LogEntry]) -> bool {
+    let times: Vec<&str> = after
+        .into_iter()
+        .chain(page.iter().map(|event| event.time.as_str()))
+        .collect();
+    times
+        .windows(2)
+        .all(|pair| newest_first(pair[0], pair[1]).is_le())
+}
+
+/// Order two event times newest first; times that do not parse compare as text
+fn newest_first(a: &str, b: &str) -> std::cmp::Ordering {
+    match (
+        chrono::DateTime::parse_from_rfc3339(a),
+        chrono::DateTime::parse_from_rfc3339(b),
+    ) {
+        (Ok(a), Ok(b)) => b.cmp(&a),
+        _ => b.cmp(a),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn event(value: serde_json::Value) -> LogEntry {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn test_filter_matches_event_types() {
+        let failed = event(json!({
+            "time": "2024-03-10T11:00:00Z", "type": "node_failed", "severity": "ERROR"
+        }));
+        let untyped = event(json!({"time": "2024-03-10T11:00:00Z", "message": "x"}));
+
+        let all = EventsFilter {
+            limit: 0,
+            since: None,
+            event_types: &[],
+        };
+        assert!(all.matches(&failed));
+        assert!(all.matches(&untyped));
+
+        let types = vec!["bdb_failed".to_string(), "NODE_FAILED".to_string()];
+        let some = EventsFilter {
+            event_types: &types,
+            ..all
+        };
+        assert!(some.matches(&failed));
+        assert!(!some.matches(&untyped));
+    }
+
+    #[test]
+    fn test_is_newest_first() {
+        let page = |times: &[&str]| -> Vec<LogEntry> {
+            times
+                .iter()
+                .map(|time| event(json!({"time": time})))
+                .collect()
+        };
+        let desc = page(&["2024-03-10T11:00:00Z", "2024-03-10T10:00:00Z"]);
+        assert!(is_newest_first(None, &desc));
+        assert!(is_newest_first(Some("2024-03-10T12:00:00Z"), &desc));
+        // Each page newest first, but the second one newer than the first: order was ignored
+        assert!(!is_newest_first(Some("2024-03-10T09:00:00Z"), &desc));
+        let asc = page(&["2024-03-10T10:00:00Z", "2024-03-10T11:00:00Z"]);
+        assert!(!is_newest_first(None, &asc));
+        assert!(is_newest_first(None, &[]));
+    }
+
+    #[test]
+    fn test_newest_first() {
+        let mut times = vec![
+            "2024-03-10T10:00:00Z",
+            "2024-03-10T12:00:00+01:00",
+            "2024-03-10T11:30:00Z",
+        ];
+        times.sort_by(|a, b| newest_first(a, b));
+        assert_eq!(
+            times,
+            vec![
+                "2024-03-10T11:30:00Z",
+                "2024-03-10T12:00:00+01:00",
+                "2024-03-10T10:00:00Z"
+            ]
+        );
+    }
+}
@@ -2,17 +2,21 @@
 
 #![allow(dead_code)]
 
-use crate::cli::OutputFormat;
+use crate::cli::{OutputFormat, StatsExportFormat};
+use crate::commands::patch::patch_update;
 use crate::connection::ConnectionManager;
-use crate::error::Result as CliResult;
+use crate::error::{RedisCtlError, Result as CliResult};
+use crate::output::notice;
+use crate::progress::TaskProgress;
 use anyhow::Context;
+use redis_enterprise::actions::{Action, ActionHandler};
 use redis_enterprise::alerts::AlertHandler;
-use redis_enterprise::bootstrap::BootstrapHandler;
-use redis_enterprise::cluster::ClusterHandler;
-use redis_enterprise::debuginfo::DebugInfoHandler;
+use redis_enterprise::cluster::{ClusterActionResponse, ClusterHandler, ClusterRecoveryPlan};
+use redis_enterprise::cm_settings::{CmSettings, CmSettingsHandler};
 use redis_enterprise::license::LicenseHandler;
 use redis_enterprise::ocsp::OcspHandler;
 
+use super::stats_export;
 use super::utils::*;
 
 // ============================================================================
@@ -37,14 +41,30 @@ pub async fn get_cluster(
 pub async fn update_cluster(
     conn_mgr: &ConnectionManager,
     profile_name: Option<&str>,
-    data: &str,
+    data: Option<&str>,
+    patch: Option<&str>,
     output_format: OutputFormat,
     query: Option<&str>,
 ) -> CliResult<()> {
     let client = conn_mgr.create_enterprise_client(profile_name).await?;
-    let handler = ClusterHandler::new(client);
-
-    let update_data = read_json_data(data).context("Failed to parse cluster data")?;
+    let handler = ClusterHandler::new(client.clone());
+
+    let update_data = match patch {
+        Some(patch) => {
+            let current = client
+                .get_raw("/v1/cluster")
+                .await
+                .context("Failed to get cluster configuration")?;
+            patch_update(&current, patch)?
+        }
+        None => {
+            Some(read_json_data(data.unwrap_or_default()).context("Failed to parse cluster data")?)
+        }
+    };
+    let Some(update_data) = update_data else {
+        notice!("No changes to apply");
+        return Ok(());
+    };
     let result = handler.update(update_data).await?;
     let data = handle_output(result, output_format, query)?;
     print_formatted_output(data, output_format)?;
@@ -98,6 +118,151 @@ pub async fn update_cluster_policy(
     Ok(())
 }
 
+/// Value type accepted by a cluster manager setting
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum CmSettingKind {
+    Integer,
+    Bool,
+    String,
+}
+
+/// Settings that can be changed with `update-cm-settings --set`
+const CM_SETTING_KEYS: &[(&str, CmSettingKind)] = &[
+    ("cm_port", CmSettingKind::Integer),
+    ("cm_session_timeout", CmSettingKind::Integer),
+    ("auto_recovery", CmSettingKind::Bool),
+    ("auto_failover", CmSettingKind::Bool),
+    ("slave_ha", CmSettingKind::Bool),
+    ("slave_ha_grace_period", CmSettingKind::Integer),
+    ("max_simultaneous_backups", CmSettingKind::Integer),
+    ("parallel_shards_upgrade", CmSettingKind::Integer),
+    ("timezone", CmSettingKind::String),
+    ("timeouts.<name>", CmSettingKind::Integer),
+];
+
+pub async fn get_cm_settings(
+    conn_mgr: &ConnectionManager,
+    profile_name: Option<&str>,
+    output_format: OutputFormat,
+    query: Option<&str>,
+) -> CliResult<()> {
+    let client = conn_mgr.create_enterprise_client(profile_name).await?;
+    let handler = CmSettingsHandler::new(client);
+    let settings = handler.get().await?;
+    let settings_json =
+        serde_json::to_value(settings).context("Failed to serialize cluster manager settings")?;
+    let data = handle_output(settings_json, output_format, query)?;
+    print_formatted_output(data, output_format)?;
+    Ok(())
+}
+
+pub async fn update_cm_settings(
+    conn_mgr: &ConnectionManager,
+    profile_name: Option<&str>,
+    set: &[String],
+    data: Option<&str>,
+    output_format: OutputFormat,
+    query: Option<&str>,
+) -> CliResult<()> {
+    let mut update = match data {
+        Some(data) => read_json_data(data).context("Failed to parse settings data")?,
+        None => serde_json::json!({}),
+    };
+    if !update.is_object() {
+        return Err(RedisCtlError::Validation {
+            message: "Settings data must be a JSON object".to_string(),
+        });
+    }
+
+    for assignment in set {
+        let (key, value) = parse_cm_setting(assignment)?;
+        insert_dotted(&mut update, &key, value);
+    }
+
+    let settings: CmSettings =
+        serde_json::from_value(update).map_err(|e| RedisCtlError::Validation {
+            message: format!("Invalid cluster manager settings: {}", e),
+        })?;
+
+    let client = conn_mgr.create_enterprise_client(profile_name).await?;
+    let handler = CmSettingsHandler::new(client);
+    let result = handler.update(settings).await?;
+    let result_json =
+        serde_json::to_value(result).context("Failed to serialize cluster manager settings")?;
+    let data = handle_output(result_json, output_format, query)?;
+    print_formatted_output(data, output_format)?;
+    Ok(())
+}
+
+/// Parse and validate a `KEY=VALUE` cluster manager setting
+fn parse_cm_setting(assignment: &str) -> CliResult<(String, serde_json::Value)> {
+    let (key, raw) = assignment
+        .split_once('=')
+        .ok_or_else(|| RedisCtlError::Validation {
+            message: format!("Invalid setting '{}': expected KEY=VALUE", assignment),
+        })?;
+    let key = key.trim();
+
+    let kind = match key.split_once('.') {
+        Some(("timeouts", name)) if !name.is_empty() && !name.contains('.') => {
+            Some(CmSettingKind::Integer)
+        }
+        Some(_) => None,
+        None => CM_SETTING_KEYS
+            .iter()
+            .find(|(known, _)| *known == key)
+            .map(|(_, kind)| *kind),
+    };
+    let Some(kind) = kind else {
+        let known: Vec<&str> = CM_SETTING_KEYS.iter().map(|(k, _)| *k).collect();
+        return Err(RedisCtlError::Validation {
+            message: format!(
+                "Unknown cluster manager setting '{}'. Known settings: {}",
+                key,
+                known.join(", ")
+            ),
+        });
+    };
+
+    let value = match kind {
+        CmSettingKind::Integer => {
+            raw.parse::<u32>()
+                .map(serde_json::Value::from)
+                .map_err(|_| RedisCtlError::Validation {
+                    message: format!("Setting '{}' expects a non-negative integer", key),
+                })?
+        }
+        CmSettingKind::Bool => raw
+            .parse::<bool>()
+            .map(serde_json::Value::from)
+            .map_err(|_| RedisCtlError::Validation {
+                message: format!("Setting '{}' expects true or false", key),
+            })?,
+        CmSettingKind::String => serde_json::Value::from(raw),
+    };
+
+    Ok((key.to_string(), value))
+}
+
+/// Insert a value into a JSON object at a dotted path, creating intermediate objects
+fn insert_dotted(target: &mut serde_json::Value, key: &str, value: serde_json::Value) {
+    let mut current = target;
+    let mut parts = key.split('.').peekable();
+    while let Some(part) = parts.next() {
+        if !current.is_object() {
+            *current = serde_json::json!({});
+        }
+        let map = current.as_object_mut().expect("object ensured above");
+        if parts.peek().is_none() {
+            map.insert(part.to_string(), value);
+            return;
+        }
+        current = map
+            .entry(part.to_string())
+            .or_insert_with(|| serde_json::json!({}));
+    }
+}
+
 pub async fn get_cluster_license(
     conn_mgr: &ConnectionManager,
     profile_name: Option<&str>,
@@ -149,20 +314,46 @@ pub async fn update_cluster_license(
 // Cluster Operations Commands
 // ============================================================================
 
+/// Bootstrap settings given as flags
+#[derive(Debug, Clone, Default)]
+pub struct BootstrapFlags {
+    pub cluster_name: Option<String>,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    /// Read the password from stdin
+    pub password_stdin: bool,
+    pub rack_aware: bool,
+    pub persistent_path: Option<String>,
+    pub ephemeral_path: Option<String>,
+    pub rack_id: Option<String>,
+    pub addr: Option<String>,
+    pub external_addr: Vec<String>,
+    pub max_listeners: Option<u32>,
+}
+
 pub async fn bootstrap_cluster(
     conn_mgr: &ConnectionManager,
     profile_name: Option<&str>,
-    data: &str,
+    data: Option<&str>,
+    flags: &BootstrapFlags,
     output_format: OutputFormat,
     query: Option<&str>,
 ) -> CliResult<()> {
-    let client = conn_mgr.create_enterprise_client(profile_name).await?;
-    let _handler = BootstrapHandler::new(client.clone());
+    let data = data
+        .map(|d| read_json_data(d).context("Failed to parse bootstrap data"))
+        .transpose()?;
+    let mut flags = flags.clone();
+    if flags.password.is_some() || flags.password_stdin || needs_password(data.as_ref()) {
+        flags.password = Some(crate::password::resolve_password(
+            flags.password.as_deref(),
+            flags.password_stdin,
+        )?);
+    }
+    let request = bootstrap_request(data, &flags)?;
 
-    let bootstrap_data = read_json_data(data).context("Failed to parse bootstrap data")?;
-    // Use raw API since BootstrapRequest doesn't have Deserialize trait
+    let client = conn_mgr.create_enterprise_client(profile_name).await?;
     let result = client
-        .post_raw("/v1/bootstrap", bootstrap_data)
+        .post_raw("/v1/bootstrap", request)
         .await
         .context("Failed to bootstrap cluster")?;
     let data = handle_output(result, output_format, query)?;
@@ -170,6 +361,77 @@ pub async fn bootstrap_cluster(
     Ok(())
 }
 
+/// Whether the request creates a cluster and `--data` has no admin password for it
+fn needs_password(data: Option<&serde_json::Value>) -> bool {
+    let Some(data) = data else {
+        return true;
+    };
+    data["action"] == "create_cluster" && data["credentials"].get("password").is_none()
+}
+
+/// The bootstrap request: `--data` (or a new `create_cluster` request) with the flags applied
+fn bootstrap_request(
+    data: Option<serde_json::Value>,
+    flags: &BootstrapFlags,
+) -> CliResult<serde_json::Value> {
+    let mut request = data.unwrap_or_else(|| serde_json::json!({ "action": "create_cluster" }));
+    if !request.is_object() {
+        return Err(RedisCtlError::Validation {
+            message: "Bootstrap data must be a JSON object".to_string(),
+        });
+    }
+
+    let strings = [
+        ("cluster.name", &flags.cluster_name),
+        ("credentials.username", &flags.username),
+        ("credentials.password", &flags.password),
+        ("node.paths.persistent_path", &flags.persistent_path),
+        ("node.paths.ephemeral_path", &flags.ephemeral_path),
+        ("node.identity.addr", &flags.addr),
+        ("node.identity.rack_id", &flags.rack_id),
+    ];
+    for (key, value) in strings {
+        if let Some(value) = value {
+            insert_dotted(&mut request, key, serde_json::json!(value));
+        }
+    }
+    if !flags.external_addr.is_empty() {
+        insert_dotted(
+            &mut request,
+            "node.identity.external_addr",
+            serde_json::json!(flags.external_addr),
+        );
+    }
+    if let Some(max_listeners) = flags.max_listeners {
+        insert_dotted(
+            &mut request,
+            "node.limits.max_listeners",
+            serde_json::json!(max_listeners),
+        );
+    }
+    if flags.rack_aware {
+        insert_dotted(&mut request, "cluster.rack_aware", serde_json::json!(true));
+    }
+
+    if request["action"] == "create_cluster" {
+        let missing: Vec<&str> = [
+            ("cluster", "name", "--cluster-name"),
+            ("credentials", "username", "--username"),
+            ("credentials", "password", "--password"),
+        ]
+        .into_iter()
+        .filter(|(section, field, _)| request[*section].get(*field).is_none())
+        .map(|(_, _, flag)| flag)
+        .collect();
+        if !missing.is_empty() {
+            return Err(RedisCtlError::Validation {
+                message: format!("Creating a cluster requires {}", missing.join(", ")),
+            });
+        }
+    }
+    Ok(request)
+}
+
 pub async fn join_cluster(
     conn_mgr: &ConnectionManager,
     profile_name: Option<&str>,
@@ -207,46 +469,262 @@ pub async fn join_cluster(
     Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
 pub async fn recover_cluster(
     conn_mgr: &ConnectionManager,
     profile_name: Option<&str>,
-    data: &str,
+    data: Option<&str>,
+    dry_run: bool,
+    timeout_secs: u64,
+    interval_secs: u64,
     output_format: OutputFormat,
     query: Option<&str>,
 ) -> CliResult<()> {
     let client = conn_mgr.create_enterprise_client(profile_name).await?;
+    let handler = ClusterHandler::new(client.clone());
 
-    let recovery_data = read_json_data(data).context("Failed to parse recovery data")?;
-    let result = client
-        .post_raw("/v1/cluster/recover", recovery_data)
-        .await?;
-    let data = handle_output(result, output_format, query)?;
-    print_formatted_output(data, output_format)?;
-    Ok(())
+    let plan = handler
+        .recovery_plan()
+        .await
+        .context("Failed to fetch the cluster recovery plan")?;
+    if dry_run {
+        if matches!(output_format, OutputFormat::Auto | OutputFormat::Table) && query.is_none() {
+            print!("{}", format_recovery_plan(&plan));
+        } else {
+            let json = serde_json::to_value(&plan).context("Failed to serialize recovery plan")?;
+            let data = handle_output(json, output_format, query)?;
+            print_formatted_output(data, output_format)?;
+        }
+        return Ok(());
+    }
+    eprint!("{}", format_recovery_plan(&plan));
+
+    let response: ClusterActionResponse = match data {
+        Some(data) => {
+            let recovery_data = read_json_data(data).context("Failed to parse recovery data")?;
+            client
+                .post("/v1/cluster/actions/recover", &recovery_data)
+                .await
+                .context("Failed to start cluster recovery")?
+        }
+        None => handler
+            .recover()
+            .await
+            .context("Failed to start cluster recovery")?,
+    };
+    notice!("Recovery started, action UID: {}", response.action_uid);
+
+    let action = wait_for_action(
+        &client,
+        &response.action_uid,
+        timeout_secs,
+        interval_secs,
+        false,
+    )
+    .await?;
+    finish_cluster_action("Recovery", response, action, output_format, query)
 }
 
 pub async fn reset_cluster(
     conn_mgr: &ConnectionManager,
     profile_name: Option<&str>,
-    force: bool,
-    _output_format: OutputFormat,
-    _query: Option<&str>,
+    timeout_secs: u64,
+    interval_secs: u64,
+    output_format: OutputFormat,
+    query: Option<&str>,
+) -> CliResult<()> {
+    let client = conn_mgr.create_enterprise_client(profile_name).await?;
+    let handler = ClusterHandler::new(client.clone());
+
+    let info = handler
+        .info()
+        .await
+        .context("Failed to read the cluster before resetting it")?;
+    eprintln!(
+        "WARNING: Resetting cluster '{}' deletes its {} database(s) and all configuration on {} node(s).",
+        info.name,
+        info.databases.as_ref().map_or(0, Vec::len),
+        info.nodes.as_ref().map_or(0, Vec::len)
+    );
+
+    // --force is mandatory; an interactive terminal additionally has to type the cluster name
+    use std::io::IsTerminal;
+    if std::io::stdin().is_terminal()
+        && !crate::interactive::is_non_interactive()
+        && !confirm_by_typing(
+            &format!("Reset cluster '{}' to factory defaults?", info.name),
+            &info.name,
+        )?
+    {
+        notice!("Operation cancelled");
+        return Ok(());
+    }
+
+    let response = handler
+        .reset()
+        .await
+        .context("Failed to start cluster reset")?;
+    notice!("Reset started, action UID: {}", response.action_uid);
+
+    // Once the reset wipes the configuration, the API stops accepting these credentials
+    let action = wait_for_action(
+        &client,
+        &response.action_uid,
+        timeout_secs,
+        interval_secs,
+        true,
+    )
+    .await?;
+    finish_cluster_action("Reset", response, action, output_format, query)
+}
+
+/// Poll an action until it finishes; with `tolerate_unreachable`, losing the API ends polling
+/// with `None` instead of an error
+pub(super) async fn wait_for_action(
+    client: &redis_enterprise::EnterpriseClient,
+    action_uid: &str,
+    timeout_secs: u64,
+    interval_secs: u64,
+    tolerate_unreachable: bool,
+) -> CliResult<Option<Action>> {
+    let handler = ActionHandler::new(client.clone());
+    let start = std::time::Instant::now();
+    let progress = TaskProgress::start(action_uid);
+
+    loop {
+        match handler.get(action_uid).await {
+            Ok(action) => {
+                let status = action.status.to_lowercase();
+                if matches!(status.as_str(), "completed" | "failed" | "cancelled") {
+                    progress.finish(&action.status, &action.status);
+                    return Ok(Some(action));
+                }
+                let display = match action.progress {
+                    Some(percent) => format!("{} ({:.0}%)", action.status, percent),
+                    None => action.status.clone(),
+                };
+                progress.update(&action.status, &display);
+            }
+            Err(e) if tolerate_unreachable => {
+                progress.finish("unreachable", "cluster API no longer responding");
+                notice!(
+                    "The cluster API stopped responding ({}); this is expected once a reset completes",
+                    e
+                );
+                return Ok(None);
+            }
+            Err(e) => {
+                return Err(RedisCtlError::api(format!(
+                    "Failed to read action {}: {}",
+                    action_uid, e
+                )));
+            }
+        }
+
+        if start.elapsed().as_secs() >= timeout_secs {
+            progress.timeout();
+            return Err(RedisCtlError::Timeout {
+                message: format!(
+                    "Action {} did not finish within {} seconds; it keeps running on the cluster",
+                    action_uid, timeout_secs
+                ),
+            });
+        }
+        tokio::time::sleep(std::time::Duration::from_secs(interval_secs)).await;
+    }
+}
+
+/// Print the outcome of a cluster action and fail if it did not complete
+fn finish_cluster_action(
+    label: &str,
+    response: ClusterActionResponse,
+    action: Option<Action>,
+    output_format: OutputFormat,
+    query: Option<&str>,
 ) -> CliResult<()> {
-    if !force {
-        eprintln!("WARNING: This will completely reset the cluster!");
-        eprintln!("All data, configurations, and databases will be lost.");
-        if !confirm_action("Are you absolutely sure you want to reset the cluster?")? {
-            println!("Operation cancelled");
-            return Ok(());
+    let status = action.as_ref().map(|action| action.status.clone());
+    let error = action.as_ref().and_then(|action| action.error.clone());
+    let json = match action {
+        Some(action) => serde_json::to_value(action),
+        None => serde_json::to_value(response),
+    }
+    .context("Failed to serialize action")?;
+
+    if matches!(output_format, OutputFormat::Auto | OutputFormat::Table) && query.is_none() {
+        match &status {
+            Some(status) => notice!("{} finished with status: {}", label, status),
+            None => notice!(
+                "{} started; the cluster no longer answers with these credentials",
+                label
+            ),
         }
+    } else {
+        let data = handle_output(json, output_format, query)?;
+        print_formatted_output(data, output_format)?;
     }
 
-    let client = conn_mgr.create_enterprise_client(profile_name).await?;
-    client
-        .post_raw("/v1/cluster/reset", serde_json::json!({}))
-        .await?;
-    println!("Cluster reset initiated");
-    Ok(())
+    match status.as_deref() {
+        Some(status) if !status.eq_ignore_ascii_case("completed") => {
+            Err(RedisCtlError::api(format!(
+                "{} ended with status {}{}",
+                label,
+                status,
+                error.map(|e| format!(": {}", e)).unwrap_or_default()
+            )))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Human-readable recovery plan
+fn format_recovery_plan(plan: &ClusterRecoveryPlan) -> String {
+    let mut out = format!(
+        "Recovery plan (status: {})\n",
+        plan.status.as_deref().unwrap_or("unknown")
+    );
+    if plan.bdbs.is_empty() {
+        out.push_str("  No databases to recover\n");
+        return out;
+    }
+    let rows: Vec<[String; 4]> = plan
+        .bdbs
+        .iter()
+        .map(|bdb| {
+            [
+                bdb.uid.map_or("-".to_string(), |uid| uid.to_string()),
+                bdb.name.clone().unwrap_or_else(|| "-".to_string()),
+                bdb.source.clone().unwrap_or_else(|| "-".to_string()),
+                bdb.status.clone().unwrap_or_else(|| "-".to_string()),
+            ]
+        })
+        .collect();
+    let header = ["UID", "NAME", "SOURCE", "STATUS"];
+    let widths: Vec<usize> = (0..4)
+        .map(|i| {
+            rows.iter()
+                .map(|row| row[i].len())
+                .chain([header[i].len()])
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+    let line = |cells: [&str; 4]| {
+        format!(
+            "  {:<w0$}  {:<w1$}  {:<w2$}  {}\n",
+            cells[0],
+            cells[1],
+            cells[2],
+            cells[3],
+            w0 = widths[0],
+            w1 = widths[1],
+            w2 = widths[2]
+        )
+    };
+    out.push_str(&line(header));
+    for row in &rows {
+        out.push_str(&line([&row[0], &row[1], &row[2], &row[3]]));
+    }
+    out
 }
 
 // ============================================================================
@@ -256,12 +734,17 @@ pub async fn reset_cluster(
 pub async fn get_cluster_stats(
     conn_mgr: &ConnectionManager,
     profile_name: Option<&str>,
+    export: Option<StatsExportFormat>,
     output_format: OutputFormat,
     query: Option<&str>,
 ) -> CliResult<()> {
     let client = conn_mgr.create_enterprise_client(profile_name).await?;
     let handler = ClusterHandler::new(client);
     let stats = handler.stats().await?;
+    if let Some(format) = export {
+        print!("{}", stats_export::render(&stats, format));
+        return Ok(());
+    }
     let data = handle_output(stats, output_format, query)?;
     print_formatted_output(data, output_format)?;
     Ok(())
@@ -303,32 +786,6 @@ pub async fn get_cluster_alerts(
     Ok(())
 }
 
-pub async fn get_cluster_events(
-    conn_mgr: &ConnectionManager,
-    profile_name: Option<&str>,
-    limit: Option<u32>,
-    output_format: OutputFormat,
-    query: Option<&str>,
-) -> CliResult<()> {
-    let client = conn_mgr.create_enterprise_client(profile_name).await?;
-
-    let endpoint = if let Some(limit) = limit {
-        format!("/v1/cluster/events?limit={}", limit)
-    } else {
-        "/v1/cluster/events".to_string()
-    };
-
-    let events = client.get_raw(&endpoint).await.unwrap_or_else(|_| {
-        serde_json::json!({
-            "message": "Events endpoint not available"
-        })
-    });
-
-    let data = handle_output(events, output_format, query)?;
-    print_formatted_output(data, output_format)?;
-    Ok(())
-}
-
 pub async fn get_audit_log(
     conn_mgr: &ConnectionManager,
     profile_name: Option<&str>,
@@ -359,65 +816,6 @@ pub async fn get_audit_log(
 // Cluster Maintenance Commands
 // ============================================================================
 
-pub async fn enable_maintenance_mode(
-    conn_mgr: &ConnectionManager,
-    profile_name: Option<&str>,
-    output_format: OutputFormat,
-    query: Option<&str>,
-) -> CliResult<()> {
-    let client = conn_mgr.create_enterprise_client(profile_name).await?;
-
-    let result = client
-        .post_raw(
-            "/v1/cluster/maintenance_mode",
-            serde_json::json!({"enabled": true}),
-        )
-        .await?;
-
-    let data = handle_output(result, output_format, query)?;
-    print_formatted_output(data, output_format)?;
-    Ok(())
-}
-
-pub async fn disable_maintenance_mode(
-    conn_mgr: &ConnectionManager,
-    profile_name: Option<&str>,
-    output_format: OutputFormat,
-    query: Option<&str>,
-) -> CliResult<()> {
-    let client = conn_mgr.create_enterprise_client(profile_name).await?;
-
-    let result = client
-        .post_raw(
-            "/v1/cluster/maintenance_mode",
-            serde_json::json!({"enabled": false}),
-        )
-        .await?;
-
-    let data = handle_output(result, output_format, query)?;
-    print_formatted_output(data, output_format)?;
-    Ok(())
-}
-
-pub async fn collect_debug_info(
-    conn_mgr: &ConnectionManager,
-    profile_name: Option<&str>,
-    output_format: OutputFormat,
-    query: Option<&str>,
-) -> CliResult<()> {
-    let client = conn_mgr.create_enterprise_client(profile_name).await?;
-    let _handler = DebugInfoHandler::new(client.clone());
-
-    // Use raw API since handler.create expects CreateCrdbRequest
-    let result = client
-        .post_raw("/v1/debuginfo", serde_json::json!({}))
-        .await?;
-    let result_json = serde_json::to_value(result).context("Failed to serialize result")?;
-    let data = handle_output(result_json, output_format, query)?;
-    print_formatted_output(data, output_format)?;
-    Ok(())
-}
-
 pub async fn check_cluster_status(
     conn_mgr: &ConnectionManager,
     profile_name: Option<&str>,
@@ -540,3 +938,131 @@ pub async fn update_ocsp_config(
     print_formatted_output(data, output_format)?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_cm_setting_types() {
+        assert_eq!(
+            parse_cm_setting("parallel_shards_upgrade=4").unwrap(),
+            ("parallel_shards_upgrade".to_string(), json!(4))
+        );
+        assert_eq!(
+            parse_cm_setting("auto_failover=false").unwrap(),
+            ("auto_failover".to_string(), json!(false))
+        );
+        assert_eq!(
+            parse_cm_setting("timeouts.edit_db=120").unwrap(),
+            ("timeouts.edit_db".to_string(), json!(120))
+        );
+        assert!(parse_cm_setting("auto_failover=maybe").is_err());
+        assert!(parse_cm_setting("cm_port").is_err());
+    }
+
+    #[test]
+    fn test_parse_cm_setting_unknown_key() {
+        assert!(parse_cm_setting("no_such_setting=1").is_err());
+        assert!(parse_cm_setting("cm_port.nested=1").is_err());
+        assert!(parse_cm_setting("timeouts.=1").is_err());
+    }
+
+    #[test]
+    fn test_insert_dotted() {
+        let mut value = json!({"timeouts": {"edit_db": 60}});
+        insert_dotted(&mut value, "timeouts.create_db", json!(90));
+        insert_dotted(&mut value, "slave_ha", json!(true));
+        assert_eq!(
+            value,
+            json!({"timeouts": {"edit_db": 60, "create_db": 90}, "slave_ha": true})
+        );
+    }
+
+    #[test]
+    fn test_bootstrap_request() {
+        let flags = BootstrapFlags {
+            cluster_name: Some("cluster.example.com".to_string()),
+            username: Some("admin@example.com".to_string()),
+            password: Some("secret".to_string()),
+            rack_aware: true,
+            persistent_path: Some("/data/persist".to_string()),
+            rack_id: Some("zone-a".to_string()),
+            external_addr: vec!["203.0.113.5".to_string()],
+            max_listeners: Some(100),
+            ..Default::default()
+        };
+        assert_eq!(
+            bootstrap_request(None, &flags).unwrap(),
+            json!({
+                "action": "create_cluster",
+                "cluster": {"name": "cluster.example.com", "rack_aware": true},
+                "credentials": {"username": "admin@example.com", "password": "secret"},
+                "node": {
+                    "paths": {"persistent_path": "/data/persist"},
+                    "identity": {"rack_id": "zone-a", "external_addr": ["203.0.113.5"]},
+                    "limits": {"max_listeners": 100}
+                }
+            })
+        );
+
+        // Flags fill in --data without dropping its other fields
+        let data = json!({
+            "action": "create_cluster",
+            "cluster": {"name": "from-data"},
+            "credentials": {"username": "a", "password": "b"},
+            "node": {"paths": {"ephemeral_path": "/tmp/re"}}
+        });
+        let flags = BootstrapFlags {
+            persistent_path: Some("/data/persist".to_string()),
+            ..Default::default()
+        };
+        let request = bootstrap_request(Some(data), &flags).unwrap();
+        assert_eq!(
+            request["node"]["paths"],
+            json!({"ephemeral_path": "/tmp/re", "persistent_path": "/data/persist"})
+        );
+
+        let err = bootstrap_request(None, &BootstrapFlags::default()).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("--cluster-name, --username, --password")
+        );
+        let join = json!({"action": "join_cluster"});
+        assert!(bootstrap_request(Some(join), &BootstrapFlags::default()).is_ok());
+    }
+
+    #[test]
+    fn test_needs_password() {
+        assert!(needs_password(None));
+        assert!(needs_password(Some(&json!({"action": "create_cluster"}))));
+        assert!(!needs_password(Some(&json!({
+            "action": "create_cluster",
+            "credentials": {"username": "a", "password": "b"}
+        }))));
+        assert!(!needs_password(Some(&json!({"action": "join_cluster"}))));
+    }
+
+    #[test]
+    fn test_format_recovery_plan() {
+        let plan: ClusterRecoveryPlan = serde_json::from_value(json!({
+            "status": "recovery_needed",
+            "bdbs": [
+                {"uid": 1, "name": "cache", "source": "persistence", "status": "pending"},
+                {"uid": 12, "name": "sessions"}
+            ]
+        }))
+        .unwrap();
+        assert_eq!(
+            format_recovery_plan(&plan),
+            "Recovery plan (status: recovery_needed)\n\
+             \x20 UID  NAME      SOURCE       STATUS\n\
+             \x20 1    cache     persistence  pending\n\
+             \x20 12   sessions  -            -\n"
+        );
+
+        let empty: ClusterRecoveryPlan = serde_json::from_value(json!({})).unwrap();
+        assert!(format_recovery_plan(&empty).ends_with("No databases to recover\n"));
+    }
+}
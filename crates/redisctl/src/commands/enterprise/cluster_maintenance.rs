@@ -0,0 +1,459 @@
+//! `enterprise cluster maintenance-mode`: node maintenance with quorum checks
+//!
+//! Redis Enterprise has no cluster-wide maintenance switch; maintenance mode is a node action
+//! that migrates the node's shards away and stops it from accepting new ones. These commands
+//! apply it to a set of nodes one at a time, and refuse to take so many nodes out of service that
+//! fewer than a majority of the cluster would keep serving.
+
+#![allow(dead_code)]
+
+use super::cluster_impl::wait_for_action;
+use super::utils::*;
+use crate::cli::{EnterpriseClusterMaintenanceCommands, OutputFormat};
+use crate::connection::ConnectionManager;
+use crate::error::{RedisCtlError, Result as CliResult};
+use crate::output::notice;
+use anyhow::Context;
+use redis_enterprise::{
+    ClusterHandler, ClusterMaintenanceStatus, MaintenanceOffRequest, MaintenanceOnRequest,
+    MaintenanceQuorum, NodeMaintenanceStatus,
+};
+use serde::Serialize;
+use tabled::{Table, Tabled, settings::Style};
+
+pub async fn handle_maintenance_command(
+    conn_mgr: &ConnectionManager,
+    profile_name: Option<&str>,
+    command: &EnterpriseClusterMaintenanceCommands,
+    output_format: OutputFormat,
+    query: Option<&str>,
+) -> CliResult<()> {
+    match command {
+        EnterpriseClusterMaintenanceCommands::Enable {
+            nodes,
+            keep_replica_shards,
+            evict_ha_replica,
+            evict_active_active_replica,
+            force,
+            timeout,
+            interval,
+        } => {
+            let request = MaintenanceOnRequest {
+                keep_slave_shards: keep_replica_shards.then_some(true),
+                evict_ha_replica: evict_ha_replica.then_some(true),
+                evict_active_active_replica: evict_active_active_replica.then_some(true),
+            };
+            enable_maintenance(
+                conn_mgr,
+                profile_name,
+                nodes,
+                &request,
+                *force,
+                (*timeout, *interval),
+                output_format,
+                query,
+            )
+            .await
+        }
+        EnterpriseClusterMaintenanceCommands::Disable {
+            nodes,
+            all,
+            skip_shards_restore,
+            timeout,
+            interval,
+        } => {
+            let request = MaintenanceOffRequest {
+                skip_shards_restore: skip_shards_restore.then_some(true),
+                snapshot_name: None,
+            };
+            disable_maintenance(
+                conn_mgr,
+                profile_name,
+                nodes,
+                *all,
+                &request,
+                (*timeout, *interval),
+                output_format,
+                query,
+            )
+            .await
+        }
+        EnterpriseClusterMaintenanceCommands::Status => {
+            let client = conn_mgr.create_enterprise_client(profile_name).await?;
+            let status = ClusterHandler::new(client)
+                .maintenance_status()
+                .await
+                .context("Failed to read node maintenance status")?;
+            print_report(&MaintenanceReport::of(status), output_format, query)
+        }
+    }
+}
+
+/// Node maintenance state together with the cluster's quorum, for display
+#[derive(Debug, Clone, Serialize)]
+pub struct MaintenanceReport {
+    pub nodes: Vec<NodeMaintenanceStatus>,
+    pub quorum: MaintenanceQuorum,
+}
+
+impl MaintenanceReport {
+    pub fn of(status: ClusterMaintenanceStatus) -> Self {
+        let quorum = status.quorum_after(&[]);
+        MaintenanceReport {
+            nodes: status.nodes,
+            quorum,
+        }
+    }
+}
+
+/// The nodes to act on, failing for UIDs the cluster does not have
+fn select_nodes(status: &ClusterMaintenanceStatus, uids: &[u32]) -> CliResult<Vec<u32>> {
+    let unknown: Vec<String> = uids
+        .iter()
+        .filter(|uid| !status.nodes.iter().any(|node| node.uid == **uid))
+        .map(|uid| uid.to_string())
+        .collect();
+    if !unknown.is_empty() {
+        return Err(RedisCtlError::Validation {
+            message: format!("No node with UID {} in the cluster", unknown.join(", ")),
+        });
+    }
+    let mut selected = uids.to_vec();
+    selected.sort_unstable();
+    selected.dedup();
+    Ok(selected)
+}
+
+/// Check that the cluster keeps a majority of serving nodes once `uids` are in maintenance
+///
+/// `force` overrides a lost majority, but never a change that would leave no node serving.
+fn check_quorum(
+    status: &ClusterMaintenanceStatus,
+    uids: &[u32],
+    force: bool,
+) -> CliResult<MaintenanceQuorum> {
+    let quorum = status.quorum_after(uids);
+    if quorum.available == 0 {
+        return Err(RedisCtlError::Validation {
+            message: format!(
+                "Refusing to put node(s) {} into maintenance: no node of the {} would keep serving",
+                join_uids(uids),
+                quorum.total
+            ),
+        });
+    }
+    if !quorum.is_met() && !force {
+        return Err(RedisCtlError::Validation {
+            message: format!(
+                "Putting node(s) {} into maintenance leaves {} of {} nodes serving, fewer than the \
+                 {} a majority needs; pass --force to proceed anyway",
+                join_uids(uids),
+                quorum.available,
+                quorum.total,
+                quorum.required
+            ),
+        });
+    }
+    Ok(quorum)
+}
+
+fn join_uids(uids: &[u32]) -> String {
+    uids.iter()
+        .map(|uid| uid.to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn is_in_maintenance(status: &ClusterMaintenanceStatus, uid: u32) -> bool {
+    status.in_maintenance().any(|node| node.uid == uid)
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn enable_maintenance(
+    conn_mgr: &ConnectionManager,
+    profile_name: Option<&str>,
+    uids: &[u32],
+    request: &MaintenanceOnRequest,
+    force: bool,
+    (timeout_secs, interval_secs): (u64, u64),
+    output_format: OutputFormat,
+    query: Option<&str>,
+) -> CliResult<()> {
+    let client = conn_mgr.create_enterprise_client(profile_name).await?;
+    let handler = ClusterHandler::new(client.clone());
+    let status = handler
+        .maintenance_status()
+        .await
+        .context("Failed to read node maintenance status")?;
+
+    let mut targets = select_nodes(&status, uids)?;
+    targets.retain(|uid| {
+        let already = is_in_maintenance(&status, *uid);
+        if already {
+            notice!("Node {} is already in maintenance mode", uid);
+        }
+        !already
+    });
+    if targets.is_empty() {
+        return print_report(&MaintenanceReport::of(status), output_format, query);
+    }
+    let quorum = check_quorum(&status, &targets, force)?;
+    if !quorum.is_met() {
+        notice!(
+            "WARNING: only {} of {} nodes will keep serving; the cluster loses its majority",
+            quorum.available,
+            quorum.total
+        );
+    }
+
+    for uid in targets {
+        let response = handler
+            .enable_node_maintenance(uid, request)
+            .await
+            .context(format!("Failed to put node {} into maintenance", uid))?;
+        notice!(
+            "Node {} entering maintenance, action UID: {}",
+            uid,
+            response.action_uid
+        );
+        finish_node_action(
+            &client,
+            uid,
+            &response.action_uid,
+            "maintenance_on",
+            timeout_secs,
+            interval_secs,
+        )
+        .await?;
+    }
+
+    let status = handler
+        .maintenance_status()
+        .await
+        .context("Failed to read node maintenance status")?;
+    print_report(&MaintenanceReport::of(status), output_format, query)
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn disable_maintenance(
+    conn_mgr: &ConnectionManager,
+    profile_name: Option<&str>,
+    uids: &[u32],
+    all: bool,
+    request: &MaintenanceOffRequest,
+    (timeout_secs, interval_secs): (u64, u64),
+    output_format: OutputFormat,
+    query: Option<&str>,
+) -> CliResult<()> {
+    let client = conn_mgr.create_enterprise_client(profile_name).await?;
+    let handler = ClusterHandler::new(client.clone());
+    let status = handler
+        .maintenance_status()
+        .await
+        .context("Failed to read node maintenance status")?;
+
+    let mut targets = if all {
+        status.in_maintenance().map(|node| node.uid).collect()
+    } else {
+        select_nodes(&status, uids)?
+    };
+    targets.retain(|uid| {
+        let in_maintenance = is_in_maintenance(&status, *uid);
+        if !in_maintenance {
+            notice!("Node {} is not in maintenance mode", uid);
+        }
+        in_maintenance
+    });
+    if targets.is_empty() && all {
+        notice!("No node is in maintenance mode");
+    }
+
+    for uid in targets {
+        let response = handler
+            .disable_node_maintenance(uid, request)
+            .await
+            .context(format!("Failed to take node {} out of maintenance", uid))?;
+        notice!(
+            "Node {} leaving maintenance, action UID: {}",
+            uid,
+            response.action_uid
+        );
+        finish_node_action(
+            &client,
+            uid,
+            &response.action_uid,
+            "maintenance_off",
+            timeout_secs,
+            interval_secs,
+        )
+        .await?;
+    }
+
+    let status = handler
+        .maintenance_status()
+        .await
+        .context("Failed to read node maintenance status")?;
+    print_report(&MaintenanceReport::of(status), output_format, query)
+}
+
+/// Wait for a node's maintenance action and fail unless it completed
+async fn finish_node_action(
+    client: &redis_enterprise::EnterpriseClient,
+    uid: u32,
+    action_uid: &str,
+    label: &str,
+    timeout_secs: u64,
+    interval_secs: u64,
+) -> CliResult<()> {
+    let action = wait_for_action(client, action_uid, timeout_secs, interval_secs, false).await?;
+    match action {
+        Some(action) if !action.status.eq_ignore_ascii_case("completed") => {
+            Err(RedisCtlError::api(format!(
+                "{} on node {} ended with status {}{}",
+                label,
+                uid,
+                action.status,
+                action.error.map(|e| format!(": {}", e)).unwrap_or_default()
+            )))
+        }
+        _ => Ok(()),
+    }
+}
+
+#[derive(Tabled)]
+struct NodeRow {
+    #[tabled(rename = "NODE")]
+    uid: u32,
+    #[tabled(rename = "ADDRESS")]
+    addr: String,
+    #[tabled(rename = "STATUS")]
+    status: String,
+    #[tabled(rename = "MAINTENANCE")]
+    maintenance: &'static str,
+    #[tabled(rename = "ACCEPTS SHARDS")]
+    accept_servers: String,
+    #[tabled(rename = "SHARDS")]
+    shards: String,
+}
+
+fn print_report(
+    report: &MaintenanceReport,
+    output_format: OutputFormat,
+    query: Option<&str>,
+) -> CliResult<()> {
+    match output_format {
+        OutputFormat::Auto | OutputFormat::Table if query.is_none() => {
+            let rows: Vec<NodeRow> = report
+                .nodes
+                .iter()
+                .map(|node| NodeRow {
+                    uid: node.uid,
+                    addr: node.addr.clone().unwrap_or_else(|| "-".to_string()),
+                    status: node.status.clone(),
+                    maintenance: if node.in_maintenance { "yes" } else { "no" },
+                    accept_servers: node
+                        .accept_servers
+                        .map_or("-".to_string(), |accepts| accepts.to_string()),
+                    shards: node
+                        .shard_count
+                        .map_or("-".to_string(), |count| count.to_string()),
+                })
+                .collect();
+            let mut table = Table::new(rows);
+            table.with(Style::blank());
+            println!("{}", table);
+            println!();
+            println!("{}", format_quorum(&report.quorum));
+            Ok(())
+        }
+        _ => {
+            let json =
+                serde_json::to_value(report).context("Failed to serialize maintenance status")?;
+            let data = handle_output(json, output_format, query)?;
+            print_formatted_output(data, output_format)
+        }
+    }
+}
+
+fn format_quorum(quorum: &MaintenanceQuorum) -> String {
+    format!(
+        "Serving: {} of {} nodes (majority: {}){}",
+        quorum.available,
+        quorum.total,
+        quorum.required,
+        if quorum.is_met() {
+            ""
+        } else {
+            " - QUORUM LOST"
+        }
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node(uid: u32, status: &str, in_maintenance: bool) -> NodeMaintenanceStatus {
+        NodeMaintenanceStatus {
+            uid,
+            addr: Some(format!("10.0.0.{}", uid)),
+            status: status.to_string(),
+            in_maintenance,
+            accept_servers: Some(!in_maintenance),
+            shard_count: None,
+        }
+    }
+
+    fn cluster(nodes: Vec<NodeMaintenanceStatus>) -> ClusterMaintenanceStatus {
+        ClusterMaintenanceStatus { nodes }
+    }
+
+    #[test]
+    fn test_check_quorum_keeps_majority() {
+        let status = cluster(vec![
+            node(1, "active", false),
+            node(2, "active", false),
+            node(3, "active", false),
+        ]);
+        let quorum = check_quorum(&status, &[2], false).unwrap();
+        assert_eq!((quorum.available, quorum.required), (2, 2));
+
+        // Two of three in maintenance loses the majority
+        assert!(check_quorum(&status, &[1, 2], false).is_err());
+        assert!(!check_quorum(&status, &[1, 2], true).unwrap().is_met());
+
+        // Never every node, even with --force
+        assert!(check_quorum(&status, &[1, 2, 3], true).is_err());
+    }
+
+    #[test]
+    fn test_check_quorum_counts_down_and_maintenance_nodes() {
+        let status = cluster(vec![
+            node(1, "active", false),
+            node(2, "active", true),
+            node(3, "down", false),
+            node(4, "active", false),
+            node(5, "active", false),
+        ]);
+        // 3 of 5 serve now; taking node 4 out leaves 2, short of the 3 needed
+        assert!(check_quorum(&status, &[], false).unwrap().is_met());
+        assert!(check_quorum(&status, &[4], false).is_err());
+    }
+
+    #[test]
+    fn test_select_nodes() {
+        let status = cluster(vec![node(1, "active", false), node(2, "active", false)]);
+        assert_eq!(select_nodes(&status, &[2, 1, 2]).unwrap(), vec![1, 2]);
+        let err = select_nodes(&status, &[1, 7]).unwrap_err();
+        assert!(err.to_string().contains("UID 7"));
+    }
+
+    #[test]
+    fn test_format_quorum() {
+        let status = cluster(vec![node(1, "active", false), node(2, "active", true)]);
+        assert_eq!(
+            format_quorum(&status.quorum_after(&[])),
+            "Serving: 1 of 2 nodes (majority: 2) - QUORUM LOST"
+        );
+    }
+}
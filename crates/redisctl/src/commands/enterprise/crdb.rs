@@ -1,6 +1,7 @@
 #![allow(dead_code)]
 
-use crate::cli::{EnterpriseCrdbCommands, OutputFormat};
+use crate::cli::{EnterpriseCrdbCommands, EnterpriseCrdbTaskCommands, OutputFormat};
+use crate::commands::examples::{ExampleKind, print_example};
 use crate::connection::ConnectionManager;
 use crate::error::Result as CliResult;
 
@@ -21,11 +22,23 @@ pub async fn handle_crdb_command(
         EnterpriseCrdbCommands::Get { id } => {
             crdb_impl::get_crdb(conn_mgr, profile_name, *id, output_format, query).await
         }
-        EnterpriseCrdbCommands::Create { data } => {
+        EnterpriseCrdbCommands::Create { data, example } => {
+            let Some(data) = data.as_deref().filter(|_| !*example) else {
+                return print_example(ExampleKind::EnterpriseCrdb, output_format);
+            };
             crdb_impl::create_crdb(conn_mgr, profile_name, data, output_format, query).await
         }
-        EnterpriseCrdbCommands::Update { id, data } => {
-            crdb_impl::update_crdb(conn_mgr, profile_name, *id, data, output_format, query).await
+        EnterpriseCrdbCommands::Update { id, data, patch } => {
+            crdb_impl::update_crdb(
+                conn_mgr,
+                profile_name,
+                *id,
+                data.as_deref(),
+                patch.as_deref(),
+                output_format,
+                query,
+            )
+            .await
         }
         EnterpriseCrdbCommands::Delete { id, force } => {
             crdb_impl::delete_crdb(conn_mgr, profile_name, *id, *force, output_format, query).await
@@ -205,6 +218,31 @@ pub async fn handle_crdb_command(
             )
             .await
         }
+        EnterpriseCrdbCommands::Task(task_cmd) => match task_cmd {
+            EnterpriseCrdbTaskCommands::List => {
+                crdb_impl::list_crdb_tasks(conn_mgr, profile_name, output_format, query).await
+            }
+            EnterpriseCrdbTaskCommands::Get { id } => {
+                crdb_impl::get_coordinated_task(conn_mgr, profile_name, id, output_format, query)
+                    .await
+            }
+            EnterpriseCrdbTaskCommands::Wait {
+                id,
+                timeout,
+                interval,
+            } => {
+                crdb_impl::wait_crdb_task(
+                    conn_mgr,
+                    profile_name,
+                    id,
+                    *timeout,
+                    *interval,
+                    output_format,
+                    query,
+                )
+                .await
+            }
+        },
 
         // Monitoring & Metrics
         EnterpriseCrdbCommands::Stats { id } => {
@@ -3,10 +3,16 @@
 #![allow(dead_code)]
 
 use crate::cli::OutputFormat;
+use crate::commands::patch::patch_update;
 use crate::connection::ConnectionManager;
-use crate::error::Result as CliResult;
+use crate::error::{RedisCtlError, Result as CliResult};
+use crate::output::notice;
+use crate::progress::TaskProgress;
 use anyhow::Context;
+use redis_enterprise::{CrdbTask, CrdbTasksHandler, RestError};
 use serde_json::Value;
+use std::time::Duration;
+use tabled::{Table, Tabled, settings::Style};
 
 use super::utils::*;
 
@@ -73,12 +79,26 @@ pub async fn update_crdb(
     conn_mgr: &ConnectionManager,
     profile_name: Option<&str>,
     id: u32,
-    data: &str,
+    data: Option<&str>,
+    patch: Option<&str>,
     output_format: OutputFormat,
     query: Option<&str>,
 ) -> CliResult<()> {
     let client = conn_mgr.create_enterprise_client(profile_name).await?;
-    let json_data = read_json_data(data)?;
+    let json_data = match patch {
+        Some(patch) => {
+            let current = client
+                .get_raw(&format!("/v1/crdbs/{}", id))
+                .await
+                .context(format!("Failed to get CRDB {}", id))?;
+            patch_update(&current, patch)?
+        }
+        None => Some(read_json_data(data.unwrap_or_default())?),
+    };
+    let Some(json_data) = json_data else {
+        notice!("No changes to apply");
+        return Ok(());
+    };
 
     let response = client
         .put_raw(&format!("/v1/crdbs/{}", id), json_data)
@@ -100,7 +120,7 @@ pub async fn delete_crdb(
     query: Option<&str>,
 ) -> CliResult<()> {
     if !force && !confirm_action(&format!("Delete CRDB {}?", id))? {
-        println!("Operation cancelled");
+        notice!("Operation cancelled");
         return Ok(());
     }
 
@@ -173,7 +193,7 @@ pub async fn remove_cluster_from_crdb(
     query: Option<&str>,
 ) -> CliResult<()> {
     if !force && !confirm_action(&format!("Remove cluster {} from CRDB {}?", cluster_id, id))? {
-        println!("Operation cancelled");
+        notice!("Operation cancelled");
         return Ok(());
     }
 
@@ -281,7 +301,7 @@ pub async fn flush_crdb_instance(
             instance_id, crdb_id
         ))?
     {
-        println!("Operation cancelled");
+        notice!("Operation cancelled");
         return Ok(());
     }
 
@@ -444,6 +464,169 @@ pub async fn get_crdb_task(
     Ok(())
 }
 
+/// List coordinated CRDB tasks
+pub async fn list_crdb_tasks(
+    conn_mgr: &ConnectionManager,
+    profile_name: Option<&str>,
+    output_format: OutputFormat,
+    query: Option<&str>,
+) -> CliResult<()> {
+    let client = conn_mgr.create_enterprise_client(profile_name).await?;
+    let response = client
+        .get_raw("/v1/crdb_tasks")
+        .await
+        .context("Failed to list CRDB tasks")?;
+
+    let data = handle_output(response, output_format, query)?;
+    print_formatted_output(data, output_format)?;
+    Ok(())
+}
+
+/// Get a coordinated CRDB task
+pub async fn get_coordinated_task(
+    conn_mgr: &ConnectionManager,
+    profile_name: Option<&str>,
+    task_id: &str,
+    output_format: OutputFormat,
+    query: Option<&str>,
+) -> CliResult<()> {
+    let client = conn_mgr.create_enterprise_client(profile_name).await?;
+    let response = client
+        .get_raw(&format!("/v1/crdb_tasks/{}", task_id))
+        .await
+        .context(format!("Failed to get CRDB task {}", task_id))?;
+
+    let data = handle_output(response, output_format, query)?;
+    print_formatted_output(data, output_format)?;
+    Ok(())
+}
+
+#[derive(Tabled)]
+struct TaskErrorRow {
+    #[tabled(rename = "CLUSTER")]
+    cluster: String,
+    #[tabled(rename = "ERROR CODE")]
+    error_code: String,
+    #[tabled(rename = "DESCRIPTION")]
+    description: String,
+}
+
+/// Wait for a coordinated CRDB task to finish
+///
+/// A failed task is fetched once more so the final per-cluster errors are reported, and the
+/// command exits with an error.
+pub async fn wait_crdb_task(
+    conn_mgr: &ConnectionManager,
+    profile_name: Option<&str>,
+    task_id: &str,
+    timeout: u64,
+    interval: u64,
+    output_format: OutputFormat,
+    query: Option<&str>,
+) -> CliResult<()> {
+    let client = conn_mgr.create_enterprise_client(profile_name).await?;
+    let handler = CrdbTasksHandler::new(client);
+
+    let progress = TaskProgress::start(task_id);
+    let task = match handler
+        .wait(
+            task_id,
+            Duration::from_secs(interval),
+            Duration::from_secs(timeout),
+        )
+        .await
+    {
+        Ok(task) => task,
+        Err(e) => {
+            if matches!(e, RestError::Timeout(_)) {
+                progress.timeout();
+            } else {
+                progress.finish("error", "error");
+            }
+            return Err(e.into());
+        }
+    };
+    progress.finish(&task.status, &task.status);
+
+    // Errors from slower clusters can arrive after the status flips to failed
+    let task = if task.is_failed() {
+        handler.get(task_id).await.unwrap_or(task)
+    } else {
+        task
+    };
+
+    match output_format {
+        OutputFormat::Auto | OutputFormat::Table if query.is_none() => {
+            println!("Task {}: {}", task.task_id, task.status);
+            if task.is_failed() {
+                print_task_errors(&task);
+            }
+        }
+        _ => {
+            let json = serde_json::to_value(&task).context("Failed to serialize task")?;
+            let data = handle_output(json, output_format, query)?;
+            print_formatted_output(data, output_format)?;
+        }
+    }
+
+    if task.is_failed() {
+        return Err(RedisCtlError::api(format!(
+            "CRDB task {} {}: {}",
+            task_id,
+            task.status,
+            failure_summary(&task)
+        )));
+    }
+    Ok(())
+}
+
+fn print_task_errors(task: &CrdbTask) {
+    if task.errors.is_empty() {
+        println!(
+            "Error: {}",
+            task.error.as_deref().unwrap_or("no error details reported")
+        );
+        return;
+    }
+
+    let rows: Vec<TaskErrorRow> = task
+        .errors
+        .iter()
+        .map(|e| TaskErrorRow {
+            cluster: e.cluster_name.clone().unwrap_or_else(|| "-".to_string()),
+            error_code: e.error_code.clone().unwrap_or_else(|| "-".to_string()),
+            description: e.description.clone().unwrap_or_else(|| "-".to_string()),
+        })
+        .collect();
+    let mut table = Table::new(rows);
+    table.with(Style::modern());
+    println!("{}", table);
+}
+
+/// One-line description of why a task failed, naming the clusters involved
+fn failure_summary(task: &CrdbTask) -> String {
+    let causes: Vec<String> = task
+        .errors
+        .iter()
+        .map(|e| {
+            let cluster = e.cluster_name.as_deref().unwrap_or("unknown cluster");
+            let reason = e
+                .description
+                .as_deref()
+                .or(e.error_code.as_deref())
+                .unwrap_or("no description");
+            format!("{}: {}", cluster, reason)
+        })
+        .collect();
+    if !causes.is_empty() {
+        causes.join("; ")
+    } else {
+        task.error
+            .clone()
+            .unwrap_or_else(|| "no error details reported".to_string())
+    }
+}
+
 /// Retry failed CRDB task
 pub async fn retry_crdb_task(
     conn_mgr: &ConnectionManager,
@@ -2,11 +2,14 @@
 
 #![allow(dead_code)]
 
-use crate::cli::{EnterpriseDatabaseCommands, OutputFormat};
+use crate::cli::{DefaultUserArg, EnterpriseDatabaseCommands, OutputFormat};
+use crate::commands::examples::{ExampleKind, print_example};
 use crate::connection::ConnectionManager;
 use crate::error::Result as CliResult;
 
+use super::database_acl;
 use super::database_impl;
+use super::database_sync;
 
 /// Handle enterprise database commands
 pub async fn handle_database_command(
@@ -17,26 +20,57 @@ pub async fn handle_database_command(
     query: Option<&str>,
 ) -> CliResult<()> {
     match command {
-        EnterpriseDatabaseCommands::List => {
-            database_impl::list_databases(conn_mgr, profile_name, output_format, query).await
+        EnterpriseDatabaseCommands::List { fields, all_fields } => {
+            database_impl::list_databases(
+                conn_mgr,
+                profile_name,
+                fields,
+                *all_fields,
+                output_format,
+                query,
+            )
+            .await
         }
         EnterpriseDatabaseCommands::Get { id } => {
             database_impl::get_database(conn_mgr, profile_name, *id, output_format, query).await
         }
-        EnterpriseDatabaseCommands::Create { data, dry_run } => {
+        EnterpriseDatabaseCommands::Create {
+            data,
+            memory,
+            example,
+            dry_run,
+        } => {
+            let Some(data) = data.as_deref().filter(|_| !*example) else {
+                return print_example(ExampleKind::EnterpriseDatabase, output_format);
+            };
             database_impl::create_database(
                 conn_mgr,
                 profile_name,
                 data,
+                *memory,
                 *dry_run,
                 output_format,
                 query,
             )
             .await
         }
-        EnterpriseDatabaseCommands::Update { id, data } => {
-            database_impl::update_database(conn_mgr, profile_name, *id, data, output_format, query)
-                .await
+        EnterpriseDatabaseCommands::Update {
+            id,
+            data,
+            patch,
+            memory,
+        } => {
+            database_impl::update_database(
+                conn_mgr,
+                profile_name,
+                *id,
+                data.as_deref(),
+                patch.as_deref(),
+                *memory,
+                output_format,
+                query,
+            )
+            .await
         }
         EnterpriseDatabaseCommands::Delete { id, force } => {
             database_impl::delete_database(
@@ -64,9 +98,21 @@ pub async fn handle_database_command(
             database_impl::restore_database(conn_mgr, profile_name, *id, data, output_format, query)
                 .await
         }
-        EnterpriseDatabaseCommands::Flush { id, force } => {
-            database_impl::flush_database(conn_mgr, profile_name, *id, *force, output_format, query)
-                .await
+        EnterpriseDatabaseCommands::Flush {
+            id,
+            force,
+            crdb_aware,
+        } => {
+            database_impl::flush_database(
+                conn_mgr,
+                profile_name,
+                *id,
+                *force,
+                *crdb_aware,
+                output_format,
+                query,
+            )
+            .await
         }
         EnterpriseDatabaseCommands::GetShards { id } => {
             database_impl::get_database_shards(conn_mgr, profile_name, *id, output_format, query)
@@ -98,25 +144,78 @@ pub async fn handle_database_command(
             )
             .await
         }
-        EnterpriseDatabaseCommands::GetAcl { id } => {
-            database_impl::get_database_acl(conn_mgr, profile_name, *id, output_format, query).await
+        EnterpriseDatabaseCommands::Sync(sync_cmd) => {
+            database_sync::handle_sync_command(
+                conn_mgr,
+                profile_name,
+                sync_cmd,
+                output_format,
+                query,
+            )
+            .await
         }
-        EnterpriseDatabaseCommands::UpdateAcl { id, data } => {
-            database_impl::update_database_acl(
+        EnterpriseDatabaseCommands::GetAcl { id } => {
+            database_acl::get_database_acl(conn_mgr, profile_name, *id, output_format, query).await
+        }
+        EnterpriseDatabaseCommands::UpdateAcl {
+            id,
+            default_user,
+            add_roles,
+            remove_roles,
+            data,
+        } => {
+            let changes = database_acl::AclChanges {
+                default_user: default_user.map(|d| d == DefaultUserArg::On),
+                add_roles,
+                remove_roles,
+                data: data.as_deref(),
+            };
+            database_acl::update_database_acl(
                 conn_mgr,
                 profile_name,
                 *id,
-                data,
+                &changes,
                 output_format,
                 query,
             )
             .await
         }
-        EnterpriseDatabaseCommands::Stats { id } => {
-            database_impl::get_database_stats(conn_mgr, profile_name, *id, output_format, query)
-                .await
+        EnterpriseDatabaseCommands::Stats {
+            id,
+            summary,
+            interval,
+            format,
+        } => {
+            database_impl::get_database_stats(
+                conn_mgr,
+                profile_name,
+                *id,
+                *summary,
+                interval.as_deref(),
+                *format,
+                output_format,
+                query,
+            )
+            .await
         }
-        EnterpriseDatabaseCommands::Metrics { id, interval } => {
+        EnterpriseDatabaseCommands::Metrics {
+            id,
+            interval,
+            chart,
+            metrics,
+        } => {
+            if *chart {
+                return database_impl::chart_database_metrics(
+                    conn_mgr,
+                    profile_name,
+                    *id,
+                    interval.as_deref(),
+                    metrics,
+                    output_format,
+                    query,
+                )
+                .await;
+            }
             database_impl::get_database_metrics(
                 conn_mgr,
                 profile_name,
@@ -0,0 +1,409 @@
+//! Access control settings of an Enterprise database
+//!
+//! A database's ACL settings are two fields of the bdb: `default_user`, which decides whether
+//! password-only `AUTH` is accepted, and `roles_permissions`, which binds roles to the Redis ACL
+//! their users get on the database. `get-acl` shows the bindings with role and ACL names, and
+//! `update-acl` edits them by name or uid, keeping the bindings it is not told to change.
+//! `roles_permissions` is only sent when roles are added or removed, and entries in a form this
+//! version does not know are sent back as they were read.
+
+#![allow(dead_code)]
+
+use crate::cli::OutputFormat;
+use crate::connection::ConnectionManager;
+use crate::error::{RedisCtlError, Result as CliResult};
+use crate::output::notice;
+use anyhow::Context;
+use redis_enterprise::bdb::BdbHandler;
+use redis_enterprise::redis_acls::{RedisAcl, RedisAclHandler};
+use redis_enterprise::roles::{RoleInfo, RolesHandler};
+use redis_enterprise::{DatabaseAcl, EnterpriseClient, RolePermission, RolePermissionEntry};
+use serde::Serialize;
+use serde_json::Value;
+use tabled::{Table, Tabled, settings::Style};
+
+use super::utils::*;
+
+/// Changes requested on the command line
+#[derive(Debug, Default)]
+pub struct AclChanges<'a> {
+    pub default_user: Option<bool>,
+    /// `ROLE:ACL` bindings to add or replace
+    pub add_roles: &'a [String],
+    /// Roles whose binding is removed
+    pub remove_roles: &'a [String],
+    /// Raw settings, sent as given instead of the flags
+    pub data: Option<&'a str>,
+}
+
+/// ACL settings with role and ACL names resolved
+#[derive(Debug, Clone, Serialize)]
+pub struct AclView {
+    pub default_user: Option<bool>,
+    pub roles_permissions: Vec<RoleBinding>,
+    /// `roles_permissions` entries that are not plain role to ACL bindings, as read
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub other_permissions: Vec<Value>,
+}
+
+/// A role binding with names, for display
+#[derive(Debug, Clone, Serialize)]
+pub struct RoleBinding {
+    pub role_uid: u32,
+    pub role: Option<String>,
+    pub redis_acl_uid: u32,
+    pub redis_acl: Option<String>,
+    pub rule: Option<String>,
+}
+
+#[derive(Tabled)]
+struct BindingRow {
+    #[tabled(rename = "ROLE")]
+    role: String,
+    #[tabled(rename = "REDIS ACL")]
+    redis_acl: String,
+    #[tabled(rename = "RULE")]
+    rule: String,
+}
+
+/// Show the ACL settings of a database
+pub async fn get_database_acl(
+    conn_mgr: &ConnectionManager,
+    profile_name: Option<&str>,
+    id: u32,
+    output_format: OutputFormat,
+    query: Option<&str>,
+) -> CliResult<()> {
+    let client = conn_mgr.create_enterprise_client(profile_name).await?;
+    let acl = BdbHandler::new(client.clone())
+        .get_acl(id)
+        .await
+        .context(format!("Failed to get ACL for database {}", id))?;
+    let (roles, acls) = if acl.bindings().next().is_none() {
+        (Vec::new(), Vec::new())
+    } else {
+        fetch_names(&client).await?
+    };
+
+    print_acl(&acl_view(&acl, &roles, &acls), output_format, query)
+}
+
+/// Update the ACL settings of a database
+pub async fn update_database_acl(
+    conn_mgr: &ConnectionManager,
+    profile_name: Option<&str>,
+    id: u32,
+    changes: &AclChanges<'_>,
+    output_format: OutputFormat,
+    query: Option<&str>,
+) -> CliResult<()> {
+    let client = conn_mgr.create_enterprise_client(profile_name).await?;
+
+    if let Some(data) = changes.data {
+        let json_data = read_json_data(data)?;
+        let response = client
+            .put_raw(&format!("/v1/bdbs/{}", id), json_data)
+            .await
+            .context(format!("Failed to update ACL for database {}", id))?;
+        let data = handle_output(response, output_format, query)?;
+        return print_formatted_output(data, output_format);
+    }
+
+    let handler = BdbHandler::new(client.clone());
+    let current = handler
+        .get_acl(id)
+        .await
+        .context(format!("Failed to get ACL for database {}", id))?;
+    let (roles, acls) = if changes.add_roles.is_empty() && changes.remove_roles.is_empty() {
+        (Vec::new(), Vec::new())
+    } else {
+        fetch_names(&client).await?
+    };
+    let updated = apply_changes(&current, changes, &roles, &acls)?;
+
+    handler
+        .update_acl(id, &updated)
+        .await
+        .context(format!("Failed to update ACL for database {}", id))?;
+    notice!("Updated ACL of database {}", id);
+
+    let result = DatabaseAcl {
+        default_user: updated.default_user.or(current.default_user),
+        roles_permissions: updated.roles_permissions.or(current.roles_permissions),
+    };
+    let (roles, acls) = if roles.is_empty() && result.bindings().next().is_some() {
+        fetch_names(&client).await?
+    } else {
+        (roles, acls)
+    };
+    print_acl(&acl_view(&result, &roles, &acls), output_format, query)
+}
+
+async fn fetch_names(client: &EnterpriseClient) -> CliResult<(Vec<RoleInfo>, Vec<RedisAcl>)> {
+    let roles = RolesHandler::new(client.clone())
+        .list()
+        .await
+        .context("Failed to list roles")?;
+    let acls = RedisAclHandler::new(client.clone())
+        .list()
+        .await
+        .context("Failed to list Redis ACLs")?;
+    Ok((roles, acls))
+}
+
+/// Apply the requested changes to the current settings
+///
+/// Removals are applied before additions, and an added role replaces its existing binding.
+/// Without role changes `roles_permissions` is `None`, so the update leaves it alone.
+pub fn apply_changes(
+    current: &DatabaseAcl,
+    changes: &AclChanges<'_>,
+    roles: &[RoleInfo],
+    acls: &[RedisAcl],
+) -> CliResult<DatabaseAcl> {
+    let role_names: Vec<(u32, &str)> = roles.iter().map(|r| (r.uid, r.name.as_str())).collect();
+    let acl_names: Vec<(u32, &str)> = acls.iter().map(|a| (a.uid, a.name.as_str())).collect();
+
+    if changes.add_roles.is_empty() && changes.remove_roles.is_empty() {
+        return Ok(DatabaseAcl {
+            default_user: changes.default_user,
+            roles_permissions: None,
+        });
+    }
+
+    let mut roles_permissions = current.roles_permissions.clone().unwrap_or_default();
+    for role in changes.remove_roles {
+        let role_uid = resolve(role, &role_names, "role")?;
+        roles_permissions.retain(|p| p.role_uid() != Some(role_uid));
+    }
+    for binding in changes.add_roles {
+        let (role, acl) = binding
+            .rsplit_once(':')
+            .filter(|(role, acl)| !role.is_empty() && !acl.is_empty())
+            .ok_or_else(|| RedisCtlError::Validation {
+                message: format!("Invalid role binding '{}': expected ROLE:ACL", binding),
+            })?;
+        let role_uid = resolve(role, &role_names, "role")?;
+        let redis_acl_uid = resolve(acl, &acl_names, "Redis ACL")?;
+        roles_permissions.retain(|p| p.role_uid() != Some(role_uid));
+        roles_permissions.push(RolePermissionEntry::Binding(RolePermission {
+            role_uid,
+            redis_acl_uid,
+        }));
+    }
+
+    Ok(DatabaseAcl {
+        default_user: changes.default_user,
+        roles_permissions: Some(roles_permissions),
+    })
+}
+
+/// Find a role or ACL by uid or name
+fn resolve(value: &str, known: &[(u32, &str)], kind: &str) -> CliResult<u32> {
+    let value = value.trim();
+    known
+        .iter()
+        .find(|(uid, name)| value.parse() == Ok(*uid) || *name == value)
+        .map(|(uid, _)| *uid)
+        .ok_or_else(|| RedisCtlError::Validation {
+            message: format!("Unknown {} '{}'", kind, value),
+        })
+}
+
+/// Name the roles and ACLs of the settings
+pub fn acl_view(acl: &DatabaseAcl, roles: &[RoleInfo], acls: &[RedisAcl]) -> AclView {
+    AclView {
+        default_user: acl.default_user,
+        roles_permissions: acl
+            .bindings()
+            .map(|p| {
+                let redis_acl = acls.iter().find(|a| a.uid == p.redis_acl_uid);
+                RoleBinding {
+                    role_uid: p.role_uid,
+                    role: roles
+                        .iter()
+                        .find(|r| r.uid == p.role_uid)
+                        .map(|r| r.name.clone()),
+                    redis_acl_uid: p.redis_acl_uid,
+                    redis_acl: redis_acl.map(|a| a.name.clone()),
+                    rule: redis_acl.map(|a| a.acl.clone()),
+                }
+            })
+            .collect(),
+        other_permissions: acl
+            .roles_permissions
+            .iter()
+            .flatten()
+            .filter_map(|entry| match entry {
+                RolePermissionEntry::Other(entry) => Some(entry.clone()),
+                RolePermissionEntry::Binding(_) => None,
+            })
+            .collect(),
+    }
+}
+
+fn print_acl(view: &AclView, output_format: OutputFormat, query: Option<&str>) -> CliResult<()> {
+    match output_format {
+        OutputFormat::Auto | OutputFormat::Table if query.is_none() => {
+            let default_user = match view.default_user {
+                Some(true) => "enabled",
+                Some(false) => "disabled",
+                None => "not reported",
+            };
+            println!("Default user: {}", default_user);
+            for entry in &view.other_permissions {
+                notice!("Unrecognized role binding, left unchanged: {}", entry);
+            }
+            if view.roles_permissions.is_empty() {
+                notice!("No roles are bound to this database");
+                return Ok(());
+            }
+            let rows: Vec<BindingRow> = view
+                .roles_permissions
+                .iter()
+                .map(|b| BindingRow {
+                    role: b.role.clone().unwrap_or_else(|| b.role_uid.to_string()),
+                    redis_acl: b
+                        .redis_acl
+                        .clone()
+                        .unwrap_or_else(|| b.redis_acl_uid.to_string()),
+                    rule: b.rule.clone().unwrap_or_default(),
+                })
+                .collect();
+            let mut table = Table::new(rows);
+            table.with(Style::blank());
+            println!();
+            println!("{}", table);
+            Ok(())
+        }
+        _ => {
+            let json = serde_json::to_value(view).context("Failed to serialize ACL")?;
+            let data = handle_output(json, output_format, query)?;
+            print_formatted_output(data, output_format)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn roles() -> Vec<RoleInfo> {
+        serde_json::from_value(json!([
+            {"uid": 3, "name": "app-rw"},
+            {"uid": 4, "name": "legacy"},
+            {"uid": 5, "name": "reporting"}
+        ]))
+        .unwrap()
+    }
+
+    fn acls() -> Vec<RedisAcl> {
+        serde_json::from_value(json!([
+            {"uid": 1, "name": "Full Access", "acl": "+@all ~*"},
+            {"uid": 2, "name": "read-only", "acl": "+@read ~*"}
+        ]))
+        .unwrap()
+    }
+
+    fn current() -> DatabaseAcl {
+        DatabaseAcl {
+            default_user: Some(true),
+            roles_permissions: Some(vec![
+                binding(3, 2),
+                binding(4, 1),
+                RolePermissionEntry::Other(
+                    json!({"role_uid": 6, "redis_acl_uid": 1, "scope": "x"}),
+                ),
+            ]),
+        }
+    }
+
+    fn binding(role_uid: u32, redis_acl_uid: u32) -> RolePermissionEntry {
+        RolePermissionEntry::Binding(RolePermission {
+            role_uid,
+            redis_acl_uid,
+        })
+    }
+
+    #[test]
+    fn test_apply_changes() {
+        let add = vec!["app-rw:Full Access".to_string(), "5:2".to_string()];
+        let remove = vec!["legacy".to_string()];
+        let changes = AclChanges {
+            default_user: Some(false),
+            add_roles: &add,
+            remove_roles: &remove,
+            data: None,
+        };
+        let updated = apply_changes(&current(), &changes, &roles(), &acls()).unwrap();
+
+        assert_eq!(updated.default_user, Some(false));
+        assert_eq!(
+            updated.roles_permissions,
+            Some(vec![
+                current().roles_permissions.unwrap()[2].clone(),
+                binding(3, 1),
+                binding(5, 2),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_apply_changes_replaces_unrecognized_entry_of_role() {
+        let add = vec!["6:1".to_string()];
+        let changes = AclChanges {
+            add_roles: &add,
+            ..Default::default()
+        };
+        let roles: Vec<RoleInfo> =
+            serde_json::from_value(json!([{"uid": 6, "name": "ops"}])).unwrap();
+        let updated = apply_changes(&current(), &changes, &roles, &acls()).unwrap();
+        assert_eq!(
+            updated.roles_permissions,
+            Some(vec![binding(3, 2), binding(4, 1), binding(6, 1)])
+        );
+    }
+
+    #[test]
+    fn test_apply_changes_errors() {
+        let check = |binding: &str| {
+            let add = vec![binding.to_string()];
+            let changes = AclChanges {
+                add_roles: &add,
+                ..Default::default()
+            };
+            apply_changes(&current(), &changes, &roles(), &acls())
+                .unwrap_err()
+                .to_string()
+        };
+        assert!(check("app-rw").contains("expected ROLE:ACL"));
+        assert!(check("nobody:read-only").contains("Unknown role 'nobody'"));
+        assert!(check("app-rw:write-only").contains("Unknown Redis ACL 'write-only'"));
+    }
+
+    #[test]
+    fn test_apply_changes_keeps_default_user() {
+        let updated = apply_changes(&current(), &AclChanges::default(), &[], &[]).unwrap();
+        assert_eq!(updated.default_user, None);
+        assert_eq!(updated.roles_permissions, None);
+
+        let changes = AclChanges {
+            default_user: Some(false),
+            ..Default::default()
+        };
+        let updated = apply_changes(&current(), &changes, &[], &[]).unwrap();
+        assert_eq!(updated.default_user, Some(false));
+        assert_eq!(updated.roles_permissions, None);
+    }
+
+    #[test]
+    fn test_acl_view() {
+        let view = acl_view(&current(), &roles(), &acls()[1..]);
+        assert_eq!(view.roles_permissions[0].role.as_deref(), Some("app-rw"));
+        assert_eq!(view.roles_permissions[0].rule.as_deref(), Some("+@read ~*"));
+        assert_eq!(view.roles_permissions[1].redis_acl, None);
+        assert_eq!(view.roles_permissions.len(), 2);
+        assert_eq!(view.other_permissions[0]["scope"], "x");
+    }
+}
@@ -2,26 +2,57 @@
 
 #![allow(dead_code)]
 
-use crate::cli::OutputFormat;
+use crate::cli::{OutputFormat, StatsExportFormat};
+use crate::commands::patch::patch_update;
 use crate::connection::ConnectionManager;
-use crate::error::Result as CliResult;
+use crate::error::{RedisCtlError, Result as CliResult};
+use crate::output::notice;
 use anyhow::Context;
+use redis_enterprise::Database;
+use redis_enterprise::bdb::BdbHandler;
 use serde_json::Value;
+use tabled::{Table, Tabled, settings::Style};
+use tracing::info;
 
+use super::stats_chart;
+use super::stats_export;
+use super::stats_summary;
 use super::utils::*;
+use crate::units::format_bytes;
+
+/// Fields `database list` fetches by default
+const DEFAULT_LIST_FIELDS: &[&str] = &["uid", "name", "status", "port", "memory_size"];
 
 /// List all databases
 pub async fn list_databases(
     conn_mgr: &ConnectionManager,
     profile_name: Option<&str>,
+    fields: &[String],
+    all_fields: bool,
     output_format: OutputFormat,
     query: Option<&str>,
 ) -> CliResult<()> {
     let client = conn_mgr.create_enterprise_client(profile_name).await?;
-    let response = client
-        .get_raw("/v1/bdbs")
-        .await
-        .context("Failed to list databases")?;
+    // A query may read any field, so it gets full objects unless fields are chosen explicitly
+    let projection: Option<Vec<&str>> = if !fields.is_empty() {
+        Some(fields.iter().map(String::as_str).collect())
+    } else if all_fields || query.is_some() {
+        None
+    } else {
+        Some(DEFAULT_LIST_FIELDS.to_vec())
+    };
+    let response = match projection {
+        Some(fields) => Value::Array(
+            BdbHandler::new(client)
+                .list_with_fields(&fields)
+                .await
+                .context("Failed to list databases")?,
+        ),
+        None => client
+            .get_raw("/v1/bdbs")
+            .await
+            .context("Failed to list databases")?,
+    };
 
     let data = handle_output(response, output_format, query)?;
     print_formatted_output(data, output_format)?;
@@ -42,31 +73,161 @@ pub async fn get_database(
         .await
         .context(format!("Failed to get database {}", id))?;
 
+    if matches!(output_format, OutputFormat::Auto | OutputFormat::Table) && query.is_none() {
+        let database: Database = serde_json::from_value(response)
+            .with_context(|| format!("Failed to parse database {}", id))?;
+        print_database(&database);
+        return Ok(());
+    }
+
     let data = handle_output(response, output_format, query)?;
     print_formatted_output(data, output_format)?;
     Ok(())
 }
 
+#[derive(Tabled)]
+struct DetailRow {
+    #[tabled(rename = "FIELD")]
+    field: &'static str,
+    #[tabled(rename = "VALUE")]
+    value: String,
+}
+
+#[derive(Tabled)]
+struct ConnectionRow {
+    #[tabled(rename = "ENDPOINT")]
+    endpoint: String,
+    #[tabled(rename = "ADDRESSES")]
+    addresses: String,
+    #[tabled(rename = "TYPE")]
+    addr_type: String,
+    #[tabled(rename = "PROXY POLICY")]
+    proxy_policy: String,
+}
+
+/// Database details followed by how to connect to it
+fn print_database(database: &Database) {
+    let rows: Vec<DetailRow> = database_details(database)
+        .into_iter()
+        .map(|(field, value)| DetailRow { field, value })
+        .collect();
+    let mut table = Table::new(rows);
+    table.with(Style::blank());
+    println!("{}", table);
+
+    let connections = connection_rows(database);
+    if connections.is_empty() {
+        println!("\nNo endpoints reported");
+        return;
+    }
+    let mut table = Table::new(connections);
+    table.with(Style::blank());
+    println!("\n{}", table);
+}
+
+fn database_details(database: &Database) -> Vec<(&'static str, String)> {
+    let text = |value: &Option<String>| value.clone().unwrap_or_else(|| "-".to_string());
+    let flag = |value: Option<bool>| match value {
+        Some(true) => "yes".to_string(),
+        Some(false) => "no".to_string(),
+        None => "-".to_string(),
+    };
+    let memory = match (database.memory_used, database.memory_size) {
+        (Some(used), Some(size)) => format!("{} / {}", format_bytes(used), format_bytes(size)),
+        (None, Some(size)) => format_bytes(size),
+        _ => "-".to_string(),
+    };
+    let shards = match (database.shards_count, database.shards_placement.as_deref()) {
+        (Some(count), Some(placement)) => format!("{} ({} placement)", count, placement),
+        (Some(count), None) => count.to_string(),
+        _ => "-".to_string(),
+    };
+    let tls = match database.tls_mode.as_deref() {
+        Some(mode) => mode.to_string(),
+        None => flag(database.ssl),
+    };
+
+    vec![
+        ("ID", database.uid.to_string()),
+        ("Name", database.name.clone()),
+        ("Status", text(&database.status)),
+        ("Version", text(&database.version)),
+        ("Memory", memory),
+        ("Shards", shards),
+        ("Replication", flag(database.replication)),
+        ("OSS Cluster", flag(database.oss_cluster)),
+        ("TLS", tls),
+        ("Client Auth", text(&database.enforce_client_authentication)),
+        (
+            "Port",
+            database
+                .connection_port()
+                .map(|port| port.to_string())
+                .unwrap_or_else(|| "-".to_string()),
+        ),
+    ]
+}
+
+fn connection_rows(database: &Database) -> Vec<ConnectionRow> {
+    database
+        .endpoint_list()
+        .iter()
+        .map(|endpoint| {
+            let port = endpoint.port.or(database.port);
+            ConnectionRow {
+                endpoint: match (endpoint.host(), port) {
+                    (Some(host), Some(port)) => format!("{}:{}", host, port),
+                    (Some(host), None) => host.to_string(),
+                    _ => "-".to_string(),
+                },
+                addresses: endpoint
+                    .addr
+                    .as_ref()
+                    .filter(|addr| !addr.is_empty())
+                    .map(|addr| addr.join(", "))
+                    .unwrap_or_else(|| "-".to_string()),
+                addr_type: endpoint
+                    .addr_type
+                    .clone()
+                    .unwrap_or_else(|| "-".to_string()),
+                proxy_policy: endpoint
+                    .proxy_policy
+                    .clone()
+                    .unwrap_or_else(|| "-".to_string()),
+            }
+        })
+        .collect()
+}
+
 /// Create a new database
 pub async fn create_database(
     conn_mgr: &ConnectionManager,
     profile_name: Option<&str>,
     data: &str,
+    memory: Option<u64>,
     dry_run: bool,
     output_format: OutputFormat,
     query: Option<&str>,
 ) -> CliResult<()> {
     let client = conn_mgr.create_enterprise_client(profile_name).await?;
-    let json_data = read_json_data(data)?;
-
-    let path = if dry_run {
-        "/v1/bdbs/dry-run"
-    } else {
-        "/v1/bdbs"
-    };
+    let json_data = with_memory_size(read_json_data(data)?, memory)?;
+
+    if dry_run {
+        let response = BdbHandler::new(client)
+            .create_dry_run(json_data)
+            .await
+            .context("Database validation failed")?;
+
+        let data = handle_output(response, output_format, query)?;
+        if matches!(output_format, OutputFormat::Auto | OutputFormat::Table) {
+            notice!("Validation passed; no database was created. Server response:");
+        }
+        print_formatted_output(data, output_format)?;
+        return Ok(());
+    }
 
     let response = client
-        .post_raw(path, json_data)
+        .post_raw("/v1/bdbs", json_data)
         .await
         .context("Failed to create database")?;
 
@@ -75,17 +236,53 @@ pub async fn create_database(
     Ok(())
 }
 
+/// Set `memory_size` from a `--memory` size in bytes, overriding the payload's value
+fn with_memory_size(mut data: Value, memory: Option<u64>) -> CliResult<Value> {
+    let Some(bytes) = memory else {
+        return Ok(data);
+    };
+    let object = data
+        .as_object_mut()
+        .ok_or_else(|| RedisCtlError::Validation {
+            message: "--memory requires --data to be a JSON object".to_string(),
+        })?;
+    object.insert("memory_size".to_string(), Value::from(bytes));
+    Ok(data)
+}
+
 /// Update database configuration
+#[allow(clippy::too_many_arguments)]
 pub async fn update_database(
     conn_mgr: &ConnectionManager,
     profile_name: Option<&str>,
     id: u32,
-    data: &str,
+    data: Option<&str>,
+    patch: Option<&str>,
+    memory: Option<u64>,
     output_format: OutputFormat,
     query: Option<&str>,
 ) -> CliResult<()> {
     let client = conn_mgr.create_enterprise_client(profile_name).await?;
-    let json_data = read_json_data(data)?;
+    let json_data = match patch {
+        Some(patch) => {
+            let current = client
+                .get_raw(&format!("/v1/bdbs/{}", id))
+                .await
+                .context(format!("Failed to get database {}", id))?;
+            patch_update(&current, patch)?
+        }
+        None => {
+            let json_data = match data {
+                Some(data) => read_json_data(data)?,
+                None => serde_json::json!({}),
+            };
+            Some(with_memory_size(json_data, memory)?)
+        }
+    };
+    let Some(json_data) = json_data else {
+        notice!("No changes to apply");
+        return Ok(());
+    };
 
     let response = client
         .put_raw(&format!("/v1/bdbs/{}", id), json_data)
@@ -107,7 +304,7 @@ pub async fn delete_database(
     query: Option<&str>,
 ) -> CliResult<()> {
     if !force && !confirm_action(&format!("Delete database {}?", id))? {
-        println!("Operation cancelled");
+        notice!("Operation cancelled");
         return Ok(());
     }
 
@@ -208,31 +405,67 @@ pub async fn restore_database(
 }
 
 /// Flush database data
+#[allow(clippy::too_many_arguments)]
 pub async fn flush_database(
     conn_mgr: &ConnectionManager,
     profile_name: Option<&str>,
     id: u32,
     force: bool,
+    crdb_aware: bool,
     output_format: OutputFormat,
     query: Option<&str>,
 ) -> CliResult<()> {
+    let client = conn_mgr.create_enterprise_client(profile_name).await?;
+    let handler = BdbHandler::new(client);
+
+    let database = handler
+        .get(id)
+        .await
+        .context(format!("Failed to get database {}", id))?;
+
+    // Flushing an Active-Active database clears the data on every participating cluster
+    if database.crdt.unwrap_or(false) && !crdb_aware {
+        return Err(RedisCtlError::Validation {
+            message: format!(
+                "Database {} ({}) is an Active-Active (CRDB) database; flushing it clears data on all participating clusters. Re-run with --crdb-aware to proceed",
+                id, database.name
+            ),
+        });
+    }
+
     if !force
-        && !confirm_action(&format!(
-            "Flush all data from database {}? This will delete all data!",
-            id
-        ))?
+        && !confirm_by_typing(
+            &format!(
+                "Flush all data from database {} ({})? This will delete all data!",
+                id, database.name
+            ),
+            &database.name,
+        )?
     {
-        println!("Operation cancelled");
+        notice!("Operation cancelled");
         return Ok(());
     }
 
-    let client = conn_mgr.create_enterprise_client(profile_name).await?;
-    let response = client
-        .put_raw(&format!("/v1/bdbs/{}/flush", id), Value::Null)
+    let response = handler
+        .flush(id)
         .await
         .context(format!("Failed to flush database {}", id))?;
 
-    let data = handle_output(response, output_format, query)?;
+    info!(
+        database_id = id,
+        database_name = %database.name,
+        action_uid = %response.action_uid,
+        "Database flush requested"
+    );
+    notice!(
+        "Flush of database {} ({}) started, action UID: {}",
+        id,
+        database.name,
+        response.action_uid
+    );
+
+    let json = serde_json::to_value(response).context("Failed to serialize flush response")?;
+    let data = handle_output(json, output_format, query)?;
     print_formatted_output(data, output_format)?;
     Ok(())
 }
@@ -319,88 +552,122 @@ pub async fn update_database_modules(
     Ok(())
 }
 
-/// Get database ACL
-pub async fn get_database_acl(
+/// Get database statistics
+#[allow(clippy::too_many_arguments)]
+pub async fn get_database_stats(
     conn_mgr: &ConnectionManager,
     profile_name: Option<&str>,
     id: u32,
+    summary: bool,
+    interval: Option<&str>,
+    export: Option<StatsExportFormat>,
     output_format: OutputFormat,
     query: Option<&str>,
 ) -> CliResult<()> {
     let client = conn_mgr.create_enterprise_client(profile_name).await?;
-    let response = client
-        .get_raw(&format!("/v1/bdbs/{}/acl", id))
+    let response: Value = client
+        .get_with_query(&format!("/v1/bdbs/{}/stats", id), &[("interval", interval)])
         .await
-        .context(format!("Failed to get ACL for database {}", id))?;
-
-    let data = handle_output(response, output_format, query)?;
-    print_formatted_output(data, output_format)?;
-    Ok(())
-}
+        .context(format!("Failed to get statistics for database {}", id))?;
 
-/// Update database ACL
-pub async fn update_database_acl(
-    conn_mgr: &ConnectionManager,
-    profile_name: Option<&str>,
-    id: u32,
-    data: &str,
-    output_format: OutputFormat,
-    query: Option<&str>,
-) -> CliResult<()> {
-    let client = conn_mgr.create_enterprise_client(profile_name).await?;
-    let json_data = read_json_data(data)?;
+    if let Some(format) = export {
+        print!("{}", stats_export::render(&response, format));
+        return Ok(());
+    }
+    if !summary {
+        let data = handle_output(response, output_format, query)?;
+        print_formatted_output(data, output_format)?;
+        return Ok(());
+    }
 
-    let response = client
-        .put_raw(&format!("/v1/bdbs/{}/acl", id), json_data)
+    let database = client
+        .get_raw(&format!("/v1/bdbs/{}", id))
         .await
-        .context(format!("Failed to update ACL for database {}", id))?;
-
-    let data = handle_output(response, output_format, query)?;
-    print_formatted_output(data, output_format)?;
+        .context(format!("Failed to get database {}", id))?;
+    let memory_size = database.get("memory_size").and_then(Value::as_u64);
+    let summary = stats_summary::summarize(&response, memory_size);
+
+    match output_format {
+        OutputFormat::Auto | OutputFormat::Table if query.is_none() => {
+            println!("{}", stats_summary::summary_table(&summary));
+        }
+        _ => {
+            let json = serde_json::to_value(&summary).context("Failed to serialize summary")?;
+            let data = handle_output(json, output_format, query)?;
+            print_formatted_output(data, output_format)?;
+        }
+    }
     Ok(())
 }
 
-/// Get database statistics
-pub async fn get_database_stats(
+/// Get database metrics
+pub async fn get_database_metrics(
     conn_mgr: &ConnectionManager,
     profile_name: Option<&str>,
     id: u32,
+    interval: Option<&str>,
     output_format: OutputFormat,
     query: Option<&str>,
 ) -> CliResult<()> {
     let client = conn_mgr.create_enterprise_client(profile_name).await?;
-    let response = client
-        .get_raw(&format!("/v1/bdbs/{}/stats", id))
+    let response: Value = client
+        .get_with_query(
+            &format!("/v1/bdbs/{}/metrics", id),
+            &[("interval", interval)],
+        )
         .await
-        .context(format!("Failed to get statistics for database {}", id))?;
+        .context(format!("Failed to get metrics for database {}", id))?;
 
     let data = handle_output(response, output_format, query)?;
     print_formatted_output(data, output_format)?;
     Ok(())
 }
 
-/// Get database metrics
-pub async fn get_database_metrics(
+/// Chart database metrics as sparklines over the stats intervals
+pub async fn chart_database_metrics(
     conn_mgr: &ConnectionManager,
     profile_name: Option<&str>,
     id: u32,
     interval: Option<&str>,
+    metrics: &[String],
     output_format: OutputFormat,
     query: Option<&str>,
 ) -> CliResult<()> {
     let client = conn_mgr.create_enterprise_client(profile_name).await?;
-    let mut path = format!("/v1/bdbs/{}/metrics", id);
-    if let Some(interval) = interval {
-        path.push_str(&format!("?interval={}", interval));
-    }
-
-    let response = client
-        .get_raw(&path)
+    let stats = redis_enterprise::StatsHandler::new(client)
+        .database(
+            id,
+            Some(redis_enterprise::StatsQuery {
+                interval: interval.map(str::to_string),
+                stime: None,
+                etime: None,
+                metrics: (!metrics.is_empty()).then(|| metrics.join(",")),
+            }),
+        )
         .await
-        .context(format!("Failed to get metrics for database {}", id))?;
-
-    let data = handle_output(response, output_format, query)?;
-    print_formatted_output(data, output_format)?;
+        .context(format!("Failed to get statistics for database {}", id))?;
+    let charts = stats_chart::charts(&stats, metrics);
+
+    match output_format {
+        OutputFormat::Auto | OutputFormat::Table if query.is_none() => {
+            if charts.is_empty() {
+                notice!("No matching metrics in the returned intervals");
+            } else {
+                println!(
+                    "{} samples, {} to {}",
+                    stats.intervals.len(),
+                    stats.intervals.first().map_or("?", |i| i.time.as_str()),
+                    stats.intervals.last().map_or("?", |i| i.time.as_str())
+                );
+                println!("{}", stats_chart::chart_table(&charts));
+            }
+        }
+        _ => {
+            let json = serde_json::to_value(&charts).context("Failed to serialize charts")?;
+            let data = handle_output(json, output_format, query)?;
+            print_formatted_output(data, output_format)?;
+        }
+    }
     Ok(())
 }
 
@@ -447,3 +714,51 @@ pub async fn get_database_clients(
     print_formatted_output(data, output_format)?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_database_details() {
+        let database: Database = serde_json::from_value(json!({
+            "uid": 3,
+            "name": "sessions",
+            "port": 12000,
+            "status": "active",
+            "memory_size": 1073741824u64,
+            "shards_count": 2,
+            "shards_placement": "dense",
+            "replication": true,
+            "oss_cluster": false,
+            "tls_mode": "enabled",
+            "endpoints": [{
+                "uid": "3:1",
+                "dns_name": "redis-12000.cluster.local",
+                "addr": ["10.0.0.1", "10.0.0.2"],
+                "addr_type": "external",
+                "proxy_policy": "all-master-shards"
+            }]
+        }))
+        .unwrap();
+
+        let details = database_details(&database);
+        let value = |field: &str| {
+            details
+                .iter()
+                .find(|(name, _)| *name == field)
+                .map(|(_, value)| value.clone())
+                .unwrap()
+        };
+        assert_eq!(value("Shards"), "2 (dense placement)");
+        assert_eq!(value("TLS"), "enabled");
+        assert_eq!(value("OSS Cluster"), "no");
+        assert_eq!(value("Port"), "12000");
+
+        let rows = connection_rows(&database);
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].endpoint, "redis-12000.cluster.local:12000");
+        assert_eq!(rows[0].addresses, "10.0.0.1, 10.0.0.2");
+    }
+}
@@ -0,0 +1,257 @@
+//! Replica Of and Active-Active sync control for Enterprise databases
+//!
+//! A database that syncs from Replica Of sources keeps the sync's state in `replica_sync` (older
+//! clusters use `sync`), and an Active-Active instance keeps it in `crdt_sync`. Pausing sets the
+//! field to `paused`, which stops the syncer without dropping the sources, so the sync can be
+//! resumed once a maintenance window is over.
+
+#![allow(dead_code)]
+
+use crate::cli::{EnterpriseDatabaseSyncCommands, OutputFormat, SyncKindArg};
+use crate::connection::ConnectionManager;
+use crate::error::{RedisCtlError, Result as CliResult};
+use crate::output::notice;
+use anyhow::Context;
+use redis_enterprise::bdb::{DatabaseHandler, DatabaseInfo};
+use redis_enterprise::{SyncKind, SyncState};
+use serde::Serialize;
+
+use super::utils::*;
+
+pub async fn handle_sync_command(
+    conn_mgr: &ConnectionManager,
+    profile_name: Option<&str>,
+    command: &EnterpriseDatabaseSyncCommands,
+    output_format: OutputFormat,
+    query: Option<&str>,
+) -> CliResult<()> {
+    match command {
+        EnterpriseDatabaseSyncCommands::Status { id } => {
+            sync_status(conn_mgr, profile_name, *id, output_format, query).await
+        }
+        EnterpriseDatabaseSyncCommands::Pause { id, kind, force } => {
+            set_sync(
+                conn_mgr,
+                profile_name,
+                *id,
+                *kind,
+                SyncState::Paused,
+                *force,
+                output_format,
+                query,
+            )
+            .await
+        }
+        EnterpriseDatabaseSyncCommands::Resume { id, kind, force } => {
+            set_sync(
+                conn_mgr,
+                profile_name,
+                *id,
+                *kind,
+                SyncState::Enabled,
+                *force,
+                output_format,
+                query,
+            )
+            .await
+        }
+    }
+}
+
+/// A database's sync, for display
+#[derive(Debug, Clone, Serialize)]
+pub struct SyncStatus {
+    pub uid: u32,
+    pub name: String,
+    pub kind: Option<SyncKind>,
+    pub state: Option<SyncState>,
+    /// Number of Replica Of sources or Active-Active peers
+    pub sources: usize,
+}
+
+impl SyncStatus {
+    pub fn of(db: &DatabaseInfo, kind: Option<SyncKind>) -> Self {
+        let kind = kind.or_else(|| db.sync_kind());
+        let sources = match kind {
+            Some(SyncKind::Replica) => db
+                .replica_sources
+                .as_ref()
+                .or(db.sync_sources.as_ref())
+                .map_or(0, Vec::len),
+            Some(SyncKind::Crdt) => db.crdt_sources.as_ref().map_or(0, Vec::len),
+            None => 0,
+        };
+        SyncStatus {
+            uid: db.uid,
+            name: db.name.clone(),
+            kind,
+            state: kind.and_then(|kind| db.sync_state(kind)),
+            sources,
+        }
+    }
+}
+
+async fn sync_status(
+    conn_mgr: &ConnectionManager,
+    profile_name: Option<&str>,
+    id: u32,
+    output_format: OutputFormat,
+    query: Option<&str>,
+) -> CliResult<()> {
+    let client = conn_mgr.create_enterprise_client(profile_name).await?;
+    let db = DatabaseHandler::new(client)
+        .get(id)
+        .await
+        .context(format!("Failed to get database {}", id))?;
+    print_status(&SyncStatus::of(&db, None), output_format, query)
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn set_sync(
+    conn_mgr: &ConnectionManager,
+    profile_name: Option<&str>,
+    id: u32,
+    kind: Option<SyncKindArg>,
+    state: SyncState,
+    force: bool,
+    output_format: OutputFormat,
+    query: Option<&str>,
+) -> CliResult<()> {
+    let client = conn_mgr.create_enterprise_client(profile_name).await?;
+    let handler = DatabaseHandler::new(client);
+    let db = handler
+        .get(id)
+        .await
+        .context(format!("Failed to get database {}", id))?;
+    let kind = resolve_kind(&db, kind)?;
+
+    let verb = if state == SyncState::Paused {
+        "Pause"
+    } else {
+        "Resume"
+    };
+    if db.sync_state(kind) == Some(state) {
+        notice!(
+            "{} sync of database {} is already {}",
+            kind_label(kind),
+            id,
+            state_label(Some(state))
+        );
+        return print_status(&SyncStatus::of(&db, Some(kind)), output_format, query);
+    }
+    if !force
+        && !confirm_action(&format!(
+            "{} {} sync of database {} ({})?",
+            verb,
+            kind_label(kind),
+            id,
+            db.name
+        ))?
+    {
+        notice!("Operation cancelled");
+        return Ok(());
+    }
+
+    let updated = handler
+        .set_sync(id, kind, state)
+        .await
+        .context(format!("Failed to {} sync", verb.to_lowercase()))?;
+    print_status(&SyncStatus::of(&updated, Some(kind)), output_format, query)
+}
+
+/// The sync to change: the one asked for, or the one the database runs
+fn resolve_kind(db: &DatabaseInfo, requested: Option<SyncKindArg>) -> CliResult<SyncKind> {
+    match requested {
+        Some(SyncKindArg::Replica) => Ok(SyncKind::Replica),
+        Some(SyncKindArg::Crdt) => Ok(SyncKind::Crdt),
+        None => db.sync_kind().ok_or_else(|| RedisCtlError::Validation {
+            message: format!(
+                "Database {} has no Replica Of sources and is not Active-Active; pass --kind to \
+                 choose the sync",
+                db.uid
+            ),
+        }),
+    }
+}
+
+fn kind_label(kind: SyncKind) -> &'static str {
+    match kind {
+        SyncKind::Replica => "Replica Of",
+        SyncKind::Crdt => "Active-Active",
+    }
+}
+
+fn state_label(state: Option<SyncState>) -> &'static str {
+    match state {
+        Some(SyncState::Enabled) => "enabled",
+        Some(SyncState::Disabled) => "disabled",
+        Some(SyncState::Paused) => "paused",
+        Some(SyncState::Stopped) => "stopped",
+        Some(SyncState::Unknown) => "unknown",
+        None => "-",
+    }
+}
+
+fn print_status(
+    status: &SyncStatus,
+    output_format: OutputFormat,
+    query: Option<&str>,
+) -> CliResult<()> {
+    match output_format {
+        OutputFormat::Auto | OutputFormat::Table if query.is_none() => {
+            println!("Database: {} ({})", status.uid, status.name);
+            match status.kind {
+                Some(kind) => {
+                    println!("Sync:     {}", kind_label(kind));
+                    println!("State:    {}", state_label(status.state));
+                    println!("Sources:  {}", status.sources);
+                }
+                None => println!("Sync:     none"),
+            }
+            Ok(())
+        }
+        _ => {
+            let json = serde_json::to_value(status).context("Failed to serialize sync status")?;
+            let data = handle_output(json, output_format, query)?;
+            print_formatted_output(data, output_format)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn db(value: serde_json::Value) -> DatabaseInfo {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn test_status_detects_kind_and_counts_sources() {
+        let crdb = db(json!({
+            "uid": 5, "name": "sessions", "crdt": true, "crdt_sync": "paused",
+            "crdt_sources": [{"uri": "a"}, {"uri": "b"}]
+        }));
+        let status = SyncStatus::of(&crdb, None);
+        assert_eq!(status.kind, Some(SyncKind::Crdt));
+        assert_eq!(status.state, Some(SyncState::Paused));
+        assert_eq!(status.sources, 2);
+
+        let plain = db(json!({"uid": 1, "name": "cache"}));
+        assert_eq!(SyncStatus::of(&plain, None).kind, None);
+    }
+
+    #[test]
+    fn test_resolve_kind() {
+        let plain = db(json!({"uid": 1, "name": "cache"}));
+        assert!(resolve_kind(&plain, None).is_err());
+        assert_eq!(
+            resolve_kind(&plain, Some(SyncKindArg::Replica)).unwrap(),
+            SyncKind::Replica
+        );
+
+        let replica = db(json!({"uid": 2, "name": "copy", "replica_sources": [{"uri": "x"}]}));
+        assert_eq!(resolve_kind(&replica, None).unwrap(), SyncKind::Replica);
+    }
+}
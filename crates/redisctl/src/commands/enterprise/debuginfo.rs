@@ -0,0 +1,462 @@
+//! Debuginfo packages and their upload to Redis support
+//!
+//! `cluster debug-info` streams the cluster's debuginfo package to a local file and records its
+//! size and SHA-256. With `--upload --ticket`, the package is then sent to the S3 presigned URL
+//! support handed out for the ticket, as a single PUT with `Content-MD5`, so S3 rejects a
+//! corrupted body. The returned ETag is compared with the package's MD5. A presigned PUT cannot
+//! be resumed, so a failed attempt is retried from the start.
+//!
+//! Other upload endpoints are refused: the support portal's upload API is not published, and a
+//! protocol guessed for it could report success for a package that never arrived.
+
+#![allow(dead_code)]
+
+use crate::cli::OutputFormat;
+use crate::connection::ConnectionManager;
+use crate::error::{RedisCtlError, Result as CliResult};
+use crate::output::notice;
+use anyhow::Context;
+use base64::Engine;
+use redis_enterprise::debuginfo::DebugInfoHandler;
+use reqwest::StatusCode;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+use super::utils::*;
+
+/// Longest wait between two upload attempts
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+const UPLOAD_TIMEOUT: Duration = Duration::from_secs(600);
+
+/// Options of `cluster debug-info`
+#[derive(Debug, Clone, Default)]
+pub struct DebugInfoOptions {
+    /// Only collect for this database
+    pub database: Option<u32>,
+    /// Where to save the package
+    pub file: Option<PathBuf>,
+    /// Upload this existing package instead of collecting a new one
+    pub from_file: Option<PathBuf>,
+    pub upload: bool,
+    pub ticket: Option<String>,
+    pub upload_url: Option<String>,
+    /// Upload attempts before giving up
+    pub attempts: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DebugInfoResult {
+    pub file: String,
+    pub size_bytes: u64,
+    pub sha256: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub upload: Option<UploadResult>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct UploadResult {
+    pub ticket: String,
+    /// Upload URL without its query string, which holds the presigned credentials
+    pub destination: String,
+    /// Whether the ETag confirmed the checksum; S3 returns no MD5 ETag for KMS-encrypted objects
+    pub checksum_confirmed: bool,
+    pub attempts: u32,
+}
+
+/// Size and digests of a package
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PackageDigest {
+    pub size: u64,
+    pub sha256: String,
+    pub md5: [u8; 16],
+}
+
+pub async fn debug_info(
+    conn_mgr: &ConnectionManager,
+    profile_name: Option<&str>,
+    options: &DebugInfoOptions,
+    output_format: OutputFormat,
+    query: Option<&str>,
+) -> CliResult<()> {
+    // Check the upload settings before spending minutes on collection
+    let upload_url = if options.upload {
+        let url = options
+            .upload_url
+            .as_deref()
+            .ok_or_else(|| RedisCtlError::Validation {
+                message: "--upload needs an endpoint; pass --upload-url or set \
+                          REDISCTL_SUPPORT_UPLOAD_URL"
+                    .to_string(),
+            })?;
+        check_upload_url(url)?;
+        Some(url)
+    } else {
+        None
+    };
+
+    let file = match &options.from_file {
+        Some(path) => path.clone(),
+        None => {
+            let path = options
+                .file
+                .clone()
+                .unwrap_or_else(|| default_file_name(options.ticket.as_deref()));
+            collect(conn_mgr, profile_name, options.database, &path).await?;
+            path
+        }
+    };
+    let digest = digest_file(&file).await?;
+
+    let upload = match upload_url {
+        Some(url) => {
+            let ticket = options.ticket.clone().unwrap_or_default();
+            Some(upload_package(&file, &digest, url, &ticket, options.attempts.max(1)).await?)
+        }
+        None => None,
+    };
+
+    let result = DebugInfoResult {
+        file: file.display().to_string(),
+        size_bytes: digest.size,
+        sha256: digest.sha256,
+        upload,
+    };
+    match output_format {
+        OutputFormat::Auto | OutputFormat::Table if query.is_none() => {
+            println!(
+                "Debuginfo package: {} ({})",
+                result.file,
+                crate::units::format_bytes(result.size_bytes)
+            );
+            println!("SHA-256: {}", result.sha256);
+            if let Some(upload) = &result.upload {
+                println!(
+                    "Uploaded for ticket {} to {} ({}){}",
+                    upload.ticket,
+                    upload.destination,
+                    if upload.checksum_confirmed {
+                        "checksum confirmed"
+                    } else {
+                        "checksum not reported by S3"
+                    },
+                    match upload.attempts {
+                        1 => String::new(),
+                        n => format!(" after {} attempts", n),
+                    }
+                );
+            }
+            Ok(())
+        }
+        _ => {
+            let json = serde_json::to_value(&result).context("Failed to serialize result")?;
+            let data = handle_output(json, output_format, query)?;
+            print_formatted_output(data, output_format)
+        }
+    }
+}
+
+/// `debuginfo-<ticket>-<UTC time>.tar.gz`, or without the ticket when there is none
+pub fn default_file_name(ticket: Option<&str>) -> PathBuf {
+    let time = chrono::Utc::now().format("%Y%m%dT%H%M%SZ");
+    PathBuf::from(match ticket {
+        Some(ticket) => format!("debuginfo-{}-{}.tar.gz", ticket, time),
+        None => format!("debuginfo-{}.tar.gz", time),
+    })
+}
+
+/// Stream the package into `path`, removing the partial file if the download fails
+async fn collect(
+    conn_mgr: &ConnectionManager,
+    profile_name: Option<&str>,
+    database: Option<u32>,
+    path: &Path,
+) -> CliResult<u64> {
+    let client = conn_mgr.create_enterprise_client(profile_name).await?;
+    let file_error = |e: std::io::Error| RedisCtlError::FileError {
+        path: path.display().to_string(),
+        message: e.to_string(),
+    };
+    let file = tokio::fs::File::create(path).await.map_err(file_error)?;
+    let mut writer = tokio::io::BufWriter::new(file);
+    notice!(
+        "Collecting debuginfo into {}; this can take several minutes",
+        path.display()
+    );
+    let size = match DebugInfoHandler::new(client)
+        .download_all_to(database, &mut writer)
+        .await
+    {
+        Ok(size) => size,
+        Err(e) => {
+            drop(writer);
+            let _ = tokio::fs::remove_file(path).await;
+            return Err(e.into());
+        }
+    };
+    writer.flush().await.map_err(file_error)?;
+    writer.into_inner().sync_all().await.map_err(file_error)?;
+    Ok(size)
+}
+
+pub async fn digest_file(path: &Path) -> CliResult<PackageDigest> {
+    let file_error = |e: std::io::Error| RedisCtlError::FileError {
+        path: path.display().to_string(),
+        message: e.to_string(),
+    };
+    let mut file = tokio::fs::File::open(path).await.map_err(file_error)?;
+    let mut sha256 = Sha256::new();
+    let mut md5 = md5::Context::new();
+    let mut size = 0u64;
+    let mut buffer = vec![0u8; 1024 * 1024];
+    loop {
+        let read = file.read(&mut buffer).await.map_err(file_error)?;
+        if read == 0 {
+            break;
+        }
+        sha256.update(&buffer[..read]);
+        md5.consume(&buffer[..read]);
+        size += read as u64;
+    }
+    Ok(PackageDigest {
+        size,
+        sha256: hex(&sha256.finalize()),
+        md5: md5.finalize().0,
+    })
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+/// S3 presigned URLs carry their signature in the query string
+pub fn is_presigned(url: &reqwest::Url) -> bool {
+    url.query_pairs()
+        .any(|(key, _)| key.eq_ignore_ascii_case("X-Amz-Signature") || key == "Signature")
+}
+
+/// Accept only S3 presigned URLs, the one upload whose protocol is documented
+fn check_upload_url(url: &str) -> CliResult<reqwest::Url> {
+    let parsed = reqwest::Url::parse(url).map_err(|e| RedisCtlError::Validation {
+        message: format!("Invalid upload URL: {}", e),
+    })?;
+    if !is_presigned(&parsed) {
+        return Err(RedisCtlError::Validation {
+            message: format!(
+                "{} is not an S3 presigned URL; ask support for a presigned upload URL, or \
+                 upload the package through the support portal in a browser",
+                redact_url(url)
+            ),
+        });
+    }
+    Ok(parsed)
+}
+
+/// The URL without its query string and fragment, safe to print
+pub fn redact_url(url: &str) -> String {
+    url.split(['?', '#']).next().unwrap_or_default().to_string()
+}
+
+/// Whether a failed attempt is worth repeating: network errors, timeouts, 408, 429 and 5xx
+fn is_retryable(status: Option<StatusCode>) -> bool {
+    match status {
+        None => true,
+        Some(status) => {
+            status.is_server_error()
+                || status == StatusCode::REQUEST_TIMEOUT
+                || status == StatusCode::TOO_MANY_REQUESTS
+        }
+    }
+}
+
+/// 1s, 2s, 4s... up to [`MAX_BACKOFF`]
+pub fn backoff(attempt: u32) -> Duration {
+    Duration::from_secs(1u64 << attempt.saturating_sub(1).min(5)).min(MAX_BACKOFF)
+}
+
+/// An attempt that failed, with the status when the server answered
+struct AttemptError {
+    status: Option<StatusCode>,
+    message: String,
+}
+
+impl AttemptError {
+    fn from_reqwest(e: reqwest::Error) -> Self {
+        AttemptError {
+            status: e.status(),
+            message: e.to_string(),
+        }
+    }
+
+    async fn from_response(response: reqwest::Response) -> Self {
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        AttemptError {
+            status: Some(status),
+            message: format!("HTTP {}: {}", status.as_u16(), body.trim()),
+        }
+    }
+}
+
+async fn upload_package(
+    path: &Path,
+    digest: &PackageDigest,
+    url: &str,
+    ticket: &str,
+    attempts: u32,
+) -> CliResult<UploadResult> {
+    let http = reqwest::Client::builder()
+        .timeout(UPLOAD_TIMEOUT)
+        .build()
+        .context("Failed to create HTTP client")?;
+
+    notice!(
+        "Uploading {} ({} bytes) for ticket {} to {}",
+        path.display(),
+        digest.size,
+        ticket,
+        redact_url(url)
+    );
+    let mut attempt = 1;
+    loop {
+        match put_presigned(&http, url, path, digest).await {
+            Ok(checksum_confirmed) => {
+                return Ok(UploadResult {
+                    ticket: ticket.to_string(),
+                    destination: redact_url(url),
+                    checksum_confirmed,
+                    attempts: attempt,
+                });
+            }
+            Err(e) if attempt < attempts && is_retryable(e.status) => {
+                let wait = backoff(attempt);
+                notice!(
+                    "Upload attempt {} failed ({}); retrying in {}s",
+                    attempt,
+                    e.message,
+                    wait.as_secs()
+                );
+                tokio::time::sleep(wait).await;
+                attempt += 1;
+            }
+            Err(e) => {
+                return Err(RedisCtlError::Network {
+                    message: format!(
+                        "Upload of {} failed after {} attempt(s): {}; the package is kept, \
+                         retry with --from-file {}",
+                        path.display(),
+                        attempt,
+                        e.message,
+                        path.display()
+                    ),
+                    source: None,
+                });
+            }
+        }
+    }
+}
+
+/// One PUT of the whole package; returns whether the ETag confirmed the MD5
+async fn put_presigned(
+    http: &reqwest::Client,
+    url: &str,
+    path: &Path,
+    digest: &PackageDigest,
+) -> Result<bool, AttemptError> {
+    let file = tokio::fs::File::open(path)
+        .await
+        .map_err(|e| AttemptError {
+            status: None,
+            message: e.to_string(),
+        })?;
+    let body = reqwest::Body::wrap_stream(tokio_util::io::ReaderStream::new(file));
+    let response = http
+        .put(url)
+        .header(reqwest::header::CONTENT_LENGTH, digest.size)
+        .header(
+            "Content-MD5",
+            base64::engine::general_purpose::STANDARD.encode(digest.md5),
+        )
+        .body(body)
+        .send()
+        .await
+        .map_err(AttemptError::from_reqwest)?;
+    if !response.status().is_success() {
+        return Err(AttemptError::from_response(response).await);
+    }
+    let etag = response
+        .headers()
+        .get(reqwest::header::ETAG)
+        .and_then(|value| value.to_str().ok())
+        .map(|value| value.trim_matches('"').to_ascii_lowercase());
+    match etag {
+        // Uploads encrypted with KMS have ETags that are not the MD5; only plain MD5s count
+        Some(etag) if etag.len() == 32 => {
+            if etag == hex(&digest.md5) {
+                Ok(true)
+            } else {
+                Err(AttemptError {
+                    status: None,
+                    message: format!(
+                        "the stored object's ETag {} does not match the package MD5 {}",
+                        etag,
+                        hex(&digest.md5)
+                    ),
+                })
+            }
+        }
+        _ => Ok(false),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_only_presigned_urls_are_accepted() {
+        let s3 = "https://bucket.s3.amazonaws.com/1234/pkg.tar.gz?X-Amz-Algorithm=AWS4-HMAC-SHA256\
+                  &X-Amz-Credential=AK%2F20260101&X-Amz-Signature=abc";
+        assert!(check_upload_url(s3).is_ok());
+        let error = check_upload_url("https://support.example.com/upload/1234?token=x")
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("not an S3 presigned URL"), "{}", error);
+        assert!(!error.contains("token"), "{}", error);
+    }
+
+    #[test]
+    fn test_redact_url() {
+        assert_eq!(
+            redact_url("https://bucket.s3.amazonaws.com/pkg.tar.gz?X-Amz-Signature=abc"),
+            "https://bucket.s3.amazonaws.com/pkg.tar.gz"
+        );
+    }
+
+    #[test]
+    fn test_backoff() {
+        assert_eq!(backoff(1), Duration::from_secs(1));
+        assert_eq!(backoff(3), Duration::from_secs(4));
+        assert_eq!(backoff(20), MAX_BACKOFF);
+        assert!(is_retryable(None));
+        assert!(is_retryable(Some(StatusCode::SERVICE_UNAVAILABLE)));
+        assert!(!is_retryable(Some(StatusCode::FORBIDDEN)));
+    }
+
+    #[tokio::test]
+    async fn test_digest_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("pkg.tar.gz");
+        std::fs::write(&path, b"hello").unwrap();
+        let digest = digest_file(&path).await.unwrap();
+        assert_eq!(digest.size, 5);
+        assert_eq!(
+            digest.sha256,
+            "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824"
+        );
+        assert_eq!(hex(&digest.md5), "5d41402abc4b2a76b9719d911017c592");
+    }
+}
@@ -0,0 +1,431 @@
+//! Database endpoint listing and reachability probing for Enterprise
+//!
+//! Endpoints come from the `endpoints` field of each database. With `--probe`, every endpoint
+//! address gets a TCP connect from this machine and, when the database has TLS enabled, a TLS
+//! handshake, so a broken network path to a database shows up without a Redis client.
+
+#![allow(dead_code)]
+
+use super::utils::*;
+use crate::cli::{EnterpriseEndpointCommands, OutputFormat};
+use crate::connection::ConnectionManager;
+use crate::error::{RedisCtlError, Result as CliResult};
+use crate::output::notice;
+use anyhow::Context;
+use redis_enterprise::{BdbHandler, Database};
+use serde::Serialize;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tabled::{Table, Tabled, settings::Style};
+use tokio::net::TcpStream;
+use tokio_rustls::TlsConnector;
+use tokio_rustls::rustls::client::danger::{
+    HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier,
+};
+use tokio_rustls::rustls::crypto::{self, CryptoProvider};
+use tokio_rustls::rustls::pki_types::{CertificateDer, ServerName, UnixTime};
+use tokio_rustls::rustls::{ClientConfig, DigitallySignedStruct, SignatureScheme};
+
+pub async fn handle_endpoint_command(
+    conn_mgr: &ConnectionManager,
+    profile_name: Option<&str>,
+    command: &EnterpriseEndpointCommands,
+    output_format: OutputFormat,
+    query: Option<&str>,
+) -> CliResult<()> {
+    match command {
+        EnterpriseEndpointCommands::List { db, probe, timeout } => {
+            list_endpoints(
+                conn_mgr,
+                profile_name,
+                *db,
+                probe.then_some(*timeout),
+                output_format,
+                query,
+            )
+            .await
+        }
+    }
+}
+
+/// One address of a database endpoint
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct EndpointAddress {
+    pub database_uid: u32,
+    pub database_name: String,
+    pub endpoint_uid: Option<String>,
+    pub dns_name: Option<String>,
+    /// IP address, or the DNS name when the endpoint lists no addresses
+    pub address: String,
+    pub port: u16,
+    /// `internal` or `external`, as reported by the cluster
+    pub addr_type: Option<String>,
+    /// Whether clients must connect with TLS
+    pub tls: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub probe: Option<ProbeResult>,
+}
+
+/// Outcome of connecting to one endpoint address
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct ProbeResult {
+    pub reachable: bool,
+    /// Time to establish the TCP connection
+    pub tcp_ms: Option<f64>,
+    /// Time for the TLS handshake, for databases with TLS enabled
+    pub tls_ms: Option<f64>,
+    pub error: Option<String>,
+}
+
+#[derive(Tabled)]
+struct EndpointRow {
+    #[tabled(rename = "DB")]
+    database: String,
+    #[tabled(rename = "NAME")]
+    name: String,
+    #[tabled(rename = "ENDPOINT")]
+    endpoint: String,
+    #[tabled(rename = "ADDRESS")]
+    address: String,
+    #[tabled(rename = "TYPE")]
+    addr_type: String,
+    #[tabled(rename = "TLS")]
+    tls: String,
+}
+
+#[derive(Tabled)]
+struct ProbeRow {
+    #[tabled(rename = "DB")]
+    database: String,
+    #[tabled(rename = "ADDRESS")]
+    address: String,
+    #[tabled(rename = "TLS")]
+    tls: String,
+    #[tabled(rename = "STATUS")]
+    status: String,
+    #[tabled(rename = "TCP")]
+    tcp: String,
+    #[tabled(rename = "HANDSHAKE")]
+    handshake: String,
+    #[tabled(rename = "ERROR")]
+    error: String,
+}
+
+async fn list_endpoints(
+    conn_mgr: &ConnectionManager,
+    profile_name: Option<&str>,
+    database: Option<u32>,
+    probe_timeout: Option<Duration>,
+    output_format: OutputFormat,
+    query: Option<&str>,
+) -> CliResult<()> {
+    let client = conn_mgr.create_enterprise_client(profile_name).await?;
+    let handler = BdbHandler::new(client);
+    let databases = match database {
+        Some(uid) => vec![
+            handler
+                .info(uid)
+                .await
+                .with_context(|| format!("Failed to get database {}", uid))?,
+        ],
+        None => handler.list().await.context("Failed to list databases")?,
+    };
+
+    let mut addresses = endpoint_addresses(&databases);
+    if addresses.is_empty() {
+        notice!("No endpoints found");
+        return Ok(());
+    }
+
+    if let Some(timeout) = probe_timeout {
+        let mut probes = tokio::task::JoinSet::new();
+        for (index, address) in addresses.iter().enumerate() {
+            let (host, port, tls) = (address.address.clone(), address.port, address.tls);
+            probes.spawn(async move { (index, probe(&host, port, tls, timeout).await) });
+        }
+        while let Some(joined) = probes.join_next().await {
+            let (index, result) = joined.context("Endpoint probe panicked")?;
+            addresses[index].probe = Some(result);
+        }
+    }
+
+    match output_format {
+        OutputFormat::Auto | OutputFormat::Table if query.is_none() => {
+            let table = if probe_timeout.is_some() {
+                Table::new(addresses.iter().map(probe_row))
+                    .with(Style::blank())
+                    .to_string()
+            } else {
+                Table::new(addresses.iter().map(endpoint_row))
+                    .with(Style::blank())
+                    .to_string()
+            };
+            println!("{}", table);
+        }
+        _ => {
+            let json = serde_json::to_value(&addresses).context("Failed to serialize endpoints")?;
+            let data = handle_output(json, output_format, query)?;
+            print_formatted_output(data, output_format)?;
+        }
+    }
+
+    let unreachable = addresses
+        .iter()
+        .filter(|a| a.probe.as_ref().is_some_and(|p| !p.reachable))
+        .count();
+    if unreachable > 0 {
+        return Err(RedisCtlError::network(format!(
+            "{} of {} endpoint addresses unreachable",
+            unreachable,
+            addresses.len()
+        )));
+    }
+    Ok(())
+}
+
+/// One entry per endpoint address, ordered by database
+pub fn endpoint_addresses(databases: &[Database]) -> Vec<EndpointAddress> {
+    let mut addresses = Vec::new();
+    let mut databases: Vec<&Database> = databases.iter().collect();
+    databases.sort_by_key(|db| db.uid);
+
+    for db in databases {
+        let tls = db.tls_enabled();
+        for endpoint in db.endpoint_list() {
+            let Some(port) = endpoint.port.or(db.port) else {
+                continue;
+            };
+            let mut hosts: Vec<String> = endpoint.addr.clone().unwrap_or_default();
+            if hosts.is_empty() {
+                hosts.extend(endpoint.dns_name.clone());
+            }
+            for host in hosts {
+                addresses.push(EndpointAddress {
+                    database_uid: db.uid,
+                    database_name: db.name.clone(),
+                    endpoint_uid: endpoint.uid.clone(),
+                    dns_name: endpoint.dns_name.clone(),
+                    address: host,
+                    port,
+                    addr_type: endpoint.addr_type.clone(),
+                    tls,
+                    probe: None,
+                });
+            }
+        }
+    }
+    addresses
+}
+
+/// TCP connect and, when `tls` is set, a TLS handshake, each bounded by `timeout`
+pub async fn probe(host: &str, port: u16, tls: bool, timeout: Duration) -> ProbeResult {
+    let failed = |error: String, tcp_ms: Option<f64>| ProbeResult {
+        reachable: false,
+        tcp_ms,
+        tls_ms: None,
+        error: Some(error),
+    };
+    let timed_out = || format!("timed out after {}", format_ms(timeout));
+
+    let start = Instant::now();
+    let stream = match tokio::time::timeout(timeout, TcpStream::connect((host, port))).await {
+        Ok(Ok(stream)) => stream,
+        Ok(Err(e)) => return failed(format!("connect: {}", e), None),
+        Err(_) => return failed(format!("connect {}", timed_out()), None),
+    };
+    let tcp_ms = Some(millis(start.elapsed()));
+    if !tls {
+        return ProbeResult {
+            reachable: true,
+            tcp_ms,
+            ..Default::default()
+        };
+    }
+
+    let server_name = match ServerName::try_from(host.to_string()) {
+        Ok(name) => name,
+        Err(e) => return failed(format!("TLS: {}", e), tcp_ms),
+    };
+    let connector = TlsConnector::from(Arc::new(probe_tls_config()));
+    let start = Instant::now();
+    match tokio::time::timeout(timeout, connector.connect(server_name, stream)).await {
+        Ok(Ok(_)) => ProbeResult {
+            reachable: true,
+            tcp_ms,
+            tls_ms: Some(millis(start.elapsed())),
+            error: None,
+        },
+        Ok(Err(e)) => failed(format!("TLS: {}", e), tcp_ms),
+        Err(_) => failed(format!("TLS handshake {}", timed_out()), tcp_ms),
+    }
+}
+
+/// Client config for probing: any certificate is accepted, since only the handshake is measured
+fn probe_tls_config() -> ClientConfig {
+    let provider = Arc::new(crypto::ring::default_provider());
+    ClientConfig::builder_with_provider(provider.clone())
+        .with_safe_default_protocol_versions()
+        .expect("the ring provider supports the default protocol versions")
+        .dangerous()
+        .with_custom_certificate_verifier(Arc::new(AcceptAnyCertificate(provider)))
+        .with_no_client_auth()
+}
+
+/// Accepts any server certificate while still checking the handshake signatures
+#[derive(Debug)]
+struct AcceptAnyCertificate(Arc<CryptoProvider>);
+
+impl ServerCertVerifier for AcceptAnyCertificate {
+    fn verify_server_cert(
+        &self,
+        _end_entity: &CertificateDer<'_>,
+        _intermediates: &[CertificateDer<'_>],
+        _server_name: &ServerName<'_>,
+        _ocsp_response: &[u8],
+        _now: UnixTime,
+    ) -> Result<ServerCertVerified, tokio_rustls::rustls::Error> {
+        Ok(ServerCertVerified::assertion())
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, tokio_rustls::rustls::Error> {
+        crypto::verify_tls12_signature(
+            message,
+            cert,
+            dss,
+            &self.0.signature_verification_algorithms,
+        )
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, tokio_rustls::rustls::Error> {
+        crypto::verify_tls13_signature(
+            message,
+            cert,
+            dss,
+            &self.0.signature_verification_algorithms,
+        )
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.0.signature_verification_algorithms.supported_schemes()
+    }
+}
+
+fn endpoint_row(address: &EndpointAddress) -> EndpointRow {
+    EndpointRow {
+        database: address.database_uid.to_string(),
+        name: address.database_name.clone(),
+        endpoint: format!(
+            "{}:{}",
+            address.dns_name.as_deref().unwrap_or("-"),
+            address.port
+        ),
+        address: address.address.clone(),
+        addr_type: address.addr_type.clone().unwrap_or_else(|| "-".to_string()),
+        tls: yes_no(address.tls),
+    }
+}
+
+fn probe_row(address: &EndpointAddress) -> ProbeRow {
+    let probe = address.probe.clone().unwrap_or_default();
+    let ms =
+        |value: Option<f64>| value.map_or_else(|| "-".to_string(), |ms| format!("{:.1} ms", ms));
+    ProbeRow {
+        database: address.database_uid.to_string(),
+        address: format!("{}:{}", address.address, address.port),
+        tls: yes_no(address.tls),
+        status: if probe.reachable {
+            "reachable"
+        } else {
+            "unreachable"
+        }
+        .to_string(),
+        tcp: ms(probe.tcp_ms),
+        handshake: ms(probe.tls_ms),
+        error: probe.error.unwrap_or_else(|| "-".to_string()),
+    }
+}
+
+fn yes_no(value: bool) -> String {
+    if value { "yes" } else { "no" }.to_string()
+}
+
+fn millis(duration: Duration) -> f64 {
+    (duration.as_secs_f64() * 10_000.0).round() / 10.0
+}
+
+fn format_ms(duration: Duration) -> String {
+    if duration < Duration::from_secs(1) {
+        format!("{} ms", duration.as_millis())
+    } else {
+        format!("{}s", duration.as_secs_f64())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_endpoint_addresses() {
+        let databases: Vec<Database> = serde_json::from_value(json!([
+            {
+                "uid": 2,
+                "name": "sessions",
+                "port": 12000,
+                "tls_mode": "enabled",
+                "endpoints": [{
+                    "uid": "2:1",
+                    "addr": ["10.0.0.1", "10.0.0.2"],
+                    "port": 12000,
+                    "dns_name": "redis-12000.cluster.local",
+                    "addr_type": "internal"
+                }]
+            },
+            {
+                "uid": 1,
+                "name": "cache",
+                "port": 13000,
+                "tls_mode": "disabled",
+                "endpoints": [{"uid": "1:1", "dns_name": "redis-13000.cluster.local"}]
+            }
+        ]))
+        .unwrap();
+
+        let addresses = endpoint_addresses(&databases);
+        assert_eq!(addresses.len(), 3);
+        assert_eq!(addresses[0].database_uid, 1);
+        assert_eq!(addresses[0].address, "redis-13000.cluster.local");
+        assert_eq!(addresses[0].port, 13000);
+        assert!(!addresses[0].tls);
+        assert_eq!(addresses[1].address, "10.0.0.1");
+        assert_eq!(addresses[2].address, "10.0.0.2");
+        assert!(addresses[2].tls);
+    }
+
+    #[tokio::test]
+    async fn test_probe() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        let result = probe("127.0.0.1", port, false, Duration::from_secs(2)).await;
+        assert!(result.reachable);
+        assert!(result.tcp_ms.is_some());
+        assert_eq!(result.tls_ms, None);
+
+        drop(listener);
+        let result = probe("127.0.0.1", port, false, Duration::from_secs(2)).await;
+        assert!(!result.reachable);
+        assert!(result.error.unwrap().starts_with("connect:"));
+    }
+}
@@ -0,0 +1,483 @@
+//! License usage and capacity trend for Enterprise
+//!
+//! `license usage` compares the cluster's shard, node and RAM usage with the license limits.
+//! With `--history` it also reads the shard stats over the last days: the shards reporting in each
+//! stats interval give the shard count at that time, and the sum of their `used_memory` the RAM
+//! in use. Rows keep the peak of each `--interval`, and a least-squares line through them projects
+//! when the license limits are reached at the current growth.
+
+#![allow(dead_code)]
+
+use super::utils::*;
+use crate::cli::{EnterpriseLicenseCommands, OutputFormat};
+use crate::connection::ConnectionManager;
+use crate::error::{RedisCtlError, Result as CliResult};
+use crate::output::notice;
+use crate::units::format_bytes;
+use anyhow::Context;
+use chrono::{DateTime, Duration as ChronoDuration, SecondsFormat, Utc};
+use redis_enterprise::license::{LicenseHandler, LicenseUsage};
+use redis_enterprise::stats::{AggregatedStatsResponse, StatsHandler, StatsQuery};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use tabled::{Table, Tabled, settings::Style};
+
+/// Stats intervals the history can be read at, largest first
+const STATS_INTERVALS: &[(&str, u64)] = &[("1week", 604_800), ("12hour", 43_200), ("1hour", 3_600)];
+
+pub async fn handle_license_command(
+    conn_mgr: &ConnectionManager,
+    profile_name: Option<&str>,
+    command: &EnterpriseLicenseCommands,
+    output_format: OutputFormat,
+    query: Option<&str>,
+) -> CliResult<()> {
+    match command {
+        EnterpriseLicenseCommands::Usage {
+            history,
+            interval,
+            days,
+        } => {
+            if *history {
+                usage_history(
+                    conn_mgr,
+                    profile_name,
+                    *interval,
+                    *days,
+                    output_format,
+                    query,
+                )
+                .await
+            } else {
+                usage(conn_mgr, profile_name, output_format, query).await
+            }
+        }
+    }
+}
+
+/// Shards and RAM in use at one point in time
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct UsageSample {
+    pub time: DateTime<Utc>,
+    pub shards: u32,
+    pub ram_bytes: u64,
+}
+
+/// When a limit is reached at the growth seen in the history
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Projection {
+    pub used: f64,
+    pub limit: f64,
+    /// Growth per day, from a least-squares fit through the samples
+    pub growth_per_day: f64,
+    /// Days until the limit is reached; `None` when usage is not growing
+    pub days_left: Option<f64>,
+    pub exhausted_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize)]
+struct UsageHistory {
+    usage: LicenseUsage,
+    interval_seconds: u64,
+    samples: Vec<UsageSample>,
+    shards: Option<Projection>,
+    ram: Option<Projection>,
+}
+
+#[derive(Tabled)]
+struct UsageRow {
+    #[tabled(rename = "RESOURCE")]
+    resource: String,
+    #[tabled(rename = "USED")]
+    used: String,
+    #[tabled(rename = "LIMIT")]
+    limit: String,
+    #[tabled(rename = "USED %")]
+    pct: String,
+}
+
+#[derive(Tabled)]
+struct SampleRow {
+    #[tabled(rename = "TIME")]
+    time: String,
+    #[tabled(rename = "SHARDS")]
+    shards: u32,
+    #[tabled(rename = "RAM")]
+    ram: String,
+}
+
+async fn usage(
+    conn_mgr: &ConnectionManager,
+    profile_name: Option<&str>,
+    output_format: OutputFormat,
+    query: Option<&str>,
+) -> CliResult<()> {
+    let client = conn_mgr.create_enterprise_client(profile_name).await?;
+    let usage = LicenseHandler::new(client)
+        .usage()
+        .await
+        .context("Failed to get license usage")?;
+
+    match output_format {
+        OutputFormat::Auto | OutputFormat::Table if query.is_none() => {
+            print_usage_table(&usage);
+            Ok(())
+        }
+        _ => {
+            let json = serde_json::to_value(&usage).context("Failed to serialize usage")?;
+            let data = handle_output(json, output_format, query)?;
+            print_formatted_output(data, output_format)
+        }
+    }
+}
+
+async fn usage_history(
+    conn_mgr: &ConnectionManager,
+    profile_name: Option<&str>,
+    interval_secs: u64,
+    days: u32,
+    output_format: OutputFormat,
+    query: Option<&str>,
+) -> CliResult<()> {
+    let stats_interval = stats_interval_for(interval_secs)?;
+    if days == 0 {
+        return Err(RedisCtlError::Validation {
+            message: "--days must be at least 1".to_string(),
+        });
+    }
+
+    let client = conn_mgr.create_enterprise_client(profile_name).await?;
+    let now = Utc::now();
+    let stats_query = StatsQuery {
+        interval: Some(stats_interval.to_string()),
+        stime: Some(
+            (now - ChronoDuration::days(days.into())).to_rfc3339_opts(SecondsFormat::Secs, true),
+        ),
+        etime: None,
+        metrics: Some("used_memory".to_string()),
+    };
+    let (usage, stats) = tokio::try_join!(
+        async {
+            LicenseHandler::new(client.clone())
+                .usage()
+                .await
+                .context("Failed to get license usage")
+        },
+        async {
+            StatsHandler::new(client.clone())
+                .shards(Some(stats_query))
+                .await
+                .context("Failed to get shard stats")
+        },
+    )?;
+
+    let samples = usage_samples(&stats, interval_secs);
+    let shards = project(
+        &samples,
+        |s| s.shards as f64,
+        usage.shards_used as f64,
+        usage.shards_limit as f64,
+        now,
+    );
+    let ram = match (usage.ram_used, usage.ram_limit) {
+        (Some(used), Some(limit)) => project(
+            &samples,
+            |s| s.ram_bytes as f64,
+            used as f64,
+            limit as f64,
+            now,
+        ),
+        _ => None,
+    };
+    let history = UsageHistory {
+        usage,
+        interval_seconds: interval_secs,
+        samples,
+        shards,
+        ram,
+    };
+
+    match output_format {
+        OutputFormat::Auto | OutputFormat::Table if query.is_none() => {
+            print_usage_table(&history.usage);
+            if history.samples.is_empty() {
+                notice!("No shard stats in the last {} days", days);
+                return Ok(());
+            }
+            let rows: Vec<SampleRow> = history
+                .samples
+                .iter()
+                .map(|s| SampleRow {
+                    time: crate::timestamps::format_timestamp(&s.time),
+                    shards: s.shards,
+                    ram: format_bytes(s.ram_bytes),
+                })
+                .collect();
+            let mut table = Table::new(rows);
+            table.with(Style::blank());
+            println!();
+            println!("{}", table);
+            println!();
+            println!(
+                "{}",
+                projection_line("Shards", history.shards.as_ref(), |v| {
+                    format!("{:.1}", v)
+                })
+            );
+            if history.usage.ram_limit.is_some() {
+                println!(
+                    "{}",
+                    projection_line("RAM", history.ram.as_ref(), |v| {
+                        format_bytes(v.max(0.0) as u64)
+                    })
+                );
+            }
+            Ok(())
+        }
+        _ => {
+            let json = serde_json::to_value(&history).context("Failed to serialize usage")?;
+            let data = handle_output(json, output_format, query)?;
+            print_formatted_output(data, output_format)
+        }
+    }
+}
+
+/// The largest stats interval that fits a whole number of times in the requested row length
+pub fn stats_interval_for(interval_secs: u64) -> CliResult<&'static str> {
+    STATS_INTERVALS
+        .iter()
+        .find(|(_, secs)| interval_secs >= *secs && interval_secs.is_multiple_of(*secs))
+        .map(|(name, _)| *name)
+        .ok_or_else(|| RedisCtlError::Validation {
+            message: format!(
+                "--interval must be a whole number of hours (got {} seconds)",
+                interval_secs
+            ),
+        })
+}
+
+/// Peak shard count and RAM per `interval_secs` window, from per-shard stats
+pub fn usage_samples(stats: &AggregatedStatsResponse, interval_secs: u64) -> Vec<UsageSample> {
+    // Shards and memory at each stats timestamp
+    let mut points: BTreeMap<DateTime<Utc>, (u32, u64)> = BTreeMap::new();
+    for shard in &stats.stats {
+        for interval in &shard.intervals {
+            let Some(time) = crate::timestamps::parse_timestamp_str(&interval.time) else {
+                continue;
+            };
+            let memory = interval
+                .metrics
+                .get("used_memory")
+                .and_then(|v| v.as_f64())
+                .unwrap_or_default();
+            let point = points.entry(time).or_default();
+            point.0 += 1;
+            point.1 += memory as u64;
+        }
+    }
+
+    // Peak of each window, stamped with the window start
+    let window = interval_secs.max(1) as i64;
+    let mut samples: Vec<UsageSample> = Vec::new();
+    for (time, (shards, ram_bytes)) in points {
+        let start = time.timestamp() - time.timestamp().rem_euclid(window);
+        let start = DateTime::from_timestamp(start, 0).unwrap_or(time);
+        match samples.last_mut() {
+            Some(last) if last.time == start => {
+                last.shards = last.shards.max(shards);
+                last.ram_bytes = last.ram_bytes.max(ram_bytes);
+            }
+            _ => samples.push(UsageSample {
+                time: start,
+                shards,
+                ram_bytes,
+            }),
+        }
+    }
+    samples
+}
+
+/// Fit a line through the samples and project when `limit` is reached from `used`
+///
+/// Needs at least two samples over some time span; a zero or negative slope means usage is not
+/// growing and nothing is projected.
+pub fn project(
+    samples: &[UsageSample],
+    value: impl Fn(&UsageSample) -> f64,
+    used: f64,
+    limit: f64,
+    now: DateTime<Utc>,
+) -> Option<Projection> {
+    if samples.len() < 2 || limit <= 0.0 {
+        return None;
+    }
+    let origin = samples[0].time;
+    let points: Vec<(f64, f64)> = samples
+        .iter()
+        .map(|s| ((s.time - origin).num_seconds() as f64 / 86_400.0, value(s)))
+        .collect();
+    let n = points.len() as f64;
+    let mean_x = points.iter().map(|(x, _)| x).sum::<f64>() / n;
+    let mean_y = points.iter().map(|(_, y)| y).sum::<f64>() / n;
+    let variance: f64 = points.iter().map(|(x, _)| (x - mean_x).powi(2)).sum();
+    if variance == 0.0 {
+        return None;
+    }
+    let covariance: f64 = points
+        .iter()
+        .map(|(x, y)| (x - mean_x) * (y - mean_y))
+        .sum();
+    let growth_per_day = covariance / variance;
+
+    let days_left = if used >= limit {
+        Some(0.0)
+    } else if growth_per_day > 0.0 {
+        Some((limit - used) / growth_per_day)
+    } else {
+        None
+    };
+    let exhausted_at =
+        days_left.map(|days| now + ChronoDuration::seconds((days * 86_400.0).round() as i64));
+    Some(Projection {
+        used,
+        limit,
+        growth_per_day,
+        days_left,
+        exhausted_at,
+    })
+}
+
+fn projection_line(
+    resource: &str,
+    projection: Option<&Projection>,
+    format: impl Fn(f64) -> String,
+) -> String {
+    match projection {
+        None => format!("{}: not enough history to project", resource),
+        Some(p) => match (p.days_left, p.exhausted_at) {
+            (Some(days), _) if days <= 0.0 => format!("{}: license limit reached", resource),
+            (Some(days), Some(at)) => format!(
+                "{}: growing {} per day, limit reached in about {:.0} days ({})",
+                resource,
+                format(p.growth_per_day),
+                days.ceil(),
+                at.format("%Y-%m-%d")
+            ),
+            _ => format!("{}: not growing, no limit in sight", resource),
+        },
+    }
+}
+
+fn print_usage_table(usage: &LicenseUsage) {
+    let pct = |used: f64, limit: f64| {
+        if limit > 0.0 {
+            format!("{:.0}%", used / limit * 100.0)
+        } else {
+            "-".to_string()
+        }
+    };
+    let mut rows = vec![
+        UsageRow {
+            resource: "Shards".to_string(),
+            used: usage.shards_used.to_string(),
+            limit: usage.shards_limit.to_string(),
+            pct: pct(usage.shards_used as f64, usage.shards_limit as f64),
+        },
+        UsageRow {
+            resource: "Nodes".to_string(),
+            used: usage.nodes_used.to_string(),
+            limit: usage.nodes_limit.to_string(),
+            pct: pct(usage.nodes_used as f64, usage.nodes_limit as f64),
+        },
+    ];
+    if let (Some(used), Some(limit)) = (usage.ram_used, usage.ram_limit) {
+        rows.push(UsageRow {
+            resource: "RAM".to_string(),
+            used: format_bytes(used),
+            limit: format_bytes(limit),
+            pct: pct(used as f64, limit as f64),
+        });
+    }
+    let mut table = Table::new(rows);
+    table.with(Style::blank());
+    println!("{}", table);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn at(s: &str) -> DateTime<Utc> {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_stats_interval_for() {
+        assert_eq!(stats_interval_for(86_400).unwrap(), "12hour");
+        assert_eq!(stats_interval_for(604_800).unwrap(), "1week");
+        assert_eq!(stats_interval_for(3_600 * 6).unwrap(), "1hour");
+        assert!(stats_interval_for(1_800).is_err());
+        assert!(stats_interval_for(5_400).is_err());
+    }
+
+    #[test]
+    fn test_usage_samples() {
+        let stats: AggregatedStatsResponse = serde_json::from_value(json!({"stats": [
+            {"uid": 1, "intervals": [
+                {"time": "2024-03-01T00:00:00Z", "metrics": {"used_memory": 100}},
+                {"time": "2024-03-01T12:00:00Z", "metrics": {"used_memory": 300}},
+                {"time": "2024-03-02T00:00:00Z", "metrics": {"used_memory": 400}}
+            ]},
+            {"uid": 2, "intervals": [
+                {"time": "2024-03-01T12:00:00Z", "metrics": {"used_memory": 50}},
+                {"time": "2024-03-02T00:00:00Z", "metrics": {"used_memory": 60}}
+            ]}
+        ]}))
+        .unwrap();
+
+        let samples = usage_samples(&stats, 86_400);
+        assert_eq!(
+            samples,
+            vec![
+                UsageSample {
+                    time: at("2024-03-01T00:00:00Z"),
+                    shards: 2,
+                    ram_bytes: 350
+                },
+                UsageSample {
+                    time: at("2024-03-02T00:00:00Z"),
+                    shards: 2,
+                    ram_bytes: 460
+                },
+            ]
+        );
+        assert_eq!(usage_samples(&stats, 43_200).len(), 3);
+    }
+
+    #[test]
+    fn test_project() {
+        let samples: Vec<UsageSample> = (0..5)
+            .map(|day| UsageSample {
+                time: at("2024-03-01T00:00:00Z") + ChronoDuration::days(day),
+                shards: 10 + 2 * day as u32,
+                ram_bytes: 0,
+            })
+            .collect();
+        let now = at("2024-03-05T00:00:00Z");
+
+        let p = project(&samples, |s| s.shards as f64, 18.0, 38.0, now).unwrap();
+        assert!((p.growth_per_day - 2.0).abs() < 1e-9);
+        assert_eq!(p.days_left, Some(10.0));
+        assert_eq!(p.exhausted_at, Some(at("2024-03-15T00:00:00Z")));
+
+        let flat = project(&samples, |s| s.ram_bytes as f64, 0.0, 100.0, now).unwrap();
+        assert_eq!(flat.days_left, None);
+        assert!(projection_line("RAM", Some(&flat), |v| v.to_string()).contains("not growing"));
+
+        let full = project(&samples, |s| s.shards as f64, 40.0, 38.0, now).unwrap();
+        assert_eq!(full.days_left, Some(0.0));
+
+        assert!(project(&samples[..1], |s| s.shards as f64, 10.0, 38.0, now).is_none());
+    }
+}
@@ -0,0 +1,370 @@
+//! Cluster event log commands for Enterprise
+//!
+//! Filters are sent to `/v1/logs` as typed query parameters and applied again to the returned
+//! entries, since older clusters ignore the filters they do not know.
+
+#![allow(dead_code)]
+
+use super::utils::*;
+use crate::cli::{EnterpriseLogsCommands, LogOrderArg, OutputFormat};
+use crate::connection::ConnectionManager;
+use crate::error::{RedisCtlError, Result as CliResult};
+use crate::output::notice;
+use anyhow::Context;
+use chrono::{DateTime, SecondsFormat, Utc};
+use redis_enterprise::{LogEntry, LogOrder, LogsHandler, LogsQuery};
+use tabled::{Table, Tabled, settings::Style};
+
+pub async fn handle_logs_command(
+    conn_mgr: &ConnectionManager,
+    profile_name: Option<&str>,
+    command: &EnterpriseLogsCommands,
+    output_format: OutputFormat,
+    query: Option<&str>,
+) -> CliResult<()> {
+    match command {
+        EnterpriseLogsCommands::List {
+            since,
+            until,
+            order,
+            limit,
+            offset,
+            level,
+            component,
+            node,
+            database,
+            originator,
+            stream,
+        } => {
+            let now = Utc::now();
+            let (originator_uid, originator_username) = match originator {
+                Some(o) => match o.parse::<u32>() {
+                    Ok(uid) => (Some(uid), None),
+                    Err(_) => (None, Some(o.clone())),
+                },
+                None => (None, None),
+            };
+            let logs_query = LogsQuery {
+                stime: since
+                    .as_deref()
+                    .map(|s| parse_log_time(s, now, false))
+                    .transpose()?,
+                etime: until
+                    .as_deref()
+                    .map(|s| parse_log_time(s, now, true))
+                    .transpose()?,
+                order: Some(match order {
+                    LogOrderArg::Asc => LogOrder::Asc,
+                    LogOrderArg::Desc => LogOrder::Desc,
+                }),
+                limit: (*limit > 0).then_some(*limit),
+                offset: *offset,
+                level: level.clone(),
+                component: component.clone(),
+                node_uid: *node,
+                bdb_uid: *database,
+                originator_uid,
+                originator_username,
+            };
+            if *stream {
+                stream_logs(conn_mgr, profile_name, logs_query, output_format, query).await
+            } else {
+                list_logs(conn_mgr, profile_name, logs_query, output_format, query).await
+            }
+        }
+    }
+}
+
+async fn list_logs(
+    conn_mgr: &ConnectionManager,
+    profile_name: Option<&str>,
+    logs_query: LogsQuery,
+    output_format: OutputFormat,
+    query: Option<&str>,
+) -> CliResult<()> {
+    let client = conn_mgr.create_enterprise_client(profile_name).await?;
+    let mut entries: Vec<LogEntry> = LogsHandler::new(client)
+        .list(Some(logs_query.clone()))
+        .await
+        .context("Failed to get cluster logs")?
+        .into_iter()
+        .filter(|entry| matches_query(entry, &logs_query))
+        .collect();
+
+    entries.sort_by(|a, b| a.time.cmp(&b.time));
+    if logs_query.order != Some(LogOrder::Asc) {
+        entries.reverse();
+    }
+    if let Some(limit) = logs_query.limit {
+        entries.truncate(limit as usize);
+    }
+
+    match output_format {
+        OutputFormat::Auto | OutputFormat::Table if query.is_none() => {
+            if entries.is_empty() {
+                notice!("No events found");
+                return Ok(());
+            }
+            let rows: Vec<LogRow> = entries.iter().map(LogRow::from).collect();
+            let mut table = Table::new(rows);
+            table.with(Style::blank());
+            println!("{}", table);
+        }
+        _ => {
+            let json = serde_json::to_value(&entries).context("Failed to serialize logs")?;
+            let data = handle_output(json, output_format, query)?;
+            print_formatted_output(data, output_format)?;
+        }
+    }
+    Ok(())
+}
+
+/// Events requested per page with `--stream`
+const STREAM_PAGE_SIZE: u32 = 100;
+
+/// Print events one page at a time, relying on the cluster for their order
+async fn stream_logs(
+    conn_mgr: &ConnectionManager,
+    profile_name: Option<&str>,
+    logs_query: LogsQuery,
+    output_format: OutputFormat,
+    query: Option<&str>,
+) -> CliResult<()> {
+    crate::output::check_stream_output(output_format, query)?;
+    let client = conn_mgr.create_enterprise_client(profile_name).await?;
+    let handler = LogsHandler::new(client);
+
+    let mut rows = crate::output::RowStream::new::<LogRow>();
+    let mut offset = logs_query.offset.unwrap_or(0);
+    let mut remaining = logs_query.limit;
+    loop {
+        let page_size = remaining.map_or(STREAM_PAGE_SIZE, |r| r.min(STREAM_PAGE_SIZE));
+        if page_size == 0 {
+            break;
+        }
+        let page = handler
+            .list(Some(LogsQuery {
+                limit: Some(page_size),
+                offset: Some(offset),
+                ..logs_query.clone()
+            }))
+            .await
+            .context("Failed to get cluster logs")?;
+        let fetched = page.len() as u32;
+        let entries: Vec<LogEntry> = page
+            .into_iter()
+            .filter(|entry| matches_query(entry, &logs_query))
+            .collect();
+
+        match output_format {
+            OutputFormat::Auto | OutputFormat::Table => {
+                rows.push(&entries.iter().map(LogRow::from).collect::<Vec<_>>());
+            }
+            _ => {
+                let json: Vec<serde_json::Value> = entries
+                    .iter()
+                    .map(serde_json::to_value)
+                    .collect::<Result<_, _>>()
+                    .context("Failed to serialize logs")?;
+                if matches!(output_format, OutputFormat::Id) {
+                    for id in crate::output::resource_ids(&serde_json::Value::Array(json)) {
+                        println!("{}", id);
+                    }
+                } else {
+                    crate::output::print_ndjson(&json).context("Failed to print logs")?;
+                }
+            }
+        }
+
+        offset += fetched;
+        remaining = remaining.map(|r| r.saturating_sub(fetched));
+        if fetched < page_size {
+            break;
+        }
+    }
+
+    if rows.rows() == 0 && matches!(output_format, OutputFormat::Auto | OutputFormat::Table) {
+        notice!("No events found");
+    }
+    Ok(())
+}
+
+/// Whether an entry satisfies the filters; fields an entry does not carry count as a mismatch
+fn matches_query(entry: &LogEntry, query: &LogsQuery) -> bool {
+    let level_matches = query
+        .level
+        .as_ref()
+        .is_none_or(|level| entry.level.eq_ignore_ascii_case(level));
+    let component_matches = query
+        .component
+        .as_ref()
+        .is_none_or(|c| entry.component.as_deref() == Some(c.as_str()));
+    let node_matches = query.node_uid.is_none_or(|uid| entry.node_uid == Some(uid));
+    let bdb_matches = query.bdb_uid.is_none_or(|uid| entry.bdb_uid == Some(uid));
+    let originator_matches = query
+        .originator_uid
+        .is_none_or(|uid| entry.originator_uid == Some(uid))
+        && query
+            .originator_username
+            .as_ref()
+            .is_none_or(|name| entry.originator_username.as_deref() == Some(name.as_str()));
+    let in_range = query.stime.as_ref().is_none_or(|s| after(&entry.time, s))
+        && query.etime.as_ref().is_none_or(|e| after(e, &entry.time));
+
+    level_matches
+        && component_matches
+        && node_matches
+        && bdb_matches
+        && originator_matches
+        && in_range
+}
+
+/// `a >= b` for two timestamps, treating unparseable times as in range
+fn after(a: &str, b: &str) -> bool {
+    match (
+        DateTime::parse_from_rfc3339(a),
+        DateTime::parse_from_rfc3339(b),
+    ) {
+        (Ok(a), Ok(b)) => a >= b,
+        _ => true,
+    }
+}
+
+/// Parse `--since`/`--until` into the timestamp format the logs API takes
+///
+/// See [`crate::units::parse_time_bound`] for the accepted values.
+pub(crate) fn parse_log_time(
+    value: &str,
+    now: DateTime<Utc>,
+    end_of_day: bool,
+) -> CliResult<String> {
+    let time = crate::units::parse_time_bound(value, now, end_of_day)
+        .map_err(|message| RedisCtlError::Validation { message })?;
+    Ok(time.to_rfc3339_opts(SecondsFormat::Secs, true))
+}
+
+#[derive(Tabled)]
+pub(crate) struct LogRow {
+    #[tabled(rename = "TIME")]
+    time: String,
+    #[tabled(rename = "SEVERITY")]
+    severity: String,
+    #[tabled(rename = "EVENT")]
+    event: String,
+    #[tabled(rename = "RESOURCE")]
+    resource: String,
+    #[tabled(rename = "BY")]
+    originator: String,
+}
+
+impl From<&LogEntry> for LogRow {
+    fn from(entry: &LogEntry) -> Self {
+        let event = match (&entry.event_type, entry.message.is_empty()) {
+            (Some(event_type), true) => event_type.clone(),
+            (Some(event_type), false) => format!("{}: {}", event_type, entry.message),
+            (None, _) => entry.message.clone(),
+        };
+        let resource = match (entry.bdb_uid, entry.node_uid) {
+            (Some(bdb), _) => format!("db:{}", bdb),
+            (None, Some(node)) => format!("node:{}", node),
+            (None, None) => entry.component.clone().unwrap_or_else(|| "-".to_string()),
+        };
+        LogRow {
+            time: crate::timestamps::display(&entry.time),
+            severity: entry.level.clone(),
+            event,
+            resource,
+            originator: entry
+                .originator_username
+                .clone()
+                .or_else(|| entry.user.clone())
+                .or_else(|| entry.originator_uid.map(|uid| uid.to_string()))
+                .unwrap_or_else(|| "-".to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn now() -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339("2024-03-10T12:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    fn entry(value: serde_json::Value) -> LogEntry {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn test_parse_log_time() {
+        assert_eq!(
+            parse_log_time("90m", now(), false).unwrap(),
+            "2024-03-10T10:30:00Z"
+        );
+        assert_eq!(
+            parse_log_time("2024-03-01", now(), true).unwrap(),
+            "2024-03-01T23:59:59Z"
+        );
+        assert_eq!(
+            parse_log_time("2024-03-01T08:00:00+02:00", now(), false).unwrap(),
+            "2024-03-01T06:00:00Z"
+        );
+        assert!(parse_log_time("yesterday", now(), false).is_err());
+        assert!(parse_log_time("20000000w", now(), false).is_err());
+    }
+
+    #[test]
+    fn test_matches_query() {
+        let event = entry(json!({
+            "time": "2024-03-10T11:00:00Z",
+            "type": "bdb_name_updated",
+            "severity": "WARNING",
+            "bdb_uid": "3",
+            "originator_uid": "1",
+            "originator_username": "admin"
+        }));
+
+        assert!(matches_query(&event, &LogsQuery::default()));
+        let query = LogsQuery {
+            stime: Some("2024-03-10T10:00:00Z".to_string()),
+            level: Some("warning".to_string()),
+            bdb_uid: Some(3),
+            originator_username: Some("admin".to_string()),
+            ..Default::default()
+        };
+        assert!(matches_query(&event, &query));
+
+        let later = LogsQuery {
+            stime: Some("2024-03-10T11:30:00Z".to_string()),
+            ..Default::default()
+        };
+        assert!(!matches_query(&event, &later));
+        let other_user = LogsQuery {
+            originator_uid: Some(2),
+            ..Default::default()
+        };
+        assert!(!matches_query(&event, &other_user));
+        let node = LogsQuery {
+            node_uid: Some(1),
+            ..Default::default()
+        };
+        assert!(!matches_query(&event, &node));
+    }
+
+    #[test]
+    fn test_log_row() {
+        let row = LogRow::from(&entry(json!({
+            "time": "2024-03-10T11:00:00Z",
+            "type": "node_status_changed",
+            "severity": "INFO",
+            "node_uid": 2
+        })));
+        assert_eq!(row.event, "node_status_changed");
+        assert_eq!(row.resource, "node:2");
+        assert_eq!(row.originator, "-");
+    }
+}
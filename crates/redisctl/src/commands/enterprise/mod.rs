@@ -1,13 +1,38 @@
 //! Enterprise command implementations
 
+pub mod acl_lint;
+pub mod alert;
 pub mod cluster;
+pub mod cluster_events;
 pub mod cluster_impl;
+pub mod cluster_maintenance;
 pub mod crdb;
 pub mod crdb_impl;
 pub mod database;
+pub mod database_acl;
 pub mod database_impl;
+pub mod database_sync;
+pub mod debuginfo;
+pub mod endpoint;
+pub mod license;
+pub mod logs;
+pub mod module;
 pub mod node;
 pub mod node_impl;
+pub mod node_resources;
+pub mod node_settings;
+pub mod ocsp;
 pub mod rbac;
 pub mod rbac_impl;
+pub mod rbac_simulate;
+pub mod service;
+pub mod service_impl;
+pub mod shard;
+pub mod stats;
+pub mod stats_chart;
+pub mod stats_export;
+pub mod stats_push;
+pub mod stats_summary;
+pub mod top;
+pub mod traffic;
 pub mod utils;
@@ -0,0 +1,238 @@
+//! Redis modules installed on an Enterprise cluster
+//!
+//! `module list` reads the typed module list and the databases' module lists, so each module is
+//! shown with the databases that load it. `--compatible-with` keeps the modules whose minimum
+//! Redis version allows the given version, and `--capability` those that support a feature such
+//! as `crdb` or `replica_of`.
+
+#![allow(dead_code)]
+
+use crate::cli::{EnterpriseModuleCommands, OutputFormat};
+use crate::connection::ConnectionManager;
+use crate::error::Result as CliResult;
+use crate::output::notice;
+use anyhow::Context;
+use redis_enterprise::bdb::DatabaseHandler;
+use redis_enterprise::modules::{Module, ModuleHandler};
+use serde::Serialize;
+use serde_json::Value;
+use tabled::{Table, Tabled, settings::Style};
+
+use super::utils::*;
+
+pub async fn handle_module_command(
+    conn_mgr: &ConnectionManager,
+    profile_name: Option<&str>,
+    command: &EnterpriseModuleCommands,
+    output_format: OutputFormat,
+    query: Option<&str>,
+) -> CliResult<()> {
+    match command {
+        EnterpriseModuleCommands::List {
+            compatible_with,
+            capability,
+        } => {
+            list_modules(
+                conn_mgr,
+                profile_name,
+                compatible_with.as_deref(),
+                capability.as_deref(),
+                output_format,
+                query,
+            )
+            .await
+        }
+    }
+}
+
+/// A module and the databases that load it
+#[derive(Debug, Clone, Serialize)]
+pub struct ModuleUsage {
+    #[serde(flatten)]
+    pub module: Module,
+    pub databases: Vec<DatabaseRef>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DatabaseRef {
+    pub uid: u64,
+    pub name: String,
+}
+
+#[derive(Tabled)]
+struct ModuleRow {
+    #[tabled(rename = "NAME")]
+    name: String,
+    #[tabled(rename = "VERSION")]
+    version: String,
+    #[tabled(rename = "MIN REDIS")]
+    min_redis: String,
+    #[tabled(rename = "CAPABILITIES")]
+    capabilities: String,
+    #[tabled(rename = "DATABASES")]
+    databases: String,
+}
+
+async fn list_modules(
+    conn_mgr: &ConnectionManager,
+    profile_name: Option<&str>,
+    compatible_with: Option<&str>,
+    capability: Option<&str>,
+    output_format: OutputFormat,
+    query: Option<&str>,
+) -> CliResult<()> {
+    let client = conn_mgr.create_enterprise_client(profile_name).await?;
+    let (modules, databases) = tokio::try_join!(
+        async {
+            ModuleHandler::new(client.clone())
+                .list()
+                .await
+                .context("Failed to list modules")
+        },
+        async {
+            DatabaseHandler::new(client.clone())
+                .list_with_fields(&["uid", "name", "module_list"])
+                .await
+                .context("Failed to list databases")
+        },
+    )?;
+
+    let usage: Vec<ModuleUsage> = module_usage(modules, &databases)
+        .into_iter()
+        .filter(|usage| compatible_with.is_none_or(|v| usage.module.supports_redis(v)))
+        .filter(|usage| capability.is_none_or(|c| usage.module.has_capability(c)))
+        .collect();
+
+    match output_format {
+        OutputFormat::Auto | OutputFormat::Table if query.is_none() => {
+            if usage.is_empty() {
+                notice!("No modules found");
+                return Ok(());
+            }
+            let rows: Vec<ModuleRow> = usage.iter().map(row).collect();
+            let mut table = Table::new(rows);
+            table.with(Style::blank());
+            println!("{}", table);
+            Ok(())
+        }
+        _ => {
+            let json = serde_json::to_value(&usage).context("Failed to serialize modules")?;
+            let data = handle_output(json, output_format, query)?;
+            print_formatted_output(data, output_format)
+        }
+    }
+}
+
+/// Pair each module with the databases whose `module_list` names it, sorted by module name
+///
+/// Databases refer to modules by `module_id`, the module's uid, which tells apart versions of a
+/// module; entries without one are matched by `module_name`.
+pub fn module_usage(modules: Vec<Module>, databases: &[Value]) -> Vec<ModuleUsage> {
+    let mut usage: Vec<ModuleUsage> = modules
+        .into_iter()
+        .map(|module| {
+            let databases = databases
+                .iter()
+                .filter(|db| {
+                    db.get("module_list")
+                        .and_then(Value::as_array)
+                        .into_iter()
+                        .flatten()
+                        .any(
+                            |entry| match entry.get("module_id").and_then(Value::as_str) {
+                                Some(id) => id == module.uid,
+                                None => entry
+                                    .get("module_name")
+                                    .and_then(Value::as_str)
+                                    .is_some_and(|name| name.eq_ignore_ascii_case(&module.name)),
+                            },
+                        )
+                })
+                .filter_map(|db| {
+                    Some(DatabaseRef {
+                        uid: db.get("uid")?.as_u64()?,
+                        name: db
+                            .get("name")
+                            .and_then(Value::as_str)
+                            .unwrap_or_default()
+                            .to_string(),
+                    })
+                })
+                .collect();
+            ModuleUsage { module, databases }
+        })
+        .collect();
+    usage.sort_by(|a, b| {
+        a.module.name.cmp(&b.module.name).then_with(|| {
+            redis_enterprise::modules::compare_versions(b.module.semver(), a.module.semver())
+        })
+    });
+    usage
+}
+
+fn row(usage: &ModuleUsage) -> ModuleRow {
+    let module = &usage.module;
+    ModuleRow {
+        name: module
+            .display_name
+            .clone()
+            .unwrap_or_else(|| module.name.clone()),
+        version: module.semver().to_string(),
+        min_redis: module
+            .min_redis_version
+            .clone()
+            .unwrap_or_else(|| "-".to_string()),
+        capabilities: module
+            .capabilities
+            .as_ref()
+            .filter(|c| !c.is_empty())
+            .map_or_else(|| "-".to_string(), |c| c.join(", ")),
+        databases: if usage.databases.is_empty() {
+            "-".to_string()
+        } else {
+            usage
+                .databases
+                .iter()
+                .map(|db| format!("{}:{}", db.uid, db.name))
+                .collect::<Vec<_>>()
+                .join(", ")
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_module_usage_matches_by_id_or_name() {
+        let modules: Vec<Module> = serde_json::from_value(json!([
+            {"uid": "m2", "module_name": "ReJSON", "version": 20606, "semantic_version": "2.6.6"},
+            {"uid": "m1", "module_name": "search", "version": 20612, "semantic_version": "2.6.12"},
+            {"uid": "m0", "module_name": "search", "version": 20410, "semantic_version": "2.4.10"}
+        ]))
+        .unwrap();
+        let databases = vec![
+            json!({"uid": 1, "name": "cache", "module_list": [{"module_id": "m1", "module_name": "search"}]}),
+            json!({"uid": 2, "name": "docs", "module_list": [{"module_name": "rejson"}]}),
+            json!({"uid": 3, "name": "plain", "module_list": []}),
+        ];
+
+        let usage = module_usage(modules, &databases);
+        assert_eq!(usage[0].module.name, "ReJSON");
+        assert_eq!(
+            usage[0].databases,
+            vec![DatabaseRef {
+                uid: 2,
+                name: "docs".to_string()
+            }]
+        );
+        // Newest version first within a name; only the version the database loads lists it
+        assert_eq!(usage[1].module.semver(), "2.6.12");
+        assert_eq!(usage[1].databases.len(), 1);
+        assert!(usage[2].databases.is_empty());
+        assert_eq!(row(&usage[1]).databases, "1:cache");
+        assert_eq!(row(&usage[1]).min_redis, "-");
+    }
+}
@@ -7,6 +7,8 @@ use crate::connection::ConnectionManager;
 use crate::error::Result as CliResult;
 
 use super::node_impl;
+use super::node_resources::{self, ResourceView};
+use super::node_settings;
 
 pub async fn handle_node_command(
     conn_mgr: &ConnectionManager,
@@ -29,16 +31,26 @@ pub async fn handle_node_command(
         EnterpriseNodeCommands::Remove { id, force } => {
             node_impl::remove_node(conn_mgr, profile_name, *id, *force, output_format, query).await
         }
-        EnterpriseNodeCommands::Update { id, data } => {
-            node_impl::update_node(conn_mgr, profile_name, *id, data, output_format, query).await
+        EnterpriseNodeCommands::Update { id, data, patch } => {
+            node_impl::update_node(
+                conn_mgr,
+                profile_name,
+                *id,
+                data.as_deref(),
+                patch.as_deref(),
+                output_format,
+                query,
+            )
+            .await
         }
 
         // Node Status & Health
         EnterpriseNodeCommands::Status { id } => {
             node_impl::get_node_status(conn_mgr, profile_name, *id, output_format, query).await
         }
-        EnterpriseNodeCommands::Stats { id } => {
-            node_impl::get_node_stats(conn_mgr, profile_name, *id, output_format, query).await
+        EnterpriseNodeCommands::Stats { id, format } => {
+            node_impl::get_node_stats(conn_mgr, profile_name, *id, *format, output_format, query)
+                .await
         }
         EnterpriseNodeCommands::Metrics { id, interval } => {
             node_impl::get_node_metrics(
@@ -51,8 +63,32 @@ pub async fn handle_node_command(
             )
             .await
         }
-        EnterpriseNodeCommands::Check { id } => {
-            node_impl::check_node_health(conn_mgr, profile_name, *id, output_format, query).await
+        EnterpriseNodeCommands::Check {
+            id,
+            cpu_warn,
+            cpu_fail,
+            memory_warn,
+            memory_fail,
+            storage_warn,
+            storage_fail,
+        } => {
+            let thresholds = node_impl::HealthThresholds {
+                cpu_warn: *cpu_warn,
+                cpu_fail: *cpu_fail,
+                memory_warn: *memory_warn,
+                memory_fail: *memory_fail,
+                storage_warn: *storage_warn,
+                storage_fail: *storage_fail,
+            };
+            node_impl::check_node_health(
+                conn_mgr,
+                profile_name,
+                *id,
+                &thresholds,
+                output_format,
+                query,
+            )
+            .await
         }
         EnterpriseNodeCommands::Alerts { id } => {
             node_impl::get_node_alerts(conn_mgr, profile_name, *id, output_format, query).await
@@ -84,10 +120,45 @@ pub async fn handle_node_command(
                 .await
         }
         EnterpriseNodeCommands::GetRack { id } => {
-            node_impl::get_node_rack(conn_mgr, profile_name, *id, output_format, query).await
+            node_settings::get_node_rack(conn_mgr, profile_name, *id, output_format, query).await
+        }
+        EnterpriseNodeCommands::SetRack {
+            id,
+            rack,
+            second_rack,
+        } => {
+            node_settings::set_node_rack(
+                conn_mgr,
+                profile_name,
+                *id,
+                rack,
+                second_rack.as_deref(),
+                output_format,
+                query,
+            )
+            .await
+        }
+        EnterpriseNodeCommands::SetExternalAddr { id, addrs, .. } => {
+            node_settings::set_external_addr(
+                conn_mgr,
+                profile_name,
+                *id,
+                addrs,
+                output_format,
+                query,
+            )
+            .await
         }
-        EnterpriseNodeCommands::SetRack { id, rack } => {
-            node_impl::set_node_rack(conn_mgr, profile_name, *id, rack, output_format, query).await
+        EnterpriseNodeCommands::SetRecoveryPath { id, path } => {
+            node_settings::set_recovery_path(
+                conn_mgr,
+                profile_name,
+                *id,
+                path,
+                output_format,
+                query,
+            )
+            .await
         }
         EnterpriseNodeCommands::GetRole { id } => {
             node_impl::get_node_role(conn_mgr, profile_name, *id, output_format, query).await
@@ -95,19 +166,59 @@ pub async fn handle_node_command(
 
         // Node Resources
         EnterpriseNodeCommands::Resources { id } => {
-            node_impl::get_node_resources(conn_mgr, profile_name, *id, output_format, query).await
+            node_resources::show_node_resources(
+                conn_mgr,
+                profile_name,
+                *id,
+                ResourceView::Summary,
+                output_format,
+                query,
+            )
+            .await
         }
         EnterpriseNodeCommands::Memory { id } => {
-            node_impl::get_node_memory(conn_mgr, profile_name, *id, output_format, query).await
+            node_resources::show_node_resources(
+                conn_mgr,
+                profile_name,
+                *id,
+                ResourceView::Memory,
+                output_format,
+                query,
+            )
+            .await
         }
         EnterpriseNodeCommands::Cpu { id } => {
-            node_impl::get_node_cpu(conn_mgr, profile_name, *id, output_format, query).await
+            node_resources::show_node_resources(
+                conn_mgr,
+                profile_name,
+                *id,
+                ResourceView::Cpu,
+                output_format,
+                query,
+            )
+            .await
         }
         EnterpriseNodeCommands::Storage { id } => {
-            node_impl::get_node_storage(conn_mgr, profile_name, *id, output_format, query).await
+            node_resources::show_node_resources(
+                conn_mgr,
+                profile_name,
+                *id,
+                ResourceView::Storage,
+                output_format,
+                query,
+            )
+            .await
         }
         EnterpriseNodeCommands::Network { id } => {
-            node_impl::get_node_network(conn_mgr, profile_name, *id, output_format, query).await
+            node_resources::show_node_resources(
+                conn_mgr,
+                profile_name,
+                *id,
+                ResourceView::Network,
+                output_format,
+                query,
+            )
+            .await
         }
     }
 }
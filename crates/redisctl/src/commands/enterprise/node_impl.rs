@@ -2,12 +2,18 @@
 
 #![allow(dead_code)]
 
-use crate::cli::OutputFormat;
+use crate::cli::{OutputFormat, StatsExportFormat};
+use crate::commands::patch::patch_update;
 use crate::connection::ConnectionManager;
-use crate::error::Result as CliResult;
+use crate::error::{RedisCtlError, Result as CliResult};
+use crate::output::notice;
 use anyhow::Context;
+use colored::Colorize;
 use redis_enterprise::nodes::NodeHandler;
+use redis_enterprise::services::ServicesHandler;
+use serde::Serialize;
 
+use super::stats_export;
 use super::utils::*;
 
 // Node Operations
@@ -71,14 +77,14 @@ pub async fn remove_node(
     _query: Option<&str>,
 ) -> CliResult<()> {
     if !force && !confirm_action(&format!("Remove node {} from cluster?", id))? {
-        println!("Operation cancelled");
+        notice!("Operation cancelled");
         return Ok(());
     }
 
     let client = conn_mgr.create_enterprise_client(profile_name).await?;
     let handler = NodeHandler::new(client);
     handler.remove(id).await?;
-    println!("Node {} removed successfully", id);
+    notice!("Node {} removed successfully", id);
     Ok(())
 }
 
@@ -86,14 +92,30 @@ pub async fn update_node(
     conn_mgr: &ConnectionManager,
     profile_name: Option<&str>,
     id: u32,
-    data: &str,
+    data: Option<&str>,
+    patch: Option<&str>,
     output_format: OutputFormat,
     query: Option<&str>,
 ) -> CliResult<()> {
     let client = conn_mgr.create_enterprise_client(profile_name).await?;
-    let handler = NodeHandler::new(client);
-
-    let update_data = read_json_data(data).context("Failed to parse update data")?;
+    let handler = NodeHandler::new(client.clone());
+
+    let update_data = match patch {
+        Some(patch) => {
+            let current = client
+                .get_raw(&format!("/v1/nodes/{}", id))
+                .await
+                .context(format!("Failed to get node {}", id))?;
+            patch_update(&current, patch)?
+        }
+        None => {
+            Some(read_json_data(data.unwrap_or_default()).context("Failed to parse update data")?)
+        }
+    };
+    let Some(update_data) = update_data else {
+        notice!("No changes to apply");
+        return Ok(());
+    };
     let updated = handler.update(id, update_data).await?;
     let updated_json = serde_json::to_value(updated).context("Failed to serialize updated node")?;
     let data = handle_output(updated_json, output_format, query)?;
@@ -122,6 +144,7 @@ pub async fn get_node_stats(
     conn_mgr: &ConnectionManager,
     profile_name: Option<&str>,
     id: u32,
+    export: Option<StatsExportFormat>,
     output_format: OutputFormat,
     query: Option<&str>,
 ) -> CliResult<()> {
@@ -129,6 +152,10 @@ pub async fn get_node_stats(
     let handler = NodeHandler::new(client);
     let stats = handler.stats(id).await?;
     let stats_json = serde_json::to_value(stats).context("Failed to serialize stats")?;
+    if let Some(format) = export {
+        print!("{}", stats_export::render(&stats_json, format));
+        return Ok(());
+    }
     let data = handle_output(stats_json, output_format, query)?;
     print_formatted_output(data, output_format)?;
     Ok(())
@@ -157,23 +184,332 @@ pub async fn get_node_metrics(
     Ok(())
 }
 
+/// Thresholds used by the node health check, as percentages
+#[derive(Debug, Clone)]
+pub struct HealthThresholds {
+    pub cpu_warn: f64,
+    pub cpu_fail: f64,
+    pub memory_warn: f64,
+    pub memory_fail: f64,
+    pub storage_warn: f64,
+    pub storage_fail: f64,
+}
+
+impl HealthThresholds {
+    /// Reject thresholds where the warning level would never be reached
+    ///
+    /// CPU and memory are graded on usage, so warn must sit below fail; storage is
+    /// graded on free space, so warn must sit above fail.
+    pub fn validate(&self) -> CliResult<()> {
+        let pairs = [
+            ("cpu", self.cpu_warn, self.cpu_fail, true),
+            ("memory", self.memory_warn, self.memory_fail, true),
+            ("storage", self.storage_warn, self.storage_fail, false),
+        ];
+        for (name, warn, fail, higher_is_worse) in pairs {
+            let ordered = if higher_is_worse {
+                warn < fail
+            } else {
+                warn > fail
+            };
+            if !ordered {
+                let relation = if higher_is_worse { "below" } else { "above" };
+                return Err(RedisCtlError::Validation {
+                    message: format!(
+                        "--{name}-warn ({warn}) must be {relation} --{name}-fail ({fail})"
+                    ),
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Outcome of a single health check
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum CheckStatus {
+    Pass,
+    Warn,
+    /// The check could not be made, so the node is not known to be healthy
+    Unknown,
+    Fail,
+}
+
+impl CheckStatus {
+    fn label(self) -> colored::ColoredString {
+        match self {
+            CheckStatus::Pass => "PASS".green(),
+            CheckStatus::Warn => "WARN".yellow(),
+            CheckStatus::Unknown => "UNKNOWN".yellow(),
+            CheckStatus::Fail => "FAIL".red(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct HealthCheck {
+    check: &'static str,
+    status: CheckStatus,
+    detail: String,
+}
+
+impl HealthCheck {
+    fn new(check: &'static str, status: CheckStatus, detail: impl Into<String>) -> Self {
+        Self {
+            check,
+            status,
+            detail: detail.into(),
+        }
+    }
+}
+
+/// Grade a usage percentage where higher is worse
+fn grade_usage(check: &'static str, used: f64, warn: f64, fail: f64) -> HealthCheck {
+    let status = if used >= fail {
+        CheckStatus::Fail
+    } else if used >= warn {
+        CheckStatus::Warn
+    } else {
+        CheckStatus::Pass
+    };
+    HealthCheck::new(check, status, format!("{:.1}% used", used))
+}
+
+/// Grade a free-space percentage where lower is worse
+fn grade_free(check: &'static str, free: f64, warn: f64, fail: f64) -> HealthCheck {
+    let status = if free < fail {
+        CheckStatus::Fail
+    } else if free < warn {
+        CheckStatus::Warn
+    } else {
+        CheckStatus::Pass
+    };
+    HealthCheck::new(check, status, format!("{:.1}% free", free))
+}
+
 pub async fn check_node_health(
     conn_mgr: &ConnectionManager,
     profile_name: Option<&str>,
     id: u32,
+    thresholds: &HealthThresholds,
     output_format: OutputFormat,
     query: Option<&str>,
 ) -> CliResult<()> {
+    thresholds.validate()?;
+
     let client = conn_mgr.create_enterprise_client(profile_name).await?;
+    let handler = NodeHandler::new(client.clone());
+
+    let node = handler
+        .get(id)
+        .await
+        .context(format!("Failed to get node {}", id))?;
+
+    // Latest stats are keyed by node UID; fall back to an unkeyed object
+    let stats = client
+        .get_raw(&format!("/v1/nodes/stats/last/{}", id))
+        .await
+        .ok()
+        .map(|s| s.get(id.to_string()).cloned().unwrap_or(s));
+    let stat = |name: &str| {
+        stats
+            .as_ref()
+            .and_then(|s| s.get(name))
+            .and_then(|v| v.as_f64())
+    };
 
-    // Health check typically combines multiple status endpoints
-    let handler = NodeHandler::new(client);
-    let status = handler.status(id).await?;
-    let data = handle_output(status, output_format, query)?;
-    print_formatted_output(data, output_format)?;
+    let mut checks = Vec::new();
+
+    checks.push(if node.status == "active" {
+        HealthCheck::new("node_status", CheckStatus::Pass, "active")
+    } else {
+        HealthCheck::new("node_status", CheckStatus::Fail, node.status.clone())
+    });
+
+    checks.push(match stat("cpu_idle") {
+        Some(idle) => grade_usage(
+            "cpu",
+            (1.0 - idle) * 100.0,
+            thresholds.cpu_warn,
+            thresholds.cpu_fail,
+        ),
+        None => HealthCheck::new("cpu", CheckStatus::Warn, "no CPU statistics available"),
+    });
+
+    checks.push(match (stat("free_memory"), node.total_memory) {
+        (Some(free), Some(total)) if total > 0 => grade_usage(
+            "memory",
+            (1.0 - free / total as f64) * 100.0,
+            thresholds.memory_warn,
+            thresholds.memory_fail,
+        ),
+        _ => HealthCheck::new(
+            "memory",
+            CheckStatus::Warn,
+            "no memory statistics available",
+        ),
+    });
+
+    checks.push(
+        match (
+            stat("persistent_storage_free"),
+            node.persistent_storage_size,
+        ) {
+            (Some(free), Some(size)) if size > 0.0 => grade_free(
+                "persistent_storage",
+                free / size * 100.0,
+                thresholds.storage_warn,
+                thresholds.storage_fail,
+            ),
+            _ => HealthCheck::new(
+                "persistent_storage",
+                CheckStatus::Warn,
+                "no storage statistics available",
+            ),
+        },
+    );
+
+    checks.push(match handler.alerts_for(id).await {
+        Ok(alerts) => {
+            let active: Vec<String> = alerts
+                .as_object()
+                .map(|alerts| {
+                    alerts
+                        .iter()
+                        .filter(|(_, alert)| {
+                            alert.get("state").and_then(|s| s.as_bool()) == Some(true)
+                        })
+                        .map(|(name, _)| name.clone())
+                        .collect()
+                })
+                .unwrap_or_default();
+            if active.is_empty() {
+                HealthCheck::new("alerts", CheckStatus::Pass, "no active alerts")
+            } else {
+                HealthCheck::new(
+                    "alerts",
+                    CheckStatus::Warn,
+                    format!("active: {}", active.join(", ")),
+                )
+            }
+        }
+        Err(e) => HealthCheck::new(
+            "alerts",
+            CheckStatus::Warn,
+            format!("unable to fetch alerts: {}", e),
+        ),
+    });
+
+    checks.push(check_node_services(client, id).await);
+
+    let overall = checks
+        .iter()
+        .map(|c| c.status)
+        .max()
+        .unwrap_or(CheckStatus::Pass);
+
+    match output_format {
+        OutputFormat::Auto | OutputFormat::Table if query.is_none() => {
+            println!("Health check for node {}:", id);
+            for check in &checks {
+                println!(
+                    "  {}  {:<20} {}",
+                    check.status.label(),
+                    check.check,
+                    check.detail
+                );
+            }
+            println!("Overall: {}", overall.label());
+        }
+        _ => {
+            let report = serde_json::json!({
+                "node": id,
+                "overall": overall,
+                "checks": checks,
+            });
+            let data = handle_output(report, output_format, query)?;
+            print_formatted_output(data, output_format)?;
+        }
+    }
+
+    if overall == CheckStatus::Fail {
+        let failed: Vec<&str> = checks
+            .iter()
+            .filter(|c| c.status == CheckStatus::Fail)
+            .map(|c| c.check)
+            .collect();
+        return Err(RedisCtlError::Validation {
+            message: format!("Node {} failed health checks: {}", id, failed.join(", ")),
+        });
+    }
     Ok(())
 }
 
+/// Check the status of every service that runs on the node
+///
+/// A service whose status cannot be read is reported as unknown rather than running.
+async fn check_node_services(client: redis_enterprise::EnterpriseClient, id: u32) -> HealthCheck {
+    let handler = ServicesHandler::new(client);
+    let services = match handler.list().await {
+        Ok(services) => services,
+        Err(e) => {
+            return HealthCheck::new(
+                "services",
+                CheckStatus::Unknown,
+                format!("unable to fetch services: {}", e),
+            );
+        }
+    };
+
+    let mut unhealthy = Vec::new();
+    let mut unknown = Vec::new();
+    for service in services
+        .iter()
+        .filter(|s| s.enabled && s.node_uids.as_ref().is_none_or(|uids| uids.contains(&id)))
+    {
+        let node_status = match handler.status(&service.service_id).await {
+            Ok(status) => status
+                .node_statuses
+                .and_then(|statuses| statuses.into_iter().find(|s| s.node_uid == id)),
+            Err(e) => {
+                unknown.push(format!("{} ({})", service.name, e));
+                continue;
+            }
+        };
+        match node_status {
+            Some(node_status)
+                if !matches!(node_status.status.as_str(), "running" | "active" | "ok") =>
+            {
+                unhealthy.push(format!("{} ({})", service.name, node_status.status));
+            }
+            Some(_) => {}
+            None => unknown.push(format!("{} (no status for this node)", service.name)),
+        }
+    }
+
+    services_check(&unhealthy, &unknown)
+}
+
+/// Grade services: any not running fails, any unread is unknown
+fn services_check(unhealthy: &[String], unknown: &[String]) -> HealthCheck {
+    let mut details = Vec::new();
+    if !unhealthy.is_empty() {
+        details.push(format!("not running: {}", unhealthy.join(", ")));
+    }
+    if !unknown.is_empty() {
+        details.push(format!("status unknown: {}", unknown.join(", ")));
+    }
+    let status = if !unhealthy.is_empty() {
+        CheckStatus::Fail
+    } else if !unknown.is_empty() {
+        CheckStatus::Unknown
+    } else {
+        return HealthCheck::new("services", CheckStatus::Pass, "all services running");
+    };
+    HealthCheck::new("services", status, details.join("; "))
+}
+
 pub async fn get_node_alerts(
     conn_mgr: &ConnectionManager,
     profile_name: Option<&str>,
@@ -268,7 +604,7 @@ pub async fn restart_node(
     query: Option<&str>,
 ) -> CliResult<()> {
     if !force && !confirm_action(&format!("Restart node {} services?", id))? {
-        println!("Operation cancelled");
+        notice!("Operation cancelled");
         return Ok(());
     }
 
@@ -322,46 +658,6 @@ pub async fn update_node_config(
     Ok(())
 }
 
-pub async fn get_node_rack(
-    conn_mgr: &ConnectionManager,
-    profile_name: Option<&str>,
-    id: u32,
-    output_format: OutputFormat,
-    query: Option<&str>,
-) -> CliResult<()> {
-    let client = conn_mgr.create_enterprise_client(profile_name).await?;
-    let handler = NodeHandler::new(client);
-
-    // Rack info is part of node details
-    let node = handler.get(id).await?;
-    let node_json = serde_json::to_value(node).context("Failed to serialize node")?;
-    let data = handle_output(node_json, output_format, query)?;
-    print_formatted_output(data, output_format)?;
-    Ok(())
-}
-
-pub async fn set_node_rack(
-    conn_mgr: &ConnectionManager,
-    profile_name: Option<&str>,
-    id: u32,
-    rack: &str,
-    output_format: OutputFormat,
-    query: Option<&str>,
-) -> CliResult<()> {
-    let client = conn_mgr.create_enterprise_client(profile_name).await?;
-    let handler = NodeHandler::new(client);
-
-    let update_data = serde_json::json!({
-        "rack_id": rack
-    });
-
-    let updated = handler.update(id, update_data).await?;
-    let updated_json = serde_json::to_value(updated).context("Failed to serialize updated node")?;
-    let data = handle_output(updated_json, output_format, query)?;
-    print_formatted_output(data, output_format)?;
-    Ok(())
-}
-
 pub async fn get_node_role(
     conn_mgr: &ConnectionManager,
     profile_name: Option<&str>,
@@ -380,94 +676,105 @@ pub async fn get_node_role(
     Ok(())
 }
 
-// Node Resources
-
-pub async fn get_node_resources(
-    conn_mgr: &ConnectionManager,
-    profile_name: Option<&str>,
-    id: u32,
-    output_format: OutputFormat,
-    query: Option<&str>,
-) -> CliResult<()> {
-    let client = conn_mgr.create_enterprise_client(profile_name).await?;
-    let handler = NodeHandler::new(client);
-
-    // Resources are typically in stats
-    let stats = handler.stats(id).await?;
-    let stats_json = serde_json::to_value(stats).context("Failed to serialize stats")?;
-    let data = handle_output(stats_json, output_format, query)?;
-    print_formatted_output(data, output_format)?;
-    Ok(())
-}
-
-pub async fn get_node_memory(
-    conn_mgr: &ConnectionManager,
-    profile_name: Option<&str>,
-    id: u32,
-    output_format: OutputFormat,
-    query: Option<&str>,
-) -> CliResult<()> {
-    let client = conn_mgr.create_enterprise_client(profile_name).await?;
-    let handler = NodeHandler::new(client);
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn defaults() -> HealthThresholds {
+        HealthThresholds {
+            cpu_warn: 80.0,
+            cpu_fail: 95.0,
+            memory_warn: 80.0,
+            memory_fail: 90.0,
+            storage_warn: 20.0,
+            storage_fail: 10.0,
+        }
+    }
 
-    // Memory details are in stats
-    let stats = handler.stats(id).await?;
-    let stats_json = serde_json::to_value(stats).context("Failed to serialize stats")?;
-    let data = handle_output(stats_json, output_format, query)?;
-    print_formatted_output(data, output_format)?;
-    Ok(())
-}
+    #[test]
+    fn test_grade_usage_boundaries() {
+        let grade = |used| grade_usage("cpu", used, 80.0, 95.0).status;
+        assert_eq!(grade(0.0), CheckStatus::Pass);
+        assert_eq!(grade(79.9), CheckStatus::Pass);
+        assert_eq!(grade(80.0), CheckStatus::Warn);
+        assert_eq!(grade(94.9), CheckStatus::Warn);
+        assert_eq!(grade(95.0), CheckStatus::Fail);
+        assert_eq!(grade(100.0), CheckStatus::Fail);
+    }
 
-pub async fn get_node_cpu(
-    conn_mgr: &ConnectionManager,
-    profile_name: Option<&str>,
-    id: u32,
-    output_format: OutputFormat,
-    query: Option<&str>,
-) -> CliResult<()> {
-    let client = conn_mgr.create_enterprise_client(profile_name).await?;
-    let handler = NodeHandler::new(client);
+    #[test]
+    fn test_grade_usage_detail() {
+        assert_eq!(
+            grade_usage("memory", 42.25, 80.0, 90.0).detail,
+            "42.2% used"
+        );
+    }
 
-    // CPU details are in stats
-    let stats = handler.stats(id).await?;
-    let stats_json = serde_json::to_value(stats).context("Failed to serialize stats")?;
-    let data = handle_output(stats_json, output_format, query)?;
-    print_formatted_output(data, output_format)?;
-    Ok(())
-}
+    #[test]
+    fn test_grade_free_boundaries() {
+        let grade = |free| grade_free("persistent_storage", free, 20.0, 10.0).status;
+        assert_eq!(grade(100.0), CheckStatus::Pass);
+        assert_eq!(grade(20.0), CheckStatus::Pass);
+        assert_eq!(grade(19.9), CheckStatus::Warn);
+        assert_eq!(grade(10.0), CheckStatus::Warn);
+        assert_eq!(grade(9.9), CheckStatus::Fail);
+        assert_eq!(grade(0.0), CheckStatus::Fail);
+    }
 
-pub async fn get_node_storage(
-    conn_mgr: &ConnectionManager,
-    profile_name: Option<&str>,
-    id: u32,
-    output_format: OutputFormat,
-    query: Option<&str>,
-) -> CliResult<()> {
-    let client = conn_mgr.create_enterprise_client(profile_name).await?;
-    let handler = NodeHandler::new(client);
+    #[test]
+    fn test_default_thresholds_are_valid() {
+        assert!(defaults().validate().is_ok());
+    }
 
-    // Storage details are in stats
-    let stats = handler.stats(id).await?;
-    let stats_json = serde_json::to_value(stats).context("Failed to serialize stats")?;
-    let data = handle_output(stats_json, output_format, query)?;
-    print_formatted_output(data, output_format)?;
-    Ok(())
-}
+    #[test]
+    fn test_rejects_warn_at_or_above_fail() {
+        for (cpu_warn, cpu_fail) in [(95.0, 95.0), (96.0, 95.0)] {
+            let thresholds = HealthThresholds {
+                cpu_warn,
+                cpu_fail,
+                ..defaults()
+            };
+            let err = thresholds.validate().unwrap_err().to_string();
+            assert!(err.contains("--cpu-warn"), "{}", err);
+        }
+
+        let thresholds = HealthThresholds {
+            memory_warn: 90.0,
+            ..defaults()
+        };
+        assert!(thresholds.validate().is_err());
+    }
 
-pub async fn get_node_network(
-    conn_mgr: &ConnectionManager,
-    profile_name: Option<&str>,
-    id: u32,
-    output_format: OutputFormat,
-    query: Option<&str>,
-) -> CliResult<()> {
-    let client = conn_mgr.create_enterprise_client(profile_name).await?;
-    let handler = NodeHandler::new(client);
+    #[test]
+    fn test_rejects_storage_warn_at_or_below_fail() {
+        for storage_warn in [10.0, 5.0] {
+            let thresholds = HealthThresholds {
+                storage_warn,
+                ..defaults()
+            };
+            let err = thresholds.validate().unwrap_err().to_string();
+            assert!(err.contains("--storage-warn"), "{}", err);
+        }
+    }
 
-    // Network stats are typically in stats
-    let stats = handler.stats(id).await?;
-    let stats_json = serde_json::to_value(stats).context("Failed to serialize stats")?;
-    let data = handle_output(stats_json, output_format, query)?;
-    print_formatted_output(data, output_format)?;
-    Ok(())
+    #[test]
+    fn test_services_check_grades_unread_status_as_unknown() {
+        assert_eq!(services_check(&[], &[]).status, CheckStatus::Pass);
+
+        let unknown = services_check(&[], &["mdns_server (timeout)".to_string()]);
+        assert_eq!(unknown.status, CheckStatus::Unknown);
+        assert_eq!(unknown.detail, "status unknown: mdns_server (timeout)");
+
+        let failed = services_check(
+            &["cm_server (stopped)".to_string()],
+            &["mdns_server (timeout)".to_string()],
+        );
+        assert_eq!(failed.status, CheckStatus::Fail);
+        assert_eq!(
+            failed.detail,
+            "not running: cm_server (stopped); status unknown: mdns_server (timeout)"
+        );
+        assert!(CheckStatus::Unknown > CheckStatus::Warn);
+        assert!(CheckStatus::Unknown < CheckStatus::Fail);
+    }
 }
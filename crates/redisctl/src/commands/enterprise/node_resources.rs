@@ -0,0 +1,499 @@
+//! Resource views of a node: CPU, memory, storage and network
+//!
+//! Combines the node's configuration (cores, total memory, storage paths and sizes) with its
+//! latest stats interval, so each view shows usage against capacity instead of raw counters.
+
+#![allow(dead_code)]
+
+use super::utils::*;
+use crate::cli::OutputFormat;
+use crate::connection::ConnectionManager;
+use crate::error::Result as CliResult;
+use crate::units::format_bytes;
+use anyhow::Context;
+use redis_enterprise::Node;
+use redis_enterprise::nodes::NodeHandler;
+use serde::Serialize;
+use serde_json::Value;
+use tabled::{Table, Tabled, settings::Style};
+
+/// Which part of the node's resources to show
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResourceView {
+    Summary,
+    Memory,
+    Cpu,
+    Storage,
+    Network,
+}
+
+#[derive(Debug, Serialize)]
+struct CpuUsage {
+    cores: Option<u32>,
+    user_percent: Option<f64>,
+    system_percent: Option<f64>,
+    iowait_percent: Option<f64>,
+    idle_percent: Option<f64>,
+    used_percent: Option<f64>,
+}
+
+#[derive(Debug, Serialize)]
+struct MemoryUsage {
+    total: Option<u64>,
+    used: Option<u64>,
+    free: Option<u64>,
+    /// Memory the node can still give to new shards
+    provisional: Option<u64>,
+    available: Option<u64>,
+    used_percent: Option<f64>,
+    /// Average fragmentation ratio of the node's shards
+    fragmentation_ratio: Option<f64>,
+}
+
+#[derive(Debug, Serialize)]
+struct StorageUsage {
+    volume: &'static str,
+    path: Option<String>,
+    size: Option<u64>,
+    used: Option<u64>,
+    free: Option<u64>,
+    /// Space still available to the cluster, after its reserved headroom
+    available: Option<u64>,
+    used_percent: Option<f64>,
+}
+
+#[derive(Debug, Serialize)]
+struct NetworkUsage {
+    addr: Option<String>,
+    external_addr: Vec<String>,
+    ingress_bytes_per_sec: Option<f64>,
+    egress_bytes_per_sec: Option<f64>,
+    connections: Option<u64>,
+    requests_per_sec: Option<f64>,
+}
+
+#[derive(Debug, Serialize)]
+struct NodeResources {
+    node: u32,
+    cpu: CpuUsage,
+    memory: MemoryUsage,
+    storage: Vec<StorageUsage>,
+    network: NetworkUsage,
+}
+
+/// Show one view of a node's resources
+pub async fn show_node_resources(
+    conn_mgr: &ConnectionManager,
+    profile_name: Option<&str>,
+    id: u32,
+    view: ResourceView,
+    output_format: OutputFormat,
+    query: Option<&str>,
+) -> CliResult<()> {
+    let client = conn_mgr.create_enterprise_client(profile_name).await?;
+    let node = NodeHandler::new(client.clone())
+        .get(id)
+        .await
+        .context(format!("Failed to get node {}", id))?;
+
+    // Latest stats are keyed by node UID; fall back to an unkeyed object
+    let stats = client
+        .get_raw(&format!("/v1/nodes/stats/last/{}", id))
+        .await
+        .context(format!("Failed to get stats for node {}", id))?;
+    let stats = stats.get(id.to_string()).cloned().unwrap_or(stats);
+
+    // Fragmentation is only reported per shard; a node without shards simply has none
+    let shard_stats = if view == ResourceView::Memory && node.shard_list.is_some() {
+        client.get_raw("/v1/shards/stats/last").await.ok()
+    } else {
+        None
+    };
+
+    let resources = node_resources(&node, &stats, shard_stats.as_ref());
+
+    if matches!(output_format, OutputFormat::Auto | OutputFormat::Table) && query.is_none() {
+        match view {
+            ResourceView::Summary => print_summary(&resources),
+            ResourceView::Memory => print_pairs(memory_pairs(&resources.memory)),
+            ResourceView::Cpu => print_pairs(cpu_pairs(&resources.cpu)),
+            ResourceView::Storage => print_storage(&resources.storage),
+            ResourceView::Network => print_pairs(network_pairs(&resources.network)),
+        }
+        return Ok(());
+    }
+
+    let json = match view {
+        ResourceView::Summary => serde_json::to_value(&resources),
+        ResourceView::Memory => serde_json::to_value(&resources.memory),
+        ResourceView::Cpu => serde_json::to_value(&resources.cpu),
+        ResourceView::Storage => serde_json::to_value(&resources.storage),
+        ResourceView::Network => serde_json::to_value(&resources.network),
+    }
+    .context("Failed to serialize node resources")?;
+    let data = handle_output(json, output_format, query)?;
+    print_formatted_output(data, output_format)?;
+    Ok(())
+}
+
+fn stat(stats: &Value, name: &str) -> Option<f64> {
+    match stats.get(name)? {
+        Value::Number(n) => n.as_f64(),
+        Value::String(s) => s.parse().ok(),
+        _ => None,
+    }
+}
+
+fn percent(part: f64, whole: f64) -> Option<f64> {
+    (whole > 0.0).then(|| part / whole * 100.0)
+}
+
+fn node_resources(node: &Node, stats: &Value, shard_stats: Option<&Value>) -> NodeResources {
+    let cpu_percent = |name: &str| stat(stats, name).map(|v| v * 100.0);
+    let cpu = CpuUsage {
+        cores: node.cores,
+        user_percent: cpu_percent("cpu_user"),
+        system_percent: cpu_percent("cpu_system"),
+        iowait_percent: cpu_percent("cpu_iowait"),
+        idle_percent: cpu_percent("cpu_idle"),
+        used_percent: stat(stats, "cpu_idle").map(|idle| (1.0 - idle) * 100.0),
+    };
+
+    let free_memory = stat(stats, "free_memory").map(|v| v as u64);
+    let used_memory = match (node.total_memory, free_memory) {
+        (Some(total), Some(free)) => Some(total.saturating_sub(free)),
+        _ => None,
+    };
+    let memory = MemoryUsage {
+        total: node.total_memory,
+        used: used_memory,
+        free: free_memory,
+        provisional: stat(stats, "provisional_memory").map(|v| v as u64),
+        available: stat(stats, "available_memory").map(|v| v as u64),
+        used_percent: match (used_memory, node.total_memory) {
+            (Some(used), Some(total)) => percent(used as f64, total as f64),
+            _ => None,
+        },
+        fragmentation_ratio: shard_stats
+            .and_then(|shards| fragmentation_ratio(shards, node.shard_list.as_deref()?)),
+    };
+
+    let storage = [
+        (
+            "persistent",
+            node.persistent_storage_path.clone(),
+            node.persistent_storage_size,
+        ),
+        (
+            "ephemeral",
+            node.ephemeral_storage_path.clone(),
+            node.ephemeral_storage_size,
+        ),
+    ]
+    .into_iter()
+    .map(|(volume, path, size)| {
+        let free = stat(stats, &format!("{}_storage_free", volume));
+        let used = match (size, free) {
+            (Some(size), Some(free)) => Some((size - free).max(0.0)),
+            _ => None,
+        };
+        StorageUsage {
+            volume,
+            path,
+            size: size.map(|v| v as u64),
+            used: used.map(|v| v as u64),
+            free: free.map(|v| v as u64),
+            available: stat(stats, &format!("{}_storage_avail", volume)).map(|v| v as u64),
+            used_percent: match (used, size) {
+                (Some(used), Some(size)) => percent(used, size),
+                _ => None,
+            },
+        }
+    })
+    .collect();
+
+    let network = NetworkUsage {
+        addr: node.addr.clone(),
+        external_addr: node.external_addr.clone().unwrap_or_default(),
+        ingress_bytes_per_sec: stat(stats, "ingress_bytes"),
+        egress_bytes_per_sec: stat(stats, "egress_bytes"),
+        connections: stat(stats, "conns").map(|v| v as u64),
+        requests_per_sec: stat(stats, "total_req"),
+    };
+
+    NodeResources {
+        node: node.uid,
+        cpu,
+        memory,
+        storage,
+        network,
+    }
+}
+
+/// Average fragmentation ratio over the shards of a node, from `/v1/shards/stats/last`
+fn fragmentation_ratio(shard_stats: &Value, shard_uids: &[u32]) -> Option<f64> {
+    let ratios: Vec<f64> = shard_uids
+        .iter()
+        .filter_map(|uid| shard_stats.get(uid.to_string()))
+        .filter_map(|shard| {
+            stat(shard, "mem_frag_ratio").or_else(|| stat(shard, "mem_fragmentation_ratio"))
+        })
+        .collect();
+    (!ratios.is_empty()).then(|| ratios.iter().sum::<f64>() / ratios.len() as f64)
+}
+
+fn bytes(value: Option<u64>) -> String {
+    value.map(format_bytes).unwrap_or_else(|| "-".to_string())
+}
+
+fn rate(value: Option<f64>) -> String {
+    value
+        .map(|v| format!("{}/s", format_bytes(v as u64)))
+        .unwrap_or_else(|| "-".to_string())
+}
+
+fn pct(value: Option<f64>) -> String {
+    value
+        .map(|v| format!("{:.1}%", v))
+        .unwrap_or_else(|| "-".to_string())
+}
+
+fn cpu_pairs(cpu: &CpuUsage) -> Vec<(&'static str, String)> {
+    vec![
+        (
+            "Cores",
+            cpu.cores
+                .map(|c| c.to_string())
+                .unwrap_or_else(|| "-".to_string()),
+        ),
+        ("Used", pct(cpu.used_percent)),
+        ("User", pct(cpu.user_percent)),
+        ("System", pct(cpu.system_percent)),
+        ("I/O wait", pct(cpu.iowait_percent)),
+        ("Idle", pct(cpu.idle_percent)),
+    ]
+}
+
+fn memory_pairs(memory: &MemoryUsage) -> Vec<(&'static str, String)> {
+    vec![
+        ("Total", bytes(memory.total)),
+        (
+            "Used",
+            format!("{} ({})", bytes(memory.used), pct(memory.used_percent)),
+        ),
+        ("Free", bytes(memory.free)),
+        ("Provisional", bytes(memory.provisional)),
+        ("Available", bytes(memory.available)),
+        (
+            "Fragmentation",
+            memory
+                .fragmentation_ratio
+                .map(|r| format!("{:.2}", r))
+                .unwrap_or_else(|| "-".to_string()),
+        ),
+    ]
+}
+
+fn network_pairs(network: &NetworkUsage) -> Vec<(&'static str, String)> {
+    vec![
+        (
+            "Address",
+            network.addr.clone().unwrap_or_else(|| "-".to_string()),
+        ),
+        (
+            "External",
+            if network.external_addr.is_empty() {
+                "-".to_string()
+            } else {
+                network.external_addr.join(", ")
+            },
+        ),
+        ("Ingress", rate(network.ingress_bytes_per_sec)),
+        ("Egress", rate(network.egress_bytes_per_sec)),
+        (
+            "Connections",
+            network
+                .connections
+                .map(|c| c.to_string())
+                .unwrap_or_else(|| "-".to_string()),
+        ),
+        (
+            "Requests",
+            network
+                .requests_per_sec
+                .map(|r| format!("{:.0}/s", r))
+                .unwrap_or_else(|| "-".to_string()),
+        ),
+    ]
+}
+
+#[derive(Tabled)]
+struct PairRow {
+    #[tabled(rename = "METRIC")]
+    metric: &'static str,
+    #[tabled(rename = "VALUE")]
+    value: String,
+}
+
+fn print_pairs(pairs: Vec<(&'static str, String)>) {
+    let rows: Vec<PairRow> = pairs
+        .into_iter()
+        .map(|(metric, value)| PairRow { metric, value })
+        .collect();
+    let mut table = Table::new(rows);
+    table.with(Style::blank());
+    println!("{}", table);
+}
+
+#[derive(Tabled)]
+struct StorageRow {
+    #[tabled(rename = "VOLUME")]
+    volume: &'static str,
+    #[tabled(rename = "PATH")]
+    path: String,
+    #[tabled(rename = "SIZE")]
+    size: String,
+    #[tabled(rename = "USED")]
+    used: String,
+    #[tabled(rename = "FREE")]
+    free: String,
+    #[tabled(rename = "USAGE")]
+    usage: String,
+}
+
+fn print_storage(storage: &[StorageUsage]) {
+    let rows: Vec<StorageRow> = storage
+        .iter()
+        .map(|volume| StorageRow {
+            volume: volume.volume,
+            path: volume.path.clone().unwrap_or_else(|| "-".to_string()),
+            size: bytes(volume.size),
+            used: bytes(volume.used),
+            free: bytes(volume.free),
+            usage: pct(volume.used_percent),
+        })
+        .collect();
+    let mut table = Table::new(rows);
+    table.with(Style::blank());
+    println!("{}", table);
+}
+
+#[derive(Tabled)]
+struct SummaryRow {
+    #[tabled(rename = "RESOURCE")]
+    resource: String,
+    #[tabled(rename = "USED")]
+    used: String,
+    #[tabled(rename = "TOTAL")]
+    total: String,
+    #[tabled(rename = "USAGE")]
+    usage: String,
+}
+
+fn print_summary(resources: &NodeResources) {
+    let mut rows = vec![
+        SummaryRow {
+            resource: "cpu".to_string(),
+            used: match (resources.cpu.cores, resources.cpu.used_percent) {
+                (Some(cores), Some(used)) => format!("{:.1} cores", cores as f64 * used / 100.0),
+                _ => "-".to_string(),
+            },
+            total: resources
+                .cpu
+                .cores
+                .map(|c| format!("{} cores", c))
+                .unwrap_or_else(|| "-".to_string()),
+            usage: pct(resources.cpu.used_percent),
+        },
+        SummaryRow {
+            resource: "memory".to_string(),
+            used: bytes(resources.memory.used),
+            total: bytes(resources.memory.total),
+            usage: pct(resources.memory.used_percent),
+        },
+    ];
+    rows.extend(resources.storage.iter().map(|volume| SummaryRow {
+        resource: format!("{} storage", volume.volume),
+        used: bytes(volume.used),
+        total: bytes(volume.size),
+        usage: pct(volume.used_percent),
+    }));
+    let mut table = Table::new(rows);
+    table.with(Style::blank());
+    println!("{}", table);
+
+    let network = &resources.network;
+    println!(
+        "\nNetwork: {} in, {} out, {} connections",
+        rate(network.ingress_bytes_per_sec),
+        rate(network.egress_bytes_per_sec),
+        network
+            .connections
+            .map(|c| c.to_string())
+            .unwrap_or_else(|| "-".to_string())
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn node() -> Node {
+        serde_json::from_value(json!({
+            "uid": 2,
+            "status": "active",
+            "addr": "10.0.0.2",
+            "cores": 4,
+            "total_memory": 8_000_000_000u64,
+            "persistent_storage_path": "/var/opt/redislabs/persist",
+            "persistent_storage_size": 100_000_000_000.0,
+            "ephemeral_storage_path": "/var/opt/redislabs/tmp",
+            "ephemeral_storage_size": 50_000_000_000.0,
+            "shard_list": [3, 4]
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_node_resources() {
+        let stats = json!({
+            "cpu_user": 0.2, "cpu_system": 0.05, "cpu_iowait": 0.0, "cpu_idle": 0.75,
+            "free_memory": 2_000_000_000u64, "provisional_memory": 1_500_000_000u64,
+            "persistent_storage_free": "25000000000", "ephemeral_storage_free": 50_000_000_000.0,
+            "ingress_bytes": 2048.0, "egress_bytes": 4096.0, "conns": 12
+        });
+        let shards = json!({
+            "3": {"mem_frag_ratio": 1.2},
+            "4": {"mem_frag_ratio": 1.4},
+            "9": {"mem_frag_ratio": 9.0}
+        });
+        let resources = node_resources(&node(), &stats, Some(&shards));
+
+        assert_eq!(resources.cpu.cores, Some(4));
+        assert_eq!(resources.cpu.used_percent, Some(25.0));
+        assert_eq!(resources.memory.used, Some(6_000_000_000));
+        assert_eq!(resources.memory.used_percent, Some(75.0));
+        assert_eq!(resources.memory.provisional, Some(1_500_000_000));
+        assert!((resources.memory.fragmentation_ratio.unwrap() - 1.3).abs() < 1e-9);
+
+        let persistent = &resources.storage[0];
+        assert_eq!(persistent.volume, "persistent");
+        assert_eq!(persistent.used, Some(75_000_000_000));
+        assert_eq!(persistent.used_percent, Some(75.0));
+        assert_eq!(resources.storage[1].used_percent, Some(0.0));
+
+        assert_eq!(resources.network.connections, Some(12));
+        assert_eq!(resources.network.egress_bytes_per_sec, Some(4096.0));
+    }
+
+    #[test]
+    fn test_node_resources_without_stats() {
+        let resources = node_resources(&node(), &json!({}), None);
+        assert_eq!(resources.cpu.used_percent, None);
+        assert_eq!(resources.memory.used, None);
+        assert_eq!(resources.memory.fragmentation_ratio, None);
+        assert_eq!(resources.storage[0].free, None);
+        assert_eq!(pct(resources.storage[0].used_percent), "-");
+    }
+}
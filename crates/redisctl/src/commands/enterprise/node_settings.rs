@@ -0,0 +1,272 @@
+//! Typed updates of a node's rack, external addresses and recovery path
+//!
+//! These settings are plain fields of the node object. The commands here validate the values
+//! before sending them, since the cluster accepts some malformed ones (a loopback external
+//! address, a relative recovery path) and only fails later, when clients or a recovery use them.
+
+#![allow(dead_code)]
+
+use crate::cli::OutputFormat;
+use crate::connection::ConnectionManager;
+use crate::error::Result as CliResult;
+use crate::output::notice;
+use anyhow::Context;
+use redis_enterprise::nodes::{Node, NodeHandler, NodeUpdateRequest};
+use std::net::IpAddr;
+use tabled::{Table, Tabled, settings::Style};
+
+use super::utils::*;
+
+#[derive(Tabled)]
+struct RackRow {
+    #[tabled(rename = "NODE")]
+    uid: u32,
+    #[tabled(rename = "ADDRESS")]
+    addr: String,
+    #[tabled(rename = "EXTERNAL ADDRESSES")]
+    external_addr: String,
+    #[tabled(rename = "RACK")]
+    rack_id: String,
+    #[tabled(rename = "SECOND RACK")]
+    second_rack_id: String,
+}
+
+/// Parse an external address: an IPv4 or IPv6 address clients can reach
+pub fn parse_external_addr(s: &str) -> Result<String, String> {
+    let addr: IpAddr = s
+        .trim()
+        .parse()
+        .map_err(|_| format!("invalid address: {} (expected an IPv4 or IPv6 address)", s))?;
+    if addr.is_unspecified() || addr.is_loopback() || addr.is_multicast() {
+        return Err(format!(
+            "invalid address: {} (unspecified, loopback and multicast addresses are not reachable)",
+            s
+        ));
+    }
+    Ok(addr.to_string())
+}
+
+/// Parse a rack id: letters, digits and `-_.:/`, up to 63 characters
+pub fn parse_rack_id(s: &str) -> Result<String, String> {
+    if s.is_empty() || s.len() > 63 {
+        return Err(format!(
+            "invalid rack id: '{}' (must be 1 to 63 characters)",
+            s
+        ));
+    }
+    if let Some(c) = s
+        .chars()
+        .find(|c| !(c.is_ascii_alphanumeric() || "-_.:/".contains(*c)))
+    {
+        return Err(format!(
+            "invalid rack id: '{}' (character '{}' is not allowed)",
+            s, c
+        ));
+    }
+    Ok(s.to_string())
+}
+
+/// Parse a recovery path: an absolute path without `..` components
+pub fn parse_recovery_path(s: &str) -> Result<String, String> {
+    if !s.starts_with('/') {
+        return Err(format!("invalid path: {} (must be absolute)", s));
+    }
+    if s.split('/').any(|part| part == "..") {
+        return Err(format!("invalid path: {} (must not contain '..')", s));
+    }
+    let trimmed = s.trim_end_matches('/');
+    Ok(if trimmed.is_empty() { "/" } else { trimmed }.to_string())
+}
+
+/// Show the rack placement of a node
+pub async fn get_node_rack(
+    conn_mgr: &ConnectionManager,
+    profile_name: Option<&str>,
+    id: u32,
+    output_format: OutputFormat,
+    query: Option<&str>,
+) -> CliResult<()> {
+    let client = conn_mgr.create_enterprise_client(profile_name).await?;
+    let node = NodeHandler::new(client)
+        .get(id)
+        .await
+        .context(format!("Failed to get node {}", id))?;
+    print_placement(&node, output_format, query)
+}
+
+/// Move a node to another rack
+pub async fn set_node_rack(
+    conn_mgr: &ConnectionManager,
+    profile_name: Option<&str>,
+    id: u32,
+    rack: &str,
+    second_rack: Option<&str>,
+    output_format: OutputFormat,
+    query: Option<&str>,
+) -> CliResult<()> {
+    let request = NodeUpdateRequest {
+        rack_id: Some(rack.to_string()),
+        second_rack_id: second_rack.map(str::to_string),
+        ..Default::default()
+    };
+    let node = update_node(conn_mgr, profile_name, id, &request).await?;
+    notice!("Node {} is now in rack {}", id, rack);
+    print_placement(&node, output_format, query)
+}
+
+/// Replace the external addresses of a node
+pub async fn set_external_addr(
+    conn_mgr: &ConnectionManager,
+    profile_name: Option<&str>,
+    id: u32,
+    addrs: &[String],
+    output_format: OutputFormat,
+    query: Option<&str>,
+) -> CliResult<()> {
+    let mut unique: Vec<String> = Vec::new();
+    for addr in addrs {
+        if !unique.contains(addr) {
+            unique.push(addr.clone());
+        }
+    }
+    let addrs = unique;
+    let request = NodeUpdateRequest {
+        external_addr: Some(addrs.clone()),
+        ..Default::default()
+    };
+    let node = update_node(conn_mgr, profile_name, id, &request).await?;
+    if addrs.is_empty() {
+        notice!("Removed the external addresses of node {}", id);
+    } else {
+        notice!("Node {} external addresses: {}", id, addrs.join(", "));
+    }
+    print_placement(&node, output_format, query)
+}
+
+/// Change where a node keeps its recovery files
+pub async fn set_recovery_path(
+    conn_mgr: &ConnectionManager,
+    profile_name: Option<&str>,
+    id: u32,
+    path: &str,
+    output_format: OutputFormat,
+    query: Option<&str>,
+) -> CliResult<()> {
+    let request = NodeUpdateRequest {
+        recovery_path: Some(path.to_string()),
+        ..Default::default()
+    };
+    let node = update_node(conn_mgr, profile_name, id, &request).await?;
+    match output_format {
+        OutputFormat::Auto | OutputFormat::Table if query.is_none() => {
+            notice!("Node {} recovery path: {}", id, path);
+            Ok(())
+        }
+        _ => {
+            let json = serde_json::to_value(node).context("Failed to serialize node")?;
+            let data = handle_output(json, output_format, query)?;
+            print_formatted_output(data, output_format)
+        }
+    }
+}
+
+async fn update_node(
+    conn_mgr: &ConnectionManager,
+    profile_name: Option<&str>,
+    id: u32,
+    request: &NodeUpdateRequest,
+) -> CliResult<Node> {
+    let client = conn_mgr.create_enterprise_client(profile_name).await?;
+    Ok(NodeHandler::new(client)
+        .update_settings(id, request)
+        .await
+        .context(format!("Failed to update node {}", id))?)
+}
+
+fn print_placement(node: &Node, output_format: OutputFormat, query: Option<&str>) -> CliResult<()> {
+    match output_format {
+        OutputFormat::Auto | OutputFormat::Table if query.is_none() => {
+            let mut table = Table::new([placement_row(node)]);
+            table.with(Style::blank());
+            println!("{}", table);
+            Ok(())
+        }
+        _ => {
+            let json = serde_json::to_value(node).context("Failed to serialize node")?;
+            let data = handle_output(json, output_format, query)?;
+            print_formatted_output(data, output_format)
+        }
+    }
+}
+
+fn placement_row(node: &Node) -> RackRow {
+    let or_dash = |value: Option<&String>| value.cloned().unwrap_or_else(|| "-".to_string());
+    RackRow {
+        uid: node.uid,
+        addr: or_dash(node.addr.as_ref()),
+        external_addr: node
+            .external_addr
+            .as_ref()
+            .filter(|addrs| !addrs.is_empty())
+            .map_or_else(|| "-".to_string(), |addrs| addrs.join(", ")),
+        rack_id: or_dash(node.rack_id.as_ref()),
+        second_rack_id: or_dash(node.second_rack_id.as_ref()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_external_addr() {
+        assert_eq!(parse_external_addr("203.0.113.5").unwrap(), "203.0.113.5");
+        assert_eq!(parse_external_addr("2001:db8::1").unwrap(), "2001:db8::1");
+        assert!(parse_external_addr("203.0.113").is_err());
+        assert!(parse_external_addr("node1.example.com").is_err());
+        assert!(
+            parse_external_addr("127.0.0.1")
+                .unwrap_err()
+                .contains("loopback")
+        );
+        assert!(parse_external_addr("0.0.0.0").is_err());
+        assert!(parse_external_addr("::1").is_err());
+    }
+
+    #[test]
+    fn test_parse_rack_id() {
+        assert_eq!(parse_rack_id("us-east-1a").unwrap(), "us-east-1a");
+        assert!(parse_rack_id("zone/rack_2.b:3").is_ok());
+        assert!(parse_rack_id("").is_err());
+        assert!(parse_rack_id("rack 1").unwrap_err().contains("' '"));
+        assert!(parse_rack_id(&"r".repeat(64)).is_err());
+    }
+
+    #[test]
+    fn test_parse_recovery_path() {
+        assert_eq!(
+            parse_recovery_path("/var/opt/redislabs/recovery/").unwrap(),
+            "/var/opt/redislabs/recovery"
+        );
+        assert_eq!(parse_recovery_path("/").unwrap(), "/");
+        assert!(parse_recovery_path("recovery").is_err());
+        assert!(parse_recovery_path("/var/../etc").is_err());
+    }
+
+    #[test]
+    fn test_placement_row() {
+        let node: Node = serde_json::from_value(json!({
+            "uid": 2,
+            "addr": "10.0.0.2",
+            "status": "active",
+            "external_addr": ["203.0.113.5", "203.0.113.6"],
+            "rack_id": "us-east-1a"
+        }))
+        .unwrap();
+        let row = placement_row(&node);
+        assert_eq!(row.external_addr, "203.0.113.5, 203.0.113.6");
+        assert_eq!(row.rack_id, "us-east-1a");
+        assert_eq!(row.second_rack_id, "-");
+    }
+}
@@ -0,0 +1,404 @@
+//! OCSP stapling diagnostics for Enterprise
+//!
+//! The proxy staples the OCSP response for its certificate, queried from the responder named in
+//! the certificate's Authority Information Access extension. The request identifies the
+//! certificate by its issuer, so the issuer must be part of the proxy chain. `inspect` shows the
+//! chain, the responder that will be queried and the cluster's OCSP configuration and status,
+//! and points out the combinations that keep stapling from working.
+
+#![allow(dead_code)]
+
+use super::utils::*;
+use crate::certificate::{self, CertificateInfo};
+use crate::cli::{EnterpriseOcspCommands, OutputFormat};
+use crate::connection::ConnectionManager;
+use crate::error::{RedisCtlError, Result as CliResult};
+use anyhow::Context;
+use chrono::Utc;
+use redis_enterprise::ClusterHandler;
+use redis_enterprise::ocsp::{OcspConfig, OcspHandler, OcspStatus};
+use serde::Serialize;
+use serde_json::Value;
+use tabled::{Table, Tabled, settings::Style};
+use x509_parser::extensions::{GeneralName, ParsedExtension};
+use x509_parser::oid_registry::OID_PKIX_ACCESS_DESCRIPTOR_OCSP;
+use x509_parser::pem::Pem;
+
+pub async fn handle_ocsp_command(
+    conn_mgr: &ConnectionManager,
+    profile_name: Option<&str>,
+    command: &EnterpriseOcspCommands,
+    output_format: OutputFormat,
+    query: Option<&str>,
+) -> CliResult<()> {
+    match command {
+        EnterpriseOcspCommands::Inspect => {
+            inspect(conn_mgr, profile_name, output_format, query).await
+        }
+    }
+}
+
+/// Everything that decides whether OCSP stapling works
+#[derive(Debug, Clone, Serialize)]
+pub struct OcspInspection {
+    /// The proxy certificate chain, leaf first
+    pub chain: Vec<CertificateInfo>,
+    /// Responder named in the proxy certificate
+    pub responder_url: Option<String>,
+    pub config: Option<OcspConfig>,
+    pub status: Option<OcspStatus>,
+    pub problems: Vec<String>,
+    pub warnings: Vec<String>,
+}
+
+#[derive(Tabled)]
+struct FieldRow {
+    #[tabled(rename = "FIELD")]
+    field: String,
+    #[tabled(rename = "VALUE")]
+    value: String,
+}
+
+async fn inspect(
+    conn_mgr: &ConnectionManager,
+    profile_name: Option<&str>,
+    output_format: OutputFormat,
+    query: Option<&str>,
+) -> CliResult<()> {
+    let client = conn_mgr.create_enterprise_client(profile_name).await?;
+
+    let certificates = ClusterHandler::new(client.clone())
+        .certificates()
+        .await
+        .context("Failed to get cluster certificates")?;
+    let pem = proxy_certificate(&certificates)
+        .map(|pem| certificate::normalize_pem(&pem))
+        .ok_or_else(|| RedisCtlError::api("The cluster returned no proxy certificate"))?;
+    let chain = certificate::parse_chain(&pem, Utc::now().timestamp()).map_err(|message| {
+        RedisCtlError::api(format!(
+            "Could not parse the proxy certificate: {}",
+            message
+        ))
+    })?;
+
+    // Status fails while OCSP is disabled or has never queried; that is reported, not fatal
+    let handler = OcspHandler::new(client);
+    let config = handler.get_config().await;
+    let status = handler.get_status().await;
+
+    let mut inspection = OcspInspection {
+        responder_url: ocsp_responder_url(&pem),
+        chain,
+        config: config.as_ref().ok().cloned(),
+        status: status.as_ref().ok().cloned(),
+        problems: Vec::new(),
+        warnings: Vec::new(),
+    };
+    if let Err(e) = &config {
+        inspection
+            .warnings
+            .push(format!("could not read the OCSP configuration: {}", e));
+    }
+    if let Err(e) = &status {
+        inspection
+            .warnings
+            .push(format!("could not read the OCSP status: {}", e));
+    }
+    diagnose(&mut inspection);
+
+    match output_format {
+        OutputFormat::Auto | OutputFormat::Table if query.is_none() => {
+            print_inspection(&inspection);
+        }
+        _ => {
+            let json =
+                serde_json::to_value(&inspection).context("Failed to serialize OCSP inspection")?;
+            let data = handle_output(json, output_format, query)?;
+            print_formatted_output(data, output_format)?;
+        }
+    }
+    Ok(())
+}
+
+/// The proxy certificate from the certificates response
+///
+/// Older clusters return an object with a `proxy_cert` field, newer ones a list of named
+/// certificates.
+pub fn proxy_certificate(certificates: &Value) -> Option<String> {
+    let pem = match certificates {
+        Value::Array(entries) => entries
+            .iter()
+            .find(|entry| entry.get("name").and_then(Value::as_str) == Some("proxy"))
+            .and_then(|entry| entry.get("certificate").or_else(|| entry.get("cert"))),
+        _ => certificates.get("proxy_cert"),
+    };
+    pem.and_then(Value::as_str)
+        .filter(|pem| !pem.trim().is_empty())
+        .map(str::to_string)
+}
+
+/// OCSP responder URL from the Authority Information Access extension of the first certificate
+pub fn ocsp_responder_url(pem: &str) -> Option<String> {
+    let block = Pem::iter_from_buffer(pem.as_bytes())
+        .filter_map(Result::ok)
+        .find(|block| block.label == "CERTIFICATE")?;
+    let cert = block.parse_x509().ok()?;
+    cert.extensions()
+        .iter()
+        .find_map(|extension| match extension.parsed_extension() {
+            ParsedExtension::AuthorityInfoAccess(aia) => aia
+                .accessdescs
+                .iter()
+                .filter(|desc| desc.access_method == OID_PKIX_ACCESS_DESCRIPTOR_OCSP)
+                .find_map(|desc| match &desc.access_location {
+                    GeneralName::URI(uri) => Some(uri.to_string()),
+                    _ => None,
+                }),
+            _ => None,
+        })
+}
+
+/// Record why stapling would fail (problems) or deserves a look (warnings)
+pub fn diagnose(inspection: &mut OcspInspection) {
+    let mut problems = Vec::new();
+    let mut warnings = Vec::new();
+
+    if let Some(config) = &inspection.config {
+        if !config.enabled {
+            problems.push(
+                "OCSP is disabled; enable it with `redisctl enterprise cluster update-ocsp`"
+                    .to_string(),
+            );
+        }
+        if let (Some(configured), Some(from_cert)) =
+            (&config.responder_url, &inspection.responder_url)
+            && configured != from_cert
+        {
+            warnings.push(format!(
+                "the cluster reports responder {}, but the certificate names {}",
+                configured, from_cert
+            ));
+        }
+    }
+
+    if let Some(leaf) = inspection.chain.first() {
+        if leaf.self_signed {
+            problems.push(
+                "the proxy certificate is self-signed; there is no issuer to ask for its status"
+                    .to_string(),
+            );
+        } else if inspection.responder_url.is_none() {
+            problems.push(
+                "the proxy certificate names no OCSP responder (Authority Information Access)"
+                    .to_string(),
+            );
+        }
+        if !leaf.self_signed && inspection.chain.len() < 2 {
+            problems.push(format!(
+                "the issuer ({}) is missing from the proxy certificate chain; the OCSP request \
+                 is built from the issuer's name and key, so upload the certificate with its chain",
+                leaf.issuer
+            ));
+        } else if let Some(issuer) = inspection.chain.get(1)
+            && issuer.subject != leaf.issuer
+        {
+            problems.push(format!(
+                "certificate 2 ({}) did not issue the proxy certificate (issuer {})",
+                issuer.subject, leaf.issuer
+            ));
+        }
+        if leaf.status == "expired" {
+            problems.push(format!(
+                "the proxy certificate expired on {}",
+                leaf.not_after
+            ));
+        }
+    }
+
+    if let Some(status) = &inspection.status {
+        match status.certificate_status.as_deref() {
+            Some(s) if s.eq_ignore_ascii_case("revoked") => problems.push(format!(
+                "the responder reports the certificate revoked{}",
+                status
+                    .revocation_time
+                    .as_deref()
+                    .map(|time| format!(" at {}", time))
+                    .unwrap_or_default()
+            )),
+            Some(s) if s.eq_ignore_ascii_case("unknown") => warnings.push(
+                "the responder does not know the certificate; check that it is the issuer's responder"
+                    .to_string(),
+            ),
+            _ => {}
+        }
+    }
+
+    inspection.problems.extend(problems);
+    inspection.warnings.extend(warnings);
+}
+
+fn print_inspection(inspection: &OcspInspection) {
+    println!("{}", certificate::chain_table(&inspection.chain));
+    println!();
+
+    let optional = |value: Option<&str>| value.unwrap_or("-").to_string();
+    let mut rows = vec![
+        FieldRow {
+            field: "Serial".to_string(),
+            value: inspection
+                .chain
+                .first()
+                .map(|leaf| leaf.serial.clone())
+                .unwrap_or_default(),
+        },
+        FieldRow {
+            field: "Responder".to_string(),
+            value: optional(inspection.responder_url.as_deref()),
+        },
+    ];
+    if let Some(config) = &inspection.config {
+        rows.push(FieldRow {
+            field: "OCSP enabled".to_string(),
+            value: config.enabled.to_string(),
+        });
+    }
+    if let Some(status) = &inspection.status {
+        rows.push(FieldRow {
+            field: "Status".to_string(),
+            value: status.status.clone(),
+        });
+        rows.push(FieldRow {
+            field: "Certificate status".to_string(),
+            value: optional(status.certificate_status.as_deref()),
+        });
+        rows.push(FieldRow {
+            field: "Last update".to_string(),
+            value: optional(status.last_update.as_deref()),
+        });
+        rows.push(FieldRow {
+            field: "Next update".to_string(),
+            value: optional(status.next_update.as_deref()),
+        });
+    }
+    let mut table = Table::new(rows);
+    table.with(Style::blank());
+    println!("{}", table);
+
+    for warning in &inspection.warnings {
+        println!("Warning: {}", warning);
+    }
+    for problem in &inspection.problems {
+        println!("Problem: {}", problem);
+    }
+    if inspection.problems.is_empty() {
+        println!("No problems found");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    /// `proxy.cluster.local`, issued by `Test Root CA`, with OCSP responder http://ocsp.example.com
+    const LEAF: &str = "-----BEGIN CERTIFICATE-----
+MIIBqjCCAVGgAwIBAgICEJIwCgYIKoZIzj0EAwIwFzEVMBMGA1UEAwwMVGVzdCBS
+b290IENBMCAXDTI2MTAxNjIzMDk1NFoYDzIxMjYwOTIyMjMwOTU0WjAeMRwwGgYD
+VQQDDBNwcm94eS5jbHVzdGVyLmxvY2FsMFkwEwYHKoZIzj0CAQYIKoZIzj0DAQcD
+QgAEnR40+IuDDfd3emeEkOc9ywEjkJViWkiMMd5lrufcHTOI+TomhR576qXbb8dw
+yiAgRLJHJq5BiilRxY69DUJ8vqOBgzCBgDAzBggrBgEFBQcBAQQnMCUwIwYIKwYB
+BQUHMAGGF2h0dHA6Ly9vY3NwLmV4YW1wbGUuY29tMAkGA1UdEwQCMAAwHQYDVR0O
+BBYEFNGcGjIJIuC5ca5DhyLU8VTrcs+oMB8GA1UdIwQYMBaAFBSs4Ne1+tYOZohW
++pSD9/cxOqcIMAoGCCqGSM49BAMCA0cAMEQCIDusfmIrs5RB9e/BHQkFDfc3JMk1
+BRLMxirvVXKEGzgfAiBhpMKd01QlHaH8cAaaBJbvIeQ1yQoPpJTjBxbGcuWX+Q==
+-----END CERTIFICATE-----
+";
+
+    const ROOT: &str = "-----BEGIN CERTIFICATE-----
+MIIBhTCCASugAwIBAgIUSB1HC5kgsmPNhRaQkzShKLVqCtUwCgYIKoZIzj0EAwIw
+FzEVMBMGA1UEAwwMVGVzdCBSb290IENBMCAXDTI2MTAxNjIzMDk1M1oYDzIxMjYw
+OTIyMjMwOTUzWjAXMRUwEwYDVQQDDAxUZXN0IFJvb3QgQ0EwWTATBgcqhkjOPQIB
+BggqhkjOPQMBBwNCAAS768DvuUPefxR+1BONMF4+4ORqMCsd+/02x5K6rjJhZoD8
+V8YmPuoHgKH+CLshfuzej848OI7jBHXu3lzos6aso1MwUTAdBgNVHQ4EFgQUFKzg
+17X61g5miFb6lIP39zE6pwgwHwYDVR0jBBgwFoAUFKzg17X61g5miFb6lIP39zE6
+pwgwDwYDVR0TAQH/BAUwAwEB/zAKBggqhkjOPQQDAgNIADBFAiEA2Tuz+3JtJMcm
+txoxGqavEgzRlflBizt++Tv/VaCdtZMCIBZuiU+qnLQzOlklkrqPmqJBhkbeJ6dB
+tNIF5T6sQKUi
+-----END CERTIFICATE-----
+";
+
+    /// 2027-01-01, inside the validity of both certificates
+    const NOW: i64 = 1_798_761_600;
+
+    fn inspect_chain(pem: &str, enabled: bool, certificate_status: &str) -> OcspInspection {
+        let mut inspection = OcspInspection {
+            chain: certificate::parse_chain(pem, NOW).unwrap(),
+            responder_url: ocsp_responder_url(pem),
+            config: Some(
+                serde_json::from_value(json!({
+                    "enabled": enabled,
+                    "responder_url": "http://ocsp.example.com"
+                }))
+                .unwrap(),
+            ),
+            status: Some(
+                serde_json::from_value(json!({
+                    "status": "ok",
+                    "certificate_status": certificate_status
+                }))
+                .unwrap(),
+            ),
+            problems: Vec::new(),
+            warnings: Vec::new(),
+        };
+        diagnose(&mut inspection);
+        inspection
+    }
+
+    #[test]
+    fn test_proxy_certificate() {
+        assert_eq!(
+            proxy_certificate(&json!({"api_cert": "a", "proxy_cert": "b"})).as_deref(),
+            Some("b")
+        );
+        assert_eq!(
+            proxy_certificate(&json!([
+                {"name": "api", "certificate": "a"},
+                {"name": "proxy", "certificate": "b"}
+            ]))
+            .as_deref(),
+            Some("b")
+        );
+        assert_eq!(proxy_certificate(&json!({"proxy_cert": ""})), None);
+    }
+
+    #[test]
+    fn test_ocsp_responder_url() {
+        assert_eq!(
+            ocsp_responder_url(LEAF).as_deref(),
+            Some("http://ocsp.example.com")
+        );
+        assert_eq!(ocsp_responder_url(ROOT), None);
+    }
+
+    #[test]
+    fn test_diagnose_healthy_chain() {
+        let chain = format!("{}{}", LEAF, ROOT);
+        let inspection = inspect_chain(&chain, true, "good");
+        assert!(inspection.problems.is_empty(), "{:?}", inspection.problems);
+        assert!(inspection.warnings.is_empty(), "{:?}", inspection.warnings);
+        assert_eq!(inspection.chain[0].serial, "10:92");
+    }
+
+    #[test]
+    fn test_diagnose_problems() {
+        let inspection = inspect_chain(LEAF, false, "revoked");
+        let problems = inspection.problems.join("\n");
+        assert!(problems.contains("OCSP is disabled"));
+        assert!(problems.contains("missing from the proxy certificate chain"));
+        assert!(problems.contains("revoked"));
+
+        let inspection = inspect_chain(ROOT, true, "good");
+        assert!(inspection.problems[0].contains("self-signed"));
+    }
+}
@@ -3,8 +3,8 @@
 #![allow(dead_code)]
 
 use crate::cli::{
-    EnterpriseAclCommands, EnterpriseAuthCommands, EnterpriseLdapCommands, EnterpriseRoleCommands,
-    EnterpriseUserCommands, OutputFormat,
+    EnterpriseAclCommands, EnterpriseAuthCommands, EnterpriseLdapCommands, EnterpriseRbacCommands,
+    EnterpriseRoleCommands, EnterpriseUserCommands, OutputFormat,
 };
 use crate::connection::ConnectionManager;
 use crate::error::Result as CliResult;
@@ -25,8 +25,21 @@ pub async fn handle_user_command(
         EnterpriseUserCommands::Get { id } => {
             rbac_impl::get_user(conn_mgr, profile_name, *id, output_format, query).await
         }
-        EnterpriseUserCommands::Create { data } => {
-            rbac_impl::create_user(conn_mgr, profile_name, data, output_format, query).await
+        EnterpriseUserCommands::Create {
+            data,
+            generate_password,
+            allow_weak_password,
+        } => {
+            rbac_impl::create_user(
+                conn_mgr,
+                profile_name,
+                data,
+                *generate_password,
+                *allow_weak_password,
+                output_format,
+                query,
+            )
+            .await
         }
         EnterpriseUserCommands::Update { id, data } => {
             rbac_impl::update_user(conn_mgr, profile_name, *id, data, output_format, query).await
@@ -34,12 +47,29 @@ pub async fn handle_user_command(
         EnterpriseUserCommands::Delete { id, force } => {
             rbac_impl::delete_user(conn_mgr, profile_name, *id, *force, output_format, query).await
         }
-        EnterpriseUserCommands::ResetPassword { id, password } => {
+        EnterpriseUserCommands::ResetPassword {
+            id,
+            password,
+            password_stdin,
+            generate_password,
+            allow_weak_password,
+            keep_sessions,
+        } => {
+            let source = if *generate_password {
+                rbac_impl::NewPassword::Generate
+            } else {
+                rbac_impl::NewPassword::Given {
+                    value: password.as_deref(),
+                    from_stdin: *password_stdin,
+                    allow_weak: *allow_weak_password,
+                }
+            };
             rbac_impl::reset_user_password(
                 conn_mgr,
                 profile_name,
                 *id,
-                password.as_deref(),
+                source,
+                !*keep_sessions,
                 output_format,
                 query,
             )
@@ -99,8 +129,8 @@ pub async fn handle_role_command(
         EnterpriseRoleCommands::GetPermissions { id } => {
             rbac_impl::get_role_permissions(conn_mgr, profile_name, *id, output_format, query).await
         }
-        EnterpriseRoleCommands::GetUsers { role_id } => {
-            rbac_impl::get_role_users(conn_mgr, profile_name, *role_id, output_format, query).await
+        EnterpriseRoleCommands::GetUsers { role } => {
+            rbac_impl::get_role_users(conn_mgr, profile_name, role, output_format, query).await
         }
     }
 }
@@ -119,8 +149,9 @@ pub async fn handle_acl_command(
         EnterpriseAclCommands::Get { id } => {
             rbac_impl::get_acl(conn_mgr, profile_name, *id, output_format, query).await
         }
-        EnterpriseAclCommands::Create { data } => {
-            rbac_impl::create_acl(conn_mgr, profile_name, data, output_format, query).await
+        EnterpriseAclCommands::Create { data, no_lint } => {
+            rbac_impl::create_acl(conn_mgr, profile_name, data, *no_lint, output_format, query)
+                .await
         }
         EnterpriseAclCommands::Update { id, data } => {
             rbac_impl::update_acl(conn_mgr, profile_name, *id, data, output_format, query).await
@@ -131,6 +162,30 @@ pub async fn handle_acl_command(
         EnterpriseAclCommands::Test { user, command } => {
             rbac_impl::test_acl(conn_mgr, profile_name, *user, command, output_format, query).await
         }
+        EnterpriseAclCommands::Lint { acl } => rbac_impl::lint_acl(acl, output_format, query),
+    }
+}
+
+pub async fn handle_rbac_command(
+    conn_mgr: &ConnectionManager,
+    profile_name: Option<&str>,
+    command: &EnterpriseRbacCommands,
+    output_format: OutputFormat,
+    query: Option<&str>,
+) -> CliResult<()> {
+    match command {
+        EnterpriseRbacCommands::Simulate { user, action, db } => {
+            rbac_impl::simulate_rbac(
+                conn_mgr,
+                profile_name,
+                *user,
+                action,
+                *db,
+                output_format,
+                query,
+            )
+            .await
+        }
     }
 }
 
@@ -157,6 +212,47 @@ pub async fn handle_ldap_command(
         EnterpriseLdapCommands::GetMappings => {
             rbac_impl::get_ldap_mappings(conn_mgr, profile_name, output_format, query).await
         }
+        EnterpriseLdapCommands::CreateMapping {
+            name,
+            dn,
+            roles,
+            email,
+        } => {
+            rbac_impl::create_ldap_mapping(
+                conn_mgr,
+                profile_name,
+                name,
+                dn,
+                roles,
+                email.as_deref(),
+                output_format,
+                query,
+            )
+            .await
+        }
+        EnterpriseLdapCommands::UpdateMapping {
+            uid,
+            name,
+            dn,
+            roles,
+            email,
+        } => {
+            let update = rbac_impl::LdapMappingUpdate {
+                name: name.as_deref(),
+                dn: dn.as_deref(),
+                roles,
+                email: email.as_deref(),
+            };
+            rbac_impl::update_ldap_mapping(
+                conn_mgr,
+                profile_name,
+                *uid,
+                update,
+                output_format,
+                query,
+            )
+            .await
+        }
     }
 }
 
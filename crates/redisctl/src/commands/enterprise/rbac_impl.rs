@@ -4,13 +4,19 @@
 
 use crate::cli::OutputFormat;
 use crate::connection::ConnectionManager;
-use crate::error::Result as CliResult;
+use crate::error::{RedisCtlError, Result as CliResult};
+use crate::output::notice;
 use anyhow::Context;
-use redis_enterprise::ldap_mappings::LdapMappingHandler;
+use redis_enterprise::bdb::BdbHandler;
+use redis_enterprise::ldap_mappings::{
+    CreateLdapMappingRequest, LdapMappingHandler, UpdateLdapMappingRequest,
+};
 use redis_enterprise::redis_acls::{CreateRedisAclRequest, RedisAclHandler};
-use redis_enterprise::roles::RolesHandler;
-use redis_enterprise::users::{AuthRequest, PasswordSet, UserHandler};
+use redis_enterprise::roles::{RoleInfo, RolesHandler};
+use redis_enterprise::users::{AuthRequest, UpdateUserRequest, User, UserHandler};
 
+use super::acl_lint;
+use super::rbac_simulate;
 use super::utils::*;
 
 // ============================================================================
@@ -57,24 +63,73 @@ pub async fn get_user(
     Ok(())
 }
 
+/// Create a user, checking the strength of the password in `data` or generating one
+///
+/// A generated password is added to the printed user, since the API never returns it.
+#[allow(clippy::too_many_arguments)]
 pub async fn create_user(
     conn_mgr: &ConnectionManager,
     profile_name: Option<&str>,
     data: &str,
+    generate_password: bool,
+    allow_weak_password: bool,
     output_format: OutputFormat,
     query: Option<&str>,
 ) -> CliResult<()> {
-    let client = conn_mgr.create_enterprise_client(profile_name).await?;
-
-    let user_data = read_json_data(data).context("Failed to parse user data")?;
+    let mut user_data = read_json_data(data).context("Failed to parse user data")?;
+    let generated = prepare_user_password(&mut user_data, generate_password, allow_weak_password)?;
 
+    let client = conn_mgr.create_enterprise_client(profile_name).await?;
     // CreateUserRequest doesn't have Deserialize, so we'll use the raw endpoint
-    let user_json = client.post_raw("/v1/users", user_data).await?;
+    let mut user_json = client.post_raw("/v1/users", user_data).await?;
+    if let Some(password) = &generated
+        && let Some(user) = user_json.as_object_mut()
+    {
+        user.insert("password".to_string(), serde_json::json!(password));
+    }
     let data = handle_output(user_json, output_format, query)?;
     print_formatted_output(data, output_format)?;
+    if generated.is_some() {
+        notice!("The password is shown only once; store it now.");
+    }
     Ok(())
 }
 
+/// Put a generated password into new user data, or check the one it has
+///
+/// Returns the generated password.
+fn prepare_user_password(
+    user_data: &mut serde_json::Value,
+    generate: bool,
+    allow_weak: bool,
+) -> CliResult<Option<String>> {
+    let Some(user) = user_data.as_object_mut() else {
+        return Err(RedisCtlError::Validation {
+            message: "User data must be a JSON object".to_string(),
+        });
+    };
+    if generate {
+        if user.contains_key("password") {
+            return Err(RedisCtlError::Validation {
+                message: "--generate-password cannot be combined with a password in the user data"
+                    .to_string(),
+            });
+        }
+        let password =
+            crate::password::generate_password(crate::password::DEFAULT_GENERATED_LENGTH);
+        user.insert("password".to_string(), serde_json::json!(password));
+        return Ok(Some(password));
+    }
+    if let Some(password) = user.get("password").and_then(|p| p.as_str()) {
+        let inputs: Vec<&str> = ["email", "name"]
+            .iter()
+            .filter_map(|key| user.get(*key).and_then(|v| v.as_str()))
+            .collect();
+        crate::password::check_password_strength(password, &inputs, allow_weak)?;
+    }
+    Ok(None)
+}
+
 pub async fn update_user(
     conn_mgr: &ConnectionManager,
     profile_name: Option<&str>,
@@ -105,51 +160,205 @@ pub async fn delete_user(
     _query: Option<&str>,
 ) -> CliResult<()> {
     if !force && !confirm_action(&format!("Delete user {}?", id))? {
-        println!("Operation cancelled");
+        notice!("Operation cancelled");
         return Ok(());
     }
 
     let client = conn_mgr.create_enterprise_client(profile_name).await?;
     let handler = UserHandler::new(client);
     handler.delete(id).await?;
-    println!("User {} deleted successfully", id);
+    notice!("User {} deleted successfully", id);
     Ok(())
 }
 
+/// Where `user reset-password` gets the new password from
+pub enum NewPassword<'a> {
+    /// A strong random password, printed once
+    Generate,
+    /// A flag value, standard input or a prompt, checked for strength unless `allow_weak`
+    Given {
+        value: Option<&'a str>,
+        from_stdin: bool,
+        allow_weak: bool,
+    },
+}
+
+/// Set a new password for a user and, unless told to keep them, log out their existing sessions
+///
+/// The password is set with an update of the user, so no current password is needed. Revoking
+/// the sessions is what makes the reset take effect at once. A cluster version without the
+/// endpoint only gets a warning; any other failure leaves the password changed and fails the
+/// command with the reason.
+#[allow(clippy::too_many_arguments)]
 pub async fn reset_user_password(
     conn_mgr: &ConnectionManager,
     profile_name: Option<&str>,
     id: u32,
-    password: Option<&str>,
-    _output_format: OutputFormat,
-    _query: Option<&str>,
+    source: NewPassword<'_>,
+    revoke_sessions: bool,
+    output_format: OutputFormat,
+    query: Option<&str>,
 ) -> CliResult<()> {
     let client = conn_mgr.create_enterprise_client(profile_name).await?;
     let handler = UserHandler::new(client);
 
-    // Get the user to get their email
+    // Look the user up first, so a wrong ID fails before the prompt and the strength check can
+    // reject passwords built from the user's own name or email
     let user = handler.get(id).await?;
-    let email = user.email.unwrap_or(user.username);
+    let (new_password, generated) = match source {
+        NewPassword::Generate => (
+            crate::password::generate_password(crate::password::DEFAULT_GENERATED_LENGTH),
+            true,
+        ),
+        NewPassword::Given {
+            value,
+            from_stdin,
+            allow_weak,
+        } => {
+            let password = crate::password::resolve_password(value, from_stdin)?;
+            crate::password::check_password_strength(
+                &password,
+                &password_user_inputs(&user),
+                allow_weak,
+            )?;
+            (password, false)
+        }
+    };
 
-    let new_password = if let Some(pwd) = password {
-        pwd.to_string()
+    let request = UpdateUserRequest::builder()
+        .password(new_password.as_str())
+        .build();
+    handler.update(id, request).await?;
+
+    let revocation = if revoke_sessions {
+        Some(handler.revoke_sessions(id).await)
     } else {
-        // Prompt for password if not provided
-        rpassword::prompt_password("New password: ").context("Failed to read password")?
+        None
     };
-
-    let request = PasswordSet {
-        email,
-        password: new_password,
+    let sessions_revoked = revocation.as_ref().map(|result| result.is_ok());
+    let revocation_error = match revocation {
+        Some(Err(e)) if revocation_unsupported(&e) => {
+            notice!("Warning: {}", session_revocation_failure(id, &e));
+            None
+        }
+        Some(Err(e)) => Some(e),
+        _ => None,
     };
+    let result = password_reset_result(
+        &user,
+        generated.then_some(new_password.as_str()),
+        sessions_revoked,
+    );
+
+    match output_format {
+        OutputFormat::Auto | OutputFormat::Table if query.is_none() => {
+            notice!(
+                "Password reset for user {} ({})",
+                id,
+                user.email.as_deref().unwrap_or(&user.username)
+            );
+            match sessions_revoked {
+                Some(true) => notice!("Existing sessions revoked; the user has to log in again"),
+                Some(false) => {}
+                None => notice!("Existing sessions were left logged in (--keep-sessions)"),
+            }
+            if generated {
+                println!("Password: {}", new_password);
+            }
+        }
+        _ => {
+            let data = handle_output(result, output_format, query)?;
+            print_formatted_output(data, output_format)?;
+        }
+    }
+    if generated {
+        notice!("The password is shown only once; store it now.");
+    }
 
-    handler.password_set(request).await?;
-    println!("Password reset successfully for user {}", id);
+    if let Some(e) = revocation_error {
+        return Err(RedisCtlError::api(format!(
+            "The password of user {} was changed, but {}",
+            id,
+            session_revocation_failure(id, &e)
+        )));
+    }
     Ok(())
 }
 
+/// Whether revoking sessions failed because the cluster version has no such endpoint
+fn revocation_unsupported(error: &redis_enterprise::RestError) -> bool {
+    error.is_not_found()
+        || matches!(
+            error,
+            redis_enterprise::RestError::ApiError { code: 405, .. }
+        )
+}
+
+/// Why revoking a user's sessions failed, worded to follow "but" or stand alone
+fn session_revocation_failure(id: u32, error: &redis_enterprise::RestError) -> String {
+    if revocation_unsupported(error) {
+        format!(
+            "the cluster cannot revoke the sessions of user {}: DELETE /v1/users/{}/sessions is \
+             not available on this cluster version ({}). Sessions end when their tokens expire",
+            id, id, error
+        )
+    } else {
+        format!("revoking the sessions of user {} failed: {}", id, error)
+    }
+}
+
+/// Words a user's new password should not be built from: their username, email and name
+fn password_user_inputs(user: &User) -> Vec<&str> {
+    let name = user.extra.get("name").and_then(|n| n.as_str());
+    [Some(user.username.as_str()), user.email.as_deref(), name]
+        .into_iter()
+        .flatten()
+        .filter(|input| !input.is_empty())
+        .collect()
+}
+
+/// The structured result of a password reset; `sessions_revoked` is `None` when they were kept
+fn password_reset_result(
+    user: &User,
+    generated: Option<&str>,
+    sessions_revoked: Option<bool>,
+) -> serde_json::Value {
+    let mut result = serde_json::json!({
+        "uid": user.uid,
+        "username": user.username,
+        "email": user.email,
+        "password_changed": true,
+        "sessions_revoked": sessions_revoked,
+    });
+    if let Some(password) = generated {
+        result["password"] = serde_json::json!(password);
+    }
+    result
+}
+
 // User-Role Assignment Commands
 
+/// Role UIDs assigned to a user
+fn user_role_uids(user: &User) -> Vec<u32> {
+    user.extra
+        .get("role_uids")
+        .and_then(|v| serde_json::from_value(v.clone()).ok())
+        .unwrap_or_default()
+}
+
+/// The roles with the given UIDs, and the UIDs no role has (e.g. roles deleted since)
+fn resolve_roles<'a>(uids: &[u32], roles: &'a [RoleInfo]) -> (Vec<&'a RoleInfo>, Vec<u32>) {
+    let mut found = Vec::new();
+    let mut missing = Vec::new();
+    for uid in uids {
+        match roles.iter().find(|r| r.uid == *uid) {
+            Some(role) => found.push(role),
+            None => missing.push(*uid),
+        }
+    }
+    (found, missing)
+}
+
 pub async fn get_user_roles(
     conn_mgr: &ConnectionManager,
     profile_name: Option<&str>,
@@ -158,13 +367,23 @@ pub async fn get_user_roles(
     query: Option<&str>,
 ) -> CliResult<()> {
     let client = conn_mgr.create_enterprise_client(profile_name).await?;
-    let handler = UserHandler::new(client);
+    let handler = UserHandler::new(client.clone());
 
     let user = handler.get(user_id).await?;
+    let role_uids = user_role_uids(&user);
+    let all_roles = if role_uids.is_empty() {
+        Vec::new()
+    } else {
+        RolesHandler::new(client).list().await?
+    };
+    let (roles, missing) = resolve_roles(&role_uids, &all_roles);
+
     let roles = serde_json::json!({
         "user_id": user_id,
+        "username": user.username,
         "role": user.role,
-        "role_uids": user.extra.get("role_uids")
+        "roles": roles,
+        "missing_role_uids": missing
     });
 
     let data = handle_output(roles, output_format, query)?;
@@ -185,11 +404,7 @@ pub async fn assign_user_role(
 
     // Get current user to preserve existing data
     let user = handler.get(user_id).await?;
-    let mut role_uids: Vec<u32> = user
-        .extra
-        .get("role_uids")
-        .and_then(|v| serde_json::from_value(v.clone()).ok())
-        .unwrap_or_default();
+    let mut role_uids = user_role_uids(&user);
 
     // Add new role if not already present
     if !role_uids.contains(&role_id) {
@@ -222,11 +437,7 @@ pub async fn remove_user_role(
 
     // Get current user to preserve existing data
     let user = handler.get(user_id).await?;
-    let mut role_uids: Vec<u32> = user
-        .extra
-        .get("role_uids")
-        .and_then(|v| serde_json::from_value(v.clone()).ok())
-        .unwrap_or_default();
+    let mut role_uids = user_role_uids(&user);
 
     // Remove the role
     role_uids.retain(|&id| id != role_id);
@@ -323,14 +534,14 @@ pub async fn delete_role(
     _query: Option<&str>,
 ) -> CliResult<()> {
     if !force && !confirm_action(&format!("Delete role {}?", id))? {
-        println!("Operation cancelled");
+        notice!("Operation cancelled");
         return Ok(());
     }
 
     let client = conn_mgr.create_enterprise_client(profile_name).await?;
     let handler = RolesHandler::new(client);
     handler.delete(id).await?;
-    println!("Role {} deleted successfully", id);
+    notice!("Role {} deleted successfully", id);
     Ok(())
 }
 
@@ -361,28 +572,25 @@ pub async fn get_role_permissions(
     Ok(())
 }
 
+/// Users bound to a role, given by UID or name
 pub async fn get_role_users(
     conn_mgr: &ConnectionManager,
     profile_name: Option<&str>,
-    role_id: u32,
+    role: &str,
     output_format: OutputFormat,
     query: Option<&str>,
 ) -> CliResult<()> {
     let client = conn_mgr.create_enterprise_client(profile_name).await?;
+    let role_id = RolesHandler::new(client.clone())
+        .resolve_uids(&[role])
+        .await?[0];
     let user_handler = UserHandler::new(client);
 
     // Get all users and filter by role
     let users = user_handler.list().await?;
     let users_with_role: Vec<_> = users
         .into_iter()
-        .filter(|u| {
-            if let Some(role_uids) = u.extra.get("role_uids")
-                && let Ok(uids) = serde_json::from_value::<Vec<u32>>(role_uids.clone())
-            {
-                return uids.contains(&role_id);
-            }
-            false
-        })
+        .filter(|u| user_role_uids(u).contains(&role_id))
         .collect();
 
     let users_json = serde_json::to_value(users_with_role).context("Failed to serialize users")?;
@@ -430,16 +638,32 @@ pub async fn create_acl(
     conn_mgr: &ConnectionManager,
     profile_name: Option<&str>,
     data: &str,
+    no_lint: bool,
     output_format: OutputFormat,
     query: Option<&str>,
 ) -> CliResult<()> {
-    let client = conn_mgr.create_enterprise_client(profile_name).await?;
-    let handler = RedisAclHandler::new(client);
-
     let acl_data = read_json_data(data).context("Failed to parse ACL data")?;
     let request: CreateRedisAclRequest =
         serde_json::from_value(acl_data).context("Invalid ACL creation request format")?;
 
+    if !no_lint {
+        let issues = acl_lint::lint_acl(&request.acl);
+        for issue in &issues {
+            eprintln!("{}", acl_lint::format_issue(issue));
+        }
+        if acl_lint::has_errors(&issues) {
+            return Err(RedisCtlError::Validation {
+                message: format!(
+                    "ACL rule '{}' failed lint; fix the errors above or pass --no-lint",
+                    request.acl
+                ),
+            });
+        }
+    }
+
+    let client = conn_mgr.create_enterprise_client(profile_name).await?;
+    let handler = RedisAclHandler::new(client);
+
     let acl = handler.create(request).await?;
     let acl_json = serde_json::to_value(acl).context("Failed to serialize ACL")?;
     let data = handle_output(acl_json, output_format, query)?;
@@ -478,14 +702,14 @@ pub async fn delete_acl(
     _query: Option<&str>,
 ) -> CliResult<()> {
     if !force && !confirm_action(&format!("Delete ACL {}?", id))? {
-        println!("Operation cancelled");
+        notice!("Operation cancelled");
         return Ok(());
     }
 
     let client = conn_mgr.create_enterprise_client(profile_name).await?;
     let handler = RedisAclHandler::new(client);
     handler.delete(id).await?;
-    println!("ACL {} deleted successfully", id);
+    notice!("ACL {} deleted successfully", id);
     Ok(())
 }
 
@@ -522,6 +746,113 @@ pub async fn test_acl(
     Ok(())
 }
 
+pub fn lint_acl(acl: &str, output_format: OutputFormat, query: Option<&str>) -> CliResult<()> {
+    let issues = acl_lint::lint_acl(acl);
+    let valid = !acl_lint::has_errors(&issues);
+
+    match output_format {
+        OutputFormat::Auto | OutputFormat::Table if query.is_none() => {
+            for issue in &issues {
+                println!("{}", acl_lint::format_issue(issue));
+            }
+            if issues.is_empty() {
+                println!("ACL rule is valid");
+            }
+        }
+        _ => {
+            let report = serde_json::json!({
+                "acl": acl,
+                "valid": valid,
+                "issues": issues,
+            });
+            let data = handle_output(report, output_format, query)?;
+            print_formatted_output(data, output_format)?;
+        }
+    }
+
+    if valid {
+        Ok(())
+    } else {
+        Err(RedisCtlError::Validation {
+            message: "ACL rule has errors".to_string(),
+        })
+    }
+}
+
+// ============================================================================
+// RBAC Analysis Commands
+// ============================================================================
+
+pub async fn simulate_rbac(
+    conn_mgr: &ConnectionManager,
+    profile_name: Option<&str>,
+    user_id: u32,
+    action: &str,
+    db: Option<u32>,
+    output_format: OutputFormat,
+    query: Option<&str>,
+) -> CliResult<()> {
+    let action = rbac_simulate::parse_action(action)
+        .map_err(|message| RedisCtlError::Validation { message })?;
+    if matches!(action, rbac_simulate::Action::Data { .. }) && db.is_none() {
+        return Err(RedisCtlError::Validation {
+            message: "--db is required for redis:<command> actions".to_string(),
+        });
+    }
+
+    let client = conn_mgr.create_enterprise_client(profile_name).await?;
+    let users = UserHandler::new(client.clone());
+    let roles = RolesHandler::new(client.clone());
+    let acls = RedisAclHandler::new(client.clone());
+
+    let (user, roles, acls) = tokio::try_join!(users.get(user_id), roles.list(), acls.list())?;
+    let roles_permissions = match db {
+        Some(uid) => BdbHandler::new(client).get(uid).await?.roles_permissions,
+        None => None,
+    };
+
+    let simulation = rbac_simulate::simulate(
+        &user,
+        &roles,
+        &acls,
+        &action,
+        db,
+        roles_permissions.as_deref(),
+    );
+
+    match output_format {
+        OutputFormat::Auto | OutputFormat::Table if query.is_none() => {
+            let verdict = match simulation.allowed {
+                Some(true) => "ALLOWED",
+                Some(false) => "DENIED",
+                None => "UNDETERMINED",
+            };
+            let target = db
+                .map(|uid| format!(" on database {}", uid))
+                .unwrap_or_default();
+            println!(
+                "User {} ({}) {}{}: {}",
+                simulation.user_id, simulation.username, simulation.action, target, verdict
+            );
+            for step in &simulation.steps {
+                let mark = match step.effect {
+                    rbac_simulate::Effect::Grant => "+",
+                    rbac_simulate::Effect::Deny => "-",
+                    rbac_simulate::Effect::Unknown => "?",
+                };
+                println!("  {} {}: {}", mark, step.source, step.reason);
+            }
+        }
+        _ => {
+            let json =
+                serde_json::to_value(&simulation).context("Failed to serialize simulation")?;
+            let data = handle_output(json, output_format, query)?;
+            print_formatted_output(data, output_format)?;
+        }
+    }
+    Ok(())
+}
+
 // ============================================================================
 // LDAP Integration Commands
 // ============================================================================
@@ -610,6 +941,96 @@ pub async fn get_ldap_mappings(
     Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
+pub async fn create_ldap_mapping(
+    conn_mgr: &ConnectionManager,
+    profile_name: Option<&str>,
+    name: &str,
+    dn: &str,
+    roles: &[String],
+    email: Option<&str>,
+    output_format: OutputFormat,
+    query: Option<&str>,
+) -> CliResult<()> {
+    let client = conn_mgr.create_enterprise_client(profile_name).await?;
+    let (role, role_uids) =
+        resolve_mapping_roles(&RolesHandler::new(client.clone()), roles).await?;
+    let request = CreateLdapMappingRequest {
+        name: name.to_string(),
+        dn: dn.to_string(),
+        role,
+        email: email.map(str::to_string),
+        role_uids: Some(role_uids),
+    };
+    let mapping = LdapMappingHandler::new(client).create(request).await?;
+    let mapping_json = serde_json::to_value(mapping).context("Failed to serialize mapping")?;
+    let data = handle_output(mapping_json, output_format, query)?;
+    print_formatted_output(data, output_format)?;
+    Ok(())
+}
+
+/// Fields to change in an LDAP mapping; roles replace the current ones when given
+pub struct LdapMappingUpdate<'a> {
+    pub name: Option<&'a str>,
+    pub dn: Option<&'a str>,
+    pub roles: &'a [String],
+    pub email: Option<&'a str>,
+}
+
+pub async fn update_ldap_mapping(
+    conn_mgr: &ConnectionManager,
+    profile_name: Option<&str>,
+    uid: u32,
+    update: LdapMappingUpdate<'_>,
+    output_format: OutputFormat,
+    query: Option<&str>,
+) -> CliResult<()> {
+    if update.name.is_none()
+        && update.dn.is_none()
+        && update.roles.is_empty()
+        && update.email.is_none()
+    {
+        return Err(RedisCtlError::Validation {
+            message: "Nothing to update; pass at least one of --name, --dn, --role or --email"
+                .to_string(),
+        });
+    }
+
+    let client = conn_mgr.create_enterprise_client(profile_name).await?;
+    let (role, role_uids) = if update.roles.is_empty() {
+        (None, None)
+    } else {
+        let (role, uids) =
+            resolve_mapping_roles(&RolesHandler::new(client.clone()), update.roles).await?;
+        (Some(role), Some(uids))
+    };
+    let request = UpdateLdapMappingRequest {
+        name: update.name.map(str::to_string),
+        dn: update.dn.map(str::to_string),
+        role,
+        email: update.email.map(str::to_string),
+        role_uids,
+    };
+    let mapping = LdapMappingHandler::new(client).update(uid, request).await?;
+    let mapping_json = serde_json::to_value(mapping).context("Failed to serialize mapping")?;
+    let data = handle_output(mapping_json, output_format, query)?;
+    print_formatted_output(data, output_format)?;
+    Ok(())
+}
+
+/// Role UIDs for `--role` values, plus the first role's name for the mapping's `role` field
+async fn resolve_mapping_roles(
+    handler: &RolesHandler,
+    roles: &[String],
+) -> CliResult<(String, Vec<u32>)> {
+    let uids = handler.resolve_uids(roles).await?;
+    let role = match uids.first() {
+        Some(uid) => handler.get(*uid).await?.name,
+        None => String::new(),
+    };
+    Ok((role, uids))
+}
+
 // ============================================================================
 // Authentication & Session Commands
 // ============================================================================
@@ -625,6 +1046,10 @@ pub async fn test_auth(
     let handler = UserHandler::new(client);
 
     // Prompt for password
+    crate::interactive::ensure_prompt_allowed(
+        &format!("Testing authentication for {} needs a password", username),
+        "this command reads the password only from a prompt",
+    )?;
     let password = rpassword::prompt_password("Password: ").context("Failed to read password")?;
 
     let auth_request = AuthRequest {
@@ -690,7 +1115,7 @@ pub async fn revoke_session(
     client
         .delete_raw(&format!("/v1/sessions/{}", session_id))
         .await?;
-    println!("Session {} revoked successfully", session_id);
+    notice!("Session {} revoked successfully", session_id);
     Ok(())
 }
 
@@ -703,14 +1128,111 @@ pub async fn revoke_all_user_sessions(
 ) -> CliResult<()> {
     let client = conn_mgr.create_enterprise_client(profile_name).await?;
 
-    client
-        .delete_raw(&format!("/v1/users/{}/sessions", user_id))
+    UserHandler::new(client)
+        .revoke_sessions(user_id)
         .await
-        .unwrap_or_else(|_| {
-            println!("Note: Session revocation endpoint may not be available");
-            serde_json::Value::Null
-        });
+        .map_err(|e| {
+            let mut message = session_revocation_failure(user_id, &e);
+            message[..1].make_ascii_uppercase();
+            RedisCtlError::api(message)
+        })?;
 
-    println!("All sessions for user {} revoked", user_id);
+    notice!("All sessions for user {} revoked", user_id);
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_prepare_user_password() {
+        let mut weak = json!({"email": "ops@example.com", "password": "ops@example"});
+        assert!(prepare_user_password(&mut weak, false, false).is_err());
+        assert_eq!(prepare_user_password(&mut weak, false, true).unwrap(), None);
+
+        let mut generated = json!({"email": "ops@example.com", "role": "db_viewer"});
+        let password = prepare_user_password(&mut generated, true, false)
+            .unwrap()
+            .unwrap();
+        assert_eq!(generated["password"], json!(password));
+        assert!(prepare_user_password(&mut generated, true, false).is_err());
+    }
+
+    #[test]
+    fn test_password_reset_result() {
+        let user: User = serde_json::from_value(json!({
+            "uid": 7, "username": "ops", "email": "ops@example.com", "role": "admin",
+            "name": "Olive Ops"
+        }))
+        .unwrap();
+        assert_eq!(
+            password_user_inputs(&user),
+            vec!["ops", "ops@example.com", "Olive Ops"]
+        );
+
+        let kept = password_reset_result(&user, None, None);
+        assert_eq!(kept["sessions_revoked"], json!(null));
+        assert!(kept.get("password").is_none());
+
+        let generated = password_reset_result(&user, Some("n3w-Pass.word"), Some(true));
+        assert_eq!(generated["uid"], json!(7));
+        assert_eq!(generated["password_changed"], json!(true));
+        assert_eq!(generated["sessions_revoked"], json!(true));
+        assert_eq!(generated["password"], json!("n3w-Pass.word"));
+    }
+
+    #[test]
+    fn test_session_revocation_failure() {
+        use redis_enterprise::RestError;
+
+        for error in [
+            RestError::NotFound,
+            RestError::ApiError {
+                code: 405,
+                message: "Method Not Allowed".to_string(),
+            },
+        ] {
+            assert!(revocation_unsupported(&error));
+            assert!(
+                session_revocation_failure(7, &error)
+                    .starts_with("the cluster cannot revoke the sessions of user 7")
+            );
+        }
+        let denied = RestError::ApiError {
+            code: 403,
+            message: "Forbidden".to_string(),
+        };
+        assert!(!revocation_unsupported(&denied));
+        assert!(
+            session_revocation_failure(7, &denied)
+                .starts_with("revoking the sessions of user 7 failed")
+        );
+    }
+
+    #[test]
+    fn test_resolve_roles() {
+        let roles: Vec<RoleInfo> = serde_json::from_value(json!([
+            {"uid": 1, "name": "Admin", "management": "admin", "data_access": "full"},
+            {"uid": 4, "name": "Viewer", "management": "db_viewer"}
+        ]))
+        .unwrap();
+        let (found, missing) = resolve_roles(&[4, 9, 1], &roles);
+        let names: Vec<&str> = found.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(names, vec!["Viewer", "Admin"]);
+        assert_eq!(missing, vec![9]);
+    }
+
+    #[test]
+    fn test_user_role_uids() {
+        let user: User = serde_json::from_value(json!({
+            "uid": 2, "username": "ops", "role": "db_member", "role_uids": [1, 4]
+        }))
+        .unwrap();
+        assert_eq!(user_role_uids(&user), vec![1, 4]);
+        let legacy: User =
+            serde_json::from_value(json!({"uid": 3, "username": "old", "role": "admin"})).unwrap();
+        assert!(user_role_uids(&legacy).is_empty());
+    }
+}
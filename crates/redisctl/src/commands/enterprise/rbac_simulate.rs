@@ -0,0 +1,592 @@
+//! Offline evaluation of Redis Enterprise RBAC
+//!
+//! Answers "would user X be allowed to do Y?" from the user's roles, the role definitions, the
+//! Redis ACLs and the database's role bindings, and explains which role or rule decided it.
+//!
+//! Two kinds of action are understood:
+//!
+//! - Management actions, `<resource>:<verb>` such as `bdbs:update` or `users:view`. These are
+//!   decided by the management role (`admin`, `cluster_member`, `db_viewer`, ...) of the user
+//!   and of each role assigned to the user, and apply cluster-wide.
+//! - Data access, `redis:<command>` such as `redis:flushdb`, which needs a database. The
+//!   database's `roles_permissions` bind the user's roles to Redis ACLs, and the ACL rules are
+//!   evaluated against the command in order.
+
+#![allow(dead_code)]
+
+use redis_enterprise::redis_acls::RedisAcl;
+use redis_enterprise::roles::RoleInfo;
+use redis_enterprise::users::User;
+use serde::Serialize;
+use serde_json::Value;
+
+use super::acl_lint::split_tokens;
+
+/// Management roles in order of increasing privilege, for help and error messages
+const MANAGEMENT_ROLES: &[&str] = &[
+    "none",
+    "db_viewer",
+    "db_member",
+    "cluster_viewer",
+    "cluster_member",
+    "user_manager",
+    "admin",
+];
+
+/// ACL categories of common commands, used to evaluate `+@category` rules
+const COMMAND_CATEGORIES: &[(&str, &[&str])] = &[
+    ("get", &["read", "string", "fast"]),
+    ("mget", &["read", "string", "fast"]),
+    ("set", &["write", "string", "slow"]),
+    ("mset", &["write", "string", "slow"]),
+    ("incr", &["write", "string", "fast"]),
+    ("del", &["write", "keyspace", "slow"]),
+    ("unlink", &["write", "keyspace", "fast"]),
+    ("exists", &["read", "keyspace", "fast"]),
+    ("expire", &["write", "keyspace", "fast"]),
+    ("ttl", &["read", "keyspace", "fast"]),
+    ("type", &["read", "keyspace", "fast"]),
+    ("scan", &["read", "keyspace", "slow"]),
+    ("keys", &["read", "keyspace", "slow", "dangerous"]),
+    ("flushdb", &["write", "keyspace", "slow", "dangerous"]),
+    ("flushall", &["write", "keyspace", "slow", "dangerous"]),
+    ("hget", &["read", "hash", "fast"]),
+    ("hgetall", &["read", "hash", "slow"]),
+    ("hset", &["write", "hash", "fast"]),
+    ("lpush", &["write", "list", "fast"]),
+    ("lrange", &["read", "list", "slow"]),
+    ("blpop", &["write", "list", "slow", "blocking"]),
+    ("sadd", &["write", "set", "fast"]),
+    ("smembers", &["read", "set", "slow"]),
+    ("zadd", &["write", "sortedset", "fast"]),
+    ("zrange", &["read", "sortedset", "slow"]),
+    ("xadd", &["write", "stream", "fast"]),
+    ("xread", &["read", "stream", "slow", "blocking"]),
+    ("publish", &["pubsub", "fast"]),
+    ("subscribe", &["pubsub", "slow"]),
+    ("eval", &["slow", "scripting"]),
+    ("evalsha", &["slow", "scripting"]),
+    ("multi", &["fast", "transaction"]),
+    ("exec", &["slow", "transaction"]),
+    ("ping", &["fast", "connection"]),
+    ("info", &["slow", "dangerous"]),
+    ("config", &["admin", "slow", "dangerous"]),
+    ("monitor", &["admin", "slow", "dangerous"]),
+    ("debug", &["admin", "slow", "dangerous"]),
+    ("shutdown", &["admin", "slow", "dangerous"]),
+    ("json.get", &["read", "json", "slow"]),
+    ("json.set", &["write", "json", "slow"]),
+    ("ft.search", &["read", "search", "slow"]),
+];
+
+/// Whether a management action only reads or also changes things
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Access {
+    View,
+    Edit,
+}
+
+/// Resources grouped by who may manage them
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ResourceGroup {
+    Databases,
+    Cluster,
+    AccessControl,
+    Metrics,
+}
+
+/// An action to evaluate
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Action {
+    Management {
+        resource: String,
+        verb: String,
+    },
+    /// A Redis command, lowercased
+    Data {
+        command: String,
+    },
+}
+
+impl std::fmt::Display for Action {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Action::Management { resource, verb } => write!(f, "{}:{}", resource, verb),
+            Action::Data { command } => write!(f, "redis:{}", command),
+        }
+    }
+}
+
+/// Parse `<resource>:<verb>` or `redis:<command>`
+pub fn parse_action(action: &str) -> Result<Action, String> {
+    let (resource, verb) = action
+        .split_once(':')
+        .filter(|(resource, verb)| !resource.is_empty() && !verb.is_empty())
+        .ok_or_else(|| {
+            format!(
+                "invalid action: {} (expected <resource>:<verb> such as bdbs:update, or redis:<command>)",
+                action
+            )
+        })?;
+    let resource = resource.to_lowercase();
+    let verb = verb.to_lowercase();
+
+    if resource == "redis" {
+        return Ok(Action::Data { command: verb });
+    }
+    if resource_group(&resource).is_none() {
+        return Err(format!(
+            "unknown resource '{}' (known: bdbs, crdbs, cluster, nodes, shards, logs, license, users, roles, redis_acls, ldap, stats, alerts)",
+            resource
+        ));
+    }
+    Ok(Action::Management { resource, verb })
+}
+
+fn resource_group(resource: &str) -> Option<ResourceGroup> {
+    match resource {
+        "bdbs" | "crdbs" | "modules" => Some(ResourceGroup::Databases),
+        "cluster" | "nodes" | "shards" | "logs" | "license" | "services" | "proxies" => {
+            Some(ResourceGroup::Cluster)
+        }
+        "users" | "roles" | "redis_acls" | "ldap" => Some(ResourceGroup::AccessControl),
+        "stats" | "alerts" => Some(ResourceGroup::Metrics),
+        _ => None,
+    }
+}
+
+/// Whether a management role grants an access level on a resource group
+fn management_allows(role: &str, group: ResourceGroup, access: Access) -> bool {
+    use Access::*;
+    use ResourceGroup::*;
+
+    match role {
+        "admin" => true,
+        "user_manager" => access == View || group == AccessControl,
+        "cluster_member" => group != AccessControl && (access == View || group == Databases),
+        "cluster_viewer" => group != AccessControl && access == View,
+        "db_member" => {
+            matches!(group, Databases | Metrics) && (access == View || group == Databases)
+        }
+        "db_viewer" => matches!(group, Databases | Metrics) && access == View,
+        _ => false,
+    }
+}
+
+/// How one role or rule bears on the action
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Effect {
+    Grant,
+    Deny,
+    /// The rule depends on command categories this tool does not know for the command
+    Unknown,
+}
+
+/// One step of the explanation
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SimulationStep {
+    /// Where the permission comes from, e.g. `role 3 (db-admins)`
+    pub source: String,
+    pub effect: Effect,
+    pub reason: String,
+}
+
+/// Outcome of a simulation
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Simulation {
+    pub user_id: u32,
+    pub username: String,
+    pub action: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub database: Option<u32>,
+    /// `None` when the outcome depends on rules that cannot be evaluated offline
+    pub allowed: Option<bool>,
+    pub steps: Vec<SimulationStep>,
+}
+
+/// Evaluate an action for a user
+///
+/// `roles` and `acls` are the cluster's role and Redis ACL definitions. `roles_permissions` is
+/// the target database's role-to-ACL binding, required for data access actions.
+pub fn simulate(
+    user: &User,
+    roles: &[RoleInfo],
+    acls: &[RedisAcl],
+    action: &Action,
+    database: Option<u32>,
+    roles_permissions: Option<&[Value]>,
+) -> Simulation {
+    let user_roles: Vec<&RoleInfo> = role_uids(user)
+        .iter()
+        .filter_map(|uid| roles.iter().find(|role| role.uid == *uid))
+        .collect();
+
+    let steps = match action {
+        Action::Management { resource, verb } => {
+            management_steps(user, &user_roles, resource, verb)
+        }
+        Action::Data { command } => match database {
+            Some(bdb) => data_steps(&user_roles, acls, command, bdb, roles_permissions),
+            None => vec![SimulationStep {
+                source: "request".to_string(),
+                effect: Effect::Deny,
+                reason: "data access actions need a database (--db)".to_string(),
+            }],
+        },
+    };
+
+    let allowed = if steps.iter().any(|s| s.effect == Effect::Grant) {
+        Some(true)
+    } else if steps.iter().any(|s| s.effect == Effect::Unknown) {
+        None
+    } else {
+        Some(false)
+    };
+
+    Simulation {
+        user_id: user.uid,
+        username: user.email.clone().unwrap_or_else(|| user.username.clone()),
+        action: action.to_string(),
+        database,
+        allowed,
+        steps,
+    }
+}
+
+/// Role IDs assigned to a user (`role_uids`, not part of the typed model)
+fn role_uids(user: &User) -> Vec<u32> {
+    user.extra
+        .get("role_uids")
+        .and_then(|uids| serde_json::from_value(uids.clone()).ok())
+        .unwrap_or_default()
+}
+
+fn role_source(role: &RoleInfo) -> String {
+    format!("role {} ({})", role.uid, role.name)
+}
+
+fn management_steps(
+    user: &User,
+    user_roles: &[&RoleInfo],
+    resource: &str,
+    verb: &str,
+) -> Vec<SimulationStep> {
+    let group = resource_group(resource).unwrap_or(ResourceGroup::Cluster);
+    let access = match verb {
+        "view" | "get" | "list" | "read" => Access::View,
+        _ => Access::Edit,
+    };
+    let wanted = match access {
+        Access::View => "view",
+        Access::Edit => "change",
+    };
+
+    let mut sources = vec![("user role field".to_string(), Some(user.role.as_str()))];
+    sources.extend(
+        user_roles
+            .iter()
+            .map(|role| (role_source(role), role.management.as_deref())),
+    );
+
+    sources
+        .into_iter()
+        .map(|(source, management)| {
+            let management = management.unwrap_or("none");
+            let effect = if management_allows(management, group, access) {
+                Effect::Grant
+            } else {
+                Effect::Deny
+            };
+            let reason = match effect {
+                Effect::Grant => format!(
+                    "management role '{}' can {} {}",
+                    management, wanted, resource
+                ),
+                _ if !MANAGEMENT_ROLES.contains(&management) => {
+                    format!("unknown management role '{}'", management)
+                }
+                _ => format!(
+                    "management role '{}' cannot {} {}",
+                    management, wanted, resource
+                ),
+            };
+            SimulationStep {
+                source,
+                effect,
+                reason,
+            }
+        })
+        .collect()
+}
+
+fn data_steps(
+    user_roles: &[&RoleInfo],
+    acls: &[RedisAcl],
+    command: &str,
+    bdb: u32,
+    roles_permissions: Option<&[Value]>,
+) -> Vec<SimulationStep> {
+    if user_roles.is_empty() {
+        return vec![SimulationStep {
+            source: "user".to_string(),
+            effect: Effect::Deny,
+            reason: "the user has no roles, so no Redis ACL applies".to_string(),
+        }];
+    }
+
+    user_roles
+        .iter()
+        .map(|role| {
+            let source = role_source(role);
+            let Some(acl_uid) = bound_acl(role, bdb, roles_permissions) else {
+                return SimulationStep {
+                    source,
+                    effect: Effect::Deny,
+                    reason: format!("role is not bound to database {}", bdb),
+                };
+            };
+            let Some(acl) = acls.iter().find(|acl| acl.uid == acl_uid) else {
+                return SimulationStep {
+                    source,
+                    effect: Effect::Deny,
+                    reason: format!("bound to Redis ACL {}, which does not exist", acl_uid),
+                };
+            };
+
+            let (effect, rule) = evaluate_acl(&acl.acl, command);
+            let acl_name = format!("Redis ACL '{}' ({})", acl.name, acl.acl);
+            let reason = match (effect, rule) {
+                (Effect::Grant, Some(rule)) => {
+                    format!("{} allows {} via '{}'", acl_name, command, rule)
+                }
+                (Effect::Deny, Some(rule)) => {
+                    format!("{} denies {} via '{}'", acl_name, command, rule)
+                }
+                (Effect::Unknown, Some(rule)) => format!(
+                    "{}: '{}' depends on the categories of {}, which are not known offline",
+                    acl_name, rule, command
+                ),
+                (_, None) => format!("{} has no rule that allows {}", acl_name, command),
+            };
+            SimulationStep {
+                source,
+                effect,
+                reason,
+            }
+        })
+        .collect()
+}
+
+/// The Redis ACL a role uses on a database, from `roles_permissions` or the role's `bdb_roles`
+fn bound_acl(role: &RoleInfo, bdb: u32, roles_permissions: Option<&[Value]>) -> Option<u32> {
+    let from_bdb = roles_permissions
+        .unwrap_or_default()
+        .iter()
+        .find_map(|binding| {
+            let role_uid = binding.get("role_uid").and_then(Value::as_u64)?;
+            (role_uid == u64::from(role.uid))
+                .then(|| binding.get("redis_acl_uid").and_then(Value::as_u64))
+                .flatten()
+                .and_then(|uid| u32::try_from(uid).ok())
+        });
+    from_bdb.or_else(|| {
+        role.bdb_roles
+            .as_ref()?
+            .iter()
+            .find(|binding| binding.bdb_uid == bdb)?
+            .redis_acl_uid
+    })
+}
+
+/// Evaluate the command rules of an ACL in order
+///
+/// Returns the effect and the last rule that decided it; `(Deny, None)` when nothing applies.
+fn evaluate_acl(acl: &str, command: &str) -> (Effect, Option<String>) {
+    let categories = COMMAND_CATEGORIES
+        .iter()
+        .find(|(name, _)| *name == command)
+        .map(|(_, categories)| *categories);
+    let base_command = command.split('|').next().unwrap_or(command);
+
+    let mut decision = (Effect::Deny, None);
+    for token in split_tokens(acl).unwrap_or_default() {
+        let rule = token.to_lowercase();
+        let effect = match rule.as_str() {
+            "allcommands" | "+@all" => Some(Effect::Grant),
+            "nocommands" | "-@all" => Some(Effect::Deny),
+            _ => {
+                let (grant, name) = match rule.split_at_checked(1) {
+                    Some(("+", name)) => (true, name),
+                    Some(("-", name)) => (false, name),
+                    _ => continue,
+                };
+                let applies = match name.strip_prefix('@') {
+                    Some(category) => categories.map(|categories| categories.contains(&category)),
+                    None => Some(name == command || name == base_command),
+                };
+                match applies {
+                    Some(true) if grant => Some(Effect::Grant),
+                    Some(true) => Some(Effect::Deny),
+                    Some(false) => None,
+                    None => Some(Effect::Unknown),
+                }
+            }
+        };
+        if let Some(effect) = effect {
+            decision = (effect, Some(token));
+        }
+    }
+    decision
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn user(role: &str, role_uids: &[u32]) -> User {
+        serde_json::from_value(json!({
+            "uid": 7,
+            "username": "alice",
+            "email": "alice@example.com",
+            "role": role,
+            "role_uids": role_uids
+        }))
+        .unwrap()
+    }
+
+    fn roles() -> Vec<RoleInfo> {
+        serde_json::from_value(json!([
+            {"uid": 1, "name": "viewers", "management": "db_viewer"},
+            {"uid": 2, "name": "app", "management": "none"},
+            {"uid": 3, "name": "ops", "management": "cluster_member"}
+        ]))
+        .unwrap()
+    }
+
+    fn acls() -> Vec<RedisAcl> {
+        serde_json::from_value(json!([
+            {"uid": 10, "name": "read-only", "acl": "+@read ~*"},
+            {"uid": 11, "name": "app", "acl": "+@all -@dangerous ~app:*"}
+        ]))
+        .unwrap()
+    }
+
+    fn bindings() -> Vec<Value> {
+        vec![
+            json!({"role_uid": 1, "redis_acl_uid": 10}),
+            json!({"role_uid": 2, "redis_acl_uid": 11}),
+        ]
+    }
+
+    #[test]
+    fn test_parse_action() {
+        assert_eq!(
+            parse_action("bdbs:update"),
+            Ok(Action::Management {
+                resource: "bdbs".to_string(),
+                verb: "update".to_string()
+            })
+        );
+        assert_eq!(
+            parse_action("redis:GET"),
+            Ok(Action::Data {
+                command: "get".to_string()
+            })
+        );
+        assert!(parse_action("bdbs").is_err());
+        assert!(parse_action("widgets:view").is_err());
+    }
+
+    #[test]
+    fn test_management_action() {
+        let action = parse_action("bdbs:update").unwrap();
+
+        let viewer = simulate(
+            &user("none", &[1]),
+            &roles(),
+            &acls(),
+            &action,
+            Some(3),
+            None,
+        );
+        assert_eq!(viewer.allowed, Some(false));
+        assert_eq!(viewer.steps.len(), 2);
+        assert!(
+            viewer.steps[1]
+                .reason
+                .contains("'db_viewer' cannot change bdbs")
+        );
+
+        let ops = simulate(
+            &user("none", &[1, 3]),
+            &roles(),
+            &acls(),
+            &action,
+            Some(3),
+            None,
+        );
+        assert_eq!(ops.allowed, Some(true));
+        assert_eq!(ops.steps[2].source, "role 3 (ops)");
+        assert_eq!(ops.steps[2].effect, Effect::Grant);
+
+        let users = parse_action("users:create").unwrap();
+        let ops = simulate(&user("none", &[3]), &roles(), &acls(), &users, None, None);
+        assert_eq!(ops.allowed, Some(false));
+        let admin = simulate(&user("admin", &[]), &roles(), &acls(), &users, None, None);
+        assert_eq!(admin.allowed, Some(true));
+        assert_eq!(admin.steps[0].source, "user role field");
+    }
+
+    #[test]
+    fn test_data_action() {
+        let simulate_command = |command: &str, role_uids: &[u32]| {
+            let action = parse_action(&format!("redis:{}", command)).unwrap();
+            simulate(
+                &user("none", role_uids),
+                &roles(),
+                &acls(),
+                &action,
+                Some(3),
+                Some(&bindings()),
+            )
+        };
+
+        let read = simulate_command("get", &[1]);
+        assert_eq!(read.allowed, Some(true));
+        assert!(read.steps[0].reason.contains("via '+@read'"));
+
+        let write = simulate_command("set", &[1]);
+        assert_eq!(write.allowed, Some(false));
+        assert!(
+            write.steps[0]
+                .reason
+                .contains("has no rule that allows set")
+        );
+
+        let flush = simulate_command("flushdb", &[2]);
+        assert_eq!(flush.allowed, Some(false));
+        assert!(flush.steps[0].reason.contains("via '-@dangerous'"));
+
+        // One role granting is enough
+        assert_eq!(simulate_command("set", &[1, 2]).allowed, Some(true));
+
+        let unbound = simulate_command("get", &[3]);
+        assert_eq!(unbound.allowed, Some(false));
+        assert!(unbound.steps[0].reason.contains("not bound to database 3"));
+
+        // Category rules cannot be evaluated for commands with unknown categories
+        assert_eq!(simulate_command("georadius", &[2]).allowed, None);
+    }
+
+    #[test]
+    fn test_evaluate_acl_order() {
+        assert_eq!(
+            evaluate_acl("+@all -flushall +flushall", "flushall"),
+            (Effect::Grant, Some("+flushall".to_string()))
+        );
+        assert_eq!(evaluate_acl("+config|get", "config|get").0, Effect::Grant);
+        assert_eq!(evaluate_acl("+get -@all", "get").0, Effect::Deny);
+    }
+}
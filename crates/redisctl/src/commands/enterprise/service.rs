@@ -0,0 +1,48 @@
+//! Service command router for Enterprise
+
+#![allow(dead_code)]
+
+use crate::cli::{EnterpriseServiceCommands, OutputFormat};
+use crate::connection::ConnectionManager;
+use crate::error::Result as CliResult;
+
+use super::service_impl;
+
+pub async fn handle_service_command(
+    conn_mgr: &ConnectionManager,
+    profile_name: Option<&str>,
+    command: &EnterpriseServiceCommands,
+    output_format: OutputFormat,
+    query: Option<&str>,
+) -> CliResult<()> {
+    match command {
+        EnterpriseServiceCommands::List { node } => {
+            service_impl::list_services(conn_mgr, profile_name, *node, output_format, query).await
+        }
+        EnterpriseServiceCommands::Restart {
+            service,
+            node,
+            all_nodes,
+            continue_on_error,
+            health_timeout,
+            force,
+        } => {
+            let options = service_impl::RestartOptions {
+                node: *node,
+                all_nodes: *all_nodes,
+                continue_on_error: *continue_on_error,
+                health_timeout: *health_timeout,
+                force: *force,
+            };
+            service_impl::restart_service(
+                conn_mgr,
+                profile_name,
+                service,
+                &options,
+                output_format,
+                query,
+            )
+            .await
+        }
+    }
+}
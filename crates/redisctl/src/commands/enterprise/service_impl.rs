@@ -0,0 +1,677 @@
+//! Service command implementations for Redis Enterprise
+//!
+//! Services are controlled through the node-local API (`/v1/local/services`), which acts on
+//! whichever node receives the request. To reach a particular node the profile URL is reused
+//! with the node's address in place of the host.
+//!
+//! `cluster rolling-restart` builds on the same per-node restart, and also waits for every
+//! database to be back in full sync before it touches the next node.
+
+#![allow(dead_code)]
+
+use crate::cli::OutputFormat;
+use crate::connection::ConnectionManager;
+use crate::error::{RedisCtlError, Result as CliResult};
+use crate::output::notice;
+use anyhow::Context;
+use redis_enterprise::local::LocalHandler;
+use redis_enterprise::nodes::{Node, NodeHandler};
+use redis_enterprise::{BdbHandler, Database, EnterpriseClient, Shard, ShardHandler};
+use serde::Serialize;
+use serde_json::Value;
+use std::time::{Duration, Instant};
+use tabled::{Table, Tabled, settings::Style};
+
+use super::utils::*;
+
+/// How often a restarted node is polled while waiting for it to become healthy
+const HEALTH_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Options for `service restart`
+#[derive(Debug, Clone)]
+pub struct RestartOptions {
+    pub node: Option<u32>,
+    pub all_nodes: bool,
+    pub continue_on_error: bool,
+    pub health_timeout: u64,
+    pub force: bool,
+}
+
+/// Options for `cluster rolling-restart`
+#[derive(Debug, Clone)]
+pub struct RollingRestartOptions {
+    pub health_timeout: u64,
+    pub sync_timeout: u64,
+    pub force: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum RestartOutcome {
+    Restarted,
+    Failed,
+    Skipped,
+}
+
+/// What happened on one node
+#[derive(Debug, Serialize)]
+struct NodeRestartResult {
+    node: Option<u32>,
+    address: String,
+    outcome: RestartOutcome,
+    duration_secs: u64,
+    message: String,
+}
+
+#[derive(Tabled)]
+struct NodeRestartRow {
+    #[tabled(rename = "NODE")]
+    node: String,
+    #[tabled(rename = "ADDRESS")]
+    address: String,
+    #[tabled(rename = "RESULT")]
+    outcome: String,
+    #[tabled(rename = "DURATION")]
+    duration: String,
+    #[tabled(rename = "MESSAGE")]
+    message: String,
+}
+
+/// A node to run a service command on
+struct Target {
+    node: Option<u32>,
+    address: String,
+    client: Result<EnterpriseClient, String>,
+}
+
+pub async fn list_services(
+    conn_mgr: &ConnectionManager,
+    profile_name: Option<&str>,
+    node: Option<u32>,
+    output_format: OutputFormat,
+    query: Option<&str>,
+) -> CliResult<()> {
+    let client = conn_mgr.create_enterprise_client(profile_name).await?;
+    let client = match node {
+        Some(id) => {
+            let node = NodeHandler::new(client.clone())
+                .get(id)
+                .await
+                .context(format!("Failed to get node {}", id))?;
+            node_client(&client, &node).map_err(|message| RedisCtlError::Validation { message })?
+        }
+        None => client,
+    };
+
+    let services = LocalHandler::new(client).services().await?;
+    let data = handle_output(services, output_format, query)?;
+    print_formatted_output(data, output_format)?;
+    Ok(())
+}
+
+pub async fn restart_service(
+    conn_mgr: &ConnectionManager,
+    profile_name: Option<&str>,
+    service: &str,
+    options: &RestartOptions,
+    output_format: OutputFormat,
+    query: Option<&str>,
+) -> CliResult<()> {
+    let scope = match (options.all_nodes, options.node) {
+        (true, _) => "every node, one at a time".to_string(),
+        (false, Some(id)) => format!("node {}", id),
+        (false, None) => "the node this profile connects to".to_string(),
+    };
+    let prompt = format!("Restart service '{}' on {}?", service, scope);
+    if !options.force && !confirm_action(&prompt)? {
+        notice!("Operation cancelled");
+        return Ok(());
+    }
+
+    let client = conn_mgr.create_enterprise_client(profile_name).await?;
+    let targets = resolve_targets(&client, options).await?;
+    let timeout = Duration::from_secs(options.health_timeout);
+
+    let mut results = Vec::new();
+    let mut failed = false;
+    for target in targets {
+        if failed && !options.continue_on_error {
+            results.push(NodeRestartResult {
+                node: target.node,
+                address: target.address,
+                outcome: RestartOutcome::Skipped,
+                duration_secs: 0,
+                message: "not attempted after an earlier failure".to_string(),
+            });
+            continue;
+        }
+
+        let label = target
+            .node
+            .map(|id| format!("node {} ({})", id, target.address))
+            .unwrap_or_else(|| target.address.clone());
+        crate::progress::step(&format!("Restarting {} on {}...", service, label));
+
+        let started = Instant::now();
+        let result = match &target.client {
+            Ok(node_client) => {
+                restart_on_node(&client, target.node, node_client, service, timeout).await
+            }
+            Err(message) => Err(message.clone()),
+        };
+        let (outcome, message) = match result {
+            Ok(message) => (RestartOutcome::Restarted, message),
+            Err(message) => {
+                failed = true;
+                (RestartOutcome::Failed, message)
+            }
+        };
+        results.push(NodeRestartResult {
+            node: target.node,
+            address: target.address,
+            outcome,
+            duration_secs: started.elapsed().as_secs(),
+            message,
+        });
+    }
+
+    print_results(&results, output_format, query)?;
+
+    let failures = results
+        .iter()
+        .filter(|r| r.outcome == RestartOutcome::Failed)
+        .count();
+    if failures > 0 {
+        return Err(RedisCtlError::api(format!(
+            "Restart of '{}' failed on {} of {} node(s)",
+            service,
+            failures,
+            results.len()
+        )));
+    }
+    Ok(())
+}
+
+/// Restart a service on every node in turn, keeping the databases in full sync
+///
+/// Nothing is restarted unless the cluster is healthy to begin with. After each node, the next
+/// one is only touched once the node is active, the service is running and every database and
+/// shard is back in sync; if that does not happen in time the rollout stops there.
+pub async fn rolling_restart(
+    conn_mgr: &ConnectionManager,
+    profile_name: Option<&str>,
+    service: &str,
+    options: &RollingRestartOptions,
+    output_format: OutputFormat,
+    query: Option<&str>,
+) -> CliResult<()> {
+    let prompt = format!(
+        "Restart service '{}' on every node, one at a time, waiting for databases to sync?",
+        service
+    );
+    if !options.force && !confirm_action(&prompt)? {
+        notice!("Operation cancelled");
+        return Ok(());
+    }
+
+    let client = conn_mgr.create_enterprise_client(profile_name).await?;
+
+    let problems = cluster_problems(&client).await;
+    if !problems.is_empty() {
+        return Err(RedisCtlError::api(format!(
+            "Cluster is degraded, not starting the rolling restart: {}",
+            summarize_problems(&problems)
+        )));
+    }
+
+    let targets = resolve_targets(
+        &client,
+        &RestartOptions {
+            node: None,
+            all_nodes: true,
+            continue_on_error: false,
+            health_timeout: options.health_timeout,
+            force: options.force,
+        },
+    )
+    .await?;
+    let health_timeout = Duration::from_secs(options.health_timeout);
+    let sync_timeout = Duration::from_secs(options.sync_timeout);
+
+    let mut results = Vec::new();
+    let mut aborted_at = None;
+    for target in targets {
+        if aborted_at.is_some() {
+            results.push(NodeRestartResult {
+                node: target.node,
+                address: target.address,
+                outcome: RestartOutcome::Skipped,
+                duration_secs: 0,
+                message: "not attempted after the rollout was aborted".to_string(),
+            });
+            continue;
+        }
+
+        let label = format!(
+            "node {} ({})",
+            target.node.unwrap_or_default(),
+            target.address
+        );
+        crate::progress::step(&format!("Restarting {} on {}...", service, label));
+
+        let started = Instant::now();
+        let result = match &target.client {
+            Ok(node_client) => {
+                match restart_on_node(&client, target.node, node_client, service, health_timeout)
+                    .await
+                {
+                    Ok(message) => {
+                        crate::progress::step(&format!(
+                            "Waiting for databases to sync after {}...",
+                            label
+                        ));
+                        wait_for_sync(&client, sync_timeout)
+                            .await
+                            .map(|synced| format!("{}, {}", message, synced))
+                    }
+                    Err(message) => Err(message),
+                }
+            }
+            Err(message) => Err(message.clone()),
+        };
+        let (outcome, message) = match result {
+            Ok(message) => (RestartOutcome::Restarted, message),
+            Err(message) => {
+                aborted_at = Some(label);
+                (RestartOutcome::Failed, message)
+            }
+        };
+        results.push(NodeRestartResult {
+            node: target.node,
+            address: target.address,
+            outcome,
+            duration_secs: started.elapsed().as_secs(),
+            message,
+        });
+    }
+
+    print_results(&results, output_format, query)?;
+
+    if let Some(label) = aborted_at {
+        return Err(RedisCtlError::api(format!(
+            "Rolling restart of '{}' aborted at {}; the remaining nodes were not restarted",
+            service, label
+        )));
+    }
+    Ok(())
+}
+
+/// Wait until no node, database or shard reports a problem
+async fn wait_for_sync(client: &EnterpriseClient, timeout: Duration) -> Result<String, String> {
+    let started = Instant::now();
+    loop {
+        let problems = cluster_problems(client).await;
+        if problems.is_empty() {
+            return Ok("databases in sync".to_string());
+        }
+        if started.elapsed() >= timeout {
+            return Err(format!(
+                "cluster not back in sync after {}s: {}",
+                timeout.as_secs(),
+                summarize_problems(&problems)
+            ));
+        }
+        tokio::time::sleep(HEALTH_POLL_INTERVAL).await;
+    }
+}
+
+/// Everything standing between the cluster and full health, read from the cluster API
+async fn cluster_problems(client: &EnterpriseClient) -> Vec<String> {
+    let nodes = NodeHandler::new(client.clone());
+    let databases = BdbHandler::new(client.clone());
+    let shards = ShardHandler::new(client.clone());
+    let (nodes, databases, shards) = tokio::join!(nodes.list(), databases.list(), shards.list());
+    match (nodes, databases, shards) {
+        (Ok(nodes), Ok(databases), Ok(shards)) => health_problems(&nodes, &databases, &shards),
+        (Err(e), _, _) | (_, Err(e), _) | (_, _, Err(e)) => {
+            vec![format!("cluster API unavailable ({})", e)]
+        }
+    }
+}
+
+/// Nodes that are not active, databases that are not active, and shards that are down or still
+/// syncing from their master
+fn health_problems(nodes: &[Node], databases: &[Database], shards: &[Shard]) -> Vec<String> {
+    let mut problems = Vec::new();
+    for node in nodes {
+        if node.status != "active" {
+            problems.push(format!("node {} is {}", node.uid, node.status));
+        }
+    }
+    for db in databases {
+        let status = db.status.as_deref().unwrap_or("unknown");
+        if status != "active" {
+            problems.push(format!("database {} ({}) is {}", db.uid, db.name, status));
+        }
+    }
+    for shard in shards {
+        let detailed = shard.extra.get("detailed_status").and_then(Value::as_str);
+        let sync = shard
+            .extra
+            .get("sync")
+            .and_then(|sync| sync.get("status"))
+            .and_then(Value::as_str);
+        let problem = if shard.status != "active" {
+            Some(shard.status.as_str())
+        } else if let Some(detailed) = detailed.filter(|d| *d != "ok") {
+            Some(detailed)
+        } else if shard.role == "slave" && matches!(sync, Some("in_progress" | "link_down")) {
+            Some(if sync == Some("link_down") {
+                "not linked to its master"
+            } else {
+                "syncing"
+            })
+        } else {
+            None
+        };
+        if let Some(problem) = problem {
+            problems.push(format!(
+                "shard {} of database {} is {}",
+                shard.uid, shard.bdb_uid, problem
+            ));
+        }
+    }
+    problems
+}
+
+/// The first few problems, for an error message
+fn summarize_problems(problems: &[String]) -> String {
+    const SHOWN: usize = 3;
+    let mut summary = problems
+        .iter()
+        .take(SHOWN)
+        .cloned()
+        .collect::<Vec<_>>()
+        .join("; ");
+    if problems.len() > SHOWN {
+        summary.push_str(&format!(" (and {} more)", problems.len() - SHOWN));
+    }
+    summary
+}
+
+/// Work out which nodes to restart the service on, in order
+async fn resolve_targets(
+    client: &EnterpriseClient,
+    options: &RestartOptions,
+) -> CliResult<Vec<Target>> {
+    let handler = NodeHandler::new(client.clone());
+    let to_target = |node: &Node| Target {
+        node: Some(node.uid),
+        address: node.addr.clone().unwrap_or_default(),
+        client: node_client(client, node),
+    };
+
+    if options.all_nodes {
+        let mut nodes = handler.list().await.context("Failed to list nodes")?;
+        nodes.sort_by_key(|n| n.uid);
+        Ok(nodes.iter().map(to_target).collect())
+    } else if let Some(id) = options.node {
+        let node = handler
+            .get(id)
+            .await
+            .context(format!("Failed to get node {}", id))?;
+        Ok(vec![to_target(&node)])
+    } else {
+        Ok(vec![Target {
+            node: None,
+            address: client.base_url().to_string(),
+            client: Ok(client.clone()),
+        }])
+    }
+}
+
+/// Client for a node's own REST API
+fn node_client(client: &EnterpriseClient, node: &Node) -> Result<EnterpriseClient, String> {
+    let addr = node
+        .addr
+        .as_deref()
+        .filter(|addr| !addr.is_empty())
+        .ok_or_else(|| format!("node {} has no address", node.uid))?;
+    Ok(client.with_base_url(node_base_url(client.base_url(), addr)))
+}
+
+/// Replace the host of the cluster URL with a node address, keeping scheme, port and path
+fn node_base_url(cluster_url: &str, addr: &str) -> String {
+    let (scheme, rest) = cluster_url
+        .split_once("://")
+        .unwrap_or(("https", cluster_url));
+    let (authority, path) = match rest.find('/') {
+        Some(i) => rest.split_at(i),
+        None => (rest, ""),
+    };
+    let port = authority
+        .rsplit_once(':')
+        .filter(|(host, port)| {
+            !port.is_empty()
+                && port.chars().all(|c| c.is_ascii_digit())
+                && (!host.contains(':') || host.ends_with(']'))
+        })
+        .map(|(_, port)| port);
+
+    let host = if addr.contains(':') && !addr.starts_with('[') {
+        format!("[{}]", addr)
+    } else {
+        addr.to_string()
+    };
+    match port {
+        Some(port) => format!("{}://{}:{}{}", scheme, host, port, path),
+        None => format!("{}://{}{}", scheme, host, path),
+    }
+}
+
+/// Restart the service on one node and wait until the node is healthy again
+async fn restart_on_node(
+    cluster: &EnterpriseClient,
+    node: Option<u32>,
+    node_client: &EnterpriseClient,
+    service: &str,
+    timeout: Duration,
+) -> Result<String, String> {
+    LocalHandler::new(node_client.clone())
+        .restart_services(&[service])
+        .await
+        .map_err(|e| format!("restart request failed: {}", e))?;
+
+    let started = Instant::now();
+    loop {
+        // Requests can fail while the node's own services come back up; keep polling
+        let service_status = match LocalHandler::new(node_client.clone()).services().await {
+            Ok(services) => {
+                local_service_status(&services, service).unwrap_or_else(|| "missing".to_string())
+            }
+            Err(_) => "unreachable".to_string(),
+        };
+        let node_status = match node {
+            Some(id) => match NodeHandler::new(cluster.clone()).get(id).await {
+                Ok(node) => node.status,
+                Err(_) => "unreachable".to_string(),
+            },
+            None => "active".to_string(),
+        };
+
+        if service_status.eq_ignore_ascii_case("running") && node_status == "active" {
+            return Ok(format!("{} running, node active", service));
+        }
+        if started.elapsed() >= timeout {
+            return Err(format!(
+                "not healthy after {}s: service {}, node {}",
+                timeout.as_secs(),
+                service_status,
+                node_status
+            ));
+        }
+        tokio::time::sleep(HEALTH_POLL_INTERVAL).await;
+    }
+}
+
+/// Status of a service in a `/v1/local/services` response
+///
+/// The response maps service names to objects with a `status` field; older versions return a
+/// list of objects with a `name` field, or the status string directly.
+fn local_service_status(services: &Value, name: &str) -> Option<String> {
+    let entry = match services {
+        Value::Object(map) => map.get(name)?,
+        Value::Array(list) => list
+            .iter()
+            .find(|s| s.get("name").and_then(Value::as_str) == Some(name))?,
+        _ => return None,
+    };
+    match entry {
+        Value::String(status) => Some(status.clone()),
+        other => other
+            .get("status")
+            .or_else(|| other.get("state"))
+            .and_then(Value::as_str)
+            .map(str::to_string),
+    }
+}
+
+fn print_results(
+    results: &[NodeRestartResult],
+    output_format: OutputFormat,
+    query: Option<&str>,
+) -> CliResult<()> {
+    match output_format {
+        OutputFormat::Auto | OutputFormat::Table if query.is_none() => {
+            let rows: Vec<NodeRestartRow> = results
+                .iter()
+                .map(|r| NodeRestartRow {
+                    node: r
+                        .node
+                        .map(|id| id.to_string())
+                        .unwrap_or_else(|| "-".to_string()),
+                    address: r.address.clone(),
+                    outcome: match r.outcome {
+                        RestartOutcome::Restarted => "restarted",
+                        RestartOutcome::Failed => "FAILED",
+                        RestartOutcome::Skipped => "skipped",
+                    }
+                    .to_string(),
+                    duration: format!("{}s", r.duration_secs),
+                    message: r.message.clone(),
+                })
+                .collect();
+            let mut table = Table::new(rows);
+            table.with(Style::modern());
+            println!("{}", table);
+        }
+        _ => {
+            let json = serde_json::to_value(results).context("Failed to serialize results")?;
+            let data = handle_output(json, output_format, query)?;
+            print_formatted_output(data, output_format)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_node_base_url() {
+        assert_eq!(
+            node_base_url("https://cluster.example.com:9443", "10.0.0.2"),
+            "https://10.0.0.2:9443"
+        );
+        assert_eq!(
+            node_base_url("https://cluster.example.com:9443/api", "node2.local"),
+            "https://node2.local:9443/api"
+        );
+        assert_eq!(
+            node_base_url("http://cluster.example.com", "10.0.0.2"),
+            "http://10.0.0.2"
+        );
+        assert_eq!(
+            node_base_url("https://[fd00::1]:9443", "fd00::2"),
+            "https://[fd00::2]:9443"
+        );
+    }
+
+    #[test]
+    fn test_local_service_status() {
+        let by_name = json!({
+            "cm_server": {"status": "RUNNING", "uptime": "0:01:02"},
+            "mdns_server": "STOPPED"
+        });
+        assert_eq!(
+            local_service_status(&by_name, "cm_server").as_deref(),
+            Some("RUNNING")
+        );
+        assert_eq!(
+            local_service_status(&by_name, "mdns_server").as_deref(),
+            Some("STOPPED")
+        );
+        assert_eq!(local_service_status(&by_name, "pdns_server"), None);
+
+        let list = json!([{"name": "cm_server", "state": "starting"}]);
+        assert_eq!(
+            local_service_status(&list, "cm_server").as_deref(),
+            Some("starting")
+        );
+    }
+
+    #[test]
+    fn test_health_problems() {
+        let nodes: Vec<Node> = serde_json::from_value(json!([
+            {"uid": 1, "status": "active"},
+            {"uid": 2, "status": "active"}
+        ]))
+        .unwrap();
+        let databases: Vec<Database> = serde_json::from_value(json!([
+            {"uid": 1, "name": "cache", "status": "active"}
+        ]))
+        .unwrap();
+        let shard = |uid: &str, role: &str, extra: Value| {
+            let mut shard = json!({
+                "uid": uid, "bdb_uid": 1, "node_uid": 1, "role": role, "status": "active"
+            });
+            shard
+                .as_object_mut()
+                .unwrap()
+                .extend(extra.as_object().unwrap().clone());
+            serde_json::from_value::<Shard>(shard).unwrap()
+        };
+        let in_sync = vec![
+            shard("1", "master", json!({"detailed_status": "ok"})),
+            shard("2", "slave", json!({"sync": {"status": "idle"}})),
+        ];
+        assert!(health_problems(&nodes, &databases, &in_sync).is_empty());
+
+        let syncing = vec![
+            shard("1", "master", json!({"detailed_status": "loading"})),
+            shard("2", "slave", json!({"sync": {"status": "in_progress"}})),
+        ];
+        let mut degraded_nodes = nodes.clone();
+        degraded_nodes[1].status = "down".to_string();
+        assert_eq!(
+            health_problems(&degraded_nodes, &databases, &syncing),
+            vec![
+                "node 2 is down",
+                "shard 1 of database 1 is loading",
+                "shard 2 of database 1 is syncing"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_summarize_problems() {
+        let problems: Vec<String> = (1..=5).map(|i| format!("node {} is down", i)).collect();
+        assert_eq!(summarize_problems(&problems[..1]), "node 1 is down");
+        assert_eq!(
+            summarize_problems(&problems),
+            "node 1 is down; node 2 is down; node 3 is down (and 2 more)"
+        );
+    }
+}
@@ -0,0 +1,440 @@
+//! Shard listing and placement overview for Enterprise
+//!
+//! `placement` counts master and replica shards per node so an uneven distribution, or a master
+//! sharing a node with its own replica, is visible at a glance.
+
+#![allow(dead_code)]
+
+use super::utils::*;
+use crate::cli::{EnterpriseShardCommands, OutputFormat};
+use crate::connection::ConnectionManager;
+use crate::error::Result as CliResult;
+use crate::output::notice;
+use crate::units::format_bytes;
+use anyhow::Context;
+use redis_enterprise::nodes::NodeHandler;
+use redis_enterprise::{Node, Shard, ShardHandler};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use tabled::{Table, Tabled, settings::Style};
+
+pub async fn handle_shard_command(
+    conn_mgr: &ConnectionManager,
+    profile_name: Option<&str>,
+    command: &EnterpriseShardCommands,
+    output_format: OutputFormat,
+    query: Option<&str>,
+) -> CliResult<()> {
+    match command {
+        EnterpriseShardCommands::List {
+            node,
+            database,
+            by_database,
+        } => {
+            list_shards(
+                conn_mgr,
+                profile_name,
+                *node,
+                *database,
+                *by_database,
+                output_format,
+                query,
+            )
+            .await
+        }
+        EnterpriseShardCommands::Placement { database } => {
+            show_placement(conn_mgr, profile_name, *database, output_format, query).await
+        }
+    }
+}
+
+/// Shards, optionally limited to one node and/or one database
+async fn fetch_shards(
+    conn_mgr: &ConnectionManager,
+    profile_name: Option<&str>,
+    node: Option<u32>,
+    database: Option<u32>,
+) -> CliResult<Vec<Shard>> {
+    let client = conn_mgr.create_enterprise_client(profile_name).await?;
+    let handler = ShardHandler::new(client);
+    let shards = match (database, node) {
+        (Some(bdb), _) => handler.list_by_database(bdb).await,
+        (None, Some(node)) => handler.list_by_node(node).await,
+        (None, None) => handler.list().await,
+    }
+    .context("Failed to list shards")?;
+
+    // Filter again: a database listing is not limited to the node, and older clusters may
+    // ignore the scope of the request
+    let mut shards: Vec<Shard> = shards
+        .into_iter()
+        .filter(|s| node.is_none_or(|n| s.node_uid == n))
+        .filter(|s| database.is_none_or(|d| s.bdb_uid == d))
+        .collect();
+    shards.sort_by(|a, b| {
+        (a.bdb_uid, !is_master(a), numeric_uid(&a.uid)).cmp(&(
+            b.bdb_uid,
+            !is_master(b),
+            numeric_uid(&b.uid),
+        ))
+    });
+    Ok(shards)
+}
+
+fn is_master(shard: &Shard) -> bool {
+    shard.role.eq_ignore_ascii_case("master")
+}
+
+fn numeric_uid(uid: &str) -> u64 {
+    uid.parse().unwrap_or(u64::MAX)
+}
+
+#[derive(Tabled)]
+struct ShardRow {
+    #[tabled(rename = "SHARD")]
+    uid: String,
+    #[tabled(rename = "DATABASE")]
+    database: u32,
+    #[tabled(rename = "NODE")]
+    node: u32,
+    #[tabled(rename = "ROLE")]
+    role: String,
+    #[tabled(rename = "STATUS")]
+    status: String,
+    #[tabled(rename = "SLOTS")]
+    slots: String,
+    #[tabled(rename = "MEMORY")]
+    memory: String,
+}
+
+impl From<&Shard> for ShardRow {
+    fn from(shard: &Shard) -> Self {
+        ShardRow {
+            uid: shard.uid.clone(),
+            database: shard.bdb_uid,
+            node: shard.node_uid,
+            role: if is_master(shard) {
+                "master".to_string()
+            } else {
+                "replica".to_string()
+            },
+            status: shard.status.clone(),
+            slots: shard.slots.clone().unwrap_or_else(|| "-".to_string()),
+            memory: shard
+                .used_memory
+                .map(format_bytes)
+                .unwrap_or_else(|| "-".to_string()),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct DatabaseShards<'a> {
+    database: u32,
+    masters: usize,
+    replicas: usize,
+    shards: Vec<&'a Shard>,
+}
+
+fn group_by_database(shards: &[Shard]) -> Vec<DatabaseShards<'_>> {
+    let mut groups: BTreeMap<u32, Vec<&Shard>> = BTreeMap::new();
+    for shard in shards {
+        groups.entry(shard.bdb_uid).or_default().push(shard);
+    }
+    groups
+        .into_iter()
+        .map(|(database, shards)| {
+            let masters = shards.iter().filter(|s| is_master(s)).count();
+            DatabaseShards {
+                database,
+                masters,
+                replicas: shards.len() - masters,
+                shards,
+            }
+        })
+        .collect()
+}
+
+async fn list_shards(
+    conn_mgr: &ConnectionManager,
+    profile_name: Option<&str>,
+    node: Option<u32>,
+    database: Option<u32>,
+    by_database: bool,
+    output_format: OutputFormat,
+    query: Option<&str>,
+) -> CliResult<()> {
+    let shards = fetch_shards(conn_mgr, profile_name, node, database).await?;
+
+    match output_format {
+        OutputFormat::Auto | OutputFormat::Table if query.is_none() => {
+            if shards.is_empty() {
+                notice!("No shards found");
+            } else if by_database {
+                for (i, group) in group_by_database(&shards).iter().enumerate() {
+                    if i > 0 {
+                        println!();
+                    }
+                    println!(
+                        "Database {} ({} master, {} replica)",
+                        group.database, group.masters, group.replicas
+                    );
+                    let rows: Vec<ShardRow> =
+                        group.shards.iter().map(|s| ShardRow::from(*s)).collect();
+                    let mut table = Table::new(rows);
+                    table.with(Style::blank());
+                    println!("{}", table);
+                }
+            } else {
+                let rows: Vec<ShardRow> = shards.iter().map(ShardRow::from).collect();
+                let mut table = Table::new(rows);
+                table.with(Style::blank());
+                println!("{}", table);
+            }
+        }
+        _ => {
+            let json = if by_database {
+                serde_json::to_value(group_by_database(&shards))
+            } else {
+                serde_json::to_value(&shards)
+            }
+            .context("Failed to serialize shards")?;
+            let data = handle_output(json, output_format, query)?;
+            print_formatted_output(data, output_format)?;
+        }
+    }
+    Ok(())
+}
+
+/// Shard counts on one node
+#[derive(Debug, Serialize)]
+struct NodePlacement {
+    node: u32,
+    address: Option<String>,
+    masters: usize,
+    replicas: usize,
+    used_memory: u64,
+}
+
+#[derive(Debug, Serialize)]
+struct Placement {
+    nodes: Vec<NodePlacement>,
+    /// Master counts, and shard counts, differ by at most one between nodes
+    balanced: bool,
+    warnings: Vec<String>,
+}
+
+/// Per-node distribution, including nodes that hold no shards
+fn compute_placement(nodes: &[Node], shards: &[Shard]) -> Placement {
+    let mut by_node: BTreeMap<u32, NodePlacement> = nodes
+        .iter()
+        .map(|n| {
+            (
+                n.uid,
+                NodePlacement {
+                    node: n.uid,
+                    address: n.addr.clone(),
+                    masters: 0,
+                    replicas: 0,
+                    used_memory: 0,
+                },
+            )
+        })
+        .collect();
+    for shard in shards {
+        let entry = by_node
+            .entry(shard.node_uid)
+            .or_insert_with(|| NodePlacement {
+                node: shard.node_uid,
+                address: None,
+                masters: 0,
+                replicas: 0,
+                used_memory: 0,
+            });
+        if is_master(shard) {
+            entry.masters += 1;
+        } else {
+            entry.replicas += 1;
+        }
+        entry.used_memory += shard.used_memory.unwrap_or(0);
+    }
+    let nodes: Vec<NodePlacement> = by_node.into_values().collect();
+
+    let spread = |count: fn(&NodePlacement) -> usize| {
+        let counts = nodes.iter().map(count);
+        counts.clone().max().unwrap_or(0) - counts.min().unwrap_or(0)
+    };
+    let balanced = spread(|n| n.masters) <= 1 && spread(|n| n.masters + n.replicas) <= 1;
+
+    Placement {
+        nodes,
+        balanced,
+        warnings: colocated_replicas(shards),
+    }
+}
+
+/// Masters that share a node with a replica of the same slots, which a node failure would lose
+fn colocated_replicas(shards: &[Shard]) -> Vec<String> {
+    let mut warnings = Vec::new();
+    for master in shards.iter().filter(|s| is_master(s)) {
+        let colocated = shards.iter().any(|s| {
+            !is_master(s)
+                && s.bdb_uid == master.bdb_uid
+                && s.node_uid == master.node_uid
+                && s.slots.is_some()
+                && s.slots == master.slots
+        });
+        if colocated {
+            warnings.push(format!(
+                "Database {}: master shard {} and its replica are both on node {}",
+                master.bdb_uid, master.uid, master.node_uid
+            ));
+        }
+    }
+    warnings
+}
+
+#[derive(Tabled)]
+struct PlacementRow {
+    #[tabled(rename = "NODE")]
+    node: u32,
+    #[tabled(rename = "ADDRESS")]
+    address: String,
+    #[tabled(rename = "MASTERS")]
+    masters: usize,
+    #[tabled(rename = "REPLICAS")]
+    replicas: usize,
+    #[tabled(rename = "MEMORY")]
+    memory: String,
+    #[tabled(rename = "DISTRIBUTION")]
+    distribution: String,
+}
+
+/// `M` per master and `r` per replica
+fn distribution_bar(placement: &NodePlacement) -> String {
+    format!(
+        "{}{}",
+        "M".repeat(placement.masters),
+        "r".repeat(placement.replicas)
+    )
+}
+
+async fn show_placement(
+    conn_mgr: &ConnectionManager,
+    profile_name: Option<&str>,
+    database: Option<u32>,
+    output_format: OutputFormat,
+    query: Option<&str>,
+) -> CliResult<()> {
+    let client = conn_mgr.create_enterprise_client(profile_name).await?;
+    let nodes = NodeHandler::new(client)
+        .list()
+        .await
+        .context("Failed to list nodes")?;
+    let shards = fetch_shards(conn_mgr, profile_name, None, database).await?;
+    let placement = compute_placement(&nodes, &shards);
+
+    match output_format {
+        OutputFormat::Auto | OutputFormat::Table if query.is_none() => {
+            let rows: Vec<PlacementRow> = placement
+                .nodes
+                .iter()
+                .map(|n| PlacementRow {
+                    node: n.node,
+                    address: n.address.clone().unwrap_or_else(|| "-".to_string()),
+                    masters: n.masters,
+                    replicas: n.replicas,
+                    memory: format_bytes(n.used_memory),
+                    distribution: distribution_bar(n),
+                })
+                .collect();
+            let mut table = Table::new(rows);
+            table.with(Style::blank());
+            println!("{}", table);
+            println!();
+            if placement.balanced {
+                println!("Shards are evenly distributed across nodes");
+            } else {
+                println!("Imbalanced: shard counts differ by more than one between nodes");
+            }
+            for warning in &placement.warnings {
+                println!("Warning: {}", warning);
+            }
+        }
+        _ => {
+            let json = serde_json::to_value(&placement).context("Failed to serialize placement")?;
+            let data = handle_output(json, output_format, query)?;
+            print_formatted_output(data, output_format)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn shard(uid: &str, bdb: u32, node: u32, role: &str, slots: &str) -> Shard {
+        serde_json::from_value(json!({
+            "uid": uid,
+            "bdb_uid": bdb,
+            "node_uid": node,
+            "role": role,
+            "status": "active",
+            "slots": slots,
+            "used_memory": 1024
+        }))
+        .unwrap()
+    }
+
+    fn node(uid: u32) -> Node {
+        serde_json::from_value(json!({"uid": uid, "status": "active"})).unwrap()
+    }
+
+    #[test]
+    fn test_compute_placement() {
+        let shards = vec![
+            shard("1", 1, 1, "master", "0-8191"),
+            shard("2", 1, 2, "slave", "0-8191"),
+            shard("3", 1, 2, "master", "8192-16383"),
+            shard("4", 1, 1, "slave", "8192-16383"),
+        ];
+        let placement = compute_placement(&[node(1), node(2)], &shards);
+        assert!(placement.balanced);
+        assert!(placement.warnings.is_empty());
+        assert_eq!(placement.nodes[0].masters, 1);
+        assert_eq!(placement.nodes[0].used_memory, 2048);
+
+        // A third node without shards makes the cluster uneven
+        let placement = compute_placement(&[node(1), node(2), node(3)], &shards);
+        assert!(!placement.balanced);
+        assert_eq!(placement.nodes[2].masters + placement.nodes[2].replicas, 0);
+    }
+
+    #[test]
+    fn test_colocated_replicas() {
+        let shards = vec![
+            shard("1", 1, 1, "master", "0-16383"),
+            shard("2", 1, 1, "slave", "0-16383"),
+            shard("3", 2, 1, "master", "0-16383"),
+            shard("4", 2, 2, "slave", "0-16383"),
+        ];
+        let warnings = colocated_replicas(&shards);
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("master shard 1"));
+    }
+
+    #[test]
+    fn test_group_by_database() {
+        let shards = vec![
+            shard("1", 1, 1, "master", "0-16383"),
+            shard("2", 1, 2, "slave", "0-16383"),
+            shard("3", 2, 1, "master", "0-16383"),
+        ];
+        let groups = group_by_database(&shards);
+        assert_eq!(groups.len(), 2);
+        assert_eq!((groups[0].masters, groups[0].replicas), (1, 1));
+        assert_eq!(groups[1].shards.len(), 1);
+    }
+}
@@ -0,0 +1,34 @@
+//! Stats command router for Enterprise
+
+#![allow(dead_code)]
+
+use crate::cli::EnterpriseStatsCommands;
+use crate::connection::ConnectionManager;
+use crate::error::Result as CliResult;
+
+use super::stats_push::{self, Sink};
+
+pub async fn handle_stats_command(
+    conn_mgr: &ConnectionManager,
+    profile_name: Option<&str>,
+    command: &EnterpriseStatsCommands,
+) -> CliResult<()> {
+    match command {
+        EnterpriseStatsCommands::Push {
+            statsd,
+            graphite,
+            stdout: _,
+            interval,
+            prefix,
+            count,
+        } => {
+            let sink = match (statsd, graphite) {
+                (Some(address), _) => Sink::Statsd(address.clone()),
+                (_, Some(address)) => Sink::Graphite(address.clone()),
+                _ => Sink::Stdout,
+            };
+            stats_push::run_stats_push(conn_mgr, profile_name, sink, prefix, *interval, *count)
+                .await
+        }
+    }
+}
@@ -0,0 +1,209 @@
+//! Sparkline charts of database stats intervals
+//!
+//! Draws one line of block characters per metric over the intervals returned by the stats API,
+//! for a quick look at trends without a Grafana dashboard.
+
+#![allow(dead_code)]
+
+use redis_enterprise::StatsResponse;
+use serde::Serialize;
+use serde_json::Value;
+use tabled::{Table, Tabled, settings::Style};
+
+use crate::units::format_bytes;
+
+/// Metrics charted when none are selected, if the intervals carry them
+pub const DEFAULT_METRICS: &[&str] = &[
+    "total_req",
+    "avg_latency",
+    "used_memory",
+    "conns",
+    "ingress_bytes",
+    "egress_bytes",
+];
+
+/// Longest sparkline drawn; longer series are averaged into this many buckets
+const MAX_WIDTH: usize = 60;
+
+const BLOCKS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// One metric over the window
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MetricChart {
+    pub metric: String,
+    pub values: Vec<f64>,
+    pub min: f64,
+    pub max: f64,
+    pub last: f64,
+    pub sparkline: String,
+}
+
+#[derive(Tabled)]
+struct ChartRow {
+    #[tabled(rename = "METRIC")]
+    metric: String,
+    #[tabled(rename = "CHART")]
+    chart: String,
+    #[tabled(rename = "MIN")]
+    min: String,
+    #[tabled(rename = "MAX")]
+    max: String,
+    #[tabled(rename = "LAST")]
+    last: String,
+}
+
+/// Chart the selected metrics, or the defaults present in the intervals when none are selected
+///
+/// Metrics without a numeric value in any interval are left out.
+pub fn charts(stats: &StatsResponse, metrics: &[String]) -> Vec<MetricChart> {
+    let selected: Vec<&str> = if metrics.is_empty() {
+        DEFAULT_METRICS.to_vec()
+    } else {
+        metrics.iter().map(String::as_str).collect()
+    };
+
+    selected
+        .into_iter()
+        .filter_map(|name| {
+            let values: Vec<f64> = stats
+                .intervals
+                .iter()
+                .filter_map(|interval| metric(&interval.metrics, name))
+                .collect();
+            let last = *values.last()?;
+            Some(MetricChart {
+                metric: name.to_string(),
+                min: values.iter().copied().fold(f64::INFINITY, f64::min),
+                max: values.iter().copied().fold(f64::NEG_INFINITY, f64::max),
+                last,
+                sparkline: sparkline(&values),
+                values,
+            })
+        })
+        .collect()
+}
+
+/// Numeric value of a metric in an interval
+fn metric(metrics: &Value, name: &str) -> Option<f64> {
+    match metrics.get(name)? {
+        Value::Number(n) => n.as_f64(),
+        Value::String(s) => s.parse().ok(),
+        _ => None,
+    }
+}
+
+/// Draw a series as block characters scaled between its minimum and maximum
+pub fn sparkline(values: &[f64]) -> String {
+    let values = downsample(values, MAX_WIDTH);
+    let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    let range = max - min;
+
+    values
+        .iter()
+        .map(|value| {
+            if range <= 0.0 {
+                return BLOCKS[0];
+            }
+            let level = ((value - min) / range * (BLOCKS.len() - 1) as f64).round() as usize;
+            BLOCKS[level.min(BLOCKS.len() - 1)]
+        })
+        .collect()
+}
+
+/// Average a series into at most `width` consecutive buckets
+fn downsample(values: &[f64], width: usize) -> Vec<f64> {
+    if values.len() <= width {
+        return values.to_vec();
+    }
+    (0..width)
+        .map(|bucket| {
+            let start = bucket * values.len() / width;
+            let end = (bucket + 1) * values.len() / width;
+            let slice = &values[start..end];
+            slice.iter().sum::<f64>() / slice.len() as f64
+        })
+        .collect()
+}
+
+/// Render the charts as a table
+pub fn chart_table(charts: &[MetricChart]) -> String {
+    let rows: Vec<ChartRow> = charts
+        .iter()
+        .map(|chart| {
+            let fmt = |value: f64| format_value(&chart.metric, value);
+            ChartRow {
+                metric: chart.metric.clone(),
+                chart: chart.sparkline.clone(),
+                min: fmt(chart.min),
+                max: fmt(chart.max),
+                last: fmt(chart.last),
+            }
+        })
+        .collect();
+    let mut table = Table::new(rows);
+    table.with(Style::blank());
+    table.to_string()
+}
+
+/// Show memory and network metrics as sizes, the rest as numbers
+fn format_value(metric: &str, value: f64) -> String {
+    if metric.contains("memory") || metric.ends_with("_bytes") {
+        format_bytes(value.max(0.0) as u64)
+    } else if value.fract() == 0.0 {
+        format!("{}", value)
+    } else {
+        format!("{:.2}", value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn stats() -> StatsResponse {
+        serde_json::from_value(json!({
+            "intervals": [
+                {"time": "2024-01-01T00:00:00Z", "metrics": {"total_req": 0, "used_memory": 1048576}},
+                {"time": "2024-01-01T00:05:00Z", "metrics": {"total_req": 50, "used_memory": 1048576}},
+                {"time": "2024-01-01T00:10:00Z", "metrics": {"total_req": 100, "used_memory": "2097152"}}
+            ]
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_sparkline() {
+        assert_eq!(sparkline(&[0.0, 50.0, 100.0]), "▁▅█");
+        assert_eq!(sparkline(&[3.0, 3.0]), "▁▁");
+        assert_eq!(sparkline(&[]), "");
+        assert_eq!(sparkline(&vec![1.0; 500]).chars().count(), MAX_WIDTH);
+    }
+
+    #[test]
+    fn test_charts_default_metrics() {
+        let charts = charts(&stats(), &[]);
+        let names: Vec<&str> = charts.iter().map(|c| c.metric.as_str()).collect();
+        assert_eq!(names, vec!["total_req", "used_memory"]);
+        assert_eq!(charts[0].sparkline, "▁▅█");
+        assert_eq!(
+            (charts[0].min, charts[0].max, charts[0].last),
+            (0.0, 100.0, 100.0)
+        );
+        assert_eq!(charts[1].last, 2097152.0);
+
+        let table = chart_table(&charts);
+        assert!(table.contains("2.0 MB"));
+    }
+
+    #[test]
+    fn test_charts_selected_metrics() {
+        let charts = charts(
+            &stats(),
+            &["used_memory".to_string(), "missing".to_string()],
+        );
+        assert_eq!(charts.len(), 1);
+        assert_eq!(charts[0].metric, "used_memory");
+    }
+}
@@ -0,0 +1,149 @@
+//! CSV export of stats intervals for spreadsheets
+//!
+//! `--format csv` writes the long layout, one `time,metric,value` row per metric and interval.
+//! `--format wide-csv` pivots it into one row per interval with a column per metric, which
+//! imports into Excel or Google Sheets as a ready-to-chart table.
+
+#![allow(dead_code)]
+
+use std::collections::BTreeSet;
+
+use serde_json::{Map, Value};
+
+use crate::cli::StatsExportFormat;
+use crate::commands::cloud::utils::csv_field;
+
+/// Interval fields that describe the interval rather than measure something
+const TIME_FIELDS: &[&str] = &["interval", "stime", "etime", "time"];
+
+/// Render a stats response (`{"intervals": [...]}`) in the requested layout
+pub fn render(stats: &Value, format: StatsExportFormat) -> String {
+    match format {
+        StatsExportFormat::Csv => long_csv(stats),
+        StatsExportFormat::WideCsv => wide_csv(stats),
+    }
+}
+
+/// One row per interval and metric
+pub fn long_csv(stats: &Value) -> String {
+    let mut out = String::from("time,metric,value\n");
+    for interval in intervals(stats) {
+        let time = csv_field(&time_of(interval));
+        for (name, value) in metrics_of(interval) {
+            out.push_str(&format!("{},{},{}\n", time, csv_field(name), cell(value)));
+        }
+    }
+    out
+}
+
+/// One row per interval with a column per metric, in name order
+///
+/// Metrics missing from an interval are left blank, so every row has the same columns.
+pub fn wide_csv(stats: &Value) -> String {
+    let intervals = intervals(stats);
+    let columns: BTreeSet<&str> = intervals
+        .iter()
+        .flat_map(|interval| metrics_of(interval).map(|(name, _)| name.as_str()))
+        .collect();
+
+    let mut out = std::iter::once("time".to_string())
+        .chain(columns.iter().map(|name| csv_field(name)))
+        .collect::<Vec<_>>()
+        .join(",");
+    out.push('\n');
+    for interval in intervals {
+        let metrics: Map<String, Value> = metrics_of(interval)
+            .map(|(name, value)| (name.clone(), value.clone()))
+            .collect();
+        let row: Vec<String> = std::iter::once(csv_field(&time_of(interval)))
+            .chain(
+                columns
+                    .iter()
+                    .map(|name| metrics.get(*name).map(cell).unwrap_or_default()),
+            )
+            .collect();
+        out.push_str(&row.join(","));
+        out.push('\n');
+    }
+    out
+}
+
+fn intervals(stats: &Value) -> Vec<&Value> {
+    stats
+        .get("intervals")
+        .and_then(Value::as_array)
+        .or_else(|| stats.as_array())
+        .map(|intervals| intervals.iter().collect())
+        .unwrap_or_default()
+}
+
+/// The interval's start time as the API reports it, falling back to its end time
+fn time_of(interval: &Value) -> String {
+    ["stime", "time", "etime"]
+        .iter()
+        .find_map(|key| interval.get(*key).and_then(Value::as_str))
+        .unwrap_or_default()
+        .to_string()
+}
+
+/// Scalar metrics of an interval, whether at the top level or under `metrics`
+fn metrics_of(interval: &Value) -> impl Iterator<Item = (&String, &Value)> {
+    let metrics = match interval.get("metrics") {
+        Some(Value::Object(metrics)) => Some(metrics),
+        _ => interval.as_object(),
+    };
+    metrics.into_iter().flatten().filter(|(name, value)| {
+        !TIME_FIELDS.contains(&name.as_str())
+            && matches!(value, Value::Number(_) | Value::String(_) | Value::Bool(_))
+    })
+}
+
+fn cell(value: &Value) -> String {
+    match value {
+        Value::String(s) => csv_field(s),
+        other => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn stats() -> Value {
+        json!({"intervals": [
+            {"interval": "1hour", "stime": "2026-01-01T00:00:00Z", "etime": "2026-01-01T01:00:00Z",
+             "total_req": 100, "used_memory": 2048.5},
+            {"interval": "1hour", "stime": "2026-01-01T01:00:00Z", "etime": "2026-01-01T02:00:00Z",
+             "total_req": 120, "conns": "7"}
+        ]})
+    }
+
+    #[test]
+    fn test_wide_csv_pivots_metrics_into_columns() {
+        assert_eq!(
+            wide_csv(&stats()),
+            "time,conns,total_req,used_memory\n\
+             2026-01-01T00:00:00Z,,100,2048.5\n\
+             2026-01-01T01:00:00Z,7,120,\n"
+        );
+    }
+
+    #[test]
+    fn test_long_csv_has_a_row_per_metric() {
+        let csv = long_csv(&json!({"intervals": [
+            {"time": "2026-01-01T00:00:00Z", "metrics": {"cpu_user": 0.5, "note": "a,b"}}
+        ]}));
+        assert_eq!(
+            csv,
+            "time,metric,value\n\
+             2026-01-01T00:00:00Z,cpu_user,0.5\n\
+             2026-01-01T00:00:00Z,note,\"a,b\"\n"
+        );
+    }
+
+    #[test]
+    fn test_wide_csv_without_intervals_is_a_header() {
+        assert_eq!(wide_csv(&json!({})), "time\n");
+    }
+}
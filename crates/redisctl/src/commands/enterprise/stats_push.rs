@@ -0,0 +1,374 @@
+//! Pushing cluster, database and node stats to StatsD or Graphite
+//!
+//! Each round reads the `stats/last` endpoints and emits every numeric metric as a gauge named
+//! `<prefix>.cluster.<metric>`, `<prefix>.bdb.<uid>.<metric>` or `<prefix>.node.<uid>.<metric>`.
+//! StatsD receives UDP datagrams; Graphite receives its plaintext protocol over TCP.
+
+#![allow(dead_code)]
+
+use crate::connection::ConnectionManager;
+use crate::error::{RedisCtlError, Result as CliResult};
+use crate::output::notice;
+use serde_json::{Map, Value};
+use std::net::{Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::time::Duration;
+use tokio::io::AsyncWriteExt;
+use tokio::net::{TcpStream, UdpSocket};
+
+/// Largest StatsD datagram; stays under a typical 1500-byte MTU
+const MAX_DATAGRAM: usize = 1432;
+
+/// Fields of a stats interval that describe the interval rather than measure anything
+const NON_METRIC_FIELDS: &[&str] = &["uid", "time", "stime", "etime", "interval"];
+
+/// Where gauges are sent
+#[derive(Debug, Clone, PartialEq)]
+pub enum Sink {
+    Statsd(String),
+    Graphite(String),
+    /// Print the lines instead of sending them
+    Stdout,
+}
+
+/// One gauge value
+#[derive(Debug, Clone, PartialEq)]
+pub struct Gauge {
+    pub name: String,
+    pub value: f64,
+}
+
+pub async fn run_stats_push(
+    conn_mgr: &ConnectionManager,
+    profile_name: Option<&str>,
+    sink: Sink,
+    prefix: &str,
+    interval_secs: u64,
+    count: Option<u64>,
+) -> CliResult<()> {
+    if interval_secs == 0 {
+        return Err(RedisCtlError::Validation {
+            message: "--interval must be at least 1s".to_string(),
+        });
+    }
+    let client = conn_mgr.create_enterprise_client(profile_name).await?;
+    let socket = match &sink {
+        Sink::Statsd(address) => Some(open_statsd_socket(address).await?),
+        _ => None,
+    };
+
+    let mut ticker = tokio::time::interval(Duration::from_secs(interval_secs));
+    ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    let mut rounds = 0;
+    loop {
+        tokio::select! {
+            _ = ticker.tick() => {}
+            _ = tokio::signal::ctrl_c() => break,
+        }
+
+        let (cluster, bdbs, nodes) = tokio::join!(
+            client.get_raw("/v1/cluster/stats/last"),
+            client.get_raw("/v1/bdbs/stats/last"),
+            client.get_raw("/v1/nodes/stats/last"),
+        );
+        let mut gauges = Vec::new();
+        for (scope, result) in [("cluster", cluster), ("bdb", bdbs), ("node", nodes)] {
+            match result {
+                Ok(stats) => gauges.extend(collect_gauges(prefix, scope, &stats)),
+                Err(e) => notice!("Warning: failed to read {} stats: {}", scope, e),
+            }
+        }
+
+        let timestamp = chrono::Utc::now().timestamp();
+        let sent = match (&sink, &socket) {
+            (Sink::Statsd(_), Some(socket)) => send_statsd(socket, &gauges).await,
+            (Sink::Graphite(address), _) => send_graphite(address, &gauges, timestamp).await,
+            _ => {
+                for gauge in &gauges {
+                    println!("{}", statsd_line(gauge));
+                }
+                Ok(())
+            }
+        };
+        match sent {
+            Ok(()) if sink != Sink::Stdout => {
+                notice!("Pushed {} gauges", gauges.len());
+            }
+            Ok(()) => {}
+            Err(e) => notice!("Warning: failed to push stats: {}", e),
+        }
+
+        rounds += 1;
+        if count.is_some_and(|count| rounds >= count) {
+            break;
+        }
+    }
+    Ok(())
+}
+
+/// UDP socket connected to the StatsD target
+///
+/// The target is resolved first so the socket is bound to the wildcard address of the same
+/// family; an IPv4 socket cannot send to an IPv6 target and vice versa.
+async fn open_statsd_socket(address: &str) -> CliResult<UdpSocket> {
+    let resolve_error = |detail: String| {
+        RedisCtlError::network(format!(
+            "Failed to resolve StatsD address {}: {}",
+            address, detail
+        ))
+    };
+    let target = tokio::net::lookup_host(address)
+        .await
+        .map_err(|e| resolve_error(e.to_string()))?
+        .next()
+        .ok_or_else(|| resolve_error("no addresses found".to_string()))?;
+    let socket = UdpSocket::bind(wildcard_for(&target))
+        .await
+        .map_err(|e| RedisCtlError::network(format!("Failed to open UDP socket: {}", e)))?;
+    socket.connect(target).await.map_err(|e| {
+        RedisCtlError::network(format!("Failed to connect to StatsD at {}: {}", target, e))
+    })?;
+    Ok(socket)
+}
+
+/// Unspecified address with an ephemeral port, in the same family as `target`
+fn wildcard_for(target: &SocketAddr) -> SocketAddr {
+    match target {
+        SocketAddr::V4(_) => (Ipv4Addr::UNSPECIFIED, 0).into(),
+        SocketAddr::V6(_) => (Ipv6Addr::UNSPECIFIED, 0).into(),
+    }
+}
+
+/// Gauges for one stats response; `scope` is `cluster`, `bdb` or `node`
+///
+/// The cluster response is a single interval. Database and node responses are either an object
+/// keyed by uid or `{"stats": [{"uid": .., "intervals": [..]}]}`; the latest interval is used.
+pub fn collect_gauges(prefix: &str, scope: &str, stats: &Value) -> Vec<Gauge> {
+    let resources: Vec<(Option<String>, &Value)> = if scope == "cluster" {
+        vec![(None, stats)]
+    } else if let Some(list) = stats.get("stats").and_then(Value::as_array) {
+        list.iter()
+            .map(|resource| (resource.get("uid").map(uid_string), resource))
+            .collect()
+    } else if let Some(list) = stats.as_array() {
+        list.iter()
+            .map(|resource| (resource.get("uid").map(uid_string), resource))
+            .collect()
+    } else if let Some(map) = stats.as_object() {
+        map.iter()
+            .map(|(uid, resource)| (Some(uid.clone()), resource))
+            .collect()
+    } else {
+        Vec::new()
+    };
+
+    let mut gauges = Vec::new();
+    for (uid, resource) in resources {
+        let Some(metrics) = latest_metrics(resource) else {
+            continue;
+        };
+        let base = match &uid {
+            Some(uid) => format!("{}.{}.{}", prefix, scope, sanitize(uid)),
+            None => format!("{}.{}", prefix, scope),
+        };
+        for (name, value) in metrics {
+            if NON_METRIC_FIELDS.contains(&name.as_str()) {
+                continue;
+            }
+            let value = match value {
+                Value::Number(n) => n.as_f64(),
+                Value::String(s) => s.parse().ok(),
+                _ => None,
+            };
+            if let Some(value) = value.filter(|v| v.is_finite()) {
+                gauges.push(Gauge {
+                    name: format!("{}.{}", base, sanitize(name)),
+                    value,
+                });
+            }
+        }
+    }
+    gauges
+}
+
+/// The metrics of the newest interval, under `metrics` or at the top level
+fn latest_metrics(resource: &Value) -> Option<&Map<String, Value>> {
+    let interval = match resource.get("intervals").and_then(Value::as_array) {
+        Some(intervals) => intervals.last()?,
+        None => resource,
+    };
+    interval
+        .get("metrics")
+        .and_then(Value::as_object)
+        .or_else(|| interval.as_object())
+}
+
+fn uid_string(uid: &Value) -> String {
+    match uid {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// Replace characters that StatsD and Graphite treat as separators
+fn sanitize(segment: &str) -> String {
+    segment
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '_' || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+/// `name:value|g`; a negative value is preceded by a reset to 0, since StatsD reads a leading
+/// sign as a change to the current value
+pub fn statsd_line(gauge: &Gauge) -> String {
+    if gauge.value < 0.0 {
+        format!("{0}:0|g\n{0}:{1}|g", gauge.name, gauge.value)
+    } else {
+        format!("{}:{}|g", gauge.name, gauge.value)
+    }
+}
+
+/// `name value timestamp`
+pub fn graphite_line(gauge: &Gauge, timestamp: i64) -> String {
+    format!("{} {} {}", gauge.name, gauge.value, timestamp)
+}
+
+/// Newline-separated StatsD lines packed into datagrams of at most [`MAX_DATAGRAM`] bytes
+pub fn statsd_datagrams(gauges: &[Gauge]) -> Vec<String> {
+    let mut datagrams = Vec::new();
+    let mut current = String::new();
+    for gauge in gauges {
+        let line = statsd_line(gauge);
+        if !current.is_empty() && current.len() + 1 + line.len() > MAX_DATAGRAM {
+            datagrams.push(std::mem::take(&mut current));
+        }
+        if !current.is_empty() {
+            current.push('\n');
+        }
+        current.push_str(&line);
+    }
+    if !current.is_empty() {
+        datagrams.push(current);
+    }
+    datagrams
+}
+
+async fn send_statsd(socket: &UdpSocket, gauges: &[Gauge]) -> std::io::Result<()> {
+    for datagram in statsd_datagrams(gauges) {
+        socket.send(datagram.as_bytes()).await?;
+    }
+    Ok(())
+}
+
+async fn send_graphite(address: &str, gauges: &[Gauge], timestamp: i64) -> std::io::Result<()> {
+    let mut stream = TcpStream::connect(address).await?;
+    let mut payload = String::new();
+    for gauge in gauges {
+        payload.push_str(&graphite_line(gauge, timestamp));
+        payload.push('\n');
+    }
+    stream.write_all(payload.as_bytes()).await?;
+    stream.shutdown().await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn names(gauges: &[Gauge]) -> Vec<&str> {
+        let mut names: Vec<&str> = gauges.iter().map(|g| g.name.as_str()).collect();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn test_wildcard_matches_target_family() {
+        let v4: SocketAddr = "10.0.0.1:8125".parse().unwrap();
+        let v6: SocketAddr = "[fd00::1]:8125".parse().unwrap();
+        assert_eq!(wildcard_for(&v4), "0.0.0.0:0".parse().unwrap());
+        assert_eq!(wildcard_for(&v6), "[::]:0".parse().unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_statsd_socket_reaches_ipv4_and_ipv6_targets() {
+        for local in ["127.0.0.1:0", "[::1]:0"] {
+            let Ok(receiver) = UdpSocket::bind(local).await else {
+                // Loopback for this family is unavailable on the host
+                continue;
+            };
+            let target = receiver.local_addr().unwrap().to_string();
+            let socket = open_statsd_socket(&target).await.unwrap();
+            socket.send(b"redis.cpu:1|g").await.unwrap();
+            let mut buf = [0u8; 64];
+            let n = receiver.recv(&mut buf).await.unwrap();
+            assert_eq!(&buf[..n], b"redis.cpu:1|g");
+        }
+    }
+
+    #[test]
+    fn test_collect_cluster_gauges() {
+        let stats = json!({"stime": "2024-01-01T00:00:00Z", "interval": "1sec",
+                           "cpu_user": 0.25, "free_memory": "1024", "name": "c1"});
+        let gauges = collect_gauges("redis.c1", "cluster", &stats);
+        assert_eq!(
+            names(&gauges),
+            vec!["redis.c1.cluster.cpu_user", "redis.c1.cluster.free_memory"]
+        );
+        let free_memory = gauges.iter().find(|g| g.name.ends_with("free_memory"));
+        assert_eq!(free_memory.unwrap().value, 1024.0);
+    }
+
+    #[test]
+    fn test_collect_resource_gauges() {
+        let keyed = json!({"1": {"used_memory": 10, "conns": 2}});
+        assert_eq!(
+            names(&collect_gauges("redis", "bdb", &keyed)),
+            vec!["redis.bdb.1.conns", "redis.bdb.1.used_memory"]
+        );
+
+        let listed = json!({"stats": [{"uid": 3, "intervals": [
+            {"time": "t0", "metrics": {"cpu_idle": 0.1}},
+            {"time": "t1", "metrics": {"cpu_idle": 0.9}}
+        ]}]});
+        let gauges = collect_gauges("redis", "node", &listed);
+        assert_eq!(names(&gauges), vec!["redis.node.3.cpu_idle"]);
+        assert_eq!(gauges[0].value, 0.9);
+    }
+
+    #[test]
+    fn test_lines_and_datagrams() {
+        let gauge = Gauge {
+            name: "redis.bdb.1.used_memory".to_string(),
+            value: 1.5,
+        };
+        assert_eq!(statsd_line(&gauge), "redis.bdb.1.used_memory:1.5|g");
+        let negative = Gauge {
+            name: "redis.cluster.delta".to_string(),
+            value: -2.0,
+        };
+        assert_eq!(
+            statsd_line(&negative),
+            "redis.cluster.delta:0|g\nredis.cluster.delta:-2|g"
+        );
+        assert_eq!(
+            graphite_line(&gauge, 100),
+            "redis.bdb.1.used_memory 1.5 100"
+        );
+        assert_eq!(sanitize("avg latency/ms"), "avg_latency_ms");
+
+        let gauges = vec![gauge; 100];
+        let datagrams = statsd_datagrams(&gauges);
+        assert!(datagrams.len() > 1);
+        assert!(datagrams.iter().all(|d| d.len() <= MAX_DATAGRAM));
+        assert_eq!(
+            datagrams.iter().map(|d| d.lines().count()).sum::<usize>(),
+            100
+        );
+    }
+}
@@ -0,0 +1,306 @@
+//! Summaries of database stats intervals
+//!
+//! Reduces the intervals returned by the stats API to averages, percentiles and maxima for the
+//! metrics people usually look at first: throughput, hit ratio, memory, connections and network.
+
+#![allow(dead_code)]
+
+use serde::Serialize;
+use serde_json::Value;
+use tabled::{Table, Tabled, settings::Style};
+
+use crate::units::format_bytes;
+
+/// Distribution of one metric over the window
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MetricSummary {
+    pub avg: f64,
+    pub p50: f64,
+    pub p95: f64,
+    pub p99: f64,
+    pub max: f64,
+}
+
+/// Summary of a database's stats over the returned intervals
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct StatsSummary {
+    pub samples: usize,
+    pub window_start: Option<String>,
+    pub window_end: Option<String>,
+    pub ops_per_sec: Option<MetricSummary>,
+    /// Hits as a percentage of all lookups, over the whole window
+    pub hit_ratio_pct: Option<f64>,
+    pub used_memory_bytes: Option<MetricSummary>,
+    pub provisioned_memory_bytes: Option<u64>,
+    pub memory_used_pct: Option<MetricSummary>,
+    pub connections: Option<MetricSummary>,
+    pub ingress_bytes_per_sec: Option<MetricSummary>,
+    pub egress_bytes_per_sec: Option<MetricSummary>,
+}
+
+#[derive(Tabled)]
+struct SummaryRow {
+    #[tabled(rename = "METRIC")]
+    metric: String,
+    #[tabled(rename = "AVG")]
+    avg: String,
+    #[tabled(rename = "P50")]
+    p50: String,
+    #[tabled(rename = "P95")]
+    p95: String,
+    #[tabled(rename = "P99")]
+    p99: String,
+    #[tabled(rename = "MAX")]
+    max: String,
+}
+
+/// Summarize a stats response (`{"intervals": [...]}`)
+///
+/// Intervals may carry metrics at the top level or under `metrics`. `provisioned_memory` is the
+/// database's `memory_size`, used for the memory usage percentage.
+pub fn summarize(stats: &Value, provisioned_memory: Option<u64>) -> StatsSummary {
+    let intervals: Vec<&Value> = stats
+        .get("intervals")
+        .and_then(Value::as_array)
+        .or_else(|| stats.as_array())
+        .map(|intervals| intervals.iter().collect())
+        .unwrap_or_default();
+
+    let series = |names: &[&str]| -> Vec<f64> {
+        intervals
+            .iter()
+            .filter_map(|interval| names.iter().find_map(|name| metric(interval, name)))
+            .collect()
+    };
+    let total = |name: &str| -> Option<f64> {
+        let values: Vec<f64> = intervals.iter().filter_map(|i| metric(i, name)).collect();
+        (!values.is_empty()).then(|| values.iter().sum())
+    };
+
+    let hits = [total("read_hits"), total("write_hits")];
+    let misses = [total("read_misses"), total("write_misses")];
+    let hit_ratio_pct = if hits.iter().chain(&misses).all(Option::is_none) {
+        None
+    } else {
+        let hits: f64 = hits.iter().flatten().sum();
+        let lookups = hits + misses.iter().flatten().sum::<f64>();
+        (lookups > 0.0).then(|| hits / lookups * 100.0)
+    };
+
+    let used_memory = series(&["used_memory"]);
+    let memory_used_pct = provisioned_memory
+        .filter(|size| *size > 0)
+        .and_then(|size| {
+            let pct: Vec<f64> = used_memory
+                .iter()
+                .map(|used| used / size as f64 * 100.0)
+                .collect();
+            distribution(&pct)
+        });
+
+    let time = |interval: &&Value, keys: &[&str]| {
+        keys.iter()
+            .find_map(|key| interval.get(*key).and_then(Value::as_str))
+            .map(str::to_string)
+    };
+
+    StatsSummary {
+        samples: intervals.len(),
+        window_start: intervals.first().and_then(|i| time(i, &["stime", "time"])),
+        window_end: intervals.last().and_then(|i| time(i, &["etime", "time"])),
+        ops_per_sec: distribution(&series(&["total_req", "instantaneous_ops_per_sec"])),
+        hit_ratio_pct,
+        used_memory_bytes: distribution(&used_memory),
+        provisioned_memory_bytes: provisioned_memory,
+        memory_used_pct,
+        connections: distribution(&series(&["conns"])),
+        ingress_bytes_per_sec: distribution(&series(&["ingress_bytes"])),
+        egress_bytes_per_sec: distribution(&series(&["egress_bytes"])),
+    }
+}
+
+/// Numeric value of a metric in an interval, at the top level or under `metrics`
+pub(crate) fn metric(interval: &Value, name: &str) -> Option<f64> {
+    let value = interval
+        .get(name)
+        .or_else(|| interval.get("metrics").and_then(|m| m.get(name)))?;
+    match value {
+        Value::Number(n) => n.as_f64(),
+        Value::String(s) => s.parse().ok(),
+        _ => None,
+    }
+}
+
+/// Average, nearest-rank percentiles and maximum of a series
+fn distribution(values: &[f64]) -> Option<MetricSummary> {
+    if values.is_empty() {
+        return None;
+    }
+    let mut sorted = values.to_vec();
+    sorted.sort_by(f64::total_cmp);
+    let percentile = |p: f64| {
+        let rank = (p / 100.0 * sorted.len() as f64).ceil() as usize;
+        sorted[rank.clamp(1, sorted.len()) - 1]
+    };
+
+    Some(MetricSummary {
+        avg: sorted.iter().sum::<f64>() / sorted.len() as f64,
+        p50: percentile(50.0),
+        p95: percentile(95.0),
+        p99: percentile(99.0),
+        max: sorted[sorted.len() - 1],
+    })
+}
+
+/// Render the summary as a compact table
+pub fn summary_table(summary: &StatsSummary) -> String {
+    let number = |v: f64| format!("{:.1}", v);
+    let bytes = |v: f64| format_bytes(v.max(0.0) as u64);
+    let rate = |v: f64| format!("{}/s", format_bytes(v.max(0.0) as u64));
+    let percent = |v: f64| format!("{:.1}%", v);
+
+    let mut rows = Vec::new();
+    let mut add = |metric: &str, stats: &Option<MetricSummary>, fmt: &dyn Fn(f64) -> String| {
+        if let Some(s) = stats {
+            rows.push(SummaryRow {
+                metric: metric.to_string(),
+                avg: fmt(s.avg),
+                p50: fmt(s.p50),
+                p95: fmt(s.p95),
+                p99: fmt(s.p99),
+                max: fmt(s.max),
+            });
+        }
+    };
+    add("Ops/sec", &summary.ops_per_sec, &number);
+    add("Used memory", &summary.used_memory_bytes, &bytes);
+    add("Memory used", &summary.memory_used_pct, &percent);
+    add("Connections", &summary.connections, &number);
+    add("Network in", &summary.ingress_bytes_per_sec, &rate);
+    add("Network out", &summary.egress_bytes_per_sec, &rate);
+    if let Some(ratio) = summary.hit_ratio_pct {
+        rows.push(SummaryRow {
+            metric: "Hit ratio".to_string(),
+            avg: percent(ratio),
+            p50: "-".to_string(),
+            p95: "-".to_string(),
+            p99: "-".to_string(),
+            max: "-".to_string(),
+        });
+    }
+
+    let mut out = format!(
+        "{} samples, {} to {}",
+        summary.samples,
+        summary
+            .window_start
+            .as_deref()
+            .map_or_else(|| "?".to_string(), crate::timestamps::display),
+        summary
+            .window_end
+            .as_deref()
+            .map_or_else(|| "?".to_string(), crate::timestamps::display)
+    );
+    if let Some(size) = summary.provisioned_memory_bytes {
+        out.push_str(&format!(", {} provisioned", format_bytes(size)));
+    }
+    if rows.is_empty() {
+        out.push_str("\nNo metrics in the returned intervals");
+    } else {
+        let mut table = Table::new(rows);
+        table.with(Style::modern());
+        out.push('\n');
+        out.push_str(&table.to_string());
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn stats() -> Value {
+        json!({
+            "intervals": [
+                {
+                    "stime": "2024-01-01T00:00:00Z",
+                    "etime": "2024-01-01T00:05:00Z",
+                    "total_req": 100.0,
+                    "read_hits": 80.0,
+                    "read_misses": 20.0,
+                    "used_memory": 268435456,
+                    "conns": 10,
+                    "ingress_bytes": 1000,
+                    "egress_bytes": 4000
+                },
+                {
+                    "stime": "2024-01-01T00:05:00Z",
+                    "etime": "2024-01-01T00:10:00Z",
+                    "total_req": 300.0,
+                    "read_hits": 150.0,
+                    "read_misses": 50.0,
+                    "used_memory": 536870912,
+                    "conns": 20,
+                    "ingress_bytes": 3000,
+                    "egress_bytes": 8000
+                }
+            ]
+        })
+    }
+
+    #[test]
+    fn test_summarize() {
+        let summary = summarize(&stats(), Some(1073741824));
+
+        assert_eq!(summary.samples, 2);
+        assert_eq!(
+            summary.window_start.as_deref(),
+            Some("2024-01-01T00:00:00Z")
+        );
+        assert_eq!(summary.window_end.as_deref(), Some("2024-01-01T00:10:00Z"));
+
+        let ops = summary.ops_per_sec.unwrap();
+        assert_eq!(ops.avg, 200.0);
+        assert_eq!(ops.p50, 100.0);
+        assert_eq!(ops.p95, 300.0);
+        assert_eq!(ops.max, 300.0);
+
+        let hit_ratio = summary.hit_ratio_pct.unwrap();
+        assert!((hit_ratio - 76.666).abs() < 0.01);
+
+        let memory = summary.memory_used_pct.unwrap();
+        assert_eq!(memory.avg, 37.5);
+        assert_eq!(memory.max, 50.0);
+        assert_eq!(summary.connections.unwrap().avg, 15.0);
+        assert_eq!(summary.egress_bytes_per_sec.unwrap().max, 8000.0);
+    }
+
+    #[test]
+    fn test_summarize_nested_metrics_and_missing_values() {
+        let stats = json!({
+            "intervals": [
+                {"time": "2024-01-01T00:00:00Z", "metrics": {"instantaneous_ops_per_sec": "50"}},
+                {"time": "2024-01-01T00:01:00Z", "metrics": {}}
+            ]
+        });
+        let summary = summarize(&stats, None);
+
+        assert_eq!(summary.samples, 2);
+        assert_eq!(summary.ops_per_sec.unwrap().avg, 50.0);
+        assert_eq!(summary.hit_ratio_pct, None);
+        assert_eq!(summary.memory_used_pct, None);
+        assert!(summary_table(&summarize(&json!({"intervals": []}), None)).contains("No metrics"));
+    }
+
+    #[test]
+    fn test_percentiles() {
+        let values: Vec<f64> = (1..=100).map(f64::from).collect();
+        let summary = distribution(&values).unwrap();
+        assert_eq!(summary.p50, 50.0);
+        assert_eq!(summary.p95, 95.0);
+        assert_eq!(summary.p99, 99.0);
+        assert_eq!(summary.avg, 50.5);
+        assert!(distribution(&[]).is_none());
+    }
+}
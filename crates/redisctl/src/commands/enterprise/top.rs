@@ -0,0 +1,700 @@
+//! Live terminal dashboard for Redis Enterprise (`redisctl enterprise top`)
+//!
+//! Collecting a snapshot of cluster, database and alert state is always compiled in; the
+//! interactive screen itself is built on ratatui and only available with the `tui` feature.
+
+#![allow(dead_code)]
+
+use crate::connection::ConnectionManager;
+use crate::error::{RedisCtlError, Result as CliResult};
+use redis_enterprise::bdb::DatabaseInfo;
+use redis_enterprise::stats::{AggregatedStatsResponse, LastStatsResponse};
+use redis_enterprise::{
+    Alert, AlertHandler, BdbHandler, EnterpriseClient, Node, NodeHandler, StatsHandler,
+};
+use serde_json::Value;
+use std::time::Duration;
+
+/// Point-in-time view of the cluster shown on the overview screen
+#[derive(Debug, Clone, Default)]
+pub struct Snapshot {
+    /// Cluster CPU utilization (0.0 - 1.0)
+    pub cpu: Option<f64>,
+    pub memory_used: Option<u64>,
+    pub memory_total: Option<u64>,
+    pub databases: Vec<DatabaseRow>,
+    pub alerts: Vec<AlertRow>,
+    /// Requests that failed while collecting this snapshot
+    pub errors: Vec<String>,
+}
+
+/// One database line on the overview screen
+#[derive(Debug, Clone, PartialEq)]
+pub struct DatabaseRow {
+    pub uid: u32,
+    pub name: String,
+    pub status: String,
+    pub ops_per_sec: Option<f64>,
+    pub latency_ms: Option<f64>,
+    pub used_memory: Option<u64>,
+    pub memory_limit: Option<u64>,
+}
+
+/// One active alert
+#[derive(Debug, Clone, PartialEq)]
+pub struct AlertRow {
+    pub severity: String,
+    pub entity: String,
+    pub name: String,
+    pub description: String,
+}
+
+/// Latest metrics and alerts for a single database, shown when drilling in
+#[derive(Debug, Clone, Default)]
+pub struct DatabaseDetail {
+    pub metrics: Vec<(String, String)>,
+    pub alerts: Vec<AlertRow>,
+    pub errors: Vec<String>,
+}
+
+/// Run the dashboard until the user quits
+pub async fn run_top(
+    conn_mgr: &ConnectionManager,
+    profile_name: Option<&str>,
+    interval: u64,
+) -> CliResult<()> {
+    if interval == 0 {
+        return Err(RedisCtlError::Validation {
+            message: "--interval must be at least 1 second".to_string(),
+        });
+    }
+    if !cfg!(feature = "tui") {
+        return Err(RedisCtlError::Validation {
+            message: "This build of redisctl does not include the dashboard. \
+                      Reinstall with `cargo install redisctl --features tui`."
+                .to_string(),
+        });
+    }
+    if !std::io::IsTerminal::is_terminal(&std::io::stdout()) {
+        return Err(RedisCtlError::Validation {
+            message: "`enterprise top` needs an interactive terminal".to_string(),
+        });
+    }
+
+    let client = conn_mgr.create_enterprise_client(profile_name).await?;
+    run_dashboard(client, Duration::from_secs(interval)).await
+}
+
+#[cfg(feature = "tui")]
+async fn run_dashboard(client: EnterpriseClient, interval: Duration) -> CliResult<()> {
+    ui::run(client, interval).await
+}
+
+#[cfg(not(feature = "tui"))]
+async fn run_dashboard(_client: EnterpriseClient, _interval: Duration) -> CliResult<()> {
+    unreachable!("checked by run_top")
+}
+
+/// Fetch everything the overview screen needs, recording failures instead of aborting
+pub async fn collect_snapshot(client: &EnterpriseClient) -> Snapshot {
+    let stats = StatsHandler::new(client.clone());
+    let bdbs = BdbHandler::new(client.clone());
+    let nodes = NodeHandler::new(client.clone());
+    let alerts = AlertHandler::new(client.clone());
+
+    let (cluster_stats, db_stats, databases, nodes, alerts) = tokio::join!(
+        stats.cluster_last(),
+        stats.databases_last(),
+        bdbs.list(),
+        nodes.list(),
+        alerts.list(),
+    );
+
+    let mut errors = Vec::new();
+    let cluster_stats = record(&mut errors, "cluster stats", cluster_stats);
+    let db_stats = record(&mut errors, "database stats", db_stats);
+    let databases = record(&mut errors, "databases", databases).unwrap_or_default();
+    let nodes = record(&mut errors, "nodes", nodes).unwrap_or_default();
+    let alerts = record(&mut errors, "alerts", alerts).unwrap_or_default();
+
+    let mut snapshot = build_snapshot(
+        cluster_stats.as_ref(),
+        db_stats.as_ref(),
+        &databases,
+        &nodes,
+        &alerts,
+    );
+    snapshot.errors = errors;
+    snapshot
+}
+
+/// Keep a successful result, or note the failure and carry on
+fn record<T>(
+    errors: &mut Vec<String>,
+    name: &str,
+    result: redis_enterprise::Result<T>,
+) -> Option<T> {
+    match result {
+        Ok(value) => Some(value),
+        Err(e) => {
+            errors.push(format!("{}: {}", name, e));
+            None
+        }
+    }
+}
+
+/// Fetch the latest metrics and alerts of one database
+pub async fn collect_database_detail(client: &EnterpriseClient, uid: u32) -> DatabaseDetail {
+    let stats = StatsHandler::new(client.clone());
+    let alerts = AlertHandler::new(client.clone());
+    let (last, alerts) = tokio::join!(stats.database_last(uid), alerts.list_by_database(uid));
+
+    let mut detail = DatabaseDetail::default();
+    match last {
+        Ok(last) => detail.metrics = metric_lines(&last.metrics),
+        Err(e) => detail.errors.push(format!("stats: {}", e)),
+    }
+    match alerts {
+        Ok(alerts) => detail.alerts = active_alerts(&alerts),
+        Err(e) => detail.errors.push(format!("alerts: {}", e)),
+    }
+    detail
+}
+
+/// Combine the raw API responses into a snapshot
+pub fn build_snapshot(
+    cluster_stats: Option<&LastStatsResponse>,
+    db_stats: Option<&AggregatedStatsResponse>,
+    databases: &[DatabaseInfo],
+    nodes: &[Node],
+    alerts: &[Alert],
+) -> Snapshot {
+    let cluster_metrics = cluster_stats.map(|s| &s.metrics);
+
+    let cpu = cluster_metrics.and_then(|m| {
+        match (metric_f64(m, "cpu_user"), metric_f64(m, "cpu_system")) {
+            (Some(user), Some(system)) => Some(user + system),
+            _ => metric_f64(m, "cpu_idle").map(|idle| 1.0 - idle),
+        }
+    });
+
+    let memory_total = nodes
+        .iter()
+        .filter_map(|n| n.total_memory)
+        .reduce(|a, b| a + b);
+    let memory_used = match (
+        memory_total,
+        cluster_metrics.and_then(|m| metric_f64(m, "free_memory")),
+    ) {
+        (Some(total), Some(free)) => Some(total.saturating_sub(free as u64)),
+        _ => None,
+    };
+
+    let latest_db_metrics = |uid: u32| {
+        db_stats?
+            .stats
+            .iter()
+            .find(|s| s.uid == uid)?
+            .intervals
+            .last()
+            .map(|i| &i.metrics)
+    };
+
+    let mut rows: Vec<DatabaseRow> = databases
+        .iter()
+        .map(|db| {
+            let metrics = latest_db_metrics(db.uid);
+            DatabaseRow {
+                uid: db.uid,
+                name: db.name.clone(),
+                status: db.status.clone().unwrap_or_else(|| "unknown".to_string()),
+                ops_per_sec: metrics.and_then(|m| metric_f64(m, "instantaneous_ops_per_sec")),
+                // Enterprise reports latency in microseconds
+                latency_ms: metrics
+                    .and_then(|m| metric_f64(m, "avg_latency"))
+                    .map(|us| us / 1000.0),
+                used_memory: metrics
+                    .and_then(|m| metric_f64(m, "used_memory"))
+                    .map(|b| b as u64),
+                memory_limit: db.memory_size,
+            }
+        })
+        .collect();
+    rows.sort_by_key(|row| row.uid);
+
+    Snapshot {
+        cpu,
+        memory_used,
+        memory_total,
+        databases: rows,
+        alerts: active_alerts(alerts),
+        errors: Vec::new(),
+    }
+}
+
+/// Alerts that are currently raised, most severe first
+fn active_alerts(alerts: &[Alert]) -> Vec<AlertRow> {
+    let rank = |severity: &str| match severity.to_lowercase().as_str() {
+        "critical" | "error" => 0,
+        "warning" => 1,
+        _ => 2,
+    };
+
+    let mut rows: Vec<AlertRow> = alerts
+        .iter()
+        .filter(|a| !a.state.eq_ignore_ascii_case("off"))
+        .map(|a| AlertRow {
+            severity: a.severity.clone(),
+            entity: match (
+                &a.entity_type,
+                a.entity_name.as_ref().or(a.entity_uid.as_ref()),
+            ) {
+                (Some(kind), Some(name)) => format!("{}:{}", kind, name),
+                (Some(kind), None) => kind.clone(),
+                (None, Some(name)) => name.clone(),
+                (None, None) => "cluster".to_string(),
+            },
+            name: a.name.clone(),
+            description: a.description.clone().unwrap_or_default(),
+        })
+        .collect();
+    rows.sort_by_key(|row| rank(&row.severity));
+    rows
+}
+
+/// Read a numeric metric that may be reported as a number or a numeric string
+fn metric_f64(metrics: &Value, name: &str) -> Option<f64> {
+    match metrics.get(name)? {
+        Value::Number(n) => n.as_f64(),
+        Value::String(s) => s.parse().ok(),
+        _ => None,
+    }
+}
+
+/// Flatten a metrics object into sorted `name = value` lines
+fn metric_lines(metrics: &Value) -> Vec<(String, String)> {
+    let mut lines: Vec<(String, String)> = metrics
+        .as_object()
+        .map(|map| {
+            map.iter()
+                .map(|(name, value)| {
+                    let value = match value {
+                        Value::String(s) => s.clone(),
+                        other => other.to_string(),
+                    };
+                    (name.clone(), value)
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    lines.sort();
+    lines
+}
+
+#[cfg(feature = "tui")]
+mod ui {
+    use super::*;
+    use crate::units::format_bytes;
+    use ratatui::DefaultTerminal;
+    use ratatui::Frame;
+    use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+    use ratatui::layout::{Constraint, Layout, Rect};
+    use ratatui::style::{Color, Modifier, Style};
+    use ratatui::text::Line;
+    use ratatui::widgets::{Block, Cell, Gauge, Paragraph, Row, Table, TableState};
+    use std::time::Instant;
+
+    enum View {
+        Overview,
+        Database(u32),
+    }
+
+    struct App {
+        client: EnterpriseClient,
+        interval: Duration,
+        snapshot: Snapshot,
+        detail: Option<DatabaseDetail>,
+        view: View,
+        table: TableState,
+        last_refresh: Option<Instant>,
+    }
+
+    impl App {
+        async fn refresh(&mut self) {
+            self.snapshot = collect_snapshot(&self.client).await;
+            if let View::Database(uid) = self.view {
+                self.detail = Some(collect_database_detail(&self.client, uid).await);
+            }
+            if self.table.selected().is_none() && !self.snapshot.databases.is_empty() {
+                self.table.select(Some(0));
+            }
+            self.last_refresh = Some(Instant::now());
+        }
+
+        fn refresh_due(&self) -> bool {
+            self.last_refresh
+                .is_none_or(|last| last.elapsed() >= self.interval)
+        }
+
+        fn selected_database(&self) -> Option<&DatabaseRow> {
+            self.snapshot.databases.get(self.table.selected()?)
+        }
+    }
+
+    /// Take over the terminal and run the dashboard
+    pub(super) async fn run(client: EnterpriseClient, interval: Duration) -> CliResult<()> {
+        let mut terminal = ratatui::init();
+        let result = event_loop(&mut terminal, client, interval).await;
+        ratatui::restore();
+        result
+    }
+
+    async fn event_loop(
+        terminal: &mut DefaultTerminal,
+        client: EnterpriseClient,
+        interval: Duration,
+    ) -> CliResult<()> {
+        let mut app = App {
+            client,
+            interval,
+            snapshot: Snapshot::default(),
+            detail: None,
+            view: View::Overview,
+            table: TableState::default(),
+            last_refresh: None,
+        };
+
+        loop {
+            if app.refresh_due() {
+                app.refresh().await;
+            }
+            terminal
+                .draw(|frame| draw(frame, &mut app))
+                .map_err(terminal_error)?;
+
+            if !event::poll(Duration::from_millis(250)).map_err(terminal_error)? {
+                continue;
+            }
+            let Event::Key(key) = event::read().map_err(terminal_error)? else {
+                continue;
+            };
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+
+            match (key.code, &app.view) {
+                (KeyCode::Char('c'), _) if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    return Ok(());
+                }
+                (KeyCode::Char('q'), _) | (KeyCode::Esc, View::Overview) => return Ok(()),
+                (KeyCode::Char('r'), _) => app.last_refresh = None,
+                (KeyCode::Down | KeyCode::Char('j'), View::Overview) => app.table.select_next(),
+                (KeyCode::Up | KeyCode::Char('k'), View::Overview) => app.table.select_previous(),
+                (KeyCode::Enter, View::Overview) => {
+                    if let Some(uid) = app.selected_database().map(|db| db.uid) {
+                        app.view = View::Database(uid);
+                        app.detail = None;
+                        app.last_refresh = None;
+                    }
+                }
+                (KeyCode::Esc | KeyCode::Backspace, View::Database(_)) => {
+                    app.view = View::Overview;
+                    app.detail = None;
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn terminal_error(e: std::io::Error) -> RedisCtlError {
+        RedisCtlError::OutputError {
+            message: format!("Terminal error: {}", e),
+        }
+    }
+
+    fn draw(frame: &mut Frame, app: &mut App) {
+        let [header, body, footer] = Layout::vertical([
+            Constraint::Length(3),
+            Constraint::Min(5),
+            Constraint::Length(1),
+        ])
+        .areas(frame.area());
+
+        draw_gauges(frame, header, &app.snapshot);
+        match app.view {
+            View::Overview => {
+                let [databases, alerts] =
+                    Layout::vertical([Constraint::Percentage(65), Constraint::Percentage(35)])
+                        .areas(body);
+                draw_databases(frame, databases, app);
+                draw_alerts(frame, alerts, &app.snapshot.alerts, " Active alerts ");
+            }
+            View::Database(uid) => draw_detail(frame, body, app, uid),
+        }
+
+        let keys = match app.view {
+            View::Overview => "q quit  ↑/↓ select  enter details  r refresh",
+            View::Database(_) => "q quit  esc back  r refresh",
+        };
+        let status = match app.snapshot.errors.first() {
+            Some(error) => format!("{}  |  error: {}", keys, error),
+            None => format!("{}  |  refresh every {}s", keys, app.interval.as_secs()),
+        };
+        frame.render_widget(
+            Paragraph::new(status).style(Style::default().fg(Color::DarkGray)),
+            footer,
+        );
+    }
+
+    fn draw_gauges(frame: &mut Frame, area: Rect, snapshot: &Snapshot) {
+        let [cpu_area, memory_area] =
+            Layout::horizontal([Constraint::Percentage(50), Constraint::Percentage(50)])
+                .areas(area);
+
+        let cpu = snapshot.cpu.unwrap_or(0.0).clamp(0.0, 1.0);
+        let cpu_label = match snapshot.cpu {
+            Some(cpu) => format!("{:.1}%", cpu * 100.0),
+            None => "n/a".to_string(),
+        };
+        frame.render_widget(
+            Gauge::default()
+                .block(Block::bordered().title(" Cluster CPU "))
+                .gauge_style(usage_color(cpu))
+                .ratio(cpu)
+                .label(cpu_label),
+            cpu_area,
+        );
+
+        let (memory, memory_label) = match (snapshot.memory_used, snapshot.memory_total) {
+            (Some(used), Some(total)) if total > 0 => (
+                (used as f64 / total as f64).clamp(0.0, 1.0),
+                format!("{} / {}", format_bytes(used), format_bytes(total)),
+            ),
+            _ => (0.0, "n/a".to_string()),
+        };
+        frame.render_widget(
+            Gauge::default()
+                .block(Block::bordered().title(" Cluster memory "))
+                .gauge_style(usage_color(memory))
+                .ratio(memory)
+                .label(memory_label),
+            memory_area,
+        );
+    }
+
+    fn usage_color(ratio: f64) -> Color {
+        if ratio >= 0.9 {
+            Color::Red
+        } else if ratio >= 0.75 {
+            Color::Yellow
+        } else {
+            Color::Green
+        }
+    }
+
+    fn draw_databases(frame: &mut Frame, area: Rect, app: &mut App) {
+        let optional = |value: Option<String>| value.unwrap_or_else(|| "-".to_string());
+        let rows = app.snapshot.databases.iter().map(|db| {
+            Row::new(vec![
+                Cell::from(db.uid.to_string()),
+                Cell::from(db.name.clone()),
+                Cell::from(db.status.clone()),
+                Cell::from(optional(db.ops_per_sec.map(|ops| format!("{:.0}", ops)))),
+                Cell::from(optional(db.latency_ms.map(|ms| format!("{:.3}", ms)))),
+                Cell::from(match (db.used_memory, db.memory_limit) {
+                    (Some(used), Some(limit)) => {
+                        format!("{} / {}", format_bytes(used), format_bytes(limit))
+                    }
+                    (Some(used), None) => format_bytes(used),
+                    _ => "-".to_string(),
+                }),
+            ])
+        });
+
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(6),
+                Constraint::Fill(2),
+                Constraint::Length(10),
+                Constraint::Length(12),
+                Constraint::Length(14),
+                Constraint::Fill(1),
+            ],
+        )
+        .header(
+            Row::new(["ID", "NAME", "STATUS", "OPS/SEC", "LATENCY (ms)", "MEMORY"])
+                .style(Style::default().add_modifier(Modifier::BOLD)),
+        )
+        .block(Block::bordered().title(format!(" Databases ({}) ", app.snapshot.databases.len())))
+        .row_highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+        .highlight_symbol("> ");
+
+        frame.render_stateful_widget(table, area, &mut app.table);
+    }
+
+    fn draw_alerts(frame: &mut Frame, area: Rect, alerts: &[AlertRow], title: &str) {
+        let block = Block::bordered().title(format!("{}({}) ", title, alerts.len()));
+        if alerts.is_empty() {
+            frame.render_widget(Paragraph::new("No active alerts").block(block), area);
+            return;
+        }
+
+        let rows = alerts.iter().map(|alert| {
+            let color = match alert.severity.to_lowercase().as_str() {
+                "critical" | "error" => Color::Red,
+                "warning" => Color::Yellow,
+                _ => Color::Reset,
+            };
+            Row::new(vec![
+                Cell::from(alert.severity.clone()).style(Style::default().fg(color)),
+                Cell::from(alert.entity.clone()),
+                Cell::from(alert.name.clone()),
+                Cell::from(alert.description.clone()),
+            ])
+        });
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(10),
+                Constraint::Length(16),
+                Constraint::Fill(1),
+                Constraint::Fill(2),
+            ],
+        )
+        .block(block);
+        frame.render_widget(table, area);
+    }
+
+    fn draw_detail(frame: &mut Frame, area: Rect, app: &App, uid: u32) {
+        let name = app
+            .snapshot
+            .databases
+            .iter()
+            .find(|db| db.uid == uid)
+            .map(|db| db.name.as_str())
+            .unwrap_or("");
+        let [metrics_area, alerts_area] =
+            Layout::vertical([Constraint::Percentage(70), Constraint::Percentage(30)]).areas(area);
+
+        let Some(detail) = &app.detail else {
+            frame.render_widget(
+                Paragraph::new("Loading...")
+                    .block(Block::bordered().title(format!(" Database {} {} ", uid, name))),
+                area,
+            );
+            return;
+        };
+
+        let mut lines: Vec<Line> = detail
+            .metrics
+            .iter()
+            .map(|(metric, value)| Line::from(format!("{:<40} {}", metric, value)))
+            .collect();
+        lines.extend(
+            detail.errors.iter().map(|e| {
+                Line::from(format!("error: {}", e)).style(Style::default().fg(Color::Red))
+            }),
+        );
+        frame.render_widget(
+            Paragraph::new(lines)
+                .block(Block::bordered().title(format!(" Database {} {} ", uid, name))),
+            metrics_area,
+        );
+        draw_alerts(frame, alerts_area, &detail.alerts, " Database alerts ");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn database(uid: u32, name: &str) -> DatabaseInfo {
+        serde_json::from_value(json!({
+            "uid": uid,
+            "name": name,
+            "status": "active",
+            "memory_size": 1073741824u64
+        }))
+        .unwrap()
+    }
+
+    fn alert(name: &str, severity: &str, state: &str) -> Alert {
+        serde_json::from_value(json!({
+            "uid": name,
+            "name": name,
+            "severity": severity,
+            "state": state,
+            "entity_type": "bdb",
+            "entity_uid": "1"
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_build_snapshot() {
+        let cluster: LastStatsResponse = serde_json::from_value(json!({
+            "time": "2024-01-01T00:00:00Z",
+            "metrics": {"cpu_user": 0.2, "cpu_system": 0.05, "free_memory": 6442450944u64}
+        }))
+        .unwrap();
+        let db_stats: AggregatedStatsResponse = serde_json::from_value(json!({
+            "stats": [{
+                "uid": 2,
+                "intervals": [
+                    {"time": "t0", "metrics": {"instantaneous_ops_per_sec": 10}},
+                    {"time": "t1", "metrics": {
+                        "instantaneous_ops_per_sec": "1500",
+                        "avg_latency": 250.0,
+                        "used_memory": 1048576
+                    }}
+                ]
+            }]
+        }))
+        .unwrap();
+        let nodes: Vec<Node> = serde_json::from_value(json!([
+            {"uid": 1, "status": "active", "total_memory": 8589934592u64},
+            {"uid": 2, "status": "active", "total_memory": 8589934592u64}
+        ]))
+        .unwrap();
+        let alerts = vec![
+            alert("bdb_size", "warning", "on"),
+            alert("bdb_backup_failed", "critical", "on"),
+            alert("bdb_latency", "warning", "off"),
+        ];
+
+        let snapshot = build_snapshot(
+            Some(&cluster),
+            Some(&db_stats),
+            &[database(2, "cache"), database(1, "sessions")],
+            &nodes,
+            &alerts,
+        );
+
+        assert_eq!(snapshot.cpu, Some(0.25));
+        assert_eq!(snapshot.memory_total, Some(17179869184));
+        assert_eq!(snapshot.memory_used, Some(10737418240));
+
+        assert_eq!(snapshot.databases[0].uid, 1);
+        assert_eq!(snapshot.databases[0].ops_per_sec, None);
+        let cache = &snapshot.databases[1];
+        assert_eq!(cache.ops_per_sec, Some(1500.0));
+        assert_eq!(cache.latency_ms, Some(0.25));
+        assert_eq!(cache.used_memory, Some(1048576));
+        assert_eq!(cache.memory_limit, Some(1073741824));
+
+        assert_eq!(snapshot.alerts.len(), 2);
+        assert_eq!(snapshot.alerts[0].name, "bdb_backup_failed");
+        assert_eq!(snapshot.alerts[0].entity, "bdb:1");
+    }
+
+    #[test]
+    fn test_build_snapshot_without_stats() {
+        let snapshot = build_snapshot(None, None, &[database(1, "db")], &[], &[]);
+        assert_eq!(snapshot.cpu, None);
+        assert_eq!(snapshot.memory_used, None);
+        assert_eq!(snapshot.databases[0].status, "active");
+        assert!(snapshot.alerts.is_empty());
+    }
+}
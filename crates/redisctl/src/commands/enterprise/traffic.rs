@@ -0,0 +1,613 @@
+//! `enterprise traffic overview`: endpoint and proxy stats merged into one view
+//!
+//! Endpoint stats are reported per endpoint and proxy pair, with a UID of the form
+//! `<bdb>:<endpoint>:<proxy>`. The latest interval of each pair is attributed to its database,
+//! to the proxy and, through the proxy, to the node it runs on. Proxy stats add the proxy's own
+//! connection count where the cluster reports them.
+
+#![allow(dead_code)]
+
+use super::stats_summary::metric;
+use super::utils::*;
+use crate::cli::{EnterpriseTrafficCommands, OutputFormat};
+use crate::connection::ConnectionManager;
+use crate::error::Result as CliResult;
+use crate::output::notice;
+use crate::units::format_bytes;
+use anyhow::Context;
+use redis_enterprise::BdbHandler;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+use tabled::{Table, Tabled, settings::Style};
+
+pub async fn handle_traffic_command(
+    conn_mgr: &ConnectionManager,
+    profile_name: Option<&str>,
+    command: &EnterpriseTrafficCommands,
+    output_format: OutputFormat,
+    query: Option<&str>,
+) -> CliResult<()> {
+    match command {
+        EnterpriseTrafficCommands::Overview { db, hot_factor } => {
+            traffic_overview(
+                conn_mgr,
+                profile_name,
+                *db,
+                *hot_factor,
+                output_format,
+                query,
+            )
+            .await
+        }
+    }
+}
+
+/// Traffic summed over a group of endpoint and proxy pairs
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct Traffic {
+    pub ops_per_sec: f64,
+    /// Request-weighted mean latency in milliseconds
+    pub avg_latency_ms: Option<f64>,
+    pub ingress_bytes_per_sec: f64,
+    pub egress_bytes_per_sec: f64,
+    pub connections: f64,
+    #[serde(skip)]
+    latency_weight: f64,
+    #[serde(skip)]
+    latency_sum: f64,
+}
+
+impl Traffic {
+    fn add(&mut self, sample: &Sample) {
+        self.ops_per_sec += sample.ops;
+        self.ingress_bytes_per_sec += sample.ingress;
+        self.egress_bytes_per_sec += sample.egress;
+        self.connections += sample.conns;
+        if let Some(latency_us) = sample.latency_us {
+            // Idle pairs still count, so a group that saw no requests gets a plain mean
+            let weight = sample.ops.max(f64::EPSILON);
+            self.latency_sum += latency_us / 1000.0 * weight;
+            self.latency_weight += weight;
+            self.avg_latency_ms = Some(self.latency_sum / self.latency_weight);
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DatabaseTraffic {
+    pub uid: u32,
+    pub name: String,
+    #[serde(flatten)]
+    pub traffic: Traffic,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct NodeTraffic {
+    pub uid: u32,
+    pub proxies: usize,
+    #[serde(flatten)]
+    pub traffic: Traffic,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ProxyTraffic {
+    pub uid: u32,
+    pub node_uid: u32,
+    /// Share of the cluster's throughput, in percent
+    pub share_pct: f64,
+    /// Connections reported by the proxy itself, if available
+    pub proxy_connections: Option<f64>,
+    pub hot: bool,
+    #[serde(flatten)]
+    pub traffic: Traffic,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct TrafficOverview {
+    pub databases: Vec<DatabaseTraffic>,
+    pub nodes: Vec<NodeTraffic>,
+    pub proxies: Vec<ProxyTraffic>,
+}
+
+/// Latest figures of one endpoint and proxy pair
+#[derive(Debug, Clone, PartialEq)]
+struct Sample {
+    bdb_uid: u32,
+    proxy_uid: Option<u32>,
+    ops: f64,
+    latency_us: Option<f64>,
+    ingress: f64,
+    egress: f64,
+    conns: f64,
+}
+
+/// Samples from an endpoint stats response: a list of `{uid, intervals}` entries
+fn samples(endpoint_stats: &Value) -> Vec<Sample> {
+    let entries = endpoint_stats
+        .as_array()
+        .or_else(|| endpoint_stats.get("stats").and_then(Value::as_array))
+        .map(|entries| entries.iter().collect::<Vec<_>>())
+        .unwrap_or_default();
+    entries
+        .into_iter()
+        .filter_map(|entry| {
+            let uid = entry.get("uid").and_then(Value::as_str).unwrap_or_default();
+            let parts: Vec<u32> = uid.split(':').filter_map(|p| p.parse().ok()).collect();
+            let id_field = |key: &str| {
+                entry.get(key).and_then(|v| {
+                    v.as_u64()
+                        .or_else(|| v.as_str().and_then(|s| s.parse().ok()))
+                        .and_then(|n| u32::try_from(n).ok())
+                })
+            };
+            let bdb_uid = id_field("bdb_uid").or_else(|| parts.first().copied())?;
+            let proxy_uid = id_field("proxy_uid").or_else(|| (parts.len() >= 3).then(|| parts[2]));
+            let last = entry
+                .get("intervals")
+                .and_then(Value::as_array)
+                .and_then(|intervals| intervals.last())
+                .unwrap_or(entry);
+            let value = |name: &str| metric(last, name).unwrap_or(0.0);
+            Some(Sample {
+                bdb_uid,
+                proxy_uid,
+                ops: value("total_req"),
+                latency_us: metric(last, "avg_latency"),
+                ingress: value("ingress_bytes"),
+                egress: value("egress_bytes"),
+                conns: value("conns"),
+            })
+        })
+        .collect()
+}
+
+/// Connections per proxy from proxy stats: `{"<uid>": {metrics}}` or a list of `{uid, intervals}`
+fn proxy_connections(proxy_stats: &Value) -> HashMap<u32, f64> {
+    let last_conns = |value: &Value| {
+        let last = value
+            .get("intervals")
+            .and_then(Value::as_array)
+            .and_then(|intervals| intervals.last())
+            .unwrap_or(value);
+        metric(last, "conns")
+    };
+    match proxy_stats {
+        Value::Object(map) => map
+            .iter()
+            .filter_map(|(uid, stats)| Some((uid.parse().ok()?, last_conns(stats)?)))
+            .collect(),
+        Value::Array(entries) => entries
+            .iter()
+            .filter_map(|entry| {
+                let uid = entry.get("uid")?;
+                let uid = uid
+                    .as_u64()
+                    .or_else(|| uid.as_str().and_then(|s| s.parse().ok()))?;
+                Some((u32::try_from(uid).ok()?, last_conns(entry)?))
+            })
+            .collect(),
+        _ => HashMap::new(),
+    }
+}
+
+/// Node of each proxy; a proxy without a `node_uid` runs on the node with its UID
+fn proxy_nodes(proxies: &Value) -> HashMap<u32, u32> {
+    proxies
+        .as_array()
+        .map(|proxies| {
+            proxies
+                .iter()
+                .filter_map(|p| {
+                    let uid = u32::try_from(p.get("uid")?.as_u64()?).ok()?;
+                    let node = p
+                        .get("node_uid")
+                        .and_then(Value::as_u64)
+                        .and_then(|n| u32::try_from(n).ok())
+                        .unwrap_or(uid);
+                    Some((uid, node))
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Merge endpoint and proxy stats into per-database, per-node and per-proxy traffic
+pub fn build_overview(
+    endpoint_stats: &Value,
+    proxy_stats: &Value,
+    proxies: &Value,
+    database_names: &HashMap<u32, String>,
+    only_db: Option<u32>,
+    hot_factor: f64,
+) -> TrafficOverview {
+    let nodes_of = proxy_nodes(proxies);
+    let conns_of = proxy_connections(proxy_stats);
+
+    let mut databases: BTreeMap<u32, Traffic> = BTreeMap::new();
+    let mut by_proxy: BTreeMap<u32, Traffic> = BTreeMap::new();
+    for sample in samples(endpoint_stats)
+        .iter()
+        .filter(|s| only_db.is_none_or(|db| s.bdb_uid == db))
+    {
+        databases.entry(sample.bdb_uid).or_default().add(sample);
+        if let Some(proxy) = sample.proxy_uid {
+            by_proxy.entry(proxy).or_default().add(sample);
+        }
+    }
+
+    let node_of = |proxy: u32| nodes_of.get(&proxy).copied().unwrap_or(proxy);
+    let mut nodes: BTreeMap<u32, (usize, Traffic)> = BTreeMap::new();
+    for (proxy, traffic) in &by_proxy {
+        let (count, node) = nodes.entry(node_of(*proxy)).or_default();
+        *count += 1;
+        merge(node, traffic);
+    }
+
+    let total_ops: f64 = by_proxy.values().map(|t| t.ops_per_sec).sum();
+    let mean_ops = total_ops / by_proxy.len().max(1) as f64;
+    let proxies = by_proxy
+        .into_iter()
+        .map(|(uid, traffic)| ProxyTraffic {
+            uid,
+            node_uid: node_of(uid),
+            share_pct: if total_ops > 0.0 {
+                traffic.ops_per_sec / total_ops * 100.0
+            } else {
+                0.0
+            },
+            proxy_connections: conns_of.get(&uid).copied(),
+            hot: total_ops > 0.0 && traffic.ops_per_sec >= mean_ops * hot_factor,
+            traffic,
+        })
+        .collect::<Vec<_>>();
+    // A lone proxy is never hotter than itself
+    let proxies = if proxies.len() < 2 {
+        proxies
+            .into_iter()
+            .map(|p| ProxyTraffic { hot: false, ..p })
+            .collect()
+    } else {
+        proxies
+    };
+
+    let mut databases: Vec<DatabaseTraffic> = databases
+        .into_iter()
+        .map(|(uid, traffic)| DatabaseTraffic {
+            uid,
+            name: database_names.get(&uid).cloned().unwrap_or_default(),
+            traffic,
+        })
+        .collect();
+    databases.sort_by(|a, b| b.traffic.ops_per_sec.total_cmp(&a.traffic.ops_per_sec));
+
+    TrafficOverview {
+        databases,
+        nodes: nodes
+            .into_iter()
+            .map(|(uid, (proxies, traffic))| NodeTraffic {
+                uid,
+                proxies,
+                traffic,
+            })
+            .collect(),
+        proxies,
+    }
+}
+
+/// Add one group's totals to another's
+fn merge(into: &mut Traffic, from: &Traffic) {
+    into.ops_per_sec += from.ops_per_sec;
+    into.ingress_bytes_per_sec += from.ingress_bytes_per_sec;
+    into.egress_bytes_per_sec += from.egress_bytes_per_sec;
+    into.connections += from.connections;
+    into.latency_sum += from.latency_sum;
+    into.latency_weight += from.latency_weight;
+    if into.latency_weight > 0.0 {
+        into.avg_latency_ms = Some(into.latency_sum / into.latency_weight);
+    }
+}
+
+/// Treat a 404 as no data; proxy stats are optional, but other errors are not
+fn unless_not_found(
+    result: Result<Value, redis_enterprise::RestError>,
+) -> Result<Value, redis_enterprise::RestError> {
+    match result {
+        Err(e) if e.is_not_found() => Ok(Value::Null),
+        other => other,
+    }
+}
+
+/// Parse `--hot-factor`, which must be a number greater than 0
+pub fn parse_hot_factor(s: &str) -> Result<f64, String> {
+    match s.parse::<f64>() {
+        Ok(factor) if factor.is_finite() && factor > 0.0 => Ok(factor),
+        _ => Err(format!(
+            "invalid hot factor: {} (must be a number greater than 0)",
+            s
+        )),
+    }
+}
+
+async fn traffic_overview(
+    conn_mgr: &ConnectionManager,
+    profile_name: Option<&str>,
+    only_db: Option<u32>,
+    hot_factor: f64,
+    output_format: OutputFormat,
+    query: Option<&str>,
+) -> CliResult<()> {
+    let client = conn_mgr.create_enterprise_client(profile_name).await?;
+    let endpoint_stats = client
+        .get_raw("/v1/endpoints/stats")
+        .await
+        .context("Failed to get endpoint stats")?;
+    // Proxy details only refine the overview; older clusters may not serve them
+    let proxy_stats = unless_not_found(client.get_raw("/v1/proxies/stats/last").await)
+        .context("Failed to get proxy stats")?;
+    let proxies =
+        unless_not_found(client.get_raw("/v1/proxies").await).context("Failed to list proxies")?;
+    let database_names: HashMap<u32, String> = BdbHandler::new(client)
+        .list()
+        .await
+        .context("Failed to list databases")?
+        .into_iter()
+        .map(|db| (db.uid, db.name))
+        .collect();
+
+    let overview = build_overview(
+        &endpoint_stats,
+        &proxy_stats,
+        &proxies,
+        &database_names,
+        only_db,
+        hot_factor,
+    );
+
+    match output_format {
+        OutputFormat::Auto | OutputFormat::Table if query.is_none() => {
+            print_overview(&overview);
+            Ok(())
+        }
+        _ => {
+            let json =
+                serde_json::to_value(&overview).context("Failed to serialize traffic overview")?;
+            let data = handle_output(json, output_format, query)?;
+            print_formatted_output(data, output_format)
+        }
+    }
+}
+
+#[derive(Tabled)]
+struct DatabaseRow {
+    #[tabled(rename = "DB")]
+    uid: u32,
+    #[tabled(rename = "NAME")]
+    name: String,
+    #[tabled(rename = "OPS/S")]
+    ops: String,
+    #[tabled(rename = "LATENCY")]
+    latency: String,
+    #[tabled(rename = "IN/S")]
+    ingress: String,
+    #[tabled(rename = "OUT/S")]
+    egress: String,
+    #[tabled(rename = "CONNS")]
+    conns: String,
+}
+
+#[derive(Tabled)]
+struct NodeRow {
+    #[tabled(rename = "NODE")]
+    uid: u32,
+    #[tabled(rename = "PROXIES")]
+    proxies: usize,
+    #[tabled(rename = "OPS/S")]
+    ops: String,
+    #[tabled(rename = "LATENCY")]
+    latency: String,
+    #[tabled(rename = "IN/S")]
+    ingress: String,
+    #[tabled(rename = "OUT/S")]
+    egress: String,
+    #[tabled(rename = "CONNS")]
+    conns: String,
+}
+
+#[derive(Tabled)]
+struct ProxyRow {
+    #[tabled(rename = "PROXY")]
+    uid: u32,
+    #[tabled(rename = "NODE")]
+    node: u32,
+    #[tabled(rename = "OPS/S")]
+    ops: String,
+    #[tabled(rename = "SHARE")]
+    share: String,
+    #[tabled(rename = "LATENCY")]
+    latency: String,
+    #[tabled(rename = "CONNS")]
+    conns: String,
+    #[tabled(rename = "")]
+    hot: String,
+}
+
+fn print_overview(overview: &TrafficOverview) {
+    if overview.databases.is_empty() {
+        notice!("No endpoint traffic reported");
+        return;
+    }
+    let section = |title: &str, table: String| println!("{}\n{}\n", title, table);
+
+    let rows: Vec<DatabaseRow> = overview
+        .databases
+        .iter()
+        .map(|db| DatabaseRow {
+            uid: db.uid,
+            name: db.name.clone(),
+            ops: format_count(db.traffic.ops_per_sec),
+            latency: format_latency(db.traffic.avg_latency_ms),
+            ingress: format_bytes(db.traffic.ingress_bytes_per_sec as u64),
+            egress: format_bytes(db.traffic.egress_bytes_per_sec as u64),
+            conns: format_count(db.traffic.connections),
+        })
+        .collect();
+    section(
+        "DATABASES",
+        Table::new(rows).with(Style::blank()).to_string(),
+    );
+
+    if !overview.nodes.is_empty() {
+        let rows: Vec<NodeRow> = overview
+            .nodes
+            .iter()
+            .map(|node| NodeRow {
+                uid: node.uid,
+                proxies: node.proxies,
+                ops: format_count(node.traffic.ops_per_sec),
+                latency: format_latency(node.traffic.avg_latency_ms),
+                ingress: format_bytes(node.traffic.ingress_bytes_per_sec as u64),
+                egress: format_bytes(node.traffic.egress_bytes_per_sec as u64),
+                conns: format_count(node.traffic.connections),
+            })
+            .collect();
+        section("NODES", Table::new(rows).with(Style::blank()).to_string());
+    }
+
+    if !overview.proxies.is_empty() {
+        let rows: Vec<ProxyRow> = overview
+            .proxies
+            .iter()
+            .map(|proxy| ProxyRow {
+                uid: proxy.uid,
+                node: proxy.node_uid,
+                ops: format_count(proxy.traffic.ops_per_sec),
+                share: format!("{:.0}%", proxy.share_pct),
+                latency: format_latency(proxy.traffic.avg_latency_ms),
+                conns: format_count(proxy.proxy_connections.unwrap_or(proxy.traffic.connections)),
+                hot: if proxy.hot { "HOT" } else { "" }.to_string(),
+            })
+            .collect();
+        section("PROXIES", Table::new(rows).with(Style::blank()).to_string());
+    }
+
+    let hot = overview.proxies.iter().filter(|p| p.hot).count();
+    if hot > 0 {
+        notice!(
+            "{} hot prox{}: consider spreading the databases' endpoints or adding proxy threads",
+            hot,
+            if hot == 1 { "y" } else { "ies" }
+        );
+    }
+}
+
+/// A rate as `850`, `12.3K` or `4.1M`
+fn format_count(value: f64) -> String {
+    match value {
+        v if v >= 1_000_000.0 => format!("{:.1}M", v / 1_000_000.0),
+        v if v >= 1_000.0 => format!("{:.1}K", v / 1_000.0),
+        v => format!("{:.0}", v),
+    }
+}
+
+fn format_latency(ms: Option<f64>) -> String {
+    ms.map_or_else(|| "-".to_string(), |ms| format!("{:.2} ms", ms))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_hot_factor() {
+        assert_eq!(parse_hot_factor("1.5"), Ok(1.5));
+        for bad in ["0", "-2", "NaN", "inf", "fast"] {
+            assert!(parse_hot_factor(bad).is_err(), "{}", bad);
+        }
+    }
+
+    #[test]
+    fn test_only_not_found_is_ignored() {
+        use redis_enterprise::RestError;
+
+        assert_eq!(
+            unless_not_found(Err(RestError::NotFound)).unwrap(),
+            Value::Null
+        );
+        let denied = Err(RestError::ApiError {
+            code: 403,
+            message: "Forbidden".to_string(),
+        });
+        assert!(unless_not_found(denied).is_err());
+    }
+
+    fn endpoint_stats() -> Value {
+        json!([
+            {"uid": "1:1:1", "intervals": [
+                {"interval": "1sec", "total_req": 100.0, "avg_latency": 900.0, "conns": 1},
+                {"interval": "1sec", "total_req": 9000.0, "avg_latency": 200.0,
+                 "ingress_bytes": 4096.0, "egress_bytes": 8192.0, "conns": 40}
+            ]},
+            {"uid": "1:1:2", "intervals": [
+                {"interval": "1sec", "total_req": 1000.0, "avg_latency": 1000.0, "conns": 10}
+            ]},
+            {"uid": "2:1:2", "intervals": [
+                {"interval": "1sec", "total_req": 0.0, "avg_latency": 0.0, "conns": 2}
+            ]}
+        ])
+    }
+
+    #[test]
+    fn test_build_overview_aggregates_by_database_node_and_proxy() {
+        let names = HashMap::from([(1, "cache".to_string()), (2, "queue".to_string())]);
+        let proxies = json!([{"uid": 1, "node_uid": 1}, {"uid": 2, "node_uid": 3}]);
+        let proxy_stats = json!({"1": {"conns": 45}, "2": {"conns": 12}});
+        let overview = build_overview(&endpoint_stats(), &proxy_stats, &proxies, &names, None, 1.5);
+
+        let cache = &overview.databases[0];
+        assert_eq!((cache.uid, cache.name.as_str()), (1, "cache"));
+        assert_eq!(cache.traffic.ops_per_sec, 10_000.0);
+        assert_eq!(cache.traffic.connections, 50.0);
+        // (9000 * 0.2 + 1000 * 1.0) / 10000
+        let latency = cache.traffic.avg_latency_ms.unwrap();
+        assert!((latency - 0.28).abs() < 1e-9);
+
+        let nodes: Vec<(u32, usize)> = overview.nodes.iter().map(|n| (n.uid, n.proxies)).collect();
+        assert_eq!(nodes, vec![(1, 1), (3, 1)]);
+
+        let hot: Vec<u32> = overview
+            .proxies
+            .iter()
+            .filter(|p| p.hot)
+            .map(|p| p.uid)
+            .collect();
+        assert_eq!(hot, vec![1]);
+        assert_eq!(overview.proxies[0].proxy_connections, Some(45.0));
+        assert!((overview.proxies[0].share_pct - 90.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_build_overview_filters_database_and_tolerates_missing_proxy_data() {
+        let overview = build_overview(
+            &endpoint_stats(),
+            &Value::Null,
+            &Value::Null,
+            &HashMap::new(),
+            Some(2),
+            2.0,
+        );
+        assert_eq!(overview.databases.len(), 1);
+        assert_eq!(overview.databases[0].uid, 2);
+        // Without proxy details, a proxy is placed on the node with its UID
+        assert_eq!(overview.nodes[0].uid, 2);
+        assert!(!overview.proxies[0].hot);
+    }
+
+    #[test]
+    fn test_format_count() {
+        assert_eq!(format_count(850.4), "850");
+        assert_eq!(format_count(12_345.0), "12.3K");
+        assert_eq!(format_count(4_100_000.0), "4.1M");
+    }
+}
@@ -4,12 +4,12 @@ use crate::cli::OutputFormat;
 use crate::error::{RedisCtlError, Result as CliResult};
 use crate::output::print_output;
 use anyhow::Context;
-use dialoguer::Confirm;
+use dialoguer::{Confirm, Input};
 use serde_json::Value;
 
 /// Apply JMESPath query to JSON data
 pub fn apply_jmespath(data: &Value, query: &str) -> CliResult<Value> {
-    let expr = jmespath::compile(query)
+    let expr = crate::query::compile(query)
         .with_context(|| format!("Invalid JMESPath expression: {}", query))?;
     let result = expr
         .search(data)
@@ -51,6 +51,11 @@ pub fn print_formatted_output(data: Value, output_format: OutputFormat) -> CliRe
                 }
             })?;
         }
+        OutputFormat::Id => {
+            crate::output::print_ids(&data).map_err(|e| RedisCtlError::OutputError {
+                message: e.to_string(),
+            })?;
+        }
         OutputFormat::Table | OutputFormat::Auto => {
             // For now, output as JSON for table format
             // TODO: Implement proper table formatting for enterprise commands
@@ -66,6 +71,10 @@ pub fn print_formatted_output(data: Value, output_format: OutputFormat) -> CliRe
 
 /// Confirm an action with the user
 pub fn confirm_action(message: &str) -> CliResult<bool> {
+    crate::interactive::ensure_prompt_allowed(
+        &format!("Confirmation required: {}", message.trim_end_matches('?')),
+        "pass --force to proceed without it",
+    )?;
     #[cfg(unix)]
     {
         use std::io::IsTerminal;
@@ -92,6 +101,26 @@ pub fn confirm_action(message: &str) -> CliResult<bool> {
     }
 }
 
+/// Confirm a destructive action by requiring the user to type an expected value
+pub fn confirm_by_typing(prompt: &str, expected: &str) -> CliResult<bool> {
+    use std::io::IsTerminal;
+    crate::interactive::ensure_prompt_allowed(
+        &format!("Confirmation required: {}", prompt.trim_end_matches('?')),
+        "pass --force to proceed without it",
+    )?;
+    if !std::io::stdin().is_terminal() {
+        eprintln!("Warning: {} Use --force to skip confirmation.", prompt);
+        return Ok(false);
+    }
+
+    let answer: String = Input::new()
+        .with_prompt(format!("{} Type '{}' to confirm", prompt, expected))
+        .allow_empty(true)
+        .interact_text()
+        .context("Failed to get user confirmation")?;
+    Ok(answer.trim() == expected)
+}
+
 /// Read JSON data from string or file
 pub fn read_json_data(data: &str) -> CliResult<Value> {
     let json_str = if let Some(file_path) = data.strip_prefix('@') {
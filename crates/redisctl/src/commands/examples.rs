@@ -0,0 +1,508 @@
+//! Example payload skeletons for create commands
+//!
+//! `--example` prints a starting template for a command's `--data` payload. Skeletons
+//! are built from the typed request models of the API libraries, so they only contain
+//! fields the API accepts. JSON output is a valid payload that can be saved and passed
+//! back with `--data @file.json`; `-o yaml` adds a comment above each field.
+
+#![allow(dead_code)]
+
+use std::collections::{HashMap, HashSet};
+
+use anyhow::Context;
+use redis_cloud::databases::DatabaseCreateRequest;
+use redis_cloud::subscriptions::{
+    DatabaseModuleSpec, DatabaseThroughputSpec, SubscriptionCreateRequest,
+    SubscriptionDatabaseSpec, SubscriptionRegionNetworkingSpec, SubscriptionRegionSpec,
+    SubscriptionSpec,
+};
+use redis_enterprise::bdb::{CreateDatabaseRequest, ModuleConfig};
+use redis_enterprise::crdb::{CreateCrdbInstance, CreateCrdbRequest};
+use serde_json::{Value, json};
+
+use crate::cli::OutputFormat;
+use crate::error::Result as CliResult;
+
+/// Create commands that can print an example payload
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExampleKind {
+    CloudSubscription,
+    CloudDatabase,
+    EnterpriseDatabase,
+    EnterpriseCrdb,
+}
+
+/// Print the example payload for a create command
+pub fn print_example(kind: ExampleKind, output_format: OutputFormat) -> CliResult<()> {
+    let skeleton = skeleton(kind)?;
+    match output_format {
+        OutputFormat::Yaml => print!("{}", annotated_yaml(&skeleton, annotations(kind))?),
+        OutputFormat::Auto | OutputFormat::Json | OutputFormat::Table | OutputFormat::Id => {
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&skeleton).context("Failed to serialize example")?
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Build the example payload from the typed request model
+fn skeleton(kind: ExampleKind) -> CliResult<Value> {
+    let value = match kind {
+        ExampleKind::CloudSubscription => serde_json::to_value(cloud_subscription()),
+        ExampleKind::CloudDatabase => serde_json::to_value(cloud_database()),
+        ExampleKind::EnterpriseDatabase => serde_json::to_value(enterprise_database()),
+        ExampleKind::EnterpriseCrdb => serde_json::to_value(enterprise_crdb()),
+    };
+    Ok(value.context("Failed to serialize example")?)
+}
+
+fn cloud_subscription() -> SubscriptionCreateRequest {
+    SubscriptionCreateRequest {
+        name: Some("my-subscription".to_string()),
+        dry_run: Some(false),
+        deployment_type: Some("single-region".to_string()),
+        payment_method: Some("credit-card".to_string()),
+        payment_method_id: Some(12345),
+        memory_storage: Some("ram".to_string()),
+        persistent_storage_encryption_type: None,
+        cloud_providers: vec![SubscriptionSpec {
+            provider: Some("AWS".to_string()),
+            cloud_account_id: Some(1),
+            regions: vec![SubscriptionRegionSpec {
+                region: "us-east-1".to_string(),
+                multiple_availability_zones: Some(false),
+                preferred_availability_zones: None,
+                networking: Some(SubscriptionRegionNetworkingSpec {
+                    deployment_cidr: Some("10.0.0.0/24".to_string()),
+                    vpc_id: None,
+                    subnet_ids: None,
+                    security_group_id: None,
+                    extra: json!({}),
+                }),
+                extra: json!({}),
+            }],
+            extra: json!({}),
+        }],
+        databases: vec![SubscriptionDatabaseSpec {
+            name: "my-database".to_string(),
+            protocol: "redis".to_string(),
+            memory_limit_in_gb: None,
+            dataset_size_in_gb: Some(1.0),
+            support_oss_cluster_api: Some(false),
+            data_persistence: Some("none".to_string()),
+            replication: Some(true),
+            throughput_measurement: Some(DatabaseThroughputSpec {
+                by: "operations-per-second".to_string(),
+                value: 25000,
+                extra: json!({}),
+            }),
+            local_throughput_measurement: None,
+            modules: Some(vec![DatabaseModuleSpec {
+                name: "RedisJSON".to_string(),
+                parameters: None,
+                extra: json!({}),
+            }]),
+            quantity: Some(1),
+            average_item_size_in_bytes: None,
+            resp_version: None,
+            redis_version: None,
+            sharding_type: None,
+            query_performance_factor: None,
+            extra: json!({}),
+        }],
+        redis_version: None,
+        command_type: None,
+        extra: json!({}),
+    }
+}
+
+fn cloud_database() -> DatabaseCreateRequest {
+    DatabaseCreateRequest {
+        subscription_id: None,
+        dry_run: Some(false),
+        name: "my-database".to_string(),
+        protocol: Some("redis".to_string()),
+        port: None,
+        memory_limit_in_gb: None,
+        dataset_size_in_gb: Some(1.0),
+        redis_version: None,
+        resp_version: Some("resp3".to_string()),
+        support_oss_cluster_api: Some(false),
+        use_external_endpoint_for_oss_cluster_api: None,
+        data_persistence: Some("aof-every-1-second".to_string()),
+        data_eviction_policy: Some("volatile-lru".to_string()),
+        replication: Some(true),
+        replica_of: None,
+        replica: None,
+        throughput_measurement: Some(redis_cloud::databases::DatabaseThroughputSpec {
+            by: "operations-per-second".to_string(),
+            value: 25000,
+            extra: json!({}),
+        }),
+        local_throughput_measurement: None,
+        average_item_size_in_bytes: None,
+        periodic_backup_path: None,
+        remote_backup: None,
+        source_ip: Some(vec!["0.0.0.0/0".to_string()]),
+        client_ssl_certificate: None,
+        client_tls_certificates: None,
+        enable_tls: Some(false),
+        password: Some("change-me".to_string()),
+        sasl_username: None,
+        sasl_password: None,
+        alerts: None,
+        modules: Some(vec![redis_cloud::databases::DatabaseModuleSpec {
+            name: "RedisJSON".to_string(),
+            parameters: Some(HashMap::new()),
+            extra: json!({}),
+        }]),
+        sharding_type: None,
+        command_type: None,
+        query_performance_factor: None,
+        extra: json!({}),
+    }
+}
+
+fn enterprise_database() -> CreateDatabaseRequest {
+    CreateDatabaseRequest::builder()
+        .name("my-database")
+        .memory_size(1024 * 1024 * 1024)
+        .port(12000)
+        .replication(true)
+        .persistence("aof")
+        .eviction_policy("volatile-lru")
+        .sharding(false)
+        .shards_count(1)
+        .proxy_policy("single")
+        .rack_aware(false)
+        .module_list(vec![ModuleConfig::builder().module_name("ReJSON").build()])
+        .crdt(false)
+        .authentication_redis_pass("change-me")
+        .build()
+}
+
+fn enterprise_crdb() -> CreateCrdbRequest {
+    let instance = |cluster: &str| {
+        CreateCrdbInstance::builder()
+            .cluster(cluster)
+            .cluster_url(format!("https://{}:9443", cluster))
+            .username("admin@example.com")
+            .password("change-me")
+            .build()
+    };
+
+    CreateCrdbRequest::builder()
+        .name("my-crdb")
+        .memory_size(1024 * 1024 * 1024)
+        .instances(vec![
+            instance("cluster1.example.com"),
+            instance("cluster2.example.com"),
+        ])
+        .encryption(true)
+        .data_persistence("aof")
+        .eviction_policy("noeviction")
+        .build()
+}
+
+/// Field descriptions shown as YAML comments, keyed by dotted field path
+fn annotations(kind: ExampleKind) -> &'static [(&'static str, &'static str)] {
+    match kind {
+        ExampleKind::CloudSubscription => &[
+            ("name", "Optional. Subscription name"),
+            (
+                "dryRun",
+                "Optional. Validate and plan without creating resources",
+            ),
+            (
+                "deploymentType",
+                "Optional. 'single-region' or 'active-active'",
+            ),
+            ("paymentMethod", "Optional. 'credit-card' or 'marketplace'"),
+            (
+                "paymentMethodId",
+                "Required for credit-card payment. See: redisctl cloud payment-method list",
+            ),
+            (
+                "memoryStorage",
+                "Optional. 'ram' or 'ram-and-flash' (Auto Tiering)",
+            ),
+            (
+                "cloudProviders",
+                "Required. Cloud provider, regions and networking",
+            ),
+            (
+                "cloudProviders.provider",
+                "Optional. 'AWS', 'GCP' or 'Azure'. Default: AWS",
+            ),
+            (
+                "cloudProviders.cloudAccountId",
+                "Optional. 1 = Redis internal cloud account",
+            ),
+            (
+                "cloudProviders.regions",
+                "Required. One region, or several for Active-Active",
+            ),
+            (
+                "cloudProviders.regions.region",
+                "Required. Region name as defined by the cloud provider",
+            ),
+            (
+                "cloudProviders.regions.multipleAvailabilityZones",
+                "Optional. Deploy across availability zones",
+            ),
+            (
+                "cloudProviders.regions.networking.deploymentCidr",
+                "Optional. /24 CIDR for the deployment",
+            ),
+            (
+                "databases",
+                "Required. Databases to create with the subscription",
+            ),
+            (
+                "databases.name",
+                "Required. Letters, digits and hyphens, at most 40 characters",
+            ),
+            ("databases.protocol", "Required. 'redis' or 'memcached'"),
+            (
+                "databases.datasetSizeInGb",
+                "Optional. Dataset size; do not combine with memoryLimitInGb",
+            ),
+            (
+                "databases.supportOssClusterApi",
+                "Optional. Enable the OSS Cluster API",
+            ),
+            (
+                "databases.dataPersistence",
+                "Optional. e.g. 'none', 'aof-every-1-second', 'snapshot-every-1-hour'",
+            ),
+            ("databases.replication", "Optional. Default: true"),
+            (
+                "databases.throughputMeasurement",
+                "Optional. Expected throughput",
+            ),
+            (
+                "databases.throughputMeasurement.by",
+                "'operations-per-second' or 'number-of-shards'",
+            ),
+            (
+                "databases.modules",
+                "Optional. Advanced capabilities, e.g. RedisJSON, RediSearch",
+            ),
+            (
+                "databases.quantity",
+                "Optional. Number of databases with these settings. Default: 1",
+            ),
+        ],
+        ExampleKind::CloudDatabase => &[
+            (
+                "dryRun",
+                "Optional. Validate and plan without creating the database",
+            ),
+            (
+                "name",
+                "Required. Letters, digits and hyphens, at most 40 characters",
+            ),
+            (
+                "protocol",
+                "Optional. 'redis' or 'memcached'. Default: redis",
+            ),
+            (
+                "datasetSizeInGb",
+                "Optional. Dataset size; do not combine with memoryLimitInGb",
+            ),
+            ("respVersion", "Optional. 'resp2' or 'resp3'"),
+            (
+                "supportOssClusterApi",
+                "Optional. Enable the OSS Cluster API",
+            ),
+            (
+                "dataPersistence",
+                "Optional. e.g. 'none', 'aof-every-1-second', 'snapshot-every-1-hour'",
+            ),
+            ("dataEvictionPolicy", "Optional. Default: volatile-lru"),
+            ("replication", "Optional. Default: true"),
+            ("throughputMeasurement", "Optional. Expected throughput"),
+            (
+                "throughputMeasurement.by",
+                "'operations-per-second' or 'number-of-shards'",
+            ),
+            ("sourceIp", "Optional. Allowed source CIDRs"),
+            ("enableTls", "Optional. Require TLS for all connections"),
+            ("password", "Optional. Generated if not set"),
+            (
+                "modules",
+                "Optional. Advanced capabilities, e.g. RedisJSON, RediSearch",
+            ),
+            ("modules.parameters", "Optional. Module parameters"),
+        ],
+        ExampleKind::EnterpriseDatabase => &[
+            ("name", "Required. Database name"),
+            ("memory_size", "Optional. Memory limit in bytes"),
+            ("port", "Optional. Assigned automatically if not set"),
+            ("replication", "Optional. Enable in-memory replicas"),
+            ("persistence", "Optional. 'disabled', 'aof' or 'snapshot'"),
+            (
+                "eviction_policy",
+                "Optional. e.g. 'volatile-lru', 'allkeys-lru', 'noeviction'",
+            ),
+            ("sharding", "Optional. Enable clustering"),
+            ("shards_count", "Optional. Number of primary shards"),
+            (
+                "proxy_policy",
+                "Optional. 'single', 'all-master-shards' or 'all-nodes'",
+            ),
+            (
+                "rack_aware",
+                "Optional. Place replicas in different racks/zones",
+            ),
+            (
+                "module_list",
+                "Optional. Modules to enable. See: redisctl enterprise module list",
+            ),
+            (
+                "crdt",
+                "Optional. Create as a CRDT (use crdb create for Active-Active)",
+            ),
+            (
+                "authentication_redis_pass",
+                "Optional. Password for the default user",
+            ),
+        ],
+        ExampleKind::EnterpriseCrdb => &[
+            ("name", "Required. Active-Active database name"),
+            ("memory_size", "Required. Memory limit in bytes"),
+            ("instances", "Required. One entry per participating cluster"),
+            ("instances.cluster", "Required. Cluster FQDN"),
+            ("instances.cluster_url", "Optional. Cluster API URL"),
+            (
+                "instances.username",
+                "Optional. Admin user on the participating cluster",
+            ),
+            (
+                "instances.password",
+                "Optional. Admin password on the participating cluster",
+            ),
+            (
+                "encryption",
+                "Optional. Encrypt replication traffic between clusters",
+            ),
+            (
+                "data_persistence",
+                "Optional. 'disabled', 'aof' or 'snapshot'",
+            ),
+            ("eviction_policy", "Optional. Default: noeviction"),
+        ],
+    }
+}
+
+/// Render a value as YAML with a comment above each annotated field
+///
+/// Field paths are tracked by indentation; list items share the path of their parent key,
+/// and each path is annotated only at its first occurrence.
+fn annotated_yaml(value: &Value, annotations: &[(&str, &str)]) -> CliResult<String> {
+    let yaml = serde_yaml::to_string(value).context("Failed to serialize example")?;
+    let mut out = String::new();
+    let mut parents: Vec<(usize, String)> = Vec::new();
+    let mut annotated = HashSet::new();
+
+    for line in yaml.lines() {
+        let trimmed = line.trim_start();
+        let indent = line.len() - trimmed.len();
+        let (depth, field) = match trimmed.strip_prefix("- ") {
+            Some(rest) => (indent + 2, rest),
+            None => (indent, trimmed),
+        };
+
+        if let Some((key, _)) = field.split_once(':') {
+            parents.retain(|(parent_depth, _)| *parent_depth < depth);
+            let path = parents
+                .iter()
+                .map(|(_, name)| name.as_str())
+                .chain(std::iter::once(key))
+                .collect::<Vec<_>>()
+                .join(".");
+
+            if let Some((_, comment)) = annotations.iter().find(|(name, _)| *name == path)
+                && annotated.insert(path)
+            {
+                out.push_str(&format!("{}# {}\n", " ".repeat(indent), comment));
+            }
+            parents.push((depth, key.to_string()));
+        }
+        out.push_str(line);
+        out.push('\n');
+    }
+
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALL: [ExampleKind; 4] = [
+        ExampleKind::CloudSubscription,
+        ExampleKind::CloudDatabase,
+        ExampleKind::EnterpriseDatabase,
+        ExampleKind::EnterpriseCrdb,
+    ];
+
+    #[test]
+    fn test_skeletons_round_trip_through_request_models() {
+        let subscription = skeleton(ExampleKind::CloudSubscription).unwrap();
+        serde_json::from_value::<SubscriptionCreateRequest>(subscription).unwrap();
+
+        let database = skeleton(ExampleKind::CloudDatabase).unwrap();
+        serde_json::from_value::<DatabaseCreateRequest>(database).unwrap();
+
+        let bdb = skeleton(ExampleKind::EnterpriseDatabase).unwrap();
+        serde_json::from_value::<CreateDatabaseRequest>(bdb).unwrap();
+
+        let crdb = skeleton(ExampleKind::EnterpriseCrdb).unwrap();
+        assert_eq!(crdb["instances"].as_array().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_annotations_match_skeleton_fields() {
+        for kind in ALL {
+            let yaml = serde_yaml::to_string(&skeleton(kind).unwrap()).unwrap();
+            let annotated = annotated_yaml(&skeleton(kind).unwrap(), annotations(kind)).unwrap();
+            let comments = annotated
+                .lines()
+                .filter(|l| l.trim_start().starts_with('#'));
+            assert_eq!(
+                comments.count(),
+                annotations(kind).len(),
+                "{:?} annotates a field missing from the skeleton:\n{}",
+                kind,
+                yaml
+            );
+        }
+    }
+
+    #[test]
+    fn test_annotated_yaml_places_comments_above_fields() {
+        let value = json!({
+            "name": "db",
+            "modules": [{"args": "x", "name": "RedisJSON"}, {"args": "y", "name": "search"}]
+        });
+        let yaml = annotated_yaml(
+            &value,
+            &[
+                ("name", "Database name"),
+                ("modules.name", "Module name"),
+                ("modules.args", "Module arguments"),
+            ],
+        )
+        .unwrap();
+
+        let parsed: Value = serde_yaml::from_str(&yaml).unwrap();
+        assert_eq!(parsed, value);
+        assert!(yaml.contains("# Database name\nname: db"));
+        assert!(yaml.contains("# Module arguments\n- args: x"));
+        assert!(yaml.contains("  # Module name\n  name: RedisJSON"));
+        assert_eq!(yaml.matches("# Module name").count(), 1);
+    }
+}
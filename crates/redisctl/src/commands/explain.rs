@@ -0,0 +1,528 @@
+//! `--explain`: print the REST calls a command makes instead of running it
+//!
+//! Each supported command has a call plan in [`PLANS`]: the requests it sends, in order, as
+//! method and path templates. A `{name}` in a template is replaced with the value of the
+//! command's `name` argument; `{name.0}` and `{name.1}` take the parts of a `sub:db` style ID.
+//! An argument with several values repeats the call once per value. Placeholders that are not
+//! arguments, such as `{action_uid}`, stand for values only known once the command runs and are
+//! printed as they are. A call can depend on a flag, so the plan follows the options given.
+//!
+//! Plans are written by hand, so the tests in `main.rs` run each planned command against a local
+//! server and compare the requests it sends with its plan. A new plan needs such a run too, or an
+//! entry in their `NOT_RUN` list saying why it cannot have one.
+
+#![allow(dead_code)]
+
+use crate::cli::OutputFormat;
+use crate::error::{RedisCtlError, Result as CliResult};
+use crate::output::notice;
+use clap::ArgMatches;
+use clap::parser::ValueSource;
+use serde::Serialize;
+use tabled::{Table, Tabled, settings::Style};
+
+use super::enterprise::utils::{handle_output, print_formatted_output};
+
+/// When a planned call is made
+#[derive(Debug, Clone, Copy)]
+enum Condition {
+    Always,
+    /// The argument was given on the command line or through the environment
+    Given(&'static str),
+    /// The argument was not given
+    NotGiven(&'static str),
+    /// One of the argument's values is this one
+    Includes(&'static str, &'static str),
+}
+
+/// One request of a call plan
+#[derive(Debug, Clone, Copy)]
+struct Step {
+    method: &'static str,
+    path: &'static str,
+    note: Option<&'static str>,
+    condition: Condition,
+}
+
+impl Step {
+    const fn new(method: &'static str, path: &'static str) -> Self {
+        Step {
+            method,
+            path,
+            note: None,
+            condition: Condition::Always,
+        }
+    }
+
+    const fn note(self, note: &'static str) -> Self {
+        Step {
+            note: Some(note),
+            ..self
+        }
+    }
+
+    const fn when(self, arg: &'static str) -> Self {
+        Step {
+            condition: Condition::Given(arg),
+            ..self
+        }
+    }
+
+    const fn unless(self, arg: &'static str) -> Self {
+        Step {
+            condition: Condition::NotGiven(arg),
+            ..self
+        }
+    }
+
+    const fn when_value(self, arg: &'static str, value: &'static str) -> Self {
+        Step {
+            condition: Condition::Includes(arg, value),
+            ..self
+        }
+    }
+}
+
+const fn get(path: &'static str) -> Step {
+    Step::new("GET", path)
+}
+
+const fn post(path: &'static str) -> Step {
+    Step::new("POST", path)
+}
+
+const fn put(path: &'static str) -> Step {
+    Step::new("PUT", path)
+}
+
+const fn delete(path: &'static str) -> Step {
+    Step::new("DELETE", path)
+}
+
+/// Call plans by command path, as typed after `redisctl`
+const PLANS: &[(&str, &[Step])] = &[
+    ("api", &[Step::new("{method}", "{path}")]),
+    // Cloud
+    (
+        "cloud subscription list",
+        &[get("/subscriptions"), get("/fixed/subscriptions")],
+    ),
+    (
+        "cloud subscription get",
+        &[
+            get("/subscriptions/{id}"),
+            get("/fixed/subscriptions/{id}").note("if it is not a flexible subscription"),
+        ],
+    ),
+    (
+        "cloud subscription cmk status",
+        &[get("/subscriptions/{id}")],
+    ),
+    (
+        "cloud subscription cmk set",
+        &[
+            get("/subscriptions/{id}"),
+            put("/subscriptions/{id}/cmks"),
+            get("/tasks/{taskId}")
+                .note("polled until the task finishes")
+                .when("wait"),
+        ],
+    ),
+    ("cloud account get", &[get("/")]),
+    (
+        "cloud account get-payment-methods",
+        &[get("/payment-methods")],
+    ),
+    ("cloud account list-modules", &[get("/database-modules")]),
+    (
+        "cloud account get-persistence-options",
+        &[get("/data-persistence")],
+    ),
+    (
+        "cloud database get",
+        &[
+            get("/fixed/subscriptions/{id.0}/databases/{id.1}"),
+            get("/subscriptions/{id.0}/databases/{id.1}")
+                .note("if it is not an Essentials database"),
+        ],
+    ),
+    (
+        "cloud database delete",
+        &[
+            delete("/subscriptions/{id.0}/databases/{id.1}"),
+            get("/tasks/{taskId}")
+                .note("polled until the task finishes")
+                .when("wait"),
+        ],
+    ),
+    ("cloud task get", &[get("/tasks/{id}")]),
+    ("cloud user list", &[get("/users")]),
+    ("cloud user get", &[get("/users/{id}")]),
+    (
+        "cloud logs forward",
+        &[
+            get("/logs?offset={offset}&limit=100")
+                .note("paged back to --since or the last forwarded entry")
+                .when_value("sources", "system"),
+            get("/session-logs?offset={offset}&limit=100")
+                .note("paged back to --since or the last forwarded entry")
+                .when_value("sources", "session"),
+        ],
+    ),
+    // Enterprise
+    ("enterprise cluster get", &[get("/v1/cluster")]),
+    (
+        "enterprise cluster recover",
+        &[
+            get("/v1/cluster/recovery"),
+            post("/v1/cluster/actions/recover").unless("dry_run"),
+            get("/v1/actions/{action_uid}")
+                .note("polled until the recovery finishes")
+                .unless("dry_run"),
+        ],
+    ),
+    (
+        "enterprise cluster reset",
+        &[
+            get("/v1/cluster"),
+            post("/v1/cluster/actions/reset"),
+            get("/v1/actions/{action_uid}").note("polled until the API stops answering"),
+        ],
+    ),
+    (
+        "enterprise cluster maintenance-mode status",
+        &[get("/v1/nodes"), get("/v1/nodes/status")],
+    ),
+    (
+        "enterprise cluster maintenance-mode enable",
+        &[
+            get("/v1/nodes"),
+            get("/v1/nodes/status"),
+            post("/v1/nodes/{nodes}/actions/maintenance_on"),
+            get("/v1/actions/{action_uid}").note("polled until each node's action finishes"),
+            get("/v1/nodes"),
+            get("/v1/nodes/status"),
+        ],
+    ),
+    (
+        "enterprise cluster maintenance-mode disable",
+        &[
+            get("/v1/nodes"),
+            get("/v1/nodes/status"),
+            post("/v1/nodes/{nodes}/actions/maintenance_off")
+                .note("for each node in maintenance with --all"),
+            get("/v1/actions/{action_uid}").note("polled until each node's action finishes"),
+            get("/v1/nodes"),
+            get("/v1/nodes/status"),
+        ],
+    ),
+    (
+        "enterprise database list",
+        &[get("/v1/bdbs").note("with ?fields= unless --all-fields or --query is given")],
+    ),
+    ("enterprise database get", &[get("/v1/bdbs/{id}")]),
+    (
+        "enterprise database create",
+        &[
+            post("/v1/bdbs/dry-run").when("dry_run"),
+            post("/v1/bdbs").unless("dry_run"),
+        ],
+    ),
+    (
+        "enterprise database update",
+        &[get("/v1/bdbs/{id}").when("patch"), put("/v1/bdbs/{id}")],
+    ),
+    ("enterprise database delete", &[delete("/v1/bdbs/{id}")]),
+    ("enterprise database sync status", &[get("/v1/bdbs/{id}")]),
+    (
+        "enterprise database sync pause",
+        &[get("/v1/bdbs/{id}"), put("/v1/bdbs/{id}")],
+    ),
+    (
+        "enterprise database sync resume",
+        &[get("/v1/bdbs/{id}"), put("/v1/bdbs/{id}")],
+    ),
+    ("enterprise crdb list", &[get("/v1/crdbs")]),
+    ("enterprise crdb get", &[get("/v1/crdbs/{id}")]),
+    ("enterprise node list", &[get("/v1/nodes")]),
+    ("enterprise node get", &[get("/v1/nodes/{id}")]),
+    ("enterprise role list", &[get("/v1/roles")]),
+    ("enterprise role get", &[get("/v1/roles/{id}")]),
+    ("enterprise role delete", &[delete("/v1/roles/{id}")]),
+    (
+        "enterprise traffic overview",
+        &[
+            get("/v1/endpoints/stats"),
+            get("/v1/proxies/stats/last"),
+            get("/v1/proxies"),
+            get("/v1/bdbs"),
+        ],
+    ),
+    ("enterprise user list", &[get("/v1/users")]),
+    ("enterprise user get", &[get("/v1/users/{id}")]),
+    ("enterprise user delete", &[delete("/v1/users/{id}")]),
+    (
+        "enterprise user reset-password",
+        &[
+            get("/v1/users/{id}"),
+            put("/v1/users/{id}").note("sets the new password"),
+            delete("/v1/users/{id}/sessions").unless("keep_sessions"),
+        ],
+    ),
+];
+
+/// A request a command would send
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PlannedCall {
+    pub method: String,
+    pub path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+}
+
+/// The commands that have a call plan
+pub fn planned_commands() -> impl Iterator<Item = &'static str> {
+    PLANS.iter().map(|(path, _)| *path)
+}
+
+/// The calls of the command in `matches`, or `None` if it has no call plan
+pub fn plan_for(matches: &ArgMatches) -> Option<(String, Vec<PlannedCall>)> {
+    let (command, leaf) = command_path(matches);
+    let (_, steps) = PLANS.iter().find(|(path, _)| *path == command)?;
+    let calls = steps
+        .iter()
+        .filter(|step| applies(step.condition, leaf))
+        .flat_map(|step| expand(step, leaf))
+        .collect();
+    Some((command, calls))
+}
+
+/// The subcommand names down to the command that runs, and that command's arguments
+fn command_path(matches: &ArgMatches) -> (String, &ArgMatches) {
+    let mut names = Vec::new();
+    let mut current = matches;
+    while let Some((name, sub)) = current.subcommand() {
+        names.push(name);
+        current = sub;
+    }
+    (names.join(" "), current)
+}
+
+fn is_given(matches: &ArgMatches, arg: &str) -> bool {
+    matches.ids().any(|id| id.as_str() == arg)
+        && matches
+            .value_source(arg)
+            .is_some_and(|source| source != ValueSource::DefaultValue)
+}
+
+fn values(matches: &ArgMatches, arg: &str) -> Vec<String> {
+    match matches.try_get_raw(arg) {
+        Ok(Some(raw)) => raw.map(|v| v.to_string_lossy().into_owned()).collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn applies(condition: Condition, matches: &ArgMatches) -> bool {
+    match condition {
+        Condition::Always => true,
+        Condition::Given(arg) => is_given(matches, arg),
+        Condition::NotGiven(arg) => !is_given(matches, arg),
+        Condition::Includes(arg, value) => values(matches, arg).iter().any(|v| v == value),
+    }
+}
+
+/// Fill in a step's placeholders, once per value of a repeated argument
+fn expand(step: &Step, matches: &ArgMatches) -> Vec<PlannedCall> {
+    let repeated = placeholders(step.path)
+        .into_iter()
+        .map(|name| values(matches, name.split('.').next().unwrap_or(name)))
+        .find(|values| values.len() > 1);
+    let fill = |value: Option<&str>| PlannedCall {
+        method: fill_template(step.method, matches, value).to_uppercase(),
+        path: fill_template(step.path, matches, value),
+        note: step.note.map(str::to_string),
+    };
+    match repeated {
+        Some(values) => values.iter().map(|value| fill(Some(value))).collect(),
+        None => vec![fill(None)],
+    }
+}
+
+/// Names between braces in a template
+fn placeholders(template: &str) -> Vec<&str> {
+    template
+        .split('{')
+        .skip(1)
+        .filter_map(|part| part.split_once('}').map(|(name, _)| name))
+        .collect()
+}
+
+/// Replace placeholders that name arguments; `repeated` is the value to use for an argument with
+/// several values
+fn fill_template(template: &str, matches: &ArgMatches, repeated: Option<&str>) -> String {
+    let mut filled = template.to_string();
+    for name in placeholders(template) {
+        let (arg, part) = match name.split_once('.') {
+            Some((arg, part)) => (arg, part.parse::<usize>().ok()),
+            None => (name, None),
+        };
+        let arg_values = values(matches, arg);
+        let value = match arg_values.as_slice() {
+            [] => continue,
+            [value] => value.as_str(),
+            _ => repeated.unwrap_or(arg_values[0].as_str()),
+        };
+        let value = match part {
+            Some(index) => match value.split(':').nth(index) {
+                Some(part) => part,
+                None => continue,
+            },
+            None => value,
+        };
+        filled = filled.replace(&format!("{{{}}}", name), value);
+    }
+    filled
+}
+
+#[derive(Tabled)]
+struct CallRow {
+    #[tabled(rename = "METHOD")]
+    method: String,
+    #[tabled(rename = "PATH")]
+    path: String,
+    #[tabled(rename = "NOTE")]
+    note: String,
+}
+
+/// Print the call plan of the command in `matches`
+pub fn run_explain(
+    matches: &ArgMatches,
+    output_format: OutputFormat,
+    query: Option<&str>,
+) -> CliResult<()> {
+    let (command, calls) = plan_for(matches).ok_or_else(|| {
+        let (command, _) = command_path(matches);
+        RedisCtlError::Validation {
+            message: format!(
+                "--explain has no call plan for `{}`; run it with -vv to log the requests it sends",
+                command
+            ),
+        }
+    })?;
+
+    match output_format {
+        OutputFormat::Auto | OutputFormat::Table if query.is_none() => {
+            let rows: Vec<CallRow> = calls
+                .into_iter()
+                .map(|call| CallRow {
+                    method: call.method,
+                    path: call.path,
+                    note: call.note.unwrap_or_default(),
+                })
+                .collect();
+            let mut table = Table::new(rows);
+            table.with(Style::blank());
+            println!("{}", table);
+            notice!("`{}` was not run; no requests were sent", command);
+            Ok(())
+        }
+        _ => {
+            let json = serde_json::json!({ "command": command, "calls": calls });
+            let data = handle_output(json, output_format, query)?;
+            print_formatted_output(data, output_format)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::Cli;
+    use clap::CommandFactory;
+
+    fn plan(args: &[&str]) -> Vec<PlannedCall> {
+        let matches = Cli::command()
+            .try_get_matches_from(std::iter::once("redisctl").chain(args.iter().copied()))
+            .unwrap();
+        plan_for(&matches).unwrap().1
+    }
+
+    fn calls(plan: &[PlannedCall]) -> Vec<String> {
+        plan.iter()
+            .map(|call| format!("{} {}", call.method, call.path))
+            .collect()
+    }
+
+    #[test]
+    fn test_every_plan_names_a_command() {
+        let root = Cli::command();
+        for (path, _) in PLANS {
+            let mut command = &root;
+            for name in path.split(' ') {
+                command = command
+                    .find_subcommand(name)
+                    .unwrap_or_else(|| panic!("no command `{}`", path));
+            }
+        }
+    }
+
+    #[test]
+    fn test_arguments_fill_placeholders() {
+        assert_eq!(
+            calls(&plan(&["enterprise", "database", "sync", "pause", "3"])),
+            ["GET /v1/bdbs/3", "PUT /v1/bdbs/3"]
+        );
+        assert_eq!(
+            calls(&plan(&["api", "enterprise", "get", "/v1/cluster"])),
+            ["GET /v1/cluster"]
+        );
+        assert_eq!(
+            calls(&plan(&["cloud", "database", "delete", "12:34"])),
+            ["DELETE /subscriptions/12/databases/34"]
+        );
+    }
+
+    #[test]
+    fn test_repeated_argument_repeats_call() {
+        let plan = plan(&[
+            "enterprise",
+            "cluster",
+            "maintenance-mode",
+            "enable",
+            "--node",
+            "2",
+            "--node",
+            "3",
+        ]);
+        let calls = calls(&plan);
+        assert_eq!(calls[2], "POST /v1/nodes/2/actions/maintenance_on");
+        assert_eq!(calls[3], "POST /v1/nodes/3/actions/maintenance_on");
+        // Runtime values stay placeholders
+        assert_eq!(calls[4], "GET /v1/actions/{action_uid}");
+    }
+
+    #[test]
+    fn test_conditions_follow_flags() {
+        assert_eq!(
+            calls(&plan(&["enterprise", "cluster", "recover", "--dry-run"])),
+            ["GET /v1/cluster/recovery"]
+        );
+        assert_eq!(
+            plan(&["cloud", "database", "delete", "12:34", "--wait"]).len(),
+            2
+        );
+        let logs = plan(&[
+            "cloud", "logs", "forward", "--stdout", "--source", "session",
+        ]);
+        assert_eq!(logs.len(), 1);
+        assert!(logs[0].path.starts_with("/session-logs"));
+    }
+
+    #[test]
+    fn test_unplanned_command() {
+        let matches = Cli::command()
+            .try_get_matches_from(["redisctl", "enterprise", "cluster", "stats"])
+            .unwrap();
+        assert!(plan_for(&matches).is_none());
+    }
+}
@@ -2,4 +2,14 @@
 
 pub mod api;
 pub mod cloud;
+pub mod diff;
+pub mod doctor;
 pub mod enterprise;
+pub mod examples;
+pub mod explain;
+pub mod patch;
+pub mod search;
+pub mod self_update;
+pub mod selftest;
+pub mod serve;
+pub mod stats;
@@ -0,0 +1,127 @@
+//! JSON Patch (RFC 6902) support for update commands
+//!
+//! `--patch` edits a resource without writing a full payload: the current object is fetched,
+//! the patch is applied locally, and the top-level fields that changed are sent as the update.
+//! Fields removed by the patch are sent as `null`.
+
+use crate::error::{RedisCtlError, Result as CliResult};
+use json_patch::Patch;
+use serde_json::{Map, Value};
+
+/// Parse a patch given as a JSON array or `@file.json`
+pub fn read_patch(patch: &str) -> CliResult<Patch> {
+    let text = match patch.strip_prefix('@') {
+        Some(path) => std::fs::read_to_string(path).map_err(|e| RedisCtlError::FileError {
+            path: path.to_string(),
+            message: e.to_string(),
+        })?,
+        None => patch.to_string(),
+    };
+    serde_json::from_str(&text).map_err(|e| RedisCtlError::Validation {
+        message: format!("Invalid JSON Patch: {}", e),
+    })
+}
+
+/// Apply a patch to the current document and return the update to send
+///
+/// Returns `None` when the patch leaves the document unchanged.
+pub fn patch_update(current: &Value, patch: &str) -> CliResult<Option<Value>> {
+    let patch = read_patch(patch)?;
+    let mut patched = current.clone();
+    json_patch::patch(&mut patched, &patch).map_err(|e| RedisCtlError::Validation {
+        message: format!("Failed to apply patch: {}", e),
+    })?;
+
+    let (Value::Object(before), Value::Object(after)) = (current, &patched) else {
+        return Err(RedisCtlError::Validation {
+            message: "Patched document must remain a JSON object".to_string(),
+        });
+    };
+    let changes = changed_fields(before, after);
+    Ok((!changes.is_empty()).then_some(Value::Object(changes)))
+}
+
+/// Top-level fields of `after` that differ from `before`, with removed fields set to `null`
+fn changed_fields(before: &Map<String, Value>, after: &Map<String, Value>) -> Map<String, Value> {
+    let mut changes: Map<String, Value> = after
+        .iter()
+        .filter(|(key, value)| before.get(*key) != Some(*value))
+        .map(|(key, value)| (key.clone(), value.clone()))
+        .collect();
+    for key in before.keys().filter(|key| !after.contains_key(*key)) {
+        changes.insert(key.clone(), Value::Null);
+    }
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn database() -> Value {
+        json!({
+            "uid": 1,
+            "name": "cache",
+            "memory_size": 1073741824,
+            "replication": false,
+            "tags": [{"key": "env", "value": "dev"}],
+            "backup_interval": 3600
+        })
+    }
+
+    #[test]
+    fn test_patch_update_sends_changed_fields() {
+        let update = patch_update(
+            &database(),
+            r#"[
+                {"op": "replace", "path": "/memory_size", "value": 2147483648},
+                {"op": "add", "path": "/tags/-", "value": {"key": "team", "value": "core"}},
+                {"op": "remove", "path": "/backup_interval"}
+            ]"#,
+        )
+        .unwrap()
+        .unwrap();
+
+        assert_eq!(
+            update,
+            json!({
+                "memory_size": 2147483648u64,
+                "tags": [{"key": "env", "value": "dev"}, {"key": "team", "value": "core"}],
+                "backup_interval": null
+            })
+        );
+    }
+
+    #[test]
+    fn test_patch_update_no_changes() {
+        let update = patch_update(
+            &database(),
+            r#"[{"op": "test", "path": "/name", "value": "cache"},
+                {"op": "replace", "path": "/replication", "value": false}]"#,
+        )
+        .unwrap();
+        assert_eq!(update, None);
+    }
+
+    #[test]
+    fn test_patch_update_errors() {
+        let failed_test = patch_update(
+            &database(),
+            r#"[{"op": "test", "path": "/name", "value": "other"}]"#,
+        );
+        assert!(failed_test.is_err());
+
+        let missing = patch_update(&database(), r#"[{"op": "remove", "path": "/nope"}]"#);
+        assert!(missing.is_err());
+
+        assert!(patch_update(&database(), r#"{"op": "replace"}"#).is_err());
+        assert!(
+            patch_update(
+                &database(),
+                r#"[{"op": "replace", "path": "", "value": 1}]"#
+            )
+            .is_err()
+        );
+    }
+}
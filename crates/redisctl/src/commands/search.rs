@@ -0,0 +1,421 @@
+//! Search across resource types of the active profile
+//!
+//! `search` lists every requested resource type with the profile's API and keeps the items that
+//! match an expression, evaluated locally. An expression is one or more terms that must all
+//! match:
+//!
+//! - `field~text` — the field contains `text`, ignoring case (`!~` negates)
+//! - `field=text` — the field equals `text`, ignoring case (`!=` negates)
+//! - `text` — shorthand for `name~text`
+//!
+//! `name` and `id` work for every type; any other field is looked up in the API object as is
+//! (`status`, `memoryLimitInGb`, `email`...). A term on a list field matches if any element does.
+
+#![allow(dead_code)]
+
+use crate::cli::OutputFormat;
+use crate::config::DeploymentType;
+use crate::connection::ConnectionManager;
+use crate::error::{RedisCtlError, Result as CliResult};
+use crate::output::notice;
+use anyhow::Context;
+use serde::Serialize;
+use serde_json::Value;
+use tabled::{Table, Tabled, settings::Style};
+
+use super::cloud::utils::{handle_output, print_formatted_output};
+
+/// Searchable types: (deployment, kind, list path, key of the list in the response)
+const RESOURCE_TYPES: &[(DeploymentType, &str, &str, &str)] = &[
+    (
+        DeploymentType::Cloud,
+        "subscription",
+        "/subscriptions",
+        "subscriptions",
+    ),
+    (DeploymentType::Cloud, "database", "", "databases"),
+    (DeploymentType::Cloud, "user", "/users", "users"),
+    (DeploymentType::Cloud, "acl-user", "/acl/users", "users"),
+    (DeploymentType::Cloud, "acl-role", "/acl/roles", "roles"),
+    (DeploymentType::Enterprise, "database", "/v1/bdbs", ""),
+    (DeploymentType::Enterprise, "node", "/v1/nodes", ""),
+    (DeploymentType::Enterprise, "user", "/v1/users", ""),
+    (DeploymentType::Enterprise, "role", "/v1/roles", ""),
+    (
+        DeploymentType::Enterprise,
+        "redis-acl",
+        "/v1/redis_acls",
+        "",
+    ),
+    (DeploymentType::Enterprise, "crdb", "/v1/crdbs", ""),
+    (DeploymentType::Enterprise, "module", "/v1/modules", ""),
+];
+
+/// Fields tried, in order, for a hit's name
+const NAME_FIELDS: &[&str] = &["name", "module_name", "email", "addr"];
+
+/// Fields tried, in order, for a hit's id
+const ID_FIELDS: &[&str] = &["uid", "id", "databaseId", "guid"];
+
+/// How a term compares a field with its text
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operator {
+    Contains,
+    NotContains,
+    Equals,
+    NotEquals,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Term {
+    pub field: String,
+    pub operator: Operator,
+    pub text: String,
+}
+
+/// A resource that matched
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SearchHit {
+    pub kind: String,
+    /// Id as other commands take it; `subscription:database` for Cloud databases
+    pub id: String,
+    pub name: String,
+}
+
+#[derive(Tabled)]
+struct HitRow {
+    #[tabled(rename = "KIND")]
+    kind: String,
+    #[tabled(rename = "ID")]
+    id: String,
+    #[tabled(rename = "NAME")]
+    name: String,
+}
+
+pub async fn run_search(
+    conn_mgr: &ConnectionManager,
+    profile_name: Option<&str>,
+    expression: &str,
+    types: &[String],
+    output_format: OutputFormat,
+    query: Option<&str>,
+) -> CliResult<()> {
+    let terms = parse_expression(expression)?;
+    let deployment = conn_mgr.get_profile(profile_name)?.deployment_type;
+    let kinds = resolve_types(deployment, types)?;
+
+    let mut hits = Vec::new();
+    for (kind, path, key) in kinds {
+        let items = match (deployment, kind) {
+            (DeploymentType::Cloud, "database") => {
+                fetch_cloud_databases(conn_mgr, profile_name).await?
+            }
+            (DeploymentType::Cloud, _) => {
+                let client = conn_mgr.create_cloud_client(profile_name).await?;
+                let response = client
+                    .get_raw(path)
+                    .await
+                    .with_context(|| format!("Failed to list {}s", kind))?;
+                items_of(response, key)
+            }
+            (DeploymentType::Enterprise, _) => {
+                let client = conn_mgr.create_enterprise_client(profile_name).await?;
+                let response = client
+                    .get_raw(path)
+                    .await
+                    .with_context(|| format!("Failed to list {}s", kind))?;
+                items_of(response, key)
+            }
+        };
+        hits.extend(
+            items
+                .iter()
+                .filter(|item| matches_all(item, &terms))
+                .map(|item| hit(kind, item)),
+        );
+    }
+
+    match output_format {
+        OutputFormat::Auto | OutputFormat::Table if query.is_none() => {
+            if hits.is_empty() {
+                notice!("No matches");
+                return Ok(());
+            }
+            let rows: Vec<HitRow> = hits
+                .into_iter()
+                .map(|hit| HitRow {
+                    kind: hit.kind,
+                    id: hit.id,
+                    name: hit.name,
+                })
+                .collect();
+            let mut table = Table::new(rows);
+            table.with(Style::blank());
+            println!("{}", table);
+            Ok(())
+        }
+        _ => {
+            let json = serde_json::to_value(&hits).context("Failed to serialize results")?;
+            let data = handle_output(json, output_format, query)?;
+            print_formatted_output(data, output_format)
+        }
+    }
+}
+
+/// The requested types that exist for `deployment`, all of them when none are requested
+fn resolve_types(
+    deployment: DeploymentType,
+    types: &[String],
+) -> CliResult<Vec<(&'static str, &'static str, &'static str)>> {
+    let available: Vec<_> = RESOURCE_TYPES
+        .iter()
+        .filter(|(d, ..)| *d == deployment)
+        .map(|(_, kind, path, key)| (*kind, *path, *key))
+        .collect();
+    if types.is_empty() {
+        return Ok(available);
+    }
+    types
+        .iter()
+        .map(|wanted| {
+            let wanted = wanted.trim().trim_end_matches('s');
+            available
+                .iter()
+                .find(|(kind, ..)| kind.eq_ignore_ascii_case(wanted))
+                .copied()
+                .ok_or_else(|| RedisCtlError::Validation {
+                    message: format!(
+                        "Unknown {} resource type '{}' (valid: {})",
+                        deployment,
+                        wanted,
+                        available
+                            .iter()
+                            .map(|(kind, ..)| *kind)
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    ),
+                })
+        })
+        .collect()
+}
+
+/// Databases of every subscription, with `subscriptionId` added to each
+async fn fetch_cloud_databases(
+    conn_mgr: &ConnectionManager,
+    profile_name: Option<&str>,
+) -> CliResult<Vec<Value>> {
+    let client = conn_mgr.create_cloud_client(profile_name).await?;
+    let subscriptions = client
+        .get_raw("/subscriptions")
+        .await
+        .context("Failed to list subscriptions")?;
+    let mut databases = Vec::new();
+    for subscription_id in items_of(subscriptions, "subscriptions")
+        .iter()
+        .filter_map(|sub| sub.get("id").and_then(Value::as_u64))
+    {
+        let page = crate::commands::cloud::subscription_impl::fetch_subscription_databases(
+            &client,
+            subscription_id as u32,
+        )
+        .await?;
+        databases.extend(page.into_iter().map(|mut db| {
+            if let Value::Object(map) = &mut db {
+                map.insert("subscriptionId".to_string(), subscription_id.into());
+            }
+            db
+        }));
+    }
+    Ok(databases)
+}
+
+/// The list in a response, whether the body is the array or holds it under `key`
+fn items_of(response: Value, key: &str) -> Vec<Value> {
+    match response {
+        Value::Array(items) => items,
+        Value::Object(mut map) => match map.remove(key) {
+            Some(Value::Array(items)) => items,
+            _ => Vec::new(),
+        },
+        _ => Vec::new(),
+    }
+}
+
+pub fn parse_expression(expression: &str) -> CliResult<Vec<Term>> {
+    let terms: Vec<Term> = expression
+        .split_whitespace()
+        .map(parse_term)
+        .collect::<CliResult<_>>()?;
+    if terms.is_empty() {
+        return Err(RedisCtlError::Validation {
+            message: "Empty search expression".to_string(),
+        });
+    }
+    Ok(terms)
+}
+
+fn parse_term(term: &str) -> CliResult<Term> {
+    // Two-character operators first, so `!=` is not read as `=`
+    const OPERATORS: &[(&str, Operator)] = &[
+        ("!~", Operator::NotContains),
+        ("!=", Operator::NotEquals),
+        ("~", Operator::Contains),
+        ("=", Operator::Equals),
+    ];
+    let found = OPERATORS
+        .iter()
+        .filter_map(|(symbol, operator)| term.find(symbol).map(|at| (at, *symbol, *operator)))
+        .min_by_key(|(at, symbol, _)| (*at, std::cmp::Reverse(symbol.len())));
+    let Some((at, symbol, operator)) = found else {
+        return Ok(Term {
+            field: "name".to_string(),
+            operator: Operator::Contains,
+            text: term.to_string(),
+        });
+    };
+    let field = &term[..at];
+    if field.is_empty() {
+        return Err(RedisCtlError::Validation {
+            message: format!("Search term '{}' has no field before '{}'", term, symbol),
+        });
+    }
+    Ok(Term {
+        field: field.to_string(),
+        operator,
+        text: term[at + symbol.len()..].to_string(),
+    })
+}
+
+pub fn matches_all(item: &Value, terms: &[Term]) -> bool {
+    terms.iter().all(|term| matches(item, term))
+}
+
+fn matches(item: &Value, term: &Term) -> bool {
+    let values = field_values(item, &term.field);
+    let text = term.text.to_lowercase();
+    let any = |f: &dyn Fn(&str) -> bool| values.iter().any(|value| f(&value.to_lowercase()));
+    match term.operator {
+        Operator::Contains => any(&|value| value.contains(&text)),
+        Operator::Equals => any(&|value| value == text),
+        Operator::NotContains => !any(&|value| value.contains(&text)),
+        Operator::NotEquals => !any(&|value| value == text),
+    }
+}
+
+/// Scalar values of a field as text; `name` and `id` fall back to the type's own fields
+fn field_values(item: &Value, field: &str) -> Vec<String> {
+    let fallbacks = match field {
+        "name" => NAME_FIELDS,
+        "id" => ID_FIELDS,
+        _ => &[][..],
+    };
+    let value = item
+        .get(field)
+        .or_else(|| fallbacks.iter().find_map(|f| item.get(*f)));
+    match value {
+        Some(Value::Array(items)) => items.iter().filter_map(scalar).collect(),
+        Some(value) => scalar(value).into_iter().collect(),
+        None => Vec::new(),
+    }
+}
+
+fn scalar(value: &Value) -> Option<String> {
+    match value {
+        Value::String(s) => Some(s.clone()),
+        Value::Number(n) => Some(n.to_string()),
+        Value::Bool(b) => Some(b.to_string()),
+        _ => None,
+    }
+}
+
+fn hit(kind: &str, item: &Value) -> SearchHit {
+    let first = |fields: &[&str]| {
+        fields
+            .iter()
+            .find_map(|f| item.get(*f).and_then(scalar))
+            .unwrap_or_default()
+    };
+    let id = match item.get("subscriptionId").and_then(Value::as_u64) {
+        Some(subscription) if kind == "database" => {
+            format!("{}:{}", subscription, first(ID_FIELDS))
+        }
+        _ => first(ID_FIELDS),
+    };
+    SearchHit {
+        kind: kind.to_string(),
+        id,
+        name: first(NAME_FIELDS),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_expression() {
+        let terms = parse_expression("name~prod status!=active eu").unwrap();
+        assert_eq!(
+            terms,
+            vec![
+                Term {
+                    field: "name".to_string(),
+                    operator: Operator::Contains,
+                    text: "prod".to_string()
+                },
+                Term {
+                    field: "status".to_string(),
+                    operator: Operator::NotEquals,
+                    text: "active".to_string()
+                },
+                Term {
+                    field: "name".to_string(),
+                    operator: Operator::Contains,
+                    text: "eu".to_string()
+                },
+            ]
+        );
+        assert!(parse_expression("  ").is_err());
+        assert!(parse_expression("~prod").is_err());
+    }
+
+    #[test]
+    fn test_matches() {
+        let db = json!({"databaseId": 7, "name": "Prod-Cache", "status": "active",
+                        "modules": ["search", "json"], "subscriptionId": 100});
+        let check = |expression: &str| matches_all(&db, &parse_expression(expression).unwrap());
+        assert!(check("name~prod"));
+        assert!(check("prod status=ACTIVE"));
+        assert!(check("modules=json"));
+        assert!(check("id=7"));
+        assert!(!check("status!=active"));
+        assert!(check("name!~staging"));
+        assert!(!check("region~us"));
+        assert_eq!(
+            hit("database", &db),
+            SearchHit {
+                kind: "database".to_string(),
+                id: "100:7".to_string(),
+                name: "Prod-Cache".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_resolve_types() {
+        let kinds = resolve_types(
+            DeploymentType::Cloud,
+            &["databases".to_string(), "user".to_string()],
+        )
+        .unwrap();
+        assert_eq!(
+            kinds.iter().map(|(kind, ..)| *kind).collect::<Vec<_>>(),
+            vec!["database", "user"]
+        );
+        let err = resolve_types(DeploymentType::Enterprise, &["subscription".to_string()])
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("valid: database, node"));
+    }
+}
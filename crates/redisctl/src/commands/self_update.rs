@@ -0,0 +1,435 @@
+//! In-place update from GitHub releases
+//!
+//! Releases are built by `dist` (see dist-workspace.toml), which publishes one archive per
+//! target, `redisctl-<target>.tar.gz`, next to a `.sha256` file holding its checksum. The
+//! update picks the newest release of the channel, downloads the archive for the target this
+//! binary was built for, checks it against the published checksum and only then swaps the
+//! binary, so a corrupted or truncated download never replaces a working one. The checksum comes
+//! from the same release as the archive, so it does not detect a release that was tampered
+//! with; releases are not signed yet.
+
+#![allow(dead_code)]
+
+use crate::error::{RedisCtlError, Result as CliResult};
+use crate::output::notice;
+use anyhow::Context;
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// GitHub API endpoint of the project's releases
+const RELEASES_URL: &str = "https://api.github.com/repos/joshrotenberg/redisctl/releases";
+
+/// Overrides [`RELEASES_URL`], e.g. for a mirror reachable from servers without GitHub access
+const RELEASES_URL_ENV: &str = "REDISCTL_RELEASES_URL";
+
+const HTTP_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Which releases to update to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum ReleaseChannel {
+    /// The latest release that is not marked as a prerelease
+    Stable,
+    /// The latest release, including prereleases
+    Prerelease,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Release {
+    pub tag_name: String,
+    #[serde(default)]
+    pub prerelease: bool,
+    #[serde(default)]
+    pub draft: bool,
+    #[serde(default)]
+    pub assets: Vec<Asset>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Asset {
+    pub name: String,
+    pub browser_download_url: String,
+}
+
+/// Options of `redisctl self-update`
+#[derive(Debug, Clone)]
+pub struct SelfUpdateOptions {
+    pub channel: ReleaseChannel,
+    /// Report whether an update is available without installing it
+    pub check: bool,
+    /// Install even when the latest release is not newer
+    pub reinstall: bool,
+    /// Skip the confirmation prompt
+    pub force: bool,
+}
+
+pub async fn run_self_update(options: &SelfUpdateOptions) -> CliResult<()> {
+    let current = env!("CARGO_PKG_VERSION");
+    let http = reqwest::Client::builder()
+        .timeout(HTTP_TIMEOUT)
+        .user_agent(concat!("redisctl/", env!("CARGO_PKG_VERSION")))
+        .build()
+        .context("Failed to create HTTP client")?;
+
+    let base = std::env::var(RELEASES_URL_ENV).unwrap_or_else(|_| RELEASES_URL.to_string());
+    let releases: Vec<Release> = http
+        .get(&base)
+        .query(&[("per_page", "30")])
+        .send()
+        .await
+        .and_then(reqwest::Response::error_for_status)
+        .map_err(|e| RedisCtlError::Network {
+            message: format!("Failed to list releases from {}: {}", base, e),
+            source: Some(Box::new(e)),
+        })?
+        .json()
+        .await
+        .context("Failed to parse the release list")?;
+    let release =
+        latest_release(&releases, options.channel).ok_or_else(|| RedisCtlError::NotFound {
+            message: format!("No {:?} release found", options.channel).to_lowercase(),
+            source: None,
+        })?;
+    let latest = version_of(&release.tag_name);
+
+    let newer = is_newer(latest, current);
+    if options.check {
+        if newer {
+            println!("redisctl {} is available (installed: {})", latest, current);
+        } else {
+            println!("redisctl {} is up to date", current);
+        }
+        return Ok(());
+    }
+    if !newer && !options.reinstall {
+        println!("redisctl {} is up to date", current);
+        return Ok(());
+    }
+
+    let target = current_target().ok_or_else(|| RedisCtlError::Validation {
+        message: "Self-update is not available for this platform; reinstall with the installer \
+                  from the release page"
+            .to_string(),
+    })?;
+    let archive_name = format!("redisctl-{}.tar.gz", target);
+    let asset = |name: &str| {
+        release
+            .assets
+            .iter()
+            .find(|asset| asset.name == name)
+            .ok_or_else(|| RedisCtlError::NotFound {
+                message: format!("Release {} has no {} asset", release.tag_name, name),
+                source: None,
+            })
+    };
+    let archive_asset = asset(&archive_name)?;
+    let checksum_asset = asset(&format!("{}.sha256", archive_name))?;
+
+    let exe = std::env::current_exe().context("Failed to locate the running binary")?;
+    let exe = exe.canonicalize().unwrap_or(exe);
+    if !options.force
+        && !crate::commands::cloud::utils::confirm_action(&format!(
+            "replace {} ({}) with {}",
+            exe.display(),
+            current,
+            latest
+        ))?
+    {
+        println!("Update cancelled");
+        return Ok(());
+    }
+
+    notice!("Downloading {}", archive_asset.browser_download_url);
+    let archive = download(&http, &archive_asset.browser_download_url).await?;
+    let checksum = download(&http, &checksum_asset.browser_download_url).await?;
+    let expected = parse_checksum(&String::from_utf8_lossy(&checksum)).ok_or_else(|| {
+        RedisCtlError::Validation {
+            message: format!(
+                "{} does not contain a SHA-256 checksum",
+                checksum_asset.name
+            ),
+        }
+    })?;
+    verify_checksum(&archive, &expected)?;
+
+    let binary = extract_binary(&archive)?;
+    replace_binary(&exe, &binary)?;
+    println!("Updated redisctl {} -> {}", current, latest);
+    Ok(())
+}
+
+/// The newest published release of the channel; the API lists releases newest first
+pub fn latest_release(releases: &[Release], channel: ReleaseChannel) -> Option<&Release> {
+    releases
+        .iter()
+        .filter(|release| !release.draft)
+        .find(|release| channel == ReleaseChannel::Prerelease || !release.prerelease)
+}
+
+/// The version in a release tag: `v0.3.0` and `redisctl-v0.3.0` both give `0.3.0`
+pub fn version_of(tag: &str) -> &str {
+    let tag = tag.strip_prefix("redisctl-").unwrap_or(tag);
+    tag.strip_prefix('v').unwrap_or(tag)
+}
+
+/// Whether `candidate` is a later version than `current`
+///
+/// Compares the numeric `major.minor.patch` parts; at equal numbers a release is newer than a
+/// prerelease (`0.3.0` > `0.3.0-rc.1`), and prereleases compare by semver precedence, so
+/// `rc.10` is newer than `rc.9`.
+pub fn is_newer(candidate: &str, current: &str) -> bool {
+    fn parse(version: &str) -> (Vec<u64>, Option<&str>) {
+        // Build metadata does not affect precedence
+        let version = version
+            .split_once('+')
+            .map_or(version, |(version, _)| version);
+        let (numbers, pre) = match version.split_once('-') {
+            Some((numbers, pre)) => (numbers, Some(pre)),
+            None => (version, None),
+        };
+        let numbers = numbers
+            .split('.')
+            .map(|part| part.parse().unwrap_or(0))
+            .collect();
+        (numbers, pre)
+    }
+    let (candidate_numbers, candidate_pre) = parse(candidate);
+    let (current_numbers, current_pre) = parse(current);
+    match candidate_numbers.cmp(&current_numbers) {
+        std::cmp::Ordering::Equal => match (candidate_pre, current_pre) {
+            (None, Some(_)) => true,
+            (Some(candidate), Some(current)) => compare_prerelease(candidate, current).is_gt(),
+            _ => false,
+        },
+        ordering => ordering.is_gt(),
+    }
+}
+
+/// Semver precedence of two prerelease suffixes such as `rc.10` and `rc.9`
+///
+/// Dot-separated identifiers compare in order: numeric ones numerically and below alphanumeric
+/// ones, the others as text. When one suffix is a prefix of the other, the shorter one is lower.
+fn compare_prerelease(a: &str, b: &str) -> std::cmp::Ordering {
+    let mut a_parts = a.split('.');
+    let mut b_parts = b.split('.');
+    loop {
+        let ordering = match (a_parts.next(), b_parts.next()) {
+            (None, None) => return std::cmp::Ordering::Equal,
+            (None, Some(_)) => return std::cmp::Ordering::Less,
+            (Some(_), None) => return std::cmp::Ordering::Greater,
+            (Some(a), Some(b)) => match (a.parse::<u64>(), b.parse::<u64>()) {
+                (Ok(a), Ok(b)) => a.cmp(&b),
+                (Ok(_), Err(_)) => std::cmp::Ordering::Less,
+                (Err(_), Ok(_)) => std::cmp::Ordering::Greater,
+                (Err(_), Err(_)) => a.cmp(b),
+            },
+        };
+        if ordering.is_ne() {
+            return ordering;
+        }
+    }
+}
+
+/// The `dist` target this binary was built for, if releases are published for it as tar.gz
+pub fn current_target() -> Option<&'static str> {
+    if cfg!(all(
+        target_os = "linux",
+        target_arch = "x86_64",
+        target_env = "musl"
+    )) {
+        Some("x86_64-unknown-linux-musl")
+    } else if cfg!(all(target_os = "linux", target_arch = "x86_64")) {
+        Some("x86_64-unknown-linux-gnu")
+    } else if cfg!(all(
+        target_os = "linux",
+        target_arch = "aarch64",
+        target_env = "musl"
+    )) {
+        Some("aarch64-unknown-linux-musl")
+    } else if cfg!(all(target_os = "linux", target_arch = "aarch64")) {
+        Some("aarch64-unknown-linux-gnu")
+    } else if cfg!(all(target_os = "macos", target_arch = "x86_64")) {
+        Some("x86_64-apple-darwin")
+    } else if cfg!(all(target_os = "macos", target_arch = "aarch64")) {
+        Some("aarch64-apple-darwin")
+    } else {
+        None
+    }
+}
+
+async fn download(http: &reqwest::Client, url: &str) -> CliResult<Vec<u8>> {
+    let bytes = http
+        .get(url)
+        .send()
+        .await
+        .and_then(reqwest::Response::error_for_status)
+        .map_err(|e| RedisCtlError::Network {
+            message: format!("Failed to download {}: {}", url, e),
+            source: Some(Box::new(e)),
+        })?
+        .bytes()
+        .await
+        .with_context(|| format!("Failed to download {}", url))?;
+    Ok(bytes.to_vec())
+}
+
+/// The hex digest of a `.sha256` file (`<hex>  <file name>` or just `<hex>`)
+pub fn parse_checksum(contents: &str) -> Option<String> {
+    let digest = contents.split_whitespace().next()?.to_ascii_lowercase();
+    (digest.len() == 64 && digest.chars().all(|c| c.is_ascii_hexdigit())).then_some(digest)
+}
+
+pub fn verify_checksum(data: &[u8], expected: &str) -> CliResult<()> {
+    let actual: String = Sha256::digest(data)
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect();
+    if actual != expected {
+        return Err(RedisCtlError::Validation {
+            message: format!(
+                "Checksum mismatch for the downloaded archive (expected {}, got {}); the binary \
+                 was not replaced",
+                expected, actual
+            ),
+        });
+    }
+    Ok(())
+}
+
+/// The `redisctl` executable inside a release archive
+pub fn extract_binary(archive: &[u8]) -> CliResult<Vec<u8>> {
+    let mut entries = tar::Archive::new(flate2::read::GzDecoder::new(archive));
+    for entry in entries
+        .entries()
+        .context("Failed to read the release archive")?
+    {
+        let mut entry = entry.context("Failed to read the release archive")?;
+        let path = entry.path().context("Failed to read the release archive")?;
+        if path.file_name().is_some_and(|name| name == "redisctl") {
+            let mut binary = Vec::new();
+            entry
+                .read_to_end(&mut binary)
+                .context("Failed to extract redisctl from the release archive")?;
+            return Ok(binary);
+        }
+    }
+    Err(RedisCtlError::Validation {
+        message: "The release archive does not contain a redisctl binary".to_string(),
+    })
+}
+
+/// Write the new binary next to the current one and rename it over it
+///
+/// The rename is atomic, so an interrupted update leaves the old binary in place.
+fn replace_binary(exe: &Path, binary: &[u8]) -> CliResult<()> {
+    let staged: PathBuf = exe.with_file_name(".redisctl-update");
+    let permission_hint = |e: std::io::Error| RedisCtlError::FileError {
+        path: exe.display().to_string(),
+        message: format!(
+            "{} (run the update as a user that can write to {})",
+            e,
+            exe.parent().unwrap_or(exe).display()
+        ),
+    };
+    std::fs::write(&staged, binary).map_err(permission_hint)?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(&staged, std::fs::Permissions::from_mode(0o755))
+            .map_err(permission_hint)?;
+    }
+    std::fs::rename(&staged, exe).map_err(|e| {
+        let _ = std::fs::remove_file(&staged);
+        permission_hint(e)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn release(tag: &str, prerelease: bool) -> Release {
+        Release {
+            tag_name: tag.to_string(),
+            prerelease,
+            draft: false,
+            assets: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_latest_release_by_channel() {
+        let releases = vec![
+            release("v0.4.0-rc.1", true),
+            release("v0.3.1", false),
+            release("v0.3.0", false),
+        ];
+        assert_eq!(
+            latest_release(&releases, ReleaseChannel::Stable)
+                .unwrap()
+                .tag_name,
+            "v0.3.1"
+        );
+        assert_eq!(
+            latest_release(&releases, ReleaseChannel::Prerelease)
+                .unwrap()
+                .tag_name,
+            "v0.4.0-rc.1"
+        );
+    }
+
+    #[test]
+    fn test_version_comparison() {
+        assert_eq!(version_of("redisctl-v0.3.0"), "0.3.0");
+        assert_eq!(version_of("v0.3.0"), "0.3.0");
+        assert!(is_newer("0.3.0", "0.2.9"));
+        assert!(is_newer("0.10.0", "0.9.0"));
+        assert!(is_newer("0.3.0", "0.3.0-rc.1"));
+        assert!(is_newer("0.3.0-rc.2", "0.3.0-rc.1"));
+        assert!(is_newer("0.3.0-rc.10", "0.3.0-rc.9"));
+        assert!(!is_newer("0.3.0-rc.9", "0.3.0-rc.10"));
+        assert!(is_newer("0.3.0-rc.1", "0.3.0-beta.2"));
+        assert!(is_newer("0.3.0-rc.1.1", "0.3.0-rc.1"));
+        assert!(is_newer("0.3.0-rc", "0.3.0-1"));
+        assert!(!is_newer("0.3.0+build.5", "0.3.0"));
+        assert!(!is_newer("0.3.0-rc.1", "0.3.0"));
+        assert!(!is_newer("0.3.0", "0.3.0"));
+    }
+
+    #[test]
+    fn test_checksum_verification() {
+        let digest = "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824";
+        assert_eq!(
+            parse_checksum(&format!("{}  redisctl.tar.gz\n", digest.to_uppercase())).unwrap(),
+            digest
+        );
+        assert!(parse_checksum("not a checksum").is_none());
+        assert!(verify_checksum(b"hello", digest).is_ok());
+        assert!(verify_checksum(b"hello!", digest).is_err());
+    }
+
+    #[test]
+    fn test_extract_binary() {
+        let mut builder = tar::Builder::new(flate2::write::GzEncoder::new(
+            Vec::new(),
+            flate2::Compression::fast(),
+        ));
+        for (name, contents) in [
+            (
+                "redisctl-x86_64-unknown-linux-gnu/README.md",
+                &b"readme"[..],
+            ),
+            ("redisctl-x86_64-unknown-linux-gnu/redisctl", &b"binary"[..]),
+        ] {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(contents.len() as u64);
+            header.set_mode(0o755);
+            header.set_cksum();
+            builder.append_data(&mut header, name, contents).unwrap();
+        }
+        let archive = builder.into_inner().unwrap().finish().unwrap();
+        assert_eq!(extract_binary(&archive).unwrap(), b"binary");
+    }
+}
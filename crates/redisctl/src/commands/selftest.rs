@@ -0,0 +1,422 @@
+//! Self-test of a profile against its deployment
+//!
+//! Runs a fixed set of read-only GET requests (authentication, list endpoints, version
+//! detection) plus repeated requests for latency statistics, and prints a plain-text report
+//! that can be attached to a bug report as is. Nothing is created, changed or deleted.
+
+#![allow(dead_code)]
+
+use crate::cli::OutputFormat;
+use crate::config::DeploymentType;
+use crate::connection::ConnectionManager;
+use crate::error::{RedisCtlError, Result as CliResult};
+use serde::Serialize;
+use serde_json::Value;
+use std::time::Instant;
+
+/// Checks against Redis Cloud: (name, path); the first one establishes authentication
+const CLOUD_CHECKS: &[(&str, &str)] = &[
+    ("authentication", "/"),
+    ("subscriptions", "/subscriptions"),
+    ("essentials subscriptions", "/fixed/subscriptions"),
+    ("account users", "/users"),
+    ("acl users", "/acl/users"),
+    ("cloud accounts", "/cloud-accounts"),
+    ("regions", "/regions"),
+];
+
+/// Checks against Redis Enterprise: (name, path); the first one establishes authentication
+const ENTERPRISE_CHECKS: &[(&str, &str)] = &[
+    ("authentication", "/v1/cluster"),
+    ("nodes", "/v1/nodes"),
+    ("databases", "/v1/bdbs"),
+    ("users", "/v1/users"),
+    ("license", "/v1/license"),
+    ("cluster stats", "/v1/cluster/stats/last"),
+];
+
+/// Result of one check
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CheckResult {
+    pub name: String,
+    pub path: String,
+    pub passed: bool,
+    pub latency_ms: f64,
+    /// A short summary on success, the error on failure
+    pub detail: String,
+}
+
+/// Latency of repeated requests to the authentication endpoint
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LatencyStats {
+    pub path: String,
+    pub samples: usize,
+    pub failed: usize,
+    pub min_ms: f64,
+    pub avg_ms: f64,
+    pub p95_ms: f64,
+    pub max_ms: f64,
+}
+
+/// The full self-test report
+#[derive(Debug, Clone, Serialize)]
+pub struct SelftestReport {
+    pub redisctl_version: String,
+    pub platform: String,
+    pub started_at: String,
+    pub profile: Option<String>,
+    pub profile_source: Option<String>,
+    pub deployment: String,
+    pub endpoint: String,
+    /// Product version reported by the deployment, when it exposes one
+    pub server_version: Option<String>,
+    pub checks: Vec<CheckResult>,
+    pub latency: Option<LatencyStats>,
+    pub passed: usize,
+    pub failed: usize,
+}
+
+/// A client for either deployment, reduced to what the self-test needs
+enum Target {
+    Cloud(redis_cloud::CloudClient),
+    Enterprise(redis_enterprise::EnterpriseClient),
+}
+
+impl Target {
+    async fn get(&self, path: &str) -> Result<Value, String> {
+        match self {
+            Target::Cloud(client) => client.get_raw(path).await.map_err(|e| e.to_string()),
+            Target::Enterprise(client) => client.get_raw(path).await.map_err(|e| e.to_string()),
+        }
+    }
+
+    fn endpoint(&self) -> &str {
+        match self {
+            Target::Cloud(client) => client.base_url(),
+            Target::Enterprise(client) => client.base_url(),
+        }
+    }
+}
+
+pub async fn run_selftest(
+    conn_mgr: &ConnectionManager,
+    profile_name: Option<&str>,
+    deployment: Option<DeploymentType>,
+    samples: usize,
+    output_format: OutputFormat,
+    query: Option<&str>,
+) -> CliResult<()> {
+    let resolved_name = profile_name
+        .map(str::to_string)
+        .or_else(|| conn_mgr.config.default_profile.clone());
+    let deployment = match deployment {
+        Some(deployment) => deployment,
+        None => match conn_mgr.get_profile(profile_name) {
+            Ok(profile) => profile.deployment_type,
+            // Credentials may come from the environment alone
+            Err(_) if std::env::var("REDIS_CLOUD_API_KEY").is_ok() => DeploymentType::Cloud,
+            Err(_) if std::env::var("REDIS_ENTERPRISE_URL").is_ok() => DeploymentType::Enterprise,
+            Err(e) => return Err(e),
+        },
+    };
+
+    let (target, checks) = match deployment {
+        DeploymentType::Cloud => (
+            Target::Cloud(conn_mgr.create_cloud_client(profile_name).await?),
+            CLOUD_CHECKS,
+        ),
+        DeploymentType::Enterprise => (
+            Target::Enterprise(conn_mgr.create_enterprise_client(profile_name).await?),
+            ENTERPRISE_CHECKS,
+        ),
+    };
+
+    let started_at = chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true);
+    let mut results = Vec::new();
+    let mut server_version = None;
+    for (name, path) in checks {
+        let start = Instant::now();
+        let response = target.get(path).await;
+        let latency_ms = start.elapsed().as_secs_f64() * 1000.0;
+        let (passed, detail) = match &response {
+            Ok(body) => (true, summarize(name, body)),
+            Err(e) => (false, e.clone()),
+        };
+        if *name == "nodes"
+            && let Ok(nodes) = &response
+        {
+            server_version = enterprise_version(nodes);
+        }
+        results.push(CheckResult {
+            name: name.to_string(),
+            path: path.to_string(),
+            passed,
+            latency_ms,
+            detail,
+        });
+    }
+
+    let latency = if samples > 0 {
+        let path = checks[0].1;
+        let mut timings = Vec::new();
+        let mut failed = 0;
+        for _ in 0..samples {
+            let start = Instant::now();
+            match target.get(path).await {
+                Ok(_) => timings.push(start.elapsed().as_secs_f64() * 1000.0),
+                Err(_) => failed += 1,
+            }
+        }
+        latency_stats(path, &timings, failed)
+    } else {
+        None
+    };
+
+    let passed = results.iter().filter(|r| r.passed).count();
+    let report = SelftestReport {
+        redisctl_version: env!("CARGO_PKG_VERSION").to_string(),
+        platform: format!("{}/{}", std::env::consts::OS, std::env::consts::ARCH),
+        started_at,
+        profile_source: resolved_name
+            .as_deref()
+            .and_then(|name| conn_mgr.config.profile_source(name))
+            .map(str::to_string),
+        profile: resolved_name,
+        deployment: match deployment {
+            DeploymentType::Cloud => "cloud".to_string(),
+            DeploymentType::Enterprise => "enterprise".to_string(),
+        },
+        endpoint: target.endpoint().to_string(),
+        server_version,
+        failed: results.len() - passed,
+        passed,
+        checks: results,
+        latency,
+    };
+
+    match output_format {
+        OutputFormat::Auto | OutputFormat::Table if query.is_none() => {
+            print!("{}", format_report(&report));
+        }
+        _ => {
+            let json = serde_json::to_value(&report).map_err(|e| RedisCtlError::OutputError {
+                message: e.to_string(),
+            })?;
+            let data = super::cloud::utils::handle_output(json, output_format, query)?;
+            super::cloud::utils::print_formatted_output(data, output_format)?;
+        }
+    }
+
+    if report.failed > 0 {
+        return Err(RedisCtlError::api(format!(
+            "{} of {} self-test checks failed",
+            report.failed,
+            report.checks.len()
+        )));
+    }
+    Ok(())
+}
+
+/// One-line summary of a successful response
+fn summarize(name: &str, body: &Value) -> String {
+    if name == "authentication" {
+        let account = body.get("account").unwrap_or(body);
+        let label = account.get("name").and_then(Value::as_str);
+        let id = account
+            .get("id")
+            .or_else(|| account.get("uid"))
+            .map(|id| id.to_string());
+        return match (label, id) {
+            (Some(label), Some(id)) => format!("authenticated ({} {})", label, id),
+            (Some(label), None) => format!("authenticated ({})", label),
+            _ => "authenticated".to_string(),
+        };
+    }
+
+    let items = match body {
+        Value::Array(items) => Some(items.len()),
+        Value::Object(fields) => fields
+            .values()
+            .find_map(|value| value.as_array().map(Vec::len)),
+        _ => None,
+    };
+    match items {
+        Some(1) => "1 item".to_string(),
+        Some(count) => format!("{} items", count),
+        None => "ok".to_string(),
+    }
+}
+
+/// Redis Enterprise version from the nodes list (the first node reporting one)
+fn enterprise_version(nodes: &Value) -> Option<String> {
+    nodes.as_array()?.iter().find_map(|node| {
+        node.get("software_version")
+            .and_then(Value::as_str)
+            .map(str::to_string)
+    })
+}
+
+/// Min, average, nearest-rank p95 and max of the successful samples
+fn latency_stats(path: &str, timings: &[f64], failed: usize) -> Option<LatencyStats> {
+    if timings.is_empty() && failed == 0 {
+        return None;
+    }
+    let mut sorted = timings.to_vec();
+    sorted.sort_by(f64::total_cmp);
+    let (min_ms, avg_ms, p95_ms, max_ms) = if sorted.is_empty() {
+        (0.0, 0.0, 0.0, 0.0)
+    } else {
+        let rank = (0.95 * sorted.len() as f64).ceil() as usize;
+        (
+            sorted[0],
+            sorted.iter().sum::<f64>() / sorted.len() as f64,
+            sorted[rank.clamp(1, sorted.len()) - 1],
+            sorted[sorted.len() - 1],
+        )
+    };
+    Some(LatencyStats {
+        path: path.to_string(),
+        samples: timings.len() + failed,
+        failed,
+        min_ms,
+        avg_ms,
+        p95_ms,
+        max_ms,
+    })
+}
+
+/// Plain-text report, stable and free of colors so it pastes cleanly into an issue
+pub fn format_report(report: &SelftestReport) -> String {
+    let mut out = String::new();
+    out.push_str("redisctl selftest report\n");
+    out.push_str(&format!("  redisctl:   {}\n", report.redisctl_version));
+    out.push_str(&format!("  platform:   {}\n", report.platform));
+    out.push_str(&format!("  started:    {}\n", report.started_at));
+    out.push_str(&format!(
+        "  profile:    {}{}\n",
+        report.profile.as_deref().unwrap_or("(environment)"),
+        report
+            .profile_source
+            .as_deref()
+            .map(|source| format!(" [{}]", source))
+            .unwrap_or_default()
+    ));
+    out.push_str(&format!("  deployment: {}\n", report.deployment));
+    out.push_str(&format!("  endpoint:   {}\n", report.endpoint));
+    if let Some(version) = &report.server_version {
+        out.push_str(&format!("  version:    {}\n", version));
+    }
+
+    out.push_str("\nChecks\n");
+    let width = report
+        .checks
+        .iter()
+        .map(|check| check.name.len())
+        .max()
+        .unwrap_or(0);
+    for check in &report.checks {
+        out.push_str(&format!(
+            "  {}  {:<width$}  {:>8.1} ms  {}  {}\n",
+            if check.passed { "PASS" } else { "FAIL" },
+            check.name,
+            check.latency_ms,
+            check.path,
+            check.detail,
+            width = width
+        ));
+    }
+
+    if let Some(latency) = &report.latency {
+        out.push_str(&format!(
+            "\nLatency ({} x GET {}, {} failed)\n",
+            latency.samples, latency.path, latency.failed
+        ));
+        out.push_str(&format!(
+            "  min {:.1} ms  avg {:.1} ms  p95 {:.1} ms  max {:.1} ms\n",
+            latency.min_ms, latency.avg_ms, latency.p95_ms, latency.max_ms
+        ));
+    }
+
+    out.push_str(&format!(
+        "\nResult: {} of {} checks passed\n",
+        report.passed,
+        report.checks.len()
+    ));
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_summarize() {
+        assert_eq!(
+            summarize(
+                "authentication",
+                &json!({"account": {"id": 42, "name": "Acme"}})
+            ),
+            "authenticated (Acme 42)"
+        );
+        assert_eq!(
+            summarize(
+                "authentication",
+                &json!({"uid": 1, "name": "cluster.local"})
+            ),
+            "authenticated (cluster.local 1)"
+        );
+        assert_eq!(
+            summarize(
+                "subscriptions",
+                &json!({"accountId": 1, "subscriptions": [{}, {}]})
+            ),
+            "2 items"
+        );
+        assert_eq!(summarize("nodes", &json!([{}])), "1 item");
+        assert_eq!(summarize("license", &json!({"expired": false})), "ok");
+    }
+
+    #[test]
+    fn test_latency_stats() {
+        let stats = latency_stats("/v1/cluster", &[30.0, 10.0, 20.0], 1).unwrap();
+        assert_eq!(stats.samples, 4);
+        assert_eq!(stats.failed, 1);
+        assert_eq!(stats.min_ms, 10.0);
+        assert_eq!(stats.avg_ms, 20.0);
+        assert_eq!(stats.p95_ms, 30.0);
+        assert_eq!(stats.max_ms, 30.0);
+        assert!(latency_stats("/", &[], 0).is_none());
+    }
+
+    #[test]
+    fn test_enterprise_version_and_report() {
+        let nodes = json!([{"uid": 1}, {"uid": 2, "software_version": "7.4.2-54"}]);
+        let report = SelftestReport {
+            redisctl_version: "1.0.0".to_string(),
+            platform: "linux/x86_64".to_string(),
+            started_at: "2024-01-01T00:00:00Z".to_string(),
+            profile: Some("prod".to_string()),
+            profile_source: Some("config".to_string()),
+            deployment: "enterprise".to_string(),
+            endpoint: "https://cluster:9443".to_string(),
+            server_version: enterprise_version(&nodes),
+            checks: vec![CheckResult {
+                name: "nodes".to_string(),
+                path: "/v1/nodes".to_string(),
+                passed: false,
+                latency_ms: 12.34,
+                detail: "connection refused".to_string(),
+            }],
+            latency: None,
+            passed: 0,
+            failed: 1,
+        };
+
+        let text = format_report(&report);
+        assert!(text.contains("version:    7.4.2-54"));
+        assert!(text.contains("profile:    prod [config]"));
+        assert!(text.contains("FAIL  nodes      12.3 ms  /v1/nodes  connection refused"));
+        assert!(text.ends_with("Result: 0 of 1 checks passed\n"));
+    }
+}
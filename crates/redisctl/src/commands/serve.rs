@@ -0,0 +1,601 @@
+//! Local REST façade over the configured profiles
+//!
+//! `redisctl serve` listens on a local address and forwards requests such as
+//! `GET /profiles/prod/api/subscriptions` to the Cloud or Enterprise API of the named profile, so
+//! dashboards and scripts can use the CLI's profiles without handling credentials themselves.
+//! Every route except `/health` needs the bearer token, and only requests matching the allowlist
+//! are forwarded; by default that is any `GET`.
+
+#![allow(dead_code)]
+
+use crate::config::DeploymentType;
+use crate::connection::ConnectionManager;
+use crate::error::{RedisCtlError, Result as CliResult};
+use crate::output::notice;
+use axum::Json;
+use axum::Router;
+use axum::body::Bytes;
+use axum::extract::{Path, RawQuery, State};
+use axum::http::{HeaderMap, Method, StatusCode, header};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{any, get};
+use serde_json::{Value, json};
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Instant;
+use tokio::sync::Mutex;
+
+const DEFAULT_LISTEN: &str = "127.0.0.1:8080";
+const DEFAULT_ALLOW: &str = "GET /**";
+const GENERATED_TOKEN_LENGTH: usize = 32;
+
+/// Settings from the command line; each one replaces the `[serve]` setting of the config file
+#[derive(Debug, Default)]
+pub struct ServeOptions {
+    pub listen: Option<String>,
+    pub token: Option<String>,
+    pub allow: Vec<String>,
+    pub profiles: Vec<String>,
+}
+
+/// One allowlist entry: a method (or `*` for any) and a path pattern
+///
+/// In the pattern, `*` matches one path segment (or, within a segment, any characters) and a
+/// trailing `**` matches any remainder, including none.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AllowRule {
+    method: String,
+    pattern: Vec<String>,
+}
+
+impl AllowRule {
+    /// Parse `METHOD /path/pattern`, e.g. `GET /v1/bdbs/**`
+    pub fn parse(rule: &str) -> CliResult<Self> {
+        let invalid = || RedisCtlError::Validation {
+            message: format!(
+                "Invalid allow rule '{}': expected `METHOD /path/pattern`, e.g. `GET /v1/bdbs/**`",
+                rule
+            ),
+        };
+        let (method, pattern) = rule
+            .trim()
+            .split_once(char::is_whitespace)
+            .ok_or_else(invalid)?;
+        let pattern = pattern.trim();
+        if !pattern.starts_with('/') {
+            return Err(invalid());
+        }
+        Ok(Self {
+            method: method.to_ascii_uppercase(),
+            pattern: segments(pattern),
+        })
+    }
+
+    /// Whether the rule allows `path`; a path that [`unsafe_path`] flags is never allowed
+    pub fn allows(&self, method: &str, path: &str) -> bool {
+        (self.method == "*" || self.method == method)
+            && unsafe_path(path).is_none()
+            && matches_segments(&self.pattern, &segments(path))
+    }
+}
+
+/// Why a decoded request path cannot be forwarded, if it cannot
+///
+/// The path arrives percent-decoded and is forwarded as is, so a decoded `?` or `#` would start
+/// a query or fragment upstream that the allowlist never saw, and a `%` would be decoded again.
+fn unsafe_path(path: &str) -> Option<&'static str> {
+    if has_dot_segment(path) {
+        Some("'.' or '..' segments")
+    } else if path.contains(['?', '#', '%']) {
+        Some("an encoded '?', '#' or '%'")
+    } else if path.chars().any(char::is_control) {
+        Some("control characters")
+    } else {
+        None
+    }
+}
+
+/// Whether `path` has a `.` or `..` segment, which the client's URL parser would resolve
+///
+/// The parser also treats `%2e` as a dot and `\` as a separator, so `/v1/bdbs/%2e%2e\users`
+/// would reach `/v1/users` after matching a `/v1/bdbs/**` rule.
+fn has_dot_segment(path: &str) -> bool {
+    path.split(['/', '\\']).any(|segment| {
+        let segment = segment.to_ascii_lowercase().replace("%2e", ".");
+        segment == "." || segment == ".."
+    })
+}
+
+fn segments(path: &str) -> Vec<String> {
+    path.split('/')
+        .filter(|segment| !segment.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+fn matches_segments(pattern: &[String], path: &[String]) -> bool {
+    match (pattern.split_first(), path.split_first()) {
+        (None, None) => true,
+        (Some((first, _)), _) if first == "**" => true,
+        (Some((first, rest)), Some((segment, path_rest))) => {
+            matches_segment(first, segment) && matches_segments(rest, path_rest)
+        }
+        _ => false,
+    }
+}
+
+fn matches_segment(pattern: &str, segment: &str) -> bool {
+    match pattern.split_once('*') {
+        None => pattern == segment,
+        Some((prefix, suffix)) => {
+            segment.len() >= prefix.len() + suffix.len()
+                && segment.starts_with(prefix)
+                && segment.ends_with(suffix)
+        }
+    }
+}
+
+/// A client for the deployment a profile points at
+#[derive(Clone)]
+enum Client {
+    Cloud(redis_cloud::CloudClient),
+    Enterprise(redis_enterprise::EnterpriseClient),
+}
+
+impl Client {
+    async fn call(
+        &self,
+        method: &Method,
+        path: &str,
+        body: Value,
+    ) -> Result<Value, (StatusCode, String)> {
+        match self {
+            Client::Cloud(client) => {
+                let result = match method.as_str() {
+                    "GET" => client.get_raw(path).await,
+                    "POST" => client.post_raw(path, body).await,
+                    "PUT" => client.put_raw(path, body).await,
+                    "PATCH" => client.patch_raw(path, body).await,
+                    "DELETE" => client.delete_raw(path).await,
+                    _ => return Err(unsupported_method(method)),
+                };
+                result.map_err(|e| (cloud_status(&e), e.to_string()))
+            }
+            Client::Enterprise(client) => {
+                let result = match method.as_str() {
+                    "GET" => client.get_raw(path).await,
+                    "POST" => client.post_raw(path, body).await,
+                    "PUT" => client.put_raw(path, body).await,
+                    "PATCH" => client.patch_raw(path, body).await,
+                    "DELETE" => client.delete_raw(path).await,
+                    _ => return Err(unsupported_method(method)),
+                };
+                result.map_err(|e| (enterprise_status(&e), e.to_string()))
+            }
+        }
+    }
+}
+
+fn unsupported_method(method: &Method) -> (StatusCode, String) {
+    (
+        StatusCode::METHOD_NOT_ALLOWED,
+        format!("{} is not supported", method),
+    )
+}
+
+/// Status for a failed Cloud request: client errors pass through, everything else is a 502
+///
+/// A 401 from the API means the profile's credentials were rejected, not the caller's token, so
+/// it is reported as a gateway error too.
+fn cloud_status(error: &redis_cloud::CloudError) -> StatusCode {
+    use redis_cloud::CloudError;
+    match error {
+        CloudError::BadRequest { .. } => StatusCode::BAD_REQUEST,
+        CloudError::Forbidden { .. } => StatusCode::FORBIDDEN,
+        CloudError::NotFound { .. } => StatusCode::NOT_FOUND,
+        CloudError::PreconditionFailed => StatusCode::PRECONDITION_FAILED,
+        CloudError::ApiError { code, .. } => client_error_status(*code),
+        _ => StatusCode::BAD_GATEWAY,
+    }
+}
+
+/// Status for a failed Enterprise request, following the same rules as [`cloud_status`]
+fn enterprise_status(error: &redis_enterprise::RestError) -> StatusCode {
+    use redis_enterprise::RestError;
+    if error.is_not_found() {
+        return StatusCode::NOT_FOUND;
+    }
+    match error {
+        RestError::ValidationError(_) => StatusCode::BAD_REQUEST,
+        RestError::ApiError { code, .. } => client_error_status(*code),
+        _ => StatusCode::BAD_GATEWAY,
+    }
+}
+
+fn client_error_status(code: u16) -> StatusCode {
+    match StatusCode::from_u16(code) {
+        Ok(status) if status.is_client_error() && status != StatusCode::UNAUTHORIZED => status,
+        _ => StatusCode::BAD_GATEWAY,
+    }
+}
+
+struct ServeState {
+    conn_mgr: ConnectionManager,
+    token: String,
+    allow: Vec<AllowRule>,
+    /// Profiles that may be used; all when empty
+    profiles: Vec<String>,
+    clients: Mutex<HashMap<String, Client>>,
+}
+
+impl ServeState {
+    /// Whether the request carries the bearer token
+    fn authorized(&self, headers: &HeaderMap) -> bool {
+        headers
+            .get(header::AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .is_some_and(|token| constant_time_eq(token.as_bytes(), self.token.as_bytes()))
+    }
+
+    fn exposes(&self, profile: &str) -> bool {
+        (self.profiles.is_empty() || self.profiles.iter().any(|p| p == profile))
+            && self.conn_mgr.config.find_profile(profile).is_some()
+    }
+
+    /// The client for a profile, created on first use and reused afterwards
+    async fn client(&self, profile: &str) -> CliResult<Client> {
+        let mut clients = self.clients.lock().await;
+        if let Some(client) = clients.get(profile) {
+            return Ok(client.clone());
+        }
+        let client = match self.conn_mgr.get_profile(Some(profile))?.deployment_type {
+            DeploymentType::Cloud => {
+                Client::Cloud(self.conn_mgr.create_cloud_client(Some(profile)).await?)
+            }
+            DeploymentType::Enterprise => Client::Enterprise(
+                self.conn_mgr
+                    .create_enterprise_client(Some(profile))
+                    .await?,
+            ),
+        };
+        clients.insert(profile.to_string(), client.clone());
+        Ok(client)
+    }
+}
+
+/// Compare without stopping at the first difference, so response times do not leak the token
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+fn unauthorized() -> Response {
+    error_response(
+        StatusCode::UNAUTHORIZED,
+        "Missing or invalid bearer token".to_string(),
+    )
+}
+
+fn error_response(status: StatusCode, message: String) -> Response {
+    (status, Json(json!({ "error": message }))).into_response()
+}
+
+pub async fn run_serve(conn_mgr: &ConnectionManager, options: ServeOptions) -> CliResult<()> {
+    let configured = conn_mgr.config.serve.clone().unwrap_or_default();
+
+    let listen = options
+        .listen
+        .or(configured.listen)
+        .unwrap_or_else(|| DEFAULT_LISTEN.to_string());
+    let address: SocketAddr = listen.parse().map_err(|_| RedisCtlError::Validation {
+        message: format!(
+            "Invalid listen address '{}': expected IP:PORT, e.g. {}",
+            listen, DEFAULT_LISTEN
+        ),
+    })?;
+
+    let rules = match (options.allow.is_empty(), configured.allow.is_empty()) {
+        (false, _) => options.allow,
+        (true, false) => configured.allow,
+        (true, true) => vec![DEFAULT_ALLOW.to_string()],
+    };
+    let allow = rules
+        .iter()
+        .map(|rule| AllowRule::parse(rule))
+        .collect::<CliResult<Vec<_>>>()?;
+
+    let profiles = if options.profiles.is_empty() {
+        configured.profiles
+    } else {
+        options.profiles
+    };
+    if let Some(missing) = profiles
+        .iter()
+        .find(|name| conn_mgr.config.find_profile(name).is_none())
+    {
+        return Err(RedisCtlError::ProfileNotFound {
+            name: missing.clone(),
+        });
+    }
+
+    let (token, generated) = match options.token.or(configured.token) {
+        Some(token) if !token.is_empty() => (token, false),
+        _ => (
+            crate::password::generate_password(GENERATED_TOKEN_LENGTH),
+            true,
+        ),
+    };
+
+    let state = Arc::new(ServeState {
+        conn_mgr: ConnectionManager::new(conn_mgr.config.clone())
+            .with_cloud_api_url(conn_mgr.cloud_api_url.clone())
+            .with_unlock(conn_mgr.unlock),
+        token,
+        allow,
+        profiles,
+        clients: Mutex::new(HashMap::new()),
+    });
+
+    let listener = tokio::net::TcpListener::bind(address)
+        .await
+        .map_err(|e| RedisCtlError::network(format!("Cannot listen on {}: {}", address, e)))?;
+
+    notice!("Listening on http://{}", address);
+    if generated {
+        eprintln!("Token: {}", state.token);
+    }
+    for rule in &rules {
+        notice!("Allowed: {}", rule);
+    }
+    if !address.ip().is_loopback() {
+        notice!(
+            "Warning: {} is reachable from other machines and the API is served over plain HTTP",
+            address
+        );
+    }
+
+    axum::serve(listener, router(state))
+        .with_graceful_shutdown(async {
+            let _ = tokio::signal::ctrl_c().await;
+        })
+        .await
+        .map_err(|e| RedisCtlError::network(format!("Server error: {}", e)))?;
+    Ok(())
+}
+
+fn router(state: Arc<ServeState>) -> Router {
+    Router::new()
+        .route("/health", get(health))
+        .route("/profiles", get(list_profiles))
+        .route("/profiles/{profile}/api/{*path}", any(forward))
+        .with_state(state)
+}
+
+async fn health() -> Json<Value> {
+    Json(json!({ "status": "ok" }))
+}
+
+async fn list_profiles(State(state): State<Arc<ServeState>>, headers: HeaderMap) -> Response {
+    if !state.authorized(&headers) {
+        return unauthorized();
+    }
+    let profiles: Vec<Value> = state
+        .conn_mgr
+        .config
+        .list_profiles()
+        .into_iter()
+        .filter(|(name, _)| state.exposes(name))
+        .map(|(name, profile)| {
+            json!({ "name": name, "deployment": profile.deployment_type.to_string() })
+        })
+        .collect();
+    Json(Value::Array(profiles)).into_response()
+}
+
+async fn forward(
+    State(state): State<Arc<ServeState>>,
+    method: Method,
+    Path((profile, path)): Path<(String, String)>,
+    RawQuery(query): RawQuery,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Response {
+    if !state.authorized(&headers) {
+        return unauthorized();
+    }
+    if !state.exposes(&profile) {
+        return error_response(
+            StatusCode::NOT_FOUND,
+            format!("Profile '{}' is not available", profile),
+        );
+    }
+    let path = format!("/{}", path);
+    if let Some(reason) = unsafe_path(&path) {
+        return error_response(
+            StatusCode::BAD_REQUEST,
+            format!("{:?} has {}", path, reason),
+        );
+    }
+    if !state
+        .allow
+        .iter()
+        .any(|rule| rule.allows(method.as_str(), &path))
+    {
+        return error_response(
+            StatusCode::FORBIDDEN,
+            format!("{} {} is not in the allowlist", method, path),
+        );
+    }
+    let body = if body.is_empty() {
+        json!({})
+    } else {
+        match serde_json::from_slice(&body) {
+            Ok(body) => body,
+            Err(e) => {
+                return error_response(
+                    StatusCode::BAD_REQUEST,
+                    format!("Request body is not valid JSON: {}", e),
+                );
+            }
+        }
+    };
+    let target = match query {
+        Some(query) => format!("{}?{}", path, query),
+        None => path,
+    };
+
+    let client = match state.client(&profile).await {
+        Ok(client) => client,
+        Err(e) => return error_response(StatusCode::INTERNAL_SERVER_ERROR, e.to_string()),
+    };
+    let start = Instant::now();
+    let (status, response) = match client.call(&method, &target, body).await {
+        Ok(value) => (StatusCode::OK, Json(value).into_response()),
+        Err((status, message)) => (status, error_response(status, message)),
+    };
+    notice!(
+        "{} {} [{}] -> {} ({} ms)",
+        method,
+        target,
+        profile,
+        status.as_u16(),
+        start.elapsed().as_millis()
+    );
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_allow_rules() {
+        let rule = AllowRule::parse("get /v1/bdbs/**").unwrap();
+        assert!(rule.allows("GET", "/v1/bdbs"));
+        assert!(rule.allows("GET", "/v1/bdbs/1/stats"));
+        assert!(!rule.allows("POST", "/v1/bdbs"));
+        assert!(!rule.allows("GET", "/v1/nodes"));
+
+        let rule = AllowRule::parse("POST /v1/bdbs/*/actions/backup*").unwrap();
+        assert!(rule.allows("POST", "/v1/bdbs/3/actions/backup_now"));
+        assert!(!rule.allows("POST", "/v1/bdbs/3/actions/restore"));
+        assert!(!rule.allows("POST", "/v1/bdbs/actions/backup"));
+
+        assert!(
+            AllowRule::parse("* /**")
+                .unwrap()
+                .allows("DELETE", "/anything")
+        );
+        assert!(AllowRule::parse("GET").is_err());
+        assert!(AllowRule::parse("GET v1/bdbs").is_err());
+    }
+
+    #[test]
+    fn test_dot_segments_never_allowed() {
+        let rule = AllowRule::parse("GET /v1/bdbs/**").unwrap();
+        for path in [
+            "/v1/bdbs/../users",
+            "/v1/bdbs/./../users",
+            "/v1/bdbs/%2e%2e/users",
+            "/v1/bdbs/.%2E/users",
+            "/v1/bdbs/..\\users",
+            "/v1/bdbs/1/..",
+        ] {
+            assert!(has_dot_segment(path), "{}", path);
+            assert!(!rule.allows("GET", path), "{}", path);
+        }
+        assert!(!has_dot_segment("/v1/bdbs/1/stats"));
+        assert!(!has_dot_segment("/v1/bdbs/my..name"));
+        assert!(rule.allows("GET", "/v1/bdbs/my..name"));
+    }
+
+    #[test]
+    fn test_encoded_query_and_fragment_never_allowed() {
+        let rule = AllowRule::parse("GET /v1/bdbs/**").unwrap();
+        for path in [
+            "/v1/bdbs/1?fields=all",
+            "/v1/bdbs/1#x",
+            "/v1/bdbs/1%3F",
+            "/v1/bdbs/1\n",
+        ] {
+            assert!(!rule.allows("GET", path), "{:?}", path);
+        }
+        assert!(!rule.allows("GET", "/v1/bdbs/1\r\nHost: x"));
+    }
+
+    #[tokio::test]
+    async fn test_forward_rejects_encoded_question_mark() {
+        let mut config = crate::config::Config::default();
+        config.set_profile(
+            "lab".to_string(),
+            crate::config::Profile {
+                deployment_type: DeploymentType::Enterprise,
+                credentials: crate::config::ProfileCredentials::Enterprise {
+                    url: "https://127.0.0.1:1".to_string(),
+                    username: "admin@lab".to_string(),
+                    password: None,
+                    insecure: true,
+                    tls_fingerprint_sha256: None,
+                },
+                read_only: true,
+            },
+        );
+        let state = Arc::new(ServeState {
+            conn_mgr: ConnectionManager::new(config),
+            token: "token".to_string(),
+            allow: vec![AllowRule::parse("GET /v1/bdbs/**").unwrap()],
+            profiles: Vec::new(),
+            clients: Mutex::new(HashMap::new()),
+        });
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, router(state)).await });
+
+        let response = reqwest::Client::new()
+            .get(format!(
+                "http://{}/profiles/lab/api/v1/bdbs/1%3Ffields=secret",
+                address
+            ))
+            .bearer_auth("token")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), reqwest::StatusCode::BAD_REQUEST);
+        let body: Value = response.json().await.unwrap();
+        assert!(body["error"].as_str().unwrap().contains("'?'"), "{}", body);
+    }
+
+    #[test]
+    fn test_error_status() {
+        assert_eq!(
+            cloud_status(&redis_cloud::CloudError::NotFound {
+                message: String::new()
+            }),
+            StatusCode::NOT_FOUND
+        );
+        assert_eq!(
+            cloud_status(&redis_cloud::CloudError::AuthenticationFailed {
+                message: String::new()
+            }),
+            StatusCode::BAD_GATEWAY
+        );
+        assert_eq!(
+            enterprise_status(&redis_enterprise::RestError::ApiError {
+                code: 409,
+                message: String::new()
+            }),
+            StatusCode::CONFLICT
+        );
+        assert_eq!(
+            enterprise_status(&redis_enterprise::RestError::ApiError {
+                code: 500,
+                message: String::new()
+            }),
+            StatusCode::BAD_GATEWAY
+        );
+        assert!(constant_time_eq(b"secret", b"secret"));
+        assert!(!constant_time_eq(b"secret", b"secreT"));
+        assert!(!constant_time_eq(b"secret", b"secret2"));
+    }
+}
@@ -0,0 +1,182 @@
+//! `redisctl stats`: the local usage statistics recorded when telemetry is enabled
+//!
+//! Commands are listed by number of runs, most used first, so the automation paths worth
+//! optimizing stand out. `--reset` empties the stats file.
+
+#![allow(dead_code)]
+
+use crate::cli::OutputFormat;
+use crate::config::Config;
+use crate::error::{RedisCtlError, Result as CliResult};
+use crate::output::notice;
+use crate::telemetry::{self, CommandUsage, UsageStats};
+use serde::Serialize;
+use tabled::{Table, Tabled, settings::Style};
+
+use super::cloud::utils::{handle_output, print_formatted_output};
+
+/// One command's usage, for display
+#[derive(Debug, Clone, Serialize)]
+pub struct CommandStats {
+    pub command: String,
+    #[serde(flatten)]
+    pub usage: CommandUsage,
+    pub mean_ms: u64,
+}
+
+#[derive(Tabled)]
+struct StatsRow {
+    #[tabled(rename = "COMMAND")]
+    command: String,
+    #[tabled(rename = "RUNS")]
+    runs: u64,
+    #[tabled(rename = "FAILED")]
+    failures: u64,
+    #[tabled(rename = "MEAN")]
+    mean: String,
+    #[tabled(rename = "MAX")]
+    max: String,
+    #[tabled(rename = "TOTAL")]
+    total: String,
+    #[tabled(rename = "LAST RUN")]
+    last_run: String,
+}
+
+pub fn run_stats(
+    config: &Config,
+    top: Option<usize>,
+    reset: bool,
+    output_format: OutputFormat,
+    query: Option<&str>,
+) -> CliResult<()> {
+    let path = telemetry::stats_path(config)?;
+    if reset {
+        telemetry::save(&path, &UsageStats::default()).map_err(|e| RedisCtlError::FileError {
+            path: path.display().to_string(),
+            message: format!("{:#}", e),
+        })?;
+        notice!("Usage stats reset");
+        return Ok(());
+    }
+
+    let stats = telemetry::load(&path).map_err(|e| RedisCtlError::FileError {
+        path: path.display().to_string(),
+        message: format!("{:#}", e),
+    })?;
+    let rows = ranked(&stats, top);
+
+    match output_format {
+        OutputFormat::Auto | OutputFormat::Table if query.is_none() => {
+            if !telemetry::enabled(config) {
+                notice!(
+                    "Usage stats are off; set `enabled = true` under [telemetry] in the config \
+                     file, or {}=1, to record them",
+                    telemetry::TELEMETRY_ENV
+                );
+            }
+            if rows.is_empty() {
+                notice!("No usage recorded");
+                return Ok(());
+            }
+            if let Some(since) = stats.since {
+                println!("Since {}\n", crate::timestamps::format_timestamp(&since));
+            }
+            let table_rows: Vec<StatsRow> = rows.iter().map(row).collect();
+            let mut table = Table::new(table_rows);
+            table.with(Style::blank());
+            println!("{}", table);
+            Ok(())
+        }
+        _ => {
+            let json = serde_json::json!({
+                "since": stats.since,
+                "commands": rows,
+            });
+            let data = handle_output(json, output_format, query)?;
+            print_formatted_output(data, output_format)
+        }
+    }
+}
+
+/// Commands by number of runs, then total time, at most `top` of them
+pub fn ranked(stats: &UsageStats, top: Option<usize>) -> Vec<CommandStats> {
+    let mut rows: Vec<CommandStats> = stats
+        .commands
+        .iter()
+        .map(|(command, usage)| CommandStats {
+            command: command.clone(),
+            usage: usage.clone(),
+            mean_ms: usage.mean_ms(),
+        })
+        .collect();
+    rows.sort_by(|a, b| {
+        b.usage
+            .runs
+            .cmp(&a.usage.runs)
+            .then_with(|| b.usage.total_ms.cmp(&a.usage.total_ms))
+            .then_with(|| a.command.cmp(&b.command))
+    });
+    if let Some(top) = top {
+        rows.truncate(top);
+    }
+    rows
+}
+
+fn row(stats: &CommandStats) -> StatsRow {
+    StatsRow {
+        command: stats.command.clone(),
+        runs: stats.usage.runs,
+        failures: stats.usage.failures,
+        mean: format_ms(stats.mean_ms),
+        max: format_ms(stats.usage.max_ms),
+        total: format_ms(stats.usage.total_ms),
+        last_run: stats.usage.last_run.map_or_else(
+            || "-".to_string(),
+            |at| crate::timestamps::format_timestamp(&at),
+        ),
+    }
+}
+
+/// Milliseconds as `850ms`, `12.3s` or `4m 05s`
+fn format_ms(ms: u64) -> String {
+    match ms {
+        0..1_000 => format!("{}ms", ms),
+        1_000..60_000 => format!("{:.1}s", ms as f64 / 1000.0),
+        _ => format!("{}m {:02}s", ms / 60_000, (ms % 60_000) / 1000),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use std::time::Duration;
+
+    #[test]
+    fn test_ranked_orders_by_runs() {
+        let now = Utc::now();
+        let mut stats = UsageStats::default();
+        stats.record("version", Duration::from_millis(1), true, now);
+        for _ in 0..3 {
+            stats.record("cloud database list", Duration::from_millis(400), true, now);
+        }
+        stats.record(
+            "enterprise cluster get",
+            Duration::from_millis(90),
+            true,
+            now,
+        );
+
+        let rows = ranked(&stats, None);
+        assert_eq!(rows[0].command, "cloud database list");
+        assert_eq!(rows[1].command, "enterprise cluster get");
+        assert_eq!(ranked(&stats, Some(1)).len(), 1);
+    }
+
+    #[test]
+    fn test_format_ms() {
+        assert_eq!(format_ms(850), "850ms");
+        assert_eq!(format_ms(12_345), "12.3s");
+        assert_eq!(format_ms(245_000), "4m 05s");
+    }
+}
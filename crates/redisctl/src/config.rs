@@ -15,6 +15,8 @@ use std::fs;
 use std::path::PathBuf;
 use tracing::{debug, info, trace, warn};
 
+use crate::profile_provider::{self, ProvidedProfile};
+
 /// Main configuration structure
 #[derive(Debug, Serialize, Deserialize, Default, Clone)]
 pub struct Config {
@@ -24,8 +26,54 @@ pub struct Config {
     /// Map of profile name -> profile configuration
     #[serde(default)]
     pub profiles: HashMap<String, Profile>,
+    /// Named Cloud API environments (name -> base URL), selectable with `--environment`
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub environments: HashMap<String, String>,
+    /// Directories of per-profile TOML files (`<name>.toml`) to load profiles from
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub profile_dirs: Vec<PathBuf>,
+    /// Settings for `redisctl serve`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub serve: Option<ServeConfig>,
+    /// Local usage statistics for `redisctl stats`; off unless enabled
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub telemetry: Option<TelemetryConfig>,
+    /// Profiles from profile directories and environment bundles, never written back
+    #[serde(skip)]
+    pub provided_profiles: HashMap<String, ProvidedProfile>,
+}
+
+/// Settings for the local REST façade started by `redisctl serve`
+#[derive(Debug, Serialize, Deserialize, Default, Clone)]
+pub struct ServeConfig {
+    /// Address to listen on, e.g. `127.0.0.1:8080`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub listen: Option<String>,
+    /// Bearer token clients must send; a random one is generated at startup when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token: Option<String>,
+    /// Allowed requests as `METHOD /path/pattern`; only `GET` requests when empty
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allow: Vec<String>,
+    /// Profiles that may be used through the façade; all profiles when empty
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub profiles: Vec<String>,
 }
 
+/// Settings for the local usage statistics shown by `redisctl stats`
+#[derive(Debug, Serialize, Deserialize, Default, Clone)]
+pub struct TelemetryConfig {
+    /// Record each command's name, duration and outcome
+    #[serde(default)]
+    pub enabled: bool,
+    /// Stats file; `stats.json` next to the config file when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<PathBuf>,
+}
+
+/// Built-in Cloud API environments; entries in the config file take precedence
+pub const CLOUD_ENVIRONMENTS: &[(&str, &str)] = &[("production", "https://api.redislabs.com/v1")];
+
 /// Individual profile configuration
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Profile {
@@ -34,6 +82,9 @@ pub struct Profile {
     /// Connection credentials (flattened into the profile)
     #[serde(flatten)]
     pub credentials: ProfileCredentials,
+    /// Refuse POST, PUT, PATCH and DELETE requests unless `--unlock` is given
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub read_only: bool,
 }
 
 /// Supported deployment types
@@ -60,6 +111,9 @@ pub enum ProfileCredentials {
         password: Option<String>, // Optional for interactive prompting
         #[serde(default)]
         insecure: bool,
+        /// SHA-256 fingerprint of the only server certificate to trust
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        tls_fingerprint_sha256: Option<String>,
     },
 }
 
@@ -86,6 +140,8 @@ impl Profile {
     }
 
     /// Returns Enterprise credentials if this is an Enterprise profile
+    ///
+    /// An empty password, e.g. an unset `${VAR:-}` reference, counts as no password.
     pub fn enterprise_credentials(&self) -> Option<(&str, &str, Option<&str>, bool)> {
         match &self.credentials {
             ProfileCredentials::Enterprise {
@@ -93,16 +149,28 @@ impl Profile {
                 username,
                 password,
                 insecure,
+                ..
             } => Some((
                 url.as_str(),
                 username.as_str(),
-                password.as_deref(),
+                password.as_deref().filter(|p| !p.is_empty()),
                 *insecure,
             )),
             _ => None,
         }
     }
 
+    /// The pinned server certificate fingerprint of an Enterprise profile
+    pub fn tls_fingerprint_sha256(&self) -> Option<&str> {
+        match &self.credentials {
+            ProfileCredentials::Enterprise {
+                tls_fingerprint_sha256,
+                ..
+            } => tls_fingerprint_sha256.as_deref(),
+            _ => None,
+        }
+    }
+
     /// Check if this profile has a stored password
     pub fn has_password(&self) -> bool {
         matches!(
@@ -113,6 +181,51 @@ impl Profile {
             }
         )
     }
+
+    /// Change one credential field by its config-file key
+    pub fn set_field(&mut self, key: &str, value: &str) -> Result<()> {
+        if key == "read_only" {
+            self.read_only = value
+                .parse()
+                .with_context(|| format!("Invalid value for read_only: '{}'", value))?;
+            return Ok(());
+        }
+        match (&mut self.credentials, key) {
+            (ProfileCredentials::Cloud { api_key, .. }, "api_key") => *api_key = value.to_string(),
+            (ProfileCredentials::Cloud { api_secret, .. }, "api_secret") => {
+                *api_secret = value.to_string()
+            }
+            (ProfileCredentials::Cloud { api_url, .. }, "api_url") => *api_url = value.to_string(),
+            (ProfileCredentials::Enterprise { url, .. }, "url") => *url = value.to_string(),
+            (ProfileCredentials::Enterprise { username, .. }, "username") => {
+                *username = value.to_string()
+            }
+            (ProfileCredentials::Enterprise { password, .. }, "password") => {
+                *password = (!value.is_empty()).then(|| value.to_string())
+            }
+            (ProfileCredentials::Enterprise { insecure, .. }, "insecure") => {
+                *insecure = value
+                    .parse()
+                    .with_context(|| format!("Invalid value for insecure: '{}'", value))?
+            }
+            (
+                ProfileCredentials::Enterprise {
+                    tls_fingerprint_sha256,
+                    ..
+                },
+                "tls_fingerprint_sha256",
+            ) => *tls_fingerprint_sha256 = (!value.is_empty()).then(|| value.to_string()),
+            (ProfileCredentials::Cloud { .. }, _) => anyhow::bail!(
+                "Unknown setting '{}' for a cloud profile (valid: api_key, api_secret, api_url, read_only)",
+                key
+            ),
+            (ProfileCredentials::Enterprise { .. }, _) => anyhow::bail!(
+                "Unknown setting '{}' for an enterprise profile (valid: url, username, password, insecure, tls_fingerprint_sha256, read_only)",
+                key
+            ),
+        }
+        Ok(())
+    }
 }
 
 impl Config {
@@ -124,7 +237,9 @@ impl Config {
 
         if !config_path.exists() {
             info!("No configuration file found, using defaults");
-            return Ok(Config::default());
+            let mut config = Config::default();
+            config.load_provided_profiles();
+            return Ok(config);
         }
 
         debug!("Reading configuration from {:?}", config_path);
@@ -147,7 +262,7 @@ impl Config {
         }
 
         debug!("Parsing TOML configuration");
-        let config: Config = toml::from_str(&expanded_content)
+        let mut config: Config = toml::from_str(&expanded_content)
             .with_context(|| format!("Failed to parse config from {:?}", config_path))?;
 
         info!(
@@ -160,9 +275,64 @@ impl Config {
             debug!("Profile '{}': type={:?}", name, profile.deployment_type);
         }
 
+        config.load_provided_profiles();
+        Ok(config)
+    }
+
+    /// Load the config file without expanding `${VAR}` references, for changes that are saved
+    /// back, so references stay references rather than becoming the values they expand to
+    pub fn load_for_update() -> Result<Self> {
+        let config_path = Self::config_path()?;
+        let mut config: Config = if config_path.exists() {
+            let content = fs::read_to_string(&config_path)
+                .with_context(|| format!("Failed to read config from {:?}", config_path))?;
+            toml::from_str(&content)
+                .with_context(|| format!("Failed to parse config from {:?}", config_path))?
+        } else {
+            Config::default()
+        };
+        config.load_provided_profiles();
         Ok(config)
     }
 
+    /// Load profiles from profile directories and environment bundles
+    ///
+    /// Profiles defined in the config file take precedence over provided ones.
+    fn load_provided_profiles(&mut self) {
+        let providers = profile_provider::configured_providers(self);
+        self.provided_profiles = profile_provider::load_profiles(&providers);
+        for name in self.profiles.keys() {
+            if self.provided_profiles.remove(name).is_some() {
+                debug!(
+                    "Profile '{}' in the config file shadows a provided profile",
+                    name
+                );
+            }
+        }
+        if !self.provided_profiles.is_empty() {
+            info!(
+                "Loaded {} profile(s) from profile providers",
+                self.provided_profiles.len()
+            );
+        }
+    }
+
+    /// Look up a profile by exact name in the config file, then in provided profiles
+    pub fn find_profile(&self, name: &str) -> Option<&Profile> {
+        self.profiles
+            .get(name)
+            .or_else(|| self.provided_profiles.get(name).map(|p| &p.profile))
+    }
+
+    /// Where a profile comes from: `config` for the config file, otherwise the provider source
+    pub fn profile_source(&self, name: &str) -> Option<&str> {
+        if self.profiles.contains_key(name) {
+            Some("config")
+        } else {
+            self.provided_profiles.get(name).map(|p| p.source.as_str())
+        }
+    }
+
     /// Save configuration to the standard location
     pub fn save(&self) -> Result<()> {
         let config_path = Self::config_path()?;
@@ -206,7 +376,7 @@ impl Config {
             }
         );
 
-        let profile = self.profiles.get(profile_name);
+        let profile = self.find_profile(profile_name);
         if profile.is_none() {
             warn!("Profile '{}' not found in configuration", profile_name);
         }
@@ -234,7 +404,7 @@ impl Config {
 
         info!("Active profile: {}", profile_name);
 
-        self.profiles.get(profile_name).ok_or_else(|| {
+        self.find_profile(profile_name).ok_or_else(|| {
             warn!("Profile '{}' not found in configuration", profile_name);
             anyhow::anyhow!("Profile '{}' not found", profile_name)
         })
@@ -254,22 +424,100 @@ impl Config {
         self.profiles.remove(name)
     }
 
+    /// Copy profile `source` to a new profile `name`, applying `KEY=VALUE` overrides
+    ///
+    /// The source may be a provided profile; the copy is always stored in the config file.
+    pub fn clone_profile(&mut self, source: &str, name: &str, overrides: &[String]) -> Result<()> {
+        if self.find_profile(name).is_some() {
+            anyhow::bail!("Profile '{}' already exists", name);
+        }
+        let mut profile = self
+            .find_profile(source)
+            .cloned()
+            .with_context(|| format!("Profile '{}' does not exist", source))?;
+        for assignment in overrides {
+            let (key, value) = assignment
+                .split_once('=')
+                .with_context(|| format!("Invalid setting '{}': expected KEY=VALUE", assignment))?;
+            profile.set_field(key.trim(), value)?;
+        }
+        self.profiles.insert(name.to_string(), profile);
+        Ok(())
+    }
+
+    /// Rename a profile stored in the config file, keeping it the default if it was
+    pub fn rename_profile(&mut self, old: &str, new: &str) -> Result<()> {
+        if self.find_profile(new).is_some() {
+            anyhow::bail!("Profile '{}' already exists", new);
+        }
+        let Some(profile) = self.profiles.remove(old) else {
+            match self.provided_profiles.get(old) {
+                Some(provided) => anyhow::bail!(
+                    "Profile '{}' comes from {} and can only be renamed there",
+                    old,
+                    provided.source
+                ),
+                None => anyhow::bail!("Profile '{}' does not exist", old),
+            }
+        };
+        self.profiles.insert(new.to_string(), profile);
+        if self.default_profile.as_deref() == Some(old) {
+            self.default_profile = Some(new.to_string());
+        }
+        Ok(())
+    }
+
     /// Set the default profile
     pub fn set_default_profile(&mut self, name: String) -> Result<()> {
-        if !self.profiles.contains_key(&name) {
+        if self.find_profile(&name).is_none() {
             anyhow::bail!("Profile '{}' does not exist", name);
         }
         self.default_profile = Some(name);
         Ok(())
     }
 
-    /// List all profiles sorted by name
+    /// List all profiles, including provided ones, sorted by name
     pub fn list_profiles(&self) -> Vec<(&String, &Profile)> {
-        let mut profiles: Vec<_> = self.profiles.iter().collect();
+        let mut profiles: Vec<_> = self
+            .profiles
+            .iter()
+            .chain(self.provided_profiles.iter().map(|(n, p)| (n, &p.profile)))
+            .collect();
         profiles.sort_by_key(|(name, _)| *name);
         profiles
     }
 
+    /// Resolve a named Cloud API environment to its base URL
+    pub fn resolve_environment(&self, name: &str) -> Result<String> {
+        if let Some(url) = self.environments.get(name) {
+            debug!(
+                "Environment '{}' resolved from configuration: {}",
+                name, url
+            );
+            return Ok(url.clone());
+        }
+        if let Some((_, url)) = CLOUD_ENVIRONMENTS.iter().find(|(n, _)| *n == name) {
+            debug!(
+                "Environment '{}' resolved from built-in presets: {}",
+                name, url
+            );
+            return Ok(url.to_string());
+        }
+
+        let mut known: Vec<&str> = CLOUD_ENVIRONMENTS
+            .iter()
+            .map(|(n, _)| *n)
+            .chain(self.environments.keys().map(String::as_str))
+            .collect();
+        known.sort_unstable();
+        known.dedup();
+        anyhow::bail!(
+            "Unknown environment '{}'. Known environments: {}. Add others under [environments] in the config file.",
+            name,
+            known.join(", ")
+        )
+    }
+
     /// Get the path to the configuration file
     ///
     /// On macOS, this supports both the standard macOS path and Linux-style ~/.config path:
@@ -336,7 +584,7 @@ impl Config {
     /// api_key = "${REDIS_CLOUD_API_KEY}"
     /// api_url = "${REDIS_CLOUD_API_URL:-https://api.redislabs.com/v1}"
     /// ```
-    fn expand_env_vars(content: &str) -> Result<String> {
+    pub(crate) fn expand_env_vars(content: &str) -> Result<String> {
         match shellexpand::env(content) {
             Ok(expanded) => Ok(expanded.to_string()),
             Err(e) => Err(anyhow::anyhow!(
@@ -347,7 +595,7 @@ impl Config {
     }
 }
 
-fn default_cloud_url() -> String {
+pub(crate) fn default_cloud_url() -> String {
     "https://api.redislabs.com/v1".to_string()
 }
 
@@ -366,6 +614,7 @@ mod tests {
                 api_secret: "test-secret".to_string(),
                 api_url: "https://api.redislabs.com/v1".to_string(),
             },
+            read_only: false,
         };
 
         config.set_profile("test".to_string(), cloud_profile);
@@ -378,6 +627,102 @@ mod tests {
         assert_eq!(config.profiles.len(), deserialized.profiles.len());
     }
 
+    #[test]
+    fn test_clone_profile() {
+        let mut config = Config::default();
+        config.set_profile(
+            "staging".to_string(),
+            Profile {
+                deployment_type: DeploymentType::Enterprise,
+                credentials: ProfileCredentials::Enterprise {
+                    url: "https://staging:9443".to_string(),
+                    username: "admin".to_string(),
+                    password: Some("${STAGING_PASSWORD}".to_string()),
+                    insecure: true,
+                    tls_fingerprint_sha256: None,
+                },
+                read_only: false,
+            },
+        );
+
+        config
+            .clone_profile(
+                "staging",
+                "prod",
+                &[
+                    "url=https://prod:9443".to_string(),
+                    "insecure=false".to_string(),
+                    "tls_fingerprint_sha256=44:CF:47:25".to_string(),
+                ],
+            )
+            .unwrap();
+        let (url, username, password, insecure) =
+            config.profiles["prod"].enterprise_credentials().unwrap();
+        assert_eq!(url, "https://prod:9443");
+        assert_eq!(username, "admin");
+        assert_eq!(password, Some("${STAGING_PASSWORD}"));
+        assert!(!insecure);
+        assert!(!config.profiles["prod"].read_only);
+        assert_eq!(
+            config.profiles["prod"].tls_fingerprint_sha256(),
+            Some("44:CF:47:25")
+        );
+        assert_eq!(config.profiles["staging"].tls_fingerprint_sha256(), None);
+
+        config
+            .clone_profile("prod", "prod-locked", &["read_only=true".to_string()])
+            .unwrap();
+        assert!(config.profiles["prod-locked"].read_only);
+        let toml = toml::to_string(&config).unwrap();
+        assert_eq!(toml.matches("read_only = true").count(), 1);
+        assert_eq!(toml.matches("tls_fingerprint_sha256").count(), 2);
+        assert_eq!(
+            config.profiles["staging"]
+                .enterprise_credentials()
+                .unwrap()
+                .0,
+            "https://staging:9443"
+        );
+
+        assert!(config.clone_profile("staging", "prod", &[]).is_err());
+        assert!(config.clone_profile("missing", "other", &[]).is_err());
+        assert!(
+            config
+                .clone_profile("staging", "other", &["api_key=x".to_string()])
+                .is_err()
+        );
+        assert!(
+            config
+                .clone_profile("staging", "other", &["url".to_string()])
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_rename_profile() {
+        let mut config = Config::default();
+        let profile = Profile {
+            deployment_type: DeploymentType::Cloud,
+            credentials: ProfileCredentials::Cloud {
+                api_key: "key".to_string(),
+                api_secret: "secret".to_string(),
+                api_url: "https://api.redislabs.com/v1".to_string(),
+            },
+            read_only: false,
+        };
+        config.set_profile("old".to_string(), profile.clone());
+        config.set_profile("other".to_string(), profile);
+        config.default_profile = Some("old".to_string());
+
+        config.rename_profile("old", "new").unwrap();
+        assert!(config.profiles.contains_key("new"));
+        assert!(!config.profiles.contains_key("old"));
+        assert_eq!(config.default_profile.as_deref(), Some("new"));
+
+        assert!(config.rename_profile("new", "other").is_err());
+        assert!(config.rename_profile("missing", "x").is_err());
+    }
+
     #[test]
     fn test_profile_credential_access() {
         let cloud_profile = Profile {
@@ -387,6 +732,7 @@ mod tests {
                 api_secret: "secret".to_string(),
                 api_url: "url".to_string(),
             },
+            read_only: false,
         };
 
         let (key, secret, url) = cloud_profile.cloud_credentials().unwrap();
@@ -396,6 +742,42 @@ mod tests {
         assert!(cloud_profile.enterprise_credentials().is_none());
     }
 
+    #[test]
+    fn test_resolve_environment() {
+        let mut config: Config = toml::from_str(
+            r#"
+[environments]
+us-gov = "https://cloud-api.example.gov/v1"
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            config.resolve_environment("production").unwrap(),
+            "https://api.redislabs.com/v1"
+        );
+        assert_eq!(
+            config.resolve_environment("us-gov").unwrap(),
+            "https://cloud-api.example.gov/v1"
+        );
+
+        let err = config
+            .resolve_environment("staging")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("production, us-gov"));
+
+        // Configured environments override the built-in presets
+        config.environments.insert(
+            "production".to_string(),
+            "https://proxy.internal/v1".to_string(),
+        );
+        assert_eq!(
+            config.resolve_environment("production").unwrap(),
+            "https://proxy.internal/v1"
+        );
+    }
+
     #[test]
     #[serial_test::serial]
     fn test_env_var_expansion() {
@@ -3,19 +3,53 @@
 use crate::config::{Config, Profile};
 use crate::error::Result as CliResult;
 use anyhow::Context;
+use std::sync::{Arc, OnceLock};
+use tokio::sync::Semaphore;
 use tracing::{debug, info, trace};
 
 /// Connection manager for creating authenticated clients
 #[allow(dead_code)] // Used by binary target
 pub struct ConnectionManager {
     pub config: Config,
+    /// Cloud API base URL from the command line, taking precedence over everything else
+    pub cloud_api_url: Option<String>,
+    /// Allow changes with read-only profiles (`--unlock`)
+    pub unlock: bool,
 }
 
 impl ConnectionManager {
     /// Create a new connection manager with the given configuration
     #[allow(dead_code)] // Used by binary target
     pub fn new(config: Config) -> Self {
-        Self { config }
+        Self {
+            config,
+            cloud_api_url: None,
+            unlock: false,
+        }
+    }
+
+    /// Override the Cloud API base URL for every client this manager creates
+    #[allow(dead_code)] // Used by binary target
+    pub fn with_cloud_api_url(mut self, api_url: Option<String>) -> Self {
+        self.cloud_api_url = api_url;
+        self
+    }
+
+    /// Allow POST, PUT, PATCH and DELETE requests with read-only profiles
+    #[allow(dead_code)] // Used by binary target
+    pub fn with_unlock(mut self, unlock: bool) -> Self {
+        self.unlock = unlock;
+        self
+    }
+
+    /// Whether clients for this profile must refuse changes
+    ///
+    /// A read-only profile stays locked even when environment variables supply the credentials.
+    fn is_read_only(&self, profile_name: Option<&str>) -> bool {
+        !self.unlock
+            && self
+                .get_profile(profile_name)
+                .is_ok_and(|profile| profile.read_only)
     }
 
     /// Get a profile by name, or the default profile if no name provided
@@ -28,7 +62,7 @@ impl ConnectionManager {
             )?,
         };
 
-        Ok(self.config.profiles.get(name).with_context(|| {
+        Ok(self.config.find_profile(name).with_context(|| {
             format!(
                 "Profile '{}' not found. Use 'redisctl profile list' to see available profiles.",
                 name
@@ -90,6 +124,14 @@ impl ConnectionManager {
                 (key, secret, url)
             };
 
+        let final_api_url = match &self.cloud_api_url {
+            Some(url) => {
+                debug!("Using Redis Cloud API URL from the command line");
+                url.clone()
+            }
+            None => final_api_url,
+        };
+
         info!("Connecting to Redis Cloud API: {}", final_api_url);
         trace!(
             "API key: {}...",
@@ -97,10 +139,19 @@ impl ConnectionManager {
         );
 
         // Create and configure the Cloud client
-        let client = redis_cloud::CloudClient::builder()
+        let mut builder = redis_cloud::CloudClient::builder()
             .api_key(&final_api_key)
             .api_secret(&final_api_secret)
             .base_url(&final_api_url)
+            .observer(crate::api_stats::global())
+            .read_only(self.is_read_only(profile_name));
+
+        if let Some(max_body_size) = max_body_size_from_env()? {
+            builder = builder.max_body_size(max_body_size);
+        }
+        builder = builder.request_limiter(request_limiter()?);
+
+        let client = builder
             .build()
             .context("Failed to create Redis Cloud client")?;
 
@@ -122,6 +173,9 @@ impl ConnectionManager {
         let env_user = std::env::var("REDIS_ENTERPRISE_USER").ok();
         let env_password = std::env::var("REDIS_ENTERPRISE_PASSWORD").ok();
         let env_insecure = std::env::var("REDIS_ENTERPRISE_INSECURE").ok();
+        let env_fingerprint = std::env::var("REDIS_ENTERPRISE_TLS_FINGERPRINT_SHA256")
+            .ok()
+            .filter(|value| !value.is_empty());
 
         if env_url.is_some() {
             debug!("Found REDIS_ENTERPRISE_URL environment variable");
@@ -136,7 +190,7 @@ impl ConnectionManager {
             debug!("Found REDIS_ENTERPRISE_INSECURE environment variable");
         }
 
-        let (final_url, final_username, final_password, final_insecure) =
+        let (final_url, final_username, final_password, final_insecure, final_fingerprint) =
             if let (Some(url), Some(user)) = (&env_url, &env_user) {
                 // Environment variables provide complete credentials
                 info!("Using Redis Enterprise credentials from environment variables");
@@ -145,7 +199,13 @@ impl ConnectionManager {
                     .as_ref()
                     .map(|s| s.to_lowercase() == "true" || s == "1")
                     .unwrap_or(false);
-                (url.clone(), user.clone(), password, insecure)
+                (
+                    url.clone(),
+                    user.clone(),
+                    password,
+                    insecure,
+                    env_fingerprint.clone(),
+                )
             } else {
                 // Fall back to profile credentials
                 info!("Using Redis Enterprise credentials from profile");
@@ -153,12 +213,14 @@ impl ConnectionManager {
                 let (url, username, password, insecure) = profile
                     .enterprise_credentials()
                     .context("Profile is not configured for Redis Enterprise")?;
+                let fingerprint = profile.tls_fingerprint_sha256();
 
                 // Check for partial overrides before consuming the Options
                 let has_overrides = env_url.is_some()
                     || env_user.is_some()
                     || env_password.is_some()
-                    || env_insecure.is_some();
+                    || env_insecure.is_some()
+                    || env_fingerprint.is_some();
 
                 // Allow partial environment variable overrides
                 let final_url = env_url.unwrap_or_else(|| url.to_string());
@@ -168,12 +230,21 @@ impl ConnectionManager {
                     .as_ref()
                     .map(|s| s.to_lowercase() == "true" || s == "1")
                     .unwrap_or(insecure);
+                let final_fingerprint = env_fingerprint
+                    .clone()
+                    .or_else(|| fingerprint.map(|f| f.to_string()));
 
                 if has_overrides {
                     debug!("Applied partial environment variable overrides");
                 }
 
-                (final_url, final_user, final_password, final_insecure)
+                (
+                    final_url,
+                    final_user,
+                    final_password,
+                    final_insecure,
+                    final_fingerprint,
+                )
             };
 
         info!("Connecting to Redis Enterprise: {}", final_url);
@@ -191,7 +262,9 @@ impl ConnectionManager {
         // Build the Enterprise client
         let mut builder = redis_enterprise::EnterpriseClient::builder()
             .base_url(&final_url)
-            .username(&final_username);
+            .username(&final_username)
+            .observer(crate::api_stats::global())
+            .read_only(self.is_read_only(profile_name));
 
         // Add password if provided
         if let Some(ref password) = final_password {
@@ -199,12 +272,20 @@ impl ConnectionManager {
             trace!("Password added to client builder");
         }
 
-        // Set insecure flag if needed
-        if final_insecure {
+        // A pinned certificate replaces CA verification, so it wins over insecure mode
+        if let Some(ref fingerprint) = final_fingerprint {
+            builder = builder.tls_fingerprint_sha256(fingerprint);
+            debug!("Server certificate pinned to SHA-256 {}", fingerprint);
+        } else if final_insecure {
             builder = builder.insecure(true);
             debug!("SSL certificate verification disabled");
         }
 
+        if let Some(max_body_size) = max_body_size_from_env()? {
+            builder = builder.max_body_size(max_body_size);
+        }
+        builder = builder.request_limiter(request_limiter()?);
+
         let client = builder
             .build()
             .context("Failed to create Redis Enterprise client")?;
@@ -213,3 +294,58 @@ impl ConnectionManager {
         Ok(client)
     }
 }
+
+static REQUEST_LIMITER: OnceLock<Arc<Semaphore>> = OnceLock::new();
+
+/// The request limit shared by every client of this process, Cloud and Enterprise alike
+///
+/// Sized from `REDISCTL_MAX_CONCURRENT_REQUESTS` when the first client is created.
+fn request_limiter() -> CliResult<Arc<Semaphore>> {
+    if let Some(limiter) = REQUEST_LIMITER.get() {
+        return Ok(limiter.clone());
+    }
+    let limit = max_concurrent_requests_from_env()?
+        .unwrap_or(redis_cloud::client::DEFAULT_MAX_CONCURRENT_REQUESTS);
+    Ok(REQUEST_LIMITER
+        .get_or_init(|| Arc::new(Semaphore::new(limit)))
+        .clone())
+}
+
+/// Read the concurrent request limit override from `REDISCTL_MAX_CONCURRENT_REQUESTS`
+fn max_concurrent_requests_from_env() -> CliResult<Option<usize>> {
+    let Ok(value) = std::env::var("REDISCTL_MAX_CONCURRENT_REQUESTS") else {
+        return Ok(None);
+    };
+    let limit = value
+        .trim()
+        .parse::<usize>()
+        .ok()
+        .filter(|limit| *limit > 0)
+        .ok_or_else(|| crate::error::RedisCtlError::Validation {
+            message: format!(
+                "Invalid REDISCTL_MAX_CONCURRENT_REQUESTS '{}': expected a positive number",
+                value
+            ),
+        })?;
+    debug!("Concurrent request limit: {}", limit);
+    Ok(Some(limit))
+}
+
+/// Read the response body size limit override (in bytes) from `REDISCTL_MAX_BODY_SIZE`
+fn max_body_size_from_env() -> CliResult<Option<usize>> {
+    let Ok(value) = std::env::var("REDISCTL_MAX_BODY_SIZE") else {
+        return Ok(None);
+    };
+    let bytes =
+        value
+            .trim()
+            .parse::<usize>()
+            .map_err(|_| crate::error::RedisCtlError::Validation {
+                message: format!(
+                    "Invalid REDISCTL_MAX_BODY_SIZE '{}': expected a size in bytes",
+                    value
+                ),
+            })?;
+    debug!("Response body size limit: {} bytes", bytes);
+    Ok(Some(bytes))
+}
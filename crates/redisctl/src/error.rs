@@ -6,7 +6,15 @@
 
 use thiserror::Error;
 
+/// The error a failure originated from, kept as the source of a [`RedisCtlError`]
+pub type BoxError = Box<dyn std::error::Error + Send + Sync + 'static>;
+
 /// Main error type for the redisctl application
+///
+/// Failures are grouped by what the user can do about them: fix credentials (`Auth`), check the
+/// network path (`Network`), correct an ID (`NotFound`) or the input (`Validation`), allow more
+/// time (`Timeout`), or read what the API said (`Api`). [`RedisCtlError::hint`] gives the
+/// suggestion printed below the error.
 #[derive(Error, Debug)]
 pub enum RedisCtlError {
     #[error("Configuration error: {0}")]
@@ -31,64 +39,237 @@ pub enum RedisCtlError {
     MissingCredentials { name: String },
 
     #[error("Authentication failed: {message}")]
-    AuthenticationFailed { message: String },
+    Auth {
+        message: String,
+        source: Option<BoxError>,
+    },
 
-    #[error("API error: {message}")]
-    ApiError { message: String },
+    #[error("Connection error: {message}")]
+    Network {
+        message: String,
+        source: Option<BoxError>,
+    },
+
+    #[error("Not found: {message}")]
+    NotFound {
+        message: String,
+        source: Option<BoxError>,
+    },
 
     #[error("Invalid input: {message}")]
-    InvalidInput { message: String },
+    Validation { message: String },
+
+    #[error("Timeout: {message}")]
+    Timeout { message: String },
+
+    #[error("API error: {message}")]
+    Api {
+        /// HTTP status code, when the failure came from a response
+        code: Option<u16>,
+        message: String,
+        /// The response body, when it was JSON
+        details: Option<Box<serde_json::Value>>,
+        source: Option<BoxError>,
+    },
 
     #[error("Command not supported for deployment type '{deployment_type}'")]
     UnsupportedDeploymentType { deployment_type: String },
     #[error("File error for '{path}': {message}")]
     FileError { path: String, message: String },
 
-    #[error("Connection error: {message}")]
-    ConnectionError { message: String },
-
-    #[error("Timeout: {message}")]
-    Timeout { message: String },
-
     #[error("Output formatting error: {message}")]
     OutputError { message: String },
+
+    #[error("{message}; the profile is read-only, pass --unlock to allow changes")]
+    ReadOnlyProfile { message: String },
+
+    #[error("{message}, but prompts are disabled (--non-interactive); {guidance}")]
+    PromptDisabled { message: String, guidance: String },
+
+    #[error("{message} (rerun with --lenient to print the raw response)")]
+    UnexpectedResponse {
+        message: String,
+        path: String,
+        raw: Box<serde_json::Value>,
+    },
+}
+
+impl RedisCtlError {
+    /// An API failure detected by redisctl rather than reported with a status code
+    pub fn api(message: impl Into<String>) -> Self {
+        RedisCtlError::Api {
+            code: None,
+            message: message.into(),
+            details: None,
+            source: None,
+        }
+    }
+
+    /// A network failure without an underlying error to carry
+    pub fn network(message: impl Into<String>) -> Self {
+        RedisCtlError::Network {
+            message: message.into(),
+            source: None,
+        }
+    }
+
+    /// Raw API response for errors caused by a response that did not match its type
+    pub fn raw_response(&self) -> Option<&serde_json::Value> {
+        match self {
+            RedisCtlError::UnexpectedResponse { raw, .. } => Some(raw),
+            _ => None,
+        }
+    }
+
+    /// What the user can try next, printed below the error
+    pub fn hint(&self) -> Option<&'static str> {
+        match self {
+            RedisCtlError::Auth { .. } | RedisCtlError::MissingCredentials { .. } => Some(
+                "check the profile's credentials with `redisctl profile show`, or run `redisctl doctor`",
+            ),
+            RedisCtlError::Network { message, .. } if message.contains("fingerprint mismatch") => {
+                Some(
+                    "the server certificate is not the one the profile pins; if the cluster's certificate was replaced, update `tls_fingerprint_sha256`",
+                )
+            }
+            RedisCtlError::Network { message, .. } if message.contains("certificate") => Some(
+                "for a cluster with a self-signed certificate, pin it with `tls_fingerprint_sha256` on the profile, or set `insecure`",
+            ),
+            RedisCtlError::Network { .. } => Some(
+                "check the profile's URL and that the API is reachable from here; `redisctl doctor` tests the connection",
+            ),
+            RedisCtlError::NotFound { .. } => {
+                Some("check the ID; the `list` command of the resource shows the ones that exist")
+            }
+            RedisCtlError::Timeout { .. } => Some(
+                "the operation may still finish; check its status, or allow more time with the command's timeout option",
+            ),
+            RedisCtlError::Api {
+                code: Some(403), ..
+            } => Some("the API key or user of this profile lacks permission for this operation"),
+            RedisCtlError::Api {
+                code: Some(429), ..
+            } => Some("the API rate limit was reached; wait a moment and retry"),
+            RedisCtlError::Api {
+                code: Some(500..), ..
+            } => Some("the server failed to handle the request; retry later"),
+            RedisCtlError::ProfileNotFound { .. } => {
+                Some("`redisctl profile list` shows the configured profiles")
+            }
+            _ => None,
+        }
+    }
 }
 
 /// Result type for redisctl operations
 pub type Result<T> = std::result::Result<T, RedisCtlError>;
 
+/// Parse an error response body so it can be shown as structured details
+fn response_details(message: &str) -> Option<Box<serde_json::Value>> {
+    serde_json::from_str::<serde_json::Value>(message)
+        .ok()
+        .filter(|value| value.is_object() || value.is_array())
+        .map(Box::new)
+}
+
 impl From<redis_cloud::CloudError> for RedisCtlError {
     fn from(err: redis_cloud::CloudError) -> Self {
+        use redis_cloud::CloudError;
+
+        let message = err.to_string();
         match err {
-            redis_cloud::CloudError::AuthenticationFailed { message } => {
-                RedisCtlError::AuthenticationFailed { message }
+            CloudError::AuthenticationFailed { .. } => RedisCtlError::Auth {
+                message,
+                source: Some(err.into()),
+            },
+            CloudError::ConnectionError(_) | CloudError::Request(_) => RedisCtlError::Network {
+                message,
+                source: Some(err.into()),
+            },
+            CloudError::NotFound { .. } => RedisCtlError::NotFound {
+                message,
+                source: Some(err.into()),
+            },
+            CloudError::ReadOnly { .. } => RedisCtlError::ReadOnlyProfile { message },
+            CloudError::DeserializationError { path, raw, .. } => {
+                RedisCtlError::UnexpectedResponse { message, path, raw }
             }
-            redis_cloud::CloudError::ConnectionError(message) => {
-                RedisCtlError::ConnectionError { message }
+            _ => {
+                let (code, details) = match &err {
+                    CloudError::BadRequest { message } => (Some(400), response_details(message)),
+                    CloudError::Forbidden { message } => (Some(403), response_details(message)),
+                    CloudError::PreconditionFailed => (Some(412), None),
+                    CloudError::InternalServerError { message } => {
+                        (Some(500), response_details(message))
+                    }
+                    CloudError::ServiceUnavailable { message } => {
+                        (Some(503), response_details(message))
+                    }
+                    CloudError::ApiError { code, message } => {
+                        (Some(*code), response_details(message))
+                    }
+                    _ => (None, None),
+                };
+                RedisCtlError::Api {
+                    code,
+                    message,
+                    details,
+                    source: Some(err.into()),
+                }
             }
-            _ => RedisCtlError::ApiError {
-                message: err.to_string(),
-            },
         }
     }
 }
 
 impl From<redis_enterprise::RestError> for RedisCtlError {
     fn from(err: redis_enterprise::RestError) -> Self {
+        use redis_enterprise::RestError;
+
+        let message = err.to_string();
+        if err.is_unauthorized() {
+            return RedisCtlError::Auth {
+                message,
+                source: Some(err.into()),
+            };
+        }
+        if err.is_not_found() {
+            return RedisCtlError::NotFound {
+                message,
+                source: Some(err.into()),
+            };
+        }
         match err {
-            redis_enterprise::RestError::AuthenticationFailed => {
-                RedisCtlError::AuthenticationFailed {
-                    message: "Authentication failed".to_string(),
-                }
+            RestError::RequestFailed(ref e) if e.is_timeout() => RedisCtlError::Timeout { message },
+            RestError::RequestFailed(_)
+            | RestError::ConnectionError(_)
+            | RestError::NotConnected => RedisCtlError::Network {
+                message,
+                source: Some(err.into()),
+            },
+            RestError::DeserializationError { path, raw, .. } => {
+                RedisCtlError::UnexpectedResponse { message, path, raw }
+            }
+            RestError::Timeout(message) => RedisCtlError::Timeout { message },
+            RestError::ReadOnly { .. } => RedisCtlError::ReadOnlyProfile { message },
+            RestError::Io(_) => RedisCtlError::OutputError { message },
+            RestError::InvalidUrl(_) | RestError::ValidationError(_) => {
+                RedisCtlError::Validation { message }
             }
-            redis_enterprise::RestError::RequestFailed(reqwest_err) => {
-                RedisCtlError::ConnectionError {
-                    message: reqwest_err.to_string(),
+            _ => {
+                let (code, details) = match &err {
+                    RestError::ApiError { code, message } => {
+                        (Some(*code), response_details(message))
+                    }
+                    RestError::ServerError(message) => (Some(500), response_details(message)),
+                    _ => (None, None),
+                };
+                RedisCtlError::Api {
+                    code,
+                    message,
+                    details,
+                    source: Some(err.into()),
                 }
             }
-            _ => RedisCtlError::ApiError {
-                message: err.to_string(),
-            },
         }
     }
 }
@@ -111,6 +292,208 @@ impl From<std::io::Error> for RedisCtlError {
 
 impl From<anyhow::Error> for RedisCtlError {
     fn from(err: anyhow::Error) -> Self {
-        RedisCtlError::Config(err.to_string())
+        // A refused request is reported as such, not as the context it was wrapped in
+        let refused = err
+            .downcast_ref::<redis_enterprise::RestError>()
+            .is_some_and(|e| matches!(e, redis_enterprise::RestError::ReadOnly { .. }))
+            || err
+                .downcast_ref::<redis_cloud::CloudError>()
+                .is_some_and(|e| matches!(e, redis_cloud::CloudError::ReadOnly { .. }));
+        if refused {
+            return RedisCtlError::ReadOnlyProfile {
+                message: format!("{:#}", err),
+            };
+        }
+
+        // Keep the raw response when an API call wrapped with context failed to deserialize
+        let unexpected = err
+            .downcast_ref::<redis_enterprise::RestError>()
+            .and_then(|e| match e {
+                redis_enterprise::RestError::DeserializationError { path, raw, .. } => {
+                    Some((path, raw))
+                }
+                _ => None,
+            })
+            .or_else(|| {
+                err.downcast_ref::<redis_cloud::CloudError>()
+                    .and_then(|e| match e {
+                        redis_cloud::CloudError::DeserializationError { path, raw, .. } => {
+                            Some((path, raw))
+                        }
+                        _ => None,
+                    })
+            });
+        match unexpected {
+            Some((path, raw)) => RedisCtlError::UnexpectedResponse {
+                message: format!("{:#}", err),
+                path: path.clone(),
+                raw: raw.clone(),
+            },
+            None => categorize(err),
+        }
+    }
+}
+
+/// Sort an error wrapped with context by the API error underneath, keeping the whole chain as
+/// the message and the error itself as the source
+fn categorize(err: anyhow::Error) -> RedisCtlError {
+    let category = err
+        .chain()
+        .find_map(|cause| {
+            if let Some(e) = cause.downcast_ref::<redis_enterprise::RestError>() {
+                return Some(enterprise_category(e));
+            }
+            cause
+                .downcast_ref::<redis_cloud::CloudError>()
+                .map(cloud_category)
+        })
+        .unwrap_or(Category::Other);
+
+    let message = format!("{:#}", err);
+    let source: Option<BoxError> = Some(err.into());
+    match category {
+        Category::Auth => RedisCtlError::Auth { message, source },
+        Category::Network => RedisCtlError::Network { message, source },
+        Category::NotFound => RedisCtlError::NotFound { message, source },
+        Category::Timeout => RedisCtlError::Timeout { message },
+        Category::Api(code) => RedisCtlError::Api {
+            code,
+            message,
+            details: None,
+            source,
+        },
+        Category::Other => RedisCtlError::Config(message),
+    }
+}
+
+/// Which [`RedisCtlError`] variant an API error belongs to
+enum Category {
+    Auth,
+    Network,
+    NotFound,
+    Timeout,
+    Api(Option<u16>),
+    Other,
+}
+
+fn enterprise_category(err: &redis_enterprise::RestError) -> Category {
+    use redis_enterprise::RestError;
+
+    if err.is_unauthorized() {
+        return Category::Auth;
+    }
+    if err.is_not_found() {
+        return Category::NotFound;
+    }
+    match err {
+        RestError::RequestFailed(e) if e.is_timeout() => Category::Timeout,
+        RestError::Timeout(_) => Category::Timeout,
+        RestError::RequestFailed(_) | RestError::ConnectionError(_) | RestError::NotConnected => {
+            Category::Network
+        }
+        RestError::ApiError { code, .. } => Category::Api(Some(*code)),
+        RestError::ServerError(_) => Category::Api(Some(500)),
+        _ => Category::Api(None),
+    }
+}
+
+fn cloud_category(err: &redis_cloud::CloudError) -> Category {
+    use redis_cloud::CloudError;
+
+    match err {
+        CloudError::AuthenticationFailed { .. } => Category::Auth,
+        CloudError::NotFound { .. } => Category::NotFound,
+        CloudError::Request(e) if e.is_timeout() => Category::Timeout,
+        CloudError::Request(_) | CloudError::ConnectionError(_) => Category::Network,
+        CloudError::Forbidden { .. } => Category::Api(Some(403)),
+        CloudError::ApiError { code, .. } => Category::Api(Some(*code)),
+        _ => Category::Api(None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::error::Error as _;
+
+    #[test]
+    fn test_enterprise_errors_are_categorized() {
+        let err = RedisCtlError::from(redis_enterprise::RestError::Unauthorized);
+        assert!(matches!(err, RedisCtlError::Auth { .. }));
+        assert!(err.source().is_some());
+        assert!(err.hint().unwrap().contains("redisctl profile show"));
+
+        let err = RedisCtlError::from(redis_enterprise::RestError::NotFound);
+        assert!(matches!(err, RedisCtlError::NotFound { .. }));
+
+        let err = RedisCtlError::from(redis_enterprise::RestError::ConnectionError(
+            "connection refused".to_string(),
+        ));
+        assert!(matches!(err, RedisCtlError::Network { .. }));
+        assert!(err.hint().unwrap().contains("redisctl doctor"));
+
+        let err = RedisCtlError::from(redis_enterprise::RestError::ConnectionError(
+            "TLS handshake with https://re:9443/v1/cluster failed: certificate fingerprint \
+             mismatch: expected SHA-256 44:CF, server presented 00:00"
+                .to_string(),
+        ));
+        assert!(
+            err.hint()
+                .unwrap()
+                .contains("update `tls_fingerprint_sha256`")
+        );
+
+        let err = RedisCtlError::from(redis_enterprise::RestError::ApiError {
+            code: 409,
+            message: r#"{"error_code": "db_busy"}"#.to_string(),
+        });
+        match &err {
+            RedisCtlError::Api { code, details, .. } => {
+                assert_eq!(*code, Some(409));
+                assert_eq!(details.as_ref().unwrap()["error_code"], "db_busy");
+            }
+            other => panic!("expected Api, got {:?}", other),
+        }
+        assert!(err.hint().is_none());
+    }
+
+    #[test]
+    fn test_cloud_errors_are_categorized() {
+        let err = RedisCtlError::from(redis_cloud::CloudError::AuthenticationFailed {
+            message: "bad key".to_string(),
+        });
+        assert!(matches!(err, RedisCtlError::Auth { .. }));
+
+        let err = RedisCtlError::from(redis_cloud::CloudError::ApiError {
+            code: 429,
+            message: "Too many requests".to_string(),
+        });
+        assert!(matches!(
+            err,
+            RedisCtlError::Api {
+                code: Some(429),
+                details: None,
+                ..
+            }
+        ));
+        assert!(err.hint().unwrap().contains("rate limit"));
+    }
+
+    #[test]
+    fn test_context_keeps_category_and_chain() {
+        let err = anyhow::Error::new(redis_enterprise::RestError::NotFound)
+            .context("Failed to get database 7");
+        let err = RedisCtlError::from(err);
+
+        assert!(matches!(err, RedisCtlError::NotFound { .. }));
+        assert!(
+            err.to_string()
+                .starts_with("Not found: Failed to get database 7: ")
+        );
+        assert!(err.source().is_some());
+
+        let err = RedisCtlError::from(anyhow::anyhow!("Invalid cron expression"));
+        assert!(matches!(err, RedisCtlError::Config(_)));
+        assert!(err.hint().is_none());
     }
 }
@@ -0,0 +1,78 @@
+//! Non-interactive mode for scripts and CI
+//!
+//! With `--non-interactive`, `REDISCTL_NON_INTERACTIVE` or a `CI` environment variable, any code
+//! path that would prompt (a confirmation, a password) fails with a message naming the flag or
+//! input that replaces the prompt, so a script never waits for input nobody will type.
+
+#![allow(dead_code)]
+
+use crate::error::{RedisCtlError, Result as CliResult};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static NON_INTERACTIVE: AtomicBool = AtomicBool::new(false);
+
+/// Enable non-interactive mode when asked for or when running under CI
+pub fn init(non_interactive: bool) {
+    let ci = std::env::var("CI").is_ok_and(|value| is_truthy(&value));
+    set_non_interactive(non_interactive || ci);
+}
+
+/// Disable prompts for this process
+pub fn set_non_interactive(non_interactive: bool) {
+    NON_INTERACTIVE.store(non_interactive, Ordering::Relaxed);
+}
+
+/// Whether prompts are disabled
+pub fn is_non_interactive() -> bool {
+    NON_INTERACTIVE.load(Ordering::Relaxed)
+}
+
+/// Fail instead of prompting when prompts are disabled
+///
+/// `action` says what the prompt was for and `guidance` how to provide the answer up front,
+/// e.g. `"pass --force"`.
+pub fn ensure_prompt_allowed(action: &str, guidance: &str) -> CliResult<()> {
+    if is_non_interactive() {
+        return Err(RedisCtlError::PromptDisabled {
+            message: action.to_string(),
+            guidance: guidance.to_string(),
+        });
+    }
+    Ok(())
+}
+
+/// CI systems set `CI=true` or `CI=1`; an empty value or `false` means not CI
+fn is_truthy(value: &str) -> bool {
+    !matches!(
+        value.trim().to_ascii_lowercase().as_str(),
+        "" | "0" | "false" | "no" | "off"
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_truthy() {
+        assert!(is_truthy("true"));
+        assert!(is_truthy("1"));
+        assert!(is_truthy("woodpecker"));
+        assert!(!is_truthy(""));
+        assert!(!is_truthy("false"));
+        assert!(!is_truthy("0"));
+    }
+
+    #[test]
+    fn test_ensure_prompt_allowed() {
+        set_non_interactive(true);
+        let err = ensure_prompt_allowed("Deleting database 3 needs confirmation", "pass --force")
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Deleting database 3 needs confirmation, but prompts are disabled (--non-interactive); pass --force"
+        );
+        set_non_interactive(false);
+        assert!(ensure_prompt_allowed("x", "y").is_ok());
+    }
+}
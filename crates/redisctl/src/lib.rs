@@ -81,9 +81,23 @@
 //! For complete documentation and examples, see the [GitHub repository](https://github.com/joshrotenberg/redisctl).
 
 // Internal modules for CLI functionality
+pub(crate) mod api_stats;
+pub(crate) mod certificate;
 pub(crate) mod cli;
 pub(crate) mod commands;
 pub(crate) mod config;
 pub(crate) mod connection;
 pub(crate) mod error;
+pub(crate) mod interactive;
 pub(crate) mod output;
+pub(crate) mod password;
+pub(crate) mod profile_bundle;
+pub(crate) mod profile_provider;
+pub(crate) mod progress;
+pub(crate) mod query;
+pub(crate) mod telemetry;
+pub(crate) mod timestamps;
+pub(crate) mod units;
+
+#[cfg(not(any(feature = "rustls-tls", feature = "native-tls")))]
+compile_error!("redisctl needs a TLS backend: enable the `rustls-tls` or `native-tls` feature");
@@ -1,14 +1,26 @@
+use crate::output::notice;
 use anyhow::Result;
-use clap::Parser;
+use clap::{CommandFactory, FromArgMatches};
 use tracing::{debug, error, info, trace};
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
+mod api_stats;
+mod certificate;
 mod cli;
 mod commands;
 mod config;
 mod connection;
 mod error;
+mod interactive;
 mod output;
+mod password;
+mod profile_bundle;
+mod profile_provider;
+mod progress;
+mod query;
+mod telemetry;
+mod timestamps;
+mod units;
 
 use cli::{Cli, Commands};
 use config::Config;
@@ -17,24 +29,127 @@ use error::RedisCtlError;
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    let cli = Cli::parse();
+    let matches = Cli::command().get_matches();
+    let cli = Cli::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
 
     // Initialize tracing based on verbosity level
     init_tracing(cli.verbose);
+    progress::init(cli.progress);
+    output::set_quiet(cli.quiet);
+    timestamps::init(cli.utc, cli.local);
+    interactive::init(cli.non_interactive);
 
-    // Load configuration
-    let config = Config::load()?;
-    let conn_mgr = ConnectionManager::new(config);
+    // Explaining a command needs neither configuration nor a connection
+    if cli.explain {
+        if let Err(e) = commands::explain::run_explain(&matches, cli.output, cli.query.as_deref()) {
+            report_error(&e, cli.verbose);
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
 
-    // Execute command
-    if let Err(e) = execute_command(&cli, &conn_mgr).await {
-        eprintln!("Error: {}", e);
+    // Load configuration; `doctor` reports a broken config file instead of stopping at it
+    let config = match Config::load() {
+        Ok(config) => config,
+        Err(e) if matches!(cli.command, Commands::Doctor { .. }) => {
+            debug!("Ignoring configuration error for doctor: {:#}", e);
+            Config::default()
+        }
+        Err(e) => return Err(e),
+    };
+    let cloud_api_url = match (&cli.api_url, &cli.environment) {
+        (Some(url), _) => Some(url.clone()),
+        (None, Some(environment)) => match config.resolve_environment(environment) {
+            Ok(url) => Some(url),
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                std::process::exit(1);
+            }
+        },
+        (None, None) => None,
+    };
+    let conn_mgr = ConnectionManager::new(config)
+        .with_cloud_api_url(cloud_api_url)
+        .with_unlock(cli.unlock);
+
+    // Execute command, recording its usage when telemetry is enabled
+    let start = std::time::Instant::now();
+    let result = execute_command(&cli, &conn_mgr).await;
+    if !matches!(cli.command, Commands::Stats { .. }) {
+        telemetry::record(
+            &conn_mgr.config,
+            &telemetry::command_path(&matches),
+            start.elapsed(),
+            result.is_ok(),
+        );
+    }
+    if let Err(e) = result {
+        if cli.lenient
+            && let RedisCtlError::UnexpectedResponse { path, raw, .. } = &e
+        {
+            print_raw_response(&cli, path, raw);
+            // The raw body stands in for the result of a read, but a change whose response could
+            // not be parsed has an unknown outcome and must not look like a success
+            if !api_stats::global().sent_changes() {
+                return Ok(());
+            }
+            eprintln!(
+                "Error: the command sent changes and their outcome could not be confirmed from the response"
+            );
+            std::process::exit(1);
+        }
+        report_error(&e, cli.verbose);
         std::process::exit(1);
     }
 
     Ok(())
 }
 
+/// Print a failed command's error, with its causes at `-v` and a hint when there is one
+fn report_error(err: &RedisCtlError, verbose: u8) {
+    eprintln!("Error: {}", err);
+    if verbose > 0 {
+        let message = err.to_string();
+        let mut cause = std::error::Error::source(err);
+        while let Some(e) = cause {
+            let text = e.to_string();
+            // Context chains are already part of the message
+            if !message.contains(&text) {
+                eprintln!("  Caused by: {}", text);
+            }
+            cause = e.source();
+        }
+        if let RedisCtlError::Api {
+            details: Some(details),
+            ..
+        } = err
+        {
+            eprintln!("  Details: {}", details);
+        }
+    }
+    if let Some(hint) = err.hint() {
+        eprintln!("Hint: {}", hint);
+    }
+}
+
+/// With `--lenient`, print a response that did not match its expected type as-is
+///
+/// The command still fails when it sent a request other than GET, see `main`.
+fn print_raw_response(cli: &Cli, path: &str, raw: &serde_json::Value) {
+    use commands::enterprise::utils::{handle_output, print_formatted_output};
+
+    notice!(
+        "Warning: response did not match the expected format at '{}'; printing it unmodified",
+        path
+    );
+    let printed = handle_output(raw.clone(), cli.output, cli.query.as_deref())
+        .and_then(|data| print_formatted_output(data, cli.output));
+    if let Err(e) = printed {
+        eprintln!("Error: {}", e);
+        std::process::exit(1);
+    }
+}
+
 fn init_tracing(verbose: u8) {
     // Check for RUST_LOG env var first, then fall back to verbosity flag
     let filter = if std::env::var("RUST_LOG").is_ok() {
@@ -76,6 +191,110 @@ async fn execute_command(cli: &Cli, conn_mgr: &ConnectionManager) -> Result<(),
             Ok(())
         }
 
+        Commands::SelfUpdate {
+            channel,
+            check,
+            reinstall,
+            force,
+        } => {
+            let options = commands::self_update::SelfUpdateOptions {
+                channel: *channel,
+                check: *check,
+                reinstall: *reinstall,
+                force: *force,
+            };
+            commands::self_update::run_self_update(&options).await
+        }
+
+        Commands::Selftest {
+            deployment,
+            samples,
+        } => {
+            commands::selftest::run_selftest(
+                conn_mgr,
+                cli.profile.as_deref(),
+                *deployment,
+                *samples,
+                cli.output,
+                cli.query.as_deref(),
+            )
+            .await
+        }
+
+        Commands::Doctor { deployment } => {
+            commands::doctor::run_doctor(
+                conn_mgr,
+                cli.profile.as_deref(),
+                *deployment,
+                cli.output,
+                cli.query.as_deref(),
+            )
+            .await
+        }
+
+        Commands::Search { expression, types } => {
+            commands::search::run_search(
+                conn_mgr,
+                cli.profile.as_deref(),
+                expression,
+                types,
+                cli.output,
+                cli.query.as_deref(),
+            )
+            .await
+        }
+
+        Commands::Diff {
+            deployment,
+            resource,
+            id,
+            against,
+            ignore,
+            all_fields,
+            exit_code,
+        } => {
+            let options = commands::diff::DiffOptions {
+                deployment: *deployment,
+                resource,
+                id: id.as_deref(),
+                against,
+                ignore,
+                all_fields: *all_fields,
+                exit_code: *exit_code,
+            };
+            commands::diff::run_diff(
+                conn_mgr,
+                cli.profile.as_deref(),
+                &options,
+                cli.output,
+                cli.query.as_deref(),
+            )
+            .await
+        }
+
+        Commands::Serve {
+            listen,
+            token,
+            allow,
+            profiles,
+        } => {
+            let options = commands::serve::ServeOptions {
+                listen: listen.clone(),
+                token: token.clone(),
+                allow: allow.clone(),
+                profiles: profiles.clone(),
+            };
+            commands::serve::run_serve(conn_mgr, options).await
+        }
+
+        Commands::Stats { top, reset } => commands::stats::run_stats(
+            &conn_mgr.config,
+            *top,
+            *reset,
+            cli.output,
+            cli.query.as_deref(),
+        ),
+
         Commands::Profile(profile_cmd) => {
             debug!("Executing profile command");
             execute_profile_command(profile_cmd, conn_mgr).await
@@ -120,6 +339,9 @@ async fn execute_command(cli: &Cli, conn_mgr: &ConnectionManager) -> Result<(),
         Ok(_) => info!("Command completed successfully in {:?}", duration),
         Err(e) => error!("Command failed after {:?}: {}", duration, e),
     }
+    if cli.verbose > 0 {
+        api_stats::print_summary();
+    }
 
     result
 }
@@ -128,6 +350,27 @@ async fn execute_command(cli: &Cli, conn_mgr: &ConnectionManager) -> Result<(),
 fn format_command(command: &Commands) -> String {
     match command {
         Commands::Version => "version".to_string(),
+        Commands::Stats { top, reset } => format!("stats --top {:?} --reset {}", top, reset),
+        Commands::SelfUpdate { channel, .. } => format!("self-update {:?}", channel),
+        Commands::Selftest { deployment, .. } => format!("selftest {:?}", deployment),
+        Commands::Doctor { deployment } => format!("doctor {:?}", deployment),
+        Commands::Search { expression, types } => {
+            format!("search {:?} --types {}", expression, types.join(","))
+        }
+        Commands::Serve { listen, .. } => format!("serve {:?} [token redacted]", listen),
+        Commands::Diff {
+            deployment,
+            resource,
+            id,
+            against,
+            ..
+        } => format!(
+            "diff {:?} {} {} --against {}",
+            deployment,
+            resource,
+            id.as_deref().unwrap_or(""),
+            against
+        ),
         Commands::Profile(cmd) => {
             use cli::ProfileCommands::*;
             match cmd {
@@ -136,6 +379,24 @@ fn format_command(command: &Commands) -> String {
                 Set { name, .. } => format!("profile set {} [credentials redacted]", name),
                 Remove { name } => format!("profile remove {}", name),
                 Default { name } => format!("profile default {}", name),
+                Clone { source, name, .. } => {
+                    format!("profile clone {} {} [settings redacted]", source, name)
+                }
+                Rename { old, new } => format!("profile rename {} {}", old, new),
+                Export {
+                    names,
+                    include_secrets,
+                    ..
+                } => format!(
+                    "profile export {}{}",
+                    names.join(" "),
+                    if *include_secrets {
+                        " --include-secrets"
+                    } else {
+                        ""
+                    }
+                ),
+                Import { file, .. } => format!("profile import {}", file),
             }
         }
         Commands::Api {
@@ -201,6 +462,12 @@ async fn execute_enterprise_command(
             )
             .await
         }
+        Rbac(rbac_cmd) => {
+            commands::enterprise::rbac::handle_rbac_command(
+                conn_mgr, profile, rbac_cmd, output, query,
+            )
+            .await
+        }
         Ldap(ldap_cmd) => {
             commands::enterprise::rbac::handle_ldap_command(
                 conn_mgr, profile, ldap_cmd, output, query,
@@ -219,6 +486,80 @@ async fn execute_enterprise_command(
             )
             .await
         }
+        Shard(shard_cmd) => {
+            commands::enterprise::shard::handle_shard_command(
+                conn_mgr, profile, shard_cmd, output, query,
+            )
+            .await
+        }
+        Endpoint(endpoint_cmd) => {
+            commands::enterprise::endpoint::handle_endpoint_command(
+                conn_mgr,
+                profile,
+                endpoint_cmd,
+                output,
+                query,
+            )
+            .await
+        }
+        Traffic(traffic_cmd) => {
+            commands::enterprise::traffic::handle_traffic_command(
+                conn_mgr,
+                profile,
+                traffic_cmd,
+                output,
+                query,
+            )
+            .await
+        }
+        Alert(alert_cmd) => {
+            commands::enterprise::alert::handle_alert_command(
+                conn_mgr, profile, alert_cmd, output, query,
+            )
+            .await
+        }
+        Ocsp(ocsp_cmd) => {
+            commands::enterprise::ocsp::handle_ocsp_command(
+                conn_mgr, profile, ocsp_cmd, output, query,
+            )
+            .await
+        }
+        Logs(logs_cmd) => {
+            commands::enterprise::logs::handle_logs_command(
+                conn_mgr, profile, logs_cmd, output, query,
+            )
+            .await
+        }
+        License(license_cmd) => {
+            commands::enterprise::license::handle_license_command(
+                conn_mgr,
+                profile,
+                license_cmd,
+                output,
+                query,
+            )
+            .await
+        }
+        Module(module_cmd) => {
+            commands::enterprise::module::handle_module_command(
+                conn_mgr, profile, module_cmd, output, query,
+            )
+            .await
+        }
+        Service(service_cmd) => {
+            commands::enterprise::service::handle_service_command(
+                conn_mgr,
+                profile,
+                service_cmd,
+                output,
+                query,
+            )
+            .await
+        }
+        Stats(stats_cmd) => {
+            commands::enterprise::stats::handle_stats_command(conn_mgr, profile, stats_cmd).await
+        }
+        Top { interval } => commands::enterprise::top::run_top(conn_mgr, profile, *interval).await,
     }
 }
 
@@ -236,8 +577,8 @@ async fn execute_profile_command(
 
             if profiles.is_empty() {
                 info!("No profiles configured");
-                println!("No profiles configured.");
-                println!("Use 'redisctl profile set' to create a profile.");
+                notice!("No profiles configured.");
+                notice!("Use 'redisctl profile set' to create a profile.");
                 return Ok(());
             }
 
@@ -259,12 +600,30 @@ async fn execute_profile_command(
                                 "URL: {}, User: {}{}",
                                 url,
                                 username,
-                                if insecure { " (insecure)" } else { "" }
+                                if profile.tls_fingerprint_sha256().is_some() {
+                                    " (pinned certificate)"
+                                } else if insecure {
+                                    " (insecure)"
+                                } else {
+                                    ""
+                                }
                             );
                         }
                     }
                 }
 
+                if profile.read_only {
+                    details.push_str(" (read-only)");
+                }
+
+                if let Some(source) = conn_mgr
+                    .config
+                    .profile_source(name)
+                    .filter(|s| *s != "config")
+                {
+                    details.push_str(&format!(" [{}]", source));
+                }
+
                 let is_default = conn_mgr.config.default_profile.as_deref() == Some(name);
                 let name_display = if is_default {
                     format!("{}*", name)
@@ -281,10 +640,13 @@ async fn execute_profile_command(
             Ok(())
         }
 
-        Show { name } => match conn_mgr.config.profiles.get(name) {
+        Show { name } => match conn_mgr.config.find_profile(name) {
             Some(profile) => {
                 println!("Profile: {}", name);
                 println!("Type: {}", profile.deployment_type);
+                if let Some(source) = conn_mgr.config.profile_source(name) {
+                    println!("Source: {}", source);
+                }
 
                 match profile.deployment_type {
                     config::DeploymentType::Cloud => {
@@ -311,10 +673,17 @@ async fn execute_profile_command(
                                 }
                             );
                             println!("Insecure: {}", insecure);
+                            if let Some(fingerprint) = profile.tls_fingerprint_sha256() {
+                                println!("Pinned certificate (SHA-256): {}", fingerprint);
+                            }
                         }
                     }
                 }
 
+                if profile.read_only {
+                    println!("Read-only: yes");
+                }
+
                 let is_default = conn_mgr.config.default_profile.as_deref() == Some(name);
                 if is_default {
                     println!("Default: yes");
@@ -325,8 +694,104 @@ async fn execute_profile_command(
             None => Err(RedisCtlError::ProfileNotFound { name: name.clone() }),
         },
 
+        Clone { source, name, set } => {
+            let mut config = Config::load_for_update()?;
+            config.clone_profile(source, name, set)?;
+            config.save()?;
+            notice!("Profile '{}' created from '{}'", name, source);
+            Ok(())
+        }
+
+        Rename { old, new } => {
+            let mut config = Config::load_for_update()?;
+            config.rename_profile(old, new)?;
+            config.save()?;
+            notice!("Profile '{}' renamed to '{}'", old, new);
+            Ok(())
+        }
+
+        Export {
+            names,
+            out,
+            armor,
+            include_secrets,
+        } => {
+            use std::io::{IsTerminal, Write};
+
+            if out.is_none() && !*armor && std::io::stdout().is_terminal() {
+                return Err(RedisCtlError::Validation {
+                    message: "Refusing to write a binary bundle to the terminal; pass --out FILE \
+                              or --armor"
+                        .to_string(),
+                });
+            }
+            let config = Config::load_for_update()?;
+            let bundle = profile_bundle::build_bundle(&config, names, *include_secrets)?;
+            let passphrase = profile_bundle::resolve_passphrase(true)?;
+            let sealed = profile_bundle::seal(&bundle, &passphrase, *armor, None)?;
+            match out {
+                Some(path) => {
+                    std::fs::write(path, &sealed).map_err(|e| RedisCtlError::FileError {
+                        path: path.display().to_string(),
+                        message: e.to_string(),
+                    })?;
+                    notice!(
+                        "Exported {} profile(s) to {}{}",
+                        bundle.profiles.len(),
+                        path.display(),
+                        if *include_secrets {
+                            ""
+                        } else {
+                            " without secrets"
+                        }
+                    );
+                }
+                None => std::io::stdout().write_all(&sealed).map_err(|e| {
+                    RedisCtlError::OutputError {
+                        message: e.to_string(),
+                    }
+                })?,
+            }
+            Ok(())
+        }
+
+        Import { file, overwrite } => {
+            use std::io::Read;
+
+            let mut sealed = Vec::new();
+            let read = if file == "-" {
+                std::io::stdin().read_to_end(&mut sealed).map(|_| ())
+            } else {
+                std::fs::File::open(file).and_then(|mut f| f.read_to_end(&mut sealed).map(|_| ()))
+            };
+            read.map_err(|e| RedisCtlError::FileError {
+                path: file.clone(),
+                message: e.to_string(),
+            })?;
+            let passphrase = profile_bundle::resolve_passphrase(false)?;
+            let bundle = profile_bundle::open(&sealed, &passphrase)?;
+            let includes_secrets = bundle.includes_secrets;
+            let mut config = Config::load_for_update()?;
+            let summary = profile_bundle::import_bundle(&mut config, bundle, *overwrite)?;
+            config.save()?;
+            for name in &summary.added {
+                notice!("Added profile '{}'", name);
+            }
+            for name in &summary.replaced {
+                notice!("Replaced profile '{}'", name);
+            }
+            if !includes_secrets {
+                notice!(
+                    "The bundle has no secrets: set REDISCTL_PROFILE_<NAME>_API_KEY and \
+                     _API_SECRET for Cloud profiles, and REDISCTL_PROFILE_<NAME>_PASSWORD for \
+                     Enterprise profiles"
+                );
+            }
+            Ok(())
+        }
+
         _ => {
-            println!("Profile management commands (set, remove, default) are not yet implemented");
+            notice!("Profile management commands (set, remove, default) are not yet implemented");
             Ok(())
         }
     }
@@ -342,6 +807,8 @@ async fn execute_api_command(
 ) -> Result<(), RedisCtlError> {
     commands::api::handle_api_command(commands::api::ApiCommandParams {
         config: conn_mgr.config.clone(),
+        cloud_api_url: conn_mgr.cloud_api_url.clone(),
+        unlock: conn_mgr.unlock,
         profile_name: cli.profile.clone(),
         deployment: *deployment,
         method: method.clone(),
@@ -464,5 +931,330 @@ async fn execute_cloud_command(
             )
             .await
         }
+        Crdb(crdb_cmd) => {
+            commands::cloud::crdb::handle_crdb_command(
+                conn_mgr,
+                cli.profile.as_deref(),
+                crdb_cmd,
+                cli.output,
+                cli.query.as_deref(),
+            )
+            .await
+        }
+        Logs(logs_cmd) => {
+            commands::cloud::logs::handle_logs_command(conn_mgr, cli.profile.as_deref(), logs_cmd)
+                .await
+        }
+        AccessReport { export } => {
+            commands::cloud::access_report::access_report(
+                conn_mgr,
+                cli.profile.as_deref(),
+                *export,
+                cli.output,
+                cli.query.as_deref(),
+            )
+            .await
+        }
+        Whoami => {
+            commands::cloud::whoami::whoami(
+                conn_mgr,
+                cli.profile.as_deref(),
+                cli.output,
+                cli.query.as_deref(),
+            )
+            .await
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    //! The `--explain` call plans checked against the requests their handlers send
+
+    use super::*;
+    use serde_json::{Value, json};
+
+    /// A planned command run against a local server that records each request
+    struct Run {
+        args: &'static [&'static str],
+        /// Response bodies by `METHOD /path`; other requests get `{}`
+        responses: Vec<(&'static str, Value)>,
+        /// Planned calls the handler only makes in situations this run does not set up
+        not_sent: &'static [&'static str],
+    }
+
+    fn run(args: &'static [&'static str]) -> Run {
+        Run {
+            args,
+            responses: Vec::new(),
+            not_sent: &[],
+        }
+    }
+
+    impl Run {
+        fn respond(mut self, call: &'static str, body: Value) -> Self {
+            self.responses.push((call, body));
+            self
+        }
+
+        fn not_sent(self, calls: &'static [&'static str]) -> Self {
+            Run {
+                not_sent: calls,
+                ..self
+            }
+        }
+    }
+
+    /// Planned commands without a [`Run`], and why
+    const NOT_RUN: &[(&str, &str)] = &[
+        ("cloud logs forward", "keeps polling until stopped"),
+        ("cloud subscription cmk set", "polls a task for --wait"),
+        ("enterprise cluster recover", "polls an action id"),
+        (
+            "enterprise cluster reset",
+            "asks for the cluster name on a terminal",
+        ),
+        (
+            "enterprise cluster maintenance-mode enable",
+            "polls an action id",
+        ),
+        (
+            "enterprise cluster maintenance-mode disable",
+            "polls an action id",
+        ),
+    ];
+
+    fn runs() -> Vec<Run> {
+        let database = json!({"uid": 1, "name": "cache", "status": "active"});
+        let replica = |sync: &str| json!({"uid": 1, "name": "cache", "replica_sources": [{"uri": "x"}], "replica_sync": sync});
+        vec![
+            run(&["api", "enterprise", "get", "/v1/cluster"]),
+            run(&["cloud", "subscription", "list"])
+                .respond("GET /subscriptions", json!({"subscriptions": []}))
+                .respond("GET /fixed/subscriptions", json!({"subscriptions": []})),
+            run(&["cloud", "subscription", "get", "12"])
+                .respond("GET /subscriptions/12", json!({"id": 12, "name": "prod"}))
+                .not_sent(&["GET /fixed/subscriptions/12"]),
+            run(&["cloud", "subscription", "cmk", "status", "12"])
+                .respond("GET /subscriptions/12", json!({"id": 12, "name": "prod"})),
+            run(&["cloud", "account", "get"]).respond("GET /", json!({"account": {"id": 1}})),
+            run(&["cloud", "account", "get-payment-methods"])
+                .respond("GET /payment-methods", json!({"paymentMethods": []})),
+            run(&["cloud", "account", "list-modules"])
+                .respond("GET /database-modules", json!({"modules": []})),
+            run(&["cloud", "account", "get-persistence-options"])
+                .respond("GET /data-persistence", json!({"dataPersistence": []})),
+            run(&["cloud", "database", "get", "12:34"])
+                .respond(
+                    "GET /fixed/subscriptions/12/databases/34",
+                    json!({"databaseId": 34, "name": "cache"}),
+                )
+                .not_sent(&["GET /subscriptions/12/databases/34"]),
+            run(&["cloud", "task", "get", "t1"]).respond(
+                "GET /tasks/t1",
+                json!({"taskId": "t1", "status": "processing-completed"}),
+            ),
+            run(&["cloud", "user", "list"]).respond("GET /users", json!([])),
+            run(&["cloud", "user", "get", "5"])
+                .respond("GET /users/5", json!({"id": 5, "name": "Ops"})),
+            run(&["enterprise", "crdb", "list"]).respond("GET /v1/crdbs", json!([])),
+            run(&["enterprise", "crdb", "get", "3"])
+                .respond("GET /v1/crdbs/3", json!({"guid": "3", "name": "global"})),
+            run(&["enterprise", "role", "list"]).respond("GET /v1/roles", json!([])),
+            run(&["enterprise", "role", "get", "4"])
+                .respond("GET /v1/roles/4", json!({"uid": 4, "name": "Viewer"})),
+            run(&["enterprise", "role", "delete", "4", "--force"]),
+            run(&["enterprise", "user", "list"]).respond("GET /v1/users", json!([])),
+            run(&["enterprise", "user", "get", "7"]).respond(
+                "GET /v1/users/7",
+                json!({"uid": 7, "username": "ops", "role": "admin"}),
+            ),
+            run(&["enterprise", "user", "delete", "7", "--force"]),
+            run(&["cloud", "database", "delete", "12:34", "--force"]).respond(
+                "DELETE /subscriptions/12/databases/34",
+                json!({"taskId": "t1", "status": "received"}),
+            ),
+            run(&["enterprise", "cluster", "get"])
+                .respond("GET /v1/cluster", json!({"name": "lab"})),
+            run(&["enterprise", "cluster", "maintenance-mode", "status"])
+                .respond("GET /v1/nodes", json!([]))
+                .respond("GET /v1/nodes/status", json!({})),
+            run(&["enterprise", "database", "list"]).respond("GET /v1/bdbs", json!([])),
+            run(&["enterprise", "database", "get", "1"])
+                .respond("GET /v1/bdbs/1", database.clone()),
+            run(&[
+                "enterprise",
+                "database",
+                "create",
+                "--data",
+                r#"{"name": "cache", "memory_size": 1073741824}"#,
+            ])
+            .respond("POST /v1/bdbs", database.clone()),
+            run(&[
+                "enterprise",
+                "database",
+                "create",
+                "--data",
+                r#"{"name": "cache", "memory_size": 1073741824}"#,
+                "--dry-run",
+            ]),
+            run(&[
+                "enterprise",
+                "database",
+                "update",
+                "1",
+                "--data",
+                r#"{"memory_size": 2147483648}"#,
+            ])
+            .respond("PUT /v1/bdbs/1", database.clone()),
+            run(&["enterprise", "database", "delete", "1", "--force"]),
+            run(&["enterprise", "database", "sync", "status", "1"])
+                .respond("GET /v1/bdbs/1", database.clone()),
+            run(&["enterprise", "database", "sync", "pause", "1", "--force"])
+                .respond("GET /v1/bdbs/1", replica("enabled"))
+                .respond("PUT /v1/bdbs/1", database.clone()),
+            run(&["enterprise", "database", "sync", "resume", "1", "--force"])
+                .respond("GET /v1/bdbs/1", replica("paused"))
+                .respond("PUT /v1/bdbs/1", database.clone()),
+            run(&["enterprise", "node", "list"]).respond("GET /v1/nodes", json!([])),
+            run(&["enterprise", "node", "get", "1"])
+                .respond("GET /v1/nodes/1", json!({"uid": 1, "status": "active"})),
+            run(&["enterprise", "traffic", "overview"])
+                .respond("GET /v1/endpoints/stats", json!({}))
+                .respond("GET /v1/proxies", json!([]))
+                .respond("GET /v1/bdbs", json!([])),
+            run(&[
+                "enterprise",
+                "user",
+                "reset-password",
+                "7",
+                "--generate-password",
+            ])
+            .respond(
+                "GET /v1/users/7",
+                json!({"uid": 7, "username": "ops", "role": "admin"}),
+            )
+            .respond(
+                "PUT /v1/users/7",
+                json!({"uid": 7, "username": "ops", "role": "admin"}),
+            ),
+        ]
+    }
+
+    /// Run a command's handler against a local server and return the requests it sent
+    async fn sent_requests(run: &Run) -> Vec<String> {
+        use axum::extract::State;
+        use axum::http::{Method, Uri};
+        use std::sync::{Arc, Mutex};
+
+        type Recorded = (Mutex<Vec<String>>, Vec<(&'static str, Value)>);
+        async fn record(
+            State(state): State<Arc<Recorded>>,
+            method: Method,
+            uri: Uri,
+        ) -> axum::Json<Value> {
+            let call = format!("{} {}", method, uri.path());
+            let body = state
+                .1
+                .iter()
+                .find(|(planned, _)| *planned == call)
+                .map(|(_, body)| body.clone())
+                .unwrap_or_else(|| json!({}));
+            state.0.lock().unwrap().push(call);
+            axum::Json(body)
+        }
+
+        let state = Arc::new((Mutex::new(Vec::new()), run.responses.clone()));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let router = axum::Router::new()
+            .fallback(record)
+            .with_state(state.clone());
+        tokio::spawn(async move { axum::serve(listener, router).await });
+
+        let mut config = crate::config::Config::default();
+        config.set_profile(
+            "re".to_string(),
+            crate::config::Profile {
+                deployment_type: crate::config::DeploymentType::Enterprise,
+                credentials: crate::config::ProfileCredentials::Enterprise {
+                    url: url.clone(),
+                    username: "admin@lab".to_string(),
+                    password: Some("secret".to_string()),
+                    insecure: false,
+                    tls_fingerprint_sha256: None,
+                },
+                read_only: false,
+            },
+        );
+        config.set_profile(
+            "rc".to_string(),
+            crate::config::Profile {
+                deployment_type: crate::config::DeploymentType::Cloud,
+                credentials: crate::config::ProfileCredentials::Cloud {
+                    api_key: "key".to_string(),
+                    api_secret: "secret".to_string(),
+                    api_url: url,
+                },
+                read_only: false,
+            },
+        );
+        let profile = if run.args.contains(&"cloud") {
+            "rc"
+        } else {
+            "re"
+        };
+        let args = ["redisctl", "--profile", profile, "-o", "json"]
+            .into_iter()
+            .chain(run.args.iter().copied());
+        let cli = <Cli as clap::Parser>::try_parse_from(args).unwrap();
+        execute_command(&cli, &ConnectionManager::new(config))
+            .await
+            .unwrap_or_else(|e| panic!("{:?}: {}", run.args, e));
+
+        let mut sent = state.0.lock().unwrap().clone();
+        // A polled request counts once
+        sent.dedup();
+        sent
+    }
+
+    #[tokio::test]
+    async fn test_plans_match_the_requests_handlers_send() {
+        for run in runs() {
+            let matches = Cli::command()
+                .try_get_matches_from(std::iter::once("redisctl").chain(run.args.iter().copied()))
+                .unwrap();
+            let (_, plan) = commands::explain::plan_for(&matches).unwrap();
+            let planned: Vec<String> = plan
+                .iter()
+                .map(|call| {
+                    let path = call.path.split('?').next().unwrap_or_default();
+                    format!("{} {}", call.method, path)
+                })
+                .filter(|call| !run.not_sent.contains(&call.as_str()))
+                .collect();
+            assert_eq!(sent_requests(&run).await, planned, "{:?}", run.args);
+        }
+    }
+
+    #[test]
+    fn test_every_plan_is_run_or_excused() {
+        let runs = runs();
+        for path in commands::explain::planned_commands() {
+            let checked = runs.iter().any(|run| {
+                let matches = Cli::command()
+                    .try_get_matches_from(
+                        std::iter::once("redisctl").chain(run.args.iter().copied()),
+                    )
+                    .unwrap();
+                telemetry::command_path(&matches) == path
+            });
+            let excused = NOT_RUN.iter().any(|(command, _)| *command == path);
+            assert!(
+                checked != excused,
+                "`{}` needs exactly one of a run or a NOT_RUN entry",
+                path
+            );
+        }
     }
 }
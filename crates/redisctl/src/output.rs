@@ -2,23 +2,43 @@
 
 use anyhow::{Context, Result};
 use comfy_table::Table;
-use jmespath::compile;
 use serde::Serialize;
 use serde_json::Value;
+use std::sync::atomic::{AtomicBool, Ordering};
 
-#[derive(Debug, Clone, Copy, clap::ValueEnum)]
+static QUIET: AtomicBool = AtomicBool::new(false);
+
+/// Suppress informational messages for this process (`--quiet`)
+pub fn set_quiet(quiet: bool) {
+    QUIET.store(quiet, Ordering::Relaxed);
+}
+
+/// Whether informational messages are suppressed
+pub fn is_quiet() -> bool {
+    QUIET.load(Ordering::Relaxed)
+}
+
+/// Print an informational message, such as the outcome of a change, on stderr
+///
+/// Stdout is kept for command output, so pipelines only see data. Nothing is printed with
+/// `--quiet`.
+macro_rules! notice {
+    ($($arg:tt)*) => {
+        if !$crate::output::is_quiet() {
+            eprintln!($($arg)*);
+        }
+    };
+}
+pub(crate) use notice;
+
+#[derive(Debug, Clone, Copy, Default, clap::ValueEnum)]
 pub enum OutputFormat {
+    #[default]
     Json,
     Yaml,
     Table,
 }
 
-impl Default for OutputFormat {
-    fn default() -> Self {
-        Self::Json
-    }
-}
-
 pub fn print_output<T: Serialize>(
     data: T,
     format: OutputFormat,
@@ -28,7 +48,7 @@ pub fn print_output<T: Serialize>(
 
     // Apply JMESPath query if provided
     if let Some(query_str) = query {
-        let expr = compile(query_str).context("Invalid JMESPath expression")?;
+        let expr = crate::query::compile(query_str).context("Invalid JMESPath expression")?;
         // Convert Value to string then parse as Variable
         let json_str = serde_json::to_string(&json_value)?;
         let data = jmespath::Variable::from_json(&json_str)
@@ -55,7 +75,170 @@ pub fn print_output<T: Serialize>(
     Ok(())
 }
 
+/// Fields that hold a resource's identifier, in order of preference
+const ID_FIELDS: &[&str] = &[
+    "resourceId",
+    "id",
+    "uid",
+    "databaseId",
+    "subscriptionId",
+    "taskId",
+];
+
+/// Identifiers in a response: one per element of an array, the first ID field of an object, or
+/// the value itself when a query already reduced it to a string or number
+pub fn resource_ids(value: &Value) -> Vec<String> {
+    match value {
+        Value::Array(items) => items.iter().flat_map(resource_ids).collect(),
+        Value::Object(fields) => ID_FIELDS
+            .iter()
+            .find_map(|field| fields.get(*field).and_then(scalar_id))
+            .into_iter()
+            .collect(),
+        other => scalar_id(other).into_iter().collect(),
+    }
+}
+
+/// The ID of the resource a finished Cloud task created or changed (`response.resourceId`)
+pub fn task_resource_id(task: &Value) -> Option<String> {
+    task.get("response")?.get("resourceId").and_then(scalar_id)
+}
+
+fn scalar_id(value: &Value) -> Option<String> {
+    match value {
+        Value::String(s) if !s.is_empty() => Some(s.clone()),
+        Value::Number(n) => Some(n.to_string()),
+        _ => None,
+    }
+}
+
+/// Print only the identifiers in `value`, one per line, for `--output id`
+pub fn print_ids(value: &Value) -> Result<()> {
+    let ids = resource_ids(value);
+    if ids.is_empty() {
+        anyhow::bail!("No resource ID found in the response; use -o json to see the full output");
+    }
+    for id in ids {
+        println!("{}", id);
+    }
+    Ok(())
+}
+
+/// Table rows printed as each page arrives, for `--stream`
+///
+/// Column widths are fixed by the header and the first batch of rows, so later batches can be
+/// printed without waiting for the rest; a wider value in a later row only widens that row.
+pub struct RowStream {
+    headers: Vec<String>,
+    widths: Option<Vec<usize>>,
+    rows: usize,
+}
+
+impl RowStream {
+    pub fn new<T: tabled::Tabled>() -> Self {
+        RowStream {
+            headers: T::headers().into_iter().map(|h| h.into_owned()).collect(),
+            widths: None,
+            rows: 0,
+        }
+    }
+
+    /// Print a batch of rows, preceded by the header on the first non-empty batch
+    pub fn push<T: tabled::Tabled>(&mut self, rows: &[T]) {
+        for line in self.render(rows) {
+            println!("{}", line);
+        }
+    }
+
+    /// Number of rows printed so far
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    fn render<T: tabled::Tabled>(&mut self, rows: &[T]) -> Vec<String> {
+        let cells: Vec<Vec<String>> = rows
+            .iter()
+            .map(|row| row.fields().into_iter().map(|f| f.into_owned()).collect())
+            .collect();
+        if cells.is_empty() {
+            return Vec::new();
+        }
+
+        let mut lines = Vec::new();
+        let widths = match &self.widths {
+            Some(widths) => widths.clone(),
+            None => {
+                let widths: Vec<usize> = self
+                    .headers
+                    .iter()
+                    .enumerate()
+                    .map(|(i, header)| {
+                        cells
+                            .iter()
+                            .map(|row| text_width(&row[i]))
+                            .chain(std::iter::once(text_width(header)))
+                            .max()
+                            .unwrap_or(0)
+                    })
+                    .collect();
+                lines.push(format_row(&self.headers, &widths));
+                self.widths = Some(widths.clone());
+                widths
+            }
+        };
+        lines.extend(cells.iter().map(|row| format_row(row, &widths)));
+        self.rows += cells.len();
+        lines
+    }
+}
+
+/// Display width, ignoring colour codes
+fn text_width(text: &str) -> usize {
+    tabled::grid::util::string::get_text_width(text)
+}
+
+/// One line in the layout of `Style::blank()` tables
+fn format_row(cells: &[String], widths: &[usize]) -> String {
+    let padded: Vec<String> = cells
+        .iter()
+        .zip(widths)
+        .map(|(cell, width)| {
+            let pad = width.saturating_sub(text_width(cell));
+            format!("{}{}", cell, " ".repeat(pad))
+        })
+        .collect();
+    format!(" {} ", padded.join("   "))
+}
+
+/// Reject output options that need the whole result before printing
+pub fn check_stream_output(
+    format: crate::cli::OutputFormat,
+    query: Option<&str>,
+) -> crate::error::Result<()> {
+    if query.is_some() {
+        return Err(crate::error::RedisCtlError::Validation {
+            message: "--stream cannot be combined with --query".to_string(),
+        });
+    }
+    if matches!(format, crate::cli::OutputFormat::Yaml) {
+        return Err(crate::error::RedisCtlError::Validation {
+            message: "--stream supports table, json (one object per line) and id output"
+                .to_string(),
+        });
+    }
+    Ok(())
+}
+
+/// Print values as newline-delimited JSON, one compact object per line
+pub fn print_ndjson(items: &[Value]) -> Result<()> {
+    for item in items {
+        println!("{}", serde_json::to_string(item)?);
+    }
+    Ok(())
+}
+
 fn print_as_table(value: &Value) -> Result<()> {
+    let value = &crate::timestamps::localize(value);
     match value {
         Value::Array(arr) if !arr.is_empty() => {
             let mut table = Table::new();
@@ -113,3 +296,54 @@ fn format_value(value: &Value) -> String {
         Value::Object(obj) => format!("{{{} fields}}", obj.len()),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_resource_ids() {
+        assert_eq!(resource_ids(&json!({"name": "db", "uid": 3})), vec!["3"]);
+        assert_eq!(
+            resource_ids(&json!([{"databaseId": 1}, {"databaseId": 2}, {"name": "x"}])),
+            vec!["1", "2"]
+        );
+        assert_eq!(resource_ids(&json!("task-abc")), vec!["task-abc"]);
+        assert!(resource_ids(&json!({"name": "db"})).is_empty());
+
+        let task = json!({"taskId": "t-1", "status": "processing-completed",
+                          "response": {"resourceId": 51234}});
+        assert_eq!(task_resource_id(&task), Some("51234".to_string()));
+        assert_eq!(task_resource_id(&json!({"taskId": "t-1"})), None);
+    }
+
+    #[derive(tabled::Tabled)]
+    struct Row {
+        #[tabled(rename = "ID")]
+        id: String,
+        #[tabled(rename = "NAME")]
+        name: String,
+    }
+
+    fn row(id: &str, name: &str) -> Row {
+        Row {
+            id: id.to_string(),
+            name: name.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_row_stream() {
+        let mut stream = RowStream::new::<Row>();
+        assert!(stream.render::<Row>(&[]).is_empty());
+
+        let first = stream.render(&[row("1:10", "cache")]);
+        assert_eq!(first, vec![" ID     NAME  ", " 1:10   cache "]);
+
+        // Later batches keep the widths of the first
+        let second = stream.render(&[row("1:2", "db")]);
+        assert_eq!(second, vec![" 1:2    db    "]);
+        assert_eq!(stream.rows(), 2);
+    }
+}
@@ -0,0 +1,225 @@
+//! Reading passwords without exposing them on the command line, checking their strength and
+//! generating strong ones
+//!
+//! A password given as a flag value ends up in shell history and process listings. Commands that
+//! accept one also take it from a hidden prompt or, for scripts, from standard input.
+//!
+//! Strength is estimated locally with zxcvbn, whose dictionaries include the most common leaked
+//! passwords; the password is never sent anywhere to be checked.
+
+#![allow(dead_code)]
+
+use crate::error::{RedisCtlError, Result as CliResult};
+use anyhow::Context;
+use rand::Rng;
+use rand::seq::SliceRandom;
+use std::io::{IsTerminal, Read};
+use zxcvbn::Score;
+
+const LOWERCASE: &[u8] = b"abcdefghijkmnopqrstuvwxyz";
+const UPPERCASE: &[u8] = b"ABCDEFGHJKLMNPQRSTUVWXYZ";
+const DIGITS: &[u8] = b"23456789";
+/// Symbols that need no quoting in common shells and connection URLs
+const SYMBOLS: &[u8] = b"-_.+=^";
+
+/// Shortest password [`generate_password`] produces
+pub const MIN_GENERATED_LENGTH: usize = 12;
+
+/// Length of the passwords `--generate-password` creates
+pub const DEFAULT_GENERATED_LENGTH: usize = 24;
+
+/// Weakest zxcvbn score accepted without `--allow-weak-password` (about 10^10 guesses)
+pub const MIN_PASSWORD_SCORE: Score = Score::Three;
+
+/// Refuse a password that zxcvbn rates below [`MIN_PASSWORD_SCORE`]
+///
+/// `user_inputs` are words the password should not be built from, such as the user's name or
+/// email. With `allow_weak`, a weak password only prints a warning.
+pub fn check_password_strength(
+    password: &str,
+    user_inputs: &[&str],
+    allow_weak: bool,
+) -> CliResult<()> {
+    let Some(problem) = weakness(password, user_inputs) else {
+        return Ok(());
+    };
+    if allow_weak {
+        crate::output::notice!("Warning: {}", problem);
+        return Ok(());
+    }
+    Err(RedisCtlError::Validation {
+        message: format!(
+            "{}. Use --generate-password for a strong one, or --allow-weak-password to use it \
+             anyway",
+            problem
+        ),
+    })
+}
+
+/// Why a password is weak, or `None` if it is strong enough
+fn weakness(password: &str, user_inputs: &[&str]) -> Option<String> {
+    let entropy = zxcvbn::zxcvbn(password, user_inputs);
+    if entropy.score() >= MIN_PASSWORD_SCORE {
+        return None;
+    }
+    let mut problem = format!(
+        "Password is too weak (strength {} of 4",
+        u8::from(entropy.score())
+    );
+    if let Some(feedback) = entropy.feedback() {
+        if let Some(warning) = feedback.warning() {
+            problem.push_str(&format!(": {}", warning));
+        }
+        problem.push(')');
+        if let Some(suggestion) = feedback.suggestions().first() {
+            problem.push_str(&format!(
+                ". {}",
+                suggestion.to_string().trim_end_matches('.')
+            ));
+        }
+    } else {
+        problem.push(')');
+    }
+    Some(problem)
+}
+
+/// The password from a flag value, standard input (`from_stdin`) or a hidden prompt, in that order
+///
+/// The prompt asks twice and requires both entries to match. Without a terminal and without
+/// `from_stdin`, there is nowhere to read the password from and this fails.
+pub fn resolve_password(value: Option<&str>, from_stdin: bool) -> CliResult<String> {
+    if let Some(value) = value {
+        return Ok(value.to_string());
+    }
+    if from_stdin {
+        return read_password_from(std::io::stdin().lock());
+    }
+    crate::interactive::ensure_prompt_allowed(
+        "No password given",
+        "pipe it in with --password-stdin",
+    )?;
+    if !std::io::stdin().is_terminal() {
+        return Err(RedisCtlError::Validation {
+            message: "No password given; pipe it in with --password-stdin or run interactively"
+                .to_string(),
+        });
+    }
+
+    let password = rpassword::prompt_password("Password: ").context("Failed to read password")?;
+    if password.is_empty() {
+        return Err(RedisCtlError::Validation {
+            message: "Password cannot be empty".to_string(),
+        });
+    }
+    let confirmation =
+        rpassword::prompt_password("Confirm password: ").context("Failed to read password")?;
+    if password != confirmation {
+        return Err(RedisCtlError::Validation {
+            message: "Passwords do not match".to_string(),
+        });
+    }
+    Ok(password)
+}
+
+/// Read a password from `reader`, dropping the trailing newline that `echo` and files add
+pub fn read_password_from(mut reader: impl Read) -> CliResult<String> {
+    let mut input = String::new();
+    reader
+        .read_to_string(&mut input)
+        .context("Failed to read password from stdin")?;
+    let password = input
+        .strip_suffix('\n')
+        .map(|rest| rest.strip_suffix('\r').unwrap_or(rest))
+        .unwrap_or(&input);
+    if password.is_empty() {
+        return Err(RedisCtlError::Validation {
+            message: "Password from stdin is empty".to_string(),
+        });
+    }
+    Ok(password.to_string())
+}
+
+/// Parse a `--length` for a generated password, rejecting anything shorter than
+/// [`MIN_GENERATED_LENGTH`]
+pub fn parse_generated_length(s: &str) -> Result<usize, String> {
+    let length: usize = s
+        .parse()
+        .map_err(|_| format!("invalid length: {} (expected a whole number)", s))?;
+    if length < MIN_GENERATED_LENGTH {
+        return Err(format!(
+            "length must be at least {} (got {})",
+            MIN_GENERATED_LENGTH, length
+        ));
+    }
+    Ok(length)
+}
+
+/// A random password with at least one lowercase letter, uppercase letter, digit and symbol
+///
+/// Look-alike characters (`0`, `O`, `1`, `l`, `I`) are left out so the password can be read back
+/// from a terminal.
+pub fn generate_password(length: usize) -> String {
+    let length = length.max(MIN_GENERATED_LENGTH);
+    let classes = [LOWERCASE, UPPERCASE, DIGITS, SYMBOLS];
+    let all: Vec<u8> = classes.concat();
+    let mut rng = rand::rng();
+
+    let mut chars: Vec<u8> = classes
+        .iter()
+        .map(|class| class[rng.random_range(0..class.len())])
+        .collect();
+    while chars.len() < length {
+        chars.push(all[rng.random_range(0..all.len())]);
+    }
+    chars.shuffle(&mut rng);
+    String::from_utf8(chars).expect("password alphabet is ASCII")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_password_from() {
+        assert_eq!(read_password_from(&b"s3cret\n"[..]).unwrap(), "s3cret");
+        assert_eq!(read_password_from(&b"s3cret\r\n"[..]).unwrap(), "s3cret");
+        assert_eq!(read_password_from(&b" spaced "[..]).unwrap(), " spaced ");
+        assert!(read_password_from(&b"\n"[..]).is_err());
+    }
+
+    #[test]
+    fn test_generate_password() {
+        let password = generate_password(24);
+        assert_eq!(password.len(), 24);
+        assert!(password.bytes().any(|c| LOWERCASE.contains(&c)));
+        assert!(password.bytes().any(|c| UPPERCASE.contains(&c)));
+        assert!(password.bytes().any(|c| DIGITS.contains(&c)));
+        assert!(password.bytes().any(|c| SYMBOLS.contains(&c)));
+        assert_eq!(generate_password(4).len(), MIN_GENERATED_LENGTH);
+        assert_ne!(generate_password(24), password);
+    }
+
+    #[test]
+    fn test_parse_generated_length() {
+        assert_eq!(parse_generated_length("24"), Ok(24));
+        assert_eq!(
+            parse_generated_length(&MIN_GENERATED_LENGTH.to_string()),
+            Ok(MIN_GENERATED_LENGTH)
+        );
+        assert!(parse_generated_length("11").is_err());
+        assert!(parse_generated_length("0").is_err());
+        assert!(parse_generated_length("-3").is_err());
+        assert!(parse_generated_length("twelve").is_err());
+    }
+
+    #[test]
+    fn test_password_strength() {
+        let problem = weakness("password1", &[]).unwrap();
+        assert!(problem.starts_with("Password is too weak (strength 0 of 4"));
+        assert!(weakness("jsmith2024!", &["jsmith"]).is_some());
+        assert!(weakness(&generate_password(MIN_GENERATED_LENGTH), &[]).is_none());
+
+        assert!(check_password_strength("password1", &[], false).is_err());
+        assert!(check_password_strength("password1", &[], true).is_ok());
+    }
+}
@@ -0,0 +1,328 @@
+//! Encrypted profile bundles for sharing profiles between machines
+//!
+//! `profile export` writes selected profiles as a TOML document shaped like the config file's
+//! `[profiles]` table, encrypted with an age passphrase (scrypt key derivation,
+//! ChaCha20-Poly1305 payload). Bundles are standard age files, so `age -d` can open them too;
+//! `--armor` makes them ASCII so they can be pasted into chat or a ticket.
+//!
+//! Secrets are left out unless asked for. Cloud API keys and Enterprise passwords become
+//! references to the profile's environment bundle variables
+//! (`${REDISCTL_PROFILE_<NAME>_API_KEY:-}`, `${REDISCTL_PROFILE_<NAME>_PASSWORD:-}`), which the
+//! config file expands at load time.
+
+#![allow(dead_code)]
+
+use age::secrecy::SecretString;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use crate::config::{Config, Profile, ProfileCredentials};
+use crate::error::{RedisCtlError, Result as CliResult};
+use crate::profile_provider::env_name;
+
+/// Environment variable holding the bundle passphrase, for scripts
+pub const PASSPHRASE_ENV: &str = "REDISCTL_BUNDLE_PASSPHRASE";
+
+/// Bundle format version
+const BUNDLE_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProfileBundle {
+    pub version: u32,
+    /// Whether the profiles carry their credentials
+    #[serde(default)]
+    pub includes_secrets: bool,
+    #[serde(default)]
+    pub profiles: BTreeMap<String, Profile>,
+}
+
+/// How an import changed the config
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ImportSummary {
+    pub added: Vec<String>,
+    pub replaced: Vec<String>,
+}
+
+/// The passphrase from [`PASSPHRASE_ENV`] or a hidden prompt, asked twice when `confirm` is set
+pub fn resolve_passphrase(confirm: bool) -> CliResult<String> {
+    if let Ok(passphrase) = std::env::var(PASSPHRASE_ENV)
+        && !passphrase.is_empty()
+    {
+        return Ok(passphrase);
+    }
+    crate::interactive::ensure_prompt_allowed(
+        "The bundle needs a passphrase",
+        &format!("set {}", PASSPHRASE_ENV),
+    )?;
+    let passphrase =
+        rpassword::prompt_password("Bundle passphrase: ").context("Failed to read passphrase")?;
+    if passphrase.is_empty() {
+        return Err(RedisCtlError::Validation {
+            message: "Passphrase cannot be empty".to_string(),
+        });
+    }
+    if confirm
+        && rpassword::prompt_password("Confirm passphrase: ")
+            .context("Failed to read passphrase")?
+            != passphrase
+    {
+        return Err(RedisCtlError::Validation {
+            message: "Passphrases do not match".to_string(),
+        });
+    }
+    Ok(passphrase)
+}
+
+/// Collect profiles `names` (all profiles when empty) into a bundle
+pub fn build_bundle(
+    config: &Config,
+    names: &[String],
+    include_secrets: bool,
+) -> Result<ProfileBundle> {
+    let selected: Vec<(String, Profile)> = if names.is_empty() {
+        config
+            .list_profiles()
+            .into_iter()
+            .map(|(name, profile)| (name.clone(), profile.clone()))
+            .collect()
+    } else {
+        names
+            .iter()
+            .map(|name| {
+                config
+                    .find_profile(name)
+                    .cloned()
+                    .map(|profile| (name.clone(), profile))
+                    .with_context(|| format!("Profile '{}' does not exist", name))
+            })
+            .collect::<Result<_>>()?
+    };
+    if selected.is_empty() {
+        anyhow::bail!("No profiles to export");
+    }
+
+    let profiles = selected
+        .into_iter()
+        .map(|(name, mut profile)| {
+            if !include_secrets {
+                strip_secrets(&name, &mut profile);
+            }
+            (name, profile)
+        })
+        .collect();
+    Ok(ProfileBundle {
+        version: BUNDLE_VERSION,
+        includes_secrets: include_secrets,
+        profiles,
+    })
+}
+
+/// Replace a profile's credentials with environment references
+fn strip_secrets(name: &str, profile: &mut Profile) {
+    let reference = |field: &str| format!("${{REDISCTL_PROFILE_{}_{}:-}}", env_name(name), field);
+    match &mut profile.credentials {
+        ProfileCredentials::Cloud {
+            api_key,
+            api_secret,
+            ..
+        } => {
+            *api_key = reference("API_KEY");
+            *api_secret = reference("API_SECRET");
+        }
+        ProfileCredentials::Enterprise { password, .. } => {
+            *password = Some(reference("PASSWORD"));
+        }
+    }
+}
+
+/// Serialize and encrypt a bundle
+///
+/// `work_factor` overrides the scrypt cost (log2 of N); age picks one that takes about a second
+/// when it is `None`.
+pub fn seal(
+    bundle: &ProfileBundle,
+    passphrase: &str,
+    armor: bool,
+    work_factor: Option<u8>,
+) -> Result<Vec<u8>> {
+    let plaintext = toml::to_string_pretty(bundle).context("Failed to serialize profiles")?;
+    let mut recipient = age::scrypt::Recipient::new(SecretString::from(passphrase.to_string()));
+    if let Some(log_n) = work_factor {
+        recipient.set_work_factor(log_n);
+    }
+    let sealed = if armor {
+        age::encrypt_and_armor(&recipient, plaintext.as_bytes())
+            .map(String::into_bytes)
+            .context("Failed to encrypt profiles")?
+    } else {
+        age::encrypt(&recipient, plaintext.as_bytes()).context("Failed to encrypt profiles")?
+    };
+    Ok(sealed)
+}
+
+/// Decrypt and parse a bundle, armored or not
+pub fn open(sealed: &[u8], passphrase: &str) -> Result<ProfileBundle> {
+    let identity = age::scrypt::Identity::new(SecretString::from(passphrase.to_string()));
+    let plaintext = age::decrypt(&identity, sealed).map_err(|e| match e {
+        age::DecryptError::DecryptionFailed | age::DecryptError::NoMatchingKeys => {
+            anyhow::anyhow!("Wrong passphrase, or the bundle is damaged")
+        }
+        other => anyhow::anyhow!("Failed to decrypt the bundle: {}", other),
+    })?;
+    let text = String::from_utf8(plaintext).context("The bundle is not valid UTF-8")?;
+    let bundle: ProfileBundle = toml::from_str(&text).context("Failed to parse the bundle")?;
+    if bundle.version > BUNDLE_VERSION {
+        anyhow::bail!(
+            "The bundle has format version {}; this redisctl reads up to version {}",
+            bundle.version,
+            BUNDLE_VERSION
+        );
+    }
+    Ok(bundle)
+}
+
+/// Add the bundle's profiles to the config file
+///
+/// Profiles already in the config file are only replaced with `overwrite`; otherwise nothing is
+/// imported and the conflicting names are reported.
+pub fn import_bundle(
+    config: &mut Config,
+    bundle: ProfileBundle,
+    overwrite: bool,
+) -> Result<ImportSummary> {
+    let conflicts: Vec<&str> = bundle
+        .profiles
+        .keys()
+        .filter(|name| config.profiles.contains_key(*name))
+        .map(String::as_str)
+        .collect();
+    if !conflicts.is_empty() && !overwrite {
+        anyhow::bail!(
+            "Profiles already exist: {}; pass --overwrite to replace them",
+            conflicts.join(", ")
+        );
+    }
+
+    let mut summary = ImportSummary::default();
+    for (name, profile) in bundle.profiles {
+        if config.profiles.insert(name.clone(), profile).is_some() {
+            summary.replaced.push(name);
+        } else {
+            summary.added.push(name);
+        }
+    }
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::DeploymentType;
+
+    /// Cheap scrypt so the tests stay fast
+    const TEST_WORK_FACTOR: Option<u8> = Some(4);
+
+    fn config() -> Config {
+        let mut config = Config::default();
+        config.set_profile(
+            "prod-cloud".to_string(),
+            Profile {
+                deployment_type: DeploymentType::Cloud,
+                credentials: ProfileCredentials::Cloud {
+                    api_key: "key".to_string(),
+                    api_secret: "secret".to_string(),
+                    api_url: "https://api.redislabs.com/v1".to_string(),
+                },
+                read_only: true,
+            },
+        );
+        config.set_profile(
+            "lab".to_string(),
+            Profile {
+                deployment_type: DeploymentType::Enterprise,
+                credentials: ProfileCredentials::Enterprise {
+                    url: "https://lab:9443".to_string(),
+                    username: "admin@lab".to_string(),
+                    password: Some("hunter2".to_string()),
+                    insecure: true,
+                    tls_fingerprint_sha256: None,
+                },
+                read_only: false,
+            },
+        );
+        config
+    }
+
+    #[test]
+    fn test_bundle_without_secrets() {
+        let bundle = build_bundle(&config(), &[], false).unwrap();
+        assert!(!bundle.includes_secrets);
+        assert_eq!(
+            bundle.profiles["prod-cloud"].cloud_credentials(),
+            Some((
+                "${REDISCTL_PROFILE_PROD_CLOUD_API_KEY:-}",
+                "${REDISCTL_PROFILE_PROD_CLOUD_API_SECRET:-}",
+                "https://api.redislabs.com/v1"
+            ))
+        );
+        assert!(matches!(
+            &bundle.profiles["lab"].credentials,
+            ProfileCredentials::Enterprise { password: Some(p), .. }
+                if p == "${REDISCTL_PROFILE_LAB_PASSWORD:-}"
+        ));
+        // Unset, the reference expands to nothing, which is no password
+        let mut lab = bundle.profiles["lab"].clone();
+        if let ProfileCredentials::Enterprise { password, .. } = &mut lab.credentials {
+            *password = Some(String::new());
+        }
+        assert_eq!(lab.enterprise_credentials().unwrap().2, None);
+
+        let bundle = build_bundle(&config(), &["lab".to_string()], true).unwrap();
+        assert_eq!(bundle.profiles.len(), 1);
+        assert!(bundle.profiles["lab"].has_password());
+        assert!(build_bundle(&config(), &["missing".to_string()], true).is_err());
+    }
+
+    #[test]
+    fn test_seal_and_open_round_trip() {
+        let bundle = build_bundle(&config(), &[], true).unwrap();
+        for armor in [false, true] {
+            let sealed = seal(&bundle, "correct horse", armor, TEST_WORK_FACTOR).unwrap();
+            assert_eq!(
+                sealed.starts_with(b"-----BEGIN AGE ENCRYPTED FILE-----"),
+                armor
+            );
+            let opened = open(&sealed, "correct horse").unwrap();
+            assert!(opened.includes_secrets);
+            assert_eq!(
+                opened.profiles["lab"].enterprise_credentials(),
+                Some(("https://lab:9443", "admin@lab", Some("hunter2"), true))
+            );
+            assert!(opened.profiles["prod-cloud"].read_only);
+        }
+
+        let sealed = seal(&bundle, "correct horse", false, TEST_WORK_FACTOR).unwrap();
+        let err = open(&sealed, "wrong").unwrap_err();
+        assert!(err.to_string().contains("Wrong passphrase"));
+    }
+
+    #[test]
+    fn test_import_refuses_conflicts_without_overwrite() {
+        let bundle = build_bundle(&config(), &[], false).unwrap();
+        let mut target = Config::default();
+        target.set_profile("lab".to_string(), config().profiles["lab"].clone());
+
+        let err = import_bundle(&mut target, bundle.clone(), false).unwrap_err();
+        assert!(err.to_string().contains("lab"));
+        assert_eq!(target.profiles.len(), 1);
+
+        let summary = import_bundle(&mut target, bundle, true).unwrap();
+        assert_eq!(summary.added, vec!["prod-cloud"]);
+        assert_eq!(summary.replaced, vec!["lab"]);
+        assert_ne!(
+            target.profiles["lab"].enterprise_credentials().unwrap().2,
+            Some("hunter2")
+        );
+    }
+}
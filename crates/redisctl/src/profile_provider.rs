@@ -0,0 +1,439 @@
+//! Profile providers
+//!
+//! Besides the `[profiles]` table in the config file, profiles can be supplied from outside so
+//! teams can distribute connection definitions centrally:
+//!
+//! - A directory of per-profile TOML files, where `<name>.toml` holds the same keys as a
+//!   `[profiles.<name>]` table. Directories come from `profile_dirs` in the config file and from
+//!   `REDISCTL_PROFILES_DIR` (a path list, separated like `PATH`).
+//! - Environment variable bundles, `REDISCTL_PROFILE_<NAME>_<FIELD>`. The name is lowercased
+//!   with `_` turned into `-`, so `REDISCTL_PROFILE_PROD_EU_URL` belongs to profile `prod-eu`.
+//!
+//! Profiles in the config file take precedence; among providers, environment bundles override
+//! directories.
+
+#![allow(dead_code)]
+
+use anyhow::{Context, Result};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tracing::{debug, warn};
+
+use crate::config::{Config, DeploymentType, Profile, ProfileCredentials, default_cloud_url};
+
+/// Prefix of environment variable bundles
+pub const ENV_BUNDLE_PREFIX: &str = "REDISCTL_PROFILE_";
+
+/// Environment variable listing extra profile directories
+pub const PROFILES_DIR_ENV: &str = "REDISCTL_PROFILES_DIR";
+
+/// Fields of an environment bundle; longer names first so `API_URL` wins over `URL`
+const ENV_FIELDS: &[&str] = &[
+    "DEPLOYMENT_TYPE",
+    "READ_ONLY",
+    "API_SECRET",
+    "API_KEY",
+    "API_URL",
+    "USERNAME",
+    "PASSWORD",
+    "INSECURE",
+    "TLS_FINGERPRINT_SHA256",
+    "URL",
+];
+
+/// A source of profiles other than the config file
+pub trait ProfileProvider {
+    /// Where the profiles come from, shown in `profile list` and `profile show`
+    fn source(&self) -> String;
+
+    /// Load all profiles this provider knows about
+    ///
+    /// A profile that cannot be read is skipped with a warning; an error means the source as a
+    /// whole is unusable.
+    fn load(&self) -> Result<Vec<(String, Profile)>>;
+}
+
+/// A profile loaded from a provider, with its source
+#[derive(Debug, Clone)]
+pub struct ProvidedProfile {
+    pub profile: Profile,
+    pub source: String,
+}
+
+/// Profiles from a directory of `<name>.toml` files
+pub struct DirectoryProvider {
+    pub path: PathBuf,
+}
+
+impl ProfileProvider for DirectoryProvider {
+    fn source(&self) -> String {
+        format!("dir:{}", self.path.display())
+    }
+
+    fn load(&self) -> Result<Vec<(String, Profile)>> {
+        let entries = fs::read_dir(&self.path)
+            .with_context(|| format!("Failed to read profile directory {:?}", self.path))?;
+
+        let mut profiles = Vec::new();
+        for entry in entries {
+            let path = match entry {
+                Ok(entry) => entry.path(),
+                Err(e) => {
+                    warn!("Skipping an entry of {:?}: {}", self.path, e);
+                    continue;
+                }
+            };
+            if path.extension().and_then(|e| e.to_str()) != Some("toml") {
+                continue;
+            }
+            let Some(name) = path.file_stem().and_then(|s| s.to_str()) else {
+                continue;
+            };
+
+            match read_profile_file(&path) {
+                Ok(profile) => profiles.push((name.to_string(), profile)),
+                Err(e) => warn!("Skipping profile '{}': {:#}", name, e),
+            }
+        }
+        profiles.sort_by(|a, b| a.0.cmp(&b.0));
+        Ok(profiles)
+    }
+}
+
+/// Read one `<name>.toml` profile file
+fn read_profile_file(path: &Path) -> Result<Profile> {
+    let content =
+        fs::read_to_string(path).with_context(|| format!("Failed to read profile {:?}", path))?;
+    let content = Config::expand_env_vars(&content)
+        .with_context(|| format!("Failed to expand environment variables in {:?}", path))?;
+    toml::from_str(&content).with_context(|| format!("Failed to parse profile {:?}", path))
+}
+
+/// Profiles from `REDISCTL_PROFILE_<NAME>_<FIELD>` environment variables
+pub struct EnvBundleProvider {
+    vars: Vec<(String, String)>,
+}
+
+impl EnvBundleProvider {
+    /// Read bundles from the process environment
+    ///
+    /// Variables whose name or value is not valid UTF-8 are skipped; `std::env::vars` would panic
+    /// on them, and no bundle variable can contain them.
+    pub fn from_env() -> Self {
+        Self::new(utf8_vars(std::env::vars_os()))
+    }
+
+    /// Read bundles from the given variables
+    pub fn new(vars: Vec<(String, String)>) -> Self {
+        Self { vars }
+    }
+}
+
+/// The variables whose name and value are both valid UTF-8
+fn utf8_vars(
+    vars: impl IntoIterator<Item = (std::ffi::OsString, std::ffi::OsString)>,
+) -> Vec<(String, String)> {
+    vars.into_iter()
+        .filter_map(|(key, value)| Some((key.into_string().ok()?, value.into_string().ok()?)))
+        .collect()
+}
+
+impl ProfileProvider for EnvBundleProvider {
+    fn source(&self) -> String {
+        "env".to_string()
+    }
+
+    fn load(&self) -> Result<Vec<(String, Profile)>> {
+        let mut bundles: BTreeMap<String, HashMap<&str, &str>> = BTreeMap::new();
+        for (key, value) in &self.vars {
+            let Some(rest) = key.strip_prefix(ENV_BUNDLE_PREFIX) else {
+                continue;
+            };
+            let Some((name, field)) = ENV_FIELDS.iter().find_map(|field| {
+                let name = rest.strip_suffix(field)?.strip_suffix('_')?;
+                (!name.is_empty()).then_some((name, *field))
+            }) else {
+                continue;
+            };
+            bundles
+                .entry(name.to_lowercase().replace('_', "-"))
+                .or_default()
+                .insert(field, value.as_str());
+        }
+
+        Ok(bundles
+            .into_iter()
+            .filter_map(|(name, fields)| match bundle_profile(&name, &fields) {
+                Ok(profile) => Some((name, profile)),
+                Err(e) => {
+                    warn!("Skipping profile '{}': {:#}", name, e);
+                    None
+                }
+            })
+            .collect())
+    }
+}
+
+/// Build a profile from the fields of one environment bundle
+fn bundle_profile(name: &str, fields: &HashMap<&str, &str>) -> Result<Profile> {
+    let var = |field: &str| format!("{}{}_{}", ENV_BUNDLE_PREFIX, env_name(name), field);
+    let required = |field: &str| -> Result<String> {
+        fields.get(field).map(|v| v.to_string()).with_context(|| {
+            format!(
+                "Profile '{}' from environment: {} is not set",
+                name,
+                var(field)
+            )
+        })
+    };
+
+    let deployment_type = match fields.get("DEPLOYMENT_TYPE") {
+        Some(value) => match value.to_lowercase().as_str() {
+            "cloud" => DeploymentType::Cloud,
+            "enterprise" => DeploymentType::Enterprise,
+            other => anyhow::bail!(
+                "Profile '{}' from environment: {} must be 'cloud' or 'enterprise', got '{}'",
+                name,
+                var("DEPLOYMENT_TYPE"),
+                other
+            ),
+        },
+        None if fields.contains_key("API_KEY") => DeploymentType::Cloud,
+        None if fields.contains_key("URL") => DeploymentType::Enterprise,
+        None => anyhow::bail!(
+            "Profile '{}' from environment: set {} or the connection variables",
+            name,
+            var("DEPLOYMENT_TYPE")
+        ),
+    };
+
+    let credentials = match deployment_type {
+        DeploymentType::Cloud => ProfileCredentials::Cloud {
+            api_key: required("API_KEY")?,
+            api_secret: required("API_SECRET")?,
+            api_url: fields
+                .get("API_URL")
+                .map(|v| v.to_string())
+                .unwrap_or_else(default_cloud_url),
+        },
+        DeploymentType::Enterprise => ProfileCredentials::Enterprise {
+            url: required("URL")?,
+            username: required("USERNAME")?,
+            password: fields.get("PASSWORD").map(|v| v.to_string()),
+            insecure: fields
+                .get("INSECURE")
+                .is_some_and(|v| matches!(v.to_lowercase().as_str(), "true" | "1" | "yes")),
+            tls_fingerprint_sha256: fields.get("TLS_FINGERPRINT_SHA256").map(|v| v.to_string()),
+        },
+    };
+
+    Ok(Profile {
+        deployment_type,
+        credentials,
+        read_only: fields
+            .get("READ_ONLY")
+            .is_some_and(|v| matches!(v.to_lowercase().as_str(), "true" | "1" | "yes")),
+    })
+}
+
+/// Environment variable form of a profile name
+pub(crate) fn env_name(name: &str) -> String {
+    name.to_uppercase().replace('-', "_")
+}
+
+/// Providers configured for this process, lowest precedence first
+pub fn configured_providers(config: &Config) -> Vec<Box<dyn ProfileProvider>> {
+    let mut dirs = config.profile_dirs.clone();
+    if let Some(paths) = std::env::var_os(PROFILES_DIR_ENV) {
+        dirs.extend(std::env::split_paths(&paths));
+    }
+
+    let mut providers: Vec<Box<dyn ProfileProvider>> = dirs
+        .into_iter()
+        .map(|path| {
+            let path = shellexpand::tilde(&path.to_string_lossy()).into_owned();
+            Box::new(DirectoryProvider { path: path.into() }) as Box<dyn ProfileProvider>
+        })
+        .collect();
+    providers.push(Box::new(EnvBundleProvider::from_env()));
+    providers
+}
+
+/// Load profiles from all providers; later providers override earlier ones
+///
+/// A provider that fails is reported and skipped so one bad source does not block the others.
+pub fn load_profiles(providers: &[Box<dyn ProfileProvider>]) -> HashMap<String, ProvidedProfile> {
+    let mut profiles = HashMap::new();
+    for provider in providers {
+        let source = provider.source();
+        match provider.load() {
+            Ok(loaded) => {
+                debug!("Loaded {} profile(s) from {}", loaded.len(), source);
+                for (name, profile) in loaded {
+                    profiles.insert(
+                        name,
+                        ProvidedProfile {
+                            profile,
+                            source: source.clone(),
+                        },
+                    );
+                }
+            }
+            Err(e) => warn!("Skipping profiles from {}: {:#}", source, e),
+        }
+    }
+    profiles
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vars(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_env_bundles() {
+        let provider = EnvBundleProvider::new(vars(&[
+            ("REDISCTL_PROFILE", "ignored"),
+            (
+                "REDISCTL_PROFILE_PROD_EU_URL",
+                "https://re.example.com:9443",
+            ),
+            ("REDISCTL_PROFILE_PROD_EU_USERNAME", "admin@example.com"),
+            ("REDISCTL_PROFILE_PROD_EU_INSECURE", "true"),
+            (
+                "REDISCTL_PROFILE_PROD_EU_TLS_FINGERPRINT_SHA256",
+                "44:CF:47:25",
+            ),
+            ("REDISCTL_PROFILE_CLOUD_API_KEY", "key"),
+            ("REDISCTL_PROFILE_CLOUD_API_SECRET", "secret"),
+            (
+                "REDISCTL_PROFILE_CLOUD_API_URL",
+                "https://cloud.example.com/v1",
+            ),
+            ("OTHER_VAR", "x"),
+        ]));
+
+        let profiles = provider.load().unwrap();
+        assert_eq!(profiles.len(), 2);
+
+        let (name, cloud) = &profiles[0];
+        assert_eq!(name, "cloud");
+        assert_eq!(
+            cloud.cloud_credentials(),
+            Some(("key", "secret", "https://cloud.example.com/v1"))
+        );
+
+        let (name, enterprise) = &profiles[1];
+        assert_eq!(name, "prod-eu");
+        assert_eq!(
+            enterprise.enterprise_credentials(),
+            Some((
+                "https://re.example.com:9443",
+                "admin@example.com",
+                None,
+                true
+            ))
+        );
+        assert_eq!(enterprise.tls_fingerprint_sha256(), Some("44:CF:47:25"));
+    }
+
+    #[test]
+    fn test_env_bundle_errors_name_the_variable() {
+        let fields = HashMap::from([("DEPLOYMENT_TYPE", "cloud")]);
+        let err = format!("{:#}", bundle_profile("staging", &fields).unwrap_err());
+        assert!(err.contains("REDISCTL_PROFILE_STAGING_API_KEY"));
+    }
+
+    #[test]
+    fn test_env_bundles_skip_an_incomplete_bundle() {
+        let provider = EnvBundleProvider::new(vars(&[
+            ("REDISCTL_PROFILE_STAGING_DEPLOYMENT_TYPE", "cloud"),
+            ("REDISCTL_PROFILE_PROD_API_KEY", "key"),
+            ("REDISCTL_PROFILE_PROD_API_SECRET", "secret"),
+        ]));
+        let profiles = provider.load().unwrap();
+        assert_eq!(profiles.len(), 1);
+        assert_eq!(profiles[0].0, "prod");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_env_bundles_skip_non_utf8_vars() {
+        use std::ffi::OsString;
+        use std::os::unix::ffi::OsStringExt;
+
+        let vars = utf8_vars(vec![
+            (
+                OsString::from("BROKEN"),
+                OsString::from_vec(vec![0xff, 0xfe]),
+            ),
+            (OsString::from_vec(vec![0xff]), OsString::from("value")),
+            (
+                OsString::from("REDISCTL_PROFILE_CI_DEPLOYMENT"),
+                OsString::from("cloud"),
+            ),
+        ]);
+        assert_eq!(
+            vars,
+            vec![(
+                "REDISCTL_PROFILE_CI_DEPLOYMENT".to_string(),
+                "cloud".to_string()
+            )]
+        );
+    }
+
+    #[test]
+    fn test_directory_provider() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join("team-cloud.toml"),
+            "deployment_type = \"cloud\"\napi_key = \"k\"\napi_secret = \"s\"\n",
+        )
+        .unwrap();
+        fs::write(dir.path().join("README.md"), "not a profile").unwrap();
+        fs::write(dir.path().join("broken.toml"), "deployment_type = [").unwrap();
+
+        let provider = DirectoryProvider {
+            path: dir.path().to_path_buf(),
+        };
+        let profiles = provider.load().unwrap();
+        assert_eq!(profiles.len(), 1);
+        assert_eq!(profiles[0].0, "team-cloud");
+        assert_eq!(profiles[0].1.deployment_type, DeploymentType::Cloud);
+    }
+
+    #[test]
+    fn test_later_providers_override_earlier() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join("shared.toml"),
+            "deployment_type = \"cloud\"\napi_key = \"from-dir\"\napi_secret = \"s\"\n",
+        )
+        .unwrap();
+
+        let providers: Vec<Box<dyn ProfileProvider>> = vec![
+            Box::new(DirectoryProvider {
+                path: dir.path().to_path_buf(),
+            }),
+            Box::new(EnvBundleProvider::new(vars(&[
+                ("REDISCTL_PROFILE_SHARED_API_KEY", "from-env"),
+                ("REDISCTL_PROFILE_SHARED_API_SECRET", "s"),
+            ]))),
+            Box::new(DirectoryProvider {
+                path: dir.path().join("missing"),
+            }),
+        ];
+
+        let profiles = load_profiles(&providers);
+        let shared = &profiles["shared"];
+        assert_eq!(shared.source, "env");
+        assert_eq!(shared.profile.cloud_credentials().unwrap().0, "from-env");
+    }
+}
@@ -0,0 +1,157 @@
+//! Progress reporting for long-running operations
+//!
+//! By default progress is shown as an interactive spinner. With `--progress json`
+//! each update is written to stderr as a single-line JSON event (NDJSON) instead,
+//! so wrapping tools can follow task polling and workflow steps without parsing
+//! human-oriented text. Command output on stdout is unaffected. `--quiet` hides the spinner and
+//! steps but keeps JSON events, which were asked for explicitly.
+
+#![allow(dead_code)]
+
+use std::sync::OnceLock;
+use std::time::Instant;
+
+use indicatif::{ProgressBar, ProgressStyle};
+use serde_json::{Map, Value, json};
+
+use crate::cli::ProgressFormat;
+
+static PROGRESS_FORMAT: OnceLock<ProgressFormat> = OnceLock::new();
+
+/// Set the progress format for this process (first call wins)
+pub fn init(format: ProgressFormat) {
+    let _ = PROGRESS_FORMAT.set(format);
+}
+
+/// Current progress format
+pub fn format() -> ProgressFormat {
+    PROGRESS_FORMAT
+        .get()
+        .copied()
+        .unwrap_or(ProgressFormat::Human)
+}
+
+/// Whether progress events are emitted as JSON
+pub fn is_json() -> bool {
+    matches!(format(), ProgressFormat::Json)
+}
+
+/// Build a progress event object with the event name and a timestamp
+fn event_json(event: &str, fields: Value) -> Value {
+    let mut map = Map::new();
+    map.insert("event".to_string(), Value::from(event));
+    map.insert(
+        "timestamp".to_string(),
+        Value::from(chrono::Utc::now().to_rfc3339()),
+    );
+    if let Value::Object(fields) = fields {
+        map.extend(fields);
+    }
+    Value::Object(map)
+}
+
+/// Write a progress event to stderr as one line of JSON
+pub fn emit(event: &str, fields: Value) {
+    eprintln!("{}", event_json(event, fields));
+}
+
+/// Report a workflow step, e.g. one stage of a multi-step operation
+pub fn step(message: &str) {
+    if is_json() {
+        emit("step", json!({ "message": message }));
+    } else {
+        crate::output::notice!("{}", message);
+    }
+}
+
+/// Progress display for a single task being polled
+pub struct TaskProgress {
+    task_id: String,
+    started: Instant,
+    spinner: Option<ProgressBar>,
+}
+
+impl TaskProgress {
+    /// Start reporting progress for a task
+    pub fn start(task_id: &str) -> Self {
+        let spinner = if is_json() {
+            emit("task_started", json!({ "task_id": task_id }));
+            None
+        } else {
+            let pb = if crate::output::is_quiet() {
+                ProgressBar::hidden()
+            } else {
+                ProgressBar::new_spinner()
+            };
+            pb.set_style(
+                ProgressStyle::default_spinner()
+                    .template("{spinner:.green} {msg} [{elapsed_precise}]")
+                    .unwrap(),
+            );
+            pb.set_message(format!("Waiting for task {}", task_id));
+            Some(pb)
+        };
+
+        Self {
+            task_id: task_id.to_string(),
+            started: Instant::now(),
+            spinner,
+        }
+    }
+
+    /// Report the latest polled state; `display` is the human-readable form
+    pub fn update(&self, state: &str, display: &str) {
+        match &self.spinner {
+            Some(pb) => pb.set_message(format!("Task {}: {}", self.task_id, display)),
+            None => emit("task_status", self.fields(state)),
+        }
+    }
+
+    /// Report that the task reached a terminal state
+    pub fn finish(&self, state: &str, display: &str) {
+        match &self.spinner {
+            Some(pb) => pb.finish_with_message(format!("Task {}: {}", self.task_id, display)),
+            None => emit("task_completed", self.fields(state)),
+        }
+    }
+
+    /// Report that waiting for the task timed out
+    pub fn timeout(&self) {
+        match &self.spinner {
+            Some(pb) => pb.finish_with_message(format!("Task {} timed out", self.task_id)),
+            None => emit(
+                "task_timeout",
+                json!({
+                    "task_id": self.task_id,
+                    "elapsed_secs": self.started.elapsed().as_secs(),
+                }),
+            ),
+        }
+    }
+
+    fn fields(&self, state: &str) -> Value {
+        json!({
+            "task_id": self.task_id,
+            "state": state,
+            "elapsed_secs": self.started.elapsed().as_secs(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_event_json_includes_name_timestamp_and_fields() {
+        let event = event_json(
+            "task_status",
+            json!({"task_id": "abc", "state": "received"}),
+        );
+        assert_eq!(event["event"], "task_status");
+        assert_eq!(event["task_id"], "abc");
+        assert_eq!(event["state"], "received");
+        assert!(event["timestamp"].is_string());
+        assert!(!event.to_string().contains('\n'));
+    }
+}
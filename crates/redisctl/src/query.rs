@@ -0,0 +1,196 @@
+//! JMESPath runtime with Redis-specific helper functions
+//!
+//! All `--query` expressions are compiled against this runtime, which extends the
+//! standard JMESPath builtins with a few transforms for human-readable reports:
+//!
+//! - `human_bytes(number)` - format a byte count, e.g. `1073741824` -> `"1.0 GB"`
+//! - `to_gb(number)` - convert a byte count to gigabytes (two decimal places)
+//! - `duration(number)` - format seconds, e.g. `93784` -> `"1d 2h 3m 4s"`
+//! - `status_icon(string)` - map a resource or task status to a status symbol
+//!
+//! Each function passes `null` through unchanged so it can be used on optional fields.
+
+#![allow(dead_code)]
+
+use std::sync::LazyLock;
+
+use jmespath::functions::{ArgumentType, CustomFunction, Signature};
+use jmespath::{Context, Expression, JmespathError, Rcvar, Runtime, Variable};
+
+use crate::units::format_bytes;
+
+const BYTES_PER_GB: f64 = 1024.0 * 1024.0 * 1024.0;
+
+static RUNTIME: LazyLock<Runtime> = LazyLock::new(|| {
+    let mut runtime = Runtime::new();
+    runtime.register_builtin_functions();
+    register_redis_functions(&mut runtime);
+    runtime
+});
+
+/// Compile a JMESPath expression with the Redis function library available
+pub fn compile(expression: &str) -> Result<Expression<'static>, JmespathError> {
+    RUNTIME.compile(expression)
+}
+
+fn register_redis_functions(runtime: &mut Runtime) {
+    runtime.register_function(
+        "human_bytes",
+        Box::new(number_function(|n| {
+            Variable::String(format_bytes(n.max(0.0) as u64))
+        })),
+    );
+    runtime.register_function(
+        "to_gb",
+        Box::new(number_function(|n| {
+            let gb = (n / BYTES_PER_GB * 100.0).round() / 100.0;
+            serde_json::Number::from_f64(gb)
+                .map(Variable::Number)
+                .unwrap_or(Variable::Null)
+        })),
+    );
+    runtime.register_function(
+        "duration",
+        Box::new(number_function(|n| Variable::String(duration(n)))),
+    );
+    runtime.register_function(
+        "status_icon",
+        Box::new(CustomFunction::new(
+            Signature::new(
+                vec![ArgumentType::Union(vec![
+                    ArgumentType::String,
+                    ArgumentType::Null,
+                ])],
+                None,
+            ),
+            Box::new(|args: &[Rcvar], _ctx: &mut Context<'_>| {
+                Ok(Rcvar::new(match args[0].as_string() {
+                    Some(status) => Variable::String(status_icon(status).to_string()),
+                    None => Variable::Null,
+                }))
+            }),
+        )),
+    );
+}
+
+/// Build a single-argument function that accepts a number (or null)
+fn number_function(f: fn(f64) -> Variable) -> CustomFunction {
+    CustomFunction::new(
+        Signature::new(
+            vec![ArgumentType::Union(vec![
+                ArgumentType::Number,
+                ArgumentType::Null,
+            ])],
+            None,
+        ),
+        Box::new(move |args: &[Rcvar], _ctx: &mut Context<'_>| {
+            Ok(Rcvar::new(match args[0].as_number() {
+                Some(n) => f(n),
+                None => Variable::Null,
+            }))
+        }),
+    )
+}
+
+fn duration(seconds: f64) -> String {
+    let total = seconds.max(0.0) as u64;
+    let (days, hours, minutes, secs) = (
+        total / 86_400,
+        (total % 86_400) / 3_600,
+        (total % 3_600) / 60,
+        total % 60,
+    );
+
+    let mut parts: Vec<String> = [(days, "d"), (hours, "h"), (minutes, "m")]
+        .iter()
+        .filter(|(value, _)| *value > 0)
+        .map(|(value, suffix)| format!("{}{}", value, suffix))
+        .collect();
+    if parts.is_empty() || secs > 0 {
+        parts.push(format!("{}s", secs));
+    }
+
+    parts.join(" ")
+}
+
+fn status_icon(status: &str) -> &'static str {
+    match status.to_lowercase().as_str() {
+        "active" | "ok" | "up" | "healthy" | "completed" | "processing-completed" => "✓",
+        "error" | "failed" | "down" | "processing-error" => "✗",
+        "pending"
+        | "creating"
+        | "initialized"
+        | "received"
+        | "processing-in-progress"
+        | "active-change-pending" => "…",
+        "suspended" | "inactive" | "deleting" => "⏸",
+        _ => "?",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn search(expression: &str, data: serde_json::Value) -> serde_json::Value {
+        let expr = compile(expression).unwrap();
+        let result = expr.search(data).unwrap();
+        serde_json::to_value(&*result).unwrap()
+    }
+
+    #[test]
+    fn test_human_bytes() {
+        assert_eq!(search("human_bytes(@)", json!(512)), json!("512 B"));
+        assert_eq!(search("human_bytes(@)", json!(1536)), json!("1.5 KB"));
+        assert_eq!(
+            search("human_bytes(@)", json!(1073741824u64)),
+            json!("1.0 GB")
+        );
+        assert_eq!(search("human_bytes(missing)", json!({})), json!(null));
+    }
+
+    #[test]
+    fn test_to_gb() {
+        assert_eq!(search("to_gb(@)", json!(2147483648u64)), json!(2.0));
+        assert_eq!(search("to_gb(@)", json!(536870912)), json!(0.5));
+    }
+
+    #[test]
+    fn test_duration() {
+        assert_eq!(search("duration(@)", json!(0)), json!("0s"));
+        assert_eq!(search("duration(@)", json!(93784)), json!("1d 2h 3m 4s"));
+        assert_eq!(search("duration(@)", json!(3600)), json!("1h"));
+    }
+
+    #[test]
+    fn test_status_icon() {
+        assert_eq!(search("status_icon(@)", json!("active")), json!("✓"));
+        assert_eq!(
+            search("status_icon(@)", json!("processing-error")),
+            json!("✗")
+        );
+        assert_eq!(search("status_icon(@)", json!("pending")), json!("…"));
+        assert_eq!(search("status_icon(@)", json!("unknown")), json!("?"));
+    }
+
+    #[test]
+    fn test_builtins_still_available() {
+        assert_eq!(
+            search(
+                "[].{name: name, size: human_bytes(memory)} | length(@)",
+                json!([
+                    {"name": "db1", "memory": 1024},
+                    {"name": "db2", "memory": 2048}
+                ])
+            ),
+            json!(2)
+        );
+    }
+
+    #[test]
+    fn test_invalid_argument_type() {
+        let expr = compile("human_bytes(@)").unwrap();
+        assert!(expr.search(json!("not a number")).is_err());
+    }
+}
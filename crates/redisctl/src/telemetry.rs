@@ -0,0 +1,203 @@
+//! Opt-in, local-only usage statistics
+//!
+//! With `[telemetry] enabled = true` in the config file, or `REDISCTL_TELEMETRY=1` for a single
+//! invocation, every command adds its subcommand path (`enterprise database list`, never its
+//! arguments), its duration and whether it failed to a JSON file next to the config file.
+//! Nothing leaves the machine; `redisctl stats` reads the file back.
+//!
+//! Recording is best-effort: a stats file that cannot be read or written is logged at debug
+//! level and never fails the command. Invocations running at the same time may drop one
+//! another's update.
+
+#![allow(dead_code)]
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tracing::debug;
+
+use crate::config::Config;
+
+/// Environment variable that turns recording on or off for one invocation
+pub const TELEMETRY_ENV: &str = "REDISCTL_TELEMETRY";
+
+/// File name of the stats file, next to the config file
+const STATS_FILE: &str = "stats.json";
+
+/// Everything recorded so far
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct UsageStats {
+    /// When recording started, or the stats were last reset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub since: Option<DateTime<Utc>>,
+    /// Usage per subcommand path
+    #[serde(default)]
+    pub commands: BTreeMap<String, CommandUsage>,
+}
+
+/// Usage of one subcommand path
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CommandUsage {
+    pub runs: u64,
+    pub failures: u64,
+    pub total_ms: u64,
+    pub max_ms: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_run: Option<DateTime<Utc>>,
+}
+
+impl CommandUsage {
+    /// Mean duration of a run
+    pub fn mean_ms(&self) -> u64 {
+        self.total_ms.checked_div(self.runs).unwrap_or(0)
+    }
+}
+
+impl UsageStats {
+    /// Count one run of `command`
+    pub fn record(&mut self, command: &str, elapsed: Duration, success: bool, at: DateTime<Utc>) {
+        self.since.get_or_insert(at);
+        let ms = u64::try_from(elapsed.as_millis()).unwrap_or(u64::MAX);
+        let usage = self.commands.entry(command.to_string()).or_default();
+        usage.runs += 1;
+        if !success {
+            usage.failures += 1;
+        }
+        usage.total_ms = usage.total_ms.saturating_add(ms);
+        usage.max_ms = usage.max_ms.max(ms);
+        usage.last_run = Some(at);
+    }
+}
+
+/// Whether this invocation records its usage
+///
+/// `REDISCTL_TELEMETRY` (`1`/`true`/`on` or `0`/`false`/`off`) takes precedence over the config
+/// file.
+pub fn enabled(config: &Config) -> bool {
+    std::env::var(TELEMETRY_ENV)
+        .ok()
+        .and_then(|value| parse_switch(&value))
+        .unwrap_or_else(|| config.telemetry.as_ref().is_some_and(|t| t.enabled))
+}
+
+fn parse_switch(value: &str) -> Option<bool> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "1" | "true" | "on" | "yes" => Some(true),
+        "0" | "false" | "off" | "no" => Some(false),
+        _ => None,
+    }
+}
+
+/// Where the stats are kept
+pub fn stats_path(config: &Config) -> Result<PathBuf> {
+    if let Some(path) = config.telemetry.as_ref().and_then(|t| t.path.clone()) {
+        return Ok(path);
+    }
+    Ok(Config::config_path()?.with_file_name(STATS_FILE))
+}
+
+/// The subcommand path of a parsed command line, such as `cloud database list`
+pub fn command_path(matches: &clap::ArgMatches) -> String {
+    let mut names = Vec::new();
+    let mut current = matches;
+    while let Some((name, sub)) = current.subcommand() {
+        names.push(name);
+        current = sub;
+    }
+    names.join(" ")
+}
+
+/// Read the stats file; a missing file means nothing has been recorded yet
+pub fn load(path: &Path) -> Result<UsageStats> {
+    match std::fs::read_to_string(path) {
+        Ok(content) => serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse stats file {}", path.display())),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(UsageStats::default()),
+        Err(e) => Err(e).with_context(|| format!("Failed to read stats file {}", path.display())),
+    }
+}
+
+/// Write the stats file, replacing it in one step so readers never see half a file
+pub fn save(path: &Path, stats: &UsageStats) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create directory {}", parent.display()))?;
+    }
+    let content = serde_json::to_string_pretty(stats).context("Failed to serialize stats")?;
+    let tmp = path.with_extension("json.tmp");
+    std::fs::write(&tmp, content)
+        .with_context(|| format!("Failed to write stats file {}", tmp.display()))?;
+    std::fs::rename(&tmp, path)
+        .with_context(|| format!("Failed to replace stats file {}", path.display()))
+}
+
+/// Add one run to the stats file if recording is enabled
+pub fn record(config: &Config, command: &str, elapsed: Duration, success: bool) {
+    if command.is_empty() || !enabled(config) {
+        return;
+    }
+    let result = stats_path(config).and_then(|path| {
+        // A damaged file is started over rather than blocking every later command
+        let mut stats = load(&path).unwrap_or_else(|e| {
+            debug!("Starting stats over: {:#}", e);
+            UsageStats::default()
+        });
+        stats.record(command, elapsed, success, Utc::now());
+        save(&path, &stats)
+    });
+    if let Err(e) = result {
+        debug!("Failed to record usage stats: {:#}", e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::{CommandFactory, FromArgMatches};
+
+    #[test]
+    fn test_record_accumulates_runs() {
+        let at = Utc::now();
+        let mut stats = UsageStats::default();
+        stats.record("cloud database list", Duration::from_millis(120), true, at);
+        stats.record("cloud database list", Duration::from_millis(80), false, at);
+
+        let usage = &stats.commands["cloud database list"];
+        assert_eq!(usage.runs, 2);
+        assert_eq!(usage.failures, 1);
+        assert_eq!(usage.max_ms, 120);
+        assert_eq!(usage.mean_ms(), 100);
+        assert_eq!(stats.since, Some(at));
+    }
+
+    #[test]
+    fn test_save_and_load_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("nested").join(STATS_FILE);
+        assert_eq!(load(&path).unwrap(), UsageStats::default());
+
+        let mut stats = UsageStats::default();
+        stats.record("version", Duration::from_millis(3), true, Utc::now());
+        save(&path, &stats).unwrap();
+        assert_eq!(load(&path).unwrap(), stats);
+    }
+
+    #[test]
+    fn test_command_path_leaves_out_arguments() {
+        let matches = crate::cli::Cli::command()
+            .try_get_matches_from(["redisctl", "enterprise", "database", "get", "42"])
+            .unwrap();
+        assert!(crate::cli::Cli::from_arg_matches(&matches).is_ok());
+        assert_eq!(command_path(&matches), "enterprise database get");
+    }
+
+    #[test]
+    fn test_parse_switch() {
+        assert_eq!(parse_switch("ON"), Some(true));
+        assert_eq!(parse_switch("0"), Some(false));
+        assert_eq!(parse_switch("maybe"), None);
+    }
+}
@@ -0,0 +1,258 @@
+//! Rendering of API timestamps in tables and human-readable output
+//!
+//! The Cloud and Enterprise APIs report times as RFC3339 strings, naive `date time` strings,
+//! epoch seconds or epoch milliseconds, depending on the endpoint. Table output renders the
+//! fields known to hold times in one format, in UTC or in the local time zone (`--utc`,
+//! `--local` or `REDISCTL_TIMEZONE`). JSON and YAML output keep the values as the API sent them.
+
+#![allow(dead_code)]
+
+use chrono::{DateTime, Local, NaiveDateTime, TimeZone, Utc};
+use serde_json::Value;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static LOCAL: AtomicBool = AtomicBool::new(false);
+
+/// Time zone used to render timestamps
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeZoneMode {
+    Utc,
+    Local,
+}
+
+/// Pick the time zone from the flags, falling back to `REDISCTL_TIMEZONE` and then UTC
+pub fn init(utc: bool, local: bool) {
+    let mode = if utc {
+        TimeZoneMode::Utc
+    } else if local {
+        TimeZoneMode::Local
+    } else {
+        std::env::var("REDISCTL_TIMEZONE")
+            .ok()
+            .and_then(|value| parse_mode(&value))
+            .unwrap_or(TimeZoneMode::Utc)
+    };
+    set_mode(mode);
+}
+
+/// Set the time zone used for this process
+pub fn set_mode(mode: TimeZoneMode) {
+    LOCAL.store(mode == TimeZoneMode::Local, Ordering::Relaxed);
+}
+
+/// The time zone used for this process
+pub fn mode() -> TimeZoneMode {
+    if LOCAL.load(Ordering::Relaxed) {
+        TimeZoneMode::Local
+    } else {
+        TimeZoneMode::Utc
+    }
+}
+
+fn parse_mode(value: &str) -> Option<TimeZoneMode> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "utc" | "z" => Some(TimeZoneMode::Utc),
+        "local" => Some(TimeZoneMode::Local),
+        _ => None,
+    }
+}
+
+/// Epoch seconds between 2000 and 2100; larger values are taken as milliseconds
+const SECONDS_RANGE: std::ops::RangeInclusive<i64> = 946_684_800..=4_102_444_800;
+
+/// Read a timestamp in any of the forms the APIs use
+pub fn parse_timestamp(value: &Value) -> Option<DateTime<Utc>> {
+    match value {
+        Value::Number(n) => from_epoch(n.as_f64()?),
+        Value::String(s) => parse_timestamp_str(s),
+        _ => None,
+    }
+}
+
+/// Read a timestamp string: RFC3339, `YYYY-MM-DD HH:MM:SS` (taken as UTC) or an epoch number
+pub fn parse_timestamp_str(s: &str) -> Option<DateTime<Utc>> {
+    let s = s.trim();
+    if let Ok(dt) = DateTime::parse_from_rfc3339(s) {
+        return Some(dt.with_timezone(&Utc));
+    }
+    for format in ["%Y-%m-%dT%H:%M:%S%.f", "%Y-%m-%d %H:%M:%S%.f"] {
+        if let Ok(naive) = NaiveDateTime::parse_from_str(s, format) {
+            return Some(naive.and_utc());
+        }
+    }
+    if !s.is_empty() && s.chars().all(|c| c.is_ascii_digit() || c == '.') {
+        return from_epoch(s.parse().ok()?);
+    }
+    None
+}
+
+fn from_epoch(number: f64) -> Option<DateTime<Utc>> {
+    let seconds = if SECONDS_RANGE.contains(&(number as i64)) {
+        number
+    } else if SECONDS_RANGE.contains(&((number / 1000.0) as i64)) {
+        number / 1000.0
+    } else {
+        return None;
+    };
+    let millis = (seconds * 1000.0).round() as i64;
+    Utc.timestamp_millis_opt(millis).single()
+}
+
+/// Format a time in the process time zone
+pub fn format_timestamp(dt: &DateTime<Utc>) -> String {
+    format_in(dt, mode())
+}
+
+/// Format a time in the given time zone
+pub fn format_in(dt: &DateTime<Utc>, mode: TimeZoneMode) -> String {
+    match mode {
+        TimeZoneMode::Utc => dt.format("%Y-%m-%d %H:%M:%S UTC").to_string(),
+        TimeZoneMode::Local => dt
+            .with_timezone(&Local)
+            .format("%Y-%m-%d %H:%M:%S %:z")
+            .to_string(),
+    }
+}
+
+/// Format a timestamp string, or return it unchanged when it is not one
+pub fn display(s: &str) -> String {
+    parse_timestamp_str(s).map_or_else(|| s.to_string(), |dt| format_timestamp(&dt))
+}
+
+/// Whether a field name is one the APIs use for a point in time
+pub fn is_timestamp_field(name: &str) -> bool {
+    const NAMES: &[&str] = &[
+        "created",
+        "updated",
+        "timestamp",
+        "time",
+        "date",
+        "lastlogin",
+        "signup",
+        "last_changed",
+        "last_login",
+        "start_time",
+        "end_time",
+        "stime",
+        "etime",
+    ];
+    const SUFFIXES: &[&str] = &[
+        "_time",
+        "_timestamp",
+        "_at",
+        "_date",
+        "time",
+        "timestamp",
+        "at",
+    ];
+    let lower = name.to_ascii_lowercase();
+    if NAMES.contains(&lower.as_str()) {
+        return true;
+    }
+    // A suffix counts after `_` or as a camelCase word, so `format` or `uptime` do not match
+    SUFFIXES.iter().any(|suffix| {
+        name.len() > suffix.len()
+            && lower.ends_with(suffix)
+            && (suffix.starts_with('_')
+                || name[name.len() - suffix.len()..].starts_with(char::is_uppercase))
+    })
+}
+
+/// Render the timestamp fields of a value for display, leaving everything else as it is
+pub fn localize(value: &Value) -> Value {
+    match value {
+        Value::Object(map) => Value::Object(
+            map.iter()
+                .map(|(key, val)| {
+                    let rendered = match val {
+                        Value::Number(_) | Value::String(_) if is_timestamp_field(key) => {
+                            parse_timestamp(val)
+                                .map(|dt| Value::String(format_timestamp(&dt)))
+                                .unwrap_or_else(|| val.clone())
+                        }
+                        _ => localize(val),
+                    };
+                    (key.clone(), rendered)
+                })
+                .collect(),
+        ),
+        Value::Array(items) => Value::Array(items.iter().map(localize).collect()),
+        other => other.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn utc(s: &str) -> String {
+        format_in(&parse_timestamp(&json!(s)).unwrap(), TimeZoneMode::Utc)
+    }
+
+    #[test]
+    fn test_parse_timestamp_forms() {
+        let expected = "2024-04-09 02:22:05 UTC";
+        assert_eq!(utc("2024-04-09T02:22:05Z"), expected);
+        assert_eq!(utc("2024-04-09T04:22:05+02:00"), expected);
+        assert_eq!(utc("2024-04-09T02:22:05.123"), expected);
+        assert_eq!(utc("2024-04-09 02:22:05"), expected);
+        assert_eq!(utc("1712629325"), expected);
+
+        let seconds = parse_timestamp(&json!(1712629325)).unwrap();
+        let millis = parse_timestamp(&json!(1712629325000u64)).unwrap();
+        assert_eq!(seconds, millis);
+        assert_eq!(format_in(&seconds, TimeZoneMode::Utc), expected);
+    }
+
+    #[test]
+    fn test_parse_timestamp_rejects_other_values() {
+        assert!(parse_timestamp(&json!(42)).is_none());
+        assert!(parse_timestamp(&json!("active")).is_none());
+        assert!(parse_timestamp(&json!("")).is_none());
+        assert!(parse_timestamp(&json!(true)).is_none());
+    }
+
+    #[test]
+    fn test_is_timestamp_field() {
+        for name in [
+            "created",
+            "lastLogin",
+            "created_time",
+            "createdTimestamp",
+            "expiration_date",
+            "updatedAt",
+            "created_at",
+            "time",
+        ] {
+            assert!(is_timestamp_field(name), "{}", name);
+        }
+        for name in ["format", "uptime", "status", "data", "activatedOn"] {
+            assert!(!is_timestamp_field(name), "{}", name);
+        }
+    }
+
+    #[test]
+    fn test_localize() {
+        set_mode(TimeZoneMode::Utc);
+        let value = json!([{
+            "name": "cache",
+            "created_time": "2024-04-09T02:22:05Z",
+            "last_changed_time": 1712629325,
+            "port": 12000,
+            "nested": {"updatedAt": "not a time"}
+        }]);
+        let rendered = localize(&value);
+        assert_eq!(rendered[0]["created_time"], "2024-04-09 02:22:05 UTC");
+        assert_eq!(rendered[0]["last_changed_time"], "2024-04-09 02:22:05 UTC");
+        assert_eq!(rendered[0]["port"], 12000);
+        assert_eq!(rendered[0]["nested"]["updatedAt"], "not a time");
+    }
+
+    #[test]
+    fn test_parse_mode() {
+        assert_eq!(parse_mode("UTC"), Some(TimeZoneMode::Utc));
+        assert_eq!(parse_mode(" local "), Some(TimeZoneMode::Local));
+        assert_eq!(parse_mode("Europe/Paris"), None);
+    }
+}
@@ -0,0 +1,310 @@
+//! Parsers for human-friendly byte sizes, durations and time bounds in CLI flags
+//!
+//! Sizes use binary units (`1gb` is 1024^3 bytes) and durations accept compound values such as
+//! `1h30m`. A bare number keeps each flag's historical unit: bytes for sizes, seconds for
+//! durations. [`format_bytes`] renders a byte count back in the same binary units.
+
+use chrono::{DateTime, NaiveDate, Utc};
+use std::time::Duration;
+
+/// Intervals accepted by the Redis Enterprise stats and metrics endpoints
+const STATS_INTERVALS: &[(&str, u64)] = &[
+    ("1sec", 1),
+    ("10sec", 10),
+    ("5min", 300),
+    ("15min", 900),
+    ("1hour", 3600),
+    ("12hour", 43200),
+    ("1week", 604800),
+];
+
+/// Parse a size such as `512mb`, `1gb` or `2.5GB` into bytes
+pub fn parse_byte_size(s: &str) -> Result<u64, String> {
+    let lower = s.trim().to_ascii_lowercase();
+    let split = lower
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or(lower.len());
+    let (number, unit) = lower.split_at(split);
+    let multiplier: u64 = match unit.trim() {
+        "" | "b" => 1,
+        "k" | "kb" | "kib" => 1 << 10,
+        "m" | "mb" | "mib" => 1 << 20,
+        "g" | "gb" | "gib" => 1 << 30,
+        "t" | "tb" | "tib" => 1 << 40,
+        other => {
+            return Err(format!(
+                "invalid size: {} (unknown unit '{}', expected b, kb, mb, gb or tb)",
+                s, other
+            ));
+        }
+    };
+    let number: f64 = number
+        .parse()
+        .map_err(|_| format!("invalid size: {} (expected e.g. 250mb or 2.5gb)", s))?;
+    Ok((number * multiplier as f64) as u64)
+}
+
+/// Parse a duration such as `90`, `45s`, `15m`, `1h30m` or `500ms`
+pub fn parse_duration(s: &str) -> Result<Duration, String> {
+    let lower = s.trim().to_ascii_lowercase();
+    if lower.is_empty() {
+        return Err("invalid duration: empty value (expected e.g. 30s, 15m or 1h)".to_string());
+    }
+    if lower.starts_with(|c: char| c.is_ascii_digit() || c == '.')
+        && let Ok(seconds) = lower.parse::<f64>()
+    {
+        return Duration::try_from_secs_f64(seconds).map_err(|_| too_long(s));
+    }
+
+    let mut total_ms = 0.0;
+    let mut rest = lower.as_str();
+    while !rest.is_empty() {
+        let number_end = rest
+            .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+            .unwrap_or(rest.len());
+        let unit_end = rest[number_end..]
+            .find(|c: char| c.is_ascii_digit() || c == '.')
+            .map_or(rest.len(), |i| number_end + i);
+        let number: f64 = rest[..number_end].parse().map_err(|_| {
+            format!(
+                "invalid duration: {} (expected e.g. 30s, 15m or 1h30m)",
+                s.trim()
+            )
+        })?;
+        let unit_ms = match rest[number_end..unit_end].trim() {
+            "ms" => 1.0,
+            "s" | "sec" | "secs" | "second" | "seconds" => 1_000.0,
+            "m" | "min" | "mins" | "minute" | "minutes" => 60_000.0,
+            "h" | "hr" | "hour" | "hours" => 3_600_000.0,
+            "d" | "day" | "days" => 86_400_000.0,
+            "w" | "week" | "weeks" => 604_800_000.0,
+            other => {
+                return Err(format!(
+                    "invalid duration: {} (unknown unit '{}', expected ms, s, m, h, d or w)",
+                    s.trim(),
+                    other
+                ));
+            }
+        };
+        total_ms += number * unit_ms;
+        rest = &rest[unit_end..];
+    }
+    let total_ms = total_ms.round();
+    // `as u64` would saturate silently; u64::MAX itself is not representable as f64
+    if !total_ms.is_finite() || total_ms >= u64::MAX as f64 {
+        return Err(too_long(s));
+    }
+    Ok(Duration::from_millis(total_ms as u64))
+}
+
+fn too_long(s: &str) -> String {
+    format!("invalid duration: {} (too long)", s.trim())
+}
+
+/// Parse a `--since`/`--until` style time bound
+///
+/// Accepts `now`, a duration back from `now` (`90m`, `7d` or `-7d`), RFC 3339, or a bare
+/// YYYY-MM-DD date. A date as an upper bound (`end_of_day`) covers the whole day.
+pub fn parse_time_bound(
+    value: &str,
+    now: DateTime<Utc>,
+    end_of_day: bool,
+) -> Result<DateTime<Utc>, String> {
+    let value = value.trim();
+    if value.eq_ignore_ascii_case("now") {
+        return Ok(now);
+    }
+    if let Ok(time) = DateTime::parse_from_rfc3339(value) {
+        return Ok(time.with_timezone(&Utc));
+    }
+    if let Ok(date) = NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+        let time = if end_of_day {
+            date.and_hms_milli_opt(23, 59, 59, 999)
+        } else {
+            date.and_hms_opt(0, 0, 0)
+        };
+        return Ok(time.expect("valid time of day").and_utc());
+    }
+
+    let too_far = || format!("invalid time: {} (too far back)", value);
+    let offset = value.strip_prefix('-').unwrap_or(value);
+    match parse_duration(offset) {
+        Ok(duration) => chrono::Duration::from_std(duration)
+            .ok()
+            .and_then(|duration| now.checked_sub_signed(duration))
+            .ok_or_else(too_far),
+        Err(e) if e == too_long(offset) => Err(too_far()),
+        Err(_) => Err(format!(
+            "invalid time: {} (expected now, a duration back from now such as 30m, 12h or 7d, \
+             RFC 3339 or YYYY-MM-DD)",
+            value
+        )),
+    }
+}
+
+/// Format a byte count with a binary unit, e.g. `1536` -> `"1.5 KB"`
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 6] = ["B", "KB", "MB", "GB", "TB", "PB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} B", bytes)
+    } else {
+        format!("{:.1} {}", value, UNITS[unit])
+    }
+}
+
+/// Parse a duration for a flag measured in whole seconds
+pub fn parse_duration_secs(s: &str) -> Result<u64, String> {
+    let duration = parse_duration(s)?;
+    if duration.subsec_millis() != 0 {
+        return Err(format!(
+            "invalid duration: {} (must be a whole number of seconds)",
+            s.trim()
+        ));
+    }
+    Ok(duration.as_secs())
+}
+
+/// Parse a stats interval, accepting the API's names (`5min`) or a matching duration (`5m`)
+pub fn parse_stats_interval(s: &str) -> Result<String, String> {
+    let lower = s.trim().to_ascii_lowercase();
+    if let Some((name, _)) = STATS_INTERVALS.iter().find(|(name, _)| *name == lower) {
+        return Ok(name.to_string());
+    }
+    let valid = STATS_INTERVALS
+        .iter()
+        .map(|(name, _)| *name)
+        .collect::<Vec<_>>()
+        .join(", ");
+    let seconds = parse_duration(&lower)
+        .map_err(|_| format!("invalid interval: {} (valid: {})", s.trim(), valid))?
+        .as_secs();
+    STATS_INTERVALS
+        .iter()
+        .find(|(_, secs)| *secs == seconds)
+        .map(|(name, _)| name.to_string())
+        .ok_or_else(|| format!("unsupported interval: {} (valid: {})", s.trim(), valid))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!(format_bytes(0), "0 B");
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(1023), "1023 B");
+        assert_eq!(format_bytes(1536), "1.5 KB");
+        assert_eq!(format_bytes(1073741824), "1.0 GB");
+        assert_eq!(format_bytes(3 * 1024u64.pow(5)), "3.0 PB");
+    }
+
+    #[test]
+    fn test_parse_byte_size() {
+        assert_eq!(parse_byte_size("1gb"), Ok(1 << 30));
+        assert_eq!(parse_byte_size("250MB"), Ok(250 << 20));
+        assert_eq!(parse_byte_size("1.5g"), Ok(3 << 29));
+        assert_eq!(parse_byte_size("2.5 GiB"), Ok(5 << 29));
+        assert_eq!(parse_byte_size("1024"), Ok(1024));
+        assert!(parse_byte_size("1pb").is_err());
+        assert!(parse_byte_size("gb").is_err());
+    }
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("90"), Ok(Duration::from_secs(90)));
+        assert_eq!(parse_duration("15m"), Ok(Duration::from_secs(900)));
+        assert_eq!(parse_duration("1h30m"), Ok(Duration::from_secs(5400)));
+        assert_eq!(parse_duration("1.5h"), Ok(Duration::from_secs(5400)));
+        assert_eq!(parse_duration("500ms"), Ok(Duration::from_millis(500)));
+        assert_eq!(parse_duration("2 days"), Ok(Duration::from_secs(172800)));
+        assert!(parse_duration("").is_err());
+        assert!(parse_duration("5y").is_err());
+        assert!(parse_duration("m5").is_err());
+        assert!(parse_duration("-5").is_err());
+        assert!(parse_duration("inf").is_err());
+        // Too long for a Duration, as seconds or once the units are added up
+        for value in [
+            "1e30",
+            "99999999999999999999",
+            "1e400",
+            "99999999999999999999w",
+        ] {
+            assert_eq!(
+                parse_duration(value),
+                Err(format!("invalid duration: {} (too long)", value))
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_time_bound() {
+        let now = DateTime::parse_from_rfc3339("2024-03-10T12:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let bound = |value: &str, end_of_day: bool| {
+            parse_time_bound(value, now, end_of_day).map(|time| time.to_rfc3339())
+        };
+        assert_eq!(bound("now", true), Ok(now.to_rfc3339()));
+        assert_eq!(bound("90m", false), Ok("2024-03-10T10:30:00+00:00".into()));
+        assert_eq!(bound("-7d", false), Ok("2024-03-03T12:00:00+00:00".into()));
+        assert_eq!(
+            bound("1h30m", false),
+            Ok("2024-03-10T10:30:00+00:00".into())
+        );
+        assert_eq!(
+            bound("2024-01-31", false),
+            Ok("2024-01-31T00:00:00+00:00".into())
+        );
+        assert_eq!(
+            bound("2024-01-31", true),
+            Ok("2024-01-31T23:59:59.999+00:00".into())
+        );
+        assert_eq!(
+            bound("2024-01-31T12:00:00+02:00", false),
+            Ok("2024-01-31T10:00:00+00:00".into())
+        );
+
+        for value in ["20000000w", "-99999999999w", "1e30", "99999999999999999999"] {
+            assert_eq!(
+                bound(value, false),
+                Err(format!("invalid time: {} (too far back)", value))
+            );
+        }
+        for value in ["yesterday", "-5é", "é", "", "2024-13-01"] {
+            assert!(
+                bound(value, false)
+                    .unwrap_err()
+                    .starts_with("invalid time: "),
+                "{}",
+                value
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_duration_secs() {
+        assert_eq!(parse_duration_secs("300"), Ok(300));
+        assert_eq!(parse_duration_secs("1h"), Ok(3600));
+        assert!(parse_duration_secs("1500ms").is_err());
+    }
+
+    #[test]
+    fn test_parse_stats_interval() {
+        assert_eq!(parse_stats_interval("1hour"), Ok("1hour".to_string()));
+        assert_eq!(parse_stats_interval("15m"), Ok("15min".to_string()));
+        assert_eq!(parse_stats_interval("10s"), Ok("10sec".to_string()));
+        assert_eq!(parse_stats_interval("7d"), Ok("1week".to_string()));
+        assert!(
+            parse_stats_interval("24h")
+                .unwrap_err()
+                .contains("valid: 1sec, 10sec")
+        );
+    }
+}